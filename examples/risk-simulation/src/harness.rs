@@ -0,0 +1,873 @@
+//! Thin litesvm harness around the three deployed programs.
+//!
+//! Accounts and instruction data are built with the Anchor-generated client
+//! types (`accounts::*` / `instruction::*`), so the simulation exercises exactly
+//! the account layouts and constraints the on-chain handlers enforce.
+
+use std::path::Path;
+
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token;
+use anchor_spl::token::spl_token;
+use litesvm::LiteSVM;
+use pyth_sdk_solana::state::{AccountType, PriceStatus, SolanaPriceAccount, MAGIC, VERSION_2};
+use solana_sdk::account::Account;
+use solana_sdk::clock::Clock;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_program;
+use solana_sdk::sysvar;
+use solana_sdk::transaction::Transaction;
+
+use aerospacer_protocol::state::{
+    trove_id_seed, CollateralConfig, LiquidityThreshold, StateAccount, UserCollateralAmount, UserDebtAmount,
+};
+
+/// Pyth feeds used by the simulation always publish with this exponent
+pub const PRICE_EXPONENT: i32 = -8;
+/// aUSD decimals
+pub const STABLECOIN_DECIMALS: u8 = 18;
+/// The oracle returns prices in micro-USD per whole token
+const TARGET_USD_DECIMALS: u32 = 6;
+/// Lamports given to every simulated wallet for rent and fees
+const WALLET_LAMPORTS: u64 = 100_000_000_000;
+/// Generous per-transaction budget - the handlers log heavily
+const COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// A simulated trove owner and their token accounts
+pub struct Wallet {
+    pub keypair: Keypair,
+    pub collateral_token_account: Pubkey,
+    pub stablecoin_token_account: Pubkey,
+}
+
+impl Wallet {
+    pub fn pubkey(&self) -> Pubkey {
+        self.keypair.pubkey()
+    }
+}
+
+/// On-chain view of a trove, read straight from its PDAs
+#[derive(Clone, Copy, Debug)]
+pub struct TroveView {
+    pub debt: u64,
+    pub collateral: u64,
+}
+
+pub struct Harness {
+    pub svm: LiteSVM,
+    pub admin: Keypair,
+    pub denom: String,
+    pub collateral_decimals: u8,
+    pub stable_coin_mint: Pubkey,
+    pub collateral_mint: Pubkey,
+    pub pyth_price_account: Pubkey,
+    pub state: Pubkey,
+    pub oracle_state: Pubkey,
+    pub fee_state: Pubkey,
+    pub stability_pool_token_account: Pubkey,
+    pub fee_vault: Pubkey,
+    pub fee_epoch_stats: Pubkey,
+    raw_price: i64,
+}
+
+impl Harness {
+    /// Load the compiled programs from `deploy_dir` and initialize oracle, fees and protocol
+    pub fn new(
+        deploy_dir: &Path,
+        denom: &str,
+        collateral_decimals: u8,
+        initial_price: f64,
+        start_timestamp: i64,
+    ) -> Result<Self, String> {
+        let mut svm = LiteSVM::new();
+        for (program_id, name) in [
+            (aerospacer_protocol::ID, "aerospacer_protocol.so"),
+            (aerospacer_oracle::ID, "aerospacer_oracle.so"),
+            (aerospacer_fees::ID, "aerospacer_fees.so"),
+        ] {
+            let path = deploy_dir.join(name);
+            svm.add_program_from_file(program_id, &path)
+                .map_err(|e| format!("failed to load {}: {} (run `anchor build` first)", path.display(), e))?;
+        }
+
+        let admin = Keypair::new();
+        svm.airdrop(&admin.pubkey(), WALLET_LAMPORTS)
+            .map_err(|e| format!("airdrop failed: {:?}", e.err))?;
+
+        let mut harness = Self {
+            svm,
+            admin,
+            denom: denom.to_string(),
+            collateral_decimals,
+            stable_coin_mint: Pubkey::new_unique(),
+            collateral_mint: Pubkey::new_unique(),
+            pyth_price_account: Pubkey::new_unique(),
+            state: Pubkey::find_program_address(&[b"state"], &aerospacer_protocol::ID).0,
+            oracle_state: Pubkey::find_program_address(&[b"state"], &aerospacer_oracle::ID).0,
+            fee_state: Pubkey::find_program_address(&[b"fee_state"], &aerospacer_fees::ID).0,
+            stability_pool_token_account: Pubkey::new_unique(),
+            fee_vault: Pubkey::default(),
+            fee_epoch_stats: Pubkey::default(),
+            raw_price: 0,
+        };
+
+        // Mints: the admin holds aUSD mint authority until protocol initialize hands it to the vault PDA
+        let admin_key = harness.admin.pubkey();
+        harness.write_mint(harness.stable_coin_mint, admin_key, STABLECOIN_DECIMALS)?;
+        harness.write_mint(harness.collateral_mint, admin_key, collateral_decimals)?;
+
+        // Stake routing disabled - fees wait in the aUSD fee vault for the default fee addresses
+        let stable = harness.stable_coin_mint;
+        harness.fee_vault = aerospacer_fees::utils::get_fee_vault_pda(&stable).0;
+        harness.fee_epoch_stats = aerospacer_fees::utils::get_fee_epoch_stats_pda(&stable).0;
+        harness.write_token_account(harness.stability_pool_token_account, stable, admin_key, 0)?;
+
+        harness.set_price(initial_price, start_timestamp)?;
+        harness.initialize_programs()?;
+
+        Ok(harness)
+    }
+
+    fn initialize_programs(&mut self) -> Result<(), String> {
+        let admin = self.admin.pubkey();
+
+        let oracle_init = Instruction {
+            program_id: aerospacer_oracle::ID,
+            accounts: aerospacer_oracle::accounts::Initialize {
+                state: self.oracle_state,
+                admin,
+                system_program: system_program::ID,
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: aerospacer_oracle::instruction::Initialize {
+                params: aerospacer_oracle::instructions::InitializeParams {
+                    oracle_address: Pubkey::new_unique(),
+                },
+            }
+            .data(),
+        };
+
+        let oracle_set_data = Instruction {
+            program_id: aerospacer_oracle::ID,
+            accounts: aerospacer_oracle::accounts::SetData {
+                admin,
+                state: self.oracle_state,
+                clock: sysvar::clock::ID,
+            }
+            .to_account_metas(None),
+            data: aerospacer_oracle::instruction::SetData {
+                params: aerospacer_oracle::instructions::SetDataParams {
+                    denom: self.denom.clone(),
+                    decimal: self.collateral_decimals,
+                    price_id: format!("simulated-{}", self.denom),
+                    pyth_price_account: self.pyth_price_account,
+                },
+            }
+            .data(),
+        };
+
+        let fees_init = Instruction {
+            program_id: aerospacer_fees::ID,
+            accounts: aerospacer_fees::accounts::Initialize {
+                state: self.fee_state,
+                admin,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: aerospacer_fees::instruction::Initialize {}.data(),
+        };
+
+        let fee_vault_init = Instruction {
+            program_id: aerospacer_fees::ID,
+            accounts: aerospacer_fees::accounts::InitializeFeeVault {
+                admin,
+                state: self.fee_state,
+                fee_mint: self.stable_coin_mint,
+                fee_claims: aerospacer_fees::utils::get_fee_claims_pda(&self.stable_coin_mint).0,
+                fee_vault: self.fee_vault,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+            }
+            .to_account_metas(None),
+            data: aerospacer_fees::instruction::InitializeFeeVault {}.data(),
+        };
+
+        let fee_epoch_stats_init = Instruction {
+            program_id: aerospacer_fees::ID,
+            accounts: aerospacer_fees::accounts::InitializeFeeEpochStats {
+                admin,
+                state: self.fee_state,
+                fee_mint: self.stable_coin_mint,
+                fee_epoch_stats: self.fee_epoch_stats,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: aerospacer_fees::instruction::InitializeFeeEpochStats {}.data(),
+        };
+
+        let fee_caller_add = Instruction {
+            program_id: aerospacer_fees::ID,
+            accounts: aerospacer_fees::accounts::AddFeeCaller {
+                admin,
+                state: self.fee_state,
+            }
+            .to_account_metas(None),
+            data: aerospacer_fees::instruction::AddFeeCaller {
+                params: aerospacer_fees::instructions::AddFeeCallerParams {
+                    caller: protocol_fee_caller().to_string(),
+                },
+            }
+            .data(),
+        };
+
+        let protocol_init = Instruction {
+            program_id: aerospacer_protocol::ID,
+            accounts: aerospacer_protocol::accounts::Initialize {
+                state: self.state,
+                admin,
+                stable_coin_mint: self.stable_coin_mint,
+                mint_authority: mint_authority(),
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: aerospacer_protocol::instruction::Initialize {
+                params: aerospacer_protocol::instructions::InitializeParams {
+                    stable_coin_code_id: 0,
+                    oracle_helper_addr: aerospacer_oracle::ID,
+                    oracle_state_addr: self.oracle_state,
+                    fee_distributor_addr: aerospacer_fees::ID,
+                    fee_state_addr: self.fee_state,
+                    config: aerospacer_protocol::instructions::ProtocolConfig::default(),
+                },
+            }
+            .data(),
+        };
+
+        // Simulated markets start past the bootstrap period, so redemptions are open from the start
+        let end_bootstrap = Instruction {
+            program_id: aerospacer_protocol::ID,
+            accounts: aerospacer_protocol::accounts::UpdateBootstrapPeriod {
+                admin,
+                state: self.state,
+            }
+            .to_account_metas(None),
+            data: aerospacer_protocol::instruction::UpdateBootstrapPeriod {
+                params: aerospacer_protocol::instructions::UpdateBootstrapPeriodParams {
+                    bootstrap_period_secs: Some(0),
+                    bootstrap_max_liquidation_batch: None,
+                },
+            }
+            .data(),
+        };
+
+        let register_collateral = Instruction {
+            program_id: aerospacer_protocol::ID,
+            accounts: aerospacer_protocol::accounts::RegisterCollateral {
+                admin,
+                state: self.state,
+                collateral_mint: self.collateral_mint,
+                protocol_collateral_vault: self.protocol_collateral_vault(),
+                total_collateral_amount: self.total_collateral_pda(),
+                collateral_config: self.collateral_config_pda(),
+                stability_pool_snapshot: Pubkey::find_program_address(
+                    &[b"stability_pool_snapshot", self.denom.as_bytes()],
+                    &aerospacer_protocol::ID,
+                )
+                .0,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: aerospacer_protocol::instruction::RegisterCollateral {
+                params: aerospacer_protocol::instructions::RegisterCollateralParams {
+                    denom: self.denom.clone(),
+                },
+            }
+            .data(),
+        };
+
+        let admin_keypair = self.admin.insecure_clone();
+        self.send(
+            &[oracle_init, oracle_set_data, fees_init, fee_vault_init, fee_epoch_stats_init, fee_caller_add, protocol_init, end_bootstrap, register_collateral],
+            &[&admin_keypair],
+        )
+        .map(|_| ())
+    }
+
+    /// Publish a new price to the simulated Pyth account and move the clock forward
+    pub fn set_price(&mut self, price: f64, timestamp: i64) -> Result<(), String> {
+        let raw_price = (price * 10f64.powi(-PRICE_EXPONENT)).round() as i64;
+        if raw_price <= 0 {
+            return Err(format!("price {} rounds to zero at exponent {}", price, PRICE_EXPONENT));
+        }
+        // The oracle rejects confidence intervals below 100 raw units
+        let confidence = ((raw_price / 1_000) as u64).max(100);
+
+        let mut clock: Clock = self.svm.get_sysvar();
+        clock.slot += 1;
+        clock.unix_timestamp = timestamp;
+        self.svm.set_sysvar(&clock);
+
+        let mut feed: SolanaPriceAccount = bytemuck::Zeroable::zeroed();
+        feed.magic = MAGIC;
+        feed.ver = VERSION_2;
+        feed.atype = AccountType::Price as u32;
+        feed.expo = PRICE_EXPONENT;
+        feed.timestamp = timestamp;
+        feed.agg.price = raw_price;
+        feed.agg.conf = confidence;
+        feed.agg.status = PriceStatus::Trading;
+        feed.agg.pub_slot = clock.slot;
+        feed.prev_price = raw_price;
+        feed.prev_conf = confidence;
+        feed.prev_timestamp = timestamp;
+        feed.prev_slot = clock.slot;
+
+        let data = bytemuck::bytes_of(&feed).to_vec();
+        self.set_raw_account(self.pyth_price_account, Pubkey::new_unique(), data)?;
+        self.raw_price = raw_price;
+        Ok(())
+    }
+
+    /// Fund a fresh wallet with collateral tokens and an empty aUSD account
+    pub fn new_wallet(&mut self, collateral_amount: u64) -> Result<Wallet, String> {
+        let keypair = Keypair::new();
+        self.svm
+            .airdrop(&keypair.pubkey(), WALLET_LAMPORTS)
+            .map_err(|e| format!("airdrop failed: {:?}", e.err))?;
+
+        // Outbound transfers pay into the owner's ATAs
+        let owner = keypair.pubkey();
+        let wallet = Wallet {
+            collateral_token_account: associated_token::get_associated_token_address(&owner, &self.collateral_mint),
+            stablecoin_token_account: associated_token::get_associated_token_address(&owner, &self.stable_coin_mint),
+            keypair,
+        };
+        self.write_token_account(wallet.collateral_token_account, self.collateral_mint, owner, collateral_amount)?;
+        self.write_token_account(wallet.stablecoin_token_account, self.stable_coin_mint, owner, 0)?;
+        Ok(wallet)
+    }
+
+    pub fn open_trove(&mut self, wallet: &Wallet, collateral_amount: u64, loan_amount: u64) -> Result<u64, String> {
+        let user = wallet.pubkey();
+        let trove_index_len = self.protocol_state()?.trove_index_len;
+        let ix = Instruction {
+            program_id: aerospacer_protocol::ID,
+            accounts: aerospacer_protocol::accounts::OpenTrove {
+                user,
+                user_debt_amount: user_debt_pda(&user),
+                liquidity_threshold: liquidity_threshold_pda(&user),
+                user_collateral_amount: user_collateral_pda(&user, &self.denom),
+                user_collateral_account: wallet.collateral_token_account,
+                collateral_mint: self.collateral_mint,
+                protocol_collateral_account: self.protocol_collateral_vault(),
+                total_collateral_amount: self.total_collateral_pda(),
+                collateral_config: self.collateral_config_pda(),
+                state: self.state,
+                user_stablecoin_account: wallet.stablecoin_token_account,
+                protocol_stablecoin_account: protocol_stablecoin_vault(),
+                mint_authority: mint_authority(),
+                liquidation_reserve_vault: liquidation_reserve_vault(),
+                stable_coin_mint: self.stable_coin_mint,
+                oracle_program: aerospacer_oracle::ID,
+                oracle_state: self.oracle_state,
+                pyth_price_account: self.pyth_price_account,
+                clock: sysvar::clock::ID,
+                fees_program: aerospacer_fees::ID,
+                fees_state: self.fee_state,
+                stability_pool_token_account: self.stability_pool_token_account,
+                fee_caller: protocol_fee_caller(),
+                fee_vault: self.fee_vault,
+                fee_epoch_stats: self.fee_epoch_stats,
+                fee_accumulator: None,
+                fee_accumulator_vault: None,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                tag_registry: None,
+                user_index: None,
+                trove_registry: trove_registry_pda(&user),
+                trove_index_page: trove_index_page_pda(trove_index_len),
+                frontend: None,
+                denom_promotion: None,
+                price_cache: None,
+                trove_history: None,
+            }
+            .to_account_metas(None),
+            data: aerospacer_protocol::instruction::OpenTrove {
+                params: aerospacer_protocol::instructions::OpenTroveParams {
+                    loan_amount,
+                    collateral_denom: self.denom.clone(),
+                    trove_id: 0,
+                    collateral_amount,
+                    tag: None,
+                    frontend: None,
+                    memo: None,
+                },
+            }
+            .data(),
+        };
+        self.send(&[ix], &[&wallet.keypair])
+    }
+
+    /// Deposit aUSD into the stability pool
+    pub fn stake(&mut self, wallet: &Wallet, amount: u64) -> Result<u64, String> {
+        let user = wallet.pubkey();
+        let mut stake_accounts = aerospacer_protocol::accounts::Stake {
+            user,
+            user_stake_amount: Pubkey::find_program_address(
+                &[b"user_stake_amount", user.as_ref()],
+                &aerospacer_protocol::ID,
+            )
+            .0,
+            state: self.state,
+            user_stablecoin_account: wallet.stablecoin_token_account,
+            stability_pool_vault: stability_pool_vault(),
+            protocol_stablecoin_vault: protocol_stablecoin_vault(),
+            stable_coin_mint: self.stable_coin_mint,
+            reward_emission: None,
+            user_index: None,
+            frontend: None,
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        // The denom's S snapshot pair, settled before the P snapshot moves
+        stake_accounts.push(AccountMeta::new_readonly(
+            Pubkey::find_program_address(
+                &[b"stability_pool_snapshot", self.denom.as_bytes()],
+                &aerospacer_protocol::ID,
+            )
+            .0,
+            false,
+        ));
+        stake_accounts.push(AccountMeta::new(
+            Pubkey::find_program_address(
+                &[b"user_collateral_snapshot", user.as_ref(), self.denom.as_bytes()],
+                &aerospacer_protocol::ID,
+            )
+            .0,
+            false,
+        ));
+        let ix = Instruction {
+            program_id: aerospacer_protocol::ID,
+            accounts: stake_accounts,
+            data: aerospacer_protocol::instruction::Stake {
+                params: aerospacer_protocol::instructions::StakeParams { amount, compound_gains: false, frontend: None },
+            }
+            .data(),
+        };
+        self.send(&[ix], &[&wallet.keypair])
+    }
+
+    /// Single-trove liquidation - covers the stability pool, hybrid and redistribution paths
+    pub fn liquidate(&mut self, target: &Wallet) -> Result<u64, String> {
+        let target_user = target.pubkey();
+        let ix = Instruction {
+            program_id: aerospacer_protocol::ID,
+            accounts: aerospacer_protocol::accounts::LiquidateTrove {
+                liquidator: self.admin.pubkey(),
+                state: self.state,
+                stable_coin_mint: self.stable_coin_mint,
+                protocol_stablecoin_vault: protocol_stablecoin_vault(),
+                stability_pool_vault: stability_pool_vault(),
+                liquidation_reserve_vault: liquidation_reserve_vault(),
+                protocol_collateral_vault: self.protocol_collateral_vault(),
+                total_collateral_amount: self.total_collateral_pda(),
+                collateral_config: self.collateral_config_pda(),
+                user_debt_amount: user_debt_pda(&target_user),
+                user_collateral_amount: user_collateral_pda(&target_user, &self.denom),
+                liquidity_threshold: liquidity_threshold_pda(&target_user),
+                user_collateral_token_account: target.collateral_token_account,
+                oracle_program: aerospacer_oracle::ID,
+                oracle_state: self.oracle_state,
+                pyth_price_account: self.pyth_price_account,
+                clock: sysvar::clock::ID,
+                stability_pool_snapshot: Pubkey::find_program_address(
+                    &[b"stability_pool_snapshot", self.denom.as_bytes()],
+                    &aerospacer_protocol::ID,
+                )
+                .0,
+                public_goods_accrual: Pubkey::find_program_address(
+                    &[b"public_goods_accrual", self.denom.as_bytes()],
+                    &aerospacer_protocol::ID,
+                )
+                .0,
+                bad_debt_backstop: Pubkey::find_program_address(&[b"bad_debt_backstop"], &aerospacer_protocol::ID).0,
+                reward_emission: None,
+                user_index: None,
+                liquidator_whitelist: None,
+                keeper: None,
+                keeper_collateral_account: None,
+                keeper_bounty_fund: None,
+                keeper_stablecoin_account: None,
+                liquidator_stablecoin_account: None,
+                noop_program: None,
+                token_program: spl_token::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: aerospacer_protocol::instruction::LiquidateTrove {
+                params: aerospacer_protocol::instructions::LiquidateTroveParams {
+                    target_user,
+                    collateral_denom: self.denom.clone(),
+                    trove_id: 0,
+                },
+            }
+            .data(),
+        };
+        let admin = self.admin.insecure_clone();
+        self.send(&[ix], &[&admin])
+    }
+
+    /// Batch liquidation of `targets` in one liquidate_troves transaction. The program wants
+    /// the list ascending by stored ICR, so the targets are sorted before sending.
+    pub fn liquidate_batch(&mut self, targets: &[&Wallet]) -> Result<u64, String> {
+        let mut targets = targets.to_vec();
+        targets.sort_by_key(|wallet| self.stored_icr(&wallet.pubkey()).unwrap_or(u64::MAX));
+
+        let mut liquidate_accounts = aerospacer_protocol::accounts::LiquidateTroves {
+            liquidator: self.admin.pubkey(),
+            state: self.state,
+            stable_coin_mint: self.stable_coin_mint,
+            protocol_stablecoin_vault: protocol_stablecoin_vault(),
+            stability_pool_vault: stability_pool_vault(),
+            liquidation_reserve_vault: liquidation_reserve_vault(),
+            protocol_collateral_vault: self.protocol_collateral_vault(),
+            total_collateral_amount: self.total_collateral_pda(),
+            collateral_config: self.collateral_config_pda(),
+            oracle_program: aerospacer_oracle::ID,
+            oracle_state: self.oracle_state,
+            pyth_price_account: self.pyth_price_account,
+            clock: sysvar::clock::ID,
+            stability_pool_snapshot: Pubkey::find_program_address(
+                &[b"stability_pool_snapshot", self.denom.as_bytes()],
+                &aerospacer_protocol::ID,
+            )
+            .0,
+            bad_debt_backstop: Pubkey::find_program_address(&[b"bad_debt_backstop"], &aerospacer_protocol::ID).0,
+            reward_emission: None,
+            liquidator_whitelist: None,
+            keeper: None,
+            keeper_collateral_account: None,
+            keeper_bounty_fund: None,
+            keeper_stablecoin_account: None,
+            liquidator_stablecoin_account: None,
+            noop_program: None,
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        for target in &targets {
+            let owner = target.pubkey();
+            liquidate_accounts.push(AccountMeta::new(user_debt_pda(&owner), false));
+            liquidate_accounts.push(AccountMeta::new(user_collateral_pda(&owner, &self.denom), false));
+            liquidate_accounts.push(AccountMeta::new(liquidity_threshold_pda(&owner), false));
+            liquidate_accounts.push(AccountMeta::new(target.collateral_token_account, false));
+        }
+        // Collateral seized while the stability pool is empty waits in the denom's seizure account
+        liquidate_accounts.push(AccountMeta::new(
+            Pubkey::find_program_address(&[b"undistributed_seizure", self.denom.as_bytes()], &aerospacer_protocol::ID).0,
+            false,
+        ));
+
+        let ix = Instruction {
+            program_id: aerospacer_protocol::ID,
+            accounts: liquidate_accounts,
+            data: aerospacer_protocol::instruction::LiquidateTroves {
+                params: aerospacer_protocol::instructions::LiquidateTrovesParams {
+                    liquidation_list: targets.iter().map(|wallet| wallet.pubkey()).collect(),
+                    collateral_denom: self.denom.clone(),
+                },
+            }
+            .data(),
+        };
+        let admin = self.admin.insecure_clone();
+        self.send(&[ix], &[&admin])
+    }
+
+    /// Redeem against `targets` (ascending by live ICR) - the protocol orders them at the
+    /// current oracle price, so their stored ICRs need no refresh.
+    pub fn redeem(&mut self, redeemer: &Wallet, amount: u64, targets: &[&Wallet]) -> Result<u64, String> {
+        let user = redeemer.pubkey();
+        let mut redeem_accounts = aerospacer_protocol::accounts::Redeem {
+            user,
+            state: self.state,
+            user_debt_amount: user_debt_pda(&user),
+            liquidity_threshold: liquidity_threshold_pda(&user),
+            user_stablecoin_account: redeemer.stablecoin_token_account,
+            user_collateral_amount: user_collateral_pda(&user, &self.denom),
+            collateral_mint: self.collateral_mint,
+            user_collateral_account: redeemer.collateral_token_account,
+            protocol_stablecoin_vault: protocol_stablecoin_vault(),
+            protocol_collateral_vault: self.protocol_collateral_vault(),
+            stable_coin_mint: self.stable_coin_mint,
+            liquidation_reserve_vault: liquidation_reserve_vault(),
+            total_collateral_amount: self.total_collateral_pda(),
+            oracle_program: aerospacer_oracle::ID,
+            oracle_state: self.oracle_state,
+            fees_program: aerospacer_fees::ID,
+            fees_state: self.fee_state,
+            stability_pool_token_account: self.stability_pool_token_account,
+            fee_caller: protocol_fee_caller(),
+            fee_vault: self.fee_vault,
+            fee_epoch_stats: self.fee_epoch_stats,
+            fee_accumulator: None,
+            fee_accumulator_vault: None,
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+            pyth_price_account: Some(self.pyth_price_account),
+            redemption_ticket: None,
+            redemption_escrow_vault: None,
+        }
+        .to_account_metas(None);
+        for target in targets {
+            let owner = target.pubkey();
+            redeem_accounts.push(AccountMeta::new(user_debt_pda(&owner), false));
+            redeem_accounts.push(AccountMeta::new(user_collateral_pda(&owner, &self.denom), false));
+            redeem_accounts.push(AccountMeta::new(liquidity_threshold_pda(&owner), false));
+            redeem_accounts.push(AccountMeta::new(target.collateral_token_account, false));
+        }
+        let redeem = Instruction {
+            program_id: aerospacer_protocol::ID,
+            accounts: redeem_accounts,
+            data: aerospacer_protocol::instruction::Redeem {
+                params: aerospacer_protocol::instructions::RedeemParams {
+                    amount,
+                    collateral_denom: self.denom.clone(),
+                    allow_partial: false,
+                    open_ticket: false,
+                    max_troves: 0,
+                    max_icr: 0,
+                },
+            }
+            .data(),
+        };
+
+        self.send(&[redeem], &[&redeemer.keypair])
+    }
+
+    /// Read a trove's debt and collateral; `None` once the accounts are gone
+    pub fn trove(&self, owner: &Pubkey) -> Option<TroveView> {
+        let debt: UserDebtAmount = self.read_anchor(&user_debt_pda(owner))?;
+        let collateral: UserCollateralAmount = self.read_anchor(&user_collateral_pda(owner, &self.denom))?;
+        Some(TroveView {
+            debt: debt.amount,
+            collateral: collateral.amount,
+        })
+    }
+
+    /// ICR last written to a trove's LiquidityThreshold, which orders liquidation batches
+    pub fn stored_icr(&self, owner: &Pubkey) -> Option<u64> {
+        let threshold: LiquidityThreshold = self.read_anchor(&liquidity_threshold_pda(owner))?;
+        Some(threshold.ratio)
+    }
+
+    pub fn protocol_state(&self) -> Result<StateAccount, String> {
+        let account = self.svm.get_account(&self.state).ok_or("protocol state missing")?;
+        let end = 8 + StateAccount::LEN;
+        if account.data.len() < end {
+            return Err("protocol state account too small".to_string());
+        }
+        Ok(bytemuck::pod_read_unaligned(&account.data[8..end]))
+    }
+
+    /// The simulated denom's MCR and liquidation threshold
+    pub fn collateral_config(&self) -> Result<CollateralConfig, String> {
+        self.read_anchor(&self.collateral_config_pda())
+            .ok_or_else(|| "collateral config missing".to_string())
+    }
+
+    pub fn token_balance(&self, token_account: &Pubkey) -> u64 {
+        self.svm
+            .get_account(token_account)
+            .and_then(|account| spl_token::state::Account::unpack(&account.data).ok())
+            .map(|account| account.amount)
+            .unwrap_or(0)
+    }
+
+    pub fn stability_pool_balance(&self) -> u64 {
+        self.token_balance(&stability_pool_vault())
+    }
+
+    /// Collateral value in micro-USD, mirroring PriceCalculator::calculate_collateral_value
+    pub fn collateral_value(&self, collateral_amount: u64) -> u128 {
+        let micro_usd_price = self.raw_price as u128 / 10u128.pow((-PRICE_EXPONENT) as u32 - TARGET_USD_DECIMALS);
+        (collateral_amount as u128) * micro_usd_price / 10u128.pow(self.collateral_decimals as u32)
+    }
+
+    /// ICR in micro-percent at the current price, mirroring PriceCalculator::calculate_collateral_ratio
+    pub fn icr(&self, trove: &TroveView) -> u64 {
+        if trove.debt == 0 {
+            return u64::MAX;
+        }
+        let ratio = self.collateral_value(trove.collateral) * 100_000_000_000_000_000_000 / trove.debt as u128;
+        ratio.min(u64::MAX as u128) as u64
+    }
+
+    fn protocol_collateral_vault(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"protocol_collateral_vault", self.denom.as_bytes()],
+            &aerospacer_protocol::ID,
+        )
+        .0
+    }
+
+    fn collateral_config_pda(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"collateral_config", self.denom.as_bytes()],
+            &aerospacer_protocol::ID,
+        )
+        .0
+    }
+
+    fn total_collateral_pda(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"total_collateral_amount", self.denom.as_bytes()],
+            &aerospacer_protocol::ID,
+        )
+        .0
+    }
+
+    /// Send a transaction and return the compute units it consumed.
+    /// On failure the error carries the program logs' error line for classification.
+    fn send(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> Result<u64, String> {
+        let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(COMPUTE_UNIT_LIMIT)];
+        instructions.extend_from_slice(ixs);
+
+        let payer = signers[0].pubkey();
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer),
+            signers,
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(tx);
+        // Identical instructions are re-sent across steps - never reuse a blockhash
+        self.svm.expire_blockhash();
+
+        match result {
+            Ok(meta) => Ok(meta.compute_units_consumed),
+            Err(failed) => {
+                let reason = failed
+                    .meta
+                    .logs
+                    .iter()
+                    .rev()
+                    .find(|line| line.contains("Error Message:") || line.contains("failed:"))
+                    .cloned()
+                    .unwrap_or_else(|| format!("{:?}", failed.err));
+                Err(reason)
+            }
+        }
+    }
+
+    fn read_anchor<T: AccountDeserialize>(&self, address: &Pubkey) -> Option<T> {
+        let account = self.svm.get_account(address)?;
+        T::try_deserialize(&mut &account.data[..]).ok()
+    }
+
+    fn write_mint(&mut self, address: Pubkey, authority: Pubkey, decimals: u8) -> Result<(), String> {
+        let mint = spl_token::state::Mint {
+            mint_authority: COption::Some(authority),
+            supply: 0,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        let mut data = vec![0u8; spl_token::state::Mint::LEN];
+        spl_token::state::Mint::pack(mint, &mut data).map_err(|e| e.to_string())?;
+        self.set_raw_account(address, spl_token::ID, data)
+    }
+
+    fn write_token_account(&mut self, address: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64) -> Result<(), String> {
+        let token_account = spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            delegate: COption::None,
+            state: spl_token::state::AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        };
+        let mut data = vec![0u8; spl_token::state::Account::LEN];
+        spl_token::state::Account::pack(token_account, &mut data).map_err(|e| e.to_string())?;
+        self.set_raw_account(address, spl_token::ID, data)
+    }
+
+    fn set_raw_account(&mut self, address: Pubkey, owner: Pubkey, data: Vec<u8>) -> Result<(), String> {
+        let lamports = self.svm.minimum_balance_for_rent_exemption(data.len());
+        self.svm
+            .set_account(
+                address,
+                Account {
+                    lamports,
+                    data,
+                    owner,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .map_err(|e| format!("failed to write account {}: {:?}", address, e))
+    }
+}
+
+pub fn user_debt_pda(owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"user_debt_amount", owner.as_ref(), &trove_id_seed(0)], &aerospacer_protocol::ID).0
+}
+
+pub fn user_collateral_pda(owner: &Pubkey, denom: &str) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"user_collateral_amount", owner.as_ref(), &trove_id_seed(0), denom.as_bytes()],
+        &aerospacer_protocol::ID,
+    )
+    .0
+}
+
+pub fn liquidity_threshold_pda(owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"liquidity_threshold", owner.as_ref(), &trove_id_seed(0)], &aerospacer_protocol::ID).0
+}
+
+pub fn trove_registry_pda(owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"trove_registry", owner.as_ref()], &aerospacer_protocol::ID).0
+}
+
+/// Trove index page holding index position `position`
+pub fn trove_index_page_pda(position: u64) -> Pubkey {
+    let page_seed = aerospacer_protocol::state::TroveIndexPage::page_seed(position);
+    Pubkey::find_program_address(&[b"trove_index_page", page_seed.as_ref()], &aerospacer_protocol::ID).0
+}
+
+pub fn protocol_stablecoin_vault() -> Pubkey {
+    Pubkey::find_program_address(&[b"protocol_stablecoin_vault"], &aerospacer_protocol::ID).0
+}
+
+pub fn stability_pool_vault() -> Pubkey {
+    Pubkey::find_program_address(&[b"stability_pool_vault"], &aerospacer_protocol::ID).0
+}
+
+/// PDA holding the aUSD mint authority
+pub fn mint_authority() -> Pubkey {
+    Pubkey::find_program_address(&[b"mint_authority"], &aerospacer_protocol::ID).0
+}
+
+pub fn liquidation_reserve_vault() -> Pubkey {
+    Pubkey::find_program_address(&[b"liquidation_reserve_vault"], &aerospacer_protocol::ID).0
+}
+
+/// PDA the protocol signs distribute_fee CPIs with
+pub fn protocol_fee_caller() -> Pubkey {
+    aerospacer_fees::utils::get_fee_caller_pda(&aerospacer_protocol::ID).0
+}
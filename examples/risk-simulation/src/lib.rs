@@ -0,0 +1,8 @@
+//! Litesvm harness and scenario builders for the Aerospacer programs.
+//!
+//! The `risk-simulation` binary replays price paths with them; `tests/` uses
+//! [`sim::Scenario`] to regression-test multi-instruction economics in Rust.
+
+pub mod harness;
+pub mod price_path;
+pub mod sim;
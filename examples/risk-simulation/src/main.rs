@@ -0,0 +1,432 @@
+//! Risk simulation for the Aerospacer protocol.
+//!
+//! Spins up litesvm with the real compiled programs, opens a configurable
+//! population of troves, replays a historical price path from CSV and reports
+//! how liquidations, the stability pool and redemptions behave along the way.
+//!
+//! Usage:
+//!   anchor build
+//!   cargo run --release --manifest-path examples/risk-simulation/Cargo.toml -- \
+//!       --prices examples/risk-simulation/data/sol_drawdown.csv --troves 100
+
+use std::path::PathBuf;
+use std::process;
+use std::str::FromStr;
+
+use risk_simulation::harness::{Harness, TroveView, Wallet, STABLECOIN_DECIMALS};
+use risk_simulation::price_path;
+
+/// Keep redemption transactions inside the legacy account limit
+const MAX_REDEMPTION_TARGETS: usize = 4;
+/// Largest debt a single trove can carry: amounts are u64 with 18 decimals
+const MAX_TROVE_DEBT: f64 = 18.0;
+
+struct Config {
+    prices: PathBuf,
+    deploy_dir: PathBuf,
+    denom: String,
+    collateral_decimals: u8,
+    troves: usize,
+    min_icr: f64,
+    max_icr: f64,
+    min_debt: f64,
+    max_debt: f64,
+    stake_fraction: f64,
+    redeem_per_step: f64,
+    redemption_targets: usize,
+    seed: u64,
+    verbose: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            prices: PathBuf::new(),
+            deploy_dir: PathBuf::from("target/deploy"),
+            denom: "SOL".to_string(),
+            collateral_decimals: 9,
+            troves: 100,
+            min_icr: 1.2,
+            max_icr: 3.0,
+            min_debt: 2.0,
+            max_debt: 15.0,
+            stake_fraction: 0.5,
+            redeem_per_step: 0.0,
+            redemption_targets: 3,
+            seed: 42,
+            verbose: false,
+        }
+    }
+}
+
+const USAGE: &str = "\
+risk-simulation --prices <csv> [options]
+
+  --prices <path>            CSV of `timestamp,price` rows (unix seconds, USD)
+  --deploy-dir <path>        Directory with the compiled .so files [target/deploy]
+  --denom <name>             Collateral denom registered with the oracle [SOL]
+  --decimals <n>             Collateral token decimals [9]
+  --troves <n>               Number of troves to open [100]
+  --min-icr <ratio>          Lowest opening ICR, e.g. 1.2 for 120% [1.2]
+  --max-icr <ratio>          Highest opening ICR [3.0]
+  --min-debt <aUSD>          Smallest trove debt [2]
+  --max-debt <aUSD>          Largest trove debt, at most 18 [15]
+  --stake-fraction <f>       Share of each borrower's aUSD deposited in the stability pool [0.5]
+  --redeem-per-step <aUSD>   aUSD redeemed against the riskiest troves at every price step [0]
+  --redemption-targets <n>   Troves passed to each redemption, at most 4 [3]
+  --seed <n>                 Seed for the trove population [42]
+  --verbose                  Print every failed transaction
+";
+
+fn parse_value<T: FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value for {}: {}", flag, value))
+}
+
+fn parse_args() -> Result<Config, String> {
+    let mut config = Config::default();
+    let mut args = std::env::args().skip(1);
+
+    while let Some(flag) = args.next() {
+        if flag == "--help" || flag == "-h" {
+            print!("{}", USAGE);
+            process::exit(0);
+        }
+        if flag == "--verbose" {
+            config.verbose = true;
+            continue;
+        }
+
+        let value = args.next().ok_or(format!("missing value for {}", flag))?;
+        match flag.as_str() {
+            "--prices" => config.prices = PathBuf::from(&value),
+            "--deploy-dir" => config.deploy_dir = PathBuf::from(&value),
+            "--denom" => config.denom = value,
+            "--decimals" => config.collateral_decimals = parse_value(&flag, &value)?,
+            "--troves" => config.troves = parse_value(&flag, &value)?,
+            "--min-icr" => config.min_icr = parse_value(&flag, &value)?,
+            "--max-icr" => config.max_icr = parse_value(&flag, &value)?,
+            "--min-debt" => config.min_debt = parse_value(&flag, &value)?,
+            "--max-debt" => config.max_debt = parse_value(&flag, &value)?,
+            "--stake-fraction" => config.stake_fraction = parse_value(&flag, &value)?,
+            "--redeem-per-step" => config.redeem_per_step = parse_value(&flag, &value)?,
+            "--redemption-targets" => config.redemption_targets = parse_value(&flag, &value)?,
+            "--seed" => config.seed = parse_value(&flag, &value)?,
+            _ => return Err(format!("unknown flag {}\n\n{}", flag, USAGE)),
+        }
+    }
+
+    if config.prices.as_os_str().is_empty() {
+        return Err(format!("--prices is required\n\n{}", USAGE));
+    }
+    if config.troves == 0 {
+        return Err("--troves must be at least 1".to_string());
+    }
+    if !(config.min_icr > 1.15 && config.min_icr <= config.max_icr) {
+        return Err("ICR range must satisfy 1.15 < min-icr <= max-icr (opening requires MCR 115%)".to_string());
+    }
+    if !(config.min_debt > 0.0 && config.min_debt <= config.max_debt && config.max_debt <= MAX_TROVE_DEBT) {
+        return Err(format!("debt range must satisfy 0 < min-debt <= max-debt <= {}", MAX_TROVE_DEBT));
+    }
+    if !(0.0..=1.0).contains(&config.stake_fraction) {
+        return Err("--stake-fraction must be within [0, 1]".to_string());
+    }
+    if config.redemption_targets == 0 || config.redemption_targets > MAX_REDEMPTION_TARGETS {
+        return Err(format!("--redemption-targets must be within [1, {}]", MAX_REDEMPTION_TARGETS));
+    }
+    Ok(config)
+}
+
+/// Small deterministic generator so a seed always reproduces the same population
+struct XorShift(u64);
+
+impl XorShift {
+    fn next_f64(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn range(&mut self, low: f64, high: f64) -> f64 {
+        low + (high - low) * self.next_f64()
+    }
+}
+
+/// Running totals across the whole price path
+#[derive(Default)]
+struct Totals {
+    liquidations: u64,
+    liquidations_rejected: u64,
+    debt_liquidated: u128,
+    collateral_seized: u128,
+    debt_absorbed_by_pool: u128,
+    debt_redistributed: u128,
+    redemptions: u64,
+    redemptions_failed: u64,
+    ausd_redeemed: u128,
+    liquidation_cu: u64,
+    redemption_cu: u64,
+    worst_tcr: Option<(i64, f64)>,
+    busiest_step: Option<(i64, u64)>,
+}
+
+fn to_ausd(amount: u128) -> f64 {
+    amount as f64 / 10f64.powi(STABLECOIN_DECIMALS as i32)
+}
+
+fn run(config: Config) -> Result<(), String> {
+    let path = price_path::load(&config.prices)?;
+    let first = path[0];
+
+    let mut harness = Harness::new(
+        &config.deploy_dir,
+        &config.denom,
+        config.collateral_decimals,
+        first.price,
+        first.timestamp,
+    )?;
+    let collateral_unit = 10f64.powi(config.collateral_decimals as i32);
+    let stable_unit = 10f64.powi(STABLECOIN_DECIMALS as i32);
+
+    // STEP 1: Open the trove population at the first price of the path
+    println!(
+        "Opening {} troves at {} = ${:.4} (ICR {:.0}%-{:.0}%, debt {}-{} aUSD)",
+        config.troves,
+        config.denom,
+        first.price,
+        config.min_icr * 100.0,
+        config.max_icr * 100.0,
+        config.min_debt,
+        config.max_debt
+    );
+
+    let mut rng = XorShift(config.seed.max(1));
+    let mut wallets: Vec<Wallet> = Vec::with_capacity(config.troves);
+    let mut opened_debt: u128 = 0;
+    let mut staked: u128 = 0;
+
+    for i in 0..config.troves {
+        let debt = rng.range(config.min_debt, config.max_debt);
+        // Opening ICRs are spread evenly across the configured range; debts are randomized
+        let spread = if config.troves > 1 { i as f64 / (config.troves - 1) as f64 } else { 0.0 };
+        let icr = config.min_icr + (config.max_icr - config.min_icr) * spread;
+        let collateral_amount = (debt * icr / first.price * collateral_unit).ceil() as u64;
+        let loan_amount = (debt * stable_unit) as u64;
+
+        let wallet = harness.new_wallet(collateral_amount)?;
+        harness
+            .open_trove(&wallet, collateral_amount, loan_amount)
+            .map_err(|e| format!("open_trove #{} failed: {}", i, e))?;
+        opened_debt += loan_amount as u128;
+
+        let stake_amount = (harness.token_balance(&wallet.stablecoin_token_account) as f64 * config.stake_fraction) as u64;
+        if stake_amount > 0 {
+            harness
+                .stake(&wallet, stake_amount)
+                .map_err(|e| format!("stake #{} failed: {}", i, e))?;
+            staked += stake_amount as u128;
+        }
+        wallets.push(wallet);
+    }
+
+    println!("Opened debt: {:.4} aUSD, stability pool: {:.4} aUSD", to_ausd(opened_debt), to_ausd(staked));
+
+    // The redeemer is a dedicated, very safe trove so it never becomes a target itself
+    let redeemer = if config.redeem_per_step > 0.0 {
+        let debt = MAX_TROVE_DEBT;
+        let collateral_amount = (debt * 10.0 / first.price * collateral_unit).ceil() as u64;
+        let wallet = harness.new_wallet(collateral_amount)?;
+        harness
+            .open_trove(&wallet, collateral_amount, (debt * stable_unit) as u64)
+            .map_err(|e| format!("redeemer open_trove failed: {}", e))?;
+        Some(wallet)
+    } else {
+        None
+    };
+    let redeem_amount = (config.redeem_per_step * stable_unit) as u64;
+
+    // STEP 2: Replay the price path
+    println!();
+    println!(
+        "{:>12} {:>12} {:>7} {:>7} {:>7} {:>5} {:>12} {:>12} {:>12} {:>12} {:>9}",
+        "timestamp", "price", "active", "<MCR", "<liq", "liq", "debt liq", "pool", "redistrib", "redeemed", "TCR %"
+    );
+
+    let collateral_config = harness.collateral_config()?;
+    let mcr = collateral_config.minimum_collateral_ratio;
+    let liquidation_threshold = collateral_config.liquidation_threshold;
+    let mut totals = Totals::default();
+
+    for point in &path {
+        harness.set_price(point.price, point.timestamp)?;
+
+        let mut views: Vec<(usize, TroveView)> = wallets
+            .iter()
+            .enumerate()
+            .filter_map(|(i, wallet)| harness.trove(&wallet.pubkey()).map(|view| (i, view)))
+            .filter(|(_, view)| view.debt > 0)
+            .collect();
+        views.sort_by_key(|(_, view)| harness.icr(view));
+
+        let below_mcr = views.iter().filter(|(_, v)| harness.icr(v) < mcr).count();
+        let liquidatable: Vec<(usize, TroveView)> = views
+            .iter()
+            .copied()
+            .filter(|(_, v)| harness.icr(v) < liquidation_threshold)
+            .collect();
+
+        // Liquidate everything under the threshold, riskiest first
+        let mut step_liquidations = 0u64;
+        let mut step_debt = 0u128;
+        let mut step_pool = 0u128;
+        let mut step_redistributed = 0u128;
+
+        for (i, view) in &liquidatable {
+            let pool_before = harness.stability_pool_balance();
+            match harness.liquidate(&wallets[*i]) {
+                Ok(cu) => {
+                    let absorbed = pool_before.saturating_sub(harness.stability_pool_balance()) as u128;
+                    step_liquidations += 1;
+                    step_debt += view.debt as u128;
+                    step_pool += absorbed;
+                    step_redistributed += (view.debt as u128).saturating_sub(absorbed);
+                    totals.collateral_seized += view.collateral as u128;
+                    totals.liquidation_cu += cu;
+                }
+                Err(reason) => {
+                    totals.liquidations_rejected += 1;
+                    if config.verbose {
+                        eprintln!("liquidation of {} rejected: {}", wallets[*i].pubkey(), reason);
+                    }
+                }
+            }
+        }
+
+        // Redeem against the riskiest remaining troves that are still solvent
+        let mut step_redeemed = 0u128;
+        if let Some(redeemer) = &redeemer {
+            let balance = harness.token_balance(&redeemer.stablecoin_token_account);
+            let targets: Vec<&Wallet> = views
+                .iter()
+                .filter(|(i, _)| harness.trove(&wallets[*i].pubkey()).is_some_and(|v| v.debt > 0))
+                .take(config.redemption_targets)
+                .map(|(i, _)| &wallets[*i])
+                .collect();
+
+            if redeem_amount > 0 && balance >= redeem_amount && !targets.is_empty() {
+                match harness.redeem(redeemer, redeem_amount, &targets) {
+                    Ok(cu) => {
+                        totals.redemptions += 1;
+                        totals.redemption_cu += cu;
+                        step_redeemed = redeem_amount as u128;
+                    }
+                    Err(reason) => {
+                        totals.redemptions_failed += 1;
+                        if config.verbose {
+                            eprintln!("redemption at {} failed: {}", point.timestamp, reason);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Total collateral ratio across every open trove after this step
+        let (total_value, total_debt) = wallets
+            .iter()
+            .chain(redeemer.iter())
+            .filter_map(|wallet| harness.trove(&wallet.pubkey()))
+            .fold((0u128, 0u128), |(value, debt), view| {
+                (value + harness.collateral_value(view.collateral), debt + view.debt as u128)
+            });
+        let tcr = if total_debt == 0 {
+            f64::INFINITY
+        } else {
+            // micro-USD collateral vs 18-decimal debt
+            total_value as f64 * 1e12 / total_debt as f64 * 100.0
+        };
+
+        println!(
+            "{:>12} {:>12.4} {:>7} {:>7} {:>7} {:>5} {:>12.4} {:>12.4} {:>12.4} {:>12.4} {:>9.2}",
+            point.timestamp,
+            point.price,
+            views.len(),
+            below_mcr,
+            liquidatable.len(),
+            step_liquidations,
+            to_ausd(step_debt),
+            to_ausd(step_pool),
+            to_ausd(step_redistributed),
+            to_ausd(step_redeemed),
+            tcr
+        );
+
+        totals.liquidations += step_liquidations;
+        totals.debt_liquidated += step_debt;
+        totals.debt_absorbed_by_pool += step_pool;
+        totals.debt_redistributed += step_redistributed;
+        totals.ausd_redeemed += step_redeemed;
+        if totals.worst_tcr.is_none_or(|(_, worst)| tcr < worst) {
+            totals.worst_tcr = Some((point.timestamp, tcr));
+        }
+        if step_liquidations > 0 && totals.busiest_step.is_none_or(|(_, most)| step_liquidations > most) {
+            totals.busiest_step = Some((point.timestamp, step_liquidations));
+        }
+    }
+
+    // STEP 3: Summary
+    let state = harness.protocol_state()?;
+    let surviving = wallets
+        .iter()
+        .filter_map(|wallet| harness.trove(&wallet.pubkey()))
+        .filter(|view| view.debt > 0)
+        .count();
+
+    println!();
+    println!("Summary");
+    println!("  Price steps:                {}", path.len());
+    println!("  Troves surviving:           {} / {}", surviving, wallets.len());
+    println!("  Liquidations:               {} ({} rejected on-chain)", totals.liquidations, totals.liquidations_rejected);
+    println!("  Debt liquidated:            {:.4} aUSD", to_ausd(totals.debt_liquidated));
+    println!("    absorbed by pool:         {:.4} aUSD", to_ausd(totals.debt_absorbed_by_pool));
+    println!("    redistributed:            {:.4} aUSD", to_ausd(totals.debt_redistributed));
+    println!(
+        "  Collateral seized:          {:.6} {}",
+        totals.collateral_seized as f64 / collateral_unit,
+        config.denom
+    );
+    println!("  Redemptions:                {} ({} failed)", totals.redemptions, totals.redemptions_failed);
+    println!("  aUSD redeemed:              {:.4}", to_ausd(totals.ausd_redeemed));
+    println!("  Stability pool remaining:   {:.4} aUSD", to_ausd(harness.stability_pool_balance() as u128));
+    println!("  Protocol total debt:        {:.4} aUSD", to_ausd(state.total_debt_amount as u128));
+    if let Some((timestamp, tcr)) = totals.worst_tcr {
+        println!("  Worst TCR:                  {:.2}% at {}", tcr, timestamp);
+    }
+    if let Some((timestamp, count)) = totals.busiest_step {
+        println!("  Busiest step:               {} liquidations at {}", count, timestamp);
+    }
+    if totals.liquidations > 0 {
+        println!("  Avg CU / liquidation:       {}", totals.liquidation_cu / totals.liquidations);
+    }
+    if totals.redemptions > 0 {
+        println!("  Avg CU / redemption:        {}", totals.redemption_cu / totals.redemptions);
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let config = match parse_args() {
+        Ok(config) => config,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(2);
+        }
+    };
+
+    if let Err(message) = run(config) {
+        eprintln!("simulation failed: {}", message);
+        process::exit(1);
+    }
+}
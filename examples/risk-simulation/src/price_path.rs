@@ -0,0 +1,62 @@
+use std::fs;
+use std::path::Path;
+
+/// One observation of the historical price path
+#[derive(Clone, Copy, Debug)]
+pub struct PricePoint {
+    pub timestamp: i64,
+    pub price: f64, // USD per whole collateral token
+}
+
+/// Parse a `timestamp,price` CSV file.
+///
+/// - An optional header row is skipped (any first row whose price column is not numeric)
+/// - Blank lines and lines starting with `#` are ignored
+/// - Timestamps are unix seconds and must be strictly increasing
+pub fn load(path: &Path) -> Result<Vec<PricePoint>, String> {
+    let raw = fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+
+    let mut points: Vec<PricePoint> = Vec::new();
+    let mut header_skipped = false;
+    for (line_no, line) in raw.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut cols = line.split(',').map(str::trim);
+        let (ts_col, price_col) = match (cols.next(), cols.next()) {
+            (Some(ts), Some(price)) => (ts, price),
+            _ => return Err(format!("line {}: expected `timestamp,price`", line_no + 1)),
+        };
+
+        let price = match price_col.parse::<f64>() {
+            Ok(price) => price,
+            Err(_) if points.is_empty() && !header_skipped => {
+                header_skipped = true;
+                continue;
+            }
+            Err(_) => return Err(format!("line {}: invalid price `{}`", line_no + 1, price_col)),
+        };
+        let timestamp = ts_col
+            .parse::<i64>()
+            .map_err(|_| format!("line {}: invalid timestamp `{}`", line_no + 1, ts_col))?;
+
+        if !(price.is_finite() && price > 0.0) {
+            return Err(format!("line {}: price must be positive", line_no + 1));
+        }
+        if let Some(prev) = points.last() {
+            if timestamp <= prev.timestamp {
+                return Err(format!("line {}: timestamps must be strictly increasing", line_no + 1));
+            }
+        }
+
+        points.push(PricePoint { timestamp, price });
+    }
+
+    if points.is_empty() {
+        return Err(format!("{} contains no price points", path.display()));
+    }
+    Ok(points)
+}
@@ -0,0 +1,151 @@
+//! Scenario builders on top of the harness.
+//!
+//! A [`Scenario`] is one freshly initialized protocol with a single collateral
+//! denom. Tests open trove populations, move the price and run liquidation
+//! batches through it, then assert on what the programs left on-chain.
+
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::harness::{Harness, TroveView, Wallet, STABLECOIN_DECIMALS};
+
+/// Keep liquidate_troves transactions inside the account lock limit (4 accounts per trove)
+pub const MAX_LIQUIDATION_BATCH: usize = 8;
+/// Seconds the clock moves with every price change
+const PRICE_STEP_SECS: i64 = 60;
+/// Scenarios start at an arbitrary fixed time so runs are reproducible
+const START_TIMESTAMP: i64 = 1_700_000_000;
+
+/// What one liquidation run did, in aUSD base units
+#[derive(Clone, Debug, Default)]
+pub struct LiquidationOutcome {
+    pub liquidated: usize,
+    pub rejections: Vec<String>, // Program error of every batch rejected on-chain
+    pub debt_liquidated: u128,
+    pub absorbed_by_pool: u128,
+    pub redistributed: u128,
+    pub compute_units: u64,
+}
+
+pub struct Scenario {
+    pub harness: Harness,
+    /// Trove owners in opening order; scenario methods address troves by index into it
+    pub wallets: Vec<Wallet>,
+    price: f64,
+    timestamp: i64,
+}
+
+impl Scenario {
+    /// Fresh oracle, fees and protocol with `denom` registered at `price`
+    pub fn new(deploy_dir: &Path, denom: &str, collateral_decimals: u8, price: f64) -> Result<Self, String> {
+        let harness = Harness::new(deploy_dir, denom, collateral_decimals, price, START_TIMESTAMP)?;
+        Ok(Self {
+            harness,
+            wallets: Vec::new(),
+            price,
+            timestamp: START_TIMESTAMP,
+        })
+    }
+
+    /// `target/deploy` of the programs workspace, where `anchor build` leaves the programs
+    pub fn default_deploy_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy")
+    }
+
+    pub fn price(&self) -> f64 {
+        self.price
+    }
+
+    /// Open `count` troves owing `debt` aUSD each at `icr` (1.5 = 150%) at the current price,
+    /// returning their indices
+    pub fn open_troves(&mut self, count: usize, icr: f64, debt: f64) -> Result<Range<usize>, String> {
+        let collateral_unit = 10f64.powi(self.harness.collateral_decimals as i32);
+        let collateral_amount = (debt * icr / self.price * collateral_unit).ceil() as u64;
+        let loan_amount = (debt * 10f64.powi(STABLECOIN_DECIMALS as i32)) as u64;
+
+        let start = self.wallets.len();
+        for i in 0..count {
+            let wallet = self.harness.new_wallet(collateral_amount)?;
+            self.harness
+                .open_trove(&wallet, collateral_amount, loan_amount)
+                .map_err(|e| format!("open_trove #{} failed: {}", start + i, e))?;
+            self.wallets.push(wallet);
+        }
+        Ok(start..self.wallets.len())
+    }
+
+    /// Stake `fraction` of the aUSD each of `troves` holds into the stability pool
+    pub fn stake(&mut self, troves: Range<usize>, fraction: f64) -> Result<u128, String> {
+        let mut staked = 0u128;
+        for i in troves {
+            let wallet = &self.wallets[i];
+            let amount = (self.harness.token_balance(&wallet.stablecoin_token_account) as f64 * fraction) as u64;
+            if amount > 0 {
+                self.harness
+                    .stake(wallet, amount)
+                    .map_err(|e| format!("stake #{} failed: {}", i, e))?;
+                staked += amount as u128;
+            }
+        }
+        Ok(staked)
+    }
+
+    /// Publish the current price moved by `percent` (-30.0 = a 30% crash) one step later
+    pub fn move_price(&mut self, percent: f64) -> Result<f64, String> {
+        self.price *= 1.0 + percent / 100.0;
+        self.timestamp += PRICE_STEP_SECS;
+        self.harness.set_price(self.price, self.timestamp)?;
+        Ok(self.price)
+    }
+
+    /// Drop the price by `percent` (30.0 = -30%)
+    pub fn crash_price(&mut self, percent: f64) -> Result<f64, String> {
+        self.move_price(-percent)
+    }
+
+    /// The trove at `index`; `None` once its accounts are gone
+    pub fn trove(&self, index: usize) -> Option<TroveView> {
+        self.harness.trove(&self.wallets[index].pubkey())
+    }
+
+    /// Open troves below the denom's liquidation threshold at the current price, riskiest first
+    pub fn liquidatable(&self) -> Result<Vec<usize>, String> {
+        let liquidation_threshold = self.harness.collateral_config()?.liquidation_threshold;
+        let mut troves: Vec<(usize, u64)> = (0..self.wallets.len())
+            .filter_map(|i| self.trove(i).filter(|view| view.debt > 0).map(|view| (i, self.harness.icr(&view))))
+            .filter(|(_, icr)| *icr < liquidation_threshold)
+            .collect();
+        troves.sort_by_key(|(_, icr)| *icr);
+        Ok(troves.into_iter().map(|(i, _)| i).collect())
+    }
+
+    /// Liquidate every trove below the liquidation threshold through liquidate_troves, in
+    /// batches of up to MAX_LIQUIDATION_BATCH. A rejected batch is recorded, not retried.
+    pub fn run_liquidation_batch(&mut self) -> Result<LiquidationOutcome, String> {
+        let mut outcome = LiquidationOutcome::default();
+        let targets = self.liquidatable()?;
+
+        for batch in targets.chunks(MAX_LIQUIDATION_BATCH) {
+            let views: Vec<TroveView> = batch.iter().filter_map(|&i| self.trove(i)).collect();
+            let pool_before = self.harness.stability_pool_balance();
+            let wallets: Vec<&Wallet> = batch.iter().map(|&i| &self.wallets[i]).collect();
+
+            match self.harness.liquidate_batch(&wallets) {
+                Ok(cu) => {
+                    let debt: u128 = views.iter().map(|view| view.debt as u128).sum();
+                    let absorbed = pool_before.saturating_sub(self.harness.stability_pool_balance()) as u128;
+                    outcome.liquidated += batch
+                        .iter()
+                        .filter(|&&i| self.trove(i).is_none_or(|view| view.debt == 0))
+                        .count();
+                    outcome.debt_liquidated += debt;
+                    outcome.absorbed_by_pool += absorbed;
+                    outcome.redistributed += debt.saturating_sub(absorbed);
+                    outcome.compute_units += cu;
+                }
+                Err(reason) => outcome.rejections.push(reason),
+            }
+        }
+        Ok(outcome)
+    }
+}
@@ -0,0 +1,72 @@
+//! Liquidation economics regression scenarios.
+//!
+//! The scenarios run the compiled programs from `target/deploy`, so `anchor build`
+//! first; without them every scenario is skipped.
+
+use risk_simulation::sim::{Scenario, MAX_LIQUIDATION_BATCH};
+
+fn scenario() -> Option<Scenario> {
+    let deploy_dir = Scenario::default_deploy_dir();
+    if !deploy_dir.join("aerospacer_protocol.so").exists() {
+        eprintln!("skipping: no compiled programs in {} (run `anchor build`)", deploy_dir.display());
+        return None;
+    }
+    Some(Scenario::new(&deploy_dir, "SOL", 9, 150.0).expect("scenario setup failed"))
+}
+
+#[test]
+fn test_crash_liquidates_risky_troves_into_pool() {
+    let Some(mut scenario) = scenario() else { return };
+    let risky = scenario.open_troves(4, 1.2, 5.0).unwrap();
+    let safe = scenario.open_troves(2, 3.0, 15.0).unwrap();
+    scenario.stake(safe.clone(), 1.0).unwrap();
+
+    // 120% troves fall under the 110% threshold, 300% ones stay well above
+    scenario.crash_price(15.0).unwrap();
+    assert_eq!(scenario.liquidatable().unwrap(), risky.clone().collect::<Vec<_>>());
+
+    let outcome = scenario.run_liquidation_batch().unwrap();
+    assert!(outcome.rejections.is_empty(), "{:?}", outcome.rejections);
+    assert_eq!(outcome.liquidated, risky.len());
+    // The pool covers all of it - nothing is redistributed to the surviving troves
+    assert_eq!(outcome.absorbed_by_pool, outcome.debt_liquidated);
+    assert_eq!(outcome.redistributed, 0);
+
+    for i in safe.clone() {
+        assert!(scenario.trove(i).is_some_and(|view| view.debt > 0));
+    }
+    let state = scenario.harness.protocol_state().unwrap();
+    assert_eq!(state.active_troves, safe.len() as u64);
+    assert!(scenario.liquidatable().unwrap().is_empty());
+}
+
+#[test]
+fn test_liquidation_run_splits_into_batches() {
+    let Some(mut scenario) = scenario() else { return };
+    let risky = scenario.open_troves(MAX_LIQUIDATION_BATCH + 2, 1.2, 2.0).unwrap();
+    let safe = scenario.open_troves(2, 3.0, 15.0).unwrap();
+    scenario.stake(safe.clone(), 1.0).unwrap();
+
+    scenario.crash_price(15.0).unwrap();
+    let outcome = scenario.run_liquidation_batch().unwrap();
+    assert!(outcome.rejections.is_empty(), "{:?}", outcome.rejections);
+    assert_eq!(outcome.liquidated, risky.len());
+    for i in risky {
+        assert!(scenario.trove(i).is_none_or(|view| view.debt == 0));
+    }
+    assert_eq!(scenario.harness.protocol_state().unwrap().active_troves, safe.len() as u64);
+}
+
+#[test]
+fn test_mild_drop_liquidates_nothing() {
+    let Some(mut scenario) = scenario() else { return };
+    scenario.open_troves(3, 1.3, 5.0).unwrap();
+
+    scenario.crash_price(5.0).unwrap();
+    assert!(scenario.liquidatable().unwrap().is_empty());
+
+    let outcome = scenario.run_liquidation_batch().unwrap();
+    assert_eq!(outcome.liquidated, 0);
+    assert_eq!(outcome.debt_liquidated, 0);
+    assert_eq!(scenario.harness.protocol_state().unwrap().active_troves, 3);
+}
@@ -0,0 +1,260 @@
+//! State snapshot export/import for fork testing.
+//!
+//! `export` pulls every account owned by the protocol program from a cluster
+//! into a JSON snapshot. `import` replays that snapshot into a local validator
+//! running a `fork-testing` build of the protocol through the `import_account`
+//! instruction, so upgrades can be rehearsed against a realistic position
+//! distribution.
+//!
+//! Token vaults and mints are not protocol-owned and are not part of the
+//! snapshot; clone them with `solana-test-validator --clone` when balances
+//! matter.
+//!
+//! Usage:
+//!   cargo run --release --manifest-path examples/state-snapshot/Cargo.toml -- \
+//!       export --url https://api.devnet.solana.com --out devnet.json
+//!   anchor build -- --features fork-testing && solana-test-validator ...
+//!   cargo run --release --manifest-path examples/state-snapshot/Cargo.toml -- \
+//!       import --url http://127.0.0.1:8899 --snapshot devnet.json --keypair ~/.config/solana/id.json
+
+mod seeds;
+
+use std::path::PathBuf;
+use std::process;
+use std::str::FromStr;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::system_program;
+use solana_sdk::transaction::Transaction;
+
+use aerospacer_protocol::instructions::ImportAccountParams;
+
+/// Raw bytes written per import transaction - keeps each one under the packet limit
+const CHUNK_SIZE: usize = 800;
+/// The admin pubkey sits right after the StateAccount discriminator
+const STATE_ADMIN_OFFSET: usize = 8;
+
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    program_id: String,
+    slot: u64,
+    accounts: Vec<SnapshotAccount>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotAccount {
+    pubkey: String,
+    kind: String,
+    /// Base64 PDA seeds without the bump
+    seeds: Vec<String>,
+    lamports: u64,
+    /// Base64 account data, discriminator included
+    data: String,
+}
+
+enum Command {
+    Export { url: String, out: PathBuf },
+    Import { url: String, snapshot: PathBuf, keypair: PathBuf },
+}
+
+const USAGE: &str = "\
+state-snapshot export --url <rpc> --out <file>
+state-snapshot import --url <local rpc> --snapshot <file> --keypair <path>
+
+  export   Write every protocol-owned account of the cluster at <rpc> to <file>
+  import   Replay <file> into a local validator running a `fork-testing` build;
+           the imported state's admin is rewritten to <keypair>
+";
+
+fn parse_value<T: FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value for {}: {}", flag, value))
+}
+
+fn parse_args() -> Result<Command, String> {
+    let mut args = std::env::args().skip(1);
+    let command = args.next().ok_or(format!("missing command\n\n{}", USAGE))?;
+    if command == "--help" || command == "-h" {
+        print!("{}", USAGE);
+        process::exit(0);
+    }
+
+    let mut url: Option<String> = None;
+    let mut out: Option<PathBuf> = None;
+    let mut snapshot: Option<PathBuf> = None;
+    let mut keypair: Option<PathBuf> = None;
+
+    while let Some(flag) = args.next() {
+        if flag == "--help" || flag == "-h" {
+            print!("{}", USAGE);
+            process::exit(0);
+        }
+
+        let value = args.next().ok_or(format!("missing value for {}", flag))?;
+        match flag.as_str() {
+            "--url" => url = Some(value),
+            "--out" => out = Some(parse_value(&flag, &value)?),
+            "--snapshot" => snapshot = Some(parse_value(&flag, &value)?),
+            "--keypair" => keypair = Some(parse_value(&flag, &value)?),
+            _ => return Err(format!("unknown flag {}\n\n{}", flag, USAGE)),
+        }
+    }
+
+    let url = url.ok_or(format!("--url is required\n\n{}", USAGE))?;
+    match command.as_str() {
+        "export" => Ok(Command::Export {
+            url,
+            out: out.ok_or(format!("--out is required\n\n{}", USAGE))?,
+        }),
+        "import" => Ok(Command::Import {
+            url,
+            snapshot: snapshot.ok_or(format!("--snapshot is required\n\n{}", USAGE))?,
+            keypair: keypair.ok_or(format!("--keypair is required\n\n{}", USAGE))?,
+        }),
+        _ => Err(format!("unknown command {}\n\n{}", command, USAGE)),
+    }
+}
+
+fn export(url: &str, out: &PathBuf) -> Result<(), String> {
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+    let program_id = aerospacer_protocol::ID;
+
+    let slot = client.get_slot().map_err(|e| format!("get_slot: {}", e))?;
+    let raw_accounts = client
+        .get_program_accounts(&program_id)
+        .map_err(|e| format!("get_program_accounts: {}", e))?;
+
+    let mut accounts = Vec::with_capacity(raw_accounts.len());
+    let mut skipped = 0usize;
+    for (pubkey, account) in raw_accounts {
+        let Some(recovered) = seeds::recover(&pubkey, &account.data, &program_id) else {
+            eprintln!("warning: skipping {} ({} bytes) - not a known PDA", pubkey, account.data.len());
+            skipped += 1;
+            continue;
+        };
+        accounts.push(SnapshotAccount {
+            pubkey: pubkey.to_string(),
+            kind: recovered.kind,
+            seeds: recovered.seeds.iter().map(|seed| BASE64.encode(seed)).collect(),
+            lamports: account.lamports,
+            data: BASE64.encode(&account.data),
+        });
+    }
+    // State first: once it exists, every later import is checked against its admin
+    accounts.sort_by_key(|account| account.kind != "state");
+
+    let snapshot = Snapshot {
+        program_id: program_id.to_string(),
+        slot,
+        accounts,
+    };
+    let json = serde_json::to_string_pretty(&snapshot).map_err(|e| e.to_string())?;
+    std::fs::write(out, json).map_err(|e| format!("{}: {}", out.display(), e))?;
+
+    println!(
+        "Exported {} accounts at slot {} to {} ({} skipped)",
+        snapshot.accounts.len(),
+        slot,
+        out.display(),
+        skipped
+    );
+    Ok(())
+}
+
+fn import_instruction(authority: &Pubkey, target: &Pubkey, params: ImportAccountParams) -> Instruction {
+    let (state, _) = Pubkey::find_program_address(&[b"state"], &aerospacer_protocol::ID);
+    Instruction {
+        program_id: aerospacer_protocol::ID,
+        accounts: aerospacer_protocol::accounts::ImportAccount {
+            authority: *authority,
+            state,
+            target: *target,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: aerospacer_protocol::instruction::ImportAccount { params }.data(),
+    }
+}
+
+fn import(url: &str, snapshot_path: &PathBuf, keypair_path: &PathBuf) -> Result<(), String> {
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+    let payer: Keypair = read_keypair_file(keypair_path)
+        .map_err(|e| format!("{}: {}", keypair_path.display(), e))?;
+
+    let json = std::fs::read_to_string(snapshot_path)
+        .map_err(|e| format!("{}: {}", snapshot_path.display(), e))?;
+    let snapshot: Snapshot = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+    if snapshot.program_id != aerospacer_protocol::ID.to_string() {
+        return Err(format!(
+            "snapshot was taken from program {}, this build is {}",
+            snapshot.program_id,
+            aerospacer_protocol::ID
+        ));
+    }
+
+    for account in &snapshot.accounts {
+        let target = Pubkey::from_str(&account.pubkey).map_err(|e| format!("{}: {}", account.pubkey, e))?;
+        let seeds = account
+            .seeds
+            .iter()
+            .map(|seed| BASE64.decode(seed))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("{}: {}", account.pubkey, e))?;
+        let mut data = BASE64.decode(&account.data).map_err(|e| format!("{}: {}", account.pubkey, e))?;
+
+        if account.kind == "state" {
+            data[STATE_ADMIN_OFFSET..STATE_ADMIN_OFFSET + 32].copy_from_slice(payer.pubkey().as_ref());
+        }
+
+        for (index, chunk) in data.chunks(CHUNK_SIZE).enumerate() {
+            let params = ImportAccountParams {
+                seeds: seeds.clone(),
+                space: data.len() as u32,
+                offset: (index * CHUNK_SIZE) as u32,
+                data: chunk.to_vec(),
+            };
+            let blockhash = client
+                .get_latest_blockhash()
+                .map_err(|e| format!("get_latest_blockhash: {}", e))?;
+            let tx = Transaction::new_signed_with_payer(
+                &[import_instruction(&payer.pubkey(), &target, params)],
+                Some(&payer.pubkey()),
+                &[&payer],
+                blockhash,
+            );
+            client
+                .send_and_confirm_transaction(&tx)
+                .map_err(|e| format!("importing {} ({}): {}", account.pubkey, account.kind, e))?;
+        }
+        println!("Imported {} {} ({} bytes)", account.kind, account.pubkey, data.len());
+    }
+
+    println!(
+        "Imported {} accounts from slot {} - admin is now {}",
+        snapshot.accounts.len(),
+        snapshot.slot,
+        payer.pubkey()
+    );
+    Ok(())
+}
+
+fn main() {
+    let result = match parse_args() {
+        Ok(Command::Export { url, out }) => export(&url, &out),
+        Ok(Command::Import { url, snapshot, keypair }) => import(&url, &snapshot, &keypair),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
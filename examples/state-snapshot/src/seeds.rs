@@ -0,0 +1,153 @@
+//! Recovers the PDA seeds of an exported protocol account from its raw data.
+//!
+//! Every protocol PDA is keyed by a constant prefix plus (optionally) the owner
+//! pubkey and/or a collateral denom, and those keys are stored in the account
+//! itself. Candidates are built from the leading fields and only accepted when
+//! they re-derive the exported address, so a layout change can never produce
+//! wrong seeds - at worst the account is reported as unknown.
+
+use solana_sdk::pubkey::Pubkey;
+
+/// Singleton PDAs
+const SINGLETONS: &[&str] = &[
+    "state",
+    "reward_emission",
+    "tag_registry",
+    "psm_config",
+    "trove_archive",
+    "keeper_bounty_fund",
+    "bad_debt_backstop",
+    "liquidator_whitelist",
+    "rebalancer_config",
+];
+/// `[prefix, owner]` PDAs, owner stored right after the discriminator
+const OWNER_KEYED: &[&str] = &[
+    "user_stake_amount",
+    "user_index",
+    "frontend",
+    "trove_registry",
+    "keeper_heartbeat",
+    "keeper",
+];
+/// `[prefix, owner, trove seed]` PDAs with the trove id at a fixed offset
+const TROVE_KEYED: &[(&str, usize)] = &[
+    ("user_debt_amount", 8 + 32 + 8 + 16 + 32 + 1),
+    ("liquidity_threshold", 8 + 32 + 8),
+];
+/// LiquidationAuction: `[prefix, owner, trove seed, denom]`, trove id right after the owner and
+/// the denom right after the trove id
+const AUCTION_PREFIX: &str = "liquidation_auction";
+/// UserCollateralAmount stores its trove id after the denom, amount(8), L snapshot(16) and scale(1)
+const COLLATERAL_TROVE_ID_AFTER_DENOM: usize = 8 + 16 + 1;
+/// `[prefix, owner, delegate]` PDAs, two pubkeys after the discriminator
+const OWNER_PAIR_KEYED: &[&str] = &["trove_delegate"];
+/// `[prefix, owner, denom]` PDAs, owner followed by a Borsh string
+const OWNER_DENOM_KEYED: &[&str] = &["user_collateral_snapshot"];
+/// `[prefix, denom]` PDAs whose first field is a Borsh string
+const DENOM_KEYED: &[&str] = &[
+    "stability_pool_snapshot",
+    "price_cache",
+    "denom_promotion",
+    "undistributed_seizure",
+    "public_goods_accrual",
+    "collateral_config",
+];
+/// TotalCollateralAmount keeps its denom zero-padded at a fixed zero-copy offset
+const TOTAL_COLLATERAL_PREFIX: &str = "total_collateral_amount";
+const TOTAL_COLLATERAL_DENOM: std::ops::Range<usize> = 8 + 16 + 16 + 8..8 + 16 + 16 + 8 + 32;
+
+const DISCRIMINATOR_LEN: usize = 8;
+const PUBKEY_LEN: usize = 32;
+/// Denoms are short tickers; anything longer is not a denom
+const MAX_DENOM_LEN: usize = 32;
+
+/// Seeds (without bump) that derive `address`, plus the prefix naming the account kind
+pub struct Recovered {
+    pub kind: String,
+    pub seeds: Vec<Vec<u8>>,
+}
+
+fn borsh_string(data: &[u8], offset: usize) -> Option<&[u8]> {
+    let len_bytes = data.get(offset..offset + 4)?;
+    let len = u32::from_le_bytes(len_bytes.try_into().ok()?) as usize;
+    if len == 0 || len > MAX_DENOM_LEN {
+        return None;
+    }
+    data.get(offset + 4..offset + 4 + len)
+}
+
+/// Trove seed: the 2-byte LE trove id (see state::trove_id_seed)
+fn trove_seed(data: &[u8], offset: usize) -> Option<Vec<u8>> {
+    data.get(offset..offset + 2).map(<[u8]>::to_vec)
+}
+
+fn candidates(data: &[u8]) -> Vec<(&'static str, Vec<Vec<u8>>)> {
+    let mut out = Vec::new();
+
+    for prefix in SINGLETONS {
+        out.push((*prefix, vec![prefix.as_bytes().to_vec()]));
+    }
+
+    if let Some(owner) = data.get(DISCRIMINATOR_LEN..DISCRIMINATOR_LEN + PUBKEY_LEN) {
+        for prefix in OWNER_KEYED {
+            out.push((*prefix, vec![prefix.as_bytes().to_vec(), owner.to_vec()]));
+        }
+        if let Some(second) = data.get(DISCRIMINATOR_LEN + PUBKEY_LEN..DISCRIMINATOR_LEN + 2 * PUBKEY_LEN) {
+            for prefix in OWNER_PAIR_KEYED {
+                out.push((*prefix, vec![prefix.as_bytes().to_vec(), owner.to_vec(), second.to_vec()]));
+            }
+        }
+        for (prefix, offset) in TROVE_KEYED {
+            if let Some(seed) = trove_seed(data, *offset) {
+                out.push((*prefix, vec![prefix.as_bytes().to_vec(), owner.to_vec(), seed]));
+            }
+        }
+        let auction_trove_id = DISCRIMINATOR_LEN + PUBKEY_LEN;
+        if let (Some(seed), Some(denom)) = (trove_seed(data, auction_trove_id), borsh_string(data, auction_trove_id + 2)) {
+            out.push((
+                AUCTION_PREFIX,
+                vec![AUCTION_PREFIX.as_bytes().to_vec(), owner.to_vec(), seed, denom.to_vec()],
+            ));
+        }
+        if let Some(denom) = borsh_string(data, DISCRIMINATOR_LEN + PUBKEY_LEN) {
+            for prefix in OWNER_DENOM_KEYED {
+                out.push((*prefix, vec![prefix.as_bytes().to_vec(), owner.to_vec(), denom.to_vec()]));
+            }
+            let trove_id_offset = DISCRIMINATOR_LEN + PUBKEY_LEN + 4 + denom.len() + COLLATERAL_TROVE_ID_AFTER_DENOM;
+            if let Some(seed) = trove_seed(data, trove_id_offset) {
+                out.push((
+                    "user_collateral_amount",
+                    vec![b"user_collateral_amount".to_vec(), owner.to_vec(), seed, denom.to_vec()],
+                ));
+            }
+        }
+    }
+
+    if let Some(denom) = borsh_string(data, DISCRIMINATOR_LEN) {
+        for prefix in DENOM_KEYED {
+            out.push((*prefix, vec![prefix.as_bytes().to_vec(), denom.to_vec()]));
+        }
+    }
+
+    if let Some(raw) = data.get(TOTAL_COLLATERAL_DENOM) {
+        let len = raw.iter().position(|b| *b == 0).unwrap_or(raw.len());
+        if len > 0 {
+            out.push((
+                TOTAL_COLLATERAL_PREFIX,
+                vec![TOTAL_COLLATERAL_PREFIX.as_bytes().to_vec(), raw[..len].to_vec()],
+            ));
+        }
+    }
+
+    out
+}
+
+/// Finds the seeds of a protocol-owned account, or None for accounts that are
+/// not PDAs of a known kind
+pub fn recover(address: &Pubkey, data: &[u8], program_id: &Pubkey) -> Option<Recovered> {
+    candidates(data).into_iter().find_map(|(kind, seeds)| {
+        let refs: Vec<&[u8]> = seeds.iter().map(|seed| seed.as_slice()).collect();
+        let (pda, _) = Pubkey::find_program_address(&refs, program_id);
+        (pda == *address).then(|| Recovered { kind: kind.to_string(), seeds })
+    })
+}
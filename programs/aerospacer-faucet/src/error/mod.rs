@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum AerospacerFaucetError {
+    #[msg("Only the asset creator may do this")]
+    Unauthorized,
+    
+    #[msg("Denom must be 1-32 characters of letters, digits, '.', '_', '-' or '/'")]
+    InvalidDenom,
+    
+    #[msg("Mock price must be positive with a confidence interval of at most 2% of it")]
+    InvalidPrice,
+    
+    #[msg("Token decimals plus price exponent must give at least 6 decimals of USD precision")]
+    InvalidPrecision,
+    
+    #[msg("Amount must be positive and within the asset's drip limit")]
+    InvalidAmount,
+}
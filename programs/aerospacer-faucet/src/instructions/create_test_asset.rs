@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+use aerospacer_oracle::cpi::accounts::RegisterFaucetAsset;
+use aerospacer_oracle::instructions::RegisterFaucetAssetParams;
+use crate::state::*;
+use crate::error::AerospacerFaucetError;
+use crate::mock_price::{init_mock_price, write_mock_price, MOCK_PRICE_ACCOUNT_LEN};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateTestAssetParams {
+    pub denom: String,
+    pub decimals: u8,
+    pub price: i64,     // Initial mock price in units of 10^expo USD
+    pub expo: i32,      // e.g. -8 like most Pyth feeds
+    pub conf: u64,      // Confidence interval, same units as price (at most 2% of it)
+    pub max_drip: u64,  // Largest amount a single drip mints
+}
+
+#[derive(Accounts)]
+#[instruction(params: CreateTestAssetParams)]
+pub struct CreateTestAsset<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + FaucetAsset::LEN,
+        seeds = [b"faucet_asset", params.denom.as_bytes()],
+        bump
+    )]
+    pub faucet_asset: Account<'info, FaucetAsset>,
+    
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = params.decimals,
+        mint::authority = faucet_authority,
+        seeds = [b"faucet_mint", params.denom.as_bytes()],
+        bump
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: Mock price account in the Pyth v2 layout, created here and written by mock_price
+    #[account(
+        init,
+        payer = creator,
+        space = MOCK_PRICE_ACCOUNT_LEN,
+        seeds = [b"mock_price", params.denom.as_bytes()],
+        bump
+    )]
+    pub mock_price: UncheckedAccount<'info>,
+    
+    /// CHECK: Signing PDA that owns the faucet mints and lists assets with the oracle
+    #[account(
+        seeds = [b"faucet_authority"],
+        bump
+    )]
+    pub faucet_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: aerospacer-oracle program
+    #[account(address = aerospacer_oracle::ID)]
+    pub oracle_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Oracle state account - validated by the oracle
+    #[account(mut)]
+    pub oracle_state: UncheckedAccount<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Handler for create_test_asset instruction
+/// Creates the test mint and its mock feed and lists the denom with the oracle in one call,
+/// so the protocol accepts it as collateral once the protocol side is configured for it.
+pub fn handler(ctx: Context<CreateTestAsset>, params: CreateTestAssetParams) -> Result<()> {
+    validate_denom(&params.denom)?;
+    require!(
+        is_valid_mock_price(params.price, params.conf),
+        AerospacerFaucetError::InvalidPrice
+    );
+    require!(
+        params.expo <= 0 && params.decimals as i64 - params.expo as i64 >= 6,
+        AerospacerFaucetError::InvalidPrecision
+    );
+    require!(params.max_drip > 0, AerospacerFaucetError::InvalidAmount);
+    
+    let clock = &ctx.accounts.clock;
+    {
+        let mut data = ctx.accounts.mock_price.try_borrow_mut_data()?;
+        init_mock_price(&mut data, params.expo);
+        write_mock_price(&mut data, params.price, params.conf, clock.slot, clock.unix_timestamp);
+    }
+    
+    let asset = &mut ctx.accounts.faucet_asset;
+    asset.denom = params.denom.clone();
+    asset.mint = ctx.accounts.mint.key();
+    asset.price_account = ctx.accounts.mock_price.key();
+    asset.creator = ctx.accounts.creator.key();
+    asset.max_drip = params.max_drip;
+    asset.decimals = params.decimals;
+    asset.expo = params.expo;
+    
+    let authority_seeds: &[&[u8]] = &[b"faucet_authority", &[ctx.bumps.faucet_authority]];
+    aerospacer_oracle::cpi::register_faucet_asset(
+        CpiContext::new_with_signer(
+            ctx.accounts.oracle_program.to_account_info(),
+            RegisterFaucetAsset {
+                faucet_authority: ctx.accounts.faucet_authority.to_account_info(),
+                state: ctx.accounts.oracle_state.to_account_info(),
+                clock: ctx.accounts.clock.to_account_info(),
+            },
+            &[authority_seeds],
+        ),
+        RegisterFaucetAssetParams {
+            denom: params.denom.clone(),
+            decimal: params.decimals,
+            pyth_price_account: ctx.accounts.mock_price.key(),
+        },
+    )?;
+    
+    msg!("Test asset created: {}", params.denom);
+    msg!("Mint: {}", asset.mint);
+    msg!("Mock price account: {}", asset.price_account);
+    msg!("Price: {} x 10^{}", params.price, params.expo);
+    
+    Ok(())
+}
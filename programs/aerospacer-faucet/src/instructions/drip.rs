@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, MintTo, Token, TokenAccount};
+use crate::state::*;
+use crate::error::AerospacerFaucetError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DripParams {
+    pub denom: String,
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: DripParams)]
+pub struct Drip<'info> {
+    #[account(
+        seeds = [b"faucet_asset", params.denom.as_bytes()],
+        bump
+    )]
+    pub faucet_asset: Account<'info, FaucetAsset>,
+    
+    #[account(
+        mut,
+        address = faucet_asset.mint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = mint
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: Signing PDA that owns the faucet mints
+    #[account(
+        seeds = [b"faucet_authority"],
+        bump
+    )]
+    pub faucet_authority: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Handler for drip instruction
+/// Mints test tokens to any token account of the faucet mint, at most max_drip per call
+pub fn handler(ctx: Context<Drip>, params: DripParams) -> Result<()> {
+    require!(
+        params.amount > 0 && params.amount <= ctx.accounts.faucet_asset.max_drip,
+        AerospacerFaucetError::InvalidAmount
+    );
+    
+    let authority_seeds: &[&[u8]] = &[b"faucet_authority", &[ctx.bumps.faucet_authority]];
+    anchor_spl::token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: ctx.accounts.faucet_authority.to_account_info(),
+            },
+            &[authority_seeds],
+        ),
+        params.amount,
+    )?;
+    
+    msg!("Dripped {} {} to {}", params.amount, params.denom, ctx.accounts.recipient_token_account.key());
+    
+    Ok(())
+}
@@ -0,0 +1,12 @@
+#[cfg(feature = "devnet")]
+pub mod create_test_asset;
+pub mod set_mock_price;
+pub mod drip;
+
+#[cfg(feature = "devnet")]
+#[allow(ambiguous_glob_reexports)]
+pub use create_test_asset::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_mock_price::*;
+#[allow(ambiguous_glob_reexports)]
+pub use drip::*;
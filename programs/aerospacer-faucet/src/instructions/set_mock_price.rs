@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerFaucetError;
+use crate::mock_price::write_mock_price;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetMockPriceParams {
+    pub denom: String,
+    pub price: i64, // In units of 10^expo USD, the asset's exponent is fixed at creation
+    pub conf: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: SetMockPriceParams)]
+pub struct SetMockPrice<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [b"faucet_asset", params.denom.as_bytes()],
+        bump,
+        constraint = faucet_asset.creator == creator.key() @ AerospacerFaucetError::Unauthorized
+    )]
+    pub faucet_asset: Account<'info, FaucetAsset>,
+    
+    /// CHECK: Mock price account of the asset
+    #[account(
+        mut,
+        seeds = [b"mock_price", params.denom.as_bytes()],
+        bump,
+        address = faucet_asset.price_account
+    )]
+    pub mock_price: UncheckedAccount<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+}
+
+/// Handler for set_mock_price instruction
+/// Lets the creator move the price, e.g. to push test troves under water
+pub fn handler(ctx: Context<SetMockPrice>, params: SetMockPriceParams) -> Result<()> {
+    require!(
+        is_valid_mock_price(params.price, params.conf),
+        AerospacerFaucetError::InvalidPrice
+    );
+    
+    let clock = &ctx.accounts.clock;
+    let mut data = ctx.accounts.mock_price.try_borrow_mut_data()?;
+    write_mock_price(&mut data, params.price, params.conf, clock.slot, clock.unix_timestamp);
+    
+    msg!("Mock price updated: {} = {} x 10^{}", params.denom, params.price, ctx.accounts.faucet_asset.expo);
+    
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+pub mod error;
+pub mod instructions;
+pub mod mock_price;
+pub mod state;
+
+use instructions::*;
+
+declare_id!("8mGavT8iHB5CJy1WqnuYnwS4Fxpao1iiFCGLXcPaL8xV");
+
+/// Aerospacer Faucet Program
+///
+/// Developer sandbox for devnet: creates test collateral mints backed by mock price feeds,
+/// lists them with the aerospacer-oracle in the same call and drips tokens to anyone, so
+/// integrators can open troves without bespoke mints and feeds. Never deploy to mainnet.
+#[program]
+pub mod aerospacer_faucet {
+    use super::*;
+
+    /// Create a test mint and mock price feed and list the denom with the oracle (devnet builds only)
+    #[cfg(feature = "devnet")]
+    pub fn create_test_asset(ctx: Context<CreateTestAsset>, params: CreateTestAssetParams) -> Result<()> {
+        instructions::create_test_asset::handler(ctx, params)
+    }
+
+    /// Move the mock price of a test asset (asset creator only)
+    pub fn set_mock_price(ctx: Context<SetMockPrice>, params: SetMockPriceParams) -> Result<()> {
+        instructions::set_mock_price::handler(ctx, params)
+    }
+
+    /// Mint test tokens to a token account
+    pub fn drip(ctx: Context<Drip>, params: DripParams) -> Result<()> {
+        instructions::drip::handler(ctx, params)
+    }
+}
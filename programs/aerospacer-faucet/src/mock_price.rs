@@ -0,0 +1,102 @@
+// Mock price accounts in the Pyth v2 price account layout.
+//
+// The oracle reads prices with pyth-sdk-solana, which only checks the header (magic, version,
+// account type) and reads the aggregate price, so a faucet-owned account with the same layout is
+// indistinguishable from a real feed. Fields the SDK ignores (product, publishers) are left zeroed.
+
+/// Size of a Pyth v2 price account (header + 32 publisher components)
+pub const MOCK_PRICE_ACCOUNT_LEN: usize = 3312;
+
+const MAGIC: u32 = 0xa1b2_c3d4;
+const VERSION_2: u32 = 2;
+const ACCOUNT_TYPE_PRICE: u32 = 3;
+const PRICE_TYPE_PRICE: u32 = 1;
+const STATUS_TRADING: u32 = 1;
+
+// Byte offsets into the price account
+const OFFSET_MAGIC: usize = 0;
+const OFFSET_VERSION: usize = 4;
+const OFFSET_ACCOUNT_TYPE: usize = 8;
+const OFFSET_SIZE: usize = 12;
+const OFFSET_PRICE_TYPE: usize = 16;
+const OFFSET_EXPO: usize = 20;
+const OFFSET_LAST_SLOT: usize = 32;
+const OFFSET_VALID_SLOT: usize = 40;
+const OFFSET_EMA_PRICE: usize = 48;  // Rational { val, numer, denom }
+const OFFSET_EMA_CONF: usize = 72;   // Rational { val, numer, denom }
+const OFFSET_TIMESTAMP: usize = 96;
+const OFFSET_PREV_SLOT: usize = 176;
+const OFFSET_PREV_PRICE: usize = 184;
+const OFFSET_PREV_CONF: usize = 192;
+const OFFSET_PREV_TIMESTAMP: usize = 200;
+const OFFSET_AGG_PRICE: usize = 208;
+const OFFSET_AGG_CONF: usize = 216;
+const OFFSET_AGG_STATUS: usize = 224;
+const OFFSET_AGG_PUB_SLOT: usize = 232;
+
+fn put(data: &mut [u8], offset: usize, bytes: &[u8]) {
+    data[offset..offset + bytes.len()].copy_from_slice(bytes);
+}
+
+/// Write the header of a new mock price account
+pub fn init_mock_price(data: &mut [u8], expo: i32) {
+    put(data, OFFSET_MAGIC, &MAGIC.to_le_bytes());
+    put(data, OFFSET_VERSION, &VERSION_2.to_le_bytes());
+    put(data, OFFSET_ACCOUNT_TYPE, &ACCOUNT_TYPE_PRICE.to_le_bytes());
+    put(data, OFFSET_SIZE, &(MOCK_PRICE_ACCOUNT_LEN as u32).to_le_bytes());
+    put(data, OFFSET_PRICE_TYPE, &PRICE_TYPE_PRICE.to_le_bytes());
+    put(data, OFFSET_EXPO, &expo.to_le_bytes());
+}
+
+/// Publish `price ± conf` (in units of 10^expo) as a trading aggregate at `slot`/`timestamp`
+///
+/// The previous aggregate and the EMA are set to the same values, so readers that fall back to
+/// them (non-trading status, EMA consumers) see the mock price too.
+pub fn write_mock_price(data: &mut [u8], price: i64, conf: u64, slot: u64, timestamp: i64) {
+    put(data, OFFSET_LAST_SLOT, &slot.to_le_bytes());
+    put(data, OFFSET_VALID_SLOT, &slot.to_le_bytes());
+    put(data, OFFSET_TIMESTAMP, &timestamp.to_le_bytes());
+    
+    for (offset, value) in [(OFFSET_EMA_PRICE, price), (OFFSET_EMA_CONF, conf as i64)] {
+        put(data, offset, &value.to_le_bytes());
+        put(data, offset + 8, &value.to_le_bytes());
+        put(data, offset + 16, &1i64.to_le_bytes());
+    }
+    
+    put(data, OFFSET_PREV_SLOT, &slot.to_le_bytes());
+    put(data, OFFSET_PREV_PRICE, &price.to_le_bytes());
+    put(data, OFFSET_PREV_CONF, &conf.to_le_bytes());
+    put(data, OFFSET_PREV_TIMESTAMP, &timestamp.to_le_bytes());
+    
+    put(data, OFFSET_AGG_PRICE, &price.to_le_bytes());
+    put(data, OFFSET_AGG_CONF, &conf.to_le_bytes());
+    put(data, OFFSET_AGG_STATUS, &STATUS_TRADING.to_le_bytes());
+    put(data, OFFSET_AGG_PUB_SLOT, &slot.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_i64(data: &[u8], offset: usize) -> i64 {
+        i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+    }
+
+    #[test]
+    fn test_mock_price_matches_pyth_v2_layout() {
+        let mut data = vec![0u8; MOCK_PRICE_ACCOUNT_LEN];
+        init_mock_price(&mut data, -8);
+        write_mock_price(&mut data, 150_00000000, 5_000_000, 42, 1_700_000_000);
+
+        assert_eq!(&data[0..4], &[0xd4, 0xc3, 0xb2, 0xa1]);
+        assert_eq!(u32::from_le_bytes(data[4..8].try_into().unwrap()), 2);
+        assert_eq!(u32::from_le_bytes(data[8..12].try_into().unwrap()), 3);
+        assert_eq!(i32::from_le_bytes(data[20..24].try_into().unwrap()), -8);
+        assert_eq!(read_i64(&data, OFFSET_AGG_PRICE), 150_00000000);
+        assert_eq!(read_i64(&data, OFFSET_AGG_CONF), 5_000_000);
+        assert_eq!(read_i64(&data, OFFSET_PREV_PRICE), 150_00000000);
+        assert_eq!(read_i64(&data, OFFSET_TIMESTAMP), 1_700_000_000);
+        // The aggregate ends where the 32 publisher components (96 bytes each) begin
+        assert_eq!(OFFSET_AGG_PUB_SLOT + 8 + 32 * 96, MOCK_PRICE_ACCOUNT_LEN);
+    }
+}
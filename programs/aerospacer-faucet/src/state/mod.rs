@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+/// Longest accepted denom - denoms are used as PDA seeds
+pub const MAX_DENOM_LEN: usize = 32;
+
+/// Whether the oracle accepts a mock price: positive, with a confidence interval within the
+/// default bound faucet assets are listed with
+pub fn is_valid_mock_price(price: i64, conf: u64) -> bool {
+    price > 0
+        && conf as u128 * 10_000
+            <= price as u128 * aerospacer_oracle::state::DEFAULT_MAX_CONFIDENCE_BPS as u128
+}
+
+/// A test collateral asset: faucet mint plus mock price feed, listed with the oracle
+#[account]
+pub struct FaucetAsset {
+    pub denom: String,
+    pub mint: Pubkey,
+    pub price_account: Pubkey,
+    pub creator: Pubkey,        // May move the mock price
+    pub max_drip: u64,          // Largest amount a single drip mints
+    pub decimals: u8,
+    pub expo: i32,              // Price exponent of the mock feed
+}
+
+impl FaucetAsset {
+    pub const LEN: usize = 4 + MAX_DENOM_LEN + 32 + 32 + 32 + 8 + 1 + 4; // denom + mint + price_account + creator + max_drip + decimals + expo
+    
+    pub fn seeds(denom: &str) -> [&[u8]; 2] {
+        [b"faucet_asset", denom.as_bytes()]
+    }
+}
+
+pub fn validate_denom(denom: &str) -> Result<()> {
+    require!(
+        !denom.is_empty()
+            && denom.len() <= MAX_DENOM_LEN
+            && denom.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-' | b'/')),
+        crate::error::AerospacerFaucetError::InvalidDenom
+    );
+    Ok(())
+}
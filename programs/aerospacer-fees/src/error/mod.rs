@@ -37,4 +37,43 @@ pub enum AerospacerFeesError {
     
     #[msg("Unauthorized token account - payer must own the payer_token_account")]
     UnauthorizedTokenAccount,
-} 
\ No newline at end of file
+    
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    
+    #[msg("Insufficient staked balance")]
+    InsufficientStake,
+    
+    #[msg("No fees to claim")]
+    NothingToClaim,
+    
+    #[msg("Public goods address not set")]
+    PublicGoodsAddressNotSet,
+    
+    #[msg("Invalid public goods account - owner must match configured public_goods_address")]
+    InvalidPublicGoodsAccount,
+    
+    #[msg("Invalid fee recipient - token account owner must be a configured fee recipient")]
+    InvalidFeeRecipient,
+    
+    #[msg("Too many fee recipients")]
+    TooManyFeeRecipients,
+    
+    #[msg("Fee recipient not found")]
+    FeeRecipientNotFound,
+    
+    #[msg("Invalid fee weight - must be greater than zero")]
+    InvalidFeeWeight,
+    
+    #[msg("Unauthorized fee caller - caller_authority must be an allowlisted fee caller")]
+    UnauthorizedFeeCaller,
+    
+    #[msg("Too many fee callers")]
+    TooManyFeeCallers,
+    
+    #[msg("Fee caller not found")]
+    FeeCallerNotFound,
+    
+    #[msg("Fee recipient has unclaimed fees - run claim_fees for it before changing its slot")]
+    UnclaimedFees,
+}
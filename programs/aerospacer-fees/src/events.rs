@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::{FeeDestination, FeeSource};
+
+#[event]
+pub struct FeeDistributed {
+    pub mint: Pubkey,
+    pub source: FeeSource,
+    pub destination: FeeDestination,
+    pub amount: u64,
+    pub epoch: u64,
+    pub epoch_amount: u64,         // Fees sent to this destination in this epoch so far
+    pub total_fees_collected: u64,
+}
+
+#[event]
+pub struct PublicGoodsRouted {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub source: Pubkey,            // Token account the share was paid from
+    pub recipient: Pubkey,         // Public-goods token account
+    pub total_routes: u64,
+}
+
+#[event]
+pub struct FeesClaimed {
+    pub mint: Pubkey,
+    pub fee_address: Pubkey,
+    pub recipient: Pubkey,         // Token account owned by fee_address
+    pub amount: u64,
+}
+
+#[event]
+pub struct CollateralFeeDistributed {
+    pub mint: Pubkey,
+    pub source: FeeSource,
+    pub amount: u64,
+    pub total_collected: u64,      // Collateral fees booked for this mint so far
+}
+
+#[event]
+pub struct FeeDonated {
+    pub mint: Pubkey,
+    pub donor: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::FeeStateAccount;
+use crate::error::AerospacerFeesError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AddFeeCallerParams {
+    pub caller: String, // Signer authority - for a program, its fee_caller PDA (utils::get_fee_caller_pda)
+}
+
+#[derive(Accounts)]
+#[instruction(params: AddFeeCallerParams)]
+pub struct AddFeeCaller<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerFeesError::Unauthorized
+    )]
+    pub state: Account<'info, FeeStateAccount>,
+}
+
+/// Allowlists a signer for distribute_fee and distribute_collateral_fee, so only the protocol
+/// can book fees into the revenue counters
+pub fn handler(ctx: Context<AddFeeCaller>, params: AddFeeCallerParams) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    
+    let caller = match Pubkey::try_from(params.caller.as_str()) {
+        Ok(pubkey) => pubkey,
+        Err(_) => return Err(AerospacerFeesError::InvalidAddress.into()),
+    };
+    require!(caller != Pubkey::default(), AerospacerFeesError::InvalidAddress);
+    require!(!state.is_fee_caller(&caller), AerospacerFeesError::InvalidAddress);
+    
+    let slot = state.fee_callers
+        .iter()
+        .position(|existing| *existing == Pubkey::default())
+        .ok_or(AerospacerFeesError::TooManyFeeCallers)?;
+    state.fee_callers[slot] = caller;
+    
+    msg!("Fee caller added successfully");
+    msg!("Caller: {} (slot {})", caller, slot);
+    
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::{FeeStateAccount, FeeRecipient};
+use crate::error::AerospacerFeesError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AddFeeRecipientParams {
+    pub address: String,
+    pub weight: u16, // Relative to the other recipients' weights
+}
+
+#[derive(Accounts)]
+#[instruction(params: AddFeeRecipientParams)]
+pub struct AddFeeRecipient<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerFeesError::Unauthorized
+    )]
+    pub state: Account<'info, FeeStateAccount>,
+}
+
+/// Adds a treasury-mode fee recipient in the first free slot.
+/// A freed slot keeps its unclaimed fees, which the new recipient inherits.
+pub fn handler(ctx: Context<AddFeeRecipient>, params: AddFeeRecipientParams) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    
+    let address = match Pubkey::try_from(params.address.as_str()) {
+        Ok(pubkey) => pubkey,
+        Err(_) => return Err(AerospacerFeesError::InvalidAddress.into()),
+    };
+    require!(address != Pubkey::default(), AerospacerFeesError::InvalidAddress);
+    require!(params.weight > 0, AerospacerFeesError::InvalidFeeWeight);
+    require!(
+        state.fee_recipient_slot(&address).is_none(),
+        AerospacerFeesError::InvalidAddress
+    );
+    
+    let slot = state.fee_recipients
+        .iter()
+        .position(|recipient| recipient.is_empty())
+        .ok_or(AerospacerFeesError::TooManyFeeRecipients)?;
+    state.fee_recipients[slot] = FeeRecipient {
+        address,
+        weight: params.weight,
+    };
+    state.sync_fee_addresses();
+    
+    msg!("Fee recipient added successfully");
+    msg!("Recipient: {} (slot {})", address, slot);
+    msg!("Weight: {} of {}", params.weight, state.total_fee_weight());
+    
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, transfer, Transfer};
+use crate::state::{FeeClaims, FeeStateAccount};
+use crate::error::AerospacerFeesError;
+use crate::events::FeesClaimed;
+
+#[derive(Accounts)]
+pub struct ClaimFees<'info> {
+    #[account(
+        seeds = [b"fee_state"],
+        bump
+    )]
+    pub state: Account<'info, FeeStateAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_claims", recipient_token_account.mint.as_ref()],
+        bump
+    )]
+    pub fee_claims: Account<'info, FeeClaims>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault", recipient_token_account.mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    /// Token account of the fee recipient being paid - its owner selects the recipient slot
+    #[account(mut)]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pays a fee recipient its claimable share of the fee vault.
+/// Anyone can crank a claim (e.g. for a fee address that is a PDA, like the protocol staking
+/// pool), but funds only ever reach token accounts owned by the configured fee recipients.
+pub fn handler(ctx: Context<ClaimFees>) -> Result<()> {
+    let fee_address = ctx.accounts.recipient_token_account.owner;
+    let claims = &mut ctx.accounts.fee_claims;
+    
+    let accrued = claims.accrue_fees(&ctx.accounts.state, ctx.accounts.fee_vault.amount)?;
+    if accrued > 0 {
+        msg!("Split {} of new fees between the fee recipients", accrued);
+    }
+    
+    let claimable = claims
+        .claimable_mut(&ctx.accounts.state, &fee_address)
+        .ok_or(AerospacerFeesError::InvalidFeeRecipient)?;
+    let claim_amount = *claimable;
+    require!(claim_amount > 0, AerospacerFeesError::NothingToClaim);
+    *claimable = 0;
+    
+    let mint = claims.mint;
+    let claims_seeds: &[&[u8]] = &[b"fee_claims", mint.as_ref(), &[ctx.bumps.fee_claims]];
+    let signer: &[&[&[u8]]] = &[claims_seeds];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.fee_vault.to_account_info(),
+            to: ctx.accounts.recipient_token_account.to_account_info(),
+            authority: claims.to_account_info(),
+        },
+        signer,
+    );
+    transfer(transfer_ctx, claim_amount)?;
+    
+    // Paid-out fees leave the vault, so they must leave the split balance as well
+    claims.last_fee_vault_balance = claims.last_fee_vault_balance.saturating_sub(claim_amount);
+    
+    msg!("Fees claimed successfully");
+    msg!("Fee address: {}", fee_address);
+    msg!("Claimed: {}", claim_amount);
+    
+    emit!(FeesClaimed {
+        mint,
+        fee_address,
+        recipient: ctx.accounts.recipient_token_account.key(),
+        amount: claim_amount,
+    });
+    
+    Ok(())
+}
+
+/// Sync every fee token's claims passed as (fee_claims, fee_vault) pairs and require `slots` to
+/// hold nothing unclaimed in any of them, before those slots are freed or re-pointed
+pub(crate) fn settle_fee_claims(state: &FeeStateAccount, accounts: &[AccountInfo], slots: &[usize]) -> Result<()> {
+    require!(
+        !accounts.is_empty() && accounts.len().is_multiple_of(2),
+        AerospacerFeesError::InvalidAddress
+    );
+    
+    for pair in accounts.chunks(2) {
+        let (claims_info, vault_info) = (&pair[0], &pair[1]);
+        require!(
+            claims_info.owner == &crate::ID && claims_info.is_writable && vault_info.owner == &anchor_spl::token::ID,
+            AerospacerFeesError::InvalidAddress
+        );
+        
+        let mut claims = FeeClaims::try_deserialize(&mut &claims_info.try_borrow_data()?[..])?;
+        let vault = TokenAccount::try_deserialize(&mut &vault_info.try_borrow_data()?[..])?;
+        let mint = claims.mint;
+        require!(
+            Pubkey::find_program_address(&[b"fee_claims", mint.as_ref()], &crate::ID).0 == *claims_info.key
+                && Pubkey::find_program_address(&[b"fee_vault", mint.as_ref()], &crate::ID).0 == *vault_info.key,
+            AerospacerFeesError::InvalidAddress
+        );
+        
+        claims.require_settled(state, vault.amount, slots)?;
+        let mut data = claims_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        claims.try_serialize(&mut writer)?;
+    }
+    
+    Ok(())
+}
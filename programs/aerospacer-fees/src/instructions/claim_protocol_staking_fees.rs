@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, transfer, Transfer};
+use crate::state::{ProtocolStakingPool, UserProtocolStake};
+use crate::error::AerospacerFeesError;
+
+#[derive(Accounts)]
+pub struct ClaimProtocolStakingFees<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol_staking"],
+        bump
+    )]
+    pub staking_pool: Account<'info, ProtocolStakingPool>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_protocol_stake", user.key().as_ref()],
+        bump,
+        constraint = user_stake.owner == user.key() @ AerospacerFeesError::Unauthorized
+    )]
+    pub user_stake: Account<'info, UserProtocolStake>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol_staking_fee_vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = user_fee_token_account.owner == user.key() @ AerospacerFeesError::UnauthorizedTokenAccount,
+        constraint = user_fee_token_account.mint == staking_pool.fee_mint @ AerospacerFeesError::InvalidTokenMint
+    )]
+    pub user_fee_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ClaimProtocolStakingFees>) -> Result<()> {
+    let pool = &mut ctx.accounts.staking_pool;
+    let user_stake = &mut ctx.accounts.user_stake;
+    
+    let accrued = pool.accrue_fees(ctx.accounts.fee_vault.amount)?;
+    if accrued > 0 {
+        msg!("Accrued {} aUSD of fees to protocol stakers", accrued);
+    }
+    
+    user_stake.settle(pool.fee_per_unit_staked)?;
+    
+    let claim_amount = user_stake.pending_fee_gain;
+    require!(claim_amount > 0, AerospacerFeesError::NothingToClaim);
+    require!(
+        ctx.accounts.fee_vault.amount >= claim_amount,
+        AerospacerFeesError::TransferFailed
+    );
+    
+    let pool_seeds: &[&[u8]] = &[b"protocol_staking", &[ctx.bumps.staking_pool]];
+    let signer: &[&[&[u8]]] = &[pool_seeds];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.fee_vault.to_account_info(),
+            to: ctx.accounts.user_fee_token_account.to_account_info(),
+            authority: pool.to_account_info(),
+        },
+        signer,
+    );
+    transfer(transfer_ctx, claim_amount)?;
+    
+    // Paid-out fees leave the vault, so they must leave the accounted balance as well
+    user_stake.pending_fee_gain = 0;
+    pool.last_fee_vault_balance = pool.last_fee_vault_balance.saturating_sub(claim_amount);
+    
+    msg!("Protocol staking fees claimed successfully");
+    msg!("User: {}", user_stake.owner);
+    msg!("Claimed: {} aUSD", claim_amount);
+    
+    Ok(())
+}
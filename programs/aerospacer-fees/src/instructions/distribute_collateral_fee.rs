@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, transfer, Transfer};
+use crate::state::{FeeClaims, FeeSource, FeeStateAccount};
+use crate::error::AerospacerFeesError;
+use crate::events::CollateralFeeDistributed;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DistributeCollateralFeeParams {
+    pub fee_amount: u64,
+    pub source: FeeSource,
+}
+
+#[derive(Accounts)]
+#[instruction(params: DistributeCollateralFeeParams)]
+pub struct DistributeCollateralFee<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"fee_state"],
+        bump
+    )]
+    pub state: Account<'info, FeeStateAccount>,
+    
+    /// Allowlisted caller - the protocol signs with its fee_caller PDA
+    #[account(
+        constraint = state.is_fee_caller(&caller_authority.key()) @ AerospacerFeesError::UnauthorizedFeeCaller
+    )]
+    pub caller_authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_claims", payer_token_account.mint.as_ref()],
+        bump
+    )]
+    pub fee_claims: Account<'info, FeeClaims>,
+    
+    /// Fee vault of the fee's mint - holds the fee recipients' shares until claim_fees
+    #[account(
+        mut,
+        seeds = [b"fee_vault", payer_token_account.mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Books a fee paid in a collateral token (liquidation penalties, PSM spreads) for the fee
+/// recipients. The whole fee always moves into the fee vault of its mint, whatever the stake
+/// mode: the stability pool only takes aUSD. Amounts are counted per mint on FeeClaims, never
+/// in FeeStateAccount's aUSD revenue counters. aUSD fees keep going through distribute_fee.
+pub fn handler(ctx: Context<DistributeCollateralFee>, params: DistributeCollateralFeeParams) -> Result<()> {
+    let fee_amount = params.fee_amount;
+    
+    if fee_amount == 0 {
+        return Err(AerospacerFeesError::NoFeesToDistribute.into());
+    }
+    
+    // CRITICAL: Validate payer owns the payer_token_account to prevent unauthorized draining
+    require!(
+        ctx.accounts.payer_token_account.owner == ctx.accounts.payer.key(),
+        AerospacerFeesError::UnauthorizedTokenAccount
+    );
+    
+    // Validate the vault and its claims belong to the fee's mint
+    let payer_mint = ctx.accounts.payer_token_account.mint;
+    require!(
+        ctx.accounts.fee_vault.mint == payer_mint && ctx.accounts.fee_claims.mint == payer_mint,
+        AerospacerFeesError::InvalidTokenMint
+    );
+    require!(
+        ctx.accounts.state.total_fee_weight() > 0,
+        AerospacerFeesError::InvalidFeeDistribution
+    );
+    
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.payer_token_account.to_account_info(),
+            to: ctx.accounts.fee_vault.to_account_info(),
+            authority: ctx.accounts.payer.to_account_info(),
+        },
+    );
+    transfer(transfer_ctx, fee_amount)?;
+    
+    let claims = &mut ctx.accounts.fee_claims;
+    claims.collateral_fees_collected = claims.collateral_fees_collected
+        .checked_add(fee_amount)
+        .ok_or(AerospacerFeesError::Overflow)?;
+    
+    msg!("Collateral fee moved to fee vault successfully");
+    msg!("Mint: {}", payer_mint);
+    msg!("Fee amount: {} ({:?})", fee_amount, params.source);
+    msg!("Collateral fees collected: {}", claims.collateral_fees_collected);
+    
+    emit!(CollateralFeeDistributed {
+        mint: payer_mint,
+        source: params.source,
+        amount: fee_amount,
+        total_collected: claims.collateral_fees_collected,
+    });
+    
+    Ok(())
+}
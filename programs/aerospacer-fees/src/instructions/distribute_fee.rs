@@ -1,11 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, transfer, Transfer};
-use crate::state::FeeStateAccount;
+use crate::state::{FeeDestination, FeeEpochStats, FeeSource, FeeStateAccount};
 use crate::error::AerospacerFeesError;
+use crate::events::FeeDistributed;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct DistributeFeeParams {
     pub fee_amount: u64,
+    pub source: FeeSource, // Counted in the matching per-source revenue counter
 }
 
 #[derive(Accounts)]
@@ -21,17 +23,33 @@ pub struct DistributeFee<'info> {
     )]
     pub state: Account<'info, FeeStateAccount>,
     
+    /// Allowlisted caller - the protocol signs with its fee_caller PDA
+    #[account(
+        constraint = state.is_fee_caller(&caller_authority.key()) @ AerospacerFeesError::UnauthorizedFeeCaller
+    )]
+    pub caller_authority: Signer<'info>,
+    
     #[account(mut)]
     pub payer_token_account: Account<'info, TokenAccount>,
     
     #[account(mut)]
     pub stability_pool_token_account: Account<'info, TokenAccount>,
     
-    #[account(mut)]
-    pub fee_address_1_token_account: Account<'info, TokenAccount>,
+    /// Fee vault of the fee's mint - holds the fee recipients' shares until claim_fees
+    #[account(
+        mut,
+        seeds = [b"fee_vault", payer_token_account.mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
     
-    #[account(mut)]
-    pub fee_address_2_token_account: Account<'info, TokenAccount>,
+    /// Rolling per-epoch stats of the fee's mint (created with initialize_fee_epoch_stats)
+    #[account(
+        mut,
+        seeds = [b"fee_epoch_stats", payer_token_account.mint.as_ref()],
+        bump
+    )]
+    pub fee_epoch_stats: Account<'info, FeeEpochStats>,
     
     pub token_program: Program<'info, Token>,
 }
@@ -50,30 +68,20 @@ pub fn handler(ctx: Context<DistributeFee>, params: DistributeFeeParams) -> Resu
         AerospacerFeesError::UnauthorizedTokenAccount
     );
     
-    // Validate all token accounts have the same mint
+    // Validate all token accounts have the same mint (the fee vault is derived from it)
     let payer_mint = ctx.accounts.payer_token_account.mint;
     require!(
         ctx.accounts.stability_pool_token_account.mint == payer_mint,
         AerospacerFeesError::InvalidTokenMint
     );
-    require!(
-        ctx.accounts.fee_address_1_token_account.mint == payer_mint,
-        AerospacerFeesError::InvalidTokenMint
-    );
-    require!(
-        ctx.accounts.fee_address_2_token_account.mint == payer_mint,
-        AerospacerFeesError::InvalidTokenMint
-    );
     
-    // Update total fees collected
-    state.total_fees_collected = state.total_fees_collected
-        .checked_add(fee_amount)
-        .ok_or(AerospacerFeesError::Overflow)?;
+    // Update total and per-source fees collected
+    state.record_fee(params.source, fee_amount)?;
     
-    msg!("Distributing fee amount: {}", fee_amount);
+    msg!("Distributing fee amount: {} ({:?})", fee_amount, params.source);
     msg!("Total fees collected: {}", state.total_fees_collected);
     
-    if state.is_stake_enabled {
+    let destination = if state.is_stake_enabled {
         // Validate stake contract address is set
         require!(
             state.stake_contract_address != Pubkey::default(),
@@ -100,66 +108,39 @@ pub fn handler(ctx: Context<DistributeFee>, params: DistributeFeeParams) -> Resu
         transfer(transfer_ctx, fee_amount)?;
         
         msg!("Fees distributed to stability pool successfully: {}", fee_amount);
+        FeeDestination::StabilityPool
     } else {
-        // Validate fee address token account owners using state values
-        // Note: ctx.accounts.fee_address_1_token_account.owner refers to the TOKEN ACCOUNT's owner field
-        // (the wallet that owns the tokens), not the account's program owner (which is always Token Program)
+        // Fee recipients pull their weighted shares with claim_fees, so a missing recipient token
+        // account can never fail the protocol transaction paying the fee
+        msg!("Moving fees to the fee vault for the fee recipients");
         
-        msg!("Validating fee address 1 token account owner");
-        msg!("Expected owner: {}", state.fee_address_1);
-        msg!("Actual owner: {}", ctx.accounts.fee_address_1_token_account.owner);
-        
-        require!(
-            ctx.accounts.fee_address_1_token_account.owner == state.fee_address_1,
-            AerospacerFeesError::InvalidFeeAddress1
-        );
-        
-        msg!("Validating fee address 2 token account owner");
-        msg!("Expected owner: {}", state.fee_address_2);
-        msg!("Actual owner: {}", ctx.accounts.fee_address_2_token_account.owner);
-        
-        require!(
-            ctx.accounts.fee_address_2_token_account.owner == state.fee_address_2,
-            AerospacerFeesError::InvalidFeeAddress2
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.payer_token_account.to_account_info(),
+                to: ctx.accounts.fee_vault.to_account_info(),
+                authority: ctx.accounts.payer.to_account_info(),
+            },
         );
         
-        let half_amount = fee_amount / 2;
-        let remaining_amount = fee_amount - half_amount;
-        
-        msg!("Distributing fees to fee addresses (50/50 split)");
-        msg!("Half amount: {}", half_amount);
-        msg!("Remaining amount: {}", remaining_amount);
-        
-        if half_amount > 0 {
-            let transfer_ctx_1 = CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.payer_token_account.to_account_info(),
-                    to: ctx.accounts.fee_address_1_token_account.to_account_info(),
-                    authority: ctx.accounts.payer.to_account_info(),
-                },
-            );
-            
-            transfer(transfer_ctx_1, half_amount)?;
-            msg!("Fees transferred to fee address 1: {}", half_amount);
-        }
-        
-        if remaining_amount > 0 {
-            let transfer_ctx_2 = CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.payer_token_account.to_account_info(),
-                    to: ctx.accounts.fee_address_2_token_account.to_account_info(),
-                    authority: ctx.accounts.payer.to_account_info(),
-                },
-            );
-            
-            transfer(transfer_ctx_2, remaining_amount)?;
-            msg!("Fees transferred to fee address 2: {}", remaining_amount);
-        }
+        transfer(transfer_ctx, fee_amount)?;
         
-        msg!("Fees distributed to fee addresses successfully");
-    }
+        msg!("Fees moved to fee vault successfully: {}", fee_amount);
+        FeeDestination::FeeVault
+    };
+    
+    let epoch = Clock::get()?.epoch;
+    let epoch_amount = ctx.accounts.fee_epoch_stats.record(epoch, destination, fee_amount)?;
+    
+    emit!(FeeDistributed {
+        mint: payer_mint,
+        source: params.source,
+        destination,
+        amount: fee_amount,
+        epoch,
+        epoch_amount,
+        total_fees_collected: state.total_fees_collected,
+    });
     
     Ok(())
 }
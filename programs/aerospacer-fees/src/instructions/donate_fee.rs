@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, transfer, Transfer};
+use crate::error::AerospacerFeesError;
+use crate::events::FeeDonated;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DonateFeeParams {
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: DonateFeeParams)]
+pub struct DonateFee<'info> {
+    pub donor: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = donor_token_account.owner == donor.key() @ AerospacerFeesError::UnauthorizedTokenAccount
+    )]
+    pub donor_token_account: Account<'info, TokenAccount>,
+    
+    /// Fee vault of the donated mint - split between the fee recipients by claim_fees
+    #[account(
+        mut,
+        seeds = [b"fee_vault", donor_token_account.mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Permissionless path for anyone to pay the fee recipients. Unlike distribute_fee the donation
+/// is never counted as protocol revenue, so it needs no allowlisted caller.
+pub fn handler(ctx: Context<DonateFee>, params: DonateFeeParams) -> Result<()> {
+    require!(params.amount > 0, AerospacerFeesError::NoFeesToDistribute);
+    
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.donor_token_account.to_account_info(),
+            to: ctx.accounts.fee_vault.to_account_info(),
+            authority: ctx.accounts.donor.to_account_info(),
+        },
+    );
+    transfer(transfer_ctx, params.amount)?;
+    
+    msg!("Fee donated successfully");
+    msg!("Donor: {}", ctx.accounts.donor.key());
+    msg!("Amount: {}", params.amount);
+    
+    emit!(FeeDonated {
+        mint: ctx.accounts.donor_token_account.mint,
+        donor: ctx.accounts.donor.key(),
+        amount: params.amount,
+    });
+    
+    Ok(())
+}
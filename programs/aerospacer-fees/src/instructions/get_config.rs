@@ -20,6 +20,12 @@ pub fn handler(ctx: Context<GetConfig>) -> Result<ConfigResponse> {
         fee_address_1: state.fee_address_1,
         fee_address_2: state.fee_address_2,
         total_fees_collected: state.total_fees_collected,
+        borrow_fees_collected: state.borrow_fees_collected,
+        redemption_fees_collected: state.redemption_fees_collected,
+        flash_mint_fees_collected: state.flash_mint_fees_collected,
+        liquidation_penalties_collected: state.liquidation_penalties_collected,
+        fee_recipients: state.fee_recipients.iter().filter(|recipient| !recipient.is_empty()).copied().collect(),
+        fee_callers: state.fee_callers.iter().filter(|caller| **caller != Pubkey::default()).copied().collect(),
     };
     
     msg!("Fee distributor config retrieved successfully");
@@ -28,6 +34,19 @@ pub fn handler(ctx: Context<GetConfig>) -> Result<ConfigResponse> {
     msg!("Fee Address 1: {}", response.fee_address_1);
     msg!("Fee Address 2: {}", response.fee_address_2);
     msg!("Total fees collected: {}", response.total_fees_collected);
+    msg!(
+        "By source - borrow: {}, redemption: {}, flash mint: {}, liquidation: {}",
+        response.borrow_fees_collected,
+        response.redemption_fees_collected,
+        response.flash_mint_fees_collected,
+        response.liquidation_penalties_collected
+    );
+    for recipient in response.fee_recipients.iter() {
+        msg!("Fee recipient: {} (weight {})", recipient.address, recipient.weight);
+    }
+    for caller in response.fee_callers.iter() {
+        msg!("Fee caller: {}", caller);
+    }
     
     Ok(response)
 } 
\ No newline at end of file
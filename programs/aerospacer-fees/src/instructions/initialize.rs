@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::{FeeStateAccount, DEFAULT_FEE_ADDR_1, DEFAULT_FEE_ADDR_2};
+use crate::state::{FeeStateAccount, FeeRecipient, DEFAULT_FEE_ADDR_1, DEFAULT_FEE_ADDR_2, DEFAULT_FEE_WEIGHT, MAX_FEE_CALLERS, MAX_FEE_RECIPIENTS};
 use std::str::FromStr;
 
 #[derive(Accounts)]
@@ -26,11 +26,26 @@ pub fn handler(ctx: Context<Initialize>) -> Result<()> {
     state.is_stake_enabled = false; // Default to disabled
     state.stake_contract_address = Pubkey::default(); // Will be set later
     
-    // Initialize with default fee addresses
-    state.fee_address_1 = Pubkey::from_str(DEFAULT_FEE_ADDR_1).unwrap();
-    state.fee_address_2 = Pubkey::from_str(DEFAULT_FEE_ADDR_2).unwrap();
+    // Initialize with the default fee addresses, split 50/50
+    state.fee_recipients = [FeeRecipient::default(); MAX_FEE_RECIPIENTS];
+    state.fee_recipients[0] = FeeRecipient {
+        address: Pubkey::from_str(DEFAULT_FEE_ADDR_1).unwrap(),
+        weight: DEFAULT_FEE_WEIGHT,
+    };
+    state.fee_recipients[1] = FeeRecipient {
+        address: Pubkey::from_str(DEFAULT_FEE_ADDR_2).unwrap(),
+        weight: DEFAULT_FEE_WEIGHT,
+    };
+    state.sync_fee_addresses();
+    
+    // No fee callers until the admin allowlists the protocol (add_fee_caller)
+    state.fee_callers = [Pubkey::default(); MAX_FEE_CALLERS];
     
     state.total_fees_collected = 0;
+    state.borrow_fees_collected = 0;
+    state.redemption_fees_collected = 0;
+    state.flash_mint_fees_collected = 0;
+    state.liquidation_penalties_collected = 0;
     
     msg!("Aerospacer Fee Distributor initialized successfully");
     msg!("Admin: {}", state.admin);
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::state::{FeeEpochEntry, FeeEpochStats, FeeStateAccount, FEE_EPOCH_HISTORY};
+use crate::error::AerospacerFeesError;
+
+#[derive(Accounts)]
+pub struct InitializeFeeEpochStats<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"fee_state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerFeesError::Unauthorized
+    )]
+    pub state: Account<'info, FeeStateAccount>,
+    
+    /// Fee token the stats are kept for (aUSD for the protocol)
+    pub fee_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + FeeEpochStats::LEN,
+        seeds = [b"fee_epoch_stats", fee_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_epoch_stats: Account<'info, FeeEpochStats>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the rolling per-epoch stats of a fee token. distribute_fee needs the stats of the
+/// fee's mint, like its fee vault.
+pub fn handler(ctx: Context<InitializeFeeEpochStats>) -> Result<()> {
+    let stats = &mut ctx.accounts.fee_epoch_stats;
+    
+    stats.mint = ctx.accounts.fee_mint.key();
+    stats.entries = [FeeEpochEntry::default(); FEE_EPOCH_HISTORY];
+    
+    msg!("Fee epoch stats initialized successfully");
+    msg!("Fee mint: {}", stats.mint);
+    msg!("Fee epoch stats: {}", stats.key());
+    
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::{FeeClaims, FeeStateAccount, MAX_FEE_RECIPIENTS};
+use crate::error::AerospacerFeesError;
+
+#[derive(Accounts)]
+pub struct InitializeFeeVault<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"fee_state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerFeesError::Unauthorized
+    )]
+    pub state: Account<'info, FeeStateAccount>,
+    
+    /// Fee token the vault holds (aUSD for the protocol)
+    pub fee_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + FeeClaims::LEN,
+        seeds = [b"fee_claims", fee_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_claims: Account<'info, FeeClaims>,
+    
+    #[account(
+        init,
+        payer = admin,
+        token::mint = fee_mint,
+        token::authority = fee_claims,
+        seeds = [b"fee_vault", fee_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Creates the vault that holds a fee token's treasury-mode fees until the fee recipients claim
+/// them. distribute_fee with stake disabled needs the vault of the fee's mint, and
+/// distribute_collateral_fee the vault of each collateral token.
+pub fn handler(ctx: Context<InitializeFeeVault>) -> Result<()> {
+    let claims = &mut ctx.accounts.fee_claims;
+    
+    claims.mint = ctx.accounts.fee_mint.key();
+    claims.claimable = [0; MAX_FEE_RECIPIENTS];
+    claims.last_fee_vault_balance = 0;
+    claims.collateral_fees_collected = 0;
+    
+    msg!("Fee vault initialized successfully");
+    msg!("Fee mint: {}", claims.mint);
+    msg!("Fee vault: {}", ctx.accounts.fee_vault.key());
+    
+    Ok(())
+}
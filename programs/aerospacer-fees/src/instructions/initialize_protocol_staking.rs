@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::{FeeStateAccount, ProtocolStakingPool};
+use crate::error::AerospacerFeesError;
+
+#[derive(Accounts)]
+pub struct InitializeProtocolStaking<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"fee_state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerFeesError::Unauthorized
+    )]
+    pub state: Account<'info, FeeStateAccount>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ProtocolStakingPool::LEN,
+        seeds = [b"protocol_staking"],
+        bump
+    )]
+    pub staking_pool: Account<'info, ProtocolStakingPool>,
+    
+    /// Governance token that is staked
+    pub stake_mint: Account<'info, Mint>,
+    
+    /// Fee token paid out to stakers (aUSD)
+    pub fee_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = admin,
+        token::mint = stake_mint,
+        token::authority = staking_pool,
+        seeds = [b"protocol_staking_vault"],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = admin,
+        token::mint = fee_mint,
+        token::authority = staking_pool,
+        seeds = [b"protocol_staking_fee_vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(ctx: Context<InitializeProtocolStaking>) -> Result<()> {
+    require!(
+        ctx.accounts.stake_mint.key() != ctx.accounts.fee_mint.key(),
+        AerospacerFeesError::InvalidTokenMint
+    );
+    
+    let pool = &mut ctx.accounts.staking_pool;
+    
+    pool.stake_mint = ctx.accounts.stake_mint.key();
+    pool.fee_mint = ctx.accounts.fee_mint.key();
+    pool.total_staked = 0;
+    pool.fee_per_unit_staked = 0;
+    pool.last_fee_vault_balance = 0;
+    pool.total_fees_accrued = 0;
+    
+    msg!("Protocol token staking initialized successfully");
+    msg!("Stake mint: {}", pool.stake_mint);
+    msg!("Fee mint: {}", pool.fee_mint);
+    msg!("Fee vault: {}", ctx.accounts.fee_vault.key());
+    msg!("Add {} as a fee recipient to route a fee share to stakers", pool.key());
+    
+    Ok(())
+}
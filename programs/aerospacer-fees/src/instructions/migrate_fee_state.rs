@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::{FeeStateAccount, FeeRecipient, DEFAULT_FEE_WEIGHT};
+use crate::error::AerospacerFeesError;
+
+#[derive(Accounts)]
+pub struct MigrateFeeState<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    /// CHECK: Fee state that may still have the legacy size - owner, discriminator and admin are checked in the handler
+    #[account(
+        mut,
+        seeds = [b"fee_state"],
+        bump
+    )]
+    pub state: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows a fee state created before the per-source revenue counters, the weighted fee
+/// recipients or the fee caller allowlist to the current size. The new counters start at zero;
+/// total_fees_collected keeps the pre-split history. The recipients start as the configured fee
+/// address pair at 1:1. The allowlist starts empty: run add_fee_caller for the protocol before
+/// it pays fees again.
+pub fn handler(ctx: Context<MigrateFeeState>) -> Result<()> {
+    let state_info = ctx.accounts.state.to_account_info();
+    let target_len = 8 + FeeStateAccount::LEN;
+    
+    require!(
+        state_info.owner == &crate::ID,
+        AerospacerFeesError::Unauthorized
+    );
+    {
+        let data = state_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 + FeeStateAccount::LEGACY_LEN && data[..8] == *FeeStateAccount::DISCRIMINATOR,
+            AerospacerFeesError::InvalidAddress
+        );
+        // admin is the first field
+        require!(
+            data[8..40] == ctx.accounts.admin.key().to_bytes(),
+            AerospacerFeesError::Unauthorized
+        );
+    }
+    
+    if state_info.data_len() >= target_len {
+        msg!("Fee state already migrated");
+        return Ok(());
+    }
+    
+    let rent = Rent::get()?.minimum_balance(target_len);
+    let top_up = rent.saturating_sub(state_info.lamports());
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: state_info.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    
+    // New bytes are zeroed, so the per-source counters start at 0 and the recipient and caller
+    // slots empty
+    state_info.resize(target_len)?;
+    
+    let mut data = state_info.try_borrow_mut_data()?;
+    let mut state = FeeStateAccount::try_deserialize(&mut &data[..])?;
+    if state.total_fee_weight() == 0 {
+        state.fee_recipients[0] = FeeRecipient { address: state.fee_address_1, weight: DEFAULT_FEE_WEIGHT };
+        state.fee_recipients[1] = FeeRecipient { address: state.fee_address_2, weight: DEFAULT_FEE_WEIGHT };
+        state.try_serialize(&mut &mut data[..])?;
+        msg!("Fee recipients seeded from the fee address pair");
+    }
+    
+    msg!("Fee state migrated to {} bytes", target_len);
+    
+    Ok(())
+}
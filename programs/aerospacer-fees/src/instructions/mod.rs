@@ -4,6 +4,23 @@ pub mod set_stake_contract_address;
 pub mod set_fee_addresses;
 pub mod distribute_fee;
 pub mod get_config;
+pub mod initialize_protocol_staking;
+pub mod stake_protocol_token;
+pub mod unstake_protocol_token;
+pub mod claim_protocol_staking_fees;
+pub mod set_public_goods_address;
+pub mod route_public_goods;
+pub mod migrate_fee_state;
+pub mod initialize_fee_vault;
+pub mod claim_fees;
+pub mod add_fee_recipient;
+pub mod remove_fee_recipient;
+pub mod set_fee_recipient_weight;
+pub mod distribute_collateral_fee;
+pub mod add_fee_caller;
+pub mod remove_fee_caller;
+pub mod donate_fee;
+pub mod initialize_fee_epoch_stats;
 
 #[allow(ambiguous_glob_reexports)]
 pub use initialize::*;
@@ -16,4 +33,38 @@ pub use set_fee_addresses::*;
 #[allow(ambiguous_glob_reexports)]
 pub use distribute_fee::*;
 #[allow(ambiguous_glob_reexports)]
-pub use get_config::*; 
\ No newline at end of file
+pub use get_config::*;
+#[allow(ambiguous_glob_reexports)]
+pub use initialize_protocol_staking::*;
+#[allow(ambiguous_glob_reexports)]
+pub use stake_protocol_token::*;
+#[allow(ambiguous_glob_reexports)]
+pub use unstake_protocol_token::*;
+#[allow(ambiguous_glob_reexports)]
+pub use claim_protocol_staking_fees::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_public_goods_address::*;
+#[allow(ambiguous_glob_reexports)]
+pub use route_public_goods::*;
+#[allow(ambiguous_glob_reexports)]
+pub use migrate_fee_state::*;
+#[allow(ambiguous_glob_reexports)]
+pub use initialize_fee_vault::*;
+#[allow(ambiguous_glob_reexports)]
+pub use claim_fees::*;
+#[allow(ambiguous_glob_reexports)]
+pub use add_fee_recipient::*;
+#[allow(ambiguous_glob_reexports)]
+pub use remove_fee_recipient::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_fee_recipient_weight::*;
+#[allow(ambiguous_glob_reexports)]
+pub use distribute_collateral_fee::*;
+#[allow(ambiguous_glob_reexports)]
+pub use add_fee_caller::*;
+#[allow(ambiguous_glob_reexports)]
+pub use remove_fee_caller::*;
+#[allow(ambiguous_glob_reexports)]
+pub use donate_fee::*;
+#[allow(ambiguous_glob_reexports)]
+pub use initialize_fee_epoch_stats::*;
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::FeeStateAccount;
+use crate::error::AerospacerFeesError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RemoveFeeCallerParams {
+    pub caller: String,
+}
+
+#[derive(Accounts)]
+#[instruction(params: RemoveFeeCallerParams)]
+pub struct RemoveFeeCaller<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerFeesError::Unauthorized
+    )]
+    pub state: Account<'info, FeeStateAccount>,
+}
+
+pub fn handler(ctx: Context<RemoveFeeCaller>, params: RemoveFeeCallerParams) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    
+    let caller = match Pubkey::try_from(params.caller.as_str()) {
+        Ok(pubkey) => pubkey,
+        Err(_) => return Err(AerospacerFeesError::InvalidAddress.into()),
+    };
+    let slot = state.fee_callers
+        .iter()
+        .position(|existing| *existing != Pubkey::default() && *existing == caller)
+        .ok_or(AerospacerFeesError::FeeCallerNotFound)?;
+    state.fee_callers[slot] = Pubkey::default();
+    
+    msg!("Fee caller removed successfully");
+    msg!("Caller: {} (slot {})", caller, slot);
+    
+    Ok(())
+}
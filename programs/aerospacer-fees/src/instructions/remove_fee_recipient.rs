@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::{FeeStateAccount, FeeRecipient};
+use crate::error::AerospacerFeesError;
+use crate::instructions::claim_fees::settle_fee_claims;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RemoveFeeRecipientParams {
+    pub address: String,
+}
+
+#[derive(Accounts)]
+#[instruction(params: RemoveFeeRecipientParams)]
+pub struct RemoveFeeRecipient<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerFeesError::Unauthorized
+    )]
+    pub state: Account<'info, FeeStateAccount>,
+    
+    // remaining_accounts: (fee_claims, fee_vault) of every fee token, both writable claims
+}
+
+/// Frees a fee recipient's slot. Fees are synced first, and the removal fails while the slot
+/// holds unclaimed fees of any fee token - crank claim_fees for it in the same transaction.
+/// The last recipient cannot be removed.
+pub fn handler(ctx: Context<RemoveFeeRecipient>, params: RemoveFeeRecipientParams) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    
+    let address = match Pubkey::try_from(params.address.as_str()) {
+        Ok(pubkey) => pubkey,
+        Err(_) => return Err(AerospacerFeesError::InvalidAddress.into()),
+    };
+    let slot = state.fee_recipient_slot(&address)
+        .ok_or(AerospacerFeesError::FeeRecipientNotFound)?;
+    settle_fee_claims(state, ctx.remaining_accounts, &[slot])?;
+    
+    state.fee_recipients[slot] = FeeRecipient::default();
+    require!(
+        state.total_fee_weight() > 0,
+        AerospacerFeesError::InvalidFeeDistribution
+    );
+    state.sync_fee_addresses();
+    
+    msg!("Fee recipient removed successfully");
+    msg!("Recipient: {} (slot {})", address, slot);
+    
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, transfer, Transfer};
+use crate::state::PublicGoodsConfig;
+use crate::error::AerospacerFeesError;
+use crate::events::PublicGoodsRouted;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RoutePublicGoodsParams {
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: RoutePublicGoodsParams)]
+pub struct RoutePublicGoods<'info> {
+    /// Owner of source_token_account (the protocol signs with its collateral vault PDA)
+    pub source_authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"public_goods_config"],
+        bump
+    )]
+    pub public_goods_config: Account<'info, PublicGoodsConfig>,
+    
+    #[account(
+        mut,
+        constraint = source_token_account.owner == source_authority.key() @ AerospacerFeesError::UnauthorizedTokenAccount
+    )]
+    pub source_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = public_goods_token_account.mint == source_token_account.mint @ AerospacerFeesError::InvalidTokenMint
+    )]
+    pub public_goods_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pays the protocol's public-goods share of liquidation penalties to the configured recipient.
+/// Any token is accepted; the recipient check is the only policy enforced here.
+pub fn handler(ctx: Context<RoutePublicGoods>, params: RoutePublicGoodsParams) -> Result<()> {
+    let config = &mut ctx.accounts.public_goods_config;
+    
+    require!(params.amount > 0, AerospacerFeesError::InvalidAmount);
+    require!(
+        config.public_goods_address != Pubkey::default(),
+        AerospacerFeesError::PublicGoodsAddressNotSet
+    );
+    require!(
+        ctx.accounts.public_goods_token_account.owner == config.public_goods_address,
+        AerospacerFeesError::InvalidPublicGoodsAccount
+    );
+    
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.source_token_account.to_account_info(),
+            to: ctx.accounts.public_goods_token_account.to_account_info(),
+            authority: ctx.accounts.source_authority.to_account_info(),
+        },
+    );
+    transfer(transfer_ctx, params.amount)?;
+    
+    config.total_routes = config.total_routes
+        .checked_add(1)
+        .ok_or(AerospacerFeesError::Overflow)?;
+    
+    msg!("Public goods share routed successfully");
+    msg!("Mint: {}", ctx.accounts.source_token_account.mint);
+    msg!("Amount: {}", params.amount);
+    msg!("Recipient: {}", ctx.accounts.public_goods_token_account.key());
+    
+    emit!(PublicGoodsRouted {
+        mint: ctx.accounts.source_token_account.mint,
+        amount: params.amount,
+        source: ctx.accounts.source_token_account.key(),
+        recipient: ctx.accounts.public_goods_token_account.key(),
+        total_routes: config.total_routes,
+    });
+    
+    Ok(())
+}
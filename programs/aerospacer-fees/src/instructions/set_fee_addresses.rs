@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::state::FeeStateAccount;
+use crate::state::{FeeStateAccount, DEFAULT_FEE_WEIGHT};
 use crate::error::AerospacerFeesError;
+use crate::instructions::claim_fees::settle_fee_claims;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct SetFeeAddressesParams {
@@ -21,8 +22,12 @@ pub struct SetFeeAddresses<'info> {
         constraint = state.admin == admin.key() @ AerospacerFeesError::Unauthorized
     )]
     pub state: Account<'info, FeeStateAccount>,
+    
+    // remaining_accounts: (fee_claims, fee_vault) of every fee token, both writable claims
 }
 
+/// Points the first two fee recipient slots at the given addresses, keeping their weights.
+/// Fees are synced first, and a slot that changes hands must hold no unclaimed fees.
 pub fn handler(ctx: Context<SetFeeAddresses>, params: SetFeeAddressesParams) -> Result<()> {
     let state = &mut ctx.accounts.state;
     
@@ -44,9 +49,27 @@ pub fn handler(ctx: Context<SetFeeAddresses>, params: SetFeeAddressesParams) ->
         AerospacerFeesError::InvalidAddress
     );
     
-    // Update fee addresses
-    state.fee_address_1 = fee_address_1;
-    state.fee_address_2 = fee_address_2;
+    // A re-pointed slot would hand its unclaimed fees to the new address
+    let changed_slots: Vec<usize> = [(0, fee_address_1), (1, fee_address_2)]
+        .iter()
+        .filter(|(slot, fee_address)| !state.fee_recipients[*slot].is_empty() && state.fee_recipients[*slot].address != *fee_address)
+        .map(|(slot, _)| *slot)
+        .collect();
+    settle_fee_claims(state, ctx.remaining_accounts, &changed_slots)?;
+    
+    // Neither may already hold another recipient slot
+    for (slot, fee_address) in [(0, fee_address_1), (1, fee_address_2)] {
+        require!(
+            !matches!(state.fee_recipient_slot(&fee_address), Some(existing) if existing > 1),
+            AerospacerFeesError::InvalidAddress
+        );
+        let recipient = &mut state.fee_recipients[slot];
+        recipient.address = fee_address;
+        if recipient.is_empty() {
+            recipient.weight = DEFAULT_FEE_WEIGHT;
+        }
+    }
+    state.sync_fee_addresses();
     
     msg!("Fee addresses updated successfully");
     msg!("New Fee Address 1: {}", state.fee_address_1);
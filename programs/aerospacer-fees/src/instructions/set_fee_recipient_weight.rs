@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::FeeStateAccount;
+use crate::error::AerospacerFeesError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetFeeRecipientWeightParams {
+    pub address: String,
+    pub weight: u16, // Remove the recipient instead of setting 0
+}
+
+#[derive(Accounts)]
+#[instruction(params: SetFeeRecipientWeightParams)]
+pub struct SetFeeRecipientWeight<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerFeesError::Unauthorized
+    )]
+    pub state: Account<'info, FeeStateAccount>,
+}
+
+/// Changes a fee recipient's weight. Fees not yet synced by claim_fees are split with the new
+/// weights, so crank claims first to settle them under the old ones.
+pub fn handler(ctx: Context<SetFeeRecipientWeight>, params: SetFeeRecipientWeightParams) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    
+    let address = match Pubkey::try_from(params.address.as_str()) {
+        Ok(pubkey) => pubkey,
+        Err(_) => return Err(AerospacerFeesError::InvalidAddress.into()),
+    };
+    require!(params.weight > 0, AerospacerFeesError::InvalidFeeWeight);
+    let slot = state.fee_recipient_slot(&address)
+        .ok_or(AerospacerFeesError::FeeRecipientNotFound)?;
+    
+    state.fee_recipients[slot].weight = params.weight;
+    
+    msg!("Fee recipient weight updated successfully");
+    msg!("Recipient: {} (slot {})", address, slot);
+    msg!("Weight: {} of {}", params.weight, state.total_fee_weight());
+    
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::{FeeStateAccount, PublicGoodsConfig};
+use crate::error::AerospacerFeesError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetPublicGoodsAddressParams {
+    pub public_goods_address: String,
+}
+
+#[derive(Accounts)]
+#[instruction(params: SetPublicGoodsAddressParams)]
+pub struct SetPublicGoodsAddress<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"fee_state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerFeesError::Unauthorized
+    )]
+    pub state: Account<'info, FeeStateAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + PublicGoodsConfig::LEN,
+        seeds = [b"public_goods_config"],
+        bump
+    )]
+    pub public_goods_config: Account<'info, PublicGoodsConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetPublicGoodsAddress>, params: SetPublicGoodsAddressParams) -> Result<()> {
+    let public_goods_address = match Pubkey::try_from(params.public_goods_address.as_str()) {
+        Ok(pubkey) => pubkey,
+        Err(_) => return Err(AerospacerFeesError::InvalidAddress.into()),
+    };
+    
+    // Clearing the address is done by the protocol setting its share to 0, not by a default key here
+    require!(
+        public_goods_address != Pubkey::default(),
+        AerospacerFeesError::InvalidAddress
+    );
+    
+    let config = &mut ctx.accounts.public_goods_config;
+    config.public_goods_address = public_goods_address;
+    
+    msg!("Public goods address updated successfully");
+    msg!("New Public Goods Address: {}", config.public_goods_address);
+    
+    Ok(())
+}
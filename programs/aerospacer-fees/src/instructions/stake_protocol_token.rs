@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, transfer, Transfer};
+use crate::state::{ProtocolStakingPool, UserProtocolStake};
+use crate::error::AerospacerFeesError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StakeProtocolTokenParams {
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: StakeProtocolTokenParams)]
+pub struct StakeProtocolToken<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol_staking"],
+        bump
+    )]
+    pub staking_pool: Account<'info, ProtocolStakingPool>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserProtocolStake::LEN,
+        seeds = [b"user_protocol_stake", user.key().as_ref()],
+        bump
+    )]
+    pub user_stake: Account<'info, UserProtocolStake>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ AerospacerFeesError::UnauthorizedTokenAccount,
+        constraint = user_token_account.mint == staking_pool.stake_mint @ AerospacerFeesError::InvalidTokenMint
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol_staking_vault"],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"protocol_staking_fee_vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<StakeProtocolToken>, params: StakeProtocolTokenParams) -> Result<()> {
+    require!(params.amount > 0, AerospacerFeesError::InvalidAmount);
+    
+    let pool = &mut ctx.accounts.staking_pool;
+    let user_stake = &mut ctx.accounts.user_stake;
+    
+    // Credit fees received since the last sync to the existing stakers before the supply changes
+    let accrued = pool.accrue_fees(ctx.accounts.fee_vault.amount)?;
+    if accrued > 0 {
+        msg!("Accrued {} aUSD of fees to protocol stakers", accrued);
+    }
+    
+    if user_stake.owner == Pubkey::default() {
+        user_stake.owner = ctx.accounts.user.key();
+        user_stake.amount = 0;
+        user_stake.pending_fee_gain = 0;
+        user_stake.fee_per_unit_snapshot = pool.fee_per_unit_staked;
+    }
+    require!(
+        user_stake.owner == ctx.accounts.user.key(),
+        AerospacerFeesError::Unauthorized
+    );
+    
+    let gain = user_stake.settle(pool.fee_per_unit_staked)?;
+    
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    transfer(transfer_ctx, params.amount)?;
+    
+    user_stake.amount = user_stake.amount
+        .checked_add(params.amount)
+        .ok_or(AerospacerFeesError::Overflow)?;
+    pool.total_staked = pool.total_staked
+        .checked_add(params.amount)
+        .ok_or(AerospacerFeesError::Overflow)?;
+    
+    msg!("Protocol tokens staked successfully");
+    msg!("User: {}", user_stake.owner);
+    msg!("Amount: {}", params.amount);
+    msg!("User stake: {}", user_stake.amount);
+    msg!("Settled fee gain: {} (pending: {})", gain, user_stake.pending_fee_gain);
+    msg!("Total staked: {}", pool.total_staked);
+    
+    Ok(())
+}
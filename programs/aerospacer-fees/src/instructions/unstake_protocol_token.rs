@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, transfer, Transfer};
+use crate::state::{ProtocolStakingPool, UserProtocolStake};
+use crate::error::AerospacerFeesError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UnstakeProtocolTokenParams {
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: UnstakeProtocolTokenParams)]
+pub struct UnstakeProtocolToken<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol_staking"],
+        bump
+    )]
+    pub staking_pool: Account<'info, ProtocolStakingPool>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_protocol_stake", user.key().as_ref()],
+        bump,
+        constraint = user_stake.owner == user.key() @ AerospacerFeesError::Unauthorized
+    )]
+    pub user_stake: Account<'info, UserProtocolStake>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ AerospacerFeesError::UnauthorizedTokenAccount,
+        constraint = user_token_account.mint == staking_pool.stake_mint @ AerospacerFeesError::InvalidTokenMint
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol_staking_vault"],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"protocol_staking_fee_vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Unstaking settles the fee gain into pending_fee_gain; it is paid out by claim_protocol_staking_fees
+pub fn handler(ctx: Context<UnstakeProtocolToken>, params: UnstakeProtocolTokenParams) -> Result<()> {
+    require!(params.amount > 0, AerospacerFeesError::InvalidAmount);
+    require!(
+        ctx.accounts.user_stake.amount >= params.amount,
+        AerospacerFeesError::InsufficientStake
+    );
+    
+    let pool = &mut ctx.accounts.staking_pool;
+    let user_stake = &mut ctx.accounts.user_stake;
+    
+    let accrued = pool.accrue_fees(ctx.accounts.fee_vault.amount)?;
+    if accrued > 0 {
+        msg!("Accrued {} aUSD of fees to protocol stakers", accrued);
+    }
+    
+    let gain = user_stake.settle(pool.fee_per_unit_staked)?;
+    
+    user_stake.amount -= params.amount;
+    pool.total_staked = pool.total_staked.saturating_sub(params.amount);
+    
+    let pool_seeds: &[&[u8]] = &[b"protocol_staking", &[ctx.bumps.staking_pool]];
+    let signer: &[&[&[u8]]] = &[pool_seeds];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: pool.to_account_info(),
+        },
+        signer,
+    );
+    transfer(transfer_ctx, params.amount)?;
+    
+    msg!("Protocol tokens unstaked successfully");
+    msg!("User: {}", user_stake.owner);
+    msg!("Amount: {}", params.amount);
+    msg!("User stake: {}", user_stake.amount);
+    msg!("Settled fee gain: {} (pending: {})", gain, user_stake.pending_fee_gain);
+    msg!("Total staked: {}", pool.total_staked);
+    
+    Ok(())
+}
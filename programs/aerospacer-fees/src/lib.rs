@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
@@ -8,6 +9,17 @@ use instructions::*;
 use crate::state::{ConfigResponse, FeeStateAccount};
 use crate::instructions::distribute_fee::DistributeFeeParams;
 use crate::instructions::set_fee_addresses::SetFeeAddressesParams;
+use crate::instructions::stake_protocol_token::StakeProtocolTokenParams;
+use crate::instructions::unstake_protocol_token::UnstakeProtocolTokenParams;
+use crate::instructions::set_public_goods_address::SetPublicGoodsAddressParams;
+use crate::instructions::route_public_goods::RoutePublicGoodsParams;
+use crate::instructions::add_fee_recipient::AddFeeRecipientParams;
+use crate::instructions::remove_fee_recipient::RemoveFeeRecipientParams;
+use crate::instructions::set_fee_recipient_weight::SetFeeRecipientWeightParams;
+use crate::instructions::distribute_collateral_fee::DistributeCollateralFeeParams;
+use crate::instructions::add_fee_caller::AddFeeCallerParams;
+use crate::instructions::remove_fee_caller::RemoveFeeCallerParams;
+use crate::instructions::donate_fee::DonateFeeParams;
 
 declare_id!("FyBGDrxVAdTnwKeXFrhQR1UyyJhqbfQmZrXWqZuhYkAj");
 
@@ -38,6 +50,74 @@ pub mod aerospacer_fees {
     pub fn get_config(ctx: Context<GetConfig>) -> Result<ConfigResponse> {
         instructions::get_config::handler(ctx)
     }
+
+    pub fn initialize_protocol_staking(ctx: Context<InitializeProtocolStaking>) -> Result<()> {
+        instructions::initialize_protocol_staking::handler(ctx)
+    }
+
+    pub fn stake_protocol_token(ctx: Context<StakeProtocolToken>, params: StakeProtocolTokenParams) -> Result<()> {
+        instructions::stake_protocol_token::handler(ctx, params)
+    }
+
+    pub fn unstake_protocol_token(ctx: Context<UnstakeProtocolToken>, params: UnstakeProtocolTokenParams) -> Result<()> {
+        instructions::unstake_protocol_token::handler(ctx, params)
+    }
+
+    pub fn claim_protocol_staking_fees(ctx: Context<ClaimProtocolStakingFees>) -> Result<()> {
+        instructions::claim_protocol_staking_fees::handler(ctx)
+    }
+
+    pub fn set_public_goods_address(ctx: Context<SetPublicGoodsAddress>, params: SetPublicGoodsAddressParams) -> Result<()> {
+        instructions::set_public_goods_address::handler(ctx, params)
+    }
+
+    pub fn route_public_goods(ctx: Context<RoutePublicGoods>, params: RoutePublicGoodsParams) -> Result<()> {
+        instructions::route_public_goods::handler(ctx, params)
+    }
+
+    pub fn migrate_fee_state(ctx: Context<MigrateFeeState>) -> Result<()> {
+        instructions::migrate_fee_state::handler(ctx)
+    }
+
+    pub fn initialize_fee_vault(ctx: Context<InitializeFeeVault>) -> Result<()> {
+        instructions::initialize_fee_vault::handler(ctx)
+    }
+
+    pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
+        instructions::claim_fees::handler(ctx)
+    }
+
+    pub fn add_fee_recipient(ctx: Context<AddFeeRecipient>, params: AddFeeRecipientParams) -> Result<()> {
+        instructions::add_fee_recipient::handler(ctx, params)
+    }
+
+    pub fn remove_fee_recipient(ctx: Context<RemoveFeeRecipient>, params: RemoveFeeRecipientParams) -> Result<()> {
+        instructions::remove_fee_recipient::handler(ctx, params)
+    }
+
+    pub fn set_fee_recipient_weight(ctx: Context<SetFeeRecipientWeight>, params: SetFeeRecipientWeightParams) -> Result<()> {
+        instructions::set_fee_recipient_weight::handler(ctx, params)
+    }
+
+    pub fn distribute_collateral_fee(ctx: Context<DistributeCollateralFee>, params: DistributeCollateralFeeParams) -> Result<()> {
+        instructions::distribute_collateral_fee::handler(ctx, params)
+    }
+
+    pub fn add_fee_caller(ctx: Context<AddFeeCaller>, params: AddFeeCallerParams) -> Result<()> {
+        instructions::add_fee_caller::handler(ctx, params)
+    }
+
+    pub fn remove_fee_caller(ctx: Context<RemoveFeeCaller>, params: RemoveFeeCallerParams) -> Result<()> {
+        instructions::remove_fee_caller::handler(ctx, params)
+    }
+
+    pub fn donate_fee(ctx: Context<DonateFee>, params: DonateFeeParams) -> Result<()> {
+        instructions::donate_fee::handler(ctx, params)
+    }
+
+    pub fn initialize_fee_epoch_stats(ctx: Context<InitializeFeeEpochStats>) -> Result<()> {
+        instructions::initialize_fee_epoch_stats::handler(ctx)
+    }
 }
 
 /// Helper functions for PDA derivation
@@ -53,4 +133,34 @@ pub mod utils {
     pub fn get_fee_state_seeds() -> [&'static [u8]; 1] {
         FeeStateAccount::seeds()
     }
+    
+    /// Get the protocol staking pool PDA (the fee address that routes fees to stakers)
+    pub fn get_protocol_staking_pool_pda() -> (Pubkey, u8) {
+        crate::state::ProtocolStakingPool::get_pda(&crate::ID)
+    }
+    
+    /// Get the public goods config PDA
+    pub fn get_public_goods_config_pda() -> (Pubkey, u8) {
+        crate::state::PublicGoodsConfig::get_pda(&crate::ID)
+    }
+    
+    /// Get the fee vault PDA of a fee token (treasury-mode fees awaiting claim_fees)
+    pub fn get_fee_vault_pda(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"fee_vault", mint.as_ref()], &crate::ID)
+    }
+    
+    /// Get the fee claims PDA of a fee token
+    pub fn get_fee_claims_pda(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"fee_claims", mint.as_ref()], &crate::ID)
+    }
+    
+    /// Get the rolling per-epoch fee stats PDA of a fee token
+    pub fn get_fee_epoch_stats_pda(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"fee_epoch_stats", mint.as_ref()], &crate::ID)
+    }
+    
+    /// Get the PDA `program_id` signs distribute_fee CPIs with (allowlist it with add_fee_caller)
+    pub fn get_fee_caller_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[crate::state::FEE_CALLER_SEED], program_id)
+    }
 } 
\ No newline at end of file
@@ -14,10 +14,23 @@ pub struct FeeStateAccount {
     pub fee_address_1: Pubkey,            // 32 bytes - NEW
     pub fee_address_2: Pubkey,            // 32 bytes - NEW
     pub total_fees_collected: u64,        // 8 bytes
+    // Revenue split by FeeSource (sums to total_fees_collected for fees distributed since the split)
+    pub borrow_fees_collected: u64,       // 8 bytes
+    pub redemption_fees_collected: u64,   // 8 bytes
+    pub flash_mint_fees_collected: u64,   // 8 bytes
+    pub liquidation_penalties_collected: u64, // 8 bytes
+    // Treasury-mode split: each fee token's vault is shared by weight (empty slots have weight 0).
+    // fee_address_1/2 mirror the first two slots.
+    pub fee_recipients: [FeeRecipient; MAX_FEE_RECIPIENTS], // 34 bytes each
+    // Signers allowed to call distribute_fee / distribute_collateral_fee (empty slots are default).
+    // Programs allowlist their FEE_CALLER_SEED PDA and sign the CPI with it.
+    pub fee_callers: [Pubkey; MAX_FEE_CALLERS], // 32 bytes each
 }
 
 impl FeeStateAccount {
-    pub const LEN: usize = 32 + 1 + 32 + 32 + 32 + 8; // Updated to include fee addresses
+    pub const LEN: usize = 32 + 1 + 32 + 32 + 32 + 8 + 4 * 8 + FeeRecipient::LEN * MAX_FEE_RECIPIENTS + 32 * MAX_FEE_CALLERS;
+    /// Size before the per-source counters, for migrate_fee_state
+    pub const LEGACY_LEN: usize = 32 + 1 + 32 + 32 + 32 + 8;
     
     /// Get the seeds for the fee state PDA
     pub fn seeds() -> [&'static [u8]; 1] {
@@ -28,6 +41,221 @@ impl FeeStateAccount {
     pub fn get_pda(program_id: &Pubkey) -> (Pubkey, u8) {
         Pubkey::find_program_address(&Self::seeds(), program_id)
     }
+    
+    /// Count a distributed fee towards the total and its source's counter
+    pub fn record_fee(&mut self, source: FeeSource, amount: u64) -> Result<()> {
+        let counter = match source {
+            FeeSource::Borrow => &mut self.borrow_fees_collected,
+            FeeSource::Redemption => &mut self.redemption_fees_collected,
+            FeeSource::FlashMint => &mut self.flash_mint_fees_collected,
+            FeeSource::LiquidationPenalty => &mut self.liquidation_penalties_collected,
+        };
+        *counter = counter
+            .checked_add(amount)
+            .ok_or(crate::error::AerospacerFeesError::Overflow)?;
+        self.total_fees_collected = self.total_fees_collected
+            .checked_add(amount)
+            .ok_or(crate::error::AerospacerFeesError::Overflow)?;
+        Ok(())
+    }
+    
+    /// Slot of `address` among the weighted fee recipients
+    pub fn fee_recipient_slot(&self, address: &Pubkey) -> Option<usize> {
+        self.fee_recipients
+            .iter()
+            .position(|recipient| !recipient.is_empty() && recipient.address == *address)
+    }
+    
+    pub fn total_fee_weight(&self) -> u64 {
+        self.fee_recipients.iter().map(|recipient| recipient.weight as u64).sum()
+    }
+    
+    pub fn is_fee_caller(&self, authority: &Pubkey) -> bool {
+        *authority != Pubkey::default() && self.fee_callers.contains(authority)
+    }
+    
+    /// Keep fee_address_1/2 in line with the first two recipient slots
+    pub fn sync_fee_addresses(&mut self) {
+        self.fee_address_1 = self.fee_recipients[0].address;
+        self.fee_address_2 = self.fee_recipients[1].address;
+    }
+}
+
+pub const MAX_FEE_RECIPIENTS: usize = 8;
+pub const MAX_FEE_CALLERS: usize = 4;
+/// Seed of the PDA a caller program signs distribute_fee CPIs with
+pub const FEE_CALLER_SEED: &[u8] = b"fee_caller";
+pub const DEFAULT_FEE_WEIGHT: u16 = 1; // Weights are relative - the default pair splits 1:1
+
+/// Treasury-mode fee recipient; its share of new fees is weight / total weight
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeRecipient {
+    pub address: Pubkey,                  // 32 bytes
+    pub weight: u16,                      // 2 bytes - 0 marks an empty slot
+}
+
+impl FeeRecipient {
+    pub const LEN: usize = 32 + 2;
+
+    pub fn is_empty(&self) -> bool {
+        self.weight == 0
+    }
+}
+
+/// Origin of a fee passed to distribute_fee, so revenue can be reported per source on-chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeeSource {
+    Borrow,             // Opening and borrowing fees
+    Redemption,
+    FlashMint,
+    LiquidationPenalty,
+}
+
+/// Recipient of the protocol's public-goods share of liquidation penalties
+///
+/// Kept apart from FeeStateAccount so the fee state layout is unchanged. The protocol books the
+/// share per collateral denom and hands it over through route_public_goods, which only ever pays
+/// token accounts owned by public_goods_address.
+#[account]
+pub struct PublicGoodsConfig {
+    pub public_goods_address: Pubkey,     // 32 bytes
+    pub total_routes: u64,                // 8 bytes - route_public_goods transfers so far
+}
+
+impl PublicGoodsConfig {
+    pub const LEN: usize = 32 + 8;
+
+    /// Get the seeds for the public goods config PDA
+    pub fn seeds() -> [&'static [u8]; 1] {
+        [b"public_goods_config"]
+    }
+
+    /// Derive the public goods config PDA
+    pub fn get_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&Self::seeds(), program_id)
+    }
+}
+
+/// Claimable treasury-mode fees of the fee recipients, one per fee token
+///
+/// distribute_fee only moves the fee into the mint's fee vault; growth of the vault balance since
+/// the last sync is split by weight between the recipient slots when claim_fees runs (the same
+/// accounting as ProtocolStakingPool). Shares belong to the slot, so a slot is only freed or
+/// re-pointed once settled; unsynced fees are split with the weights current at the sync.
+#[account]
+pub struct FeeClaims {
+    pub mint: Pubkey,                     // 32 bytes
+    pub claimable: [u64; MAX_FEE_RECIPIENTS], // 8 bytes each - by fee recipient slot
+    pub last_fee_vault_balance: u64,      // 8 bytes - fee vault balance already split
+    pub collateral_fees_collected: u64,   // 8 bytes - booked by distribute_collateral_fee (aUSD fees count in FeeStateAccount)
+}
+
+impl FeeClaims {
+    pub const LEN: usize = 32 + 8 * MAX_FEE_RECIPIENTS + 8 + 8;
+
+    /// Split fees that arrived in the fee vault since the last sync between the weighted
+    /// recipients (the last one gets the rounding remainder)
+    pub fn accrue_fees(&mut self, state: &FeeStateAccount, fee_vault_balance: u64) -> Result<u64> {
+        let new_fees = fee_vault_balance.saturating_sub(self.last_fee_vault_balance);
+        let total_weight = state.total_fee_weight();
+        if new_fees == 0 || total_weight == 0 {
+            return Ok(0);
+        }
+
+        let last_slot = state.fee_recipients
+            .iter()
+            .rposition(|recipient| !recipient.is_empty())
+            .ok_or(crate::error::AerospacerFeesError::InvalidFeeDistribution)?;
+        let mut remaining = new_fees;
+        for (slot, recipient) in state.fee_recipients.iter().enumerate() {
+            if recipient.is_empty() {
+                continue;
+            }
+            let share = if slot == last_slot {
+                remaining
+            } else {
+                // share <= new_fees, so it fits in a u64
+                ((new_fees as u128 * recipient.weight as u128) / total_weight as u128) as u64
+            };
+            remaining -= share;
+            self.claimable[slot] = self.claimable[slot]
+                .checked_add(share)
+                .ok_or(crate::error::AerospacerFeesError::Overflow)?;
+        }
+        self.last_fee_vault_balance = fee_vault_balance;
+
+        Ok(new_fees)
+    }
+
+    /// Sync the fee vault under the current weights, then fail if any of `slots` still holds
+    /// unclaimed fees: a slot's balance is paid to whoever holds it at claim time
+    pub fn require_settled(&mut self, state: &FeeStateAccount, fee_vault_balance: u64, slots: &[usize]) -> Result<()> {
+        self.accrue_fees(state, fee_vault_balance)?;
+        require!(
+            slots.iter().all(|slot| self.claimable[*slot] == 0),
+            crate::error::AerospacerFeesError::UnclaimedFees
+        );
+        Ok(())
+    }
+
+    /// Claimable balance of `fee_address`, or None when it is not a configured fee recipient
+    pub fn claimable_mut(&mut self, state: &FeeStateAccount, fee_address: &Pubkey) -> Option<&mut u64> {
+        state.fee_recipient_slot(fee_address).map(|slot| &mut self.claimable[slot])
+    }
+}
+
+pub const FEE_EPOCH_HISTORY: usize = 32;
+
+/// Where distribute_fee sent a fee
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeeDestination {
+    StabilityPool,      // Stake mode
+    FeeVault,           // Treasury mode, split between the fee recipients by claim_fees
+}
+
+/// Fees distributed during one Solana epoch, by destination
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeEpochEntry {
+    pub epoch: u64,                       // 8 bytes
+    pub stability_pool_amount: u64,       // 8 bytes
+    pub fee_vault_amount: u64,            // 8 bytes
+}
+
+impl FeeEpochEntry {
+    pub const LEN: usize = 8 + 8 + 8;
+}
+
+/// Rolling per-epoch fee flow of one fee token, so dashboards can chart it from a single account
+///
+/// Epoch `e` lives in entry `e % FEE_EPOCH_HISTORY` and is reset when the ring comes back to it,
+/// so the last FEE_EPOCH_HISTORY epochs with fees are available (an entry whose epoch is not
+/// within that window is stale).
+#[account]
+pub struct FeeEpochStats {
+    pub mint: Pubkey,                     // 32 bytes
+    pub entries: [FeeEpochEntry; FEE_EPOCH_HISTORY], // 24 bytes each
+}
+
+impl FeeEpochStats {
+    pub const LEN: usize = 32 + FeeEpochEntry::LEN * FEE_EPOCH_HISTORY;
+
+    /// Add `amount` to `destination` for `epoch`, returning the epoch's total for it
+    pub fn record(&mut self, epoch: u64, destination: FeeDestination, amount: u64) -> Result<u64> {
+        let entry = &mut self.entries[(epoch % FEE_EPOCH_HISTORY as u64) as usize];
+        if entry.epoch != epoch {
+            *entry = FeeEpochEntry { epoch, ..FeeEpochEntry::default() };
+        }
+
+        let counter = match destination {
+            FeeDestination::StabilityPool => &mut entry.stability_pool_amount,
+            FeeDestination::FeeVault => &mut entry.fee_vault_amount,
+        };
+        *counter = counter
+            .checked_add(amount)
+            .ok_or(crate::error::AerospacerFeesError::Overflow)?;
+
+        Ok(*counter)
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -38,4 +266,143 @@ pub struct ConfigResponse {
     pub fee_address_1: Pubkey,            // NEW
     pub fee_address_2: Pubkey,            // NEW
     pub total_fees_collected: u64,
-} 
\ No newline at end of file
+    pub borrow_fees_collected: u64,
+    pub redemption_fees_collected: u64,
+    pub flash_mint_fees_collected: u64,
+    pub liquidation_penalties_collected: u64,
+    pub fee_recipients: Vec<FeeRecipient>, // Occupied slots only
+    pub fee_callers: Vec<Pubkey>,          // Occupied slots only
+} 
+
+// Scale of the fee-per-staked-token accumulator (matches aUSD's 18 decimals)
+pub const FEE_PER_UNIT_PRECISION: u128 = 1_000_000_000_000_000_000;
+
+/// Protocol token staking pool (LQTY-style fee share)
+///
+/// Fees reach the pool through the regular distribute_fee split: the admin adds this PDA as a
+/// fee recipient, so its share is claimed into the pool's fee vault. Growth of the vault
+/// balance since the last sync is spread over the staked supply via fee_per_unit_staked (F).
+#[account]
+pub struct ProtocolStakingPool {
+    pub stake_mint: Pubkey,               // 32 bytes - governance token
+    pub fee_mint: Pubkey,                 // 32 bytes - aUSD
+    pub total_staked: u64,                // 8 bytes
+    pub fee_per_unit_staked: u128,        // 16 bytes - F, scaled by FEE_PER_UNIT_PRECISION
+    pub last_fee_vault_balance: u64,      // 8 bytes - fee vault balance already accounted in F
+    pub total_fees_accrued: u64,          // 8 bytes
+}
+
+impl ProtocolStakingPool {
+    pub const LEN: usize = 32 + 32 + 8 + 16 + 8 + 8;
+
+    /// Get the seeds for the protocol staking pool PDA
+    pub fn seeds() -> [&'static [u8]; 1] {
+        [b"protocol_staking"]
+    }
+
+    /// Derive the protocol staking pool PDA
+    pub fn get_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&Self::seeds(), program_id)
+    }
+
+    /// Fold fees that arrived in the fee vault since the last sync into F.
+    /// While nothing is staked the balance is left unaccounted and picked up by the first staker.
+    pub fn accrue_fees(&mut self, fee_vault_balance: u64) -> Result<u64> {
+        let new_fees = fee_vault_balance.saturating_sub(self.last_fee_vault_balance);
+        if new_fees == 0 || self.total_staked == 0 {
+            return Ok(0);
+        }
+
+        let increment = (new_fees as u128)
+            .checked_mul(FEE_PER_UNIT_PRECISION)
+            .ok_or(crate::error::AerospacerFeesError::Overflow)?
+            / self.total_staked as u128;
+        self.fee_per_unit_staked = self.fee_per_unit_staked
+            .checked_add(increment)
+            .ok_or(crate::error::AerospacerFeesError::Overflow)?;
+        self.last_fee_vault_balance = fee_vault_balance;
+        self.total_fees_accrued = self.total_fees_accrued
+            .checked_add(new_fees)
+            .ok_or(crate::error::AerospacerFeesError::Overflow)?;
+
+        Ok(new_fees)
+    }
+}
+
+/// Per-user protocol token stake
+#[account]
+pub struct UserProtocolStake {
+    pub owner: Pubkey,                    // 32 bytes
+    pub amount: u64,                      // 8 bytes
+    pub fee_per_unit_snapshot: u128,      // 16 bytes - F at the last settlement
+    pub pending_fee_gain: u64,            // 8 bytes - settled but unclaimed aUSD
+}
+
+impl UserProtocolStake {
+    pub const LEN: usize = 32 + 8 + 16 + 8;
+
+    /// Move fees earned since the last snapshot into pending_fee_gain
+    pub fn settle(&mut self, fee_per_unit_staked: u128) -> Result<u64> {
+        let gain = (self.amount as u128)
+            .checked_mul(fee_per_unit_staked.saturating_sub(self.fee_per_unit_snapshot))
+            .ok_or(crate::error::AerospacerFeesError::Overflow)?
+            / FEE_PER_UNIT_PRECISION;
+        let gain = u64::try_from(gain).map_err(|_| crate::error::AerospacerFeesError::Overflow)?;
+
+        self.pending_fee_gain = self.pending_fee_gain
+            .checked_add(gain)
+            .ok_or(crate::error::AerospacerFeesError::Overflow)?;
+        self.fee_per_unit_snapshot = fee_per_unit_staked;
+
+        Ok(gain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fee_state(weights: [u16; 2]) -> FeeStateAccount {
+        let mut fee_recipients = [FeeRecipient::default(); MAX_FEE_RECIPIENTS];
+        for (slot, weight) in weights.iter().enumerate() {
+            fee_recipients[slot] = FeeRecipient { address: Pubkey::new_unique(), weight: *weight };
+        }
+        FeeStateAccount {
+            admin: Pubkey::default(),
+            is_stake_enabled: false,
+            stake_contract_address: Pubkey::default(),
+            fee_address_1: fee_recipients[0].address,
+            fee_address_2: fee_recipients[1].address,
+            total_fees_collected: 0,
+            borrow_fees_collected: 0,
+            redemption_fees_collected: 0,
+            flash_mint_fees_collected: 0,
+            liquidation_penalties_collected: 0,
+            fee_recipients,
+            fee_callers: [Pubkey::default(); MAX_FEE_CALLERS],
+        }
+    }
+
+    #[test]
+    fn test_recipient_with_unclaimed_fees_is_not_settled() {
+        let state = fee_state([1, 3]);
+        let mut claims = FeeClaims {
+            mint: Pubkey::new_unique(),
+            claimable: [0; MAX_FEE_RECIPIENTS],
+            last_fee_vault_balance: 0,
+            collateral_fees_collected: 0,
+        };
+
+        // 1_000 arrived since the last sync: the slot being removed is owed its 250 share
+        assert!(claims.require_settled(&state, 1_000, &[0]).is_err());
+        assert_eq!(claims.claimable[..2], [250, 750]);
+        assert_eq!(claims.last_fee_vault_balance, 1_000);
+
+        // claim_fees pays the slot out of the vault, after which it can be freed
+        claims.claimable[0] = 0;
+        claims.last_fee_vault_balance -= 250;
+        claims.require_settled(&state, 750, &[0]).unwrap();
+        // The other recipient's balance does not hold up the slot
+        assert_eq!(claims.claimable[1], 750);
+    }
+}
@@ -49,4 +49,19 @@ pub enum AerospacerOracleError {
     
     #[msg("Pyth price account validation failed")]
     PythPriceAccountValidationFailed,
+    
+    #[msg("Asset already listed - use update_asset")]
+    AssetAlreadyExists,
+    
+    #[msg("Stake pool account missing, not the configured one or not an SPL stake pool")]
+    InvalidStakePool,
+    
+    #[msg("Stake pool exchange rate has not been updated for the current epoch")]
+    StakePoolNotUpdated,
+    
+    #[msg("Chainlink feed loading failed")]
+    ChainlinkFeedLoadFailed,
+    
+    #[msg("Price confidence interval is wider than the denom allows")]
+    ConfidenceTooWide,
 }
\ No newline at end of file
@@ -0,0 +1,194 @@
+use anchor_lang::prelude::*;
+use pyth_sdk_solana::state::SolanaPriceAccount;
+use crate::error::AerospacerOracleError;
+use crate::state::{CollateralData, FeedType};
+
+/// Chainlink OCR2 store program owning the Chainlink-on-Solana feed accounts
+pub const CHAINLINK_STORE_PROGRAM_ID: Pubkey = pubkey!("HEvSKofvBgfaexv23kMabbYqxasxU3mQ4ibBMEmJWHny");
+
+/// Chainlink `Transmissions` feed account layout: 8-byte discriminator, then a packed header of
+/// version(1) state(1) owner(32) proposed_owner(32) writer(32) description(32) decimals(1)
+/// flagging_threshold(4) latest_round_id(4) granularity(1) live_length(4) live_cursor(4)
+/// historical_cursor(4), padded to HEADER_SIZE, then the live ring buffer of transmissions
+const CHAINLINK_DISCRIMINATOR_LEN: usize = 8;
+const CHAINLINK_HEADER_SIZE: usize = 192;
+const CHAINLINK_DECIMALS_OFFSET: usize = 130;
+const CHAINLINK_LIVE_LENGTH_OFFSET: usize = 140;
+const CHAINLINK_LIVE_CURSOR_OFFSET: usize = 144;
+
+/// One transmission: slot(8) timestamp(4) padding(4) answer(16) padding(16)
+const CHAINLINK_TRANSMISSION_SIZE: usize = 48;
+
+/// Decimals of every price the oracle returns: micro-USD per whole token
+pub const PRICE_DECIMALS: u8 = 6;
+
+/// Latest price of a feed, whatever its type: `price × 10^expo` USD per whole token
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeedPrice {
+    pub price: i64,
+    pub confidence: u64, // 0 for feeds without a confidence interval (Chainlink)
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+/// Load the latest price of a denom from its configured feed account, rejecting confidence
+/// intervals wider than the denom's `max_confidence_bps`
+pub fn load_feed_price(collateral_data: &CollateralData, feed_account: &AccountInfo) -> Result<FeedPrice> {
+    // SECURITY: Only the feed bound to this denom may price it
+    require_keys_eq!(
+        feed_account.key(),
+        collateral_data.pyth_price_account,
+        AerospacerOracleError::PythPriceAccountValidationFailed
+    );
+
+    let price = match collateral_data.feed_type {
+        FeedType::Pyth => load_pyth_price(feed_account)?,
+        FeedType::Chainlink => load_chainlink_price(feed_account)?,
+    };
+    collateral_data.check_confidence(price.price, price.confidence)?;
+    Ok(price)
+}
+
+fn load_pyth_price(feed_account: &AccountInfo) -> Result<FeedPrice> {
+    // PRODUCTION PYTH INTEGRATION CODE
+    // Use Pyth SDK to load and validate price feed data
+    let price_feed = SolanaPriceAccount::account_info_to_feed(feed_account)
+        .map_err(|_| AerospacerOracleError::PythPriceFeedLoadFailed)?;
+
+    // Get price with hardcoded staleness validation for mainnet (60 seconds)
+    // let current_time = clock.unix_timestamp;
+    // let price = price_feed.get_price_no_older_than(current_time, 60)
+    //     .ok_or(AerospacerOracleError::PriceTooOld)?;
+
+    // Get the latest available price data (no staleness validation for devnet testing)
+    let price = price_feed.get_price_unchecked();
+
+    // Validate price data integrity (the confidence bound is per denom, see load_feed_price)
+    require!(price.price > 0, AerospacerOracleError::InvalidPriceData);
+
+    Ok(FeedPrice {
+        price: price.price,
+        confidence: price.conf,
+        expo: price.expo,
+        publish_time: price.publish_time,
+    })
+}
+
+fn load_chainlink_price(feed_account: &AccountInfo) -> Result<FeedPrice> {
+    require_keys_eq!(
+        *feed_account.owner,
+        CHAINLINK_STORE_PROGRAM_ID,
+        AerospacerOracleError::ChainlinkFeedLoadFailed
+    );
+    let data = feed_account.try_borrow_data()?;
+    let price = parse_chainlink_feed(&data).ok_or(AerospacerOracleError::ChainlinkFeedLoadFailed)?;
+    require!(price.price > 0, AerospacerOracleError::InvalidPriceData);
+    Ok(price)
+}
+
+/// Latest round of a Chainlink `Transmissions` account, None if the data is malformed or empty
+fn parse_chainlink_feed(data: &[u8]) -> Option<FeedPrice> {
+    let header = data.get(CHAINLINK_DISCRIMINATOR_LEN..CHAINLINK_DISCRIMINATOR_LEN + CHAINLINK_HEADER_SIZE)?;
+    let read_u32 = |offset: usize| -> Option<u32> {
+        Some(u32::from_le_bytes(header.get(offset..offset + 4)?.try_into().ok()?))
+    };
+    let decimals = *header.get(CHAINLINK_DECIMALS_OFFSET)?;
+    let live_length = read_u32(CHAINLINK_LIVE_LENGTH_OFFSET)? as usize;
+    let live_cursor = read_u32(CHAINLINK_LIVE_CURSOR_OFFSET)? as usize;
+    if live_length == 0 {
+        return None;
+    }
+
+    // live_cursor is the next slot to write, the latest round sits just before it
+    let latest = (live_cursor + live_length - 1) % live_length;
+    let start = CHAINLINK_DISCRIMINATOR_LEN + CHAINLINK_HEADER_SIZE + latest * CHAINLINK_TRANSMISSION_SIZE;
+    let transmission = data.get(start..start + CHAINLINK_TRANSMISSION_SIZE)?;
+    let timestamp = u32::from_le_bytes(transmission[8..12].try_into().ok()?);
+    let answer = i128::from_le_bytes(transmission[16..32].try_into().ok()?);
+    if timestamp == 0 {
+        return None;
+    }
+
+    Some(FeedPrice {
+        price: i64::try_from(answer).ok()?,
+        confidence: 0,
+        expo: -(decimals as i32),
+        publish_time: timestamp as i64,
+    })
+}
+
+/// Rescale a feed price and its confidence from `10^expo` USD to micro-USD per whole token,
+/// rounding down. Prices worth less than one micro-USD are rejected.
+pub fn to_micro_usd(price: i64, confidence: u64, expo: i32) -> Result<(i64, u64)> {
+    require!(price > 0, AerospacerOracleError::InvalidPriceData);
+    let shift = expo + PRICE_DECIMALS as i32;
+    let rescale = |value: u64| -> Result<u64> {
+        let pow10 = 10u128.checked_pow(shift.unsigned_abs());
+        let scaled = if shift >= 0 {
+            pow10
+                .and_then(|p| (value as u128).checked_mul(p))
+                .ok_or(AerospacerOracleError::InvalidPriceData)?
+        } else {
+            pow10.map_or(0, |p| value as u128 / p)
+        };
+        u64::try_from(scaled).map_err(|_| AerospacerOracleError::InvalidPriceData.into())
+    };
+
+    let micro_usd_price = i64::try_from(rescale(price as u64)?)
+        .map_err(|_| AerospacerOracleError::InvalidPriceData)?;
+    require!(micro_usd_price > 0, AerospacerOracleError::InvalidPriceData);
+    Ok((micro_usd_price, rescale(confidence)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chainlink_feed(decimals: u8, rounds: &[(u32, i128)], live_cursor: u32) -> Vec<u8> {
+        let live_length = rounds.len() as u32;
+        let mut data = vec![0u8; CHAINLINK_DISCRIMINATOR_LEN + CHAINLINK_HEADER_SIZE + rounds.len() * CHAINLINK_TRANSMISSION_SIZE];
+        let header = CHAINLINK_DISCRIMINATOR_LEN;
+        data[header + CHAINLINK_DECIMALS_OFFSET] = decimals;
+        data[header + CHAINLINK_LIVE_LENGTH_OFFSET..][..4].copy_from_slice(&live_length.to_le_bytes());
+        data[header + CHAINLINK_LIVE_CURSOR_OFFSET..][..4].copy_from_slice(&live_cursor.to_le_bytes());
+        for (i, (timestamp, answer)) in rounds.iter().enumerate() {
+            let start = header + CHAINLINK_HEADER_SIZE + i * CHAINLINK_TRANSMISSION_SIZE;
+            data[start + 8..start + 12].copy_from_slice(&timestamp.to_le_bytes());
+            data[start + 16..start + 32].copy_from_slice(&answer.to_le_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn test_chainlink_feed_reads_latest_round() {
+        // Ring buffer wrapped: the round before the cursor (index 0) is the latest
+        let data = chainlink_feed(8, &[(1_700_000_300, 15_200_000_000), (1_700_000_100, 15_000_000_000), (1_700_000_200, 15_100_000_000)], 1);
+        assert_eq!(
+            parse_chainlink_feed(&data),
+            Some(FeedPrice { price: 15_200_000_000, confidence: 0, expo: -8, publish_time: 1_700_000_300 })
+        );
+
+        let data = chainlink_feed(18, &[(1_700_000_100, 1), (1_700_000_200, 2)], 0);
+        assert_eq!(parse_chainlink_feed(&data).map(|p| (p.price, p.expo)), Some((2, -18)));
+
+        // Empty ring buffer, never written or truncated
+        assert_eq!(parse_chainlink_feed(&chainlink_feed(8, &[], 0)), None);
+        assert_eq!(parse_chainlink_feed(&chainlink_feed(8, &[(0, 0)], 1)), None);
+        assert_eq!(parse_chainlink_feed(&data[..data.len() - 1]), None);
+    }
+
+    #[test]
+    fn test_to_micro_usd_rescales_any_exponent() {
+        // SOL at $150.12345678 (Pyth expo -8) rounds down to micro-USD
+        assert_eq!(to_micro_usd(15_012_345_678, 1_234_567, -8).unwrap(), (150_123_456, 12_345));
+        // Chainlink 18-decimal answer, and coarse feeds scaled up
+        assert_eq!(to_micro_usd(3_000_000_000_000_000_000, 0, -18).unwrap(), (3_000_000, 0));
+        assert_eq!(to_micro_usd(42, 1, -2).unwrap(), (420_000, 10_000));
+        assert_eq!(to_micro_usd(1, 0, 0).unwrap(), (1_000_000, 0));
+
+        // Below one micro-USD, non-positive, or too large once rescaled
+        assert!(to_micro_usd(99, 0, -8).is_err());
+        assert!(to_micro_usd(0, 0, -8).is_err());
+        assert!(to_micro_usd(i64::MAX, 0, 0).is_err());
+    }
+}
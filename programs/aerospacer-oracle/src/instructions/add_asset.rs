@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerOracleError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AddAssetParams {
+    /// Asset denomination (e.g., "inj", "atom")
+    pub denom: String,
+    
+    /// Decimal precision for price calculations (6, 18, etc.)
+    pub decimal: u8,
+    
+    /// Pyth Network price feed identifier (hex format), empty for a Chainlink feed
+    pub price_id: String,
+    
+    /// Price feed account for this asset (the SOL feed for a derived LST)
+    pub pyth_price_account: Pubkey,
+    
+    /// SPL stake pool whose exchange rate derives the price from the feed, None = priced directly
+    pub stake_pool: Option<Pubkey>,
+    
+    /// Kind of feed `pyth_price_account` is
+    pub feed_type: FeedType,
+    
+    /// Widest accepted confidence interval in basis points of the price (1-10_000)
+    pub max_confidence_bps: u16,
+}
+
+impl AddAssetParams {
+    pub fn to_collateral_data(&self, configured_at: i64) -> CollateralData {
+        CollateralData {
+            denom: self.denom.clone(),
+            decimal: self.decimal,
+            price_id: self.price_id.clone(),
+            configured_at,
+            pyth_price_account: self.pyth_price_account,
+            stake_pool: self.stake_pool,
+            feed_type: self.feed_type,
+            max_confidence_bps: self.max_confidence_bps,
+        }
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: AddAssetParams)]
+pub struct AddAsset<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    // Grown to fit the new asset, the admin pays the extra rent
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerOracleError::Unauthorized
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+}
+
+/// Lists a new collateral asset, reallocating the oracle state when it is full, so new
+/// collateral never needs a redeploy or a fresh oracle state. Existing assets are changed with
+/// update_asset.
+pub fn handler(ctx: Context<AddAsset>, params: AddAssetParams) -> Result<()> {
+    // Grow before the state is written back with the asset stored
+    let new_len = ctx.accounts.state.space_with(
+        &params.to_collateral_data(0),
+        ctx.accounts.state.to_account_info().data_len(),
+    );
+    OracleStateAccount::resize(
+        &ctx.accounts.state.to_account_info(),
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        new_len,
+    )?;
+    
+    let state = &mut ctx.accounts.state;
+    let clock = &ctx.accounts.clock;
+    
+    let collateral_data = params.to_collateral_data(clock.unix_timestamp);
+    collateral_data.validate()?;
+    require!(
+        !state.collateral_data.iter().any(|d| d.denom == params.denom),
+        AerospacerOracleError::AssetAlreadyExists
+    );
+    
+    state.collateral_data.push(collateral_data);
+    state.last_update = clock.unix_timestamp;
+    
+    msg!("Add asset successful");
+    msg!("Denom: {}", params.denom);
+    msg!("Decimal: {}", params.decimal);
+    msg!("Price ID: {}", params.price_id);
+    msg!("Price feed: {} ({:?})", params.pyth_price_account, params.feed_type);
+    msg!("Max confidence: {} bps", params.max_confidence_bps);
+    if let Some(stake_pool) = params.stake_pool {
+        msg!("Derived via stake pool: {}", stake_pool);
+    }
+    msg!("Total assets: {}", state.collateral_data.len());
+    msg!("State size: {} bytes", state.to_account_info().data_len());
+    
+    Ok(())
+}
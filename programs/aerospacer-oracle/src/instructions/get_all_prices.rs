@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::AerospacerOracleError;
-use pyth_sdk_solana::state::SolanaPriceAccount;
+use crate::feeds::{load_feed_price, to_micro_usd, PRICE_DECIMALS};
+use crate::stake_pool::compose_price;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct GetAllPricesParams {
@@ -23,48 +24,48 @@ pub struct GetAllPrices<'info> {
 
 pub fn handler(ctx: Context<GetAllPrices>, _params: GetAllPricesParams) -> Result<Vec<PriceResponse>> {
     let state = &ctx.accounts.state;
-    let _clock = &ctx.accounts.clock;
+    let clock = &ctx.accounts.clock;
     
-    // Get remaining accounts (should contain Pyth price accounts for each asset)
+    // Get remaining accounts (should contain Pyth price accounts for each asset,
+    // each derived asset's followed by its stake pool)
     let remaining_accounts = &ctx.remaining_accounts;
     
-    // Validate we have enough Pyth accounts for all assets
+    // Validate we have enough Pyth and stake pool accounts for all assets
+    let required_accounts = state.collateral_data.len()
+        + state.collateral_data.iter().filter(|d| d.stake_pool.is_some()).count();
     require!(
-        remaining_accounts.len() >= state.collateral_data.len(),
+        remaining_accounts.len() >= required_accounts,
         AerospacerOracleError::InvalidPriceData
     );
+    let mut accounts = remaining_accounts.iter();
     
     let mut prices = Vec::new();
 
-    // PRODUCTION PYTH INTEGRATION CODE
-    // For each collateral asset, fetch real price data using corresponding Pyth account
-    for (index, collateral_data) in state.collateral_data.iter().enumerate() {
-        // Get the corresponding Pyth price account from remaining_accounts
-        let pyth_price_account = &remaining_accounts[index];
+    // For each collateral asset, fetch real price data using its corresponding feed account
+    for collateral_data in state.collateral_data.iter() {
+        // Get the corresponding feed account (and stake pool) from remaining_accounts
+        let pyth_price_account = accounts.next().ok_or(AerospacerOracleError::InvalidPriceData)?;
+        let stake_pool = if collateral_data.stake_pool.is_some() { accounts.next() } else { None };
         
-        // Use Pyth SDK to load and validate price feed data (reusing get_price logic)
-        let price_feed = SolanaPriceAccount::account_info_to_feed(pyth_price_account)
-            .map_err(|_| AerospacerOracleError::PythPriceFeedLoadFailed)?;
-        
-        // Get price with hardcoded staleness validation for mainnet (60 seconds)
-        // let current_time = clock.unix_timestamp;
-        // let price = price_feed.get_price_no_older_than(current_time, 60)
-        //     .ok_or(AerospacerOracleError::PriceTooOld)?;
-
-        // Get the latest available price data (no staleness validation for devnet testing)
-        let price = price_feed.get_price_unchecked();
+        // SECURITY: remaining_accounts must follow collateral_data order with the bound feeds
+        let price = load_feed_price(collateral_data, pyth_price_account)?;
 
-        // Validate price data integrity with lenient confidence for devnet testing
-        require!(price.price > 0, AerospacerOracleError::InvalidPriceData);
-        require!(price.conf >= 100, AerospacerOracleError::PythPriceValidationFailed); // Reduced from 1000 to 100 for devnet
+        // Derived denoms (LSTs) are priced as SOL x stake pool exchange rate
+        let (composed_price, composed_confidence) = compose_price(
+            collateral_data,
+            price.price,
+            price.confidence,
+            stake_pool,
+            clock.epoch,
+        )?;
+        let (micro_usd_price, micro_usd_confidence) = to_micro_usd(composed_price, composed_confidence, price.expo)?;
 
         let price_response = PriceResponse {
             denom: collateral_data.denom.clone(),
-            price: price.price,
+            price: micro_usd_price,
             decimal: collateral_data.decimal,
             timestamp: price.publish_time,
-            confidence: price.conf,
-            exponent: price.expo,
+            confidence: micro_usd_confidence,
         };
         
         prices.push(price_response);
@@ -75,7 +76,7 @@ pub fn handler(ctx: Context<GetAllPrices>, _params: GetAllPricesParams) -> Resul
     msg!("Real Pyth data extracted for all assets using official SDK");
     msg!("Each asset uses its own Pyth price account via remaining_accounts");
     for price in &prices {
-        msg!("- {}: {} ± {} x 10^{}", price.denom, price.price, price.confidence, price.exponent);
+        msg!("- {}: {} ± {} x 10^-{}", price.denom, price.price, price.confidence, PRICE_DECIMALS);
     }
     
     Ok(prices)
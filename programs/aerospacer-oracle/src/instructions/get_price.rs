@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::AerospacerOracleError;
-use pyth_sdk_solana::state::SolanaPriceAccount;
+use crate::feeds::{load_feed_price, to_micro_usd, PRICE_DECIMALS};
+use crate::stake_pool::compose_price;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct GetPriceParams {
@@ -17,87 +18,54 @@ pub struct GetPrice<'info> {
     )]
     pub state: Account<'info, OracleStateAccount>,
     
-    /// CHECK: This is the Pyth price account that contains the price data
+    /// CHECK: Price feed account of the denom (Pyth or Chainlink) - checked against its CollateralData in the handler
     pub pyth_price_account: AccountInfo<'info>,
     
     /// CHECK: Clock sysvar for timestamp validation
     pub clock: Sysvar<'info, Clock>,
+    
+    /// CHECK: SPL stake pool of a derived denom - checked against its CollateralData, omit otherwise
+    pub stake_pool: Option<AccountInfo<'info>>,
 }
 
 pub fn handler(ctx: Context<GetPrice>, params: GetPriceParams) -> Result<PriceResponse> {
     let state = &ctx.accounts.state;
-    let _clock = &ctx.accounts.clock;
+    let clock = &ctx.accounts.clock;
     
     // Find the collateral data for the requested denom
     let collateral_data = state.collateral_data
         .iter()
         .find(|d| d.denom == params.denom)
         .ok_or(AerospacerOracleError::PriceFeedNotFound)?;
-
-    // PRODUCTION PYTH INTEGRATION CODE
-    // Use Pyth SDK to load and validate price feed data
-    let price_feed = SolanaPriceAccount::account_info_to_feed(&ctx.accounts.pyth_price_account)
-        .map_err(|_| AerospacerOracleError::PythPriceFeedLoadFailed)?;
-    
-    // Get price with hardcoded staleness validation for mainnet (60 seconds)
-    // let current_time = clock.unix_timestamp;
-    // let price = price_feed.get_price_no_older_than(current_time, 60)
-    //     .ok_or(AerospacerOracleError::PriceTooOld)?;
     
-    // Get the latest available price data (no staleness validation for devnet testing)
-    let price = price_feed.get_price_unchecked();
-
-    // Validate price data integrity with lenient confidence for devnet testing
-    require!(price.price > 0, AerospacerOracleError::InvalidPriceData);
-    require!(price.conf >= 100, AerospacerOracleError::PythPriceValidationFailed); // Reduced from 1000 to 100 for devnet
-    
-    let price_exponent = (-price.expo) as u8;
-    let token_decimals = collateral_data.decimal;
+    // Pyth or Chainlink, whichever feed the denom is configured with (bound to it)
+    let price = load_feed_price(collateral_data, &ctx.accounts.pyth_price_account)?;
     
-    // CRITICAL FIX: Calculate decimal to produce micro-USD (6 decimals) collateral values
-    // Formula: decimal = token_decimals + price_exponent - 6
-    // This ensures calculate_collateral_value returns values in micro-USD units,
-    // which is required for calculate_collateral_ratio's 10^12 scaling to work correctly.
-    //
-    // Example for SOL:
-    //   token_decimals = 9 (SOL has 9 decimals)
-    //   price_exponent = 8 (Pyth price has exponent -8)
-    //   target_decimal = 9 + 8 - 6 = 11
-    //
-    // This makes: collateral_value = (amount × price) / 10^11
-    // With amount in lamports (10^-9 SOL) and price as Pyth raw value:
-    //   collateral_value = (lamports × price) / 10^11
-    //                    = (SOL × 10^9 × price × 10^-8) / 10^11
-    //                    = (SOL × price) / 10^10
-    //                    = USD / 10^6  (since SOL × price = USD)
-    //                    = micro-USD ✓
-    const TARGET_USD_DECIMALS: u8 = 6; // micro-USD (10^-6 USD)
+    // Derived denoms (LSTs) are priced as SOL x stake pool exchange rate
+    let (composed_price, composed_confidence) = compose_price(
+        collateral_data,
+        price.price,
+        price.confidence,
+        ctx.accounts.stake_pool.as_ref(),
+        clock.epoch,
+    )?;
     
-    // Validate token has sufficient precision for micro-USD calculation
-    // Reject tokens with total_precision < 6 (extremely rare in practice)
-    let total_precision = token_decimals.saturating_add(price_exponent);
-    require!(
-        total_precision >= TARGET_USD_DECIMALS,
-        AerospacerOracleError::InvalidPriceData
-    );
+    // Every response is micro-USD per whole token, whatever the feed's exponent
+    let (micro_usd_price, micro_usd_confidence) = to_micro_usd(composed_price, composed_confidence, price.expo)?;
     
-    let adjusted_decimal = total_precision - TARGET_USD_DECIMALS;
-
     msg!("Price query successful");
     msg!("Denom: {}", params.denom);
-    msg!("Token decimal: {}", token_decimals);
-    msg!("Price exponent: {}", price_exponent);
-    msg!("Adjusted decimal (for micro-USD): {}", adjusted_decimal);
+    msg!("Decimal: {}", collateral_data.decimal);
+    msg!("Feed price: {} ± {} x 10^{}", composed_price, composed_confidence, price.expo);
+    msg!("Price: {} ± {} x 10^-{}", micro_usd_price, micro_usd_confidence, PRICE_DECIMALS);
     msg!("Publish Time: {}", price.publish_time);
-    msg!("Price: {} ± {} x 10^{}", price.price, price.conf, price.expo);
-    msg!("Real Pyth data extracted successfully using official SDK");
+    msg!("Feed type: {:?}", collateral_data.feed_type);
     
     Ok(PriceResponse {
         denom: params.denom,
-        price: price.price,
-        decimal: adjusted_decimal, // Adjusted to produce micro-USD collateral values
+        price: micro_usd_price,
+        decimal: collateral_data.decimal,
         timestamp: price.publish_time,
-        confidence: price.conf,
-        exponent: price.expo,
+        confidence: micro_usd_confidence,
     })
 }
\ No newline at end of file
@@ -10,6 +10,12 @@ pub mod get_price_id;
 pub mod get_all_prices;
 pub mod check_denom;
 pub mod update_pyth_price;
+pub mod quote;
+pub mod add_asset;
+pub mod update_asset;
+pub mod remove_asset;
+#[cfg(feature = "devnet")]
+pub mod register_faucet_asset;
 
 #[allow(ambiguous_glob_reexports)]
 pub use initialize::*;
@@ -34,4 +40,15 @@ pub use get_all_prices::*;
 #[allow(ambiguous_glob_reexports)]
 pub use check_denom::*;
 #[allow(ambiguous_glob_reexports)]
-pub use update_pyth_price::*;
\ No newline at end of file
+pub use update_pyth_price::*;
+#[allow(ambiguous_glob_reexports)]
+pub use quote::*;
+#[allow(ambiguous_glob_reexports)]
+pub use add_asset::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_asset::*;
+#[allow(ambiguous_glob_reexports)]
+pub use remove_asset::*;
+#[cfg(feature = "devnet")]
+#[allow(ambiguous_glob_reexports)]
+pub use register_faucet_asset::*;
@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerOracleError;
+use crate::feeds::{load_feed_price, to_micro_usd, PRICE_DECIMALS};
+use crate::stake_pool::compose_price;
+
+/// Decimals of a USD quote (micro-USD, same unit as the protocol's collateral values)
+pub const USD_QUOTE_DECIMALS: u8 = 6;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct QuoteParams {
+    pub from_denom: String,
+    pub to_denom: Option<String>, // None quotes in micro-USD
+    pub amount: u64,              // In base units of from_denom
+}
+
+#[derive(Accounts)]
+#[instruction(params: QuoteParams)]
+pub struct Quote<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+
+    /// CHECK: Price feed account of from_denom - validated against collateral data
+    pub from_pyth_price_account: AccountInfo<'info>,
+
+    /// CHECK: Price feed account of to_denom - validated against collateral data, omit for USD
+    pub to_pyth_price_account: Option<AccountInfo<'info>>,
+
+    /// CHECK: Clock sysvar for timestamp validation
+    pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: SPL stake pool of a derived from_denom - validated against collateral data
+    pub from_stake_pool: Option<AccountInfo<'info>>,
+
+    /// CHECK: SPL stake pool of a derived to_denom - validated against collateral data
+    pub to_stake_pool: Option<AccountInfo<'info>>,
+}
+
+pub fn handler(ctx: Context<Quote>, params: QuoteParams) -> Result<QuoteResponse> {
+    let state = &ctx.accounts.state;
+
+    let epoch = ctx.accounts.clock.epoch;
+    let from = load_price(
+        state,
+        &params.from_denom,
+        &ctx.accounts.from_pyth_price_account,
+        ctx.accounts.from_stake_pool.as_ref(),
+        epoch,
+    )?;
+
+    // USD is quoted as one dollar per whole unit with micro-USD decimals
+    let to = match params.to_denom.as_ref() {
+        Some(to_denom) => {
+            let to_pyth_price_account = ctx.accounts.to_pyth_price_account
+                .as_ref()
+                .ok_or(AerospacerOracleError::PythPriceAccountValidationFailed)?;
+            load_price(state, to_denom, to_pyth_price_account, ctx.accounts.to_stake_pool.as_ref(), epoch)?
+        }
+        None => QuotedPrice {
+            price: 10i64.pow(PRICE_DECIMALS as u32),
+            decimal: USD_QUOTE_DECIMALS,
+            publish_time: from.publish_time,
+        },
+    };
+
+    let amount_out = convert_amount(
+        params.amount,
+        from.price,
+        from.decimal,
+        to.price,
+        to.decimal,
+    )?;
+
+    let to_denom = params.to_denom.unwrap_or_else(|| "usd".to_string());
+
+    msg!("Quote successful");
+    msg!("{} {} -> {} {}", params.amount, params.from_denom, amount_out, to_denom);
+    msg!("From price: {} micro-USD ({} decimals)", from.price, from.decimal);
+    msg!("To price: {} micro-USD ({} decimals)", to.price, to.decimal);
+
+    Ok(QuoteResponse {
+        from_denom: params.from_denom,
+        to_denom,
+        amount_in: params.amount,
+        amount_out,
+        to_decimal: to.decimal,
+        // The quote is only as fresh as its older price
+        timestamp: from.publish_time.min(to.publish_time),
+    })
+}
+
+/// Micro-USD price of one side of a quote together with the token's decimals
+struct QuotedPrice {
+    price: i64,
+    decimal: u8,
+    publish_time: i64,
+}
+
+/// Load and validate the feed price of a configured denom (composed with its stake pool rate
+/// for derived denoms)
+fn load_price(
+    state: &OracleStateAccount,
+    denom: &str,
+    pyth_price_account: &AccountInfo,
+    stake_pool: Option<&AccountInfo>,
+    epoch: u64,
+) -> Result<QuotedPrice> {
+    let collateral_data = state.collateral_data
+        .iter()
+        .find(|d| d.denom == denom)
+        .ok_or(AerospacerOracleError::PriceFeedNotFound)?;
+
+    // Quotes are consumed by integrators, so only the configured feed is accepted
+    let price = load_feed_price(collateral_data, pyth_price_account)?;
+
+    let (price_value, confidence) = compose_price(collateral_data, price.price, price.confidence, stake_pool, epoch)?;
+    let (micro_usd_price, _confidence) = to_micro_usd(price_value, confidence, price.expo)?;
+
+    Ok(QuotedPrice {
+        price: micro_usd_price,
+        decimal: collateral_data.decimal,
+        publish_time: price.publish_time,
+    })
+}
+
+/// Convert `amount` base units of token A into base units of token B (rounded down), both
+/// prices in micro-USD per whole token
+///
+/// value = amount × price_a × 10^(−decimals_a)
+/// out   = value / (price_b × 10^(−decimals_b))
+///       = amount × price_a / price_b × 10^(decimals_b − decimals_a)
+///
+/// All scaling is folded into a single power of ten so that multiplication always
+/// happens before the final division.
+pub fn convert_amount(
+    amount: u64,
+    price_a: i64,
+    decimals_a: u8,
+    price_b: i64,
+    decimals_b: u8,
+) -> Result<u64> {
+    require!(price_a > 0 && price_b > 0, AerospacerOracleError::InvalidPriceData);
+
+    let scale = decimals_b as i32 - decimals_a as i32;
+    let pow10 = |exp: u32| 10u128.checked_pow(exp).ok_or(AerospacerOracleError::InvalidPriceData);
+
+    let mut numerator = (amount as u128)
+        .checked_mul(price_a as u128)
+        .ok_or(AerospacerOracleError::InvalidPriceData)?;
+    let mut denominator = price_b as u128;
+    if scale >= 0 {
+        numerator = numerator
+            .checked_mul(pow10(scale as u32)?)
+            .ok_or(AerospacerOracleError::InvalidPriceData)?;
+    } else {
+        denominator = denominator
+            .checked_mul(pow10(scale.unsigned_abs())?)
+            .ok_or(AerospacerOracleError::InvalidPriceData)?;
+    }
+
+    u64::try_from(numerator / denominator).map_err(|_| AerospacerOracleError::InvalidPriceData.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_amount_across_decimals() {
+        // 1.5 SOL (9 decimals) at $150 into micro-USD
+        let usd = convert_amount(1_500_000_000, 150_000_000, 9, 1_000_000, USD_QUOTE_DECIMALS).unwrap();
+        assert_eq!(usd, 225_000_000);
+
+        // 2 ETH (18 decimals) at $3000 into SOL at $150 (9 decimals) = 40 SOL
+        let sol = convert_amount(2_000_000_000_000_000_000, 3_000_000_000, 18, 150_000_000, 9).unwrap();
+        assert_eq!(sol, 40_000_000_000);
+
+        // 100 USDC (6 decimals) at $1 into SOL at $150 rounds down
+        let sol = convert_amount(100_000_000, 1_000_000, 6, 150_000_000, 9).unwrap();
+        assert_eq!(sol, 666_666_666);
+
+        assert!(convert_amount(1, 0, 9, 1_000_000, 6).is_err());
+    }
+}
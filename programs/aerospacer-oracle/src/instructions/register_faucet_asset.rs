@@ -0,0 +1,86 @@
+// Devnet only: this instruction exists in builds with the `devnet` feature and lets the
+// aerospacer-faucet program list the test assets it creates, backed by its mock price accounts.
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerOracleError;
+
+/// aerospacer-faucet program, whose `faucet_authority` PDA may register assets
+pub const FAUCET_PROGRAM_ID: Pubkey = anchor_lang::solana_program::pubkey!("8mGavT8iHB5CJy1WqnuYnwS4Fxpao1iiFCGLXcPaL8xV");
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RegisterFaucetAssetParams {
+    /// Asset denomination (e.g., "tSOL")
+    pub denom: String,
+    
+    /// Decimal precision of the test mint
+    pub decimal: u8,
+    
+    /// Mock price account maintained by the faucet
+    pub pyth_price_account: Pubkey,
+}
+
+#[derive(Accounts)]
+#[instruction(params: RegisterFaucetAssetParams)]
+pub struct RegisterFaucetAsset<'info> {
+    /// Signing PDA of the faucet program - validated in handler
+    pub faucet_authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    /// CHECK: Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<RegisterFaucetAsset>, params: RegisterFaucetAssetParams) -> Result<()> {
+    let (expected_authority, _bump) = Pubkey::find_program_address(&[b"faucet_authority"], &FAUCET_PROGRAM_ID);
+    require!(
+        ctx.accounts.faucet_authority.key() == expected_authority,
+        AerospacerOracleError::Unauthorized
+    );
+    
+    let state = &mut ctx.accounts.state;
+    let clock = &ctx.accounts.clock;
+    
+    require!(!params.denom.is_empty(), AerospacerOracleError::InvalidCollateralData);
+    require!(params.decimal > 0, AerospacerOracleError::InvalidCollateralData);
+    
+    // Faucet-created assets never overwrite a real listing
+    if let Some(existing) = state.collateral_data.iter().find(|d| d.denom == params.denom) {
+        require!(
+            existing.pyth_price_account == params.pyth_price_account,
+            AerospacerOracleError::Unauthorized
+        );
+    }
+    
+    // Mock feeds have no Pyth feed id; the price account address stands in for it
+    let collateral_data = CollateralData {
+        denom: params.denom.clone(),
+        decimal: params.decimal,
+        price_id: hex::encode(params.pyth_price_account.to_bytes()),
+        configured_at: clock.unix_timestamp,
+        pyth_price_account: params.pyth_price_account,
+        stake_pool: None,
+        feed_type: FeedType::Pyth,
+        max_confidence_bps: DEFAULT_MAX_CONFIDENCE_BPS,
+    };
+    
+    if let Some(index) = state.collateral_data.iter().position(|d| d.denom == params.denom) {
+        state.collateral_data[index] = collateral_data;
+        msg!("Updated faucet asset: {}", params.denom);
+    } else {
+        state.collateral_data.push(collateral_data);
+        msg!("Added faucet asset: {}", params.denom);
+    }
+    
+    state.last_update = clock.unix_timestamp;
+    
+    msg!("Mock price account: {}", params.pyth_price_account);
+    msg!("Total assets: {}", state.collateral_data.len());
+    
+    Ok(())
+}
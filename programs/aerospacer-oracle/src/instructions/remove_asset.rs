@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerOracleError;
+use super::remove_data::RemoveDataParams;
+
+#[derive(Accounts)]
+#[instruction(params: RemoveDataParams)]
+pub struct RemoveAsset<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    // Shrunk back (never below the initialize size), the admin gets the freed rent
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerOracleError::Unauthorized
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+}
+
+/// Delists a collateral asset (takes remove_data's params) and releases the space add_asset
+/// grew the state by
+pub fn handler(ctx: Context<RemoveAsset>, params: RemoveDataParams) -> Result<()> {
+    // Shrink before the state is written back, which then fits the smaller size
+    let new_len = ctx.accounts.state.space_without(&params.collateral_denom);
+    OracleStateAccount::resize(
+        &ctx.accounts.state.to_account_info(),
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        new_len,
+    )?;
+    
+    let state = &mut ctx.accounts.state;
+    let clock = &ctx.accounts.clock;
+    
+    let index = state.collateral_data
+        .iter()
+        .position(|d| d.denom == params.collateral_denom)
+        .ok_or(AerospacerOracleError::CollateralDataNotFound)?;
+    let removed = state.collateral_data.remove(index);
+    state.last_update = clock.unix_timestamp;
+    
+    msg!("Remove asset successful");
+    msg!("Removed denom: {}", removed.denom);
+    msg!("Removed price ID: {}", removed.price_id);
+    msg!("Remaining assets: {}", state.collateral_data.len());
+    msg!("State size: {} bytes", state.to_account_info().data_len());
+    
+    Ok(())
+}
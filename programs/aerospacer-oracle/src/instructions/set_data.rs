@@ -56,6 +56,9 @@ pub fn handler(ctx: Context<SetData>, params: SetDataParams) -> Result<()> {
         price_id: params.price_id.clone(),
         configured_at: clock.unix_timestamp,
         pyth_price_account: params.pyth_price_account,
+        stake_pool: None,
+        feed_type: FeedType::Pyth,
+        max_confidence_bps: DEFAULT_MAX_CONFIDENCE_BPS,
     };
     
     // Check if denom already exists and update, otherwise add new
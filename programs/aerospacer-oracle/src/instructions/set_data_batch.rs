@@ -55,6 +55,9 @@ pub fn handler(ctx: Context<SetDataBatch>, params: SetDataBatchParams) -> Result
             price_id: collateral_data.price_id.clone(),
             configured_at: clock.unix_timestamp,
             pyth_price_account: collateral_data.pyth_price_account,
+            stake_pool: None,
+            feed_type: FeedType::Pyth,
+            max_confidence_bps: DEFAULT_MAX_CONFIDENCE_BPS,
         };
         
         // Check if denom already exists and update, otherwise add new
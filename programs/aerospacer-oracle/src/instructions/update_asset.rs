@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerOracleError;
+use super::add_asset::AddAssetParams;
+
+#[derive(Accounts)]
+#[instruction(params: AddAssetParams)]
+pub struct UpdateAsset<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.admin == admin.key() @ AerospacerOracleError::Unauthorized
+    )]
+    pub state: Account<'info, OracleStateAccount>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: Clock sysvar for timestamp
+    pub clock: Sysvar<'info, Clock>,
+}
+
+/// Replaces the configuration of a listed asset (decimals, Pyth feed id and price account).
+/// Takes add_asset's params; the denom selects the asset.
+pub fn handler(ctx: Context<UpdateAsset>, params: AddAssetParams) -> Result<()> {
+    // Grow before the state is written back with the asset stored
+    let new_len = ctx.accounts.state.space_with(
+        &params.to_collateral_data(0),
+        ctx.accounts.state.to_account_info().data_len(),
+    );
+    OracleStateAccount::resize(
+        &ctx.accounts.state.to_account_info(),
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        new_len,
+    )?;
+    
+    let state = &mut ctx.accounts.state;
+    let clock = &ctx.accounts.clock;
+    
+    let collateral_data = params.to_collateral_data(clock.unix_timestamp);
+    collateral_data.validate()?;
+    let index = state.collateral_data
+        .iter()
+        .position(|d| d.denom == params.denom)
+        .ok_or(AerospacerOracleError::CollateralDataNotFound)?;
+    
+    let previous = std::mem::replace(&mut state.collateral_data[index], collateral_data);
+    state.last_update = clock.unix_timestamp;
+    
+    msg!("Update asset successful");
+    msg!("Denom: {}", params.denom);
+    msg!("Decimal: {} -> {}", previous.decimal, params.decimal);
+    msg!("Price ID: {} -> {}", previous.price_id, params.price_id);
+    msg!("Pyth Price Account: {} -> {}", previous.pyth_price_account, params.pyth_price_account);
+    msg!("Stake pool: {:?} -> {:?}", previous.stake_pool, params.stake_pool);
+    msg!("Feed type: {:?} -> {:?}", previous.feed_type, params.feed_type);
+    msg!("Max confidence: {} -> {} bps", previous.max_confidence_bps, params.max_confidence_bps);
+    
+    Ok(())
+}
@@ -1,12 +1,14 @@
 use anchor_lang::prelude::*;
 
 pub mod error;
+pub mod feeds;
 pub mod instructions;
 pub mod state;
 pub mod msg;
+pub mod stake_pool;
 
 use instructions::*;
-use crate::state::{PriceResponse, ConfigResponse, OracleStateAccount};
+use crate::state::{PriceResponse, ConfigResponse, QuoteResponse, OracleStateAccount};
 
 declare_id!("8Fu4YnUkfmrGQ3PTVoPfsAGjQ6NistGsiKpBEkPhzA2K");
 
@@ -81,6 +83,32 @@ pub mod aerospacer_oracle {
     pub fn update_pyth_price(ctx: Context<UpdatePythPrice>, params: UpdatePythPriceParams) -> Result<()> {
         instructions::update_pyth_price::handler(ctx, params)
     }
+
+    /// Convert an amount of one asset into another asset (or micro-USD) using current Pyth prices
+    pub fn quote(ctx: Context<Quote>, params: QuoteParams) -> Result<QuoteResponse> {
+        instructions::quote::handler(ctx, params)
+    }
+
+    /// List a new collateral asset, growing the oracle state as needed (admin only)
+    pub fn add_asset(ctx: Context<AddAsset>, params: AddAssetParams) -> Result<()> {
+        instructions::add_asset::handler(ctx, params)
+    }
+
+    /// Replace the configuration of a listed collateral asset (admin only)
+    pub fn update_asset(ctx: Context<UpdateAsset>, params: AddAssetParams) -> Result<()> {
+        instructions::update_asset::handler(ctx, params)
+    }
+
+    /// Delist a collateral asset and shrink the oracle state back (admin only)
+    pub fn remove_asset(ctx: Context<RemoveAsset>, params: RemoveDataParams) -> Result<()> {
+        instructions::remove_asset::handler(ctx, params)
+    }
+
+    /// List a test asset created by the aerospacer-faucet program (devnet builds only)
+    #[cfg(feature = "devnet")]
+    pub fn register_faucet_asset(ctx: Context<RegisterFaucetAsset>, params: RegisterFaucetAssetParams) -> Result<()> {
+        instructions::register_faucet_asset::handler(ctx, params)
+    }
 }
 
 /// Helper functions for PDA derivation
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use crate::error::AerospacerOracleError;
+use crate::state::CollateralData;
+
+/// SPL stake pool program (jitoSOL, bSOL and most other stake pool LSTs)
+pub const SPL_STAKE_POOL_PROGRAM_ID: Pubkey = pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+
+/// `AccountType::StakePool` tag in the first byte of a stake pool account
+const STAKE_POOL_ACCOUNT_TYPE: u8 = 1;
+
+/// Offset of `total_lamports` in the Borsh layout of an SPL `StakePool`:
+/// account_type(1) manager(32) staker(32) stake_deposit_authority(32) stake_withdraw_bump_seed(1)
+/// validator_list(32) reserve_stake(32) pool_mint(32) manager_fee_account(32) token_program_id(32)
+const TOTAL_LAMPORTS_OFFSET: usize = 258;
+
+/// Exchange rate of a stake pool token: `total_lamports` SOL backs `pool_token_supply` tokens
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StakePoolRate {
+    pub total_lamports: u64,
+    pub pool_token_supply: u64,
+    pub last_update_epoch: u64,
+}
+
+impl StakePoolRate {
+    /// Read the exchange rate from an SPL stake pool account. The rate must have been updated
+    /// this epoch or the previous one - a pool that missed its epoch update misses the rewards
+    /// (and any slashing) of the epochs since.
+    pub fn load(stake_pool: &AccountInfo, current_epoch: u64) -> Result<Self> {
+        require_keys_eq!(
+            *stake_pool.owner,
+            SPL_STAKE_POOL_PROGRAM_ID,
+            AerospacerOracleError::InvalidStakePool
+        );
+        let data = stake_pool.try_borrow_data()?;
+        let rate = Self::parse(&data).ok_or(AerospacerOracleError::InvalidStakePool)?;
+        require!(
+            rate.last_update_epoch.saturating_add(1) >= current_epoch,
+            AerospacerOracleError::StakePoolNotUpdated
+        );
+        Ok(rate)
+    }
+
+    fn parse(data: &[u8]) -> Option<Self> {
+        if *data.first()? != STAKE_POOL_ACCOUNT_TYPE {
+            return None;
+        }
+        let read_u64 = |offset: usize| -> Option<u64> {
+            Some(u64::from_le_bytes(data.get(offset..offset + 8)?.try_into().ok()?))
+        };
+        let rate = StakePoolRate {
+            total_lamports: read_u64(TOTAL_LAMPORTS_OFFSET)?,
+            pool_token_supply: read_u64(TOTAL_LAMPORTS_OFFSET + 8)?,
+            last_update_epoch: read_u64(TOTAL_LAMPORTS_OFFSET + 16)?,
+        };
+        (rate.total_lamports > 0 && rate.pool_token_supply > 0).then_some(rate)
+    }
+
+    /// Convert a SOL-denominated amount (price or confidence, same exponent) into the pool
+    /// token's, rounding down
+    pub fn apply(&self, sol_amount: u64) -> Result<u64> {
+        let amount = (sol_amount as u128)
+            .checked_mul(self.total_lamports as u128)
+            .ok_or(AerospacerOracleError::InvalidPriceData)?
+            / self.pool_token_supply as u128;
+        u64::try_from(amount).map_err(|_| AerospacerOracleError::InvalidPriceData.into())
+    }
+}
+
+/// Price and confidence of a denom from its feed's: unchanged for directly priced denoms, times
+/// the stake pool exchange rate for derived ones (`stake_pool` must then be the configured pool)
+pub fn compose_price(
+    collateral_data: &CollateralData,
+    price: i64,
+    confidence: u64,
+    stake_pool: Option<&AccountInfo>,
+    current_epoch: u64,
+) -> Result<(i64, u64)> {
+    let Some(expected_pool) = collateral_data.stake_pool else {
+        return Ok((price, confidence));
+    };
+    let stake_pool = stake_pool.ok_or(AerospacerOracleError::InvalidStakePool)?;
+    require_keys_eq!(stake_pool.key(), expected_pool, AerospacerOracleError::InvalidStakePool);
+    require!(price > 0, AerospacerOracleError::InvalidPriceData);
+
+    let rate = StakePoolRate::load(stake_pool, current_epoch)?;
+    let derived_price = i64::try_from(rate.apply(price as u64)?)
+        .map_err(|_| AerospacerOracleError::InvalidPriceData)?;
+    let derived_confidence = rate.apply(confidence)?;
+
+    msg!("Derived from stake pool {}: {} lamports / {} tokens (epoch {})",
+         expected_pool, rate.total_lamports, rate.pool_token_supply, rate.last_update_epoch);
+    Ok((derived_price, derived_confidence))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stake_pool_data(total_lamports: u64, pool_token_supply: u64, last_update_epoch: u64) -> Vec<u8> {
+        let mut data = vec![0u8; 611];
+        data[0] = STAKE_POOL_ACCOUNT_TYPE;
+        data[258..266].copy_from_slice(&total_lamports.to_le_bytes());
+        data[266..274].copy_from_slice(&pool_token_supply.to_le_bytes());
+        data[274..282].copy_from_slice(&last_update_epoch.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_stake_pool_rate_composes_sol_price() {
+        // 1.1 SOL per pool token
+        let rate = StakePoolRate::parse(&stake_pool_data(1_100_000, 1_000_000, 600)).unwrap();
+        assert_eq!(rate.last_update_epoch, 600);
+
+        // SOL at $150 (expo -8) -> $165 per pool token
+        assert_eq!(rate.apply(15_000_000_000).unwrap(), 16_500_000_000);
+        assert_eq!(rate.apply(7).unwrap(), 7);
+
+        // Not a stake pool, empty pool or truncated data
+        let mut data = stake_pool_data(1_100_000, 1_000_000, 600);
+        data[0] = 2;
+        assert_eq!(StakePoolRate::parse(&data), None);
+        assert_eq!(StakePoolRate::parse(&stake_pool_data(0, 0, 600)), None);
+        assert_eq!(StakePoolRate::parse(&stake_pool_data(1, 1, 600)[..280]), None);
+    }
+}
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+/// Confidence interval accepted by default, as a share of the price (2%)
+pub const DEFAULT_MAX_CONFIDENCE_BPS: u16 = 200;
+
 /// Main oracle state account containing all configuration and data
 #[account]
 pub struct OracleStateAccount {
@@ -33,6 +36,70 @@ impl OracleStateAccount {
     pub fn get_pda(program_id: &Pubkey) -> (Pubkey, u8) {
         Pubkey::find_program_address(&Self::seeds(), program_id)
     }
+    
+    /// Account size (discriminator included) that fits exactly the given assets
+    pub fn space_for<'a>(assets: impl IntoIterator<Item = &'a CollateralData>) -> usize {
+        8 + 32 + 32 + 4 + assets.into_iter().map(CollateralData::space).sum::<usize>() + 8
+    }
+    
+    /// Account size after add_asset / update_asset stores `asset`: grows to fit, never shrinks
+    pub fn space_with(&self, asset: &CollateralData, current_len: usize) -> usize {
+        let others = self.collateral_data.iter().filter(|d| d.denom != asset.denom);
+        Self::space_for(others.chain(std::iter::once(asset))).max(current_len)
+    }
+    
+    /// Account size after remove_asset drops `denom`. Never below the initialize size, so
+    /// set_data keeps the headroom it was written for.
+    pub fn space_without(&self, denom: &str) -> usize {
+        let remaining = self.collateral_data.iter().filter(|d| d.denom != denom);
+        Self::space_for(remaining).max(8 + Self::LEN)
+    }
+    
+    /// Resize the state account to `new_len`, keeping it rent exempt: `payer` tops up the
+    /// rent of a larger account and gets back what a smaller one frees
+    pub fn resize<'info>(
+        state: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        new_len: usize,
+    ) -> Result<()> {
+        if new_len == state.data_len() {
+            return Ok(());
+        }
+        
+        let rent_exempt = Rent::get()?.minimum_balance(new_len);
+        let balance = state.lamports();
+        if rent_exempt > balance {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    anchor_lang::system_program::Transfer {
+                        from: payer.clone(),
+                        to: state.clone(),
+                    },
+                ),
+                rent_exempt - balance,
+            )?;
+        } else if balance > rent_exempt {
+            // The state is owned by this program, so its lamports can be moved directly
+            let refund = balance - rent_exempt;
+            **state.try_borrow_mut_lamports()? -= refund;
+            **payer.try_borrow_mut_lamports()? += refund;
+        }
+        
+        state.resize(new_len)?;
+        Ok(())
+    }
+}
+
+/// Kind of price feed a denom is read from - every kind yields the same PriceResponse
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FeedType {
+    /// Pyth pull-oracle price account
+    #[default]
+    Pyth,
+    /// Chainlink-on-Solana push feed (OCR2 store `Transmissions` account)
+    Chainlink,
 }
 
 /// Collateral asset data structure for oracle integration
@@ -44,37 +111,105 @@ pub struct CollateralData {
     /// Decimal precision for price calculations (6, 18, etc.)
     pub decimal: u8,
     
-    /// Pyth Network price feed identifier (hex format)
+    /// Pyth Network price feed identifier (hex format), empty for Chainlink feeds
     /// Example: "0x2f95862b045670cd22bee3114c39763a34a94be1d3d9e600dfe3238c6f7bcef3"
     pub price_id: String,
     
     /// Timestamp when this asset was last configured
     pub configured_at: i64,
     
-    /// Pyth price account address for this asset
+    /// Price feed account for this asset (a Chainlink feed for FeedType::Chainlink) - the only
+    /// account the price instructions accept for this denom
     pub pyth_price_account: Pubkey,
+    
+    /// SPL stake pool of a liquid staking token priced as derived: the feed's SOL price times
+    /// the pool's exchange rate (e.g. jitoSOL). None = priced directly by its feed
+    pub stake_pool: Option<Pubkey>,
+    
+    /// Kind of feed `pyth_price_account` is
+    pub feed_type: FeedType,
+    
+    /// Widest accepted confidence interval in basis points of the price (1-10_000)
+    pub max_confidence_bps: u16,
+}
+
+impl CollateralData {
+    /// Borsh size of this entry
+    pub fn space(&self) -> usize {
+        4 + self.denom.len() + 1 + 4 + self.price_id.len() + 8 + 32 + 1 + 32 * self.stake_pool.is_some() as usize + 1 + 2
+    }
+    
+    /// Checks shared by the asset admin instructions
+    pub fn validate(&self) -> Result<()> {
+        require!(!self.denom.is_empty(), crate::error::AerospacerOracleError::InvalidCollateralData);
+        require!(self.decimal > 0, crate::error::AerospacerOracleError::InvalidCollateralData);
+        
+        let valid_price_id = match self.feed_type {
+            // Pyth feed ids are 32 bytes of hex
+            FeedType::Pyth => self.price_id.len() == 64 && self.price_id.chars().all(|c| c.is_ascii_hexdigit()),
+            // Chainlink feeds are identified by their account alone
+            FeedType::Chainlink => self.price_id.is_empty(),
+        };
+        require!(valid_price_id, crate::error::AerospacerOracleError::InvalidPriceId);
+        require!(
+            self.max_confidence_bps > 0 && self.max_confidence_bps <= 10_000,
+            crate::error::AerospacerOracleError::InvalidCollateralData
+        );
+        Ok(())
+    }
+    
+    /// Rejects a price whose confidence interval is wider than `max_confidence_bps` of it
+    pub fn check_confidence(&self, price: i64, confidence: u64) -> Result<()> {
+        require!(price > 0, crate::error::AerospacerOracleError::InvalidPriceData);
+        require!(
+            confidence as u128 * 10_000 <= price as u128 * self.max_confidence_bps as u128,
+            crate::error::AerospacerOracleError::ConfidenceTooWide
+        );
+        Ok(())
+    }
 }
 
-/// Price response containing real-time asset price data
+/// Price response containing real-time asset price data, in the same fixed-point format for
+/// every denom and feed type: value of `amount` base units in micro-USD is
+/// `amount × price / 10^decimal`
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct PriceResponse {
     /// Asset denomination (e.g., "inj", "atom")
     pub denom: String,
     
-    /// Current real-time price from oracle (scaled by decimals)
+    /// Current real-time price in micro-USD per whole token
     pub price: i64,
     
-    /// Decimal precision for price calculations
+    /// Decimals of the token (base units per whole token)
     pub decimal: u8,
     
     /// Timestamp when price was fetched
     pub timestamp: i64,
     
-    /// Price confidence interval (from Pyth)
+    /// Price confidence interval in micro-USD (0 for feeds without one)
     pub confidence: u64,
+}
+
+/// Quote response converting an amount between two denoms (or into USD)
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct QuoteResponse {
+    /// Denomination the amount was quoted from
+    pub from_denom: String,
+    
+    /// Denomination the amount was quoted into ("usd" for USD quotes)
+    pub to_denom: String,
+    
+    /// Input amount in base units of from_denom
+    pub amount_in: u64,
+    
+    /// Output amount in base units of to_denom (micro-USD for USD quotes), rounded down
+    pub amount_out: u64,
     
-    /// Price exponent (from Pyth)
-    pub exponent: i32,
+    /// Decimal precision of amount_out
+    pub to_decimal: u8,
+    
+    /// Publish time of the older of the two prices used
+    pub timestamp: i64,
 }
 
 /// Configuration response containing contract settings
@@ -91,4 +226,63 @@ pub struct ConfigResponse {
     
     /// Timestamp of last configuration update
     pub last_update: i64,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(denom: &str) -> CollateralData {
+        CollateralData {
+            denom: denom.to_string(),
+            decimal: 9,
+            price_id: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d".to_string(),
+            configured_at: 1_700_000_000,
+            pyth_price_account: Pubkey::new_unique(),
+            stake_pool: None,
+            feed_type: FeedType::Pyth,
+            max_confidence_bps: DEFAULT_MAX_CONFIDENCE_BPS,
+        }
+    }
+
+    #[test]
+    fn test_space_for_matches_serialized_state() {
+        let state = OracleStateAccount {
+            admin: Pubkey::new_unique(),
+            oracle_address: Pubkey::new_unique(),
+            collateral_data: vec![asset("SOL"), asset("ibc/ATOM")],
+            last_update: 1_700_000_000,
+        };
+
+        let mut data = Vec::new();
+        state.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), OracleStateAccount::space_for(&state.collateral_data));
+
+        // A new denom grows the account by its entry; replacing one never shrinks it
+        let exact = OracleStateAccount::space_for(&state.collateral_data);
+        assert_eq!(state.space_with(&asset("USDC"), exact), exact + 4 + 4 + 1 + 4 + 64 + 8 + 32 + 1 + 1 + 2);
+        assert_eq!(state.space_with(&asset("SOL"), exact), exact);
+        assert_eq!(state.space_with(&asset("SOL"), exact + 100), exact + 100);
+
+        // Removing never shrinks below the initialize size
+        assert_eq!(state.space_without("SOL"), 8 + OracleStateAccount::LEN);
+    }
+
+    #[test]
+    fn test_confidence_bound_is_a_share_of_the_price() {
+        // $150 at expo -8 with the default 2% bound: up to $3 of confidence
+        let sol = asset("SOL");
+        assert!(sol.check_confidence(15_000_000_000, 0).is_ok());
+        assert!(sol.check_confidence(15_000_000_000, 300_000_000).is_ok());
+        assert!(sol.check_confidence(15_000_000_000, 300_000_001).is_err());
+        assert!(sol.check_confidence(0, 0).is_err());
+
+        let mut wide = asset("MEME");
+        wide.max_confidence_bps = 10_000;
+        assert!(wide.check_confidence(100, 100).is_ok());
+        assert!(wide.check_confidence(100, 101).is_err());
+        assert!(wide.validate().is_ok());
+        wide.max_confidence_bps = 0;
+        assert!(wide.validate().is_err());
+    }
+}
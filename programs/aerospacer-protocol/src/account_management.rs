@@ -1,67 +1,27 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, Burn};
 use crate::state::*;
-use crate::error::*;
 
 /// Account management utilities for the protocol
 /// This module provides clean, type-safe account loading and management
 
-/// Context for managing user trove accounts
-#[derive(Accounts)]
-pub struct TroveContext<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    #[account(
-        mut,
-        seeds = [b"user_debt_amount", user.key().as_ref()],
-        bump,
-        constraint = user_debt_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
-    )]
-    pub user_debt_amount: Account<'info, UserDebtAmount>,
-    
-    #[account(
-        mut,
-        seeds = [b"liquidity_threshold", user.key().as_ref()],
-        bump,
-        constraint = liquidity_threshold.owner == user.key() @ AerospacerProtocolError::Unauthorized
-    )]
-    pub liquidity_threshold: Account<'info, LiquidityThreshold>,
-    
-    #[account(mut)]
-    pub state: Account<'info, StateAccount>,
+/// Trove accounts of an instruction, borrowed from its Anchor accounts so TroveManager
+/// writes land on them directly
+pub struct TroveContext<'a, 'info> {
+    pub user: &'a Signer<'info>,
+    pub user_debt_amount: &'a mut Account<'info, UserDebtAmount>,
+    pub liquidity_threshold: &'a mut Account<'info, LiquidityThreshold>,
+    pub state: &'a AccountLoader<'info, StateAccount>,
 }
 
-/// Context for managing collateral-specific operations
-#[derive(Accounts)]
-#[instruction(collateral_denom: String)]
-pub struct CollateralContext<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    #[account(
-        mut,
-        seeds = [b"user_collateral_amount", user.key().as_ref(), collateral_denom.as_bytes()],
-        bump,
-        constraint = user_collateral_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
-    )]
-    pub user_collateral_amount: Account<'info, UserCollateralAmount>,
-    
-    #[account(mut)]
-    pub user_collateral_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub protocol_collateral_account: Account<'info, TokenAccount>,
-    
-    /// CHECK: Per-denom collateral total PDA
-    #[account(
-        mut,
-        seeds = [b"total_collateral_amount", collateral_denom.as_bytes()],
-        bump
-    )]
-    pub total_collateral_amount: Account<'info, TotalCollateralAmount>,
-    
-    pub token_program: Program<'info, Token>,
+/// Collateral accounts of an instruction for one denom, borrowed like TroveContext
+pub struct CollateralContext<'a, 'info> {
+    pub user: &'a Signer<'info>,
+    pub user_collateral_amount: &'a mut Account<'info, UserCollateralAmount>,
+    pub user_collateral_account: &'a Account<'info, TokenAccount>,
+    pub protocol_collateral_account: &'a Account<'info, TokenAccount>,
+    pub total_collateral_amount: &'a AccountLoader<'info, TotalCollateralAmount>,
+    pub token_program: &'a Program<'info, Token>,
 }
 
 // NOTE: SortedTrovesContext removed - using off-chain sorting architecture
@@ -73,7 +33,7 @@ pub struct LiquidationContext<'info> {
     pub liquidator: Signer<'info>,
     
     #[account(mut)]
-    pub state: Account<'info, StateAccount>,
+    pub state: AccountLoader<'info, StateAccount>,
     
     #[account(mut)]
     pub stable_coin_mint: Account<'info, Mint>,
@@ -86,6 +46,14 @@ pub struct LiquidationContext<'info> {
     )]
     pub protocol_stablecoin_vault: AccountInfo<'info>,
     
+    /// CHECK: Stability pool vault PDA
+    #[account(
+        mut,
+        seeds = [b"stability_pool_vault"],
+        bump
+    )]
+    pub stability_pool_vault: AccountInfo<'info>,
+    
     /// CHECK: Protocol collateral vault PDA
     #[account(
         mut,
@@ -100,7 +68,7 @@ pub struct LiquidationContext<'info> {
         seeds = [b"total_collateral_amount", b"SOL"],
         bump
     )]
-    pub total_collateral_amount: Account<'info, TotalCollateralAmount>,
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
     
     // NOTE: sorted_troves_state removed - using off-chain sorting
     
@@ -109,7 +77,7 @@ pub struct LiquidationContext<'info> {
 }
 
 /// Helper functions for account management
-impl<'info> TroveContext<'info> {
+impl TroveContext<'_, '_> {
     /// Get user's trove information
     pub fn get_trove_info(&self) -> Result<TroveInfo> {
         Ok(TroveInfo {
@@ -149,7 +117,7 @@ pub struct CollateralInfo {
     pub protocol_account: Pubkey,
 }
 
-impl<'info> CollateralContext<'info> {
+impl CollateralContext<'_, '_> {
     /// Get collateral information
     pub fn get_collateral_info(&self) -> Result<CollateralInfo> {
         Ok(CollateralInfo {
@@ -208,13 +176,17 @@ impl<'info> CollateralContext<'info> {
 
 /// Liquidation management
 impl<'info> LiquidationContext<'info> {
-    /// Process liquidation for a single trove
+    /// Process liquidation for a single trove: `backstop_debt` is burned from the bad debt
+    /// backstop's funds in the protocol stablecoin vault, `pool_debt` from the stability pool vault
     pub fn liquidate_trove(
         &mut self,
         user: Pubkey,
-        debt_amount: u64,
+        backstop_debt: u64,
+        pool_debt: u64,
         collateral_amounts: Vec<(String, u64)>,
     ) -> Result<()> {
+        let debt_amount = backstop_debt.saturating_add(pool_debt);
+        
         // Calculate liquidation gains
         let mut total_collateral_gain = 0u64;
         for (_denom, amount) in &collateral_amounts {
@@ -222,30 +194,38 @@ impl<'info> LiquidationContext<'info> {
         }
         
         // Update global state
-        self.state.total_debt_amount = self.state.total_debt_amount.saturating_sub(debt_amount);
+        {
+            let mut state = self.state.load_mut()?;
+            state.total_debt_amount = state.total_debt_amount.saturating_sub(debt_amount);
+        }
         
         // Update total collateral amounts for each denomination
         for (denom, amount) in &collateral_amounts {
             self.update_total_collateral_amount(denom, *amount)?;
         }
         
-        // Burn stablecoins from protocol vault (PDA signer)
-        let burn_seeds = &[
-            b"protocol_stablecoin_vault".as_ref(),
-            &[Pubkey::find_program_address(&[b"protocol_stablecoin_vault"], &crate::ID).1],
-        ];
-        let burn_signer = &[&burn_seeds[..]];
+        // Burn stablecoins from each vault (PDA signers)
+        for (vault, seed, amount) in [
+            (&self.protocol_stablecoin_vault, b"protocol_stablecoin_vault".as_ref(), backstop_debt),
+            (&self.stability_pool_vault, b"stability_pool_vault".as_ref(), pool_debt),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+            let burn_seeds = &[seed, &[Pubkey::find_program_address(&[seed], &crate::ID).1]];
+            let burn_signer = &[&burn_seeds[..]];
 
-        let burn_ctx = CpiContext::new_with_signer(
-            self.token_program.to_account_info(),
-            Burn {
-                mint: self.stable_coin_mint.to_account_info(),
-                from: self.protocol_stablecoin_vault.to_account_info(),
-                authority: self.protocol_stablecoin_vault.to_account_info(),
-            },
-            burn_signer,
-        );
-        anchor_spl::token::burn(burn_ctx, debt_amount)?;
+            let burn_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.stable_coin_mint.to_account_info(),
+                    from: vault.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                burn_signer,
+            );
+            anchor_spl::token::burn(burn_ctx, amount)?;
+        }
         
         // Distribute liquidation gains to stakers
         self.distribute_liquidation_gains(collateral_amounts)?;
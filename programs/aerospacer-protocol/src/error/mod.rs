@@ -89,4 +89,196 @@ pub enum AerospacerProtocolError {
     
     #[msg("Invalid snapshot account - does not match expected PDA")]
     InvalidSnapshotAccount,
-}
\ No newline at end of file
+    
+    #[msg("Trove tag must not be all zeros")]
+    InvalidTroveTag,
+    
+    #[msg("Trove tag is not registered")]
+    UnknownTroveTag,
+    
+    #[msg("Tag registry is full")]
+    TagRegistryFull,
+    
+    #[msg("Oracle price is stale")]
+    StalePrice,
+    
+    #[msg("Invalid oracle fallback haircut")]
+    InvalidFallbackHaircut,
+    
+    #[msg("Reward emission account is required while emissions are enabled")]
+    MissingRewardEmission,
+    
+    #[msg("Frontend kickback rate must be at most 10000 bps")]
+    InvalidKickbackRate,
+    
+    #[msg("Frontend is not registered or does not match the supplied tag")]
+    UnknownFrontend,
+    
+    #[msg("Frontend kickback rate does not match the registered rate")]
+    FrontendKickbackMismatch,
+    
+    #[msg("Flash mint must be repaid by a repay_flash_mint instruction later in the same transaction")]
+    FlashMintNotRepaid,
+    
+    #[msg("A flash mint is already outstanding")]
+    FlashMintOutstanding,
+    
+    #[msg("Flash mint cannot be invoked through CPI")]
+    FlashMintCpiNotAllowed,
+    
+    #[msg("Trove status does not allow this transition")]
+    InvalidTroveStatusTransition,
+    
+    #[msg("PSM fee exceeds the maximum spread")]
+    InvalidPsmFee,
+    
+    #[msg("PSM swap would exceed the debt ceiling")]
+    PsmDebtCeilingExceeded,
+    
+    #[msg("PSM reserve is insufficient for this swap")]
+    PsmInsufficientReserve,
+    
+    #[msg("Redemption amount is below the configured minimum")]
+    RedemptionBelowMinimum,
+    
+    #[msg("Redemption touches more troves than allowed per transaction")]
+    TooManyRedemptionTroves,
+    
+    #[msg("Redemption volume cap for the current window reached")]
+    RedemptionCapExceeded,
+    
+    #[msg("Wallet already has the maximum number of troves")]
+    TooManyTroves,
+    
+    #[msg("Only closed or liquidated troves can be archived")]
+    TroveNotArchivable,
+    
+    #[msg("Account still holds a balance or is not a reclaimable user account")]
+    AccountNotReclaimable,
+    
+    #[msg("Denom must be 1-32 characters of letters, digits, '.', '_', '-' or '/'")]
+    InvalidDenom,
+    
+    #[msg("Keeper bounty payout account is required for a registered keeper")]
+    MissingKeeperBountyAccount,
+    
+    #[msg("Keeper bounty share exceeds the maximum")]
+    InvalidKeeperBounty,
+    
+    #[msg("Liquidator is not whitelisted while liquidations are permissioned")]
+    LiquidatorNotWhitelisted,
+    
+    #[msg("Liquidator whitelist is full")]
+    LiquidatorWhitelistFull,
+    
+    #[msg("Trove was modified earlier in this slot and cannot be liquidated or redeemed until the next")]
+    TroveLocked,
+    
+    #[msg("Stability pool covers the trove; liquidate it instead of auctioning")]
+    AuctionNotRequired,
+    
+    #[msg("Auction has ended or is sold out")]
+    AuctionClosed,
+    
+    #[msg("Auction is still running")]
+    AuctionNotSettleable,
+    
+    #[msg("Auction payment exceeds the bid's maximum")]
+    AuctionPriceAboveLimit,
+    
+    #[msg("Trove owner's collateral account is required to return the auction surplus")]
+    AuctionSurplusAccountRequired,
+    
+    #[msg("No rebalancer program is enabled")]
+    RebalancerDisabled,
+    
+    #[msg("Rebalancer hook moved collateral outside its interface")]
+    RebalancerInterfaceViolation,
+    
+    #[msg("Collateral denom is deactivated: no new troves or borrowing against it")]
+    CollateralDeactivated,
+    
+    #[msg("Liquidation threshold must be above 100% and at most the minimum collateral ratio")]
+    InvalidCollateralRatios,
+    
+    #[msg("Protocol fee must be below 100%")]
+    InvalidProtocolFee,
+    
+    #[msg("Oracle return data is missing, not from the oracle program or not for the requested denom")]
+    InvalidOracleResponse,
+    
+    #[msg("Redemption needs the denom's price account to order troves by live ICR")]
+    RedemptionPriceRequired,
+    
+    #[msg("Neighbor hints are required to keep the sorted troves list ordered")]
+    NeighborHintsRequired,
+    
+    #[msg("Stability pool vault holds less aUSD than the total stake")]
+    StabilityPoolUnderfunded,
+    
+    #[msg("Trove net debt is below the minimum net debt")]
+    NetDebtBelowMinimum,
+    
+    #[msg("The trove's liquidation reserve needs the liquidator's aUSD account")]
+    MissingLiquidationReserveAccount,
+    
+    #[msg("Redemptions are disabled during the bootstrap period")]
+    BootstrapPeriodRedemption,
+    
+    #[msg("Liquidation batch exceeds the bootstrap period limit")]
+    BootstrapLiquidationBatchTooLarge,
+    
+    #[msg("Trove index page does not hold the trove's entry or is not the index tail")]
+    InvalidTroveIndexPage,
+    
+    #[msg("Trove index entry still belongs to a live trove")]
+    TroveIndexEntryLive,
+    
+    #[msg("The fee accumulator needs its vault")]
+    MissingFeeAccumulatorVault,
+    
+    #[msg("No parked fees to distribute")]
+    NoAccumulatedFees,
+    
+    #[msg("aUSD mint authority is not held by the protocol's mint authority PDA")]
+    MintAuthorityNotAssigned,
+    
+    #[msg("Mint would take total trove debt past the trove debt cap")]
+    TroveDebtCapExceeded,
+    
+    #[msg("No mint authority migration is pending")]
+    NoMintAuthorityMigration,
+    
+    #[msg("Mint authority migration timelock has not passed")]
+    MintAuthorityMigrationTimelocked,
+    
+    #[msg("Token metadata name, symbol or URI is empty or too long")]
+    InvalidTokenMetadata,
+    
+    #[msg("Protocol config value is out of range")]
+    InvalidProtocolConfig,
+    
+    #[msg("Liquidation penalty is above the maximum")]
+    InvalidLiquidationPenalty,
+    
+    #[msg("Swap program is not whitelisted for leverage")]
+    SwapProgramNotWhitelisted,
+    
+    #[msg("Swap program whitelist is full")]
+    SwapProgramWhitelistFull,
+    
+    #[msg("Collateral received from the swap is worth too little of the aUSD spent")]
+    LeverageSlippageExceeded,
+    
+    #[msg("Opening a redemption ticket needs the ticket and the redemption escrow vault")]
+    RedemptionTicketRequired,
+    
+    #[msg("Every collateral denom the stability pool has paid out needs its snapshot accounts")]
+    MissingCollateralSnapshot,
+    
+    #[msg("Trove id must be below the per-wallet trove limit")]
+    InvalidTroveId,
+    
+    #[msg("Every collateral leg of the trove must be passed")]
+    IncompleteCollateralLegs,
+}
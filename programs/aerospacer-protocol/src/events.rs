@@ -0,0 +1,324 @@
+use anchor_lang::prelude::*;
+
+// Trove lifecycle events. Every event carries the trove's attribution tag
+// (all zeros when untagged) so indexers can attribute volume per frontend/bot.
+// Opens, adjustments and closes also echo the caller's optional memo (e.g. a
+// vault strategy id), so integrators can tag individual operations.
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TroveOperation {
+    AddCollateral,
+    RemoveCollateral,
+    BorrowLoan,
+    RepayLoan,
+    BatchRepay,
+    Deleverage,
+    Leverage,
+}
+
+#[event]
+pub struct TroveOpened {
+    pub owner: Pubkey,
+    pub trove_id: u16,
+    pub collateral_denom: String,
+    pub collateral_amount: u64,
+    pub debt_amount: u64,
+    pub icr: u64,
+    pub tag: [u8; 32],
+    pub memo: Option<[u8; 32]>,
+}
+
+#[event]
+pub struct TroveUpdated {
+    pub owner: Pubkey,
+    pub trove_id: u16,
+    pub collateral_denom: String,
+    pub operation: TroveOperation,
+    pub amount: u64,               // Amount of collateral or aUSD moved by the operation
+    pub collateral_amount: u64,    // Resulting trove collateral
+    pub debt_amount: u64,          // Resulting trove debt
+    pub icr: u64,
+    pub tag: [u8; 32],
+    pub memo: Option<[u8; 32]>,    // None for batch repays
+}
+
+#[event]
+pub struct TroveClosed {
+    pub owner: Pubkey,
+    pub trove_id: u16,
+    pub collateral_denom: String,
+    pub debt_repaid: u64,
+    pub collateral_returned: u64,
+    pub tag: [u8; 32],
+    pub memo: Option<[u8; 32]>,
+}
+
+#[event]
+pub struct TroveArchived {
+    pub owner: Pubkey,
+    pub trove_id: u16,
+    pub merkle_tree: Pubkey,
+    pub leaf_index: u64,           // Leaf holding the hash of the logged ArchivedTrove record
+}
+
+#[event]
+pub struct TroveTransferred {
+    pub from: Pubkey,
+    pub from_trove_id: u16,
+    pub to: Pubkey,
+    pub to_trove_id: u16,
+    pub debt_amount: u64,
+    pub collateral_denoms: Vec<String>, // Every collateral leg that moved, primary first
+}
+
+#[event]
+pub struct RentReclaimed {
+    pub owner: Pubkey,
+    pub accounts_closed: u32,
+    pub lamports: u64,             // Rent refunded to the owner
+}
+
+// Stability pool events. Each carries the pool composition after the operation
+// (total stake, P factor with its scale, epoch) so coverage can be charted from events alone.
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StakeOperation {
+    Stake,
+    Unstake,
+}
+
+#[event]
+pub struct StakeUpdated {
+    pub user: Pubkey,
+    pub operation: StakeOperation,
+    pub amount: u64,               // aUSD deposited or withdrawn
+    pub compounded_gains: u64,     // Fee yield rolled into the deposit (stake only)
+    pub deposit_amount: u64,       // Resulting user deposit
+    pub total_stake_amount: u64,
+    pub p_factor: u128,
+    pub current_scale: u8,
+    pub epoch: u64,
+}
+
+// Priority redemption lane events
+
+#[event]
+pub struct PriorityRedemptionModeUpdated {
+    pub treasury: Pubkey,
+    pub emergency_mode: bool,
+}
+
+#[event]
+pub struct PriorityRedemption {
+    pub treasury: Pubkey,
+    pub collateral_denom: String,
+    pub amount: u64,               // aUSD burned (no fee is charged on this lane)
+    pub collateral_sent: u64,
+    pub troves_redeemed: u32,
+}
+
+// Flash mint events
+
+#[event]
+pub struct FlashMintRepaid {
+    pub payer: Pubkey,
+    pub amount: u64,               // aUSD flash minted and burned again
+    pub fee: u64,                  // aUSD fee credited to stability depositors
+}
+
+// Peg Stability Module events
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PsmSwapDirection {
+    SwapIn,   // Reserve deposited, aUSD minted
+    SwapOut,  // aUSD burned, reserve withdrawn
+}
+
+#[event]
+pub struct PsmSwapped {
+    pub user: Pubkey,
+    pub direction: PsmSwapDirection,
+    pub reserve_amount: u64,       // Reserve deposited or withdrawn
+    pub stablecoin_amount: u64,    // aUSD received or paid by the user (fee included for SwapOut)
+    pub fee: u64,                  // aUSD fee credited to stability depositors
+    pub total_minted: u64,         // aUSD backed by the reserve after the swap
+}
+
+// Collateral listing
+
+#[event]
+pub struct CollateralRegistered {
+    pub denom: String,
+    pub mint: Pubkey,              // Bound to the denom by its vault
+    pub vault: Pubkey,
+}
+
+#[event]
+pub struct CollateralDeactivationUpdated {
+    pub denom: String,
+    pub deactivated: bool,         // New troves and borrow increases are blocked while set
+}
+
+// Undistributed seizure events
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SeizureRoute {
+    Backstop,       // Transferred to a backstop/treasury token account
+    Auction,        // Transferred to an auction program's token account
+    StabilityPool,  // Distributed to current stability pool depositors (S factor)
+}
+
+#[event]
+pub struct UndistributedSeizureRouted {
+    pub denom: String,
+    pub route: SeizureRoute,
+    pub amount: u64,
+    pub destination: Pubkey,       // Receiving token account (default for StabilityPool)
+    pub remaining: u64,            // Still unrouted after this call
+}
+
+#[event]
+pub struct SeizedCollateralForwarded {
+    pub denom: String,
+    pub amount: u64,
+    pub rebalancer: Pubkey,        // Hook program invoked
+    pub inbox: Pubkey,             // Hook's receiving token account
+    pub remaining: u64,            // Still unrouted after this call
+}
+
+// Public-goods share of liquidation penalties
+
+#[event]
+pub struct PublicGoodsAccrued {
+    pub denom: String,
+    pub amount: u64,               // Held back from this liquidation
+    pub penalty: u64,              // Collateral seized in excess of the debt's value
+    pub public_goods_bps: u16,
+    pub pending: u64,              // Booked and not yet routed after this liquidation
+}
+
+// Keeper liveness
+
+#[event]
+pub struct HeartbeatRegistered {
+    pub keeper: Pubkey,
+    pub slot: u64,
+    pub previous_slot: u64,        // Last heartbeat before this one (0 on first registration)
+    pub heartbeats: u64,
+}
+
+// Liquidation keeper bounties
+
+#[event]
+pub struct KeeperBountyPaid {
+    pub keeper: Pubkey,
+    pub denom: String,
+    pub collateral_bounty: u64,    // Penalty share paid in `denom`
+    pub flat_bounty: u64,          // aUSD paid from the KeeperBountyFund
+    pub troves: u64,               // Troves liquidated in this call
+    pub debt_liquidated: u64,
+}
+
+// Liquidations below 100% ICR
+
+#[event]
+pub struct BadDebtLiquidated {
+    pub owner: Pubkey,
+    pub collateral_denom: String,
+    pub debt_amount: u64,
+    pub icr: u64,
+    pub shortfall: u64,            // Debt not backed by the seized collateral
+    pub absorbed: u64,             // Covered by the BadDebtBackstop
+    pub socialized: u64,           // Redistributed to active troves
+}
+
+// Liquidations under a denom liquidation penalty
+
+#[event]
+pub struct LiquidationSurplusLeft {
+    pub owner: Pubkey,
+    pub trove_id: u16,
+    pub collateral_denom: String,
+    pub seized: u64,               // Debt's worth of collateral plus the penalty
+    pub surplus: u64,              // Left on the trove for close_trove
+    pub liquidation_penalty_bps: u16,
+}
+
+// Dutch auction liquidations
+
+#[event]
+pub struct LiquidationAuctionStarted {
+    pub owner: Pubkey,
+    pub trove_id: u16,
+    pub collateral_denom: String,
+    pub initiator: Pubkey,
+    pub debt_amount: u64,
+    pub lot_size: u64,
+    pub icr: u64,
+    pub start_price: u128,         // aUSD asked for the whole lot
+    pub floor_price: u128,
+    pub ends_at: i64,
+}
+
+#[event]
+pub struct LiquidationAuctionBid {
+    pub owner: Pubkey,
+    pub trove_id: u16,
+    pub bidder: Pubkey,
+    pub collateral: u64,           // Collateral bought
+    pub payment: u64,              // aUSD burned
+    pub collateral_remaining: u64,
+    pub debt_remaining: u64,
+}
+
+#[event]
+pub struct LiquidationAuctionSettled {
+    pub owner: Pubkey,
+    pub trove_id: u16,
+    pub collateral_denom: String,
+    pub debt_raised: u64,
+    pub debt_redistributed: u64,   // Unraised debt socialized to active troves
+    pub collateral_redistributed: u64,
+    pub surplus_returned: u64,     // Unsold collateral returned to the owner after full repayment
+}
+
+#[event]
+pub struct AccumulatedFeesDistributed {
+    pub amount: u64,               // Parked fees sent through aerospacer-fees by this call
+    pub total_distributed: u64,
+}
+
+#[event]
+pub struct MintAuthorityMigrationProposed {
+    pub new_authority: Pubkey,
+    pub eta: i64,                  // Earliest execute_mint_authority_migration
+}
+
+#[event]
+pub struct MintAuthorityMigrationCancelled {
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct MintAuthorityMigrated {
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub old_minimum_collateral_ratio: u64,
+    pub new_minimum_collateral_ratio: u64,
+    pub old_protocol_fee: u8,
+    pub new_protocol_fee: u8,
+}
+
+#[event]
+pub struct LeverageSwapped {
+    pub owner: Pubkey,
+    pub trove_id: u16,
+    pub collateral_denom: String,
+    pub loan_amount: u64,          // Gross aUSD borrowed, fee included
+    pub stablecoin_spent: u64,     // aUSD the swap took from the owner
+    pub collateral_received: u64,  // Deposited into the trove
+    pub swap_program: Pubkey,
+}
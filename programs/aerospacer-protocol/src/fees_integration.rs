@@ -1,24 +1,90 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{TokenAccount, Transfer};
 use crate::utils::*;
 use crate::error::*;
+use crate::state::{FeeAccumulator, StateAccount};
+use crate::trove_management::distribute_fee_yield_to_stakers;
+
+/// Origin of a fee, mirroring aerospacer-fees' FeeSource (same variant order, so the same Borsh
+/// encoding); the fees program keeps a revenue counter per source
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeeSource {
+    Borrow,             // Opening and borrowing fees
+    Redemption,
+    FlashMint,
+    LiquidationPenalty,
+}
+
+impl FeeSource {
+    pub const ALL: [FeeSource; crate::state::FEE_SOURCE_COUNT] =
+        [FeeSource::Borrow, FeeSource::Redemption, FeeSource::FlashMint, FeeSource::LiquidationPenalty];
+}
+
+/// Fee accumulator of an instruction charging a protocol fee, with the vault parked fees go to
+pub struct FeeAccumulatorAccounts<'a, 'info> {
+    pub fee_accumulator: &'a mut FeeAccumulator,
+    pub fee_accumulator_vault: AccountInfo<'info>,
+}
+
+impl<'a, 'info> FeeAccumulatorAccounts<'a, 'info> {
+    /// The optional accumulator accounts of an instruction; the vault is required with the accumulator
+    pub fn new(
+        fee_accumulator: Option<&'a mut FeeAccumulator>,
+        fee_accumulator_vault: Option<&AccountInfo<'info>>,
+    ) -> Result<Option<Self>> {
+        let Some(fee_accumulator) = fee_accumulator else {
+            return Ok(None);
+        };
+        let fee_accumulator_vault = fee_accumulator_vault
+            .ok_or(AerospacerProtocolError::MissingFeeAccumulatorVault)?
+            .clone();
+        Ok(Some(Self { fee_accumulator, fee_accumulator_vault }))
+    }
+}
+
+/// Accounts a protocol fee is paid with: the aerospacer-fees CPI accounts, the payer and its aUSD
+/// account, and the bump of the protocol's fee_caller PDA
+pub struct ProtocolFeeAccounts<'info> {
+    pub fees_program: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub fees_state: AccountInfo<'info>,
+    pub fee_caller: AccountInfo<'info>,
+    pub fee_caller_bump: u8,
+    pub payer_token_account: AccountInfo<'info>,
+    pub stability_pool_token_account: AccountInfo<'info>,
+    pub fee_vault: AccountInfo<'info>,
+    pub fee_epoch_stats: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
 
 /// Process protocol fee collection and distribution via CPI to aerospacer-fees
 /// This function handles the complete fee flow:
 /// 1. Calculate fee amount
 /// 2. Call distribute_fee instruction via CPI (which handles token transfers)
 /// 3. Return net amount after fee
+///
+/// With `fee_accumulator`, a fee below its min_distribution_amount skips the CPI: it is moved
+/// into the accumulator vault and distributed later by distribute_accumulated.
 pub fn process_protocol_fee<'info>(
     operation_amount: u64,
     protocol_fee_percentage: u8,
-    fees_program: AccountInfo<'info>,
-    payer: AccountInfo<'info>,
-    fees_state: AccountInfo<'info>,
-    payer_token_account: AccountInfo<'info>,
-    stability_pool_token_account: AccountInfo<'info>,
-    fee_address_1_token_account: AccountInfo<'info>,
-    fee_address_2_token_account: AccountInfo<'info>,
-    token_program: AccountInfo<'info>,
+    source: FeeSource,
+    accounts: ProtocolFeeAccounts<'info>,
+    fee_accumulator: Option<FeeAccumulatorAccounts<'_, 'info>>,
 ) -> Result<u64> {
+    let ProtocolFeeAccounts {
+        fees_program,
+        payer,
+        fees_state,
+        fee_caller,
+        fee_caller_bump,
+        payer_token_account,
+        stability_pool_token_account,
+        fee_vault,
+        fee_epoch_stats,
+        token_program,
+    } = accounts;
+    
     // Calculate fee amount
     let fee_amount = calculate_protocol_fee(operation_amount, protocol_fee_percentage)?;
     
@@ -26,22 +92,42 @@ pub fn process_protocol_fee<'info>(
         return Ok(operation_amount);
     }
     
-    msg!("Processing protocol fee: {} aUSD ({}%)", fee_amount, protocol_fee_percentage);
+    if let Some(accumulator) = fee_accumulator.filter(|accumulator| fee_amount < accumulator.fee_accumulator.min_distribution_amount) {
+        anchor_spl::token::transfer(
+            CpiContext::new(
+                token_program,
+                Transfer {
+                    from: payer_token_account,
+                    to: accumulator.fee_accumulator_vault,
+                    authority: payer,
+                },
+            ),
+            fee_amount,
+        )?;
+        accumulator.fee_accumulator.park(source, fee_amount)?;
+        msg!("Protocol fee parked: {} aUSD ({:?}, pending: {})", fee_amount, source, accumulator.fee_accumulator.pending[source as usize]);
+        return calculate_net_amount_after_fee(operation_amount, protocol_fee_percentage);
+    }
+    
+    msg!("Processing protocol fee: {} aUSD ({}%, {:?})", fee_amount, protocol_fee_percentage, source);
     msg!("Operation amount: {} aUSD", operation_amount);
     
     // Call distribute_fee instruction via CPI
     // The fee contract will handle transferring tokens from payer_token_account
-    // to the appropriate destinations (stability pool or fee addresses)
+    // to the appropriate destination (stability pool, or its fee vault for the fee addresses)
     distribute_fee_via_cpi(
         &fees_program,
         &payer,
         &fees_state,
+        &fee_caller,
         &payer_token_account,
         &stability_pool_token_account,
-        &fee_address_1_token_account,
-        &fee_address_2_token_account,
+        &fee_vault,
+        &fee_epoch_stats,
         &token_program,
         fee_amount,
+        source,
+        &[&[b"fee_caller", &[fee_caller_bump]]],
     )?;
     
     msg!("Fee distributed successfully: {} aUSD", fee_amount);
@@ -50,14 +136,44 @@ pub fn process_protocol_fee<'info>(
     calculate_net_amount_after_fee(operation_amount, protocol_fee_percentage)
 }
 
+/// aUSD balance of the fee contract's stability pool destination, or None when it is not the
+/// protocol stablecoin vault (only fees routed into the vault can be claimed by depositors)
+pub fn stability_pool_vault_balance(stability_pool_token_account: &AccountInfo) -> Result<Option<u64>> {
+    let (vault, _bump) = Pubkey::find_program_address(&[b"protocol_stablecoin_vault"], &crate::ID);
+    if stability_pool_token_account.key() != vault {
+        return Ok(None);
+    }
+    
+    let data = stability_pool_token_account.try_borrow_data()?;
+    let token_account = TokenAccount::try_deserialize(&mut &data[..])?;
+    Ok(Some(token_account.amount))
+}
+
+/// Credit whatever the fee CPI routed into the stability pool vault to depositors (G factor)
+/// `balance_before` is the result of `stability_pool_vault_balance` taken before the CPI
+pub fn accrue_routed_fee_yield(
+    state: &mut StateAccount,
+    stability_pool_token_account: &AccountInfo,
+    balance_before: Option<u64>,
+) -> Result<()> {
+    let Some(balance_before) = balance_before else {
+        return Ok(());
+    };
+    let balance_after = stability_pool_vault_balance(stability_pool_token_account)?
+        .ok_or(AerospacerProtocolError::InvalidAddress)?;
+    
+    // Zero when the fee contract booked the fee for the fee addresses instead
+    let routed = balance_after.saturating_sub(balance_before);
+    distribute_fee_yield_to_stakers(state, routed)
+}
+
 /// Validate fees contract accounts
 pub fn validate_fees_accounts<'info>(
     fees_program: &AccountInfo<'info>,
     fees_state: &AccountInfo<'info>,
     payer_token_account: &AccountInfo<'info>,
     stability_pool_token_account: &AccountInfo<'info>,
-    fee_address_1_token_account: &AccountInfo<'info>,
-    fee_address_2_token_account: &AccountInfo<'info>,
+    fee_vault: &AccountInfo<'info>,
     token_program: &AccountInfo<'info>,
 ) -> Result<()> {
     // Validate fees program
@@ -84,12 +200,7 @@ pub fn validate_fees_accounts<'info>(
     );
     
     require!(
-        *fee_address_1_token_account.owner == token_program.key(),
-        AerospacerProtocolError::Unauthorized
-    );
-    
-    require!(
-        *fee_address_2_token_account.owner == token_program.key(),
+        *fee_vault.owner == token_program.key(),
         AerospacerProtocolError::Unauthorized
     );
     
@@ -98,20 +209,26 @@ pub fn validate_fees_accounts<'info>(
 }
 
 /// Call distribute_fee instruction on aerospacer-fees contract via CPI
-/// The fee contract will transfer tokens from payer to destinations directly
-fn distribute_fee_via_cpi<'info>(
+/// The fee contract will transfer tokens from payer to destinations directly.
+/// `fee_caller` is the protocol's fee_caller PDA, allowlisted in the fees program; `signer_seeds`
+/// must hold its seeds, and the payer's too when it is a PDA.
+#[allow(clippy::too_many_arguments)]
+pub fn distribute_fee_via_cpi<'info>(
     fees_program: &AccountInfo<'info>,
     payer: &AccountInfo<'info>,
     fees_state: &AccountInfo<'info>,
+    fee_caller: &AccountInfo<'info>,
     payer_token_account: &AccountInfo<'info>,
     stability_pool_token_account: &AccountInfo<'info>,
-    fee_address_1_token_account: &AccountInfo<'info>,
-    fee_address_2_token_account: &AccountInfo<'info>,
+    fee_vault: &AccountInfo<'info>,
+    fee_epoch_stats: &AccountInfo<'info>,
     token_program: &AccountInfo<'info>,
     fee_amount: u64,
+    source: FeeSource,
+    signer_seeds: &[&[&[u8]]],
 ) -> Result<()> {
     use anchor_lang::solana_program::instruction::Instruction;
-    use anchor_lang::solana_program::program::invoke;
+    use anchor_lang::solana_program::program::invoke_signed;
     use anchor_lang::solana_program::hash::hash;
     
     msg!("Distributing fee via aerospacer-fees contract CPI");
@@ -123,9 +240,10 @@ fn distribute_fee_via_cpi<'info>(
     #[derive(AnchorSerialize)]
     struct DistributeFeeParams {
         fee_amount: u64,
+        source: FeeSource,
     }
     
-    let params = DistributeFeeParams { fee_amount };
+    let params = DistributeFeeParams { fee_amount, source };
     
     // Calculate instruction discriminator: first 8 bytes of SHA256("global:distribute_fee")
     let preimage = b"global:distribute_fee";
@@ -142,10 +260,11 @@ fn distribute_fee_via_cpi<'info>(
     let account_metas = vec![
         anchor_lang::solana_program::instruction::AccountMeta::new(*payer.key, true),           // ✅ payer as signer
         anchor_lang::solana_program::instruction::AccountMeta::new(*fees_state.key, false),    // ✅ fees_state as writable, not signer
+        anchor_lang::solana_program::instruction::AccountMeta::new_readonly(*fee_caller.key, true), // ✅ fee_caller as PDA signer
         anchor_lang::solana_program::instruction::AccountMeta::new(*payer_token_account.key, false),     // ✅ payer_token_account as writable, not signer
         anchor_lang::solana_program::instruction::AccountMeta::new(*stability_pool_token_account.key, false), // ✅ stability_pool_token_account as writable, not signer
-        anchor_lang::solana_program::instruction::AccountMeta::new(*fee_address_1_token_account.key, false),   // ✅ fee_address_1_token_account as writable, not signer
-        anchor_lang::solana_program::instruction::AccountMeta::new(*fee_address_2_token_account.key, false),   // ✅ fee_address_2_token_account as writable, not signer
+        anchor_lang::solana_program::instruction::AccountMeta::new(*fee_vault.key, false),      // ✅ fee_vault as writable, not signer
+        anchor_lang::solana_program::instruction::AccountMeta::new(*fee_epoch_stats.key, false), // ✅ fee_epoch_stats as writable, not signer
         anchor_lang::solana_program::instruction::AccountMeta::new_readonly(*token_program.key, false),       // ✅ token_program as readonly
     ];
     
@@ -162,19 +281,148 @@ fn distribute_fee_via_cpi<'info>(
         fees_program.to_account_info(),
         payer.to_account_info(),
         fees_state.to_account_info(),
+        fee_caller.to_account_info(),
         payer_token_account.to_account_info(),
         stability_pool_token_account.to_account_info(),
-        fee_address_1_token_account.to_account_info(),
-        fee_address_2_token_account.to_account_info(),
+        fee_vault.to_account_info(),
+        fee_epoch_stats.to_account_info(),
         token_program.to_account_info(),
     ];
     
-    invoke(&ix, &account_infos)?;
+    invoke_signed(&ix, &account_infos, signer_seeds)?;
     
     msg!("Fee distribution CPI completed successfully");
     Ok(())
 }
 
+/// Call distribute_collateral_fee on aerospacer-fees via CPI, for fees paid in a collateral
+/// token (liquidation penalties, PSM spreads). The fee always lands in the fee vault of
+/// `payer_token_account`'s mint, split by the fees program's recipient weights.
+/// `payer` owns `payer_token_account`; `signer_seeds` must hold the fee_caller PDA's seeds, and
+/// the payer's too when it is a PDA.
+#[allow(clippy::too_many_arguments)]
+pub fn distribute_collateral_fee_via_cpi<'info>(
+    fees_program: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    fees_state: &AccountInfo<'info>,
+    fee_caller: &AccountInfo<'info>,
+    payer_token_account: &AccountInfo<'info>,
+    fee_claims: &AccountInfo<'info>,
+    fee_vault: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    fee_amount: u64,
+    source: FeeSource,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+    use anchor_lang::solana_program::program::invoke_signed;
+    use anchor_lang::solana_program::hash::hash;
+    
+    msg!("Distributing collateral fee via aerospacer-fees contract CPI");
+    msg!("Fee amount: {} ({:?})", fee_amount, source);
+    msg!("Fees program: {}", fees_program.key());
+    
+    #[derive(AnchorSerialize)]
+    struct DistributeCollateralFeeParams {
+        fee_amount: u64,
+        source: FeeSource,
+    }
+    
+    // Instruction discriminator: first 8 bytes of SHA256("global:distribute_collateral_fee")
+    let hash_result = hash(b"global:distribute_collateral_fee");
+    let mut instruction_data = hash_result.to_bytes()[..8].to_vec();
+    DistributeCollateralFeeParams { fee_amount, source }.serialize(&mut instruction_data)?;
+    
+    let account_metas = vec![
+        AccountMeta::new(*payer.key, true),                          // payer
+        AccountMeta::new_readonly(*fees_state.key, false),           // state
+        AccountMeta::new_readonly(*fee_caller.key, true),            // caller_authority (PDA signer)
+        AccountMeta::new(*payer_token_account.key, false),           // payer_token_account
+        AccountMeta::new(*fee_claims.key, false),                    // fee_claims
+        AccountMeta::new(*fee_vault.key, false),                     // fee_vault
+        AccountMeta::new_readonly(*token_program.key, false),        // token_program
+    ];
+    
+    let ix = Instruction {
+        program_id: *fees_program.key,
+        accounts: account_metas,
+        data: instruction_data,
+    };
+    
+    let account_infos = vec![
+        fees_program.to_account_info(),
+        payer.to_account_info(),
+        fees_state.to_account_info(),
+        fee_caller.to_account_info(),
+        payer_token_account.to_account_info(),
+        fee_claims.to_account_info(),
+        fee_vault.to_account_info(),
+        token_program.to_account_info(),
+    ];
+    
+    invoke_signed(&ix, &account_infos, signer_seeds)?;
+    
+    msg!("Collateral fee distribution CPI completed successfully");
+    Ok(())
+}
+
+/// Call route_public_goods on aerospacer-fees via CPI
+/// `vault` is both the paying token account and its owner, signing with `signer_seeds`
+pub fn route_public_goods_via_cpi<'info>(
+    fees_program: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    public_goods_config: &AccountInfo<'info>,
+    public_goods_token_account: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+    use anchor_lang::solana_program::program::invoke_signed;
+    use anchor_lang::solana_program::hash::hash;
+    
+    msg!("Routing public goods share via aerospacer-fees contract CPI");
+    msg!("Amount: {}", amount);
+    msg!("Fees program: {}", fees_program.key());
+    
+    #[derive(AnchorSerialize)]
+    struct RoutePublicGoodsParams {
+        amount: u64,
+    }
+    
+    // Instruction discriminator: first 8 bytes of SHA256("global:route_public_goods")
+    let hash_result = hash(b"global:route_public_goods");
+    let mut instruction_data = hash_result.to_bytes()[..8].to_vec();
+    RoutePublicGoodsParams { amount }.serialize(&mut instruction_data)?;
+    
+    let account_metas = vec![
+        AccountMeta::new_readonly(*vault.key, true),                 // source_authority (PDA signer)
+        AccountMeta::new(*public_goods_config.key, false),           // public_goods_config
+        AccountMeta::new(*vault.key, false),                         // source_token_account
+        AccountMeta::new(*public_goods_token_account.key, false),    // public_goods_token_account
+        AccountMeta::new_readonly(*token_program.key, false),        // token_program
+    ];
+    
+    let ix = Instruction {
+        program_id: *fees_program.key,
+        accounts: account_metas,
+        data: instruction_data,
+    };
+    
+    let account_infos = vec![
+        fees_program.to_account_info(),
+        vault.to_account_info(),
+        public_goods_config.to_account_info(),
+        public_goods_token_account.to_account_info(),
+        token_program.to_account_info(),
+    ];
+    
+    invoke_signed(&ix, &account_infos, signer_seeds)?;
+    
+    msg!("Public goods routing CPI completed successfully");
+    Ok(())
+}
+
 /// Initialize fees contract if needed
 pub fn initialize_fees_contract_if_needed<'info>(
     fees_program: &AccountInfo<'info>,
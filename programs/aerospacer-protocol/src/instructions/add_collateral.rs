@@ -5,13 +5,26 @@ use crate::error::*;
 use crate::trove_management::*;
 use crate::account_management::*;
 use crate::oracle::*;
+use crate::events::{TroveOperation, TroveUpdated};
+use crate::validation::{ValidateParams, validate_amount, validate_denom, validate_optional_pubkey};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct AddCollateralParams {
     pub amount: u64,
     pub collateral_denom: String,
+    pub trove_id: u16, // Which of the owner's troves (0 = the wallet's first trove)
     pub prev_node_id: Option<Pubkey>,
     pub next_node_id: Option<Pubkey>,
+    pub memo: Option<[u8; 32]>, // Optional integrator memo, echoed in the emitted event
+}
+
+impl ValidateParams for AddCollateralParams {
+    fn validate(&self) -> Result<()> {
+        validate_amount(self.amount)?;
+        validate_denom(&self.collateral_denom)?;
+        validate_optional_pubkey(&self.prev_node_id)?;
+        validate_optional_pubkey(&self.next_node_id)
+    }
 }
 
 #[derive(Accounts)]
@@ -19,33 +32,37 @@ pub struct AddCollateralParams {
 pub struct AddCollateral<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
+    
+    /// CHECK: Trove owner - the signer itself, or the owner who approved the signer via trove_delegate
+    pub trove_owner: UncheckedAccount<'info>,
 
     #[account(
         mut,
-        seeds = [b"user_debt_amount", user.key().as_ref()],
+        seeds = [b"user_debt_amount", trove_owner.key().as_ref(), trove_id_seed(params.trove_id).as_slice()],
         bump,
-        constraint = user_debt_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
+        constraint = user_debt_amount.owner == trove_owner.key() @ AerospacerProtocolError::Unauthorized,
+        constraint = user_debt_amount.is_active() @ AerospacerProtocolError::TroveDoesNotExist
     )]
     pub user_debt_amount: Account<'info, UserDebtAmount>,
 
     #[account(
         mut,
-        seeds = [b"user_collateral_amount", user.key().as_ref(), params.collateral_denom.as_bytes()],
+        seeds = [b"user_collateral_amount", trove_owner.key().as_ref(), trove_id_seed(params.trove_id).as_slice(), params.collateral_denom.as_bytes()],
         bump,
-        constraint = user_collateral_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
+        constraint = user_collateral_amount.owner == trove_owner.key() @ AerospacerProtocolError::Unauthorized
     )]
     pub user_collateral_amount: Account<'info, UserCollateralAmount>,
 
     #[account(
         mut,
-        seeds = [b"liquidity_threshold", user.key().as_ref()],
+        seeds = [b"liquidity_threshold", trove_owner.key().as_ref(), trove_id_seed(params.trove_id).as_slice()],
         bump,
-        constraint = liquidity_threshold.owner == user.key() @ AerospacerProtocolError::Unauthorized
+        constraint = liquidity_threshold.owner == trove_owner.key() @ AerospacerProtocolError::Unauthorized
     )]
     pub liquidity_threshold: Account<'info, LiquidityThreshold>,
 
     #[account(mut)]
-    pub state: Account<'info, StateAccount>,
+    pub state: AccountLoader<'info, StateAccount>,
 
     #[account(
         mut,
@@ -57,12 +74,10 @@ pub struct AddCollateral<'info> {
     pub collateral_mint: Account<'info, Mint>,
 
     #[account(
-        init_if_needed,
-        payer = user,
-        token::mint = collateral_mint,
-        token::authority = protocol_collateral_account,
+        mut,
         seeds = [b"protocol_collateral_vault", params.collateral_denom.as_bytes()],
-        bump
+        bump,
+        constraint = protocol_collateral_account.mint == collateral_mint.key() @ AerospacerProtocolError::InvalidMint
     )]
     pub protocol_collateral_account: Account<'info, TokenAccount>,
 
@@ -72,7 +87,13 @@ pub struct AddCollateral<'info> {
         seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
         bump
     )]
-    pub total_collateral_amount: Account<'info, TotalCollateralAmount>,
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+
+    #[account(
+        seeds = [b"collateral_config", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
 
     // Oracle context - UncheckedAccount to reduce stack usage
     /// CHECK: Our oracle program - validated against state in handler
@@ -90,6 +111,33 @@ pub struct AddCollateral<'info> {
 
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+    
+    // Keeper-refreshed price (see refresh_price) - read instead of the oracle while fresh, and the
+    // last-good price used if the oracle feed is stale (see StateAccount.oracle_fallback_enabled)
+    #[account(
+        seeds = [b"price_cache", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub price_cache: Option<Box<Account<'info, PriceCache>>>,
+    
+    // Delegate-signer path: required when user is not the trove owner
+    #[account(
+        seeds = [b"trove_delegate", trove_owner.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub trove_delegate: Option<Account<'info, TroveDelegate>>,
+    
+    /// Optional operation history, appended to when passed
+    #[account(
+        mut,
+        seeds = [b"trove_history", trove_owner.key().as_ref()],
+        bump
+    )]
+    pub trove_history: Option<Box<Account<'info, TroveHistory>>>,
+    
+    // remaining_accounts: optional neighbor hints [prev LiquidityThreshold, next LiquidityThreshold],
+    // then for every other collateral denom the trove holds:
+    // [UserCollateralAmount (writable), TotalCollateralAmount, pyth_price_account for that denom]
 }
 
 impl<'info> AddCollateral<'info> {
@@ -98,21 +146,23 @@ impl<'info> AddCollateral<'info> {
     }
 }
 
-pub fn handler(ctx: Context<AddCollateral>, params: AddCollateralParams) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, AddCollateral<'info>>, params: AddCollateralParams) -> Result<()> {
+    params.validate()?;
+    
     // Validate oracle accounts
     require!(
-        ctx.accounts.oracle_program.key() == ctx.accounts.state.oracle_helper_addr,
+        ctx.accounts.oracle_program.key() == ctx.accounts.state.load()?.oracle_helper_addr,
         AerospacerProtocolError::Unauthorized
     );
     require!(
-        ctx.accounts.oracle_state.key() == ctx.accounts.state.oracle_state_addr,
+        ctx.accounts.oracle_state.key() == ctx.accounts.state.load()?.oracle_state_addr,
         AerospacerProtocolError::Unauthorized
     );
     
-    // Validate input parameters
+    // Signer is the owner, or a delegate the owner approved (trove_delegate seeds bind both keys)
     require!(
-        params.amount > 0,
-        AerospacerProtocolError::InvalidAmount
+        ctx.accounts.user.key() == ctx.accounts.trove_owner.key() || ctx.accounts.trove_delegate.is_some(),
+        AerospacerProtocolError::Unauthorized
     );
     
     require!(
@@ -120,27 +170,47 @@ pub fn handler(ctx: Context<AddCollateral>, params: AddCollateralParams) -> Resu
         AerospacerProtocolError::InsufficientCollateral
     );
     
+    // Multi-denom troves: the other collateral legs follow the neighbor hints in remaining_accounts
+    // as [UserCollateralAmount, TotalCollateralAmount, pyth_price_account] triples
+    let hint_count = params.prev_node_id.is_some() as usize + params.next_node_id.is_some() as usize;
     require!(
-        !params.collateral_denom.is_empty(),
-        AerospacerProtocolError::InvalidAmount
+        ctx.remaining_accounts.len() >= hint_count,
+        AerospacerProtocolError::InvalidList
     );
+    let other_collateral_value = {
+        let oracle_ctx = OracleContext {
+            oracle_program: ctx.accounts.oracle_program.to_account_info(),
+            oracle_state: ctx.accounts.oracle_state.to_account_info(),
+            pyth_price_account: ctx.accounts.pyth_price_account.to_account_info(),
+            clock: ctx.accounts.clock.to_account_info(),
+            max_price_age: PriceReadClass::Derisking.max_price_age(&*ctx.accounts.state.load()?),
+            fallback: None,
+            cached_price: None,
+        };
+        value_other_collateral_legs(
+            &ctx.remaining_accounts[hint_count..],
+            &mut ctx.accounts.user_debt_amount,
+            &params.collateral_denom,
+            &oracle_ctx,
+        )?
+    };
     
     // Create contexts in scoped block to reduce stack usage
     let result = {
         let mut trove_ctx = TroveContext {
-            user: ctx.accounts.user.clone(),
-            user_debt_amount: ctx.accounts.user_debt_amount.clone(),
-            liquidity_threshold: ctx.accounts.liquidity_threshold.clone(),
-            state: ctx.accounts.state.clone(),
+            user: &ctx.accounts.user,
+            user_debt_amount: &mut ctx.accounts.user_debt_amount,
+            liquidity_threshold: &mut ctx.accounts.liquidity_threshold,
+            state: &ctx.accounts.state,
         };
         
         let mut collateral_ctx = CollateralContext {
-            user: ctx.accounts.user.clone(),
-            user_collateral_amount: ctx.accounts.user_collateral_amount.clone(),
-            user_collateral_account: ctx.accounts.user_collateral_account.clone(),
-            protocol_collateral_account: ctx.accounts.protocol_collateral_account.clone(),
-            total_collateral_amount: ctx.accounts.total_collateral_amount.clone(),
-            token_program: ctx.accounts.token_program.clone(),
+            user: &ctx.accounts.user,
+            user_collateral_amount: &mut ctx.accounts.user_collateral_amount,
+            user_collateral_account: &ctx.accounts.user_collateral_account,
+            protocol_collateral_account: &ctx.accounts.protocol_collateral_account,
+            total_collateral_amount: &ctx.accounts.total_collateral_amount,
+            token_program: &ctx.accounts.token_program,
         };
         
         let oracle_ctx = OracleContext {
@@ -148,6 +218,18 @@ pub fn handler(ctx: Context<AddCollateral>, params: AddCollateralParams) -> Resu
             oracle_state: ctx.accounts.oracle_state.to_account_info(),
             pyth_price_account: ctx.accounts.pyth_price_account.to_account_info(),
             clock: ctx.accounts.clock.to_account_info(),
+            max_price_age: PriceReadClass::Derisking.max_price_age(&*ctx.accounts.state.load()?),
+            fallback: PriceFallback::from_cache(
+                &*ctx.accounts.state.load()?,
+                ctx.accounts.price_cache.as_deref().map(|c| &**c),
+                &params.collateral_denom,
+            ),
+            cached_price: PriceData::from_cache(
+                &*ctx.accounts.state.load()?,
+                ctx.accounts.price_cache.as_deref().map(|c| &**c),
+                &params.collateral_denom,
+                PriceReadClass::Derisking.max_price_age(&*ctx.accounts.state.load()?),
+            )?,
         };
         
         // Use TroveManager for clean implementation
@@ -157,10 +239,10 @@ pub fn handler(ctx: Context<AddCollateral>, params: AddCollateralParams) -> Resu
             &oracle_ctx,
             params.amount,
             params.collateral_denom.clone(),
+            other_collateral_value,
+            ctx.accounts.collateral_config.minimum_collateral_ratio,
         )?;
         
-        // Update state before contexts are dropped
-        ctx.accounts.state.total_debt_amount = trove_ctx.state.total_debt_amount;
         
         Ok::<_, Error>(result)
     }?;
@@ -185,9 +267,11 @@ pub fn handler(ctx: Context<AddCollateral>, params: AddCollateralParams) -> Resu
         );
         
         let prev_ratio = prev_threshold.ratio;
+        
+        let prev_trove_id = prev_threshold.trove_id;
         drop(prev_data);
         
-        sorted_troves::verify_liquidity_threshold_pda(prev_lt, prev_id, ctx.program_id)?;
+        sorted_troves::verify_liquidity_threshold_pda(prev_lt, prev_id, prev_trove_id, ctx.program_id)?;
         
         Some(prev_ratio)
     } else {
@@ -210,9 +294,11 @@ pub fn handler(ctx: Context<AddCollateral>, params: AddCollateralParams) -> Resu
         );
         
         let next_ratio = next_threshold.ratio;
+        
+        let next_trove_id = next_threshold.trove_id;
         drop(next_data);
         
-        sorted_troves::verify_liquidity_threshold_pda(next_lt, next_id, ctx.program_id)?;
+        sorted_troves::verify_liquidity_threshold_pda(next_lt, next_id, next_trove_id, ctx.program_id)?;
         
         Some(next_ratio)
     } else {
@@ -223,19 +309,42 @@ pub fn handler(ctx: Context<AddCollateral>, params: AddCollateralParams) -> Resu
         sorted_troves::validate_icr_ordering(result.new_icr, prev_icr, next_icr)?;
         msg!("✓ ICR ordering validated successfully");
     } else {
+        require!(
+            ctx.accounts.state.load()?.require_hints == 0,
+            AerospacerProtocolError::NeighborHintsRequired
+        );
         msg!("⚠ WARNING: No neighbor hints provided - skipping ICR ordering validation");
         msg!("⚠ Production deployments should enforce neighbor hints for sorted list integrity");
     }
     
-    // Update the actual accounts with the results
-    ctx.accounts.user_collateral_amount.amount = result.new_collateral_amount;
-    ctx.accounts.liquidity_threshold.ratio = result.new_icr;
-    
     msg!("Collateral added successfully");
     msg!("Added: {} {}", params.amount, params.collateral_denom);
     msg!("New collateral amount: {}", result.new_collateral_amount);
     msg!("New ICR: {}", result.new_icr);
     msg!("Debt amount: {}", result.new_debt_amount);
+    
+    emit!(TroveUpdated {
+        owner: ctx.accounts.trove_owner.key(),
+        trove_id: params.trove_id,
+        collateral_denom: params.collateral_denom.clone(),
+        operation: TroveOperation::AddCollateral,
+        amount: params.amount,
+        collateral_amount: result.new_collateral_amount,
+        debt_amount: result.new_debt_amount,
+        icr: result.new_icr,
+        tag: ctx.accounts.user_debt_amount.tag,
+        memo: params.memo,
+    });
+    
+    if let Some(trove_history) = ctx.accounts.trove_history.as_mut() {
+        trove_history.record(TroveHistoryEntry {
+            op: TroveHistoryOp::AddCollateral,
+            trove_id: params.trove_id,
+            amount: params.amount,
+            icr: result.new_icr,
+            slot: Clock::get()?.slot,
+        });
+    }
 
     Ok(())
 }
\ No newline at end of file
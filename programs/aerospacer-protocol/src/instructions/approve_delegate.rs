@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::validation::{ValidateParams, validate_pubkey};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ApproveDelegateParams {
+    pub delegate: Pubkey,   // Key allowed to add collateral and repay on the owner's behalf
+}
+
+impl ValidateParams for ApproveDelegateParams {
+    fn validate(&self) -> Result<()> {
+        validate_pubkey(&self.delegate)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: ApproveDelegateParams)]
+pub struct ApproveDelegate<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + TroveDelegate::LEN,
+        seeds = [b"trove_delegate", owner.key().as_ref(), params.delegate.as_ref()],
+        bump
+    )]
+    pub trove_delegate: Account<'info, TroveDelegate>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for approve_delegate instruction
+/// The delegate can only ever de-risk the trove (add_collateral, repay_loan); approving is idempotent
+pub fn handler(ctx: Context<ApproveDelegate>, params: ApproveDelegateParams) -> Result<()> {
+    params.validate()?;
+    
+    require!(
+        params.delegate != ctx.accounts.owner.key(),
+        AerospacerProtocolError::InvalidAddress
+    );
+    
+    let trove_delegate = &mut ctx.accounts.trove_delegate;
+    trove_delegate.owner = ctx.accounts.owner.key();
+    trove_delegate.delegate = params.delegate;
+    
+    msg!("Trove delegate approved");
+    msg!("Owner: {}", ctx.accounts.owner.key());
+    msg!("Delegate: {}", params.delegate);
+    
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::trove_archive::{archive_trove_record, ArchiveAccounts, ACCOUNT_COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID};
+use crate::validation::{ValidateParams, validate_denom, validate_pubkey};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ArchiveTroveParams {
+    pub owner: Pubkey,
+    pub trove_id: u16,
+    pub collateral_denom: String, // Denom of the leftover collateral record, if one is passed
+}
+
+impl ValidateParams for ArchiveTroveParams {
+    fn validate(&self) -> Result<()> {
+        validate_pubkey(&self.owner)?;
+        // The collateral record is optional, and so is its denom
+        if !self.collateral_denom.is_empty() {
+            validate_denom(&self.collateral_denom)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: ArchiveTroveParams)]
+pub struct ArchiveTrove<'info> {
+    pub keeper: Signer<'info>,
+    
+    /// CHECK: Trove owner - only receives the rent of the closed records
+    #[account(
+        mut,
+        address = params.owner @ AerospacerProtocolError::Unauthorized
+    )]
+    pub owner: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"user_debt_amount", params.owner.as_ref(), trove_id_seed(params.trove_id).as_slice()],
+        bump,
+        constraint = user_debt_amount.owner == params.owner @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_debt_amount: Account<'info, UserDebtAmount>,
+    
+    /// Zeroed collateral record a liquidation leaves behind (close_trove already closes it)
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"user_collateral_amount", params.owner.as_ref(), trove_id_seed(params.trove_id).as_slice(), params.collateral_denom.as_bytes()],
+        bump,
+        constraint = user_collateral_amount.amount == 0 @ AerospacerProtocolError::TroveNotArchivable
+    )]
+    pub user_collateral_amount: Option<Account<'info, UserCollateralAmount>>,
+    
+    /// Zeroed threshold record a liquidation leaves behind (close_trove already closes it)
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"liquidity_threshold", params.owner.as_ref(), trove_id_seed(params.trove_id).as_slice()],
+        bump
+    )]
+    pub liquidity_threshold: Option<Account<'info, LiquidityThreshold>>,
+    
+    #[account(
+        mut,
+        seeds = [b"trove_archive"],
+        bump
+    )]
+    pub trove_archive: Account<'info, TroveArchive>,
+    
+    /// CHECK: Archive tree - must match trove_archive.merkle_tree
+    #[account(
+        mut,
+        address = trove_archive.merkle_tree @ AerospacerProtocolError::InvalidAddress
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID @ AerospacerProtocolError::InvalidAddress)]
+    pub compression_program: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL noop program
+    #[account(address = NOOP_PROGRAM_ID @ AerospacerProtocolError::InvalidAddress)]
+    pub noop_program: UncheckedAccount<'info>,
+}
+
+/// Handler for archive_trove instruction
+/// Permissionless: moves a closed or liquidated trove into the compressed archive and returns
+/// the rent of its remaining records to the owner. A later open_trove recreates them as needed.
+pub fn handler(ctx: Context<ArchiveTrove>, params: ArchiveTroveParams) -> Result<()> {
+    params.validate()?;
+    
+    archive_trove_record(
+        ArchiveAccounts {
+            trove_archive: &mut ctx.accounts.trove_archive,
+            merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+            compression_program: &ctx.accounts.compression_program.to_account_info(),
+            noop_program: &ctx.accounts.noop_program.to_account_info(),
+        },
+        &ctx.accounts.user_debt_amount,
+    )?;
+    
+    msg!("Archived trove records closed");
+    msg!("Owner: {}", params.owner);
+    msg!("Trove id: {}", params.trove_id);
+    msg!("Collateral record closed: {}", ctx.accounts.user_collateral_amount.is_some());
+    msg!("Threshold record closed: {}", ctx.accounts.liquidity_threshold.is_some());
+    
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, Mint, SetAuthority, set_authority, spl_token::instruction::AuthorityType};
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AssignMintAuthorityParams {
+    pub trove_debt_cap: u64, // Max total trove debt trove mints may reach, 0 = uncapped
+}
+
+#[derive(Accounts)]
+pub struct AssignMintAuthority<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        mut,
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub stable_coin_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + MintAuthority::LEN,
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    /// CHECK: Protocol stablecoin vault PDA - the mint authority of deployments initialized before the mint authority PDA
+    #[account(
+        seeds = [b"protocol_stablecoin_vault"],
+        bump
+    )]
+    pub protocol_stablecoin_vault: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for assign_mint_authority instruction
+/// Moves the aUSD mint authority from the stablecoin vault PDA to the dedicated mint authority PDA
+/// if the vault still holds it, and sets the trove debt cap. Later calls only update the cap.
+pub fn handler(ctx: Context<AssignMintAuthority>, params: AssignMintAuthorityParams) -> Result<()> {
+    let mint_authority = &mut ctx.accounts.mint_authority;
+    require!(
+        mint_authority.migrated_to == Pubkey::default(),
+        AerospacerProtocolError::MintAuthorityNotAssigned
+    );
+    mint_authority.trove_debt_cap = params.trove_debt_cap;
+
+    let vault = ctx.accounts.protocol_stablecoin_vault.key();
+    if ctx.accounts.stable_coin_mint.mint_authority == Some(vault).into() {
+        let vault_seeds: &[&[u8]] = &[b"protocol_stablecoin_vault", &[ctx.bumps.protocol_stablecoin_vault]];
+        set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    account_or_mint: ctx.accounts.stable_coin_mint.to_account_info(),
+                    current_authority: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            AuthorityType::MintTokens,
+            Some(mint_authority.key()),
+        )?;
+        msg!("Stablecoin mint authority moved from {} to {}", vault, mint_authority.key());
+    } else {
+        require!(
+            ctx.accounts.stable_coin_mint.mint_authority == Some(mint_authority.key()).into(),
+            AerospacerProtocolError::MintAuthorityNotAssigned
+        );
+    }
+
+    msg!("Trove debt cap: {} aUSD", mint_authority.trove_debt_cap);
+
+    Ok(())
+}
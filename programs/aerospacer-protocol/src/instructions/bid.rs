@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Mint, Burn, Transfer};
+use crate::state::*;
+use crate::error::*;
+use crate::events::LiquidationAuctionBid;
+use crate::validation::{ValidateParams, validate_amount, validate_denom, validate_pubkey};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct BidParams {
+    pub target_user: Pubkey,       // Owner of the auctioned trove
+    pub collateral_denom: String,
+    pub trove_id: u16,
+    pub collateral_amount: u64,    // Collateral to buy (cut down to what the remaining debt buys)
+    pub max_payment: u64,          // Slippage bound on the aUSD burned
+}
+
+impl ValidateParams for BidParams {
+    fn validate(&self) -> Result<()> {
+        validate_pubkey(&self.target_user)?;
+        validate_denom(&self.collateral_denom)?;
+        validate_amount(self.collateral_amount)?;
+        validate_amount(self.max_payment)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: BidParams)]
+pub struct Bid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidation_auction", params.target_user.as_ref(), trove_id_seed(params.trove_id).as_slice(), params.collateral_denom.as_bytes()],
+        bump,
+        constraint = auction.denom == params.collateral_denom @ AerospacerProtocolError::InvalidDenom
+    )]
+    pub auction: Account<'info, LiquidationAuction>,
+
+    #[account(
+        mut,
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub stable_coin_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = bidder_stablecoin_account.owner == bidder.key() @ AerospacerProtocolError::Unauthorized,
+        constraint = bidder_stablecoin_account.mint == stable_coin_mint.key() @ AerospacerProtocolError::InvalidMint
+    )]
+    pub bidder_stablecoin_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_collateral_vault", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub protocol_collateral_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint = collateral_mint.key() == protocol_collateral_vault.mint @ AerospacerProtocolError::InvalidMint
+    )]
+    pub collateral_mint: Box<Account<'info, Mint>>,
+
+    // Bidder's collateral ATA, created when missing
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = bidder
+    )]
+    pub bidder_collateral_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for bid instruction
+///
+/// Buys collateral from a running liquidation auction at the current decayed price. The aUSD
+/// paid is burned against the auctioned debt. Anyone may bid; a bid that would raise more than
+/// the remaining debt only buys the collateral the remaining debt is worth.
+pub fn handler(ctx: Context<Bid>, params: BidParams) -> Result<()> {
+    params.validate()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let auction = &ctx.accounts.auction;
+    require!(
+        !auction.is_settleable(now),
+        AerospacerProtocolError::AuctionClosed
+    );
+
+    let (collateral, payment) = auction.quote(params.collateral_amount, now)?;
+    require!(collateral > 0 && payment > 0, AerospacerProtocolError::InvalidAmount);
+    require!(payment <= params.max_payment, AerospacerProtocolError::AuctionPriceAboveLimit);
+
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.stable_coin_mint.to_account_info(),
+            from: ctx.accounts.bidder_stablecoin_account.to_account_info(),
+            authority: ctx.accounts.bidder.to_account_info(),
+        },
+    );
+    anchor_spl::token::burn(burn_ctx, payment)?;
+
+    let vault_seeds: &[&[u8]] = &[
+        b"protocol_collateral_vault",
+        params.collateral_denom.as_bytes(),
+        &[ctx.bumps.protocol_collateral_vault],
+    ];
+    let signer: &[&[&[u8]]] = &[vault_seeds];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.protocol_collateral_vault.to_account_info(),
+            to: ctx.accounts.bidder_collateral_account.to_account_info(),
+            authority: ctx.accounts.protocol_collateral_vault.to_account_info(),
+        },
+        signer,
+    );
+    anchor_spl::token::transfer(transfer_ctx, collateral)?;
+
+    {
+        let mut state = ctx.accounts.state.load_mut()?;
+        state.total_debt_amount = state.total_debt_amount.saturating_sub(payment);
+    }
+
+    let auction = &mut ctx.accounts.auction;
+    auction.collateral_remaining -= collateral;
+    auction.debt_remaining -= payment;
+    auction.debt_raised = auction.debt_raised
+        .checked_add(payment)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+
+    msg!("Auction bid filled");
+    msg!("Trove: {} #{}", params.target_user, params.trove_id);
+    msg!("Bidder: {}", ctx.accounts.bidder.key());
+    msg!("Bought: {} {} for {} aUSD", collateral, params.collateral_denom, payment);
+    msg!("Remaining: {} collateral, {} debt", auction.collateral_remaining, auction.debt_remaining);
+
+    emit!(LiquidationAuctionBid {
+        owner: params.target_user,
+        trove_id: params.trove_id,
+        bidder: ctx.accounts.bidder.key(),
+        collateral,
+        payment,
+        collateral_remaining: auction.collateral_remaining,
+        debt_remaining: auction.debt_remaining,
+    });
+
+    Ok(())
+}
@@ -1,19 +1,33 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Mint, MintTo};
+use anchor_spl::token::{Token, TokenAccount, Mint};
 use crate::state::*;
 use crate::error::*;
 use crate::trove_management::*;
 use crate::account_management::*;
 use crate::oracle::*;
+use crate::events::{TroveOperation, TroveUpdated};
 use crate::fees_integration::*;
+use crate::mint_gate::{mint_stablecoin, MintPurpose};
 use crate::utils::*;
+use crate::validation::{ValidateParams, validate_min_amount, validate_denom, validate_optional_pubkey};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct BorrowLoanParams {
     pub loan_amount: u64,
     pub collateral_denom: String,
+    pub trove_id: u16, // Which of the owner's troves (0 = the wallet's first trove)
     pub prev_node_id: Option<Pubkey>,
     pub next_node_id: Option<Pubkey>,
+    pub memo: Option<[u8; 32]>, // Optional integrator memo, echoed in the emitted event
+}
+
+impl ValidateParams for BorrowLoanParams {
+    fn validate(&self) -> Result<()> {
+        validate_min_amount(self.loan_amount, MINIMUM_LOAN_AMOUNT, AerospacerProtocolError::LoanAmountBelowMinimum)?;
+        validate_denom(&self.collateral_denom)?;
+        validate_optional_pubkey(&self.prev_node_id)?;
+        validate_optional_pubkey(&self.next_node_id)
+    }
 }
 
 #[derive(Accounts)]
@@ -24,7 +38,7 @@ pub struct BorrowLoan<'info> {
     
     #[account(
         mut,
-        seeds = [b"user_debt_amount", user.key().as_ref()],
+        seeds = [b"user_debt_amount", user.key().as_ref(), trove_id_seed(params.trove_id).as_slice()],
         bump,
         constraint = user_debt_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
     )]
@@ -32,24 +46,23 @@ pub struct BorrowLoan<'info> {
 
     #[account(
         mut,
-        seeds = [b"liquidity_threshold", user.key().as_ref()],
+        seeds = [b"liquidity_threshold", user.key().as_ref(), trove_id_seed(params.trove_id).as_slice()],
         bump,
         constraint = liquidity_threshold.owner == user.key() @ AerospacerProtocolError::Unauthorized
     )]
     pub liquidity_threshold: Box<Account<'info, LiquidityThreshold>>,
 
     #[account(mut)]
-    pub state: Box<Account<'info, StateAccount>>,
+    pub state: AccountLoader<'info, StateAccount>,
 
     #[account(mut)]
     pub user_stablecoin_account: Box<Account<'info, TokenAccount>>,
 
-    /// CHECK: This is the stable coin mint account - validated against state
     #[account(
         mut,
-        constraint = stable_coin_mint.key() == state.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
     )]
-    pub stable_coin_mint: UncheckedAccount<'info>,
+    pub stable_coin_mint: Box<Account<'info, Mint>>,
     
     #[account(
         init_if_needed,
@@ -61,10 +74,17 @@ pub struct BorrowLoan<'info> {
     )]
     pub protocol_stablecoin_account: Box<Account<'info, TokenAccount>>,
 
+    // Dedicated aUSD mint authority PDA (see mint_gate)
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: Box<Account<'info, MintAuthority>>,
+
     // Collateral context accounts
     #[account(
         mut,
-        seeds = [b"user_collateral_amount", user.key().as_ref(), params.collateral_denom.as_bytes()],
+        seeds = [b"user_collateral_amount", user.key().as_ref(), trove_id_seed(params.trove_id).as_slice(), params.collateral_denom.as_bytes()],
         bump,
         constraint = user_collateral_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
     )]
@@ -79,12 +99,10 @@ pub struct BorrowLoan<'info> {
     pub collateral_mint: Account<'info, Mint>,
 
     #[account(
-        init_if_needed,
-        payer = user,
-        token::mint = collateral_mint,
-        token::authority = protocol_collateral_account,
+        mut,
         seeds = [b"protocol_collateral_vault", params.collateral_denom.as_bytes()],
-        bump
+        bump,
+        constraint = protocol_collateral_account.mint == collateral_mint.key() @ AerospacerProtocolError::InvalidMint
     )]
     pub protocol_collateral_account: Box<Account<'info, TokenAccount>>,
 
@@ -94,20 +112,26 @@ pub struct BorrowLoan<'info> {
         seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
         bump
     )]
-    pub total_collateral_amount: Account<'info, TotalCollateralAmount>,
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+
+    #[account(
+        seeds = [b"collateral_config", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
 
     // Oracle context - integration with our aerospacer-oracle
     /// CHECK: Our oracle program - validated against state
     #[account(
         mut,
-        constraint = oracle_program.key() == state.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
+        constraint = oracle_program.key() == state.load()?.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
     )]
     pub oracle_program: AccountInfo<'info>,
     
     /// CHECK: Oracle state account - validated against state
     #[account(
         mut,
-        constraint = oracle_state.key() == state.oracle_state_addr @ AerospacerProtocolError::Unauthorized
+        constraint = oracle_state.key() == state.load()?.oracle_state_addr @ AerospacerProtocolError::Unauthorized
     )]
     pub oracle_state: AccountInfo<'info>,
     
@@ -120,14 +144,14 @@ pub struct BorrowLoan<'info> {
     // Fee distribution accounts
     /// CHECK: Fees program - validated against state
     #[account(
-        constraint = fees_program.key() == state.fee_distributor_addr @ AerospacerProtocolError::Unauthorized
+        constraint = fees_program.key() == state.load()?.fee_distributor_addr @ AerospacerProtocolError::Unauthorized
     )]
     pub fees_program: AccountInfo<'info>,
     
     /// CHECK: Fees state account - validated against state
     #[account(
         mut,
-        constraint = fees_state.key() == state.fee_state_addr @ AerospacerProtocolError::Unauthorized
+        constraint = fees_state.key() == state.load()?.fee_state_addr @ AerospacerProtocolError::Unauthorized
     )]
     pub fees_state: AccountInfo<'info>,
     
@@ -135,58 +159,98 @@ pub struct BorrowLoan<'info> {
     #[account(mut)]
     pub stability_pool_token_account: AccountInfo<'info>,
     
-    /// CHECK: Fee address 1 token account
+    /// CHECK: Protocol's fee caller PDA - signs the distribute_fee CPI, allowlisted in aerospacer-fees
+    #[account(
+        seeds = [b"fee_caller"],
+        bump
+    )]
+    pub fee_caller: UncheckedAccount<'info>,
+    
+    /// CHECK: aerospacer-fees fee vault - checked by the fees program
     #[account(mut)]
-    pub fee_address_1_token_account: AccountInfo<'info>,
+    pub fee_vault: AccountInfo<'info>,
     
-    /// CHECK: Fee address 2 token account
+    /// CHECK: aerospacer-fees per-epoch fee stats - checked by the fees program
     #[account(mut)]
-    pub fee_address_2_token_account: AccountInfo<'info>,
+    pub fee_epoch_stats: AccountInfo<'info>,
+
+    /// Dust fee accumulator - optional; a fee below its threshold is parked instead of distributed
+    #[account(
+        mut,
+        seeds = [b"fee_accumulator"],
+        bump
+    )]
+    pub fee_accumulator: Option<Box<Account<'info, FeeAccumulator>>>,
+    
+    /// CHECK: Fee accumulator vault PDA - required with the fee accumulator
+    #[account(
+        mut,
+        seeds = [b"fee_accumulator_vault"],
+        bump
+    )]
+    pub fee_accumulator_vault: Option<UncheckedAccount<'info>>,
     
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+    
+    // Promotional terms for the denom - borrowing fee is waived while active
+    #[account(
+        seeds = [b"denom_promotion", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub denom_promotion: Option<Box<Account<'info, DenomPromotion>>>,
+    
+    // Keeper-refreshed price (see refresh_price) - read instead of the oracle while fresh
+    #[account(
+        seeds = [b"price_cache", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub price_cache: Option<Box<Account<'info, PriceCache>>>,
+    
+    /// Optional operation history, appended to when passed
+    #[account(
+        mut,
+        seeds = [b"trove_history", user.key().as_ref()],
+        bump
+    )]
+    pub trove_history: Option<Box<Account<'info, TroveHistory>>>,
 }
 
 
 
 pub fn handler(ctx: Context<BorrowLoan>, params: BorrowLoanParams) -> Result<()> {
-    // Validate input parameters
-    require!(
-        params.loan_amount > 0,
-        AerospacerProtocolError::InvalidAmount
-    );
+    params.validate()?;
     
+    // Check if user has existing trove
     require!(
-        params.loan_amount >= MINIMUM_LOAN_AMOUNT,
-        AerospacerProtocolError::LoanAmountBelowMinimum
+        ctx.accounts.user_debt_amount.is_active() && ctx.accounts.user_debt_amount.amount > 0,
+        AerospacerProtocolError::TroveDoesNotExist
     );
     
+    // No borrowing against a deactivated denom - repaying and withdrawing stay open
     require!(
-        !params.collateral_denom.is_empty(),
-        AerospacerProtocolError::InvalidAmount
+        ctx.accounts.total_collateral_amount.load()?.is_active(),
+        AerospacerProtocolError::CollateralDeactivated
     );
     
-    // Check if user has existing trove
-    require!(
-        ctx.accounts.user_debt_amount.amount > 0,
-        AerospacerProtocolError::TroveDoesNotExist
-    );
+    // Lock the trove against liquidation/redemption for the rest of the slot
+    ctx.accounts.user_debt_amount.lock(Clock::get()?.slot);
     
     // Create context structs for clean architecture
     let mut trove_ctx = TroveContext {
-        user: ctx.accounts.user.clone(),
-        user_debt_amount: (*ctx.accounts.user_debt_amount).clone(),
-        liquidity_threshold: (*ctx.accounts.liquidity_threshold).clone(),
-        state: (*ctx.accounts.state).clone(),
+        user: &ctx.accounts.user,
+        user_debt_amount: &mut ctx.accounts.user_debt_amount,
+        liquidity_threshold: &mut ctx.accounts.liquidity_threshold,
+        state: &ctx.accounts.state,
     };
     
     let mut collateral_ctx = CollateralContext {
-        user: ctx.accounts.user.clone(),
-        user_collateral_amount: (*ctx.accounts.user_collateral_amount).clone(),
-        user_collateral_account: (*ctx.accounts.user_collateral_account).clone(),
-        protocol_collateral_account: (*ctx.accounts.protocol_collateral_account).clone(),
-        total_collateral_amount: ctx.accounts.total_collateral_amount.clone(),
-        token_program: ctx.accounts.token_program.clone(),
+        user: &ctx.accounts.user,
+        user_collateral_amount: &mut ctx.accounts.user_collateral_amount,
+        user_collateral_account: &ctx.accounts.user_collateral_account,
+        protocol_collateral_account: &ctx.accounts.protocol_collateral_account,
+        total_collateral_amount: &ctx.accounts.total_collateral_amount,
+        token_program: &ctx.accounts.token_program,
     };
     
     let oracle_ctx = OracleContext {
@@ -194,10 +258,23 @@ pub fn handler(ctx: Context<BorrowLoan>, params: BorrowLoanParams) -> Result<()>
         oracle_state: ctx.accounts.oracle_state.clone(),
         pyth_price_account: ctx.accounts.pyth_price_account.clone(),
         clock: ctx.accounts.clock.to_account_info(),
+        max_price_age: PriceReadClass::Standard.max_price_age(&*ctx.accounts.state.load()?),
+        fallback: None,
+        cached_price: PriceData::from_cache(
+            &*ctx.accounts.state.load()?,
+            ctx.accounts.price_cache.as_deref().map(|c| &**c),
+            &params.collateral_denom,
+            PriceReadClass::Standard.max_price_age(&*ctx.accounts.state.load()?),
+        )?,
     };
     
     // Calculate fee amount for distribution
-    let fee_amount = calculate_protocol_fee(params.loan_amount, ctx.accounts.state.protocol_fee)?;
+    let protocol_fee = effective_borrowing_fee(
+        ctx.accounts.collateral_config.protocol_fee,
+        ctx.accounts.denom_promotion.as_deref().map(|p| &**p),
+        Clock::get()?.slot,
+    );
+    let fee_amount = calculate_protocol_fee(params.loan_amount, protocol_fee)?;
     
     // CRITICAL: Record FULL gross amount as debt (including fee)
     // This ensures all minted tokens have matching debt liability
@@ -207,12 +284,13 @@ pub fn handler(ctx: Context<BorrowLoan>, params: BorrowLoanParams) -> Result<()>
         &mut collateral_ctx,
         &oracle_ctx,
         params.loan_amount,  // Use gross amount, not net
+        ctx.accounts.collateral_config.minimum_collateral_ratio,
     )?;
     
     // CRITICAL: Validate ICR ordering if neighbor hints provided
     // Production clients MUST provide neighbor hints via remainingAccounts for proper sorted list maintenance
     // Pattern: [prev_LiquidityThreshold, next_LiquidityThreshold] or [prev_LT] or [next_LT] or []
-    // Optional for backward compatibility with tests, but REQUIRED in production
+    // Optional for backward compatibility with tests; mandatory while state.require_hints is set
     if !ctx.remaining_accounts.is_empty() {
         use crate::sorted_troves;
         
@@ -224,10 +302,11 @@ pub fn handler(ctx: Context<BorrowLoan>, params: BorrowLoanParams) -> Result<()>
             let prev_threshold = LiquidityThreshold::try_deserialize(&mut &prev_data[..])?;
             let prev_owner = prev_threshold.owner;
             let prev_ratio = prev_threshold.ratio;
+            let prev_trove_id = prev_threshold.trove_id;
             drop(prev_data);
             
             // Verify this is a real PDA, not a fake account
-            sorted_troves::verify_liquidity_threshold_pda(prev_lt, prev_owner, ctx.program_id)?;
+            sorted_troves::verify_liquidity_threshold_pda(prev_lt, prev_owner, prev_trove_id, ctx.program_id)?;
             
             Some(prev_ratio)
         } else {
@@ -240,10 +319,11 @@ pub fn handler(ctx: Context<BorrowLoan>, params: BorrowLoanParams) -> Result<()>
             let next_threshold = LiquidityThreshold::try_deserialize(&mut &next_data[..])?;
             let next_owner = next_threshold.owner;
             let next_ratio = next_threshold.ratio;
+            let next_trove_id = next_threshold.trove_id;
             drop(next_data);
             
             // Verify this is a real PDA, not a fake account
-            sorted_troves::verify_liquidity_threshold_pda(next_lt, next_owner, ctx.program_id)?;
+            sorted_troves::verify_liquidity_threshold_pda(next_lt, next_owner, next_trove_id, ctx.program_id)?;
             
             Some(next_ratio)
         } else {
@@ -254,47 +334,54 @@ pub fn handler(ctx: Context<BorrowLoan>, params: BorrowLoanParams) -> Result<()>
         sorted_troves::validate_icr_ordering(result.new_icr, prev_icr, next_icr)?;
         msg!("✓ ICR ordering validated successfully");
     } else {
+        require!(
+            ctx.accounts.state.load()?.require_hints == 0,
+            AerospacerProtocolError::NeighborHintsRequired
+        );
         msg!("⚠ WARNING: No neighbor hints provided - skipping ICR ordering validation");
         msg!("⚠ Production clients MUST provide neighbor hints for sorted list integrity");
     }
     
-    // Update the actual accounts with the results
-    ctx.accounts.user_debt_amount.amount = result.new_debt_amount;
-    ctx.accounts.liquidity_threshold.ratio = result.new_icr;
-    ctx.accounts.state.total_debt_amount = trove_ctx.state.total_debt_amount;
-    
     // Mint total loan amount (including fee)
-    // Use invoke_signed for PDA authority
-    let mint_seeds = &[
-        b"protocol_stablecoin_vault".as_ref(),
-        &[ctx.bumps.protocol_stablecoin_account],
-    ];
-    let mint_signer = &[&mint_seeds[..]];
-    
-    let mint_ctx = CpiContext::new_with_signer(
+    let total_debt = ctx.accounts.state.load()?.total_debt_amount;
+    mint_stablecoin(
+        &ctx.accounts.stable_coin_mint,
+        &ctx.accounts.mint_authority,
+        ctx.bumps.mint_authority,
+        ctx.accounts.user_stablecoin_account.to_account_info(),
         ctx.accounts.token_program.to_account_info(),
-        MintTo {
-            mint: ctx.accounts.stable_coin_mint.to_account_info(),
-            to: ctx.accounts.user_stablecoin_account.to_account_info(),
-            authority: ctx.accounts.protocol_stablecoin_account.to_account_info(),
-        },
-        mint_signer,
-    );
-    anchor_spl::token::mint_to(mint_ctx, params.loan_amount)?;
+        params.loan_amount,
+        MintPurpose::TroveDebt { total_debt },
+    )?;
 
     // Distribute fee via CPI to aerospacer-fees
     if fee_amount > 0 {
+        let sp_balance_before = stability_pool_vault_balance(&ctx.accounts.stability_pool_token_account)?;
         let net_amount = process_protocol_fee(
             params.loan_amount,
-            ctx.accounts.state.protocol_fee,
-            ctx.accounts.fees_program.to_account_info(),
-            ctx.accounts.user.to_account_info(),
-            ctx.accounts.fees_state.to_account_info(),
-            ctx.accounts.user_stablecoin_account.to_account_info(),
-            ctx.accounts.stability_pool_token_account.to_account_info(),
-            ctx.accounts.fee_address_1_token_account.to_account_info(),
-            ctx.accounts.fee_address_2_token_account.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
+            protocol_fee,
+            FeeSource::Borrow,
+            ProtocolFeeAccounts {
+                fees_program: ctx.accounts.fees_program.to_account_info(),
+                payer: ctx.accounts.user.to_account_info(),
+                fees_state: ctx.accounts.fees_state.to_account_info(),
+                fee_caller: ctx.accounts.fee_caller.to_account_info(),
+                fee_caller_bump: ctx.bumps.fee_caller,
+                payer_token_account: ctx.accounts.user_stablecoin_account.to_account_info(),
+                stability_pool_token_account: ctx.accounts.stability_pool_token_account.to_account_info(),
+                fee_vault: ctx.accounts.fee_vault.to_account_info(),
+                fee_epoch_stats: ctx.accounts.fee_epoch_stats.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            FeeAccumulatorAccounts::new(
+                ctx.accounts.fee_accumulator.as_deref_mut().map(|fee_accumulator| &mut **fee_accumulator),
+                ctx.accounts.fee_accumulator_vault.as_deref(),
+            )?,
+        )?;
+        accrue_routed_fee_yield(
+            &mut *ctx.accounts.state.load_mut()?,
+            &ctx.accounts.stability_pool_token_account,
+            sp_balance_before,
         )?;
         
         msg!("Fee collected and distributed: {} aUSD", fee_amount);
@@ -310,5 +397,28 @@ pub fn handler(ctx: Context<BorrowLoan>, params: BorrowLoanParams) -> Result<()>
     msg!("New ICR: {}", result.new_icr);
     msg!("Collateral amount: {}", result.new_collateral_amount);
     
+    emit!(TroveUpdated {
+        owner: ctx.accounts.user.key(),
+        trove_id: params.trove_id,
+        collateral_denom: params.collateral_denom.clone(),
+        operation: TroveOperation::BorrowLoan,
+        amount: params.loan_amount,
+        collateral_amount: result.new_collateral_amount,
+        debt_amount: result.new_debt_amount,
+        icr: result.new_icr,
+        tag: ctx.accounts.user_debt_amount.tag,
+        memo: params.memo,
+    });
+    
+    if let Some(trove_history) = ctx.accounts.trove_history.as_mut() {
+        trove_history.record(TroveHistoryEntry {
+            op: TroveHistoryOp::BorrowLoan,
+            trove_id: params.trove_id,
+            amount: params.loan_amount,
+            icr: result.new_icr,
+            slot: Clock::get()?.slot,
+        });
+    }
+    
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::events::MintAuthorityMigrationCancelled;
+
+#[derive(Accounts)]
+pub struct CancelMintAuthorityMigration<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+}
+
+/// Handler for cancel_mint_authority_migration instruction
+pub fn handler(ctx: Context<CancelMintAuthorityMigration>) -> Result<()> {
+    let mint_authority = &mut ctx.accounts.mint_authority;
+    let new_authority = mint_authority.pending_authority;
+    mint_authority.cancel_migration()?;
+
+    msg!("Mint authority migration to {} cancelled", new_authority);
+
+    emit!(MintAuthorityMigrationCancelled { new_authority });
+
+    Ok(())
+}
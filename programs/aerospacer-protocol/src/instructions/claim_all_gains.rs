@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::utils::*;
+use crate::error::*;
+
+// Accounts per collateral denom in remaining_accounts
+const ACCOUNTS_PER_DENOM: usize = 5;
+
+#[derive(Accounts)]
+pub struct ClaimAllGains<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"user_stake_amount", user.key().as_ref()],
+        bump,
+        constraint = user_stake_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_stake_amount: Account<'info, UserStakeAmount>,
+
+    pub state: AccountLoader<'info, StateAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    // remaining_accounts should contain, for every collateral denom to claim:
+    // [StabilityPoolSnapshot, UserCollateralSnapshot, protocol_collateral_vault,
+    //  TotalCollateralAmount, user token account for that mint]
+    // (all writable except the StabilityPoolSnapshot). A UserCollateralSnapshot that does
+    // not exist yet is created on the fly at the current S, paid for by the user.
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimAllGains<'info>>) -> Result<()> {
+    let user_key = ctx.accounts.user.key();
+    let user_stake_amount = &ctx.accounts.user_stake_amount;
+    let current_epoch = ctx.accounts.state.load()?.epoch;
+
+    // SECURITY: Validate user has stake
+    require!(
+        user_stake_amount.amount > 0,
+        AerospacerProtocolError::InvalidAmount
+    );
+    require!(
+        !ctx.remaining_accounts.is_empty()
+            && ctx.remaining_accounts.len().is_multiple_of(ACCOUNTS_PER_DENOM),
+        AerospacerProtocolError::InvalidList
+    );
+
+    let mut claimed_denoms: Vec<String> = Vec::new();
+    let mut total_claims = 0usize;
+
+    for chunk in ctx.remaining_accounts.chunks(ACCOUNTS_PER_DENOM) {
+        let (pool_snapshot_info, user_snapshot_info, vault_info, total_collateral_info, user_token_info) =
+            (&chunk[0], &chunk[1], &chunk[2], &chunk[3], &chunk[4]);
+
+        // SECURITY: The pool snapshot is program-owned and determines the denom of this group
+        require!(
+            pool_snapshot_info.owner == &crate::ID,
+            AerospacerProtocolError::InvalidList
+        );
+        let pool_snapshot = {
+            let data = pool_snapshot_info.try_borrow_data()?;
+            StabilityPoolSnapshot::try_deserialize(&mut &data[..])?
+        };
+        let denom = pool_snapshot.denom.clone();
+
+        // Each denom may only be claimed once per transaction
+        require!(
+            !claimed_denoms.contains(&denom),
+            AerospacerProtocolError::InvalidList
+        );
+
+        // SECURITY: Every account in the group must be the canonical PDA for this denom
+        let (expected_pool_snapshot_pda, _bump) = Pubkey::find_program_address(
+            &StabilityPoolSnapshot::seeds(&denom),
+            &crate::ID,
+        );
+        let (expected_user_snapshot_pda, user_snapshot_bump) = Pubkey::find_program_address(
+            &UserCollateralSnapshot::seeds(&user_key, &denom),
+            &crate::ID,
+        );
+        let (expected_vault_pda, vault_bump) = Pubkey::find_program_address(
+            &[b"protocol_collateral_vault", denom.as_bytes()],
+            &crate::ID,
+        );
+        let (expected_total_coll_pda, _bump) = Pubkey::find_program_address(
+            &TotalCollateralAmount::seeds(&denom),
+            &crate::ID,
+        );
+        require!(
+            expected_pool_snapshot_pda == *pool_snapshot_info.key
+                && expected_user_snapshot_pda == *user_snapshot_info.key
+                && expected_vault_pda == *vault_info.key
+                && expected_total_coll_pda == *total_collateral_info.key,
+            AerospacerProtocolError::InvalidList
+        );
+        require!(
+            total_collateral_info.owner == &crate::ID,
+            AerospacerProtocolError::InvalidList
+        );
+        require!(
+            user_snapshot_info.is_writable && vault_info.is_writable
+                && total_collateral_info.is_writable && user_token_info.is_writable,
+            AerospacerProtocolError::InvalidList
+        );
+
+        // SECURITY: Validate the user token account belongs to user and matches the vault's mint
+        let vault_account = {
+            let data = vault_info.try_borrow_data()?;
+            TokenAccount::try_deserialize(&mut &data[..])?
+        };
+        let user_token_account = {
+            let data = user_token_info.try_borrow_data()?;
+            TokenAccount::try_deserialize(&mut &data[..])?
+        };
+        require!(
+            user_token_account.owner == user_key,
+            AerospacerProtocolError::Unauthorized
+        );
+        require!(
+            user_token_account.mint == vault_account.mint,
+            AerospacerProtocolError::InvalidMint
+        );
+
+        // Load the user's S snapshot, creating it on first claim for this denom
+        let mut user_collateral_snapshot = if user_snapshot_info.owner == &crate::ID {
+            let data = user_snapshot_info.try_borrow_data()?;
+            let snapshot = UserCollateralSnapshot::try_deserialize(&mut &data[..])?;
+            require!(
+                snapshot.owner == user_key,
+                AerospacerProtocolError::Unauthorized
+            );
+            require!(
+                snapshot.denom == denom,
+                AerospacerProtocolError::InvalidList
+            );
+            snapshot
+        } else {
+            create_program_pda(
+                &ctx.accounts.user.to_account_info(),
+                user_snapshot_info,
+                &ctx.accounts.system_program.to_account_info(),
+                8 + UserCollateralSnapshot::LEN,
+                &[b"user_collateral_snapshot", user_key.as_ref(), denom.as_bytes(), &[user_snapshot_bump]],
+            )?;
+            // SECURITY: The deposit's gains start here - a snapshot at S = 0 would pay out the
+            // pool's whole gain history for this denom
+            msg!("No S snapshot for {} yet - starting one at the current S", denom);
+            UserCollateralSnapshot::starting_at(user_key, &pool_snapshot, current_epoch)
+        };
+
+        let (collateral_gain, total_gain) = settle_collateral_gain(
+            user_stake_amount,
+            &mut user_collateral_snapshot,
+            pool_snapshot.s_factor,
+            current_epoch,
+        )?;
+
+        if total_gain > 0 {
+            // SECURITY: Verify protocol vault has sufficient balance before transfer
+            require!(
+                vault_account.amount >= total_gain,
+                AerospacerProtocolError::InsufficientCollateral
+            );
+
+            let transfer_seeds = &[
+                b"protocol_collateral_vault".as_ref(),
+                denom.as_bytes(),
+                &[vault_bump],
+            ];
+            let transfer_signer = &[&transfer_seeds[..]];
+
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: vault_info.clone(),
+                    to: user_token_info.clone(),
+                    authority: vault_info.clone(),
+                },
+                transfer_signer,
+            );
+            anchor_spl::token::transfer(transfer_ctx, total_gain)?;
+
+            user_collateral_snapshot.pending_collateral_gain = 0;
+
+            update_total_collateral_from_account_info(
+                total_collateral_info,
+                -i64::try_from(total_gain).map_err(|_| AerospacerProtocolError::OverflowError)?,
+            )?;
+
+            total_claims += 1;
+            msg!("Claimed {} {} (new: {}, pending: {})", total_gain, denom, collateral_gain, total_gain - collateral_gain);
+        } else {
+            msg!("No collateral gains available for {}", denom);
+        }
+
+        // Persist the refreshed S snapshot
+        {
+            let mut data = user_snapshot_info.try_borrow_mut_data()?;
+            let mut writer: &mut [u8] = &mut data;
+            user_collateral_snapshot.try_serialize(&mut writer)?;
+        }
+
+        claimed_denoms.push(denom);
+    }
+
+    msg!("Liquidation gains claimed for {} of {} denoms", total_claims, claimed_denoms.len());
+    msg!("User: {}", user_key);
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct ClaimFrontendRewards<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"frontend", operator.key().as_ref()],
+        bump,
+        constraint = frontend.operator == operator.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub frontend: Account<'info, Frontend>,
+
+    #[account(
+        seeds = [b"reward_emission"],
+        bump
+    )]
+    pub reward_emission: Account<'info, RewardEmission>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_vault"],
+        bump
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Reward token mint - validated against the emission schedule
+    #[account(
+        constraint = reward_mint.key() == reward_emission.reward_mint @ AerospacerProtocolError::InvalidMint
+    )]
+    pub reward_mint: UncheckedAccount<'info>,
+
+    // Operator's reward token ATA, created when missing
+    #[account(
+        init_if_needed,
+        payer = operator,
+        associated_token::mint = reward_mint,
+        associated_token::authority = operator
+    )]
+    pub operator_reward_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Claim the reward emissions credited to a frontend by its tagged depositors' claims
+pub fn handler(ctx: Context<ClaimFrontendRewards>) -> Result<()> {
+    let claimable = ctx.accounts.frontend.pending_rewards;
+    if claimable == 0 {
+        msg!("No frontend rewards available");
+        return Ok(());
+    }
+
+    require!(
+        ctx.accounts.reward_vault.amount >= claimable,
+        AerospacerProtocolError::InsufficientCollateral
+    );
+
+    let transfer_seeds = &[
+        b"reward_vault".as_ref(),
+        &[ctx.bumps.reward_vault],
+    ];
+    let transfer_signer = &[&transfer_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            to: ctx.accounts.operator_reward_account.to_account_info(),
+            authority: ctx.accounts.reward_vault.to_account_info(),
+        },
+        transfer_signer,
+    );
+    anchor_spl::token::transfer(transfer_ctx, claimable)?;
+
+    ctx.accounts.frontend.pending_rewards = 0;
+
+    msg!("Frontend rewards claimed successfully");
+    msg!("Operator: {}", ctx.accounts.operator.key());
+    msg!("Amount: {}", claimable);
+
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::utils::*;
+use crate::error::*;
+use crate::trove_management::issue_reward_emissions;
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user_stake_amount", user.key().as_ref()],
+        bump,
+        constraint = user_stake_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_stake_amount: Account<'info, UserStakeAmount>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_emission"],
+        bump
+    )]
+    pub reward_emission: Account<'info, RewardEmission>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_vault"],
+        bump
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Reward token mint - validated against the emission schedule
+    #[account(
+        constraint = reward_mint.key() == reward_emission.reward_mint @ AerospacerProtocolError::InvalidMint
+    )]
+    pub reward_mint: UncheckedAccount<'info>,
+
+    // User's reward token ATA, created when missing
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = reward_mint,
+        associated_token::authority = user
+    )]
+    pub user_reward_account: Box<Account<'info, TokenAccount>>,
+
+    /// Optional position summary, refreshed when passed
+    #[account(
+        mut,
+        seeds = [b"user_index", user.key().as_ref()],
+        bump
+    )]
+    pub user_index: Option<Account<'info, UserIndex>>,
+
+    /// Frontend that onboarded the deposit - required when the deposit is tagged
+    #[account(
+        mut,
+        seeds = [b"frontend", frontend.operator.as_ref()],
+        bump
+    )]
+    pub frontend: Option<Account<'info, Frontend>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Claim the reward token emissions (reward G factor) earned by the caller's stability pool deposit
+pub fn handler(ctx: Context<ClaimRewards>) -> Result<()> {
+    let user_stake_amount = &mut ctx.accounts.user_stake_amount;
+    let mut state = ctx.accounts.state.load_mut()?;
+
+    issue_reward_emissions(&mut state, &mut ctx.accounts.reward_emission, Clock::get()?.unix_timestamp)?;
+
+    // Both G accumulators share the deposit snapshots, so settle both before re-snapshotting
+    let reward_gain = settle_reward_emissions(user_stake_amount, &state)?;
+    settle_fee_yield(user_stake_amount, &state)?;
+
+    // Re-snapshot the deposit so the settled rewards cannot be claimed twice
+    // (same compounding as stake/unstake - the deposit moves to the current P/scale/epoch)
+    if user_stake_amount.amount > 0 && user_stake_amount.p_snapshot > 0 {
+        let compounded = calculate_compounded_stake(
+            user_stake_amount.amount,
+            user_stake_amount.p_snapshot,
+            state.p_factor,
+            user_stake_amount.scale_snapshot,
+            state.current_scale,
+            user_stake_amount.epoch_snapshot,
+            state.epoch,
+        )?;
+        user_stake_amount.amount = compounded;
+        user_stake_amount.p_snapshot = state.p_factor;
+        user_stake_amount.epoch_snapshot = state.epoch;
+        user_stake_amount.scale_snapshot = state.current_scale;
+        user_stake_amount.g_snapshot = state.g_factor;
+        user_stake_amount.reward_g_snapshot = state.reward_g_factor;
+        user_stake_amount.last_update_block = Clock::get()?.slot;
+    }
+
+    let claimable = user_stake_amount.pending_reward_gain;
+    if claimable == 0 {
+        msg!("No reward emissions available");
+        if let Some(user_index) = ctx.accounts.user_index.as_mut() {
+            user_index.sync_stake(user_stake_amount);
+            user_index.last_update_slot = Clock::get()?.slot;
+        }
+        return Ok(());
+    }
+
+    // Tagged deposits pass the frontend's share on to it (it stays in the vault until claimed)
+    let (user_amount, frontend_amount) = if user_stake_amount.frontend != Pubkey::default() {
+        let frontend = ctx.accounts.frontend
+            .as_mut()
+            .ok_or(AerospacerProtocolError::UnknownFrontend)?;
+        require!(
+            frontend.operator == user_stake_amount.frontend,
+            AerospacerProtocolError::UnknownFrontend
+        );
+        let (user_amount, frontend_amount) = frontend.split_rewards(claimable)?;
+        frontend.pending_rewards = safe_add(frontend.pending_rewards, frontend_amount)?;
+        frontend.total_rewards_earned = safe_add(frontend.total_rewards_earned, frontend_amount)?;
+        (user_amount, frontend_amount)
+    } else {
+        (claimable, 0)
+    };
+
+    require!(
+        ctx.accounts.reward_vault.amount >= claimable,
+        AerospacerProtocolError::InsufficientCollateral
+    );
+
+    let transfer_seeds = &[
+        b"reward_vault".as_ref(),
+        &[ctx.bumps.reward_vault],
+    ];
+    let transfer_signer = &[&transfer_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            to: ctx.accounts.user_reward_account.to_account_info(),
+            authority: ctx.accounts.reward_vault.to_account_info(),
+        },
+        transfer_signer,
+    );
+    if user_amount > 0 {
+        anchor_spl::token::transfer(transfer_ctx, user_amount)?;
+    }
+
+    user_stake_amount.pending_reward_gain = 0;
+
+    msg!("Reward emissions claimed successfully");
+    msg!("User: {}", ctx.accounts.user.key());
+    msg!("Settled this claim: {}", reward_gain);
+    msg!("Amount: {}", user_amount);
+    if frontend_amount > 0 {
+        msg!("Frontend share: {} ({})", frontend_amount, user_stake_amount.frontend);
+    }
+    msg!("Remaining deposit: {} aUSD", user_stake_amount.amount);
+
+    if let Some(user_index) = ctx.accounts.user_index.as_mut() {
+        user_index.sync_stake(user_stake_amount);
+        user_index.last_update_slot = Clock::get()?.slot;
+    }
+
+    Ok(())
+}
@@ -1,11 +1,29 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer, Burn};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, Burn};
 use crate::state::*;
 use crate::error::*;
+use crate::utils::*;
+use crate::events::TroveClosed;
+use crate::liquidation_reserve::burn_liquidation_reserve;
+use crate::trove_index::tombstone_entry;
+use crate::trove_archive::{archive_trove_record, ArchiveAccounts, ACCOUNT_COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID};
+use crate::validation::{ValidateParams, validate_denom};
+
+// Accounts per additional collateral leg in remaining_accounts
+const ACCOUNTS_PER_LEG: usize = 4;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CloseTroveParams {
     pub collateral_denom: String,
+    pub trove_id: u16, // Which of the owner's troves (0 = the wallet's first trove)
+    pub memo: Option<[u8; 32]>, // Optional integrator memo, echoed in the emitted event
+}
+
+impl ValidateParams for CloseTroveParams {
+    fn validate(&self) -> Result<()> {
+        validate_denom(&self.collateral_denom)
+    }
 }
 
 #[derive(Accounts)]
@@ -16,16 +34,18 @@ pub struct CloseTrove<'info> {
 
     #[account(
         mut,
-        seeds = [b"user_debt_amount", user.key().as_ref()],
+        close = user,
+        seeds = [b"user_debt_amount", user.key().as_ref(), trove_id_seed(params.trove_id).as_slice()],
         bump,
         constraint = user_debt_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized,
-        constraint = user_debt_amount.amount > 0 @ AerospacerProtocolError::TroveDoesNotExist
+        constraint = matches!(user_debt_amount.status, TroveStatus::Active | TroveStatus::RedeemedClosed | TroveStatus::LiquidatedWithSurplus) @ AerospacerProtocolError::TroveDoesNotExist
     )]
     pub user_debt_amount: Box<Account<'info, UserDebtAmount>>,
 
     #[account(
         mut,
-        seeds = [b"user_collateral_amount", user.key().as_ref(), params.collateral_denom.as_bytes()],
+        close = user,
+        seeds = [b"user_collateral_amount", user.key().as_ref(), trove_id_seed(params.trove_id).as_slice(), params.collateral_denom.as_bytes()],
         bump,
         constraint = user_collateral_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
     )]
@@ -34,14 +54,14 @@ pub struct CloseTrove<'info> {
     #[account(
         mut,
         close = user,
-        seeds = [b"liquidity_threshold", user.key().as_ref()],
+        seeds = [b"liquidity_threshold", user.key().as_ref(), trove_id_seed(params.trove_id).as_slice()],
         bump,
         constraint = liquidity_threshold.owner == user.key() @ AerospacerProtocolError::Unauthorized
     )]
     pub liquidity_threshold: Box<Account<'info, LiquidityThreshold>>,
 
     #[account(mut)]
-    pub state: Box<Account<'info, StateAccount>>,
+    pub state: AccountLoader<'info, StateAccount>,
 
     // User's stablecoin account (to pay off debt)
     #[account(
@@ -50,92 +70,294 @@ pub struct CloseTrove<'info> {
     )]
     pub user_stablecoin_account: Box<Account<'info, TokenAccount>>,
 
-    // User's collateral account (to receive collateral back)
-    #[account(
-        mut,
-        constraint = user_collateral_account.owner == user.key() @ AerospacerProtocolError::Unauthorized
-    )]
-    pub user_collateral_account: Box<Account<'info, TokenAccount>>,
-
     // Protocol's collateral vault
     #[account(
         mut,
         seeds = [b"protocol_collateral_vault", params.collateral_denom.as_bytes()],
-        bump,
-        constraint = protocol_collateral_vault.mint == user_collateral_account.mint @ AerospacerProtocolError::InvalidMint
+        bump
     )]
     pub protocol_collateral_vault: Box<Account<'info, TokenAccount>>,
 
+    #[account(
+        constraint = collateral_mint.key() == protocol_collateral_vault.mint @ AerospacerProtocolError::InvalidMint
+    )]
+    pub collateral_mint: Box<Account<'info, Mint>>,
+
+    // User's collateral ATA (to receive collateral back), created when missing
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = user
+    )]
+    pub user_collateral_account: Box<Account<'info, TokenAccount>>,
+
     /// CHECK: This is the stable coin mint account
     #[account(
         mut,
-        constraint = stable_coin_mint.key() == state.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
     )]
     pub stable_coin_mint: UncheckedAccount<'info>,
 
-    /// CHECK: Per-denom collateral total PDA
+    /// CHECK: Liquidation reserve vault - the trove's reserve is burned from it (never created
+    /// while no trove was opened with a reserve)
+    #[account(
+        mut,
+        seeds = [b"liquidation_reserve_vault"],
+        bump
+    )]
+    pub liquidation_reserve_vault: UncheckedAccount<'info>,
+
     #[account(
         mut,
         seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
         bump
     )]
-    pub total_collateral_amount: AccountInfo<'info>,
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+
+    /// Optional position summary, refreshed when passed
+    #[account(
+        mut,
+        seeds = [b"user_index", user.key().as_ref()],
+        bump
+    )]
+    pub user_index: Option<Box<Account<'info, UserIndex>>>,
+    
+    /// Trove id registry - absent only for troves opened before wallets could hold several
+    #[account(
+        mut,
+        seeds = [b"trove_registry", user.key().as_ref()],
+        bump
+    )]
+    pub trove_registry: Option<Box<Account<'info, TroveRegistry>>>,
+    
+    /// Trove index page holding the trove's entry - required unless the trove was opened before
+    /// the index existed
+    #[account(
+        mut,
+        seeds = [b"trove_index_page", user_debt_amount.index_page_seed().as_ref()],
+        bump
+    )]
+    pub trove_index_page: Option<Box<Account<'info, TroveIndexPage>>>,
+
+    /// Compressed trove archive - when passed (with the three accounts below) a record of the
+    /// trove is appended to it before its accounts are closed
+    #[account(
+        mut,
+        seeds = [b"trove_archive"],
+        bump
+    )]
+    pub trove_archive: Option<Box<Account<'info, TroveArchive>>>,
+
+    /// CHECK: Archive tree - validated against trove_archive.merkle_tree
+    #[account(mut)]
+    pub archive_merkle_tree: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: SPL account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID @ AerospacerProtocolError::InvalidAddress)]
+    pub compression_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: SPL noop program
+    #[account(address = NOOP_PROGRAM_ID @ AerospacerProtocolError::InvalidAddress)]
+    pub noop_program: Option<UncheckedAccount<'info>>,
 
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
+    
+    /// Optional operation history, appended to when passed
+    #[account(
+        mut,
+        seeds = [b"trove_history", user.key().as_ref()],
+        bump
+    )]
+    pub trove_history: Option<Box<Account<'info, TroveHistory>>>,
+    
+    // remaining_accounts should contain, for every other collateral denom the trove holds:
+    // [UserCollateralAmount, TotalCollateralAmount, protocol_collateral_vault, user token account for that mint]
+    // (all writable). Each leg is returned to the user and its UserCollateralAmount is closed.
 }
 
-pub fn handler(ctx: Context<CloseTrove>, params: CloseTroveParams) -> Result<()> {
-    // Validate collateral denomination
+// Additional collateral denom held by the trove, validated from remaining_accounts
+struct CollateralLeg<'a, 'info> {
+    collateral: UserCollateralAmount,
+    collateral_account: &'a AccountInfo<'info>,
+    total_collateral_account: &'a AccountInfo<'info>,
+    vault: &'a AccountInfo<'info>,
+    vault_bump: u8,
+    user_token_account: &'a AccountInfo<'info>,
+}
+
+fn parse_collateral_legs<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    user: &Pubkey,
+    trove_id: u16,
+    primary_denom: &str,
+) -> Result<Vec<CollateralLeg<'a, 'info>>> {
+    let trove_seed = trove_id_seed(trove_id);
     require!(
-        !params.collateral_denom.is_empty(),
-        AerospacerProtocolError::InvalidAmount
+        remaining_accounts.len().is_multiple_of(ACCOUNTS_PER_LEG),
+        AerospacerProtocolError::InvalidList
     );
     
+    let mut legs: Vec<CollateralLeg> = Vec::new();
+    for chunk in remaining_accounts.chunks(ACCOUNTS_PER_LEG) {
+        let (collateral_account, total_collateral_account, vault, user_token_account) =
+            (&chunk[0], &chunk[1], &chunk[2], &chunk[3]);
+        
+        require!(
+            collateral_account.owner == &crate::ID && total_collateral_account.owner == &crate::ID,
+            AerospacerProtocolError::Unauthorized
+        );
+        require!(
+            collateral_account.is_writable && total_collateral_account.is_writable
+                && vault.is_writable && user_token_account.is_writable,
+            AerospacerProtocolError::InvalidList
+        );
+        
+        let collateral = {
+            let data = collateral_account.try_borrow_data()?;
+            UserCollateralAmount::try_deserialize(&mut &data[..])?
+        };
+        require!(
+            collateral.owner == *user && collateral.trove_id == trove_id,
+            AerospacerProtocolError::Unauthorized
+        );
+        
+        // Each denom only once, and never the primary one (it has its own accounts)
+        require!(
+            collateral.denom != primary_denom && legs.iter().all(|leg| leg.collateral.denom != collateral.denom),
+            AerospacerProtocolError::InvalidList
+        );
+        
+        // SECURITY: All per-denom accounts must be the canonical PDAs for this leg's denom
+        let (expected_collateral_pda, _bump) = Pubkey::find_program_address(
+            &UserCollateralAmount::seeds(user, &collateral.denom, &trove_seed),
+            &crate::ID,
+        );
+        let (expected_total_pda, _bump) = Pubkey::find_program_address(
+            &TotalCollateralAmount::seeds(&collateral.denom),
+            &crate::ID,
+        );
+        let (expected_vault_pda, vault_bump) = Pubkey::find_program_address(
+            &[b"protocol_collateral_vault", collateral.denom.as_bytes()],
+            &crate::ID,
+        );
+        require!(
+            expected_collateral_pda == *collateral_account.key
+                && expected_total_pda == *total_collateral_account.key
+                && expected_vault_pda == *vault.key,
+            AerospacerProtocolError::InvalidList
+        );
+        
+        // User token account must belong to the user and hold the vault's mint
+        let vault_mint = {
+            let data = vault.try_borrow_data()?;
+            TokenAccount::try_deserialize(&mut &data[..])?.mint
+        };
+        let user_token = {
+            let data = user_token_account.try_borrow_data()?;
+            TokenAccount::try_deserialize(&mut &data[..])?
+        };
+        require!(
+            user_token.owner == *user,
+            AerospacerProtocolError::Unauthorized
+        );
+        require!(
+            user_token.mint == vault_mint,
+            AerospacerProtocolError::InvalidMint
+        );
+        
+        legs.push(CollateralLeg {
+            collateral,
+            collateral_account,
+            total_collateral_account,
+            vault,
+            vault_bump,
+            user_token_account,
+        });
+    }
+    
+    Ok(legs)
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CloseTrove<'info>>, params: CloseTroveParams) -> Result<()> {
+    params.validate()?;
+    
+    let mut legs = parse_collateral_legs(
+        ctx.remaining_accounts,
+        &ctx.accounts.user.key(),
+        params.trove_id,
+        &params.collateral_denom,
+    )?;
+    // A leg left out would be stranded once the debt record is closed
+    ctx.accounts.user_debt_amount.require_all_legs(legs.len() + 1)?;
+    
     // Apply pending redistribution rewards before closing trove
+    // Every leg carries its own L snapshots, so each can add redistributed debt
     use crate::trove_management::apply_pending_rewards;
-    let total_collateral_data = ctx.accounts.total_collateral_amount.try_borrow_mut_data()?;
-    let total_collateral: TotalCollateralAmount = TotalCollateralAmount::try_from_slice(&total_collateral_data[8..])?;
-    drop(total_collateral_data);
-    
     apply_pending_rewards(
         &mut ctx.accounts.user_debt_amount,
         &mut ctx.accounts.user_collateral_amount,
-        &total_collateral,
+        &*ctx.accounts.total_collateral_amount.load()?,
     )?;
+    for leg in legs.iter_mut() {
+        let data = leg.total_collateral_account.try_borrow_data()?;
+        let total_collateral = TotalCollateralAmount::from_account_data(&data)?;
+        apply_pending_rewards(
+            &mut ctx.accounts.user_debt_amount,
+            &mut leg.collateral,
+            total_collateral,
+        )?;
+    }
     
     let debt_amount = ctx.accounts.user_debt_amount.amount;
     let collateral_amount = ctx.accounts.user_collateral_amount.amount;
     
+    // The user repays the net debt; the liquidation reserve covers the rest from its vault
+    let liquidation_reserve = ctx.accounts.user_debt_amount.liquidation_reserve;
+    let net_debt = ctx.accounts.user_debt_amount.net_debt();
+    
     // Validate user has sufficient stablecoins to repay full debt
     require!(
-        ctx.accounts.user_stablecoin_account.amount >= debt_amount,
+        ctx.accounts.user_stablecoin_account.amount >= net_debt,
         AerospacerProtocolError::InsufficientCollateral
     );
     
     msg!("Closing trove for user: {}", ctx.accounts.user.key());
-    msg!("Debt to repay: {} aUSD", debt_amount);
+    msg!("Debt to repay: {} aUSD (liquidation reserve: {})", debt_amount, liquidation_reserve);
     msg!("Collateral to return: {} {}", collateral_amount, params.collateral_denom);
     
     // STEP 1: Update global state BEFORE token operations (for atomicity)
     // If any subsequent CPI fails, this will rollback automatically
-    ctx.accounts.state.total_debt_amount = ctx.accounts.state.total_debt_amount
-        .checked_sub(debt_amount)
-        .ok_or(AerospacerProtocolError::OverflowError)?;
+    // A fully redeemed trove already left the trove counters when it was zeroed
+    let was_active = ctx.accounts.user_debt_amount.is_active();
+    let total_debt_amount = {
+        let mut state = ctx.accounts.state.load_mut()?;
+        state.total_debt_amount = state.total_debt_amount
+            .checked_sub(debt_amount)
+            .ok_or(AerospacerProtocolError::OverflowError)?;
+        if was_active {
+            state.record_trove_ended();
+        }
+        state.total_debt_amount
+    };
     
     // Update total collateral for this denomination
-    let mut total_collateral_data = ctx.accounts.total_collateral_amount.try_borrow_mut_data()?;
-    let mut total_collateral: TotalCollateralAmount = TotalCollateralAmount::try_from_slice(&total_collateral_data[8..])?;
-    total_collateral.amount = total_collateral.amount
-        .checked_sub(collateral_amount)
-        .ok_or(AerospacerProtocolError::OverflowError)?;
-    total_collateral.try_serialize(&mut &mut total_collateral_data[8..])?;
-    drop(total_collateral_data);
+    {
+        let mut total_collateral = ctx.accounts.total_collateral_amount.load_mut()?;
+        total_collateral.amount = total_collateral.amount
+            .checked_sub(collateral_amount)
+            .ok_or(AerospacerProtocolError::OverflowError)?;
+        if was_active {
+            total_collateral.record_trove_ended();
+        }
+    }
     
-    msg!("Updated global state - debt: {}, collateral tracked", ctx.accounts.state.total_debt_amount);
+    msg!("Updated global state - debt: {}, collateral tracked", total_debt_amount);
     
     // STEP 2: Burn stablecoins to repay debt
-    if debt_amount > 0 {
+    if net_debt > 0 {
         let burn_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
             Burn {
@@ -144,10 +366,17 @@ pub fn handler(ctx: Context<CloseTrove>, params: CloseTroveParams) -> Result<()>
                 authority: ctx.accounts.user.to_account_info(),
             },
         );
-        anchor_spl::token::burn(burn_ctx, debt_amount)?;
+        anchor_spl::token::burn(burn_ctx, net_debt)?;
         
-        msg!("Burned {} aUSD to repay debt", debt_amount);
+        msg!("Burned {} aUSD to repay debt", net_debt);
     }
+    burn_liquidation_reserve(
+        &ctx.accounts.liquidation_reserve_vault.to_account_info(),
+        ctx.bumps.liquidation_reserve_vault,
+        &ctx.accounts.stable_coin_mint.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        liquidation_reserve,
+    )?;
     
     // STEP 3: Transfer collateral back to user
     if collateral_amount > 0 {
@@ -174,19 +403,132 @@ pub fn handler(ctx: Context<CloseTrove>, params: CloseTroveParams) -> Result<()>
         msg!("Transferred {} {} back to user", collateral_amount, params.collateral_denom);
     }
     
+    // STEP 3b: Return every additional collateral leg and close its record
+    for leg in legs.iter() {
+        let leg_amount = leg.collateral.amount;
+        let denom = &leg.collateral.denom;
+        
+        if leg_amount > 0 {
+            update_total_collateral_from_account_info(
+                leg.total_collateral_account,
+                -i64::try_from(leg_amount).map_err(|_| AerospacerProtocolError::OverflowError)?,
+            )?;
+            
+            let seeds = &[
+                b"protocol_collateral_vault",
+                denom.as_bytes(),
+                &[leg.vault_bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+            
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: leg.vault.clone(),
+                    to: leg.user_token_account.clone(),
+                    authority: leg.vault.clone(),
+                },
+                signer_seeds,
+            );
+            anchor_spl::token::transfer(transfer_ctx, leg_amount)?;
+        }
+        
+        close_program_account(leg.collateral_account, &ctx.accounts.user.to_account_info())?;
+        
+        msg!("Transferred {} {} back to user (collateral record closed)", leg_amount, denom);
+    }
+    
     // STEP 4: Zero out user accounts AFTER successful token operations
     ctx.accounts.user_debt_amount.amount = 0;
+    ctx.accounts.user_debt_amount.liquidation_reserve = 0;
     ctx.accounts.user_collateral_amount.amount = 0;
+    ctx.accounts.user_debt_amount.transition(TroveStatus::Closed)?;
     
     // NOTE: Sorted troves management moved off-chain
-    // LiquidityThreshold account is automatically closed via Anchor's `close` constraint
-    // This ensures proper lamport refund and account cleanup
+    // UserDebtAmount, LiquidityThreshold and the primary UserCollateralAmount are closed via Anchor's
+    // `close` constraint. This ensures proper lamport refund and account cleanup, and lets the same
+    // trove id be opened again.
     
     msg!("Trove closed successfully - All accounts cleaned up");
     msg!("Final state:");
     msg!("  Debt repaid: {} aUSD", debt_amount);
     msg!("  Collateral returned: {} {}", collateral_amount, params.collateral_denom);
-    msg!("  Total protocol debt: {}", ctx.accounts.state.total_debt_amount);
+    for leg in legs.iter() {
+        msg!("  Collateral returned: {} {}", leg.collateral.amount, leg.collateral.denom);
+    }
+    msg!("  Total protocol debt: {}", total_debt_amount);
+    
+    emit!(TroveClosed {
+        owner: ctx.accounts.user.key(),
+        trove_id: params.trove_id,
+        collateral_denom: params.collateral_denom.clone(),
+        debt_repaid: debt_amount,
+        collateral_returned: collateral_amount,
+        tag: ctx.accounts.user_debt_amount.tag,
+        memo: params.memo,
+    });
+    
+    if let Some(trove_history) = ctx.accounts.trove_history.as_mut() {
+        trove_history.record(TroveHistoryEntry {
+            op: TroveHistoryOp::Close,
+            trove_id: params.trove_id,
+            amount: debt_amount,
+            icr: 0,
+            slot: Clock::get()?.slot,
+        });
+    }
+    
+    let index_position = ctx.accounts.user_debt_amount.index_position;
+    if index_position != 0 {
+        let trove_index_page = ctx.accounts.trove_index_page
+            .as_mut()
+            .ok_or(AerospacerProtocolError::InvalidTroveIndexPage)?;
+        tombstone_entry(
+            trove_index_page,
+            &mut *ctx.accounts.state.load_mut()?,
+            index_position,
+            TroveIndexEntry { owner: ctx.accounts.user.key(), trove_id: params.trove_id },
+        )?;
+    }
+    
+    let other_troves_open = match ctx.accounts.trove_registry.as_mut() {
+        Some(trove_registry) => {
+            trove_registry.unregister(params.trove_id);
+            !trove_registry.trove_ids.is_empty()
+        }
+        None => false,
+    };
+    
+    // With other troves still open the summary is left for sync_user_index to rebuild
+    if let Some(user_index) = ctx.accounts.user_index.as_mut() {
+        if !other_troves_open {
+            user_index.clear_troves();
+        }
+        user_index.last_update_slot = Clock::get()?.slot;
+    }
+    
+    // Keep a compressed record of the finished trove
+    if let (Some(trove_archive), Some(merkle_tree), Some(compression_program), Some(noop_program)) = (
+        ctx.accounts.trove_archive.as_mut(),
+        ctx.accounts.archive_merkle_tree.as_ref(),
+        ctx.accounts.compression_program.as_ref(),
+        ctx.accounts.noop_program.as_ref(),
+    ) {
+        archive_trove_record(
+            ArchiveAccounts {
+                trove_archive,
+                merkle_tree: &merkle_tree.to_account_info(),
+                compression_program: &compression_program.to_account_info(),
+                noop_program: &noop_program.to_account_info(),
+            },
+            &ctx.accounts.user_debt_amount,
+        )?;
+    } else {
+        require!(
+            ctx.accounts.trove_archive.is_none(),
+            AerospacerProtocolError::InvalidList
+        );
+    }
     
     Ok(())
 }
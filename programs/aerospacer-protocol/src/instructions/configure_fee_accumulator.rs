@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ConfigureFeeAccumulatorParams {
+    pub min_distribution_amount: u64, // Protocol fees below this are parked for distribute_accumulated, 0 = off
+}
+
+#[derive(Accounts)]
+pub struct ConfigureFeeAccumulator<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub stable_coin_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + FeeAccumulator::LEN,
+        seeds = [b"fee_accumulator"],
+        bump
+    )]
+    pub fee_accumulator: Account<'info, FeeAccumulator>,
+
+    // Holds the parked fees until distribute_accumulated sends them through aerospacer-fees
+    #[account(
+        init_if_needed,
+        payer = admin,
+        token::mint = stable_coin_mint,
+        token::authority = fee_accumulator_vault,
+        seeds = [b"fee_accumulator_vault"],
+        bump
+    )]
+    pub fee_accumulator_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for configure_fee_accumulator instruction
+/// Only affects future fees; fees already parked stay pending until distributed
+pub fn handler(ctx: Context<ConfigureFeeAccumulator>, params: ConfigureFeeAccumulatorParams) -> Result<()> {
+    let fee_accumulator = &mut ctx.accounts.fee_accumulator;
+    fee_accumulator.min_distribution_amount = params.min_distribution_amount;
+
+    msg!("Fee accumulator threshold: {} aUSD", fee_accumulator.min_distribution_amount);
+    msg!("Parked fees pending: {} aUSD", fee_accumulator.pending.iter().sum::<u64>());
+
+    Ok(())
+}
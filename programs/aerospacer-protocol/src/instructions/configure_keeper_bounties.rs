@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::validation::{ValidateParams, validate_bps};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ConfigureKeeperBountiesParams {
+    pub keeper_bounty_bps: Option<u16>, // Share of liquidation penalties paid to registered keepers, 0 = off
+    pub flat_bounty: Option<u64>,       // aUSD per liquidated trove from the bounty fund, 0 = off
+}
+
+impl ValidateParams for ConfigureKeeperBountiesParams {
+    fn validate(&self) -> Result<()> {
+        if let Some(bps) = self.keeper_bounty_bps {
+            validate_bps(bps, MAX_KEEPER_BOUNTY_BPS, AerospacerProtocolError::InvalidKeeperBounty)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ConfigureKeeperBounties<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + KeeperBountyFund::LEN,
+        seeds = [b"keeper_bounty_fund"],
+        bump
+    )]
+    pub keeper_bounty_fund: Account<'info, KeeperBountyFund>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for configure_keeper_bounties instruction
+/// Only affects future liquidations; the fund balance is left untouched
+pub fn handler(ctx: Context<ConfigureKeeperBounties>, params: ConfigureKeeperBountiesParams) -> Result<()> {
+    params.validate()?;
+    
+    let mut state = ctx.accounts.state.load_mut()?;
+    if let Some(bps) = params.keeper_bounty_bps {
+        state.keeper_bounty_bps = bps;
+    }
+    
+    let fund = &mut ctx.accounts.keeper_bounty_fund;
+    if let Some(flat_bounty) = params.flat_bounty {
+        fund.flat_bounty = flat_bounty;
+    }
+    
+    msg!("Keeper bounty share of liquidation penalties: {} bps", state.keeper_bounty_bps);
+    msg!("Flat keeper bounty: {} aUSD per trove (fund balance: {})", fund.flat_bounty, fund.balance);
+    
+    Ok(())
+}
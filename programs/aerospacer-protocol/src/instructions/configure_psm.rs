@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint};
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::validation::{ValidateParams, validate_bps};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ConfigurePsmParams {
+    pub swap_in_fee_bps: u16,
+    pub swap_out_fee_bps: u16,
+    pub debt_ceiling: u64,   // Lowering it below total_minted only blocks further swap-ins
+}
+
+impl ValidateParams for ConfigurePsmParams {
+    fn validate(&self) -> Result<()> {
+        validate_bps(self.swap_in_fee_bps, MAX_PSM_FEE_BPS, AerospacerProtocolError::InvalidPsmFee)?;
+        validate_bps(self.swap_out_fee_bps, MAX_PSM_FEE_BPS, AerospacerProtocolError::InvalidPsmFee)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: ConfigurePsmParams)]
+pub struct ConfigurePsm<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + PsmConfig::LEN,
+        seeds = [b"psm_config"],
+        bump
+    )]
+    pub psm_config: Account<'info, PsmConfig>,
+    
+    // Reserve stablecoin (USDC) - fixed once the PSM is created
+    pub reserve_mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        token::mint = reserve_mint,
+        token::authority = psm_vault,
+        seeds = [b"psm_vault"],
+        bump
+    )]
+    pub psm_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Handler for configure_psm instruction
+/// Creates the Peg Stability Module on first call, afterwards only updates fees and ceiling
+pub fn handler(ctx: Context<ConfigurePsm>, params: ConfigurePsmParams) -> Result<()> {
+    params.validate()?;
+    
+    let reserve_mint = &ctx.accounts.reserve_mint;
+    let psm_config = &mut ctx.accounts.psm_config;
+    
+    if psm_config.reserve_mint == Pubkey::default() {
+        require!(
+            reserve_mint.decimals <= STABLECOIN_DECIMALS,
+            AerospacerProtocolError::InvalidMint
+        );
+        psm_config.reserve_mint = reserve_mint.key();
+        psm_config.reserve_decimals = reserve_mint.decimals;
+        psm_config.total_minted = 0;
+        msg!("PSM created for reserve mint: {}", reserve_mint.key());
+    } else {
+        require!(
+            psm_config.reserve_mint == reserve_mint.key(),
+            AerospacerProtocolError::InvalidMint
+        );
+    }
+    
+    psm_config.swap_in_fee_bps = params.swap_in_fee_bps;
+    psm_config.swap_out_fee_bps = params.swap_out_fee_bps;
+    psm_config.debt_ceiling = params.debt_ceiling;
+    
+    msg!("PSM configured");
+    msg!("Swap in fee: {} bps", params.swap_in_fee_bps);
+    msg!("Swap out fee: {} bps", params.swap_out_fee_bps);
+    msg!("Debt ceiling: {} aUSD (minted: {})", params.debt_ceiling, psm_config.total_minted);
+    
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::state::{StateAccount, RebalancerConfig};
+use crate::error::AerospacerProtocolError;
+use crate::validation::{ValidateParams, validate_pubkey};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ConfigureRebalancerParams {
+    pub program: Option<Pubkey>, // Hook program (unchanged when None)
+    pub enabled: bool,
+}
+
+impl ValidateParams for ConfigureRebalancerParams {
+    fn validate(&self) -> Result<()> {
+        if let Some(program) = self.program.as_ref() {
+            validate_pubkey(program)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ConfigureRebalancer<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + RebalancerConfig::LEN,
+        seeds = [b"rebalancer_config"],
+        bump
+    )]
+    pub rebalancer_config: Account<'info, RebalancerConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for configure_rebalancer instruction
+/// Registers the external strategy program that forward_seized_collateral hands undistributed
+/// seized collateral to, and switches forwarding on or off
+pub fn handler(ctx: Context<ConfigureRebalancer>, params: ConfigureRebalancerParams) -> Result<()> {
+    params.validate()?;
+
+    let config = &mut ctx.accounts.rebalancer_config;
+    if let Some(program) = params.program {
+        config.program = program;
+    }
+
+    // Never enabled without a hook program to forward to
+    require!(
+        !params.enabled || config.program != Pubkey::default(),
+        AerospacerProtocolError::InvalidAddress
+    );
+    config.enabled = params.enabled;
+
+    msg!("Rebalancer configured");
+    msg!("Program: {}", config.program);
+    msg!("Enabled: {}", config.enabled);
+
+    Ok(())
+}
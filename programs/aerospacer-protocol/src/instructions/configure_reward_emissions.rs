@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::trove_management::issue_reward_emissions;
+use crate::validation::{ValidateParams};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ConfigureRewardEmissionsParams {
+    pub emission_per_second: u64,   // Reward token base units streamed per second
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+impl ValidateParams for ConfigureRewardEmissionsParams {
+    fn validate(&self) -> Result<()> {
+        require!(
+            self.end_time > self.start_time,
+            AerospacerProtocolError::InvalidAmount
+        );
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ConfigureRewardEmissions<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + RewardEmission::LEN,
+        seeds = [b"reward_emission"],
+        bump
+    )]
+    pub reward_emission: Account<'info, RewardEmission>,
+    
+    pub reward_mint: Account<'info, Mint>,
+    
+    /// Reward vault - funded by the admin with the tokens to be emitted
+    #[account(
+        init_if_needed,
+        payer = admin,
+        token::mint = reward_mint,
+        token::authority = reward_vault,
+        seeds = [b"reward_vault"],
+        bump
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ConfigureRewardEmissions>, params: ConfigureRewardEmissionsParams) -> Result<()> {
+    params.validate()?;
+    
+    let now = Clock::get()?.unix_timestamp;
+    let emission = &mut ctx.accounts.reward_emission;
+    
+    if emission.reward_mint == Pubkey::default() {
+        emission.reward_mint = ctx.accounts.reward_mint.key();
+        emission.last_issuance_time = now;
+        emission.total_issued = 0;
+        ctx.accounts.state.load_mut()?.reward_emissions_enabled = 1;
+        msg!("Reward emissions initialized for mint {}", emission.reward_mint);
+    } else {
+        // The reward token cannot change once depositors have accrued it
+        require!(
+            emission.reward_mint == ctx.accounts.reward_mint.key(),
+            AerospacerProtocolError::InvalidMint
+        );
+        
+        // Credit what accrued under the old schedule before it changes
+        issue_reward_emissions(&mut *ctx.accounts.state.load_mut()?, emission, now)?;
+    }
+    
+    emission.emission_per_second = params.emission_per_second;
+    emission.start_time = params.start_time;
+    emission.end_time = params.end_time;
+    
+    msg!("Reward emissions configured: {} per second from {} to {}",
+         params.emission_per_second, params.start_time, params.end_time);
+    msg!("Total issued so far: {}", emission.total_issued);
+    
+    Ok(())
+}
@@ -0,0 +1,371 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, Burn};
+use crate::state::*;
+use crate::error::*;
+use crate::fees_integration::*;
+use crate::instructions::redeem::{fill_redemption, load_redemption_price, RedemptionFill, RedemptionVaults};
+use crate::liquidation_reserve::burn_liquidation_reserve;
+use crate::trove_management::{RedemptionLimits, RedemptionResult};
+use crate::validation::{ValidateParams, validate_denom};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ContinueRedemptionParams {
+    pub collateral_denom: String,
+    pub cancel: bool, // Return the escrowed aUSD to the redeemer and close the ticket instead
+    pub max_troves: u8, // Redeem from at most this many troves, 0 = no cap
+    pub max_icr: u64, // Stop at the first trove above this live ICR (micro-percent), 0 = no cap
+}
+
+impl ValidateParams for ContinueRedemptionParams {
+    fn validate(&self) -> Result<()> {
+        validate_denom(&self.collateral_denom)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: ContinueRedemptionParams)]
+pub struct ContinueRedemption<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"redemption_ticket", user.key().as_ref(), params.collateral_denom.as_bytes()],
+        bump,
+        constraint = redemption_ticket.redeemer == user.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub redemption_ticket: Box<Account<'info, RedemptionTicket>>,
+
+    #[account(
+        mut,
+        seeds = [b"redemption_escrow_vault"],
+        bump
+    )]
+    pub redemption_escrow_vault: Box<Account<'info, TokenAccount>>,
+
+    // Receives the escrow back on cancel
+    #[account(
+        mut,
+        constraint = user_stablecoin_account.owner == user.key() @ AerospacerProtocolError::Unauthorized,
+        constraint = user_stablecoin_account.mint == stable_coin_mint.key() @ AerospacerProtocolError::InvalidMint
+    )]
+    pub user_stablecoin_account: Box<Account<'info, TokenAccount>>,
+
+    /// Collateral mint of the denom - the vault transfer rejects any other
+    pub collateral_mint: Box<Account<'info, Mint>>,
+
+    // Redeemer's collateral ATA, created when missing
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = user
+    )]
+    pub user_collateral_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Protocol collateral vault PDA
+    #[account(
+        mut,
+        seeds = [b"protocol_collateral_vault", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub protocol_collateral_vault: AccountInfo<'info>,
+
+    /// CHECK: This is the stable coin mint account
+    #[account(
+        mut,
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub stable_coin_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Liquidation reserve vault - reserves of fully redeemed troves are burned from it
+    #[account(
+        mut,
+        seeds = [b"liquidation_reserve_vault"],
+        bump
+    )]
+    pub liquidation_reserve_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+
+    // Oracle context - integration with our aerospacer-oracle
+    /// CHECK: Our oracle program - validated against state
+    #[account(
+        mut,
+        constraint = oracle_program.key() == state.load()?.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_program: AccountInfo<'info>,
+
+    /// CHECK: Oracle state account - validated against state
+    #[account(
+        mut,
+        constraint = oracle_state.key() == state.load()?.oracle_state_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_state: AccountInfo<'info>,
+
+    // Fee distribution accounts, as in redeem - the fee on the escrow is charged as it is filled
+    /// CHECK: Fees program - validated against state
+    #[account(
+        constraint = fees_program.key() == state.load()?.fee_distributor_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub fees_program: AccountInfo<'info>,
+    
+    /// CHECK: Fees state account - validated against state
+    #[account(
+        mut,
+        constraint = fees_state.key() == state.load()?.fee_state_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub fees_state: AccountInfo<'info>,
+    
+    /// CHECK: Stability pool token account
+    #[account(mut)]
+    pub stability_pool_token_account: AccountInfo<'info>,
+    
+    /// CHECK: Protocol's fee caller PDA - signs the distribute_fee CPI, allowlisted in aerospacer-fees
+    #[account(
+        seeds = [b"fee_caller"],
+        bump
+    )]
+    pub fee_caller: UncheckedAccount<'info>,
+    
+    /// CHECK: aerospacer-fees fee vault - checked by the fees program
+    #[account(mut)]
+    pub fee_vault: AccountInfo<'info>,
+    
+    /// CHECK: aerospacer-fees per-epoch fee stats - checked by the fees program
+    #[account(mut)]
+    pub fee_epoch_stats: AccountInfo<'info>,
+
+    /// Dust fee accumulator - optional; a fee below its threshold is parked instead of distributed
+    #[account(
+        mut,
+        seeds = [b"fee_accumulator"],
+        bump
+    )]
+    pub fee_accumulator: Option<Box<Account<'info, FeeAccumulator>>>,
+    
+    /// CHECK: Fee accumulator vault PDA - required with the fee accumulator
+    #[account(
+        mut,
+        seeds = [b"fee_accumulator_vault"],
+        bump
+    )]
+    pub fee_accumulator_vault: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// Clock sysvar for the oracle price read
+    pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: Pyth price account of the redeemed denom - bound to the denom by the oracle.
+    /// Troves are ordered by their live ICR at this price, as in redeem.
+    pub pyth_price_account: Option<AccountInfo<'info>>,
+
+    // remaining_accounts: the next pre-sorted troves, 4 accounts each as in redeem
+}
+
+/// Handler for continue_redemption instruction
+///
+/// Fills the aUSD escrowed on the redeemer's ticket against the next troves, with the same
+/// ordering and trove checks as redeem, and burns the filled part from the escrow. The redeemer
+/// pays the redemption fee on the filled share here, at the current rate; the redemption throttle
+/// was settled when the ticket was opened. The ticket is closed to the redeemer once empty, or on
+/// cancel with the escrow refunded.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ContinueRedemption<'info>>,
+    params: ContinueRedemptionParams,
+) -> Result<()> {
+    params.validate()?;
+
+    let escrow_seeds: &[&[u8]] = &[b"redemption_escrow_vault", &[ctx.bumps.redemption_escrow_vault]];
+    let escrow_signer: &[&[&[u8]]] = &[escrow_seeds];
+
+    if params.cancel {
+        let refund = ctx.accounts.redemption_ticket.remaining;
+        
+        // The refunded escrow was never redeemed, so its throttle volume is given back
+        ctx.accounts.state.load_mut()?.release_redemption_volume(
+            ctx.accounts.redemption_ticket.reserved_volume,
+            ctx.accounts.redemption_ticket.reserved_window_start_slot,
+        );
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.redemption_escrow_vault.to_account_info(),
+                to: ctx.accounts.user_stablecoin_account.to_account_info(),
+                authority: ctx.accounts.redemption_escrow_vault.to_account_info(),
+            },
+            escrow_signer,
+        );
+        anchor_spl::token::transfer(transfer_ctx, refund)?;
+        ctx.accounts.redemption_ticket.close(ctx.accounts.user.to_account_info())?;
+
+        msg!("Redemption ticket cancelled: {} aUSD returned to {}", refund, ctx.accounts.user.key());
+        return Ok(());
+    }
+
+    {
+        let state = ctx.accounts.state.load()?;
+        require!(
+            state.max_redemption_troves == 0
+                || ctx.remaining_accounts.len() / 4 <= state.max_redemption_troves as usize,
+            AerospacerProtocolError::TooManyRedemptionTroves
+        );
+    }
+
+    let live_price = load_redemption_price(
+        &ctx.accounts.state,
+        &ctx.accounts.oracle_program,
+        &ctx.accounts.oracle_state,
+        ctx.accounts.pyth_price_account.as_ref(),
+        ctx.accounts.clock.to_account_info(),
+        &params.collateral_denom,
+    )?;
+
+    let protocol_fee = ctx.accounts.state.load()?.protocol_fee;
+    let mut state = ctx.accounts.state.load_mut()?;
+    let escrowed = ctx.accounts.redemption_ticket.remaining;
+    
+    // PRIORITY LANE: the treasury redeems without the fee during a de-peg emergency, as in redeem
+    let priority_lane = state.emergency_redemption_mode != 0
+        && state.treasury_addr != Pubkey::default()
+        && ctx.accounts.user.key() == state.treasury_addr;
+
+    let RedemptionFill {
+        remaining_amount,
+        collateral_sent,
+        troves_redeemed,
+        troves_closed,
+        reserves_burned,
+        limit_reached,
+        troves_skipped_undercollateralized,
+    } = fill_redemption(
+        &mut state,
+        &RedemptionVaults {
+            total_collateral_amount: &ctx.accounts.total_collateral_amount,
+            protocol_collateral_vault: ctx.accounts.protocol_collateral_vault.to_account_info(),
+            protocol_collateral_vault_bump: ctx.bumps.protocol_collateral_vault,
+            redeemer_collateral_account: ctx.accounts.user_collateral_account.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+        ctx.remaining_accounts,
+        &params.collateral_denom,
+        live_price.as_ref(),
+        escrowed,
+        RedemptionLimits {
+            max_troves: params.max_troves,
+            max_icr: params.max_icr,
+        },
+    )?;
+
+    let filled_amount = escrowed.saturating_sub(remaining_amount);
+    require!(
+        filled_amount > 0,
+        AerospacerProtocolError::InsufficientCollateral
+    );
+    
+    // FEE: charged on the fee-inclusive share of the filled part, paid from the redeemer's account
+    let filled_gross = ctx.accounts.redemption_ticket.fill(filled_amount)?;
+    let fee_amount = if priority_lane {
+        0
+    } else {
+        let sp_balance_before = stability_pool_vault_balance(&ctx.accounts.stability_pool_token_account)?;
+        let net_amount = process_protocol_fee(
+            filled_gross,
+            protocol_fee,
+            FeeSource::Redemption,
+            ProtocolFeeAccounts {
+                fees_program: ctx.accounts.fees_program.to_account_info(),
+                payer: ctx.accounts.user.to_account_info(),
+                fees_state: ctx.accounts.fees_state.to_account_info(),
+                fee_caller: ctx.accounts.fee_caller.to_account_info(),
+                fee_caller_bump: ctx.bumps.fee_caller,
+                payer_token_account: ctx.accounts.user_stablecoin_account.to_account_info(),
+                stability_pool_token_account: ctx.accounts.stability_pool_token_account.to_account_info(),
+                fee_vault: ctx.accounts.fee_vault.to_account_info(),
+                fee_epoch_stats: ctx.accounts.fee_epoch_stats.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            FeeAccumulatorAccounts::new(
+                ctx.accounts.fee_accumulator.as_deref_mut().map(|fee_accumulator| &mut **fee_accumulator),
+                ctx.accounts.fee_accumulator_vault.as_deref(),
+            )?,
+        )?;
+        accrue_routed_fee_yield(
+            &mut state,
+            &ctx.accounts.stability_pool_token_account,
+            sp_balance_before,
+        )?;
+        filled_gross.saturating_sub(net_amount)
+    };
+
+    // Burn the filled part straight from the escrow
+    let burn_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.stable_coin_mint.to_account_info(),
+            from: ctx.accounts.redemption_escrow_vault.to_account_info(),
+            authority: ctx.accounts.redemption_escrow_vault.to_account_info(),
+        },
+        escrow_signer,
+    );
+    anchor_spl::token::burn(burn_ctx, filled_amount)?;
+
+    burn_liquidation_reserve(
+        &ctx.accounts.liquidation_reserve_vault.to_account_info(),
+        ctx.bumps.liquidation_reserve_vault,
+        &ctx.accounts.stable_coin_mint.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        reserves_burned,
+    )?;
+
+    state.total_debt_amount = state.total_debt_amount
+        .checked_sub(filled_amount)
+        .and_then(|debt| debt.checked_sub(reserves_burned))
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    drop(state);
+
+    msg!("Redemption continued");
+    msg!("User: {}", ctx.accounts.user.key());
+    msg!("Fee: {} aUSD ({}%)", fee_amount, protocol_fee);
+    msg!("Net redemption: {} aUSD", filled_amount);
+    msg!("Collateral sent: {} {}", collateral_sent, params.collateral_denom);
+    msg!("Troves redeemed: {}", troves_redeemed);
+    msg!("Undercollateralized troves skipped: {}", troves_skipped_undercollateralized);
+    msg!("Still escrowed: {} aUSD", remaining_amount);
+
+    if remaining_amount == 0 {
+        ctx.accounts.redemption_ticket.close(ctx.accounts.user.to_account_info())?;
+        msg!("Redemption ticket filled and closed");
+    }
+
+    // Return data via Anchor's set_return_data, as in redeem
+    let result = RedemptionResult {
+        collateral_denom: params.collateral_denom,
+        gross_amount: filled_gross,
+        fee_amount,
+        redeemed_amount: filled_amount,
+        unfilled_amount: remaining_amount,
+        collateral_received: collateral_sent,
+        troves_redeemed,
+        troves_closed,
+        escrowed_amount: remaining_amount,
+        limit_reached,
+        troves_skipped_undercollateralized,
+    };
+    anchor_lang::solana_program::program::set_return_data(&result.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::events::CollateralDeactivationUpdated;
+use crate::validation::{ValidateParams, validate_denom};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DeactivateCollateralParams {
+    pub denom: String,
+    pub deactivated: bool, // false reactivates the denom
+}
+
+impl ValidateParams for DeactivateCollateralParams {
+    fn validate(&self) -> Result<()> {
+        validate_denom(&self.denom)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: DeactivateCollateralParams)]
+pub struct DeactivateCollateral<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"total_collateral_amount", params.denom.as_bytes()],
+        bump
+    )]
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+}
+
+/// Handler for deactivate_collateral instruction
+///
+/// Sunsets a collateral denom: open_trove and borrow_loan reject it, while repayments, collateral
+/// top-ups and withdrawals, redemptions and liquidations keep working so existing troves can wind
+/// down. Setting `deactivated` back to false lifts the restriction.
+pub fn handler(ctx: Context<DeactivateCollateral>, params: DeactivateCollateralParams) -> Result<()> {
+    params.validate()?;
+
+    let mut total_collateral = ctx.accounts.total_collateral_amount.load_mut()?;
+    total_collateral.deactivated = params.deactivated as u8;
+
+    msg!("Collateral {} {}", params.denom, if params.deactivated { "deactivated" } else { "reactivated" });
+    msg!("Outstanding collateral: {}", total_collateral.amount);
+
+    emit!(CollateralDeactivationUpdated {
+        denom: params.denom,
+        deactivated: params.deactivated,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,384 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint, Burn};
+use crate::state::*;
+use crate::error::*;
+use crate::trove_management::*;
+use crate::account_management::*;
+use crate::oracle::*;
+use crate::events::{TroveOperation, TroveUpdated};
+use crate::liquidation_reserve::check_net_debt;
+use crate::validation::{ValidateParams, validate_amount, validate_denom, validate_optional_pubkey};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DeleverageParams {
+    pub repay_amount: u64, // aUSD to repay; the same share of the denom's collateral is withdrawn
+    pub collateral_denom: String,
+    pub trove_id: u16, // Which of the owner's troves (0 = the wallet's first trove)
+    pub prev_node_id: Option<Pubkey>,
+    pub next_node_id: Option<Pubkey>,
+    pub memo: Option<[u8; 32]>, // Optional integrator memo, echoed in the emitted event
+}
+
+impl ValidateParams for DeleverageParams {
+    fn validate(&self) -> Result<()> {
+        validate_amount(self.repay_amount)?;
+        validate_denom(&self.collateral_denom)?;
+        validate_optional_pubkey(&self.prev_node_id)?;
+        validate_optional_pubkey(&self.next_node_id)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: DeleverageParams)]
+pub struct Deleverage<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: Trove owner - the signer itself, or the owner who approved the signer via trove_delegate
+    pub trove_owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user_debt_amount", trove_owner.key().as_ref(), trove_id_seed(params.trove_id).as_slice()],
+        bump,
+        constraint = user_debt_amount.owner == trove_owner.key() @ AerospacerProtocolError::Unauthorized,
+        constraint = user_debt_amount.is_active() @ AerospacerProtocolError::TroveDoesNotExist
+    )]
+    pub user_debt_amount: Account<'info, UserDebtAmount>,
+
+    #[account(
+        mut,
+        seeds = [b"user_collateral_amount", trove_owner.key().as_ref(), trove_id_seed(params.trove_id).as_slice(), params.collateral_denom.as_bytes()],
+        bump,
+        constraint = user_collateral_amount.owner == trove_owner.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_collateral_amount: Account<'info, UserCollateralAmount>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_threshold", trove_owner.key().as_ref(), trove_id_seed(params.trove_id).as_slice()],
+        bump,
+        constraint = liquidity_threshold.owner == trove_owner.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub liquidity_threshold: Account<'info, LiquidityThreshold>,
+
+    #[account(mut)]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(mut)]
+    pub user_stablecoin_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_collateral_account.mint == collateral_mint.key() @ AerospacerProtocolError::InvalidMint,
+        // The withdrawn collateral always goes to the owner, also on the delegate path
+        constraint = user_collateral_account.owner == trove_owner.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_collateral_account: Box<Account<'info, TokenAccount>>,
+
+    pub collateral_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_collateral_vault", params.collateral_denom.as_bytes()],
+        bump,
+        constraint = protocol_collateral_account.mint == collateral_mint.key() @ AerospacerProtocolError::InvalidMint
+    )]
+    pub protocol_collateral_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Stable coin mint - used for burn (supply change) - validated against state
+    #[account(
+        mut,
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub stable_coin_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Per-denom collateral total PDA
+    #[account(
+        mut,
+        seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+
+    #[account(
+        seeds = [b"collateral_config", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
+
+    // Oracle context - UncheckedAccount to reduce stack usage
+    /// CHECK: Our oracle program - validated against state in handler
+    pub oracle_program: UncheckedAccount<'info>,
+
+    /// CHECK: Oracle state account - validated against state in handler
+    #[account(mut)]
+    pub oracle_state: UncheckedAccount<'info>,
+
+    /// CHECK: Pyth price account for collateral price feed
+    pub pyth_price_account: UncheckedAccount<'info>,
+
+    /// CHECK: Clock sysvar - validated in handler if needed
+    pub clock: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    // Keeper-refreshed price (see refresh_price) - read instead of the oracle while fresh, and the
+    // last-good price used if the oracle feed is stale (see StateAccount.oracle_fallback_enabled)
+    #[account(
+        seeds = [b"price_cache", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub price_cache: Option<Box<Account<'info, PriceCache>>>,
+
+    // Delegate-signer path: required when user is not the trove owner
+    #[account(
+        seeds = [b"trove_delegate", trove_owner.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub trove_delegate: Option<Account<'info, TroveDelegate>>,
+
+    /// Optional operation history, appended to when passed
+    #[account(
+        mut,
+        seeds = [b"trove_history", trove_owner.key().as_ref()],
+        bump
+    )]
+    pub trove_history: Option<Box<Account<'info, TroveHistory>>>,
+
+    // remaining_accounts: optional neighbor hints [prev LiquidityThreshold, next LiquidityThreshold],
+    // then for every other collateral denom the trove holds:
+    // [UserCollateralAmount (writable), TotalCollateralAmount, pyth_price_account for that denom]
+}
+
+/// Repay `repay_amount` aUSD and withdraw the same share of the denom's collateral in one step.
+/// The trove's ICR is checked once, after both, against the denom's minimum collateral ratio.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, Deleverage<'info>>, params: DeleverageParams) -> Result<()> {
+    params.validate()?;
+
+    // Validate oracle accounts
+    require!(
+        ctx.accounts.oracle_program.key() == ctx.accounts.state.load()?.oracle_helper_addr,
+        AerospacerProtocolError::Unauthorized
+    );
+    require!(
+        ctx.accounts.oracle_state.key() == ctx.accounts.state.load()?.oracle_state_addr,
+        AerospacerProtocolError::Unauthorized
+    );
+
+    // Signer is the owner, or a delegate the owner approved (trove_delegate seeds bind both keys)
+    require!(
+        ctx.accounts.user.key() == ctx.accounts.trove_owner.key() || ctx.accounts.trove_delegate.is_some(),
+        AerospacerProtocolError::Unauthorized
+    );
+
+    require!(
+        ctx.accounts.user_debt_amount.amount > 0,
+        AerospacerProtocolError::TroveDoesNotExist
+    );
+
+    // Check if user has sufficient stablecoins
+    require!(
+        params.repay_amount <= ctx.accounts.user_stablecoin_account.amount,
+        AerospacerProtocolError::InsufficientCollateral
+    );
+
+    // Redistributed debt and collateral are part of what is deleveraged, so apply them first
+    apply_pending_rewards(
+        &mut ctx.accounts.user_debt_amount,
+        &mut ctx.accounts.user_collateral_amount,
+        &*ctx.accounts.total_collateral_amount.load()?,
+    )?;
+    let collateral_before = ctx.accounts.user_collateral_amount.amount;
+
+    // Lock the trove against liquidation/redemption for the rest of the slot
+    ctx.accounts.user_debt_amount.lock(Clock::get()?.slot);
+
+    // Multi-denom troves: the other collateral legs follow the neighbor hints in remaining_accounts
+    // as [UserCollateralAmount, TotalCollateralAmount, pyth_price_account] triples
+    let hint_count = params.prev_node_id.is_some() as usize + params.next_node_id.is_some() as usize;
+    require!(
+        ctx.remaining_accounts.len() >= hint_count,
+        AerospacerProtocolError::InvalidList
+    );
+    let other_collateral_value = {
+        let oracle_ctx = OracleContext {
+            oracle_program: ctx.accounts.oracle_program.to_account_info(),
+            oracle_state: ctx.accounts.oracle_state.to_account_info(),
+            pyth_price_account: ctx.accounts.pyth_price_account.to_account_info(),
+            clock: ctx.accounts.clock.to_account_info(),
+            max_price_age: PriceReadClass::Derisking.max_price_age(&*ctx.accounts.state.load()?),
+            fallback: None,
+            cached_price: None,
+        };
+        value_other_collateral_legs(
+            &ctx.remaining_accounts[hint_count..],
+            &mut ctx.accounts.user_debt_amount,
+            &params.collateral_denom,
+            &oracle_ctx,
+        )?
+    };
+
+    // Create contexts in scoped block to reduce stack usage
+    let result = {
+        let mut trove_ctx = TroveContext {
+            user: &ctx.accounts.user,
+            user_debt_amount: &mut ctx.accounts.user_debt_amount,
+            liquidity_threshold: &mut ctx.accounts.liquidity_threshold,
+            state: &ctx.accounts.state,
+        };
+
+        let mut collateral_ctx = CollateralContext {
+            user: &ctx.accounts.user,
+            user_collateral_amount: &mut ctx.accounts.user_collateral_amount,
+            user_collateral_account: &ctx.accounts.user_collateral_account,
+            protocol_collateral_account: &ctx.accounts.protocol_collateral_account,
+            total_collateral_amount: &ctx.accounts.total_collateral_amount,
+            token_program: &ctx.accounts.token_program,
+        };
+
+        let oracle_ctx = OracleContext {
+            oracle_program: ctx.accounts.oracle_program.to_account_info(),
+            oracle_state: ctx.accounts.oracle_state.to_account_info(),
+            pyth_price_account: ctx.accounts.pyth_price_account.to_account_info(),
+            clock: ctx.accounts.clock.to_account_info(),
+            max_price_age: PriceReadClass::Derisking.max_price_age(&*ctx.accounts.state.load()?),
+            fallback: PriceFallback::from_cache(
+                &*ctx.accounts.state.load()?,
+                ctx.accounts.price_cache.as_deref().map(|c| &**c),
+                &params.collateral_denom,
+            ),
+            cached_price: PriceData::from_cache(
+                &*ctx.accounts.state.load()?,
+                ctx.accounts.price_cache.as_deref().map(|c| &**c),
+                &params.collateral_denom,
+                PriceReadClass::Derisking.max_price_age(&*ctx.accounts.state.load()?),
+            )?,
+        };
+
+        // Use TroveManager for clean implementation
+        let result = TroveManager::deleverage(
+            &mut trove_ctx,
+            &mut collateral_ctx,
+            &oracle_ctx,
+            params.repay_amount,
+            ctx.bumps.protocol_collateral_account,
+            other_collateral_value,
+            ctx.accounts.collateral_config.minimum_collateral_ratio,
+        )?;
+
+
+        Ok::<_, Error>(result)
+    }?;
+
+    let withdrawn = collateral_before.saturating_sub(result.new_collateral_amount);
+
+    // A partial deleverage may not leave a dust trove behind
+    check_net_debt(
+        result.new_debt_amount.saturating_sub(ctx.accounts.user_debt_amount.liquidation_reserve),
+        ctx.accounts.state.load()?.min_net_debt,
+    )?;
+
+    // CRITICAL: Validate ICR ordering
+    // Neighbor hints come from params.prev_node_id and params.next_node_id, their accounts
+    // lead remainingAccounts
+    use crate::sorted_troves;
+
+    let prev_icr = if let Some(prev_id) = params.prev_node_id {
+        let prev_lt = &ctx.remaining_accounts[0];
+        let prev_data = prev_lt.try_borrow_data()?;
+        let prev_threshold = LiquidityThreshold::try_deserialize(&mut &prev_data[..])?;
+
+        require!(
+            prev_threshold.owner == prev_id,
+            AerospacerProtocolError::InvalidList
+        );
+
+        let prev_ratio = prev_threshold.ratio;
+        let prev_trove_id = prev_threshold.trove_id;
+        drop(prev_data);
+
+        sorted_troves::verify_liquidity_threshold_pda(prev_lt, prev_id, prev_trove_id, ctx.program_id)?;
+
+        Some(prev_ratio)
+    } else {
+        None
+    };
+
+    let next_icr = if let Some(next_id) = params.next_node_id {
+        let next_lt = &ctx.remaining_accounts[hint_count - 1];
+        let next_data = next_lt.try_borrow_data()?;
+        let next_threshold = LiquidityThreshold::try_deserialize(&mut &next_data[..])?;
+
+        require!(
+            next_threshold.owner == next_id,
+            AerospacerProtocolError::InvalidList
+        );
+
+        let next_ratio = next_threshold.ratio;
+        let next_trove_id = next_threshold.trove_id;
+        drop(next_data);
+
+        sorted_troves::verify_liquidity_threshold_pda(next_lt, next_id, next_trove_id, ctx.program_id)?;
+
+        Some(next_ratio)
+    } else {
+        None
+    };
+
+    if prev_icr.is_some() || next_icr.is_some() {
+        sorted_troves::validate_icr_ordering(result.new_icr, prev_icr, next_icr)?;
+        msg!("✓ ICR ordering validated successfully");
+    } else {
+        require!(
+            ctx.accounts.state.load()?.require_hints == 0,
+            AerospacerProtocolError::NeighborHintsRequired
+        );
+        msg!("⚠ WARNING: No neighbor hints provided - skipping ICR ordering validation");
+        msg!("⚠ Production deployments should enforce neighbor hints for sorted list integrity");
+    }
+
+    // Burn stablecoin
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.stable_coin_mint.to_account_info(),
+            from: ctx.accounts.user_stablecoin_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    anchor_spl::token::burn(burn_ctx, params.repay_amount)?;
+
+    msg!("Trove deleveraged successfully");
+    msg!("Repaid: {} aUSD", params.repay_amount);
+    msg!("Withdrawn: {} {}", withdrawn, params.collateral_denom);
+    msg!("New debt amount: {}", result.new_debt_amount);
+    msg!("New collateral amount: {}", result.new_collateral_amount);
+    msg!("New ICR: {}", result.new_icr);
+
+    emit!(TroveUpdated {
+        owner: ctx.accounts.trove_owner.key(),
+        trove_id: params.trove_id,
+        collateral_denom: params.collateral_denom.clone(),
+        operation: TroveOperation::Deleverage,
+        amount: params.repay_amount,
+        collateral_amount: result.new_collateral_amount,
+        debt_amount: result.new_debt_amount,
+        icr: result.new_icr,
+        tag: ctx.accounts.user_debt_amount.tag,
+        memo: params.memo,
+    });
+
+    if let Some(trove_history) = ctx.accounts.trove_history.as_mut() {
+        trove_history.record(TroveHistoryEntry {
+            op: TroveHistoryOp::Deleverage,
+            trove_id: params.trove_id,
+            amount: params.repay_amount,
+            icr: result.new_icr,
+            slot: Clock::get()?.slot,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct DeregisterKeeper<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = keeper,
+        seeds = [b"keeper", keeper.key().as_ref()],
+        bump
+    )]
+    pub keeper_registration: Account<'info, Keeper>,
+}
+
+/// Handler for deregister_keeper instruction
+/// Closing the registration ends bounty payouts and refunds its rent; its stats are dropped with it
+pub fn handler(ctx: Context<DeregisterKeeper>) -> Result<()> {
+    let registration = &ctx.accounts.keeper_registration;
+    
+    msg!("Keeper deregistered");
+    msg!("Keeper: {}", registration.keeper);
+    msg!("Troves liquidated: {}", registration.troves_liquidated);
+    msg!("Flat bounties earned: {} aUSD", registration.flat_bounties_earned);
+    
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::events::AccumulatedFeesDistributed;
+use crate::fees_integration::*;
+
+#[derive(Accounts)]
+pub struct DistributeAccumulated<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_accumulator"],
+        bump
+    )]
+    pub fee_accumulator: Account<'info, FeeAccumulator>,
+
+    /// CHECK: Fee accumulator vault PDA - pays the parked fees to aerospacer-fees
+    #[account(
+        mut,
+        seeds = [b"fee_accumulator_vault"],
+        bump
+    )]
+    pub fee_accumulator_vault: AccountInfo<'info>,
+
+    /// CHECK: Fees program - validated against state
+    #[account(
+        constraint = fees_program.key() == state.load()?.fee_distributor_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub fees_program: AccountInfo<'info>,
+
+    /// CHECK: Fees state account - validated against state
+    #[account(
+        mut,
+        constraint = fees_state.key() == state.load()?.fee_state_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub fees_state: AccountInfo<'info>,
+
+    /// CHECK: Stability pool token account - checked by the fees program
+    #[account(mut)]
+    pub stability_pool_token_account: AccountInfo<'info>,
+
+    /// CHECK: Protocol's fee caller PDA - signs the distribute_fee CPI, allowlisted in aerospacer-fees
+    #[account(
+        seeds = [b"fee_caller"],
+        bump
+    )]
+    pub fee_caller: UncheckedAccount<'info>,
+
+    /// CHECK: aerospacer-fees fee vault - checked by the fees program
+    #[account(mut)]
+    pub fee_vault: AccountInfo<'info>,
+
+    /// CHECK: aerospacer-fees per-epoch fee stats - checked by the fees program
+    #[account(mut)]
+    pub fee_epoch_stats: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Permissionless crank: send the fees parked in the fee accumulator through aerospacer-fees,
+/// one distribute_fee CPI per fee source so its per-source revenue counters stay exact.
+/// Fees routed to the stability pool vault are credited to depositors like any other fee.
+pub fn handler(ctx: Context<DistributeAccumulated>) -> Result<()> {
+    let vault_seeds: &[&[u8]] = &[b"fee_accumulator_vault", &[ctx.bumps.fee_accumulator_vault]];
+    let fee_caller_seeds: &[&[u8]] = &[b"fee_caller", &[ctx.bumps.fee_caller]];
+
+    let mut distributed = 0u64;
+    for source in FeeSource::ALL {
+        let amount = ctx.accounts.fee_accumulator.take(source);
+        if amount == 0 {
+            continue;
+        }
+
+        let sp_balance_before = stability_pool_vault_balance(&ctx.accounts.stability_pool_token_account)?;
+        distribute_fee_via_cpi(
+            &ctx.accounts.fees_program,
+            &ctx.accounts.fee_accumulator_vault,
+            &ctx.accounts.fees_state,
+            &ctx.accounts.fee_caller.to_account_info(),
+            &ctx.accounts.fee_accumulator_vault,
+            &ctx.accounts.stability_pool_token_account,
+            &ctx.accounts.fee_vault,
+            &ctx.accounts.fee_epoch_stats,
+            &ctx.accounts.token_program.to_account_info(),
+            amount,
+            source,
+            &[vault_seeds, fee_caller_seeds],
+        )?;
+        accrue_routed_fee_yield(
+            &mut *ctx.accounts.state.load_mut()?,
+            &ctx.accounts.stability_pool_token_account,
+            sp_balance_before,
+        )?;
+
+        msg!("Distributed parked {:?} fees: {} aUSD", source, amount);
+        distributed += amount;
+    }
+
+    require!(distributed > 0, AerospacerProtocolError::NoAccumulatedFees);
+
+    msg!("Accumulated fees distributed: {} aUSD", distributed);
+    msg!("Caller: {}", ctx.accounts.caller.key());
+
+    emit!(AccumulatedFeesDistributed {
+        amount: distributed,
+        total_distributed: ctx.accounts.fee_accumulator.total_distributed,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, Mint, SetAuthority, set_authority, spl_token::instruction::AuthorityType};
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::events::MintAuthorityMigrated;
+
+#[derive(Accounts)]
+pub struct ExecuteMintAuthorityMigration<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        mut,
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint,
+        constraint = stable_coin_mint.mint_authority == Some(mint_authority.key()).into() @ AerospacerProtocolError::MintAuthorityNotAssigned
+    )]
+    pub stable_coin_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Handler for execute_mint_authority_migration instruction
+/// Hands the aUSD mint authority to the proposed address once the timelock has passed.
+/// This program can no longer mint afterwards.
+pub fn handler(ctx: Context<ExecuteMintAuthorityMigration>) -> Result<()> {
+    let new_authority = ctx.accounts.mint_authority.take_ready_migration(Clock::get()?.unix_timestamp)?;
+
+    let seeds: &[&[u8]] = &[b"mint_authority", &[ctx.bumps.mint_authority]];
+    set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SetAuthority {
+                account_or_mint: ctx.accounts.stable_coin_mint.to_account_info(),
+                current_authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            &[seeds],
+        ),
+        AuthorityType::MintTokens,
+        Some(new_authority),
+    )?;
+
+    msg!("Stablecoin mint authority migrated to {}", new_authority);
+
+    emit!(MintAuthorityMigrated { new_authority });
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_spl::token::{Token, TokenAccount, Mint};
+use crate::state::*;
+use crate::error::*;
+use crate::instructions::repay_flash_mint::RepayFlashMintParams;
+use crate::mint_gate::{mint_stablecoin, MintPurpose};
+use crate::validation::{ValidateParams, validate_amount};
+
+// Callback instruction data rides in the same transaction, so it can never be large
+const MAX_CALLBACK_DATA_LEN: usize = 1024;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct FlashMintParams {
+    pub amount: u64,
+    pub callback_data: Vec<u8>, // Instruction data for the optional callback program
+}
+
+impl ValidateParams for FlashMintParams {
+    fn validate(&self) -> Result<()> {
+        validate_amount(self.amount)?;
+        require!(
+            self.callback_data.len() <= MAX_CALLBACK_DATA_LEN,
+            AerospacerProtocolError::InvalidList
+        );
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: FlashMintParams)]
+pub struct FlashMint<'info> {
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        mut,
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub stable_coin_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = borrower_stablecoin_account.mint == stable_coin_mint.key() @ AerospacerProtocolError::InvalidMint
+    )]
+    pub borrower_stablecoin_account: Account<'info, TokenAccount>,
+
+    // Dedicated aUSD mint authority PDA (see mint_gate)
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    /// CHECK: Instructions sysvar - used to find the matching repay_flash_mint
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// CHECK: Optional program invoked with the minted funds; receives remaining_accounts
+    #[account(executable)]
+    pub callback_program: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Handler for flash_mint instruction
+///
+/// Mints `amount` aUSD to the borrower, optionally invokes a callback program with
+/// remaining_accounts, and requires a repay_flash_mint for the same amount later in the
+/// transaction. The repayment (amount + FLASH_MINT_FEE_BPS) is enforced by that instruction;
+/// if it fails the whole transaction, including the mint, is reverted.
+///
+/// Solana forbids re-entering a program through CPI, so a callback cannot call back into this
+/// protocol. Flows that need protocol instructions (e.g. self-liquidation) place them as
+/// top-level instructions between flash_mint and repay_flash_mint instead.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, FlashMint<'info>>, params: FlashMintParams) -> Result<()> {
+    params.validate()?;
+    
+    let instructions_sysvar = ctx.accounts.instructions_sysvar.to_account_info();
+    let current_index = load_current_index_checked(&instructions_sysvar)? as usize;
+
+    // SECURITY: Must be a top-level instruction, otherwise the repay search below is meaningless
+    let current_ix = load_instruction_at_checked(current_index, &instructions_sysvar)?;
+    require!(
+        current_ix.program_id == crate::ID,
+        AerospacerProtocolError::FlashMintCpiNotAllowed
+    );
+
+    // A matching repay_flash_mint must follow in this transaction
+    let mut index = current_index + 1;
+    let mut repay_found = false;
+    while let Ok(ix) = load_instruction_at_checked(index, &instructions_sysvar) {
+        if ix.program_id == crate::ID
+            && ix.data.starts_with(crate::instruction::RepayFlashMint::DISCRIMINATOR)
+        {
+            let mut data = &ix.data[crate::instruction::RepayFlashMint::DISCRIMINATOR.len()..];
+            let repay_params = RepayFlashMintParams::deserialize(&mut data)?;
+            if repay_params.amount == params.amount {
+                repay_found = true;
+                break;
+            }
+        }
+        index += 1;
+    }
+    require!(repay_found, AerospacerProtocolError::FlashMintNotRepaid);
+
+    // One flash mint at a time, so a single repay cannot settle two mints
+    {
+        let mut state = ctx.accounts.state.load_mut()?;
+        require!(
+            state.flash_mint_outstanding == 0,
+            AerospacerProtocolError::FlashMintOutstanding
+        );
+        state.flash_mint_outstanding = params.amount;
+    }
+
+    mint_stablecoin(
+        &ctx.accounts.stable_coin_mint,
+        &ctx.accounts.mint_authority,
+        ctx.bumps.mint_authority,
+        ctx.accounts.borrower_stablecoin_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        params.amount,
+        MintPurpose::FlashMint,
+    )?;
+
+    msg!("Flash minted {} aUSD to {}", params.amount, ctx.accounts.borrower_stablecoin_account.key());
+
+    if let Some(callback_program) = ctx.accounts.callback_program.as_ref() {
+        let callback_ix = Instruction {
+            program_id: callback_program.key(),
+            accounts: ctx.remaining_accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: params.callback_data,
+        };
+        invoke(&callback_ix, ctx.remaining_accounts)?;
+
+        msg!("Flash mint callback completed: {}", callback_program.key());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::events::SeizedCollateralForwarded;
+use crate::validation::{ValidateParams, validate_amount, validate_denom};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ForwardSeizedCollateralParams {
+    pub denom: String,
+    pub amount: u64,
+}
+
+impl ValidateParams for ForwardSeizedCollateralParams {
+    fn validate(&self) -> Result<()> {
+        validate_denom(&self.denom)?;
+        validate_amount(self.amount)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: ForwardSeizedCollateralParams)]
+pub struct ForwardSeizedCollateral<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [b"rebalancer_config"],
+        bump,
+        constraint = rebalancer_config.enabled @ AerospacerProtocolError::RebalancerDisabled
+    )]
+    pub rebalancer_config: Account<'info, RebalancerConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"undistributed_seizure", params.denom.as_bytes()],
+        bump
+    )]
+    pub undistributed_seizure: Account<'info, UndistributedSeizure>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_collateral_vault", params.denom.as_bytes()],
+        bump
+    )]
+    pub protocol_collateral_vault: Account<'info, TokenAccount>,
+
+    // SECURITY: Only the registered hook's inbox for this denom can receive the collateral
+    #[account(
+        mut,
+        address = rebalancer_config.inbox(&params.denom) @ AerospacerProtocolError::Unauthorized,
+        constraint = rebalancer_inbox.mint == protocol_collateral_vault.mint @ AerospacerProtocolError::InvalidMint
+    )]
+    pub rebalancer_inbox: Account<'info, TokenAccount>,
+
+    /// CHECK: Registered hook program - invoked with remaining_accounts
+    #[account(
+        executable,
+        address = rebalancer_config.program @ AerospacerProtocolError::Unauthorized
+    )]
+    pub rebalancer_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Handler for forward_seized_collateral instruction
+///
+/// Moves collateral booked as undistributed after a liquidation into the registered rebalancer's
+/// inbox and notifies the hook through its fixed interface (see RebalancerConfig), so the strategy
+/// can e.g. sell it in the same transaction. Anyone may crank it while the admin keeps the hook
+/// enabled; the caller only chooses the remaining accounts handed to the hook.
+///
+/// The hook is invoked without any protocol signer, and the vault balance is re-checked after the
+/// call, so a hook can never move more than the forwarded amount.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ForwardSeizedCollateral<'info>>,
+    params: ForwardSeizedCollateralParams,
+) -> Result<()> {
+    params.validate()?;
+
+    require!(
+        params.amount <= ctx.accounts.undistributed_seizure.amount,
+        AerospacerProtocolError::InvalidAmount
+    );
+
+    let expected_vault_balance = ctx.accounts.protocol_collateral_vault.amount
+        .checked_sub(params.amount)
+        .ok_or(AerospacerProtocolError::InsufficientCollateral)?;
+
+    let vault_seeds: &[&[u8]] = &[
+        b"protocol_collateral_vault",
+        params.denom.as_bytes(),
+        &[ctx.bumps.protocol_collateral_vault],
+    ];
+    let signer: &[&[&[u8]]] = &[vault_seeds];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.protocol_collateral_vault.to_account_info(),
+            to: ctx.accounts.rebalancer_inbox.to_account_info(),
+            authority: ctx.accounts.protocol_collateral_vault.to_account_info(),
+        },
+        signer,
+    );
+    anchor_spl::token::transfer(transfer_ctx, params.amount)?;
+
+    let seizure = &mut ctx.accounts.undistributed_seizure;
+    seizure.amount -= params.amount;
+    seizure.total_routed = seizure.total_routed
+        .checked_add(params.amount)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+
+    // Hook interface: on_seized_collateral(denom, amount) with [inbox, vault, ..remaining_accounts]
+    let mut data = hash(REBALANCER_HOOK_IX.as_bytes()).to_bytes()[..8].to_vec();
+    (params.denom.clone(), params.amount).serialize(&mut data)?;
+
+    let mut accounts = vec![
+        AccountMeta::new(ctx.accounts.rebalancer_inbox.key(), false),
+        AccountMeta::new_readonly(ctx.accounts.protocol_collateral_vault.key(), false),
+    ];
+    accounts.extend(ctx.remaining_accounts.iter().map(|account| AccountMeta {
+        pubkey: account.key(),
+        is_signer: account.is_signer,
+        is_writable: account.is_writable,
+    }));
+
+    let mut account_infos = vec![
+        ctx.accounts.rebalancer_inbox.to_account_info(),
+        ctx.accounts.protocol_collateral_vault.to_account_info(),
+    ];
+    account_infos.extend(ctx.remaining_accounts.iter().cloned());
+
+    let hook_ix = Instruction {
+        program_id: ctx.accounts.rebalancer_program.key(),
+        accounts,
+        data,
+    };
+    invoke(&hook_ix, &account_infos)?;
+
+    // The hook must not have touched the vault
+    ctx.accounts.protocol_collateral_vault.reload()?;
+    require!(
+        ctx.accounts.protocol_collateral_vault.amount == expected_vault_balance,
+        AerospacerProtocolError::RebalancerInterfaceViolation
+    );
+
+    msg!("Seized collateral forwarded to rebalancer");
+    msg!("Denom: {}", params.denom);
+    msg!("Amount: {}", params.amount);
+    msg!("Rebalancer: {}", ctx.accounts.rebalancer_program.key());
+    msg!("Remaining: {}", ctx.accounts.undistributed_seizure.amount);
+
+    emit!(SeizedCollateralForwarded {
+        denom: params.denom,
+        amount: params.amount,
+        rebalancer: ctx.accounts.rebalancer_program.key(),
+        inbox: ctx.accounts.rebalancer_inbox.key(),
+        remaining: ctx.accounts.undistributed_seizure.amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::validation::{ValidateParams, validate_amount};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct FundBadDebtBackstopParams {
+    pub amount: u64, // aUSD added to the backstop
+}
+
+impl ValidateParams for FundBadDebtBackstopParams {
+    fn validate(&self) -> Result<()> {
+        validate_amount(self.amount)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: FundBadDebtBackstopParams)]
+pub struct FundBadDebtBackstop<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    #[account(
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = 8 + BadDebtBackstop::LEN,
+        seeds = [b"bad_debt_backstop"],
+        bump
+    )]
+    pub bad_debt_backstop: Account<'info, BadDebtBackstop>,
+    
+    #[account(
+        mut,
+        constraint = funder_stablecoin_account.owner == funder.key() @ AerospacerProtocolError::Unauthorized,
+        constraint = funder_stablecoin_account.mint == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub funder_stablecoin_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol_stablecoin_vault"],
+        bump
+    )]
+    pub protocol_stablecoin_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for fund_bad_debt_backstop instruction
+/// Anyone may top up the backstop - typically the treasury. The aUSD sits in the protocol
+/// stablecoin vault, booked on the backstop, and is only burned against underwater liquidations.
+pub fn handler(ctx: Context<FundBadDebtBackstop>, params: FundBadDebtBackstopParams) -> Result<()> {
+    params.validate()?;
+    
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.funder_stablecoin_account.to_account_info(),
+            to: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        },
+    );
+    anchor_spl::token::transfer(transfer_ctx, params.amount)?;
+    
+    let backstop = &mut ctx.accounts.bad_debt_backstop;
+    backstop.balance = backstop.balance
+        .checked_add(params.amount)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    backstop.total_funded = backstop.total_funded
+        .checked_add(params.amount)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    
+    msg!("Bad debt backstop topped up");
+    msg!("Funder: {}", ctx.accounts.funder.key());
+    msg!("Amount: {} aUSD (balance: {})", params.amount, backstop.balance);
+    
+    Ok(())
+}
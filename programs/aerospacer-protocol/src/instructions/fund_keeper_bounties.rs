@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::validation::{ValidateParams, validate_amount};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct FundKeeperBountiesParams {
+    pub amount: u64, // aUSD added to the flat bounty fund
+}
+
+impl ValidateParams for FundKeeperBountiesParams {
+    fn validate(&self) -> Result<()> {
+        validate_amount(self.amount)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: FundKeeperBountiesParams)]
+pub struct FundKeeperBounties<'info> {
+    pub funder: Signer<'info>,
+    
+    #[account(
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+    
+    // Created by configure_keeper_bounties
+    #[account(
+        mut,
+        seeds = [b"keeper_bounty_fund"],
+        bump
+    )]
+    pub keeper_bounty_fund: Account<'info, KeeperBountyFund>,
+    
+    #[account(
+        mut,
+        constraint = funder_stablecoin_account.owner == funder.key() @ AerospacerProtocolError::Unauthorized,
+        constraint = funder_stablecoin_account.mint == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub funder_stablecoin_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol_stablecoin_vault"],
+        bump
+    )]
+    pub protocol_stablecoin_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Handler for fund_keeper_bounties instruction
+/// Anyone may top up the fund - in practice the DAO, out of its fee income. The aUSD sits in the
+/// protocol stablecoin vault, booked on the fund, and only leaves it as keeper bounties.
+pub fn handler(ctx: Context<FundKeeperBounties>, params: FundKeeperBountiesParams) -> Result<()> {
+    params.validate()?;
+    
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.funder_stablecoin_account.to_account_info(),
+            to: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        },
+    );
+    anchor_spl::token::transfer(transfer_ctx, params.amount)?;
+    
+    let fund = &mut ctx.accounts.keeper_bounty_fund;
+    fund.balance = fund.balance
+        .checked_add(params.amount)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    fund.total_funded = fund.total_funded
+        .checked_add(params.amount)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    
+    msg!("Keeper bounty fund topped up");
+    msg!("Funder: {}", ctx.accounts.funder.key());
+    msg!("Amount: {} aUSD (balance: {})", params.amount, fund.balance);
+    
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::msg::PendingRewardsResponse;
+use crate::trove_management::pending_rewards;
+use crate::validation::{ValidateParams, validate_denom, validate_pubkey};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GetPendingRewardsParams {
+    pub owner: Pubkey,
+    pub collateral_denom: String,
+    pub trove_id: u16, // Which of the owner's troves (0 = the wallet's first trove)
+}
+
+impl ValidateParams for GetPendingRewardsParams {
+    fn validate(&self) -> Result<()> {
+        validate_pubkey(&self.owner)?;
+        validate_denom(&self.collateral_denom)
+    }
+}
+
+/// Query context - read-only, no mutations
+#[derive(Accounts)]
+#[instruction(params: GetPendingRewardsParams)]
+pub struct GetPendingRewards<'info> {
+    #[account(
+        seeds = [b"user_debt_amount", params.owner.as_ref(), trove_id_seed(params.trove_id).as_slice()],
+        bump,
+        constraint = user_debt_amount.owner == params.owner @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_debt_amount: Account<'info, UserDebtAmount>,
+
+    #[account(
+        seeds = [b"user_collateral_amount", params.owner.as_ref(), trove_id_seed(params.trove_id).as_slice(), params.collateral_denom.as_bytes()],
+        bump,
+        constraint = user_collateral_amount.owner == params.owner @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_collateral_amount: Account<'info, UserCollateralAmount>,
+
+    #[account(
+        seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+}
+
+/// Handler for get_pending_rewards instruction
+/// Returns a PendingRewardsResponse via Anchor return data (set_return_data)
+///
+/// The amounts are exactly what apply_pending_rewards would add to this collateral leg at the
+/// next trove operation or refresh_trove_icr.
+pub fn handler(ctx: Context<GetPendingRewards>, params: GetPendingRewardsParams) -> Result<()> {
+    params.validate()?;
+
+    let user_debt = &ctx.accounts.user_debt_amount;
+    let user_collateral = &ctx.accounts.user_collateral_amount;
+    let (pending_debt, pending_collateral) = pending_rewards(
+        user_debt,
+        user_collateral,
+        &*ctx.accounts.total_collateral_amount.load()?,
+    )?;
+
+    let response = PendingRewardsResponse {
+        pending_debt,
+        pending_collateral,
+        debt_amount: user_debt.amount.saturating_add(pending_debt),
+        collateral_amount: user_collateral.amount.saturating_add(pending_collateral),
+    };
+
+    msg!("Pending rewards for {} trove {}: debt +{}, {} +{}",
+         params.owner, params.trove_id, pending_debt, params.collateral_denom, pending_collateral);
+
+    anchor_lang::solana_program::program::set_return_data(&response.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::msg::TcrResponse;
+use crate::oracle::PriceCalculator;
+
+/// Query context - read-only, no mutations
+#[derive(Accounts)]
+pub struct GetTcr<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+}
+
+/// Handler for get_tcr instruction
+/// Returns a TcrResponse via Anchor return data (set_return_data)
+///
+/// Collateral values are as of each denom's last refresh_tcr; the debt is always current, so
+/// `tcr` is recomputed against it while `cached_tcr` is the ratio stored by the last crank.
+pub fn handler(ctx: Context<GetTcr>) -> Result<()> {
+    let state = ctx.accounts.state.load()?;
+    
+    let response = TcrResponse {
+        tcr: PriceCalculator::calculate_collateral_ratio(state.total_collateral_value, state.total_debt_amount)?,
+        cached_tcr: state.tcr,
+        total_collateral_value: state.total_collateral_value,
+        total_debt_amount: state.total_debt_amount,
+        valued_denoms: state.valued_denoms,
+        last_update_slot: state.tcr_slot,
+        active_troves: state.active_troves,
+    };
+    
+    msg!("TCR: {} (cached: {}, slot {})", response.tcr, response.cached_tcr, response.last_update_slot);
+    msg!("Total collateral value: {} micro-USD over {} denoms", response.total_collateral_value, response.valued_denoms);
+    msg!("Active troves: {}", response.active_troves);
+    msg!("Total debt: {}", response.total_debt_amount);
+    
+    anchor_lang::solana_program::program::set_return_data(&response.try_to_vec()?);
+    
+    Ok(())
+}
@@ -0,0 +1,202 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+use crate::utils::token_balance;
+
+// Accounts per collateral denom in remaining_accounts
+const ACCOUNTS_PER_DENOM: usize = 3;
+
+// Keeps the serialized report well within the 1024-byte return data limit
+const MAX_HEALTH_CHECK_DENOMS: usize = 12;
+
+/// Overall protocol status, worst of all individual checks
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum HealthStatus {
+    Ok,
+    Degraded,   // Risk-increasing operations are blocked for some denom (stale or missing price)
+    Critical,   // A vault holds less than the protocol has accounted for
+}
+
+/// Typed result of a single check
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HealthCode {
+    Ok,
+    PriceStale,     // Cached price is older than StateAccount.max_price_age
+    PriceMissing,   // No price cache has been written for the denom yet
+    VaultDeficit,   // Vault balance is below the accounted amount
+    VaultSurplus,   // Vault balance is above the accounted amount (informational)
+}
+
+impl HealthCode {
+    fn status(self) -> HealthStatus {
+        match self {
+            HealthCode::Ok | HealthCode::VaultSurplus => HealthStatus::Ok,
+            HealthCode::PriceStale | HealthCode::PriceMissing => HealthStatus::Degraded,
+            HealthCode::VaultDeficit => HealthStatus::Critical,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DenomHealth {
+    pub denom: String,
+    pub price_code: HealthCode,
+    pub price_age: i64,         // Seconds since the cached price was published (-1 if missing)
+    pub vault_code: HealthCode,
+    pub accounted_amount: u64,  // TotalCollateralAmount.amount
+    pub vault_balance: u64,     // protocol_collateral_vault token balance
+}
+
+/// Compact status returned via return data, decodable without parsing logs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    pub checked_at: i64,
+    pub max_price_age: u32,
+    pub oracle_fallback_enabled: bool,
+    pub stability_pool_code: HealthCode,
+    pub total_stake_amount: u64,
+    pub stability_pool_balance: u64,
+    pub denoms: Vec<DenomHealth>,
+}
+
+/// Query context - read-only, no mutations
+#[derive(Accounts)]
+pub struct HealthCheck<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    /// CHECK: Stability pool vault PDA - may not exist before the first stake
+    #[account(
+        seeds = [b"stability_pool_vault"],
+        bump
+    )]
+    pub stability_pool_vault: UncheckedAccount<'info>,
+
+    // remaining_accounts should contain, for every collateral denom to check:
+    // [TotalCollateralAmount, protocol_collateral_vault, PriceCache]
+    // The PriceCache may be passed even if it was never written (reported as PriceMissing).
+}
+
+/// Handler for health_check instruction
+/// Returns a HealthReport via Anchor return data (set_return_data)
+///
+/// Oracle staleness is judged from the keeper-maintained PriceCache rather than a live
+/// oracle read, so monitors see exactly what the protocol's last refresh observed.
+/// The protocol has no pause switch or queued governance changes, so none are reported.
+pub fn handler(ctx: Context<HealthCheck>) -> Result<()> {
+    require!(
+        ctx.remaining_accounts.len().is_multiple_of(ACCOUNTS_PER_DENOM)
+            && ctx.remaining_accounts.len() / ACCOUNTS_PER_DENOM <= MAX_HEALTH_CHECK_DENOMS,
+        AerospacerProtocolError::InvalidList
+    );
+
+    let state = ctx.accounts.state.load()?;
+    let now = Clock::get()?.unix_timestamp;
+
+    // Stability pool: its vault must cover every staked aUSD
+    let stability_pool_balance = token_balance(&ctx.accounts.stability_pool_vault)?;
+    let stability_pool_code = drift_code(state.total_stake_amount, stability_pool_balance);
+
+    let mut denoms: Vec<DenomHealth> = Vec::new();
+    for chunk in ctx.remaining_accounts.chunks(ACCOUNTS_PER_DENOM) {
+        let (total_collateral_info, vault_info, price_cache_info) = (&chunk[0], &chunk[1], &chunk[2]);
+
+        // SECURITY: Only program-owned totals are trusted to name the denom
+        require!(
+            total_collateral_info.owner == &crate::ID,
+            AerospacerProtocolError::InvalidList
+        );
+        let (denom, accounted_amount) = {
+            let data = total_collateral_info.try_borrow_data()?;
+            let total_collateral = TotalCollateralAmount::from_account_data(&data)?;
+            (total_collateral.denom().to_string(), total_collateral.amount)
+        };
+
+        // SECURITY: Vault and price cache must be the canonical PDAs for this denom
+        let (expected_total_pda, _bump) = Pubkey::find_program_address(
+            &TotalCollateralAmount::seeds(&denom),
+            &crate::ID,
+        );
+        let (expected_vault_pda, _bump) = Pubkey::find_program_address(
+            &[b"protocol_collateral_vault", denom.as_bytes()],
+            &crate::ID,
+        );
+        let (expected_cache_pda, _bump) = Pubkey::find_program_address(
+            &PriceCache::seeds(&denom),
+            &crate::ID,
+        );
+        require!(
+            expected_total_pda == *total_collateral_info.key
+                && expected_vault_pda == *vault_info.key
+                && expected_cache_pda == *price_cache_info.key,
+            AerospacerProtocolError::InvalidList
+        );
+
+        let (price_code, price_age) = if price_cache_info.owner == &crate::ID {
+            let data = price_cache_info.try_borrow_data()?;
+            let cache = PriceCache::try_deserialize(&mut &data[..])?;
+            let age = now.saturating_sub(cache.publish_time);
+            if state.max_price_age > 0 && age > state.max_price_age as i64 {
+                (HealthCode::PriceStale, age)
+            } else {
+                (HealthCode::Ok, age)
+            }
+        } else {
+            (HealthCode::PriceMissing, -1)
+        };
+
+        let vault_balance = token_balance(vault_info)?;
+        let vault_code = drift_code(accounted_amount, vault_balance);
+
+        msg!("{}: price {:?} ({}s), vault {:?} ({} accounted / {} held)",
+             denom, price_code, price_age, vault_code, accounted_amount, vault_balance);
+
+        denoms.push(DenomHealth {
+            denom,
+            price_code,
+            price_age,
+            vault_code,
+            accounted_amount,
+            vault_balance,
+        });
+    }
+
+    let status = denoms
+        .iter()
+        .flat_map(|d| [d.price_code.status(), d.vault_code.status()])
+        .chain(std::iter::once(stability_pool_code.status()))
+        .max()
+        .unwrap_or(HealthStatus::Ok);
+
+    let report = HealthReport {
+        status,
+        checked_at: now,
+        max_price_age: state.max_price_age,
+        oracle_fallback_enabled: state.oracle_fallback_enabled != 0,
+        stability_pool_code,
+        total_stake_amount: state.total_stake_amount,
+        stability_pool_balance,
+        denoms,
+    };
+
+    msg!("Health check: {:?} ({} denoms checked)", report.status, report.denoms.len());
+    msg!("Stability pool: {:?} ({} staked / {} held)", stability_pool_code, state.total_stake_amount, stability_pool_balance);
+
+    // Return data via Anchor's set_return_data
+    // Clients can decode this as HealthReport
+    anchor_lang::solana_program::program::set_return_data(&report.try_to_vec()?);
+
+    Ok(())
+}
+
+fn drift_code(accounted: u64, held: u64) -> HealthCode {
+    match held.cmp(&accounted) {
+        std::cmp::Ordering::Less => HealthCode::VaultDeficit,
+        std::cmp::Ordering::Greater => HealthCode::VaultSurplus,
+        std::cmp::Ordering::Equal => HealthCode::Ok,
+    }
+}
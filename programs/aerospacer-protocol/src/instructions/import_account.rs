@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use crate::state::StateAccount;
+use crate::error::AerospacerProtocolError;
+use crate::utils::create_program_pda;
+use crate::validation::{ValidateParams, validate_list_len};
+use anchor_lang::solana_program::pubkey::{MAX_SEEDS, MAX_SEED_LEN};
+
+// Fork testing only: this instruction exists in builds with the `fork-testing` feature and
+// lets examples/state-snapshot replay exported mainnet/devnet accounts into a local validator.
+// It must never be enabled for a deployed program.
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ImportAccountParams {
+    pub seeds: Vec<Vec<u8>>,   // PDA seeds of the imported account (without bump)
+    pub space: u32,            // Account size, used when the account is created
+    pub offset: u32,           // Where `data` starts within the account
+    pub data: Vec<u8>,         // Raw account bytes (discriminator included), in chunks
+}
+
+impl ValidateParams for ImportAccountParams {
+    fn validate(&self) -> Result<()> {
+        validate_list_len(self.seeds.len(), MAX_SEEDS - 1)?; // The bump takes the last seed slot
+        require!(
+            self.seeds.iter().all(|seed| seed.len() <= MAX_SEED_LEN),
+            AerospacerProtocolError::InvalidList
+        );
+        require!(!self.data.is_empty(), AerospacerProtocolError::InvalidAmount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: ImportAccountParams)]
+pub struct ImportAccount<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Protocol state PDA - may not exist yet; once it does, authority must be its admin
+    #[account(
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: UncheckedAccount<'info>,
+    
+    /// CHECK: Imported account - must be the PDA of params.seeds, created when missing
+    #[account(mut)]
+    pub target: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for import_account instruction
+pub fn handler(ctx: Context<ImportAccount>, params: ImportAccountParams) -> Result<()> {
+    params.validate()?;
+    
+    // Once a state exists only its admin may import (the exporter rewrites the admin of an
+    // imported StateAccount to the importing wallet)
+    {
+        let state_info = ctx.accounts.state.to_account_info();
+        if state_info.owner == &crate::ID && state_info.data_len() >= 8 + StateAccount::LEN {
+            let data = state_info.try_borrow_data()?;
+            let state: &StateAccount = bytemuck::from_bytes(&data[8..8 + StateAccount::LEN]);
+            require!(
+                state.admin == ctx.accounts.authority.key(),
+                AerospacerProtocolError::Unauthorized
+            );
+        }
+    }
+    
+    let seeds: Vec<&[u8]> = params.seeds.iter().map(|seed| seed.as_slice()).collect();
+    let (expected_pda, bump) = Pubkey::find_program_address(&seeds, &crate::ID);
+    require!(
+        expected_pda == ctx.accounts.target.key(),
+        AerospacerProtocolError::InvalidList
+    );
+    
+    let target = ctx.accounts.target.to_account_info();
+    if target.owner == &anchor_lang::system_program::ID {
+        let bump_seed = [bump];
+        let mut signer_seeds = seeds.clone();
+        signer_seeds.push(&bump_seed);
+        create_program_pda(
+            &ctx.accounts.authority.to_account_info(),
+            &target,
+            &ctx.accounts.system_program.to_account_info(),
+            params.space as usize,
+            &signer_seeds,
+        )?;
+        msg!("Created {} ({} bytes)", target.key(), params.space);
+    }
+    require!(
+        target.owner == &crate::ID,
+        AerospacerProtocolError::Unauthorized
+    );
+    
+    let start = params.offset as usize;
+    let end = start
+        .checked_add(params.data.len())
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    require!(
+        end <= target.data_len(),
+        AerospacerProtocolError::InvalidAmount
+    );
+    target.try_borrow_mut_data()?[start..end].copy_from_slice(&params.data);
+    
+    msg!("Imported {} bytes into {} at offset {}", params.data.len(), target.key(), start);
+    
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::{
+    create_metadata_accounts_v3, mpl_token_metadata, CreateMetadataAccountsV3, Metadata,
+    mpl_token_metadata::types::DataV2,
+};
+use anchor_spl::token::Mint;
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::validation::ValidateParams;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitStablecoinParams {
+    pub name: String,   // e.g. "Aeroscraper USD"
+    pub symbol: String, // e.g. "aUSD"
+    pub uri: String,    // Off-chain JSON with the logo and description
+}
+
+impl ValidateParams for InitStablecoinParams {
+    fn validate(&self) -> Result<()> {
+        require!(
+            !self.name.is_empty()
+                && self.name.len() <= mpl_token_metadata::MAX_NAME_LENGTH
+                && !self.symbol.is_empty()
+                && self.symbol.len() <= mpl_token_metadata::MAX_SYMBOL_LENGTH
+                && self.uri.len() <= mpl_token_metadata::MAX_URI_LENGTH,
+            AerospacerProtocolError::InvalidTokenMetadata
+        );
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitStablecoin<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint,
+        constraint = stable_coin_mint.mint_authority == Some(mint_authority.key()).into() @ AerospacerProtocolError::MintAuthorityNotAssigned
+    )]
+    pub stable_coin_mint: Account<'info, Mint>,
+
+    // Dedicated aUSD mint authority PDA - Metaplex requires the mint authority to sign
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    /// CHECK: Metadata PDA of the aUSD mint - created by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), stable_coin_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Handler for init_stablecoin instruction
+/// Creates the Metaplex metadata account of the aUSD mint. The admin stays its update authority,
+/// so name, symbol and URI can later be changed with the token metadata program directly.
+pub fn handler(ctx: Context<InitStablecoin>, params: InitStablecoinParams) -> Result<()> {
+    params.validate()?;
+
+    let seeds: &[&[u8]] = &[b"mint_authority", &[ctx.bumps.mint_authority]];
+    create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: ctx.accounts.metadata.to_account_info(),
+                mint: ctx.accounts.stable_coin_mint.to_account_info(),
+                mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                payer: ctx.accounts.admin.to_account_info(),
+                update_authority: ctx.accounts.admin.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            &[seeds],
+        ),
+        DataV2 {
+            name: params.name.clone(),
+            symbol: params.symbol.clone(),
+            uri: params.uri.clone(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        true,
+        true,
+        None,
+    )?;
+
+    msg!("aUSD metadata created: {}", ctx.accounts.metadata.key());
+    msg!("Name: {}, symbol: {}", params.name, params.symbol);
+    msg!("URI: {}", params.uri);
+
+    Ok(())
+}
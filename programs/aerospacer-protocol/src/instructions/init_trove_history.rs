@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct InitTroveHistory<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + TroveHistory::LEN,
+        seeds = [b"trove_history", user.key().as_ref()],
+        bump
+    )]
+    pub trove_history: Account<'info, TroveHistory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the user's trove history. Trove instructions record into it from then on whenever
+/// it is passed to them.
+pub fn handler(ctx: Context<InitTroveHistory>) -> Result<()> {
+    let trove_history = &mut ctx.accounts.trove_history;
+    trove_history.owner = ctx.accounts.user.key();
+
+    msg!("Trove history created for {}", trove_history.owner);
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
+use crate::error::AerospacerProtocolError;
 use anchor_spl::token::{Token, Mint, SetAuthority, set_authority, spl_token::instruction::AuthorityType};
+use crate::validation::{ValidateParams, validate_pubkey};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct InitializeParams {
@@ -9,6 +11,99 @@ pub struct InitializeParams {
     pub oracle_state_addr: Pubkey,
     pub fee_distributor_addr: Pubkey,
     pub fee_state_addr: Pubkey,
+    pub config: ProtocolConfig,
+}
+
+impl ValidateParams for InitializeParams {
+    fn validate(&self) -> Result<()> {
+        validate_pubkey(&self.oracle_helper_addr)?;
+        validate_pubkey(&self.oracle_state_addr)?;
+        validate_pubkey(&self.fee_distributor_addr)?;
+        validate_pubkey(&self.fee_state_addr)?;
+        self.config.validate()
+    }
+}
+
+/// Risk parameters a deployment starts with. Every value is range checked, so a typo cannot leave
+/// a protocol whose collateral cannot be registered or whose redemptions never open.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ProtocolConfig {
+    pub minimum_collateral_ratio: u64,  // Micro-percent, inherited by registered denoms (115_000_000 = 115%)
+    pub protocol_fee: u8,               // Opening/borrowing fee percentage, at most MAX_PROTOCOL_FEE
+    pub min_redemption_amount: u64,     // 0 leaves only MINIMUM_LOAN_AMOUNT
+    pub max_redemption_troves: u8,      // 0 = unlimited
+    pub redemption_cap_per_window: u64, // 0 disables the throttle
+    pub redemption_window_slots: u64,   // Required with a redemption cap
+    pub liquidation_reserve: u64,       // aUSD minted to the reserve vault per opened trove (0 = off)
+    pub min_net_debt: u64,              // 0 leaves only MINIMUM_LOAN_AMOUNT
+    pub bootstrap_period_secs: u64,     // Redemptions are blocked this long after launch, at most MAX_BOOTSTRAP_PERIOD_SECS
+    pub trove_debt_cap: u64,            // Total trove debt mint_gate lets troves reach (0 = uncapped)
+}
+
+impl Default for ProtocolConfig {
+    fn default() -> Self {
+        Self {
+            minimum_collateral_ratio: DEFAULT_MINIMUM_COLLATERAL_RATIO,
+            protocol_fee: DEFAULT_PROTOCOL_FEE,
+            min_redemption_amount: DEFAULT_MIN_REDEMPTION_AMOUNT,
+            max_redemption_troves: DEFAULT_MAX_REDEMPTION_TROVES,
+            redemption_cap_per_window: 0,
+            redemption_window_slots: 0,
+            liquidation_reserve: 0,
+            min_net_debt: 0,
+            bootstrap_period_secs: DEFAULT_BOOTSTRAP_PERIOD_SECS,
+            trove_debt_cap: 0,
+        }
+    }
+}
+
+impl ValidateParams for ProtocolConfig {
+    fn validate(&self) -> Result<()> {
+        ProtocolConfig::validate_minimum_collateral_ratio(self.minimum_collateral_ratio)?;
+        ProtocolConfig::validate_protocol_fee(self.protocol_fee)?;
+        // A throttle needs a window to reset over
+        require!(
+            self.redemption_cap_per_window == 0 || self.redemption_window_slots > 0,
+            AerospacerProtocolError::InvalidProtocolConfig
+        );
+        require!(
+            self.bootstrap_period_secs <= MAX_BOOTSTRAP_PERIOD_SECS,
+            AerospacerProtocolError::InvalidProtocolConfig
+        );
+        Ok(())
+    }
+}
+
+impl ProtocolConfig {
+    /// register_collateral pairs the MCR with DEFAULT_LIQUIDATION_THRESHOLD, so a lower MCR
+    /// would make every denom unregistrable
+    pub fn validate_minimum_collateral_ratio(minimum_collateral_ratio: u64) -> Result<()> {
+        require!(
+            (DEFAULT_LIQUIDATION_THRESHOLD..=MAX_MINIMUM_COLLATERAL_RATIO).contains(&minimum_collateral_ratio),
+            AerospacerProtocolError::InvalidCollateralRatio
+        );
+        Ok(())
+    }
+    
+    pub fn validate_protocol_fee(protocol_fee: u8) -> Result<()> {
+        require!(
+            protocol_fee <= MAX_PROTOCOL_FEE,
+            AerospacerProtocolError::InvalidProtocolConfig
+        );
+        Ok(())
+    }
+    
+    pub fn apply(&self, state: &mut StateAccount) {
+        state.minimum_collateral_ratio = self.minimum_collateral_ratio;
+        state.protocol_fee = self.protocol_fee;
+        state.min_redemption_amount = self.min_redemption_amount;
+        state.max_redemption_troves = self.max_redemption_troves;
+        state.redemption_cap_per_window = self.redemption_cap_per_window;
+        state.redemption_window_slots = self.redemption_window_slots;
+        state.liquidation_reserve = self.liquidation_reserve;
+        state.min_net_debt = self.min_net_debt;
+        state.bootstrap_period_secs = self.bootstrap_period_secs;
+    }
 }
 
 #[derive(Accounts)]
@@ -21,7 +116,7 @@ pub struct Initialize<'info> {
         seeds = [b"state"],
         bump
     )]
-    pub state: Account<'info, StateAccount>,
+    pub state: AccountLoader<'info, StateAccount>,
     
     #[account(mut)]
     pub admin: Signer<'info>,
@@ -29,12 +124,24 @@ pub struct Initialize<'info> {
     #[account(mut)]
     pub stable_coin_mint: Account<'info, Mint>,
     
+    // Dedicated aUSD mint authority; every mint goes through mint_gate
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MintAuthority::LEN,
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+    
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()> {
-    let state = &mut ctx.accounts.state;
+    params.validate()?;
+    
+    let mut state = ctx.accounts.state.load_init()?;
     
     // Initialize state exactly like INJECTIVE's instantiate
     state.admin = ctx.accounts.admin.key();
@@ -44,8 +151,8 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     state.oracle_state_addr = params.oracle_state_addr;
     state.fee_distributor_addr = params.fee_distributor_addr;
     state.fee_state_addr = params.fee_state_addr;
-    state.minimum_collateral_ratio = DEFAULT_MINIMUM_COLLATERAL_RATIO; // 115%
-    state.protocol_fee = DEFAULT_PROTOCOL_FEE; // 5%
+    params.config.apply(&mut state);
+    state.launch_timestamp = Clock::get()?.unix_timestamp;
     state.total_debt_amount = 0;
     state.total_stake_amount = 0;
     
@@ -53,12 +160,11 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     state.p_factor = StateAccount::SCALE_FACTOR; // 10^18
     state.epoch = 0;
     
-    // Move mint authority for the stable coin mint to the protocol PDA (protocol_stablecoin_vault)
+    ctx.accounts.mint_authority.trove_debt_cap = params.config.trove_debt_cap;
+    
+    // Move mint authority for the stable coin mint to the dedicated mint authority PDA.
     // This matches Injective's model where the protocol contract is the minter.
-    let (protocol_stablecoin_vault_pda, _bump) = Pubkey::find_program_address(
-        &[b"protocol_stablecoin_vault"],
-        &crate::ID,
-    );
+    let mint_authority_pda = ctx.accounts.mint_authority.key();
 
     // Current authority is the admin who created the mint on devnet
     let set_auth_ctx = CpiContext::new(
@@ -71,10 +177,10 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     set_authority(
         set_auth_ctx,
         AuthorityType::MintTokens,
-        Some(protocol_stablecoin_vault_pda),
+        Some(mint_authority_pda),
     )?;
 
-    msg!("Stablecoin mint authority set to protocol PDA: {}", protocol_stablecoin_vault_pda);
+    msg!("Stablecoin mint authority set to protocol PDA: {}", mint_authority_pda);
     
     msg!("Aerospacer Protocol initialized successfully");
     msg!("Admin: {}", state.admin);
@@ -85,6 +191,8 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     msg!("Fee State: {}", state.fee_state_addr);
     msg!("Minimum Collateral Ratio: {}%", state.minimum_collateral_ratio);
     msg!("Protocol Fee: {}%", state.protocol_fee);
+    msg!("Bootstrap period: {}s", state.bootstrap_period_secs);
+    msg!("Trove debt cap: {} aUSD", params.config.trove_debt_cap);
     msg!("P factor initialized: {}", state.p_factor);
     msg!("Epoch initialized: {}", state.epoch);
     
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::trove_archive::{init_archive_tree, ACCOUNT_COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID};
+use crate::validation::{ValidateParams};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitializeTroveArchiveParams {
+    pub max_depth: u32,        // Tree holds 2^max_depth archived troves
+    pub max_buffer_size: u32,  // Concurrent appends tolerated per slot
+}
+
+impl ValidateParams for InitializeTroveArchiveParams {
+    fn validate(&self) -> Result<()> {
+        require!(
+            self.max_depth > 0 && self.max_buffer_size > 0,
+            AerospacerProtocolError::InvalidAmount
+        );
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeTroveArchive<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + TroveArchive::LEN,
+        seeds = [b"trove_archive"],
+        bump
+    )]
+    pub trove_archive: Account<'info, TroveArchive>,
+    
+    /// CHECK: Concurrent merkle tree, allocated by the admin for (max_depth, max_buffer_size) and
+    /// owned by the account compression program, which validates its size and emptiness
+    #[account(
+        mut,
+        owner = ACCOUNT_COMPRESSION_PROGRAM_ID @ AerospacerProtocolError::InvalidAddress
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID @ AerospacerProtocolError::InvalidAddress)]
+    pub compression_program: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL noop program
+    #[account(address = NOOP_PROGRAM_ID @ AerospacerProtocolError::InvalidAddress)]
+    pub noop_program: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for initialize_trove_archive instruction
+pub fn handler(ctx: Context<InitializeTroveArchive>, params: InitializeTroveArchiveParams) -> Result<()> {
+    params.validate()?;
+    
+    init_archive_tree(
+        &ctx.accounts.trove_archive.to_account_info(),
+        &ctx.accounts.merkle_tree.to_account_info(),
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.noop_program.to_account_info(),
+        params.max_depth,
+        params.max_buffer_size,
+        ctx.bumps.trove_archive,
+    )?;
+    
+    let archive = &mut ctx.accounts.trove_archive;
+    archive.merkle_tree = ctx.accounts.merkle_tree.key();
+    archive.max_depth = params.max_depth;
+    archive.max_buffer_size = params.max_buffer_size;
+    archive.records = 0;
+    
+    msg!("Trove archive initialized");
+    msg!("Merkle tree: {}", archive.merkle_tree);
+    
+    Ok(())
+}
@@ -0,0 +1,509 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::token::{Token, TokenAccount, Mint};
+use crate::state::*;
+use crate::error::*;
+use crate::trove_management::*;
+use crate::account_management::*;
+use crate::oracle::*;
+use crate::events::{LeverageSwapped, TroveOperation, TroveUpdated};
+use crate::fees_integration::*;
+use crate::mint_gate::{mint_stablecoin, MintPurpose};
+use crate::utils::*;
+use crate::validation::{ValidateParams, validate_bps, validate_min_amount, validate_denom, validate_optional_pubkey};
+
+// Swap instruction data rides in the same transaction, so it can never be large
+const MAX_SWAP_DATA_LEN: usize = 1024;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LeverageOpenParams {
+    pub loan_amount: u64,
+    pub collateral_denom: String,
+    pub trove_id: u16, // Which of the owner's troves (0 = the wallet's first trove)
+    pub max_slippage_bps: u16, // Collateral received may be worth this much less than the aUSD spent
+    pub swap_data: Vec<u8>, // Instruction data for the swap program
+    pub prev_node_id: Option<Pubkey>,
+    pub next_node_id: Option<Pubkey>,
+    pub memo: Option<[u8; 32]>, // Optional integrator memo, echoed in the emitted event
+}
+
+impl ValidateParams for LeverageOpenParams {
+    fn validate(&self) -> Result<()> {
+        validate_min_amount(self.loan_amount, MINIMUM_LOAN_AMOUNT, AerospacerProtocolError::LoanAmountBelowMinimum)?;
+        validate_denom(&self.collateral_denom)?;
+        validate_bps(self.max_slippage_bps, MAX_LEVERAGE_SLIPPAGE_BPS, AerospacerProtocolError::InvalidAmount)?;
+        require!(
+            self.swap_data.len() <= MAX_SWAP_DATA_LEN,
+            AerospacerProtocolError::InvalidList
+        );
+        validate_optional_pubkey(&self.prev_node_id)?;
+        validate_optional_pubkey(&self.next_node_id)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: LeverageOpenParams)]
+pub struct LeverageOpen<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user_debt_amount", user.key().as_ref(), trove_id_seed(params.trove_id).as_slice()],
+        bump,
+        constraint = user_debt_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_debt_amount: Box<Account<'info, UserDebtAmount>>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_threshold", user.key().as_ref(), trove_id_seed(params.trove_id).as_slice()],
+        bump,
+        constraint = liquidity_threshold.owner == user.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub liquidity_threshold: Box<Account<'info, LiquidityThreshold>>,
+
+    #[account(mut)]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        mut,
+        constraint = user_stablecoin_account.mint == stable_coin_mint.key() @ AerospacerProtocolError::InvalidMint,
+        constraint = user_stablecoin_account.owner == user.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_stablecoin_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub stable_coin_mint: Box<Account<'info, Mint>>,
+
+    // Dedicated aUSD mint authority PDA (see mint_gate)
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: Box<Account<'info, MintAuthority>>,
+
+    // Collateral context accounts
+    #[account(
+        mut,
+        seeds = [b"user_collateral_amount", user.key().as_ref(), trove_id_seed(params.trove_id).as_slice(), params.collateral_denom.as_bytes()],
+        bump,
+        constraint = user_collateral_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_collateral_amount: Box<Account<'info, UserCollateralAmount>>,
+
+    // The swap delivers the collateral here; only the increase is deposited
+    #[account(
+        mut,
+        constraint = user_collateral_account.mint == collateral_mint.key() @ AerospacerProtocolError::InvalidMint,
+        constraint = user_collateral_account.owner == user.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_collateral_account: Box<Account<'info, TokenAccount>>,
+
+    pub collateral_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_collateral_vault", params.collateral_denom.as_bytes()],
+        bump,
+        constraint = protocol_collateral_account.mint == collateral_mint.key() @ AerospacerProtocolError::InvalidMint
+    )]
+    pub protocol_collateral_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Per-denom collateral total PDA
+    #[account(
+        mut,
+        seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+
+    #[account(
+        seeds = [b"collateral_config", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
+
+    #[account(
+        seeds = [b"swap_program_whitelist"],
+        bump
+    )]
+    pub swap_program_whitelist: Box<Account<'info, SwapProgramWhitelist>>,
+
+    /// CHECK: Swap program - must be whitelisted; invoked with the remaining_accounts after the hints
+    #[account(
+        executable,
+        constraint = swap_program_whitelist.contains(&swap_program.key()) @ AerospacerProtocolError::SwapProgramNotWhitelisted
+    )]
+    pub swap_program: UncheckedAccount<'info>,
+
+    // Oracle context - integration with our aerospacer-oracle
+    /// CHECK: Our oracle program - validated against state
+    #[account(
+        mut,
+        constraint = oracle_program.key() == state.load()?.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_program: AccountInfo<'info>,
+
+    /// CHECK: Oracle state account - validated against state
+    #[account(
+        mut,
+        constraint = oracle_state.key() == state.load()?.oracle_state_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_state: AccountInfo<'info>,
+
+    /// CHECK: Pyth price account for collateral price feed
+    pub pyth_price_account: AccountInfo<'info>,
+
+    /// Clock sysvar for timestamp validation
+    pub clock: Sysvar<'info, Clock>,
+
+    // Fee distribution accounts
+    /// CHECK: Fees program - validated against state
+    #[account(
+        constraint = fees_program.key() == state.load()?.fee_distributor_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub fees_program: AccountInfo<'info>,
+
+    /// CHECK: Fees state account - validated against state
+    #[account(
+        mut,
+        constraint = fees_state.key() == state.load()?.fee_state_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub fees_state: AccountInfo<'info>,
+
+    /// CHECK: Stability pool token account
+    #[account(mut)]
+    pub stability_pool_token_account: AccountInfo<'info>,
+
+    /// CHECK: Protocol's fee caller PDA - signs the distribute_fee CPI, allowlisted in aerospacer-fees
+    #[account(
+        seeds = [b"fee_caller"],
+        bump
+    )]
+    pub fee_caller: UncheckedAccount<'info>,
+
+    /// CHECK: aerospacer-fees fee vault - checked by the fees program
+    #[account(mut)]
+    pub fee_vault: AccountInfo<'info>,
+
+    /// CHECK: aerospacer-fees per-epoch fee stats - checked by the fees program
+    #[account(mut)]
+    pub fee_epoch_stats: AccountInfo<'info>,
+
+    /// Dust fee accumulator - optional; a fee below its threshold is parked instead of distributed
+    #[account(
+        mut,
+        seeds = [b"fee_accumulator"],
+        bump
+    )]
+    pub fee_accumulator: Option<Box<Account<'info, FeeAccumulator>>>,
+
+    /// CHECK: Fee accumulator vault PDA - required with the fee accumulator
+    #[account(
+        mut,
+        seeds = [b"fee_accumulator_vault"],
+        bump
+    )]
+    pub fee_accumulator_vault: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    // Promotional terms for the denom - borrowing fee is waived while active
+    #[account(
+        seeds = [b"denom_promotion", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub denom_promotion: Option<Box<Account<'info, DenomPromotion>>>,
+
+    // Keeper-refreshed price (see refresh_price) - read instead of the oracle while fresh
+    #[account(
+        seeds = [b"price_cache", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub price_cache: Option<Box<Account<'info, PriceCache>>>,
+
+    /// Optional operation history, appended to when passed
+    #[account(
+        mut,
+        seeds = [b"trove_history", user.key().as_ref()],
+        bump
+    )]
+    pub trove_history: Option<Box<Account<'info, TroveHistory>>>,
+
+    // remaining_accounts: optional neighbor hints [prev LiquidityThreshold, next LiquidityThreshold],
+    // then the accounts of the swap instruction, in the swap program's order
+}
+
+/// Handler for leverage_open instruction
+///
+/// Borrows `loan_amount` aUSD against an open trove, swaps it into collateral through a
+/// whitelisted swap program and deposits what the swap delivered, all in one instruction.
+/// The swap is measured by the balance changes of the user's aUSD and collateral accounts; the
+/// collateral must be worth at least the aUSD spent, less `max_slippage_bps`, at the oracle
+/// price. The trove's ICR is only checked once, after the deposit.
+///
+/// The swap program runs with the user's signature but without any protocol signer, so it can
+/// only move the user's own tokens.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, LeverageOpen<'info>>, params: LeverageOpenParams) -> Result<()> {
+    params.validate()?;
+
+    // Check if user has existing trove
+    require!(
+        ctx.accounts.user_debt_amount.is_active() && ctx.accounts.user_debt_amount.amount > 0,
+        AerospacerProtocolError::TroveDoesNotExist
+    );
+
+    // No borrowing against a deactivated denom - repaying and withdrawing stay open
+    require!(
+        ctx.accounts.total_collateral_amount.load()?.is_active(),
+        AerospacerProtocolError::CollateralDeactivated
+    );
+
+    let hint_count = params.prev_node_id.is_some() as usize + params.next_node_id.is_some() as usize;
+    require!(
+        ctx.remaining_accounts.len() >= hint_count,
+        AerospacerProtocolError::InvalidList
+    );
+
+    // Lock the trove against liquidation/redemption for the rest of the slot
+    ctx.accounts.user_debt_amount.lock(Clock::get()?.slot);
+
+    // Mint the full loan (including fee); the debt is booked once the swap has settled
+    let total_debt = ctx.accounts.state.load()?.total_debt_amount
+        .checked_add(params.loan_amount)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    mint_stablecoin(
+        &ctx.accounts.stable_coin_mint,
+        &ctx.accounts.mint_authority,
+        ctx.bumps.mint_authority,
+        ctx.accounts.user_stablecoin_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        params.loan_amount,
+        MintPurpose::TroveDebt { total_debt },
+    )?;
+
+    // Distribute fee via CPI to aerospacer-fees
+    let protocol_fee = effective_borrowing_fee(
+        ctx.accounts.collateral_config.protocol_fee,
+        ctx.accounts.denom_promotion.as_deref().map(|p| &**p),
+        Clock::get()?.slot,
+    );
+    let fee_amount = calculate_protocol_fee(params.loan_amount, protocol_fee)?;
+    if fee_amount > 0 {
+        let sp_balance_before = stability_pool_vault_balance(&ctx.accounts.stability_pool_token_account)?;
+        process_protocol_fee(
+            params.loan_amount,
+            protocol_fee,
+            FeeSource::Borrow,
+            ProtocolFeeAccounts {
+                fees_program: ctx.accounts.fees_program.to_account_info(),
+                payer: ctx.accounts.user.to_account_info(),
+                fees_state: ctx.accounts.fees_state.to_account_info(),
+                fee_caller: ctx.accounts.fee_caller.to_account_info(),
+                fee_caller_bump: ctx.bumps.fee_caller,
+                payer_token_account: ctx.accounts.user_stablecoin_account.to_account_info(),
+                stability_pool_token_account: ctx.accounts.stability_pool_token_account.to_account_info(),
+                fee_vault: ctx.accounts.fee_vault.to_account_info(),
+                fee_epoch_stats: ctx.accounts.fee_epoch_stats.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            FeeAccumulatorAccounts::new(
+                ctx.accounts.fee_accumulator.as_deref_mut().map(|fee_accumulator| &mut **fee_accumulator),
+                ctx.accounts.fee_accumulator_vault.as_deref(),
+            )?,
+        )?;
+        accrue_routed_fee_yield(
+            &mut *ctx.accounts.state.load_mut()?,
+            &ctx.accounts.stability_pool_token_account,
+            sp_balance_before,
+        )?;
+
+        msg!("Fee collected and distributed: {} aUSD", fee_amount);
+    }
+
+    // Swap aUSD into collateral
+    ctx.accounts.user_stablecoin_account.reload()?;
+    let stablecoin_before = ctx.accounts.user_stablecoin_account.amount;
+    let collateral_before = ctx.accounts.user_collateral_account.amount;
+
+    let swap_accounts = &ctx.remaining_accounts[hint_count..];
+    let swap_ix = Instruction {
+        program_id: ctx.accounts.swap_program.key(),
+        accounts: swap_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: params.swap_data.clone(),
+    };
+    let mut account_infos = swap_accounts.to_vec();
+    account_infos.push(ctx.accounts.swap_program.to_account_info());
+    invoke(&swap_ix, &account_infos)?;
+
+    ctx.accounts.user_stablecoin_account.reload()?;
+    ctx.accounts.user_collateral_account.reload()?;
+    let stablecoin_spent = stablecoin_before.saturating_sub(ctx.accounts.user_stablecoin_account.amount);
+    let collateral_received = ctx.accounts.user_collateral_account.amount.saturating_sub(collateral_before);
+    require!(
+        collateral_received > 0,
+        AerospacerProtocolError::InvalidAmount
+    );
+
+    // Deposit the swapped collateral and book the loan with a single ICR check
+    let result = {
+        let mut trove_ctx = TroveContext {
+            user: &ctx.accounts.user,
+            user_debt_amount: &mut ctx.accounts.user_debt_amount,
+            liquidity_threshold: &mut ctx.accounts.liquidity_threshold,
+            state: &ctx.accounts.state,
+        };
+
+        let mut collateral_ctx = CollateralContext {
+            user: &ctx.accounts.user,
+            user_collateral_amount: &mut ctx.accounts.user_collateral_amount,
+            user_collateral_account: &ctx.accounts.user_collateral_account,
+            protocol_collateral_account: &ctx.accounts.protocol_collateral_account,
+            total_collateral_amount: &ctx.accounts.total_collateral_amount,
+            token_program: &ctx.accounts.token_program,
+        };
+
+        let oracle_ctx = OracleContext {
+            oracle_program: ctx.accounts.oracle_program.clone(),
+            oracle_state: ctx.accounts.oracle_state.clone(),
+            pyth_price_account: ctx.accounts.pyth_price_account.clone(),
+            clock: ctx.accounts.clock.to_account_info(),
+            max_price_age: PriceReadClass::Standard.max_price_age(&*ctx.accounts.state.load()?),
+            fallback: None,
+            cached_price: PriceData::from_cache(
+                &*ctx.accounts.state.load()?,
+                ctx.accounts.price_cache.as_deref().map(|c| &**c),
+                &params.collateral_denom,
+                PriceReadClass::Standard.max_price_age(&*ctx.accounts.state.load()?),
+            )?,
+        };
+
+        collateral_ctx.transfer_to_protocol(collateral_received)?;
+
+        TroveManager::leverage(
+            &mut trove_ctx,
+            &mut collateral_ctx,
+            &oracle_ctx,
+            params.loan_amount,
+            stablecoin_spent,
+            collateral_received,
+            params.max_slippage_bps,
+            ctx.accounts.collateral_config.minimum_collateral_ratio,
+        )?
+    };
+
+    // CRITICAL: Validate ICR ordering
+    // Neighbor hints come from params.prev_node_id and params.next_node_id, their accounts
+    // lead remainingAccounts
+    use crate::sorted_troves;
+
+    let prev_icr = if let Some(prev_id) = params.prev_node_id {
+        let prev_lt = &ctx.remaining_accounts[0];
+        let prev_data = prev_lt.try_borrow_data()?;
+        let prev_threshold = LiquidityThreshold::try_deserialize(&mut &prev_data[..])?;
+
+        require!(
+            prev_threshold.owner == prev_id,
+            AerospacerProtocolError::InvalidList
+        );
+
+        let prev_ratio = prev_threshold.ratio;
+        let prev_trove_id = prev_threshold.trove_id;
+        drop(prev_data);
+
+        sorted_troves::verify_liquidity_threshold_pda(prev_lt, prev_id, prev_trove_id, ctx.program_id)?;
+
+        Some(prev_ratio)
+    } else {
+        None
+    };
+
+    let next_icr = if let Some(next_id) = params.next_node_id {
+        let next_lt = &ctx.remaining_accounts[hint_count - 1];
+        let next_data = next_lt.try_borrow_data()?;
+        let next_threshold = LiquidityThreshold::try_deserialize(&mut &next_data[..])?;
+
+        require!(
+            next_threshold.owner == next_id,
+            AerospacerProtocolError::InvalidList
+        );
+
+        let next_ratio = next_threshold.ratio;
+        let next_trove_id = next_threshold.trove_id;
+        drop(next_data);
+
+        sorted_troves::verify_liquidity_threshold_pda(next_lt, next_id, next_trove_id, ctx.program_id)?;
+
+        Some(next_ratio)
+    } else {
+        None
+    };
+
+    if prev_icr.is_some() || next_icr.is_some() {
+        sorted_troves::validate_icr_ordering(result.new_icr, prev_icr, next_icr)?;
+        msg!("✓ ICR ordering validated successfully");
+    } else {
+        require!(
+            ctx.accounts.state.load()?.require_hints == 0,
+            AerospacerProtocolError::NeighborHintsRequired
+        );
+        msg!("⚠ WARNING: No neighbor hints provided - skipping ICR ordering validation");
+        msg!("⚠ Production deployments should enforce neighbor hints for sorted list integrity");
+    }
+
+    msg!("Leverage applied successfully");
+    msg!("Gross loan amount (recorded as debt): {} aUSD", params.loan_amount);
+    msg!("Swapped: {} aUSD -> {} {}", stablecoin_spent, collateral_received, params.collateral_denom);
+    msg!("Swap program: {}", ctx.accounts.swap_program.key());
+    msg!("New total debt: {}", result.new_debt_amount);
+    msg!("New collateral amount: {}", result.new_collateral_amount);
+    msg!("New ICR: {}", result.new_icr);
+
+    emit!(LeverageSwapped {
+        owner: ctx.accounts.user.key(),
+        trove_id: params.trove_id,
+        collateral_denom: params.collateral_denom.clone(),
+        loan_amount: params.loan_amount,
+        stablecoin_spent,
+        collateral_received,
+        swap_program: ctx.accounts.swap_program.key(),
+    });
+
+    emit!(TroveUpdated {
+        owner: ctx.accounts.user.key(),
+        trove_id: params.trove_id,
+        collateral_denom: params.collateral_denom.clone(),
+        operation: TroveOperation::Leverage,
+        amount: params.loan_amount,
+        collateral_amount: result.new_collateral_amount,
+        debt_amount: result.new_debt_amount,
+        icr: result.new_icr,
+        tag: ctx.accounts.user_debt_amount.tag,
+        memo: params.memo,
+    });
+
+    if let Some(trove_history) = ctx.accounts.trove_history.as_mut() {
+        trove_history.record(TroveHistoryEntry {
+            op: TroveHistoryOp::Leverage,
+            trove_id: params.trove_id,
+            amount: params.loan_amount,
+            icr: result.new_icr,
+            slot: Clock::get()?.slot,
+        });
+    }
+
+    Ok(())
+}
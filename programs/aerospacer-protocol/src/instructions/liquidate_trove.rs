@@ -1,16 +1,39 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Token, TokenAccount, Mint, Burn};
 use crate::state::*;
 use crate::error::*;
-use crate::oracle::{OracleContext, PriceCalculator};
-use crate::trove_management::distribute_liquidation_gains_to_stakers;
+use crate::oracle::{OracleContext, PriceCalculator, PriceReadClass};
+use crate::trove_management::{
+    apply_pending_rewards, bad_debt_shortfall, distribute_liquidation_gains_to_stakers, liquidation_seizure, penalty_share,
+    redistribute_debt_and_collateral, split_pro_rata, value_collateral_legs,
+};
+use crate::instructions::liquidate_troves::load_or_create_stability_pool_snapshot;
+use crate::events::{BadDebtLiquidated, LiquidationSurplusLeft, PublicGoodsAccrued};
+use crate::keeper_bounty::{pay_keeper_bounty, KeeperBountyAccounts};
+use crate::liquidation_log::{log_liquidations, LiquidationRecord};
+use crate::liquidation_reserve::pay_liquidation_reserve;
+use crate::trove_archive::NOOP_PROGRAM_ID;
+use crate::utils::{check_stability_pool_backing, is_liquidatable_icr};
+use crate::validation::{ValidateParams, validate_denom, validate_pubkey};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct LiquidateTroveParams {
     pub target_user: Pubkey,
     pub collateral_denom: String,
+    pub trove_id: u16, // Which of the owner's troves (0 = the wallet's first trove)
 }
 
+impl ValidateParams for LiquidateTroveParams {
+    fn validate(&self) -> Result<()> {
+        validate_pubkey(&self.target_user)?;
+        validate_denom(&self.collateral_denom)
+    }
+}
+
+// Accounts per other collateral leg of a multi-denom trove, see LiquidateTrove
+pub const ACCOUNTS_PER_LIQUIDATED_LEG: usize = 4;
+
 #[derive(Accounts)]
 #[instruction(params: LiquidateTroveParams)]
 pub struct LiquidateTrove<'info> {
@@ -18,11 +41,11 @@ pub struct LiquidateTrove<'info> {
     pub liquidator: Signer<'info>,
 
     #[account(mut)]
-    pub state: Account<'info, StateAccount>,
+    pub state: AccountLoader<'info, StateAccount>,
 
     #[account(
         mut,
-        constraint = stable_coin_mint.key() == state.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
     )]
     pub stable_coin_mint: Account<'info, Mint>,
 
@@ -34,6 +57,22 @@ pub struct LiquidateTrove<'info> {
     )]
     pub protocol_stablecoin_vault: AccountInfo<'info>,
 
+    /// CHECK: Stability pool vault PDA - stakers' aUSD burned against liquidated debt
+    #[account(
+        mut,
+        seeds = [b"stability_pool_vault"],
+        bump
+    )]
+    pub stability_pool_vault: AccountInfo<'info>,
+
+    /// CHECK: Liquidation reserve vault PDA - the trove's reserve is paid from it to the liquidator
+    #[account(
+        mut,
+        seeds = [b"liquidation_reserve_vault"],
+        bump
+    )]
+    pub liquidation_reserve_vault: AccountInfo<'info>,
+
     /// CHECK: Protocol collateral vault PDA
     #[account(
         mut,
@@ -47,12 +86,18 @@ pub struct LiquidateTrove<'info> {
         seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
         bump
     )]
-    pub total_collateral_amount: Account<'info, TotalCollateralAmount>,
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+
+    #[account(
+        seeds = [b"collateral_config", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
 
     // Target trove accounts
     #[account(
         mut,
-        seeds = [b"user_debt_amount", params.target_user.as_ref()],
+        seeds = [b"user_debt_amount", params.target_user.as_ref(), trove_id_seed(params.trove_id).as_slice()],
         bump,
         constraint = user_debt_amount.owner == params.target_user @ AerospacerProtocolError::Unauthorized
     )]
@@ -60,7 +105,7 @@ pub struct LiquidateTrove<'info> {
 
     #[account(
         mut,
-        seeds = [b"user_collateral_amount", params.target_user.as_ref(), params.collateral_denom.as_bytes()],
+        seeds = [b"user_collateral_amount", params.target_user.as_ref(), trove_id_seed(params.trove_id).as_slice(), params.collateral_denom.as_bytes()],
         bump,
         constraint = user_collateral_amount.owner == params.target_user @ AerospacerProtocolError::Unauthorized
     )]
@@ -68,7 +113,7 @@ pub struct LiquidateTrove<'info> {
 
     #[account(
         mut,
-        seeds = [b"liquidity_threshold", params.target_user.as_ref()],
+        seeds = [b"liquidity_threshold", params.target_user.as_ref(), trove_id_seed(params.trove_id).as_slice()],
         bump,
         constraint = liquidity_threshold.owner == params.target_user @ AerospacerProtocolError::Unauthorized
     )]
@@ -81,14 +126,14 @@ pub struct LiquidateTrove<'info> {
     /// CHECK: Our oracle program - validated against state
     #[account(
         mut,
-        constraint = oracle_program.key() == state.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
+        constraint = oracle_program.key() == state.load()?.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
     )]
     pub oracle_program: AccountInfo<'info>,
 
     /// CHECK: Oracle state account - validated against state
     #[account(
         mut,
-        constraint = oracle_state.key() == state.oracle_state_addr @ AerospacerProtocolError::Unauthorized
+        constraint = oracle_state.key() == state.load()?.oracle_state_addr @ AerospacerProtocolError::Unauthorized
     )]
     pub oracle_state: AccountInfo<'info>,
 
@@ -106,59 +151,236 @@ pub struct LiquidateTrove<'info> {
     )]
     pub stability_pool_snapshot: Account<'info, StabilityPoolSnapshot>,
 
+    /// Public-goods share of this denom's liquidation penalties (see state.public_goods_bps)
+    #[account(
+        init_if_needed,
+        payer = liquidator,
+        space = 8 + PublicGoodsAccrual::LEN,
+        seeds = [b"public_goods_accrual", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub public_goods_accrual: Account<'info, PublicGoodsAccrual>,
+
+    /// Absorbs the shortfall of a trove liquidated below 100% ICR before it is redistributed
+    #[account(
+        init_if_needed,
+        payer = liquidator,
+        space = 8 + BadDebtBackstop::LEN,
+        seeds = [b"bad_debt_backstop"],
+        bump
+    )]
+    pub bad_debt_backstop: Box<Account<'info, BadDebtBackstop>>,
+
+    /// Reward emission schedule - optional; credits emissions accrued before the pool is depleted
+    #[account(
+        mut,
+        seeds = [b"reward_emission"],
+        bump
+    )]
+    pub reward_emission: Option<Account<'info, RewardEmission>>,
+
+    /// Optional position summary, refreshed when passed
+    #[account(
+        mut,
+        seeds = [b"user_index", params.target_user.as_ref()],
+        bump
+    )]
+    pub user_index: Option<Account<'info, UserIndex>>,
+
+    /// Liquidator whitelist - required while state.permissioned_liquidation is set
+    #[account(
+        seeds = [b"liquidator_whitelist"],
+        bump
+    )]
+    pub liquidator_whitelist: Option<Box<Account<'info, LiquidatorWhitelist>>>,
+
+    /// Liquidator's keeper registration - optional; a registered keeper is paid the keeper bounty
+    #[account(
+        mut,
+        seeds = [b"keeper", liquidator.key().as_ref()],
+        bump
+    )]
+    pub keeper: Option<Box<Account<'info, Keeper>>>,
+
+    /// Keeper's token account for the collateral bounty - required while state.keeper_bounty_bps is set
+    #[account(
+        mut,
+        constraint = keeper_collateral_account.owner == liquidator.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub keeper_collateral_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Flat aUSD bounty fund - optional; pays registered keepers while funded
+    #[account(
+        mut,
+        seeds = [b"keeper_bounty_fund"],
+        bump
+    )]
+    pub keeper_bounty_fund: Option<Box<Account<'info, KeeperBountyFund>>>,
+
+    /// Keeper's aUSD ATA for the flat bounty, created when missing - required with the bounty fund
+    #[account(
+        init_if_needed,
+        payer = liquidator,
+        associated_token::mint = stable_coin_mint,
+        associated_token::authority = liquidator
+    )]
+    pub keeper_stablecoin_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Liquidator's aUSD ATA for the trove's liquidation reserve, created when missing - required when it has one
+    #[account(
+        init_if_needed,
+        payer = liquidator,
+        associated_token::mint = stable_coin_mint,
+        associated_token::authority = liquidator
+    )]
+    pub liquidator_stablecoin_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: SPL noop program - optional; liquidated troves are logged through it when passed
+    #[account(address = NOOP_PROGRAM_ID @ AerospacerProtocolError::InvalidAddress)]
+    pub noop_program: Option<UncheckedAccount<'info>>,
+
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
+    
+    // remaining_accounts should contain, for every other collateral denom the trove holds:
+    // [UserCollateralAmount, TotalCollateralAmount, pyth_price_account, StabilityPoolSnapshot]
+    // (all but the price account writable). Each leg counts towards the ICR and is seized in the
+    // same proportion as the primary collateral; its seized collateral never leaves its vault.
 }
 
-pub fn handler(ctx: Context<LiquidateTrove>, params: LiquidateTroveParams) -> Result<()> {
-    // Basic input checks
-    require!(!params.collateral_denom.is_empty(), AerospacerProtocolError::InvalidAmount);
-
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, LiquidateTrove<'info>>, params: LiquidateTroveParams) -> Result<()> {
+    params.validate()?;
+    
+    // Permissioned mode (early mainnet): only whitelisted liquidators
+    LiquidatorWhitelist::authorize(
+        &*ctx.accounts.state.load()?,
+        ctx.accounts.liquidator_whitelist.as_deref().map(|whitelist| &**whitelist),
+        &ctx.accounts.liquidator.key(),
+    )?;
+    
     // Build oracle context
     let oracle_ctx = OracleContext {
         oracle_program: ctx.accounts.oracle_program.clone(),
         oracle_state: ctx.accounts.oracle_state.clone(),
         pyth_price_account: ctx.accounts.pyth_price_account.clone(),
         clock: ctx.accounts.clock.to_account_info(),
+        max_price_age: PriceReadClass::Critical.max_price_age(&*ctx.accounts.state.load()?),
+        fallback: None,
+        cached_price: None,
     };
 
-    // Compute ICR and ensure undercollateralized (ICR < 110)
-    let debt_amount = ctx.accounts.user_debt_amount.amount;
-    let coll_info = &ctx.accounts.user_collateral_amount;
+    // Bring the trove up to date so it is priced and seized with its redistribution share
+    apply_pending_rewards(
+        &mut ctx.accounts.user_debt_amount,
+        &mut ctx.accounts.user_collateral_amount,
+        &*ctx.accounts.total_collateral_amount.load()?,
+    )?;
 
-    // If no debt, nothing to liquidate
-    require!(debt_amount > 0, AerospacerProtocolError::TroveDoesNotExist);
+    // Only an active trove with debt can be liquidated
+    require!(
+        ctx.accounts.user_debt_amount.is_active() && ctx.accounts.user_debt_amount.amount > 0,
+        AerospacerProtocolError::TroveDoesNotExist
+    );
+    ctx.accounts.user_debt_amount.require_unlocked(Clock::get()?.slot)?;
 
     // Require denom match
-    require!(coll_info.denom == params.collateral_denom, AerospacerProtocolError::InvalidAmount);
+    require!(
+        ctx.accounts.user_collateral_amount.denom == params.collateral_denom,
+        AerospacerProtocolError::InvalidAmount
+    );
+
+    // Multi-denom troves: every other collateral leg is valued and seized with the primary one
+    let legs = value_collateral_legs(
+        ctx.remaining_accounts,
+        ACCOUNTS_PER_LIQUIDATED_LEG,
+        &mut ctx.accounts.user_debt_amount,
+        &params.collateral_denom,
+        &oracle_ctx,
+    )?;
+    let leg_accounts: Vec<&[AccountInfo<'info>]> = ctx.remaining_accounts.chunks(ACCOUNTS_PER_LIQUIDATED_LEG).collect();
+    require!(
+        leg_accounts.iter().all(|accounts| accounts[1].is_writable),
+        AerospacerProtocolError::InvalidList
+    );
+
+    // Compute ICR and ensure it is below the denom's liquidation threshold
+    let debt_amount = ctx.accounts.user_debt_amount.amount;
+    let primary_amount = ctx.accounts.user_collateral_amount.amount;
 
     // Price validation
     let price = oracle_ctx.get_price(&params.collateral_denom)?;
     oracle_ctx.validate_price(&price)?;
 
-    let collateral_value = PriceCalculator::calculate_collateral_value(
-        coll_info.amount,
+    let primary_value = PriceCalculator::calculate_collateral_value(
+        primary_amount,
         price.price as u64,
         price.decimal,
     )?;
+    // Value of every leg, primary first - the redistributed debt is split by it
+    let leg_values: Vec<u64> = std::iter::once(primary_value).chain(legs.iter().map(|leg| leg.value)).collect();
+    let collateral_value = leg_values
+        .iter()
+        .try_fold(0u64, |total, value| total.checked_add(*value))
+        .ok_or(AerospacerProtocolError::OverflowError)?;
 
     let current_icr = PriceCalculator::calculate_collateral_ratio(collateral_value, debt_amount)?;
-    // Use micro-percent threshold (110% = 110_000_000)
-    require!(current_icr < 110_000_000, AerospacerProtocolError::CollateralBelowMinimum);
+    require!(
+        is_liquidatable_icr(current_icr, ctx.accounts.collateral_config.liquidation_threshold),
+        AerospacerProtocolError::CollateralBelowMinimum
+    );
 
-    // Prepare PDA signer for potential burn operations
-    let (_pda, bump) = Pubkey::find_program_address(&[b"protocol_stablecoin_vault"], &crate::ID);
-    let vault_seeds: &[&[u8]] = &[b"protocol_stablecoin_vault", &[bump]];
+    // Prepare PDA signers for potential burn operations: the backstop's aUSD sits in the
+    // protocol stablecoin vault, the stakers' in the stability pool vault
+    let vault_seeds: &[&[u8]] = &[b"protocol_stablecoin_vault", &[ctx.bumps.protocol_stablecoin_vault]];
     let signer: &[&[&[u8]]] = &[vault_seeds];
+    let pool_vault_seeds: &[&[u8]] = &[b"stability_pool_vault", &[ctx.bumps.stability_pool_vault]];
+    let pool_signer: &[&[&[u8]]] = &[pool_vault_seeds];
+
+    // Seize the debt's worth plus the denom's liquidation penalty; the rest stays on the trove.
+    // Every leg gives up the same share of itself, so the trove is seized proportionally.
+    let liquidation_penalty_bps = ctx.accounts.collateral_config.liquidation_penalty_bps;
+    let (seized_amount, seized_icr) = liquidation_seizure(primary_amount, current_icr, liquidation_penalty_bps)?;
+    let surplus = primary_amount - seized_amount;
+    let mut leg_seizures = Vec::with_capacity(legs.len());
+    for leg in &legs {
+        let (seized, _) = liquidation_seizure(leg.amount, current_icr, liquidation_penalty_bps)?;
+        leg_seizures.push((seized, leg.amount - seized));
+    }
+    let has_surplus = surplus > 0 || leg_seizures.iter().any(|(_, leg_surplus)| *leg_surplus > 0);
+
+    // Hold back the public-goods share of the penalty; it stays in the vault, booked on the accrual
+    let public_goods_bps = ctx.accounts.state.load()?.public_goods_bps;
+    let (public_goods_amount, penalty) = penalty_share(seized_amount, seized_icr, public_goods_bps)?;
 
-    // Build collateral_amounts vector for distribution function
-    let collateral_amount = coll_info.amount;
-    let collateral_amounts = vec![(params.collateral_denom.clone(), collateral_amount)];
+    // A registered keeper's share of the penalty is held back the same way and paid out below
+    let keeper_bounty_bps = match ctx.accounts.keeper {
+        Some(_) => ctx.accounts.state.load()?.keeper_bounty_bps,
+        None => 0,
+    };
+    let (keeper_bounty, _) = penalty_share(seized_amount, seized_icr, keeper_bounty_bps)?;
+
+    // Build collateral_amounts vector for distribution function: both shares only come out of the
+    // primary leg, the other legs reach the pool whole
+    let collateral_amount = seized_amount - public_goods_amount - keeper_bounty;
+    let collateral_amounts: Vec<(String, u64)> = std::iter::once((params.collateral_denom.clone(), collateral_amount))
+        .chain(legs.iter().zip(&leg_seizures).map(|(leg, (seized, _))| (leg.denom.clone(), *seized)))
+        .collect();
+    let seized_per_leg: Vec<u64> = collateral_amounts.iter().map(|(_, amount)| *amount).collect();
     
-    // Zero user trove data (effectively liquidated)
+    // Zero user trove data (effectively liquidated); a surplus waits for close_trove
+    let liquidation_reserve = ctx.accounts.user_debt_amount.liquidation_reserve;
     ctx.accounts.user_debt_amount.amount = 0;
-    ctx.accounts.user_collateral_amount.amount = 0;
+    ctx.accounts.user_debt_amount.liquidation_reserve = 0;
+    ctx.accounts.user_collateral_amount.amount = surplus;
     ctx.accounts.liquidity_threshold.ratio = 0;
+    ctx.accounts.user_debt_amount.transition(if has_surplus {
+        TroveStatus::LiquidatedWithSurplus
+    } else {
+        TroveStatus::Liquidated
+    })?;
+    ctx.accounts.state.load_mut()?.record_trove_ended();
+    ctx.accounts.total_collateral_amount.load_mut()?.record_trove_ended();
 
     // Initialize StabilityPoolSnapshot if it's newly created
     let snapshot = &mut ctx.accounts.stability_pool_snapshot;
@@ -169,11 +391,96 @@ pub fn handler(ctx: Context<LiquidateTrove>, params: LiquidateTroveParams) -> Re
         snapshot.epoch = 0;
         msg!("Initialized new StabilityPoolSnapshot for {}", params.collateral_denom);
     }
+    let mut stability_pool_snapshots = vec![(**snapshot).clone()];
+    for (leg, accounts) in legs.iter().zip(&leg_accounts) {
+        stability_pool_snapshots.push(load_or_create_stability_pool_snapshot(
+            &accounts[3],
+            &leg.denom,
+            &ctx.accounts.liquidator.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?);
+    }
+
+    if public_goods_amount > 0 {
+        let accrual = &mut ctx.accounts.public_goods_accrual;
+        if accrual.denom.is_empty() {
+            accrual.denom = params.collateral_denom.clone();
+        }
+        accrual.book(public_goods_amount)?;
+        msg!("Public goods share booked: {} (unrouted: {})", public_goods_amount, accrual.amount);
+
+        emit!(PublicGoodsAccrued {
+            denom: params.collateral_denom.clone(),
+            amount: public_goods_amount,
+            penalty,
+            public_goods_bps,
+            pending: accrual.amount,
+        });
+    }
 
+    // Credit reward emissions at the pre-liquidation P and total stake
+    if let Some(reward_emission) = ctx.accounts.reward_emission.as_mut() {
+        use crate::trove_management::issue_reward_emissions;
+        issue_reward_emissions(
+            &mut *ctx.accounts.state.load_mut()?,
+            reward_emission,
+            Clock::get()?.unix_timestamp,
+        )?;
+    }
+    
+    // UNDERWATER PATH: below 100% ICR the collateral only backs part of the debt. The shortfall
+    // never reaches the stability pool - the backstop burns what it can cover, and only the rest is
+    // socialized by redistributing it (debt only) to active troves
+    let shortfall = bad_debt_shortfall(debt_amount, current_icr)?;
+    if shortfall > 0 {
+        let (absorbed, socialized) = ctx.accounts.bad_debt_backstop.absorb(shortfall);
+        msg!("Using bad debt liquidation path (ICR below 100%)");
+        msg!("  Shortfall: {} (backstop: {}, socialized: {})", shortfall, absorbed, socialized);
+        
+        if absorbed > 0 {
+            let burn_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.stable_coin_mint.to_account_info(),
+                    from: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+                    authority: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+                },
+                signer,
+            );
+            anchor_spl::token::burn(burn_ctx, absorbed)?;
+            
+            let mut state = ctx.accounts.state.load_mut()?;
+            state.total_debt_amount = state.total_debt_amount.saturating_sub(absorbed);
+        }
+        if socialized > 0 {
+            redistribute_over_legs(
+                &ctx.accounts.total_collateral_amount,
+                &leg_accounts,
+                &mut *ctx.accounts.state.load_mut()?,
+                socialized,
+                &leg_values,
+                &vec![0; leg_values.len()],
+            )?;
+        }
+        
+        emit!(BadDebtLiquidated {
+            owner: params.target_user,
+            collateral_denom: params.collateral_denom.clone(),
+            debt_amount,
+            icr: current_icr,
+            shortfall,
+            absorbed,
+            socialized,
+        });
+    }
+    
+    // What is left is fully backed by the seized collateral
+    let backed_debt = debt_amount - shortfall;
+    
     // HYBRID LIQUIDATION PATH: Stability pool primary, redistribution fallback
-    let total_stake = ctx.accounts.state.total_stake_amount;
+    let total_stake = ctx.accounts.state.load()?.total_stake_amount;
     
-    if total_stake >= debt_amount {
+    if total_stake >= backed_debt {
         // PATH 1: Stability pool has sufficient funds - burn entire debt
         msg!("Using stability pool liquidation path (sufficient funds)");
         
@@ -181,94 +488,237 @@ pub fn handler(ctx: Context<LiquidateTrove>, params: LiquidateTroveParams) -> Re
             ctx.accounts.token_program.to_account_info(),
             Burn {
                 mint: ctx.accounts.stable_coin_mint.to_account_info(),
-                from: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
-                authority: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+                from: ctx.accounts.stability_pool_vault.to_account_info(),
+                authority: ctx.accounts.stability_pool_vault.to_account_info(),
             },
-            signer,
+            pool_signer,
         );
-        anchor_spl::token::burn(burn_ctx, debt_amount)?;
+        anchor_spl::token::burn(burn_ctx, backed_debt)?;
         
-        ctx.accounts.state.total_debt_amount = ctx
-            .accounts
-            .state
-            .total_debt_amount
-            .saturating_sub(debt_amount);
+        let mut state = ctx.accounts.state.load_mut()?;
+        state.total_debt_amount = state.total_debt_amount.saturating_sub(backed_debt);
         
         distribute_liquidation_gains_to_stakers(
-            &mut ctx.accounts.state,
+            &mut state,
             &collateral_amounts,
-            debt_amount,
-            &mut ctx.accounts.stability_pool_snapshot,
+            backed_debt,
+            &mut stability_pool_snapshots,
         )?;
     } else if total_stake > 0 {
         // PATH 2: Partial coverage - burn only covered portion, redistribute the rest
         msg!("Using hybrid liquidation path (partial stability pool coverage)");
-        msg!("  Pool covers: {} of {} debt", total_stake, debt_amount);
+        msg!("  Pool covers: {} of {} debt", total_stake, backed_debt);
         
         let covered_debt = total_stake;
-        let uncovered_debt = debt_amount.saturating_sub(total_stake);
+        let uncovered_debt = backed_debt.saturating_sub(total_stake);
         
         let burn_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             Burn {
                 mint: ctx.accounts.stable_coin_mint.to_account_info(),
-                from: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
-                authority: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+                from: ctx.accounts.stability_pool_vault.to_account_info(),
+                authority: ctx.accounts.stability_pool_vault.to_account_info(),
             },
-            signer,
+            pool_signer,
         );
         anchor_spl::token::burn(burn_ctx, covered_debt)?;
         
-        ctx.accounts.state.total_debt_amount = ctx
-            .accounts
-            .state
-            .total_debt_amount
-            .saturating_sub(covered_debt);
-        
-        let covered_collateral = (collateral_amount as u128)
-            .checked_mul(covered_debt as u128)
-            .ok_or(AerospacerProtocolError::OverflowError)?
-            .checked_div(debt_amount as u128)
-            .ok_or(AerospacerProtocolError::DivideByZeroError)? as u64;
+        let mut state = ctx.accounts.state.load_mut()?;
+        state.total_debt_amount = state.total_debt_amount.saturating_sub(covered_debt);
         
-        let redistributed_collateral = collateral_amount.saturating_sub(covered_collateral);
+        // Every leg is covered in the same proportion as the debt
+        let mut covered_amounts = Vec::with_capacity(collateral_amounts.len());
+        let mut redistributed_collateral = Vec::with_capacity(collateral_amounts.len());
+        for (denom, amount) in &collateral_amounts {
+            let covered_collateral = (*amount as u128)
+                .checked_mul(covered_debt as u128)
+                .ok_or(AerospacerProtocolError::OverflowError)?
+                .checked_div(backed_debt as u128)
+                .ok_or(AerospacerProtocolError::DivideByZeroError)? as u64;
+            covered_amounts.push((denom.clone(), covered_collateral));
+            redistributed_collateral.push(amount.saturating_sub(covered_collateral));
+        }
         
-        let covered_amounts = vec![(params.collateral_denom.clone(), covered_collateral)];
         distribute_liquidation_gains_to_stakers(
-            &mut ctx.accounts.state,
+            &mut state,
             &covered_amounts,
             covered_debt,
-            &mut ctx.accounts.stability_pool_snapshot,
+            &mut stability_pool_snapshots,
         )?;
         
-        use crate::trove_management::redistribute_debt_and_collateral;
-        redistribute_debt_and_collateral(
-            &mut ctx.accounts.total_collateral_amount,
-            &mut ctx.accounts.state,
+        redistribute_over_legs(
+            &ctx.accounts.total_collateral_amount,
+            &leg_accounts,
+            &mut state,
             uncovered_debt,
-            redistributed_collateral,
+            &leg_values,
+            &redistributed_collateral,
         )?;
     } else {
         // PATH 3: Stability pool is empty - NO BURN, redistribute to all active troves
         msg!("Using redistribution liquidation path (stability pool empty)");
-        use crate::trove_management::redistribute_debt_and_collateral;
-        redistribute_debt_and_collateral(
-            &mut ctx.accounts.total_collateral_amount,
-            &mut ctx.accounts.state,
+        redistribute_over_legs(
+            &ctx.accounts.total_collateral_amount,
+            &leg_accounts,
+            &mut *ctx.accounts.state.load_mut()?,
+            backed_debt,
+            &leg_values,
+            &seized_per_leg,
+        )?;
+    }
+
+    check_stability_pool_backing(&ctx.accounts.stability_pool_vault, ctx.accounts.state.load()?.total_stake_amount)?;
+
+    // Write back the updated S factors
+    let mut updated_snapshots = stability_pool_snapshots.into_iter();
+    if let Some(primary) = updated_snapshots.next() {
+        ctx.accounts.stability_pool_snapshot.set_inner(primary);
+    }
+    for (snapshot, accounts) in updated_snapshots.zip(&leg_accounts) {
+        let mut data = accounts[3].try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        snapshot.try_serialize(&mut writer)?;
+    }
+
+    // After its own redistribution, so the surplus trove owes nothing from it or earlier ones
+    if surplus > 0 {
+        let total_collateral = ctx.accounts.total_collateral_amount.load()?;
+        ctx.accounts.user_collateral_amount.l_debt_snapshot = Some(total_collateral.l_debt);
+        ctx.accounts.user_collateral_amount.l_collateral_snapshot = total_collateral.l_collateral;
+        ctx.accounts.user_collateral_amount.l_scale_snapshot = total_collateral.l_scale;
+        drop(total_collateral);
+
+        msg!("Liquidation surplus left on trove: {}", surplus);
+        emit!(LiquidationSurplusLeft {
+            owner: params.target_user,
+            trove_id: params.trove_id,
+            collateral_denom: params.collateral_denom.clone(),
+            seized: seized_amount,
+            surplus,
+            liquidation_penalty_bps,
+        });
+    }
+
+    // The other legs keep only their surplus, like the primary one
+    for ((leg, (seized, leg_surplus)), accounts) in legs.iter().zip(&leg_seizures).zip(&leg_accounts) {
+        let mut collateral = {
+            let data = accounts[0].try_borrow_data()?;
+            UserCollateralAmount::try_deserialize(&mut &data[..])?
+        };
+        collateral.amount = *leg_surplus;
+        if *leg_surplus > 0 {
+            let data = accounts[1].try_borrow_data()?;
+            let total_collateral = TotalCollateralAmount::from_account_data(&data)?;
+            collateral.l_debt_snapshot = Some(total_collateral.l_debt);
+            collateral.l_collateral_snapshot = total_collateral.l_collateral;
+            collateral.l_scale_snapshot = total_collateral.l_scale;
+            
+            emit!(LiquidationSurplusLeft {
+                owner: params.target_user,
+                trove_id: params.trove_id,
+                collateral_denom: leg.denom.clone(),
+                seized: *seized,
+                surplus: *leg_surplus,
+                liquidation_penalty_bps,
+            });
+        }
+        let mut data = accounts[0].try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        collateral.try_serialize(&mut writer)?;
+        
+        msg!("Seized collateral leg: {} {} (surplus: {})", seized, leg.denom, leg_surplus);
+    }
+
+    pay_liquidation_reserve(
+        &ctx.accounts.liquidation_reserve_vault,
+        ctx.bumps.liquidation_reserve_vault,
+        ctx.accounts.liquidator_stablecoin_account.as_deref(),
+        &ctx.accounts.token_program.to_account_info(),
+        liquidation_reserve,
+    )?;
+
+    if let Some(keeper) = ctx.accounts.keeper.as_deref_mut() {
+        let token_program = ctx.accounts.token_program.to_account_info();
+        pay_keeper_bounty(
+            KeeperBountyAccounts {
+                keeper,
+                keeper_collateral_account: ctx.accounts.keeper_collateral_account.as_deref(),
+                keeper_stablecoin_account: ctx.accounts.keeper_stablecoin_account.as_deref(),
+                keeper_bounty_fund: ctx.accounts.keeper_bounty_fund.as_deref_mut(),
+                protocol_collateral_vault: &ctx.accounts.protocol_collateral_vault,
+                protocol_collateral_vault_bump: ctx.bumps.protocol_collateral_vault,
+                protocol_stablecoin_vault: &ctx.accounts.protocol_stablecoin_vault,
+                protocol_stablecoin_vault_bump: ctx.bumps.protocol_stablecoin_vault,
+                token_program: &token_program,
+            },
+            &params.collateral_denom,
+            keeper_bounty,
+            1,
             debt_amount,
-            collateral_amount,
         )?;
     }
 
+    if let Some(noop_program) = ctx.accounts.noop_program.as_ref() {
+        let slot = Clock::get()?.slot;
+        let records: Vec<LiquidationRecord> = std::iter::once((params.collateral_denom.clone(), seized_amount))
+            .chain(legs.iter().zip(&leg_seizures).map(|(leg, (seized, _))| (leg.denom.clone(), *seized)))
+            .map(|(denom, collateral_amount)| LiquidationRecord {
+                owner: params.target_user,
+                trove_id: params.trove_id,
+                denom,
+                debt_amount,
+                collateral_amount,
+                slot,
+            })
+            .collect();
+        log_liquidations(&noop_program.to_account_info(), &records)?;
+    }
+
     msg!(
-        "Single trove liquidated successfully: user={}, denom={}, debt={}, collateral={}",
+        "Single trove liquidated successfully: user={}, denom={}, debt={}, collateral={}, other legs={}",
         params.target_user,
         params.collateral_denom,
         debt_amount,
-        collateral_amount
+        seized_amount,
+        legs.len()
     );
+    
+    if let Some(user_index) = ctx.accounts.user_index.as_mut() {
+        user_index.clear_troves();
+        user_index.last_update_slot = Clock::get()?.slot;
+    }
 
     Ok(())
 }
 
-
+/// Redistribute `debt` over the denoms of a liquidated trove's legs in proportion to `leg_values`,
+/// with each leg's own `collateral` (both primary first, then the remaining_accounts legs)
+fn redistribute_over_legs<'info>(
+    total_collateral_amount: &AccountLoader<'info, TotalCollateralAmount>,
+    leg_accounts: &[&[AccountInfo<'info>]],
+    state: &mut StateAccount,
+    debt: u64,
+    leg_values: &[u64],
+    collateral: &[u64],
+) -> Result<()> {
+    let debt_shares = split_pro_rata(debt, leg_values)?;
+    redistribute_debt_and_collateral(
+        &mut *total_collateral_amount.load_mut()?,
+        state,
+        debt_shares[0],
+        collateral[0],
+    )?;
+    for ((accounts, debt_share), leg_collateral) in leg_accounts.iter().zip(&debt_shares[1..]).zip(&collateral[1..]) {
+        if *debt_share == 0 && *leg_collateral == 0 {
+            continue;
+        }
+        let mut data = accounts[1].try_borrow_mut_data()?;
+        redistribute_debt_and_collateral(
+            TotalCollateralAmount::from_account_data_mut(&mut data)?,
+            state,
+            *debt_share,
+            *leg_collateral,
+        )?;
+    }
+    Ok(())
+}
@@ -1,13 +1,23 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Token, Mint, TokenAccount};
 use crate::state::*;
 use crate::error::*;
 use crate::trove_management::*;
 use crate::account_management::*;
 use crate::oracle::*;
+use crate::utils::{check_stability_pool_backing, create_program_pda};
+use crate::events::PublicGoodsAccrued;
+use crate::keeper_bounty::{pay_keeper_bounty, KeeperBountyAccounts};
+use crate::liquidation_log::log_liquidations;
+use crate::liquidation_reserve::pay_liquidation_reserve;
+use crate::trove_archive::NOOP_PROGRAM_ID;
+use crate::validation::{ValidateParams, validate_denom, validate_list_len, validate_pubkey};
 
 // Constants
 const MAX_LIQUIDATION_BATCH_SIZE: usize = 50;
+// Accounts per seized denom other than `collateral_denom` (snapshot, total, config, price feed)
+pub const ACCOUNTS_PER_EXTRA_DENOM: usize = 4;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct TroveAmounts {
@@ -21,6 +31,16 @@ pub struct LiquidateTrovesParams {
     pub collateral_denom: String,
 }
 
+impl ValidateParams for LiquidateTrovesParams {
+    fn validate(&self) -> Result<()> {
+        validate_list_len(self.liquidation_list.len(), MAX_LIQUIDATION_BATCH_SIZE)?;
+        for user in self.liquidation_list.iter() {
+            validate_pubkey(user)?;
+        }
+        validate_denom(&self.collateral_denom)
+    }
+}
+
 #[derive(Accounts)]
 #[instruction(params: LiquidateTrovesParams)]
 pub struct LiquidateTroves<'info> {
@@ -28,11 +48,11 @@ pub struct LiquidateTroves<'info> {
     pub liquidator: Signer<'info>,
 
     #[account(mut)]
-    pub state: Account<'info, StateAccount>,
+    pub state: AccountLoader<'info, StateAccount>,
 
     #[account(
         mut,
-        constraint = stable_coin_mint.key() == state.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
     )]
     pub stable_coin_mint: Account<'info, Mint>,
 
@@ -43,6 +63,22 @@ pub struct LiquidateTroves<'info> {
         bump
     )]
     pub protocol_stablecoin_vault: AccountInfo<'info>,
+
+    /// CHECK: Stability pool vault PDA - stakers' aUSD burned against liquidated debt
+    #[account(
+        mut,
+        seeds = [b"stability_pool_vault"],
+        bump
+    )]
+    pub stability_pool_vault: AccountInfo<'info>,
+
+    /// CHECK: Liquidation reserve vault PDA - the troves' reserves are paid from it to the liquidator
+    #[account(
+        mut,
+        seeds = [b"liquidation_reserve_vault"],
+        bump
+    )]
+    pub liquidation_reserve_vault: AccountInfo<'info>,
     
     /// CHECK: Protocol collateral vault PDA
     #[account(
@@ -58,24 +94,30 @@ pub struct LiquidateTroves<'info> {
         seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
         bump
     )]
-    pub total_collateral_amount: Account<'info, TotalCollateralAmount>,
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+
+    #[account(
+        seeds = [b"collateral_config", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
 
     // Oracle context - integration with our aerospacer-oracle
     /// CHECK: Our oracle program - validated against state
     #[account(
         mut,
-        constraint = oracle_program.key() == state.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
+        constraint = oracle_program.key() == state.load()?.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
     )]
     pub oracle_program: AccountInfo<'info>,
     
     /// CHECK: Oracle state account - validated against state
     #[account(
         mut,
-        constraint = oracle_state.key() == state.oracle_state_addr @ AerospacerProtocolError::Unauthorized
+        constraint = oracle_state.key() == state.load()?.oracle_state_addr @ AerospacerProtocolError::Unauthorized
     )]
     pub oracle_state: AccountInfo<'info>,
     
-    /// CHECK: Pyth price account for collateral price feed
+    /// CHECK: Pyth price account for the `collateral_denom` price feed
     pub pyth_price_account: AccountInfo<'info>,
     
     /// Clock sysvar for timestamp validation
@@ -90,29 +132,111 @@ pub struct LiquidateTroves<'info> {
     )]
     pub stability_pool_snapshot: Account<'info, StabilityPoolSnapshot>,
 
+    /// Absorbs the shortfall of troves liquidated below 100% ICR before it is redistributed
+    #[account(
+        init_if_needed,
+        payer = liquidator,
+        space = 8 + BadDebtBackstop::LEN,
+        seeds = [b"bad_debt_backstop"],
+        bump
+    )]
+    pub bad_debt_backstop: Box<Account<'info, BadDebtBackstop>>,
+
+    /// Reward emission schedule - optional; credits emissions accrued before the pool is depleted
+    #[account(
+        mut,
+        seeds = [b"reward_emission"],
+        bump
+    )]
+    pub reward_emission: Option<Account<'info, RewardEmission>>,
+
+    /// Liquidator whitelist - required while state.permissioned_liquidation is set
+    #[account(
+        seeds = [b"liquidator_whitelist"],
+        bump
+    )]
+    pub liquidator_whitelist: Option<Box<Account<'info, LiquidatorWhitelist>>>,
+
+    /// Liquidator's keeper registration - optional; a registered keeper is paid the keeper bounty
+    #[account(
+        mut,
+        seeds = [b"keeper", liquidator.key().as_ref()],
+        bump
+    )]
+    pub keeper: Option<Box<Account<'info, Keeper>>>,
+
+    /// Keeper's token account for the collateral bounty - required while state.keeper_bounty_bps is set
+    #[account(
+        mut,
+        constraint = keeper_collateral_account.owner == liquidator.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub keeper_collateral_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Flat aUSD bounty fund - optional; pays registered keepers while funded
+    #[account(
+        mut,
+        seeds = [b"keeper_bounty_fund"],
+        bump
+    )]
+    pub keeper_bounty_fund: Option<Box<Account<'info, KeeperBountyFund>>>,
+
+    /// Keeper's aUSD ATA for the flat bounty, created when missing - required with the bounty fund
+    #[account(
+        init_if_needed,
+        payer = liquidator,
+        associated_token::mint = stable_coin_mint,
+        associated_token::authority = liquidator
+    )]
+    pub keeper_stablecoin_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Liquidator's aUSD ATA for the troves' liquidation reserves, created when missing - required when any has one
+    #[account(
+        init_if_needed,
+        payer = liquidator,
+        associated_token::mint = stable_coin_mint,
+        associated_token::authority = liquidator
+    )]
+    pub liquidator_stablecoin_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: SPL noop program - optional; liquidated troves are logged through it when passed
+    #[account(address = NOOP_PROGRAM_ID @ AerospacerProtocolError::InvalidAddress)]
+    pub noop_program: Option<UncheckedAccount<'info>>,
+
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     
     // remaining_accounts should contain:
     // - 4*N accounts: Per-trove accounts (UserDebtAmount, UserCollateralAmount, LiquidityThreshold, TokenAccount)
+    // - 4 accounts per seized denom other than `collateral_denom`, in order of first appearance
+    //   in the liquidation list: writable StabilityPoolSnapshot PDA (created if it does not exist
+    //   yet), writable TotalCollateralAmount PDA, CollateralConfig PDA and Pyth price account
+    // - Writable UndistributedSeizure PDAs for the seized denoms, in any order. Only required for
+    //   collateral seized while the stability pool is empty (created if it does not exist yet)
+    // - Writable PublicGoodsAccrual PDAs for the seized denoms, mixed in with the above. Only
+    //   required while state.public_goods_bps is set (created if it does not exist yet)
 }
 
-pub fn handler(ctx: Context<LiquidateTroves>, params: LiquidateTrovesParams) -> Result<()> {
-    // Validate input parameters
-    require!(
-        !params.liquidation_list.is_empty(),
-        AerospacerProtocolError::InvalidList
-    );
-    
-    require!(
-        params.liquidation_list.len() <= MAX_LIQUIDATION_BATCH_SIZE,
-        AerospacerProtocolError::InvalidList
-    );
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, LiquidateTroves<'info>>, params: LiquidateTrovesParams) -> Result<()> {
+    params.validate()?;
+    
+    // Permissioned mode (early mainnet): only whitelisted liquidators
+    LiquidatorWhitelist::authorize(
+        &*ctx.accounts.state.load()?,
+        ctx.accounts.liquidator_whitelist.as_deref().map(|whitelist| &**whitelist),
+        &ctx.accounts.liquidator.key(),
+    )?;
     
-    require!(
-        !params.collateral_denom.is_empty(),
-        AerospacerProtocolError::InvalidAmount
-    );
+    // Optionally cap batch sizes while the bootstrap period runs
+    {
+        let state = ctx.accounts.state.load()?;
+        require!(
+            state.bootstrap_max_liquidation_batch == 0
+                || !state.in_bootstrap_period(Clock::get()?.unix_timestamp)
+                || params.liquidation_list.len() <= state.bootstrap_max_liquidation_batch as usize,
+            AerospacerProtocolError::BootstrapLiquidationBatchTooLarge
+        );
+    }
     
     // Validate remaining accounts count
     let expected_accounts = params.liquidation_list.len() * 4; // 4 accounts per user
@@ -126,7 +250,7 @@ pub fn handler(ctx: Context<LiquidateTroves>, params: LiquidateTrovesParams) ->
     msg!("Liquidation by: {}", ctx.accounts.liquidator.key());
     
     // Validate remaining accounts for each user
-    validate_remaining_accounts(&params.liquidation_list, &ctx.remaining_accounts, &params.collateral_denom)?;
+    let extra_denoms = validate_remaining_accounts(&params.liquidation_list, ctx.remaining_accounts, &params.collateral_denom)?;
     
     // Initialize StabilityPoolSnapshot if it's newly created
     let snapshot = &mut ctx.accounts.stability_pool_snapshot;
@@ -138,12 +262,50 @@ pub fn handler(ctx: Context<LiquidateTroves>, params: LiquidateTrovesParams) ->
         msg!("Initialized new StabilityPoolSnapshot for {}", params.collateral_denom);
     }
     
+    // The accounts of every other seized denom follow the per-trove accounts,
+    // and the UndistributedSeizure accounts follow them
+    let extra_denom_accounts = extra_denoms.len() * ACCOUNTS_PER_EXTRA_DENOM;
+    require!(
+        ctx.remaining_accounts.len() >= expected_accounts + extra_denom_accounts,
+        AerospacerProtocolError::InvalidList
+    );
+    let (denom_accounts, seizure_accounts) =
+        ctx.remaining_accounts[expected_accounts..].split_at(extra_denom_accounts);
+    let mut stability_pool_snapshots = vec![(*ctx.accounts.stability_pool_snapshot).clone()];
+    let mut liquidation_denoms = vec![LiquidationDenom {
+        denom: params.collateral_denom.clone(),
+        total_collateral_amount: ctx.accounts.total_collateral_amount.to_account_info(),
+        pyth_price_account: ctx.accounts.pyth_price_account.clone(),
+        liquidation_threshold: ctx.accounts.collateral_config.liquidation_threshold,
+        liquidation_penalty_bps: ctx.accounts.collateral_config.liquidation_penalty_bps,
+    }];
+    for (denom, accounts) in extra_denoms.iter().zip(denom_accounts.chunks(ACCOUNTS_PER_EXTRA_DENOM)) {
+        stability_pool_snapshots.push(load_or_create_stability_pool_snapshot(
+            &accounts[0],
+            denom,
+            &ctx.accounts.liquidator.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?);
+        liquidation_denoms.push(load_liquidation_denom(denom, &accounts[1], &accounts[2], &accounts[3])?);
+    }
+    
+    // Credit reward emissions at the pre-liquidation P and total stake
+    if let Some(reward_emission) = ctx.accounts.reward_emission.as_mut() {
+        use crate::trove_management::issue_reward_emissions;
+        issue_reward_emissions(
+            &mut *ctx.accounts.state.load_mut()?,
+            reward_emission,
+            Clock::get()?.unix_timestamp,
+        )?;
+    }
+    
     // Create context structs for clean architecture
     let mut liquidation_ctx = LiquidationContext {
         liquidator: ctx.accounts.liquidator.clone(),
         state: ctx.accounts.state.clone(),
         stable_coin_mint: ctx.accounts.stable_coin_mint.clone(),
         protocol_stablecoin_vault: ctx.accounts.protocol_stablecoin_vault.clone(),
+        stability_pool_vault: ctx.accounts.stability_pool_vault.clone(),
         protocol_collateral_vault: ctx.accounts.protocol_collateral_vault.clone(),
         total_collateral_amount: ctx.accounts.total_collateral_amount.clone(),
         token_program: ctx.accounts.token_program.clone(),
@@ -155,21 +317,109 @@ pub fn handler(ctx: Context<LiquidateTroves>, params: LiquidateTrovesParams) ->
         oracle_state: ctx.accounts.oracle_state.clone(),
         pyth_price_account: ctx.accounts.pyth_price_account.clone(),
         clock: ctx.accounts.clock.to_account_info(),
+        max_price_age: PriceReadClass::Critical.max_price_age(&*ctx.accounts.state.load()?),
+        fallback: None,
+        cached_price: None,
+    };
+
+    // A registered keeper is paid a share of the penalty on `collateral_denom` legs
+    let keeper_bounty = match ctx.accounts.keeper {
+        Some(_) => Some((params.collateral_denom.as_str(), ctx.accounts.state.load()?.keeper_bounty_bps)),
+        None => None,
     };
 
     // Use TroveManager for clean implementation
-    let result = TroveManager::liquidate_troves(
+    let mut result = TroveManager::liquidate_troves(
         &mut liquidation_ctx,
         &oracle_ctx,
         params.liquidation_list.clone(),
-        &ctx.remaining_accounts,
-        &mut ctx.accounts.stability_pool_snapshot,
+        &ctx.remaining_accounts[..expected_accounts],
+        &mut stability_pool_snapshots,
+        keeper_bounty,
+        &mut ctx.accounts.bad_debt_backstop,
+        &liquidation_denoms,
+    )?;
+    check_stability_pool_backing(&ctx.accounts.stability_pool_vault, ctx.accounts.state.load()?.total_stake_amount)?;
+    pay_liquidation_reserve(
+        &ctx.accounts.liquidation_reserve_vault,
+        ctx.bumps.liquidation_reserve_vault,
+        ctx.accounts.liquidator_stablecoin_account.as_deref(),
+        &ctx.accounts.token_program.to_account_info(),
+        result.liquidation_reserve,
     )?;
-
-    // Update the actual accounts with the results
-    ctx.accounts.state.total_debt_amount = liquidation_ctx.state.total_debt_amount;
-    ctx.accounts.state.total_stake_amount = liquidation_ctx.state.total_stake_amount;
     
+    // Write back the updated S factors
+    let mut updated_snapshots = stability_pool_snapshots.into_iter();
+    if let Some(primary) = updated_snapshots.next() {
+        ctx.accounts.stability_pool_snapshot.set_inner(primary);
+    }
+    for (snapshot, accounts) in updated_snapshots.zip(denom_accounts.chunks(ACCOUNTS_PER_EXTRA_DENOM)) {
+        let mut data = accounts[0].try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        snapshot.try_serialize(&mut writer)?;
+    }
+
+    // Book collateral seized while the pool was empty so it is never untracked in the vault
+    for (denom, amount) in &result.undistributed_collateral {
+        let (expected_pda, bump) = Pubkey::find_program_address(
+            &UndistributedSeizure::seeds(denom),
+            &crate::ID,
+        );
+        let account_info = seizure_accounts
+            .iter()
+            .find(|account| *account.key == expected_pda)
+            .ok_or(AerospacerProtocolError::InvalidList)?;
+        credit_undistributed_seizure(
+            account_info,
+            denom,
+            *amount,
+            bump,
+            &ctx.accounts.liquidator.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+    }
+
+    // Book the public-goods share held back from the stability pool
+    let public_goods_bps = ctx.accounts.state.load()?.public_goods_bps;
+    for (denom, amount, penalty) in &result.public_goods_collateral {
+        credit_public_goods_accrual(
+            seizure_accounts,
+            denom,
+            *amount,
+            *penalty,
+            public_goods_bps,
+            &ctx.accounts.liquidator.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+    }
+
+    if let Some(keeper) = ctx.accounts.keeper.as_deref_mut() {
+        let token_program = ctx.accounts.token_program.to_account_info();
+        pay_keeper_bounty(
+            KeeperBountyAccounts {
+                keeper,
+                keeper_collateral_account: ctx.accounts.keeper_collateral_account.as_deref(),
+                keeper_stablecoin_account: ctx.accounts.keeper_stablecoin_account.as_deref(),
+                keeper_bounty_fund: ctx.accounts.keeper_bounty_fund.as_deref_mut(),
+                protocol_collateral_vault: &ctx.accounts.protocol_collateral_vault,
+                protocol_collateral_vault_bump: ctx.bumps.protocol_collateral_vault,
+                protocol_stablecoin_vault: &ctx.accounts.protocol_stablecoin_vault,
+                protocol_stablecoin_vault_bump: ctx.bumps.protocol_stablecoin_vault,
+                token_program: &token_program,
+            },
+            &params.collateral_denom,
+            result.keeper_collateral,
+            result.liquidated_count as u64,
+            result.total_debt_liquidated,
+        )?;
+    }
+
+    // The per-trove records go to the liquidation log, not the return data
+    let records = std::mem::take(&mut result.records);
+    if let Some(noop_program) = ctx.accounts.noop_program.as_ref() {
+        log_liquidations(&noop_program.to_account_info(), &records)?;
+    }
+
     // NOTE: Sorted troves management moved off-chain
     msg!("Troves liquidated successfully");
     msg!("Liquidator: {}", ctx.accounts.liquidator.key());
@@ -177,22 +427,36 @@ pub fn handler(ctx: Context<LiquidateTroves>, params: LiquidateTrovesParams) ->
     msg!("Liquidated troves: {}", result.liquidated_count);
     msg!("Total debt liquidated: {}", result.total_debt_liquidated);
     msg!("Total collateral gained: {}", result.total_collateral_gained);
+    if result.bad_debt_absorbed > 0 || result.bad_debt_socialized > 0 {
+        msg!("Bad debt absorbed by backstop: {}", result.bad_debt_absorbed);
+        msg!("Bad debt socialized: {}", result.bad_debt_socialized);
+    }
     
     // Log liquidation gains by denomination
     for (denom, amount) in &result.liquidation_gains {
         msg!("Collateral gained - {}: {}", denom, amount);
     }
 
+    // Return data via Anchor's set_return_data
+    // CPI callers can decode this as LiquidationResult (with empty records)
+    anchor_lang::solana_program::program::set_return_data(&result.try_to_vec()?);
+
     Ok(())
 }
 
 /// Validate remaining accounts for liquidation
+/// 
+/// The list must be ordered by ascending stored ICR, so a liquidator cannot skip the riskier
+/// troves of the batch in favour of more profitable ones.
+/// 
+/// Returns the seized denoms other than `collateral_denom`, in order of first appearance
 fn validate_remaining_accounts(
     liquidation_list: &[Pubkey],
     remaining_accounts: &[AccountInfo],
     collateral_denom: &str,
-) -> Result<()> {
+) -> Result<Vec<String>> {
     let expected_count = liquidation_list.len() * 4;
+    let mut extra_denoms: Vec<String> = Vec::new();
     
     require!(
         remaining_accounts.len() >= expected_count,
@@ -200,27 +464,35 @@ fn validate_remaining_accounts(
     );
     
     // Validate each user's accounts
+    let mut prev_icr: Option<u64> = None;
     for (i, user) in liquidation_list.iter().enumerate() {
         let account_start = i * 4;
         
         // Validate UserDebtAmount account
-        validate_user_debt_account(&remaining_accounts[account_start], user)?;
+        let trove_id = validate_user_debt_account(&remaining_accounts[account_start], user)?;
         
         // Validate UserCollateralAmount account
-        validate_user_collateral_account(&remaining_accounts[account_start + 1], user, collateral_denom)?;
+        let denom = validate_user_collateral_account(&remaining_accounts[account_start + 1], user, trove_id)?;
+        if denom != collateral_denom && !extra_denoms.contains(&denom) {
+            extra_denoms.push(denom);
+        }
         
-        // Validate LiquidityThreshold account
-        validate_liquidity_threshold_account(&remaining_accounts[account_start + 2], user)?;
+        // Validate LiquidityThreshold account of the same trove, then the ordering
+        let icr = validate_liquidity_threshold_account(&remaining_accounts[account_start + 2], user, trove_id)?;
+        if let Some(prev) = prev_icr {
+            require!(icr >= prev, AerospacerProtocolError::InvalidList);
+        }
+        prev_icr = Some(icr);
         
         // Validate TokenAccount
         validate_token_account(&remaining_accounts[account_start + 3], user)?;
     }
     
-    Ok(())
+    Ok(extra_denoms)
 }
 
-/// Validate UserDebtAmount account
-fn validate_user_debt_account(account_info: &AccountInfo, expected_user: &Pubkey) -> Result<()> {
+/// Validate UserDebtAmount account, returning its trove id
+fn validate_user_debt_account(account_info: &AccountInfo, expected_user: &Pubkey) -> Result<u16> {
     require!(
         account_info.owner == &crate::ID,
         AerospacerProtocolError::Unauthorized
@@ -232,18 +504,21 @@ fn validate_user_debt_account(account_info: &AccountInfo, expected_user: &Pubkey
     );
     
     let account_data = account_info.try_borrow_data()?;
-    let user_debt_amount = UserDebtAmount::try_from_slice(&account_data)?;
+    let user_debt_amount = UserDebtAmount::try_deserialize(&mut &account_data[..])?;
     
     require!(
         user_debt_amount.owner == *expected_user,
         AerospacerProtocolError::Unauthorized
     );
     
-    Ok(())
+    // Batches take one collateral leg per trove; multi-denom troves go through liquidate_trove
+    user_debt_amount.require_all_legs(1)?;
+    
+    Ok(user_debt_amount.trove_id)
 }
 
-/// Validate UserCollateralAmount account
-fn validate_user_collateral_account(account_info: &AccountInfo, expected_user: &Pubkey, expected_denom: &str) -> Result<()> {
+/// Validate UserCollateralAmount account of the trove `trove_id`, returning its denom
+fn validate_user_collateral_account(account_info: &AccountInfo, expected_user: &Pubkey, trove_id: u16) -> Result<String> {
     require!(
         account_info.owner == &crate::ID,
         AerospacerProtocolError::Unauthorized
@@ -255,23 +530,231 @@ fn validate_user_collateral_account(account_info: &AccountInfo, expected_user: &
     );
     
     let account_data = account_info.try_borrow_data()?;
-    let user_collateral_amount = UserCollateralAmount::try_from_slice(&account_data)?;
+    let user_collateral_amount = UserCollateralAmount::try_deserialize(&mut &account_data[..])?;
     
     require!(
-        user_collateral_amount.owner == *expected_user,
+        user_collateral_amount.owner == *expected_user && user_collateral_amount.trove_id == trove_id,
         AerospacerProtocolError::Unauthorized
     );
     
+    // SECURITY: Must be the canonical PDA of this trove's leg, not another of the owner's troves
+    let (expected_pda, _bump) = Pubkey::find_program_address(
+        &UserCollateralAmount::seeds(expected_user, &user_collateral_amount.denom, &trove_id_seed(trove_id)),
+        &crate::ID,
+    );
     require!(
-        user_collateral_amount.denom == expected_denom,
-        AerospacerProtocolError::InvalidAmount
+        expected_pda == *account_info.key,
+        AerospacerProtocolError::InvalidList
     );
     
+    Ok(user_collateral_amount.denom)
+}
+
+/// Load the StabilityPoolSnapshot PDA for `denom`, creating it (paid by the liquidator) if needed
+pub(crate) fn load_or_create_stability_pool_snapshot<'info>(
+    account_info: &AccountInfo<'info>,
+    denom: &str,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<StabilityPoolSnapshot> {
+    let (expected_pda, bump) = Pubkey::find_program_address(
+        &StabilityPoolSnapshot::seeds(denom),
+        &crate::ID,
+    );
+    require!(
+        expected_pda == *account_info.key,
+        AerospacerProtocolError::InvalidList
+    );
+    require!(
+        account_info.is_writable,
+        AerospacerProtocolError::Unauthorized
+    );
+    
+    if account_info.owner == &crate::ID {
+        let account_data = account_info.try_borrow_data()?;
+        let snapshot = StabilityPoolSnapshot::try_deserialize(&mut &account_data[..])?;
+        require!(
+            snapshot.denom == denom,
+            AerospacerProtocolError::InvalidAmount
+        );
+        return Ok(snapshot);
+    }
+    
+    create_program_pda(
+        payer,
+        account_info,
+        system_program,
+        8 + StabilityPoolSnapshot::LEN,
+        &[b"stability_pool_snapshot", denom.as_bytes(), &[bump]],
+    )?;
+    msg!("Initialized new StabilityPoolSnapshot for {}", denom);
+    
+    Ok(StabilityPoolSnapshot {
+        denom: denom.to_string(),
+        s_factor: 0,
+        total_collateral_gained: 0,
+        epoch: 0,
+    })
+}
+
+/// Validate the TotalCollateralAmount and CollateralConfig PDAs of a seized denom other than
+/// `collateral_denom` and pair them with its price feed
+fn load_liquidation_denom<'info>(
+    denom: &str,
+    total_collateral_account: &AccountInfo<'info>,
+    collateral_config_account: &AccountInfo<'info>,
+    pyth_price_account: &AccountInfo<'info>,
+) -> Result<LiquidationDenom<'info>> {
+    let (expected_total_pda, _bump) = Pubkey::find_program_address(
+        &TotalCollateralAmount::seeds(denom),
+        &crate::ID,
+    );
+    let (expected_config_pda, _bump) = Pubkey::find_program_address(
+        &CollateralConfig::seeds(denom),
+        &crate::ID,
+    );
+    require!(
+        expected_total_pda == *total_collateral_account.key
+            && expected_config_pda == *collateral_config_account.key,
+        AerospacerProtocolError::InvalidList
+    );
+    require!(
+        total_collateral_account.owner == &crate::ID && collateral_config_account.owner == &crate::ID,
+        AerospacerProtocolError::Unauthorized
+    );
+    require!(
+        total_collateral_account.is_writable,
+        AerospacerProtocolError::Unauthorized
+    );
+    
+    let config = {
+        let account_data = collateral_config_account.try_borrow_data()?;
+        CollateralConfig::try_deserialize(&mut &account_data[..])?
+    };
+    
+    Ok(LiquidationDenom {
+        denom: denom.to_string(),
+        total_collateral_amount: total_collateral_account.clone(),
+        pyth_price_account: pyth_price_account.clone(),
+        liquidation_threshold: config.liquidation_threshold,
+        liquidation_penalty_bps: config.liquidation_penalty_bps,
+    })
+}
+
+/// Add `amount` to the denom's UndistributedSeizure PDA, creating it on first use
+fn credit_undistributed_seizure<'info>(
+    account_info: &AccountInfo<'info>,
+    denom: &str,
+    amount: u64,
+    bump: u8,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    require!(
+        account_info.is_writable,
+        AerospacerProtocolError::Unauthorized
+    );
+    
+    let mut seizure = if account_info.owner == &crate::ID {
+        let account_data = account_info.try_borrow_data()?;
+        UndistributedSeizure::try_deserialize(&mut &account_data[..])?
+    } else {
+        create_program_pda(
+            payer,
+            account_info,
+            system_program,
+            8 + UndistributedSeizure::LEN,
+            &[b"undistributed_seizure", denom.as_bytes(), &[bump]],
+        )?;
+        msg!("Initialized new UndistributedSeizure for {}", denom);
+        UndistributedSeizure {
+            denom: denom.to_string(),
+            amount: 0,
+            total_seized: 0,
+            total_routed: 0,
+        }
+    };
+    
+    seizure.amount = seizure.amount
+        .checked_add(amount)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    seizure.total_seized = seizure.total_seized
+        .checked_add(amount)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    
+    let mut data = account_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    seizure.try_serialize(&mut writer)?;
+    
+    msg!("Undistributed seizure booked: {} {} (unrouted: {})", amount, denom, seizure.amount);
+    
+    Ok(())
+}
+
+/// Add `amount` to the denom's PublicGoodsAccrual PDA (looked up in `accounts`), creating it on first use
+fn credit_public_goods_accrual<'info>(
+    accounts: &[AccountInfo<'info>],
+    denom: &str,
+    amount: u64,
+    penalty: u64,
+    public_goods_bps: u16,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let (expected_pda, bump) = Pubkey::find_program_address(
+        &PublicGoodsAccrual::seeds(denom),
+        &crate::ID,
+    );
+    let account_info = accounts
+        .iter()
+        .find(|account| *account.key == expected_pda)
+        .ok_or(AerospacerProtocolError::InvalidList)?;
+    require!(
+        account_info.is_writable,
+        AerospacerProtocolError::Unauthorized
+    );
+    
+    let mut accrual = if account_info.owner == &crate::ID {
+        let account_data = account_info.try_borrow_data()?;
+        PublicGoodsAccrual::try_deserialize(&mut &account_data[..])?
+    } else {
+        create_program_pda(
+            payer,
+            account_info,
+            system_program,
+            8 + PublicGoodsAccrual::LEN,
+            &[b"public_goods_accrual", denom.as_bytes(), &[bump]],
+        )?;
+        msg!("Initialized new PublicGoodsAccrual for {}", denom);
+        PublicGoodsAccrual {
+            denom: denom.to_string(),
+            amount: 0,
+            total_accrued: 0,
+            total_routed: 0,
+        }
+    };
+    
+    accrual.book(amount)?;
+    
+    let mut data = account_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    accrual.try_serialize(&mut writer)?;
+    
+    msg!("Public goods share booked: {} {} (unrouted: {})", amount, denom, accrual.amount);
+    
+    emit!(PublicGoodsAccrued {
+        denom: denom.to_string(),
+        amount,
+        penalty,
+        public_goods_bps,
+        pending: accrual.amount,
+    });
+    
     Ok(())
 }
 
-/// Validate LiquidityThreshold account
-fn validate_liquidity_threshold_account(account_info: &AccountInfo, expected_user: &Pubkey) -> Result<()> {
+/// Validate LiquidityThreshold account of the trove `trove_id`, returning its stored ICR
+fn validate_liquidity_threshold_account(account_info: &AccountInfo, expected_user: &Pubkey, trove_id: u16) -> Result<u64> {
     require!(
         account_info.owner == &crate::ID,
         AerospacerProtocolError::Unauthorized
@@ -283,14 +766,21 @@ fn validate_liquidity_threshold_account(account_info: &AccountInfo, expected_use
     );
     
     let account_data = account_info.try_borrow_data()?;
-    let liquidity_threshold = LiquidityThreshold::try_from_slice(&account_data)?;
+    let liquidity_threshold = LiquidityThreshold::try_deserialize(&mut &account_data[..])?;
     
     require!(
         liquidity_threshold.owner == *expected_user,
         AerospacerProtocolError::Unauthorized
     );
     
-    Ok(())
+    // The ratio orders the batch, so it must be this trove's and not another of the owner's
+    require!(
+        liquidity_threshold.trove_id == trove_id,
+        AerospacerProtocolError::InvalidList
+    );
+    crate::sorted_troves::verify_liquidity_threshold_pda(account_info, *expected_user, trove_id, &crate::ID)?;
+    
+    Ok(liquidity_threshold.ratio)
 }
 
 /// Validate TokenAccount
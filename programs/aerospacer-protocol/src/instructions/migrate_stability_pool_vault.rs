@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::utils::check_stability_pool_backing;
+
+#[derive(Accounts)]
+pub struct MigrateStabilityPoolVault<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+    
+    /// CHECK: This is the stable coin mint account
+    #[account(
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub stable_coin_mint: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol_stablecoin_vault"],
+        bump
+    )]
+    pub protocol_stablecoin_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        token::mint = stable_coin_mint,
+        token::authority = stability_pool_vault,
+        seeds = [b"stability_pool_vault"],
+        bump
+    )]
+    pub stability_pool_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for migrate_stability_pool_vault instruction
+/// Deployments from before the dedicated stability pool vault kept staked aUSD in the protocol
+/// stablecoin vault. Moves whatever the pool vault is missing of total_stake_amount across; a
+/// no-op once the pool vault is fully backed.
+pub fn handler(ctx: Context<MigrateStabilityPoolVault>) -> Result<()> {
+    let total_stake_amount = ctx.accounts.state.load()?.total_stake_amount;
+    let missing = total_stake_amount.saturating_sub(ctx.accounts.stability_pool_vault.amount);
+    
+    if missing > 0 {
+        let vault_seeds: &[&[u8]] = &[b"protocol_stablecoin_vault", &[ctx.bumps.protocol_stablecoin_vault]];
+        let signer: &[&[&[u8]]] = &[vault_seeds];
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+                to: ctx.accounts.stability_pool_vault.to_account_info(),
+                authority: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+            },
+            signer,
+        );
+        anchor_spl::token::transfer(transfer_ctx, missing)?;
+    }
+    check_stability_pool_backing(&ctx.accounts.stability_pool_vault.to_account_info(), total_stake_amount)?;
+    
+    msg!("Stability pool vault migrated");
+    msg!("Total stake: {} aUSD", total_stake_amount);
+    msg!("Moved from protocol stablecoin vault: {} aUSD", missing);
+    
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::UserStakeAmount;
+use crate::error::AerospacerProtocolError;
+
+#[derive(Accounts)]
+pub struct MigrateUserStake<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Depositor the stake belongs to - only used to derive the PDA
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: Stake that may still have a legacy size - owner, discriminator and depositor are checked in the handler
+    #[account(
+        mut,
+        seeds = [b"user_stake_amount", owner.key().as_ref()],
+        bump
+    )]
+    pub user_stake_amount: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for migrate_user_stake instruction
+/// Grows a stability pool deposit created before the fee yield, reward emission and frontend
+/// fields to the current size, so stake, unstake and the claims can load it again. Permissionless;
+/// the payer covers the extra rent. The new bytes are zeroed: G factors were 0 when the deposit
+/// last snapshotted, nothing is pending, and no frontend is tagged. A no-op once migrated.
+pub fn handler(ctx: Context<MigrateUserStake>) -> Result<()> {
+    let stake_info = ctx.accounts.user_stake_amount.to_account_info();
+    let target_len = 8 + UserStakeAmount::LEN;
+
+    require!(
+        stake_info.owner == &crate::ID,
+        AerospacerProtocolError::Unauthorized
+    );
+    {
+        let data = stake_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 + UserStakeAmount::LEGACY_LEN && data[..8] == *UserStakeAmount::DISCRIMINATOR,
+            AerospacerProtocolError::InvalidSnapshot
+        );
+        // owner is the first field
+        require!(
+            data[8..40] == ctx.accounts.owner.key().to_bytes(),
+            AerospacerProtocolError::Unauthorized
+        );
+    }
+
+    if stake_info.data_len() >= target_len {
+        msg!("Stake already migrated");
+        return Ok(());
+    }
+
+    let rent = Rent::get()?.minimum_balance(target_len);
+    let top_up = rent.saturating_sub(stake_info.lamports());
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: stake_info.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    stake_info.resize(target_len)?;
+
+    // Fails rather than leave a stake that no instruction can load
+    let stake = UserStakeAmount::try_deserialize(&mut &stake_info.try_borrow_data()?[..])?;
+
+    msg!("Stake of {} migrated to {} bytes", stake.owner, target_len);
+    msg!("Deposit: {}", stake.amount);
+
+    Ok(())
+}
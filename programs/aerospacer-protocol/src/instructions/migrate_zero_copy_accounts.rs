@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+
+#[derive(Accounts)]
+pub struct MigrateZeroCopyAccounts<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Protocol state that may still have the legacy Borsh layout - owner, discriminator and admin are checked in the handler
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // remaining_accounts: writable TotalCollateralAmount PDAs to convert (any already converted are skipped)
+}
+
+/// Handler for migrate_zero_copy_accounts instruction
+/// One-shot upgrade of a deployment created while StateAccount and TotalCollateralAmount were
+/// Borsh accounts: rewrites the state, grown to the zero-copy size at the admin's expense, and
+/// every passed collateral total in the zero-copy layout. No other instruction can load them
+/// before this runs. Safe to repeat; converted accounts are left as they are.
+pub fn handler(ctx: Context<MigrateZeroCopyAccounts>) -> Result<()> {
+    let state_info = ctx.accounts.state.to_account_info();
+    let target_len = 8 + StateAccount::LEN;
+
+    require!(
+        state_info.owner == &crate::ID,
+        AerospacerProtocolError::Unauthorized
+    );
+    let legacy_state = {
+        let data = state_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 + 32 && data[..8] == *StateAccount::DISCRIMINATOR,
+            AerospacerProtocolError::InvalidSnapshot
+        );
+        // admin is the first field of both layouts
+        require!(
+            data[8..40] == ctx.accounts.admin.key().to_bytes(),
+            AerospacerProtocolError::Unauthorized
+        );
+        if data.len() < target_len {
+            Some(LegacyStateAccount::deserialize(&mut &data[8..])?)
+        } else {
+            None
+        }
+    };
+
+    if let Some(legacy_state) = legacy_state {
+        let rent = Rent::get()?.minimum_balance(target_len);
+        let top_up = rent.saturating_sub(state_info.lamports());
+        if top_up > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.admin.to_account_info(),
+                        to: state_info.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        state_info.resize(target_len)?;
+
+        let state = legacy_state.migrate();
+        state_info.try_borrow_mut_data()?[8..target_len].copy_from_slice(bytemuck::bytes_of(&state));
+        msg!("State migrated to {} bytes", target_len);
+    } else {
+        msg!("State already migrated");
+    }
+
+    for total_info in ctx.remaining_accounts {
+        require!(
+            total_info.owner == &crate::ID && total_info.is_writable,
+            AerospacerProtocolError::Unauthorized
+        );
+
+        let legacy_total = {
+            let data = total_info.try_borrow_data()?;
+            LegacyTotalCollateralAmount::from_account_data(&data, total_info.key)
+        };
+        let Some(legacy_total) = legacy_total else {
+            // Not legacy: it must then be a converted total of its own denom
+            let data = total_info.try_borrow_data()?;
+            let total = TotalCollateralAmount::from_account_data(&data)?;
+            let (expected_pda, _bump) = Pubkey::find_program_address(
+                &TotalCollateralAmount::seeds(total.denom()),
+                &crate::ID,
+            );
+            require!(
+                expected_pda == *total_info.key,
+                AerospacerProtocolError::InvalidList
+            );
+            msg!("Collateral total {} already migrated", total.denom());
+            continue;
+        };
+
+        require!(
+            total_info.data_len() >= 8 + TotalCollateralAmount::LEN,
+            AerospacerProtocolError::InvalidSnapshot
+        );
+        let total = legacy_total.migrate()?;
+        total_info.try_borrow_mut_data()?[8..8 + TotalCollateralAmount::LEN].copy_from_slice(bytemuck::bytes_of(&total));
+        msg!("Collateral total {} migrated: amount={}", legacy_total.denom, legacy_total.amount);
+    }
+
+    Ok(())
+}
@@ -14,6 +14,79 @@ pub mod withdraw_liquidation_gains;
 pub mod redeem;
 pub mod update_protocol_addresses;
 pub mod transfer_stablecoin;
+pub mod refresh_denom_bucket;
+pub mod renormalize_l_factors;
+pub mod update_tag_registry;
+pub mod update_oracle_fallback;
+pub mod withdraw_fee_yield;
+pub mod claim_all_gains;
+pub mod health_check;
+pub mod update_priority_redemption;
+pub mod configure_reward_emissions;
+pub mod claim_rewards;
+pub mod sync_user_index;
+pub mod repay_batch;
+pub mod register_frontend;
+pub mod claim_frontend_rewards;
+pub mod flash_mint;
+pub mod repay_flash_mint;
+pub mod set_denom_promotion;
+pub mod configure_psm;
+pub mod psm_swap_in;
+pub mod psm_swap_out;
+pub mod update_redemption_limits;
+pub mod route_undistributed_seizure;
+pub mod approve_delegate;
+pub mod revoke_delegate;
+pub mod update_public_goods_share;
+pub mod route_public_goods;
+pub mod initialize_trove_archive;
+pub mod archive_trove;
+pub mod transfer_trove;
+pub mod reclaim_rent;
+pub mod register_heartbeat;
+pub mod query_stale_keepers;
+pub mod register_keeper;
+pub mod deregister_keeper;
+pub mod configure_keeper_bounties;
+pub mod fund_keeper_bounties;
+pub mod fund_bad_debt_backstop;
+pub mod update_liquidator_whitelist;
+pub mod start_auction;
+pub mod bid;
+pub mod settle;
+pub mod configure_rebalancer;
+pub mod forward_seized_collateral;
+pub mod register_collateral;
+pub mod deactivate_collateral;
+pub mod set_collateral_params;
+pub mod refresh_price;
+pub mod refresh_trove_icr;
+pub mod update_hint_policy;
+pub mod migrate_stability_pool_vault;
+pub mod update_liquidation_reserve;
+pub mod refresh_tcr;
+pub mod get_tcr;
+pub mod update_bootstrap_period;
+pub mod prune_trove_index;
+pub mod configure_fee_accumulator;
+pub mod distribute_accumulated;
+pub mod assign_mint_authority;
+pub mod propose_mint_authority_migration;
+pub mod cancel_mint_authority_migration;
+pub mod execute_mint_authority_migration;
+pub mod init_stablecoin;
+pub mod update_config;
+pub mod init_trove_history;
+pub mod get_pending_rewards;
+pub mod deleverage;
+pub mod update_swap_program_whitelist;
+pub mod leverage_open;
+pub mod continue_redemption;
+pub mod migrate_user_stake;
+pub mod migrate_zero_copy_accounts;
+#[cfg(feature = "fork-testing")]
+pub mod import_account;
 
 #[allow(ambiguous_glob_reexports)]
 pub use initialize::*;
@@ -46,4 +119,149 @@ pub use redeem::*;
 #[allow(ambiguous_glob_reexports)]
 pub use update_protocol_addresses::*;
 #[allow(ambiguous_glob_reexports)]
-pub use transfer_stablecoin::*; 
\ No newline at end of file
+pub use transfer_stablecoin::*;
+#[allow(ambiguous_glob_reexports)]
+pub use refresh_denom_bucket::*;
+#[allow(ambiguous_glob_reexports)]
+pub use renormalize_l_factors::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_tag_registry::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_oracle_fallback::*;
+#[allow(ambiguous_glob_reexports)]
+pub use withdraw_fee_yield::*;
+#[allow(ambiguous_glob_reexports)]
+pub use claim_all_gains::*;
+#[allow(ambiguous_glob_reexports)]
+pub use health_check::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_priority_redemption::*;
+#[allow(ambiguous_glob_reexports)]
+pub use configure_reward_emissions::*;
+#[allow(ambiguous_glob_reexports)]
+pub use claim_rewards::*;
+#[allow(ambiguous_glob_reexports)]
+pub use sync_user_index::*;
+#[allow(ambiguous_glob_reexports)]
+pub use repay_batch::*;
+#[allow(ambiguous_glob_reexports)]
+pub use register_frontend::*;
+#[allow(ambiguous_glob_reexports)]
+pub use claim_frontend_rewards::*;
+#[allow(ambiguous_glob_reexports)]
+pub use flash_mint::*;
+#[allow(ambiguous_glob_reexports)]
+pub use repay_flash_mint::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_denom_promotion::*;
+#[allow(ambiguous_glob_reexports)]
+pub use configure_psm::*;
+#[allow(ambiguous_glob_reexports)]
+pub use psm_swap_in::*;
+#[allow(ambiguous_glob_reexports)]
+pub use psm_swap_out::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_redemption_limits::*;
+#[allow(ambiguous_glob_reexports)]
+pub use route_undistributed_seizure::*;
+#[allow(ambiguous_glob_reexports)]
+pub use approve_delegate::*;
+#[allow(ambiguous_glob_reexports)]
+pub use revoke_delegate::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_public_goods_share::*;
+#[allow(ambiguous_glob_reexports)]
+pub use route_public_goods::*;
+#[allow(ambiguous_glob_reexports)]
+pub use initialize_trove_archive::*;
+#[allow(ambiguous_glob_reexports)]
+pub use archive_trove::*;
+#[allow(ambiguous_glob_reexports)]
+pub use transfer_trove::*;
+#[allow(ambiguous_glob_reexports)]
+pub use reclaim_rent::*;
+#[allow(ambiguous_glob_reexports)]
+pub use register_heartbeat::*;
+#[allow(ambiguous_glob_reexports)]
+pub use query_stale_keepers::*;
+#[allow(ambiguous_glob_reexports)]
+pub use register_keeper::*;
+#[allow(ambiguous_glob_reexports)]
+pub use deregister_keeper::*;
+#[allow(ambiguous_glob_reexports)]
+pub use configure_keeper_bounties::*;
+#[allow(ambiguous_glob_reexports)]
+pub use fund_keeper_bounties::*;
+#[allow(ambiguous_glob_reexports)]
+pub use fund_bad_debt_backstop::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_liquidator_whitelist::*;
+#[allow(ambiguous_glob_reexports)]
+pub use start_auction::*;
+#[allow(ambiguous_glob_reexports)]
+pub use bid::*;
+#[allow(ambiguous_glob_reexports)]
+pub use settle::*;
+#[allow(ambiguous_glob_reexports)]
+pub use configure_rebalancer::*;
+#[allow(ambiguous_glob_reexports)]
+pub use forward_seized_collateral::*;
+#[allow(ambiguous_glob_reexports)]
+pub use register_collateral::*;
+#[allow(ambiguous_glob_reexports)]
+pub use deactivate_collateral::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_collateral_params::*;
+#[allow(ambiguous_glob_reexports)]
+pub use refresh_price::*;
+#[allow(ambiguous_glob_reexports)]
+pub use refresh_trove_icr::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_hint_policy::*;
+#[allow(ambiguous_glob_reexports)]
+pub use migrate_stability_pool_vault::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_liquidation_reserve::*;
+#[allow(ambiguous_glob_reexports)]
+pub use refresh_tcr::*;
+#[allow(ambiguous_glob_reexports)]
+pub use get_tcr::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_bootstrap_period::*;
+#[allow(ambiguous_glob_reexports)]
+pub use prune_trove_index::*;
+#[allow(ambiguous_glob_reexports)]
+pub use configure_fee_accumulator::*;
+#[allow(ambiguous_glob_reexports)]
+pub use distribute_accumulated::*;
+#[allow(ambiguous_glob_reexports)]
+pub use assign_mint_authority::*;
+#[allow(ambiguous_glob_reexports)]
+pub use propose_mint_authority_migration::*;
+#[allow(ambiguous_glob_reexports)]
+pub use cancel_mint_authority_migration::*;
+#[allow(ambiguous_glob_reexports)]
+pub use execute_mint_authority_migration::*;
+#[allow(ambiguous_glob_reexports)]
+pub use init_stablecoin::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_config::*;
+#[allow(ambiguous_glob_reexports)]
+pub use init_trove_history::*;
+#[allow(ambiguous_glob_reexports)]
+pub use get_pending_rewards::*;
+#[allow(ambiguous_glob_reexports)]
+pub use deleverage::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_swap_program_whitelist::*;
+#[allow(ambiguous_glob_reexports)]
+pub use leverage_open::*;
+#[allow(ambiguous_glob_reexports)]
+pub use continue_redemption::*;
+#[allow(ambiguous_glob_reexports)]
+pub use migrate_user_stake::*;
+#[allow(ambiguous_glob_reexports)]
+pub use migrate_zero_copy_accounts::*;
+#[cfg(feature = "fork-testing")]
+#[allow(ambiguous_glob_reexports)]
+pub use import_account::*;
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Mint, MintTo};
+use anchor_spl::token::{Token, TokenAccount, Mint};
 use crate::state::*;
 use crate::error::*;
 use crate::account_management::*;
@@ -8,6 +8,11 @@ use crate::trove_management::TroveManager;
 use crate::state::{MINIMUM_LOAN_AMOUNT, MINIMUM_COLLATERAL_AMOUNT};
 use crate::fees_integration::*;
 use crate::utils::*;
+use crate::events::TroveOpened;
+use crate::liquidation_reserve::check_net_debt;
+use crate::mint_gate::{mint_stablecoin, MintPurpose};
+use crate::trove_index::append_trove;
+use crate::validation::{ValidateParams, validate_min_amount, validate_denom, validate_pubkey};
 
 // Oracle integration is now handled via our aerospacer-oracle contract
 
@@ -15,7 +20,28 @@ use crate::utils::*;
 pub struct OpenTroveParams {
     pub loan_amount: u64,
     pub collateral_denom: String,
+    pub trove_id: u16, // Which of the owner's troves (0 = the wallet's first trove)
     pub collateral_amount: u64,
+    pub tag: Option<[u8; 32]>, // Optional frontend/bot attribution tag
+    pub frontend: Option<FrontendTag>, // Optional registered frontend that onboarded the trove
+    pub memo: Option<[u8; 32]>, // Optional integrator memo, echoed in the emitted event
+}
+
+impl ValidateParams for OpenTroveParams {
+    fn validate(&self) -> Result<()> {
+        validate_min_amount(self.loan_amount, MINIMUM_LOAN_AMOUNT, AerospacerProtocolError::LoanAmountBelowMinimum)?;
+        validate_min_amount(self.collateral_amount, MINIMUM_COLLATERAL_AMOUNT, AerospacerProtocolError::CollateralBelowMinimum)?;
+        validate_denom(&self.collateral_denom)?;
+        // Untagged troves pass None rather than an all-zero tag
+        require!(
+            self.tag != Some(UNTAGGED),
+            AerospacerProtocolError::InvalidTroveTag
+        );
+        if let Some(frontend) = &self.frontend {
+            validate_pubkey(&frontend.operator)?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
@@ -29,7 +55,7 @@ pub struct OpenTrove<'info> {
         init,
         payer = user,
         space = 8 + UserDebtAmount::LEN,
-        seeds = [b"user_debt_amount", user.key().as_ref()],
+        seeds = [b"user_debt_amount", user.key().as_ref(), trove_id_seed(params.trove_id).as_slice()],
         bump
     )]
     pub user_debt_amount: Box<Account<'info, UserDebtAmount>>,
@@ -38,7 +64,7 @@ pub struct OpenTrove<'info> {
         init,
         payer = user,
         space = 8 + LiquidityThreshold::LEN,
-        seeds = [b"liquidity_threshold", user.key().as_ref()],
+        seeds = [b"liquidity_threshold", user.key().as_ref(), trove_id_seed(params.trove_id).as_slice()],
         bump
     )]
     pub liquidity_threshold: Box<Account<'info, LiquidityThreshold>>,
@@ -48,7 +74,7 @@ pub struct OpenTrove<'info> {
         init,
         payer = user,
         space = 8 + UserCollateralAmount::LEN,
-        seeds = [b"user_collateral_amount", user.key().as_ref(), params.collateral_denom.as_bytes()],
+        seeds = [b"user_collateral_amount", user.key().as_ref(), trove_id_seed(params.trove_id).as_slice(), params.collateral_denom.as_bytes()],
         bump
     )]
     pub user_collateral_amount: Box<Account<'info, UserCollateralAmount>>,
@@ -63,28 +89,30 @@ pub struct OpenTrove<'info> {
     pub collateral_mint: Box<Account<'info, Mint>>,
     
     #[account(
-        init_if_needed,
-        payer = user,
-        token::mint = collateral_mint,
-        token::authority = protocol_collateral_account,
+        mut,
         seeds = [b"protocol_collateral_vault", params.collateral_denom.as_bytes()],
-        bump
+        bump,
+        constraint = protocol_collateral_account.mint == collateral_mint.key() @ AerospacerProtocolError::InvalidMint
     )]
     pub protocol_collateral_account: Box<Account<'info, TokenAccount>>,
     
-    /// CHECK: Per-denom collateral total PDA
+    /// CHECK: Per-denom collateral total PDA - created by register_collateral
     #[account(
-        init_if_needed,
-        payer = user,
-        space = 8 + TotalCollateralAmount::LEN,
+        mut,
         seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
         bump
     )]
-    pub total_collateral_amount: Box<Account<'info, TotalCollateralAmount>>,
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+    
+    #[account(
+        seeds = [b"collateral_config", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
     
     // State account - Box<> to reduce stack usage
     #[account(mut)]
-    pub state: Box<Account<'info, StateAccount>>,
+    pub state: AccountLoader<'info, StateAccount>,
     
     // Token accounts - Box<> to reduce stack usage
     #[account(
@@ -103,9 +131,27 @@ pub struct OpenTrove<'info> {
     )]
     pub protocol_stablecoin_account: Box<Account<'info, TokenAccount>>,
     
+    // Dedicated aUSD mint authority PDA (see mint_gate)
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: Box<Account<'info, MintAuthority>>,
+    
+    // Holds the liquidation reserve of every open trove (see update_liquidation_reserve)
+    #[account(
+        init_if_needed,
+        payer = user,
+        token::mint = stable_coin_mint,
+        token::authority = liquidation_reserve_vault,
+        seeds = [b"liquidation_reserve_vault"],
+        bump
+    )]
+    pub liquidation_reserve_vault: Box<Account<'info, TokenAccount>>,
+    
     #[account(
         mut,
-        constraint = stable_coin_mint.key() == state.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
     )]
     pub stable_coin_mint: Box<Account<'info, Mint>>,
     
@@ -135,68 +181,138 @@ pub struct OpenTrove<'info> {
     #[account(mut)]
     pub stability_pool_token_account: UncheckedAccount<'info>,
     
-    /// CHECK: Fee address 1 token account
+    /// CHECK: Protocol's fee caller PDA - signs the distribute_fee CPI, allowlisted in aerospacer-fees
+    #[account(
+        seeds = [b"fee_caller"],
+        bump
+    )]
+    pub fee_caller: UncheckedAccount<'info>,
+    
+    /// CHECK: aerospacer-fees fee vault - checked by the fees program
     #[account(mut)]
-    pub fee_address_1_token_account: UncheckedAccount<'info>,
+    pub fee_vault: UncheckedAccount<'info>,
     
-    /// CHECK: Fee address 2 token account
+    /// CHECK: aerospacer-fees per-epoch fee stats - checked by the fees program
     #[account(mut)]
-    pub fee_address_2_token_account: UncheckedAccount<'info>,
+    pub fee_epoch_stats: UncheckedAccount<'info>,
+
+    /// Dust fee accumulator - optional; a fee below its threshold is parked instead of distributed
+    #[account(
+        mut,
+        seeds = [b"fee_accumulator"],
+        bump
+    )]
+    pub fee_accumulator: Option<Box<Account<'info, FeeAccumulator>>>,
+    
+    /// CHECK: Fee accumulator vault PDA - required with the fee accumulator
+    #[account(
+        mut,
+        seeds = [b"fee_accumulator_vault"],
+        bump
+    )]
+    pub fee_accumulator_vault: Option<UncheckedAccount<'info>>,
+    
+    /// Optional position summary, refreshed when passed
+    #[account(
+        mut,
+        seeds = [b"user_index", user.key().as_ref()],
+        bump
+    )]
+    pub user_index: Option<Box<Account<'info, UserIndex>>>,
+    
+    // Trove ids the wallet has in use
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + TroveRegistry::LEN,
+        seeds = [b"trove_registry", user.key().as_ref()],
+        bump
+    )]
+    pub trove_registry: Box<Account<'info, TroveRegistry>>,
     
+    // Tail page of the on-chain trove index, created when the trove starts a new page
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + TroveIndexPage::LEN,
+        seeds = [b"trove_index_page", state.load()?.trove_index_tail_seed().as_ref()],
+        bump
+    )]
+    pub trove_index_page: Box<Account<'info, TroveIndexPage>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+    
+    // Only required when tag enforcement is enabled and a tag is supplied
+    #[account(
+        seeds = [b"tag_registry"],
+        bump
+    )]
+    pub tag_registry: Option<Box<Account<'info, TagRegistry>>>,
+    
+    // Registered frontend - required when params.frontend is set
+    #[account(
+        mut,
+        seeds = [b"frontend", frontend.operator.as_ref()],
+        bump
+    )]
+    pub frontend: Option<Box<Account<'info, Frontend>>>,
+    
+    // Promotional terms for the denom - borrowing fee is waived while active
+    #[account(
+        seeds = [b"denom_promotion", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub denom_promotion: Option<Box<Account<'info, DenomPromotion>>>,
+    
+    // Keeper-refreshed price (see refresh_price) - read instead of the oracle while fresh
+    #[account(
+        seeds = [b"price_cache", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub price_cache: Option<Box<Account<'info, PriceCache>>>,
+    
+    /// Optional operation history, appended to when passed
+    #[account(
+        mut,
+        seeds = [b"trove_history", user.key().as_ref()],
+        bump
+    )]
+    pub trove_history: Option<Box<Account<'info, TroveHistory>>>,
 }
 
 pub fn handler(ctx: Context<OpenTrove>, params: OpenTroveParams) -> Result<()> {
+    params.validate()?;
+    
     // Validate oracle accounts
     require!(
-        ctx.accounts.oracle_program.key() == ctx.accounts.state.oracle_helper_addr,
+        ctx.accounts.oracle_program.key() == ctx.accounts.state.load()?.oracle_helper_addr,
         AerospacerProtocolError::Unauthorized
     );
     require!(
-        ctx.accounts.oracle_state.key() == ctx.accounts.state.oracle_state_addr,
+        ctx.accounts.oracle_state.key() == ctx.accounts.state.load()?.oracle_state_addr,
         AerospacerProtocolError::Unauthorized
     );
     
     // Validate fee accounts
     require!(
-        ctx.accounts.fees_program.key() == ctx.accounts.state.fee_distributor_addr,
+        ctx.accounts.fees_program.key() == ctx.accounts.state.load()?.fee_distributor_addr,
         AerospacerProtocolError::Unauthorized
     );
     require!(
-        ctx.accounts.fees_state.key() == ctx.accounts.state.fee_state_addr,
+        ctx.accounts.fees_state.key() == ctx.accounts.state.load()?.fee_state_addr,
         AerospacerProtocolError::Unauthorized
     );
     
-    // Validate input parameters
-    require!(
-        params.loan_amount > 0,
-        AerospacerProtocolError::InvalidAmount
-    );
-    
-    require!(
-        params.loan_amount >= MINIMUM_LOAN_AMOUNT,
-        AerospacerProtocolError::LoanAmountBelowMinimum
-    );
-    
+    // No new troves on a deactivated denom
     require!(
-        params.collateral_amount > 0,
-        AerospacerProtocolError::InvalidAmount
+        ctx.accounts.total_collateral_amount.load()?.is_active(),
+        AerospacerProtocolError::CollateralDeactivated
     );
     
+    // Check if user already has a trove (a redeemed-closed trove must reclaim its surplus via close_trove first)
     require!(
-        params.collateral_amount >= MINIMUM_COLLATERAL_AMOUNT,
-        AerospacerProtocolError::CollateralBelowMinimum
-    );
-    
-    require!(
-        !params.collateral_denom.is_empty(),
-        AerospacerProtocolError::InvalidAmount
-    );
-    
-    // Check if user already has a trove (should be 0 for new trove)
-    require!(
-        ctx.accounts.user_debt_amount.amount == 0,
+        !ctx.accounts.user_debt_amount.is_active(),
         AerospacerProtocolError::TroveExists
     );
     
@@ -206,45 +322,102 @@ pub fn handler(ctx: Context<OpenTrove>, params: OpenTroveParams) -> Result<()> {
         AerospacerProtocolError::InsufficientCollateral
     );
     
+    // Validate the attribution tag (untagged troves are always allowed)
+    let tag = params.tag.unwrap_or(UNTAGGED);
+    if let Some(tag) = params.tag {
+        if ctx.accounts.state.load()?.enforce_trove_tags != 0 {
+            let registry = ctx.accounts.tag_registry
+                .as_ref()
+                .ok_or(AerospacerProtocolError::UnknownTroveTag)?;
+            require!(
+                registry.contains(&tag),
+                AerospacerProtocolError::UnknownTroveTag
+            );
+        }
+    }
+    
+    // Validate and record the frontend attribution
+    if let Some(frontend_tag) = params.frontend {
+        let frontend = ctx.accounts.frontend
+            .as_mut()
+            .ok_or(AerospacerProtocolError::UnknownFrontend)?;
+        frontend.validate_tag(&frontend_tag)?;
+        frontend.troves_onboarded = safe_add(frontend.troves_onboarded, 1)?;
+        msg!("Frontend: {}", frontend_tag.operator);
+    }
+    
     // Initialize user debt amount
     ctx.accounts.user_debt_amount.owner = ctx.accounts.user.key();
     ctx.accounts.user_debt_amount.amount = 0; // Will be set below
     ctx.accounts.user_debt_amount.l_debt_snapshot = 0; // Will be set to current global L value later
+    ctx.accounts.user_debt_amount.tag = tag;
+    ctx.accounts.user_debt_amount.trove_id = params.trove_id;
+    ctx.accounts.user_debt_amount.collateral_legs = 1;
+    ctx.accounts.user_debt_amount.transition(TroveStatus::Active)?;
+    ctx.accounts.state.load_mut()?.record_trove_opened();
+    append_trove(
+        &mut ctx.accounts.trove_index_page,
+        &mut *ctx.accounts.state.load_mut()?,
+        &mut ctx.accounts.user_debt_amount,
+    )?;
     
     // Initialize user collateral amount
     ctx.accounts.user_collateral_amount.owner = ctx.accounts.user.key();
     ctx.accounts.user_collateral_amount.denom = params.collateral_denom.clone();
     ctx.accounts.user_collateral_amount.amount = 0; // Will be set below
     ctx.accounts.user_collateral_amount.l_collateral_snapshot = 0; // Will be set to current global L value later
+    ctx.accounts.user_collateral_amount.trove_id = params.trove_id;
     
     // Initialize liquidity threshold
     ctx.accounts.liquidity_threshold.owner = ctx.accounts.user.key();
     ctx.accounts.liquidity_threshold.ratio = 0; // Will be set below
+    ctx.accounts.liquidity_threshold.trove_id = params.trove_id;
+    
+    // Register the trove id (a wallet may run several isolated troves)
+    ctx.accounts.trove_registry.owner = ctx.accounts.user.key();
+    ctx.accounts.trove_registry.register(params.trove_id)?;
     
     // Calculate opening fee BEFORE trove operations
-    let fee_amount = calculate_protocol_fee(params.loan_amount, ctx.accounts.state.protocol_fee)?;
+    let protocol_fee = effective_borrowing_fee(
+        ctx.accounts.collateral_config.protocol_fee,
+        ctx.accounts.denom_promotion.as_deref().map(|p| &**p),
+        Clock::get()?.slot,
+    );
+    let fee_amount = calculate_protocol_fee(params.loan_amount, protocol_fee)?;
     let net_loan_amount = params.loan_amount.saturating_sub(fee_amount);
     
-    msg!("Opening fee: {} aUSD ({}%)", fee_amount, ctx.accounts.state.protocol_fee);
+    msg!("Opening fee: {} aUSD ({}%)", fee_amount, protocol_fee);
     msg!("Net loan amount: {} aUSD", net_loan_amount);
     
+    // The liquidation reserve is minted to the reserve vault on top of the loan and carried as
+    // debt, so even the smallest trove pays its liquidator; the net debt excludes it
+    let (liquidation_reserve, min_net_debt) = {
+        let state = ctx.accounts.state.load()?;
+        (state.liquidation_reserve, state.min_net_debt)
+    };
+    check_net_debt(net_loan_amount, min_net_debt)?;
+    let debt_amount = net_loan_amount
+        .checked_add(liquidation_reserve)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    ctx.accounts.user_debt_amount.liquidation_reserve = liquidation_reserve;
+    
     // Create contexts in scoped block to reduce stack usage
     // Execute trove operations and capture results
     let result = {
         let mut trove_ctx = TroveContext {
-            user: ctx.accounts.user.clone(),
-            user_debt_amount: (*ctx.accounts.user_debt_amount).clone(),
-            liquidity_threshold: (*ctx.accounts.liquidity_threshold).clone(),
-            state: (*ctx.accounts.state).clone(),
+            user: &ctx.accounts.user,
+            user_debt_amount: &mut ctx.accounts.user_debt_amount,
+            liquidity_threshold: &mut ctx.accounts.liquidity_threshold,
+            state: &ctx.accounts.state,
         };
         
         let mut collateral_ctx = CollateralContext {
-            user: ctx.accounts.user.clone(),
-            user_collateral_amount: (*ctx.accounts.user_collateral_amount).clone(),
-            user_collateral_account: (*ctx.accounts.user_collateral_account).clone(),
-            protocol_collateral_account: (*ctx.accounts.protocol_collateral_account).clone(),
-            total_collateral_amount: (*ctx.accounts.total_collateral_amount).clone(),
-            token_program: ctx.accounts.token_program.clone(),
+            user: &ctx.accounts.user,
+            user_collateral_amount: &mut ctx.accounts.user_collateral_amount,
+            user_collateral_account: &ctx.accounts.user_collateral_account,
+            protocol_collateral_account: &ctx.accounts.protocol_collateral_account,
+            total_collateral_amount: &ctx.accounts.total_collateral_amount,
+            token_program: &ctx.accounts.token_program,
         };
         
         let oracle_ctx = OracleContext {
@@ -252,20 +425,27 @@ pub fn handler(ctx: Context<OpenTrove>, params: OpenTroveParams) -> Result<()> {
             oracle_state: ctx.accounts.oracle_state.to_account_info(),
             pyth_price_account: ctx.accounts.pyth_price_account.to_account_info(),
             clock: ctx.accounts.clock.to_account_info(),
+            max_price_age: PriceReadClass::Standard.max_price_age(&*ctx.accounts.state.load()?),
+            fallback: None,
+            cached_price: PriceData::from_cache(
+                &*ctx.accounts.state.load()?,
+                ctx.accounts.price_cache.as_deref().map(|c| &**c),
+                &params.collateral_denom,
+                PriceReadClass::Standard.max_price_age(&*ctx.accounts.state.load()?),
+            )?,
         };
         
-        // Use TroveManager with NET loan amount (after fee)
+        // Use TroveManager with NET loan amount (after fee) plus the liquidation reserve
         let result = TroveManager::open_trove(
             &mut trove_ctx,
             &mut collateral_ctx,
             &oracle_ctx,
-            net_loan_amount,  // Use net amount for debt recording
+            debt_amount,  // Use net amount and reserve for debt recording
             params.collateral_amount,
             params.collateral_denom.clone(),
+            ctx.accounts.collateral_config.minimum_collateral_ratio,
         )?;
         
-        // Update state total debt before contexts are dropped
-        ctx.accounts.state.total_debt_amount = trove_ctx.state.total_debt_amount;
         
         Ok::<_, Error>(result)
     }?;
@@ -273,7 +453,7 @@ pub fn handler(ctx: Context<OpenTrove>, params: OpenTroveParams) -> Result<()> {
     // CRITICAL: Validate ICR ordering if neighbor hints provided
     // Production clients MUST provide neighbor hints via remainingAccounts for proper sorted list maintenance
     // Pattern: [prev_LiquidityThreshold, next_LiquidityThreshold] or [prev_LT] or [next_LT] or []
-    // Optional for backward compatibility with tests, but REQUIRED in production
+    // Optional for backward compatibility with tests; mandatory while state.require_hints is set
     if !ctx.remaining_accounts.is_empty() {
         use crate::sorted_troves;
         
@@ -286,10 +466,11 @@ pub fn handler(ctx: Context<OpenTrove>, params: OpenTroveParams) -> Result<()> {
             let prev_threshold = LiquidityThreshold::try_deserialize(&mut &prev_data[..])?;
             let prev_owner = prev_threshold.owner;
             let prev_ratio = prev_threshold.ratio;
+            let prev_trove_id = prev_threshold.trove_id;
             drop(prev_data);
             
             // Verify this is a real PDA, not a fake account
-            sorted_troves::verify_liquidity_threshold_pda(prev_lt, prev_owner, ctx.program_id)?;
+            sorted_troves::verify_liquidity_threshold_pda(prev_lt, prev_owner, prev_trove_id, ctx.program_id)?;
             
             msg!("Previous neighbor: owner={}, ICR={}", prev_owner, prev_ratio);
             Some(prev_ratio)
@@ -304,10 +485,11 @@ pub fn handler(ctx: Context<OpenTrove>, params: OpenTroveParams) -> Result<()> {
             let next_threshold = LiquidityThreshold::try_deserialize(&mut &next_data[..])?;
             let next_owner = next_threshold.owner;
             let next_ratio = next_threshold.ratio;
+            let next_trove_id = next_threshold.trove_id;
             drop(next_data);
             
             // Verify this is a real PDA, not a fake account
-            sorted_troves::verify_liquidity_threshold_pda(next_lt, next_owner, ctx.program_id)?;
+            sorted_troves::verify_liquidity_threshold_pda(next_lt, next_owner, next_trove_id, ctx.program_id)?;
             
             msg!("Next neighbor: owner={}, ICR={}", next_owner, next_ratio);
             Some(next_ratio)
@@ -319,72 +501,88 @@ pub fn handler(ctx: Context<OpenTrove>, params: OpenTroveParams) -> Result<()> {
         sorted_troves::validate_icr_ordering(result.new_icr, prev_icr, next_icr)?;
         msg!("✓ ICR ordering validated successfully");
     } else {
+        require!(
+            ctx.accounts.state.load()?.require_hints == 0,
+            AerospacerProtocolError::NeighborHintsRequired
+        );
         msg!("⚠ WARNING: No neighbor hints provided - skipping ICR ordering validation");
         msg!("⚠ Production clients MUST provide neighbor hints for sorted list integrity");
     }
     
-    // Update the actual accounts with the results
-    ctx.accounts.user_debt_amount.amount = result.new_debt_amount;
-    ctx.accounts.liquidity_threshold.ratio = result.new_icr;
-    ctx.accounts.user_collateral_amount.amount = result.new_collateral_amount;
-    
-    // Initialize total_collateral_amount if it was just created
-    if ctx.accounts.total_collateral_amount.denom.is_empty() {
-        ctx.accounts.total_collateral_amount.denom = params.collateral_denom.clone();
-        ctx.accounts.total_collateral_amount.amount = params.collateral_amount;
-        ctx.accounts.total_collateral_amount.l_debt = 0;
-        ctx.accounts.total_collateral_amount.l_collateral = 0;
-        
-        msg!("First trove for {} - initializing L factors to 0", params.collateral_denom);
-    } else {
-        // Update existing total
-        ctx.accounts.total_collateral_amount.amount = ctx.accounts.total_collateral_amount.amount
+    // Add to the per-denom total (the denom was registered with register_collateral)
+    {
+        let mut total_collateral = ctx.accounts.total_collateral_amount.load_mut()?;
+        total_collateral.amount = total_collateral.amount
             .checked_add(params.collateral_amount)
             .ok_or(AerospacerProtocolError::OverflowError)?;
+        total_collateral.record_trove_opened();
+        
+        // CRITICAL: Set L snapshots to current global values to prevent unearned retroactive rewards
+        // When a new trove opens after redistributions have occurred, it should NOT receive rewards
+        // from liquidations that happened before it existed
+        ctx.accounts.user_collateral_amount.l_debt_snapshot = Some(total_collateral.l_debt);
+        ctx.accounts.user_collateral_amount.l_collateral_snapshot = total_collateral.l_collateral;
+        ctx.accounts.user_collateral_amount.l_scale_snapshot = total_collateral.l_scale;
     }
     
-    // CRITICAL: Set L snapshots to current global values to prevent unearned retroactive rewards
-    // When a new trove opens after redistributions have occurred, it should NOT receive rewards
-    // from liquidations that happened before it existed
-    ctx.accounts.user_debt_amount.l_debt_snapshot = ctx.accounts.total_collateral_amount.l_debt;
-    ctx.accounts.user_collateral_amount.l_collateral_snapshot = ctx.accounts.total_collateral_amount.l_collateral;
-    
-    msg!("Initialized user L snapshots: l_debt={}, l_collateral={}", 
-         ctx.accounts.user_debt_amount.l_debt_snapshot,
+    msg!("Initialized user L snapshots: l_debt={:?}, l_collateral={}", 
+         ctx.accounts.user_collateral_amount.l_debt_snapshot,
          ctx.accounts.user_collateral_amount.l_collateral_snapshot);
     
     // Mint full loan amount to user first (user requested full amount, will pay fee from it)
-    // Use invoke_signed for PDA authority
-    let mint_seeds = &[
-        b"protocol_stablecoin_vault".as_ref(),
-        &[ctx.bumps.protocol_stablecoin_account],
-    ];
-    let mint_signer = &[&mint_seeds[..]];
-    
-    let mint_ctx = CpiContext::new_with_signer(
+    // The trove's debt, reserve included, is already in total_debt_amount
+    let total_debt = ctx.accounts.state.load()?.total_debt_amount;
+    mint_stablecoin(
+        &ctx.accounts.stable_coin_mint,
+        &ctx.accounts.mint_authority,
+        ctx.bumps.mint_authority,
+        ctx.accounts.user_stablecoin_account.to_account_info(),
         ctx.accounts.token_program.to_account_info(),
-        MintTo {
-            mint: ctx.accounts.stable_coin_mint.to_account_info(),
-            to: ctx.accounts.user_stablecoin_account.to_account_info(),
-            authority: ctx.accounts.protocol_stablecoin_account.to_account_info(),
-        },
-        mint_signer,
-    );
-    anchor_spl::token::mint_to(mint_ctx, params.loan_amount)?;
+        params.loan_amount,
+        MintPurpose::TroveDebt { total_debt },
+    )?;
+    
+    if liquidation_reserve > 0 {
+        mint_stablecoin(
+            &ctx.accounts.stable_coin_mint,
+            &ctx.accounts.mint_authority,
+            ctx.bumps.mint_authority,
+            ctx.accounts.liquidation_reserve_vault.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            liquidation_reserve,
+            MintPurpose::TroveDebt { total_debt },
+        )?;
+        msg!("Liquidation reserve: {} aUSD", liquidation_reserve);
+    }
     
     // Distribute opening fee via CPI to aerospacer-fees
     if fee_amount > 0 {
+        let sp_balance_before = stability_pool_vault_balance(&ctx.accounts.stability_pool_token_account)?;
         let _net_amount = process_protocol_fee(
             params.loan_amount,
-            ctx.accounts.state.protocol_fee,
-            ctx.accounts.fees_program.to_account_info(),
-            ctx.accounts.user.to_account_info(),
-            ctx.accounts.fees_state.to_account_info(),
-            ctx.accounts.user_stablecoin_account.to_account_info(),
-            ctx.accounts.stability_pool_token_account.to_account_info(),
-            ctx.accounts.fee_address_1_token_account.to_account_info(),
-            ctx.accounts.fee_address_2_token_account.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
+            protocol_fee,
+            FeeSource::Borrow,
+            ProtocolFeeAccounts {
+                fees_program: ctx.accounts.fees_program.to_account_info(),
+                payer: ctx.accounts.user.to_account_info(),
+                fees_state: ctx.accounts.fees_state.to_account_info(),
+                fee_caller: ctx.accounts.fee_caller.to_account_info(),
+                fee_caller_bump: ctx.bumps.fee_caller,
+                payer_token_account: ctx.accounts.user_stablecoin_account.to_account_info(),
+                stability_pool_token_account: ctx.accounts.stability_pool_token_account.to_account_info(),
+                fee_vault: ctx.accounts.fee_vault.to_account_info(),
+                fee_epoch_stats: ctx.accounts.fee_epoch_stats.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            FeeAccumulatorAccounts::new(
+                ctx.accounts.fee_accumulator.as_deref_mut().map(|fee_accumulator| &mut **fee_accumulator),
+                ctx.accounts.fee_accumulator_vault.as_deref(),
+            )?,
+        )?;
+        accrue_routed_fee_yield(
+            &mut *ctx.accounts.state.load_mut()?,
+            &ctx.accounts.stability_pool_token_account,
+            sp_balance_before,
         )?;
         
         msg!("Opening fee collected and distributed: {} aUSD", fee_amount);
@@ -397,6 +595,35 @@ pub fn handler(ctx: Context<OpenTrove>, params: OpenTroveParams) -> Result<()> {
     msg!("Loan amount: {} aUSD (fee: {})", params.loan_amount, fee_amount);
     msg!("Collateral: {} {}", params.collateral_amount, params.collateral_denom);
     msg!("ICR: {}", result.new_icr);
+    if tag != UNTAGGED {
+        msg!("Tag: {:?}", tag);
+    }
+    
+    emit!(TroveOpened {
+        owner: ctx.accounts.user.key(),
+        trove_id: params.trove_id,
+        collateral_denom: params.collateral_denom.clone(),
+        collateral_amount: result.new_collateral_amount,
+        debt_amount: result.new_debt_amount,
+        icr: result.new_icr,
+        tag,
+        memo: params.memo,
+    });
+    
+    if let Some(trove_history) = ctx.accounts.trove_history.as_mut() {
+        trove_history.record(TroveHistoryEntry {
+            op: TroveHistoryOp::Open,
+            trove_id: params.trove_id,
+            amount: params.loan_amount,
+            icr: result.new_icr,
+            slot: Clock::get()?.slot,
+        });
+    }
+    
+    if let Some(user_index) = ctx.accounts.user_index.as_mut() {
+        user_index.add_trove_denom(&params.collateral_denom)?;
+        user_index.last_update_slot = Clock::get()?.slot;
+    }
     
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::events::MintAuthorityMigrationProposed;
+use crate::validation::{ValidateParams, validate_pubkey};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProposeMintAuthorityMigrationParams {
+    pub new_authority: Pubkey, // Receives the aUSD mint authority, e.g. the mint authority PDA of an upgraded program
+}
+
+impl ValidateParams for ProposeMintAuthorityMigrationParams {
+    fn validate(&self) -> Result<()> {
+        validate_pubkey(&self.new_authority)
+    }
+}
+
+#[derive(Accounts)]
+pub struct ProposeMintAuthorityMigration<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+}
+
+/// Handler for propose_mint_authority_migration instruction
+/// Starts the timelock; a new proposal replaces a pending one and restarts it
+pub fn handler(ctx: Context<ProposeMintAuthorityMigration>, params: ProposeMintAuthorityMigrationParams) -> Result<()> {
+    params.validate()?;
+
+    let mint_authority = &mut ctx.accounts.mint_authority;
+    require!(
+        mint_authority.migrated_to == Pubkey::default(),
+        AerospacerProtocolError::MintAuthorityNotAssigned
+    );
+    mint_authority.propose_migration(params.new_authority, Clock::get()?.unix_timestamp)?;
+
+    msg!("Mint authority migration proposed: {}", params.new_authority);
+    msg!("Executable from: {}", mint_authority.migration_eta);
+
+    emit!(MintAuthorityMigrationProposed {
+        new_authority: params.new_authority,
+        eta: mint_authority.migration_eta,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+use crate::trove_index::{entry_at, tombstone_entry};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PruneTroveIndexParams {
+    pub position: u64, // Index position of the entry to prune
+}
+
+#[derive(Accounts)]
+#[instruction(params: PruneTroveIndexParams)]
+pub struct PruneTroveIndex<'info> {
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"trove_index_page", TroveIndexPage::page_seed(params.position).as_ref()],
+        bump
+    )]
+    pub trove_index_page: Box<Account<'info, TroveIndexPage>>,
+
+    /// CHECK: UserDebtAmount PDA of the entry's trove - may be closed; verified in the handler
+    pub user_debt_amount: UncheckedAccount<'info>,
+}
+
+/// Permissionless keeper crank: tombstone a trove index entry whose trove is gone - liquidated,
+/// closed and reclaimed, or reopened at a newer position. Entries of Active, RedeemedClosed and
+/// LiquidatedWithSurplus troves are live and only close_trove removes them.
+pub fn handler(ctx: Context<PruneTroveIndex>, params: PruneTroveIndexParams) -> Result<()> {
+    let index_position = params.position
+        .checked_add(1)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    let entry = *entry_at(&mut ctx.accounts.trove_index_page, index_position)?;
+    require!(!entry.is_tombstone(), AerospacerProtocolError::InvalidTroveIndexPage);
+
+    // SECURITY: The trove checked must be the entry's own
+    let trove_seed = trove_id_seed(entry.trove_id);
+    let (expected_debt_pda, _bump) = Pubkey::find_program_address(
+        &UserDebtAmount::seeds(&entry.owner, &trove_seed),
+        &crate::ID,
+    );
+    require_keys_eq!(
+        ctx.accounts.user_debt_amount.key(),
+        expected_debt_pda,
+        AerospacerProtocolError::InvalidAddress
+    );
+
+    let debt_info = ctx.accounts.user_debt_amount.to_account_info();
+    let live = if debt_info.owner == &crate::ID && !debt_info.data_is_empty() {
+        let data = debt_info.try_borrow_data()?;
+        let debt = UserDebtAmount::try_deserialize(&mut &data[..])?;
+        debt.index_position == index_position
+            && matches!(debt.status, TroveStatus::Active | TroveStatus::RedeemedClosed | TroveStatus::LiquidatedWithSurplus)
+    } else {
+        false
+    };
+    require!(!live, AerospacerProtocolError::TroveIndexEntryLive);
+
+    tombstone_entry(
+        &mut ctx.accounts.trove_index_page,
+        &mut *ctx.accounts.state.load_mut()?,
+        index_position,
+        entry,
+    )?;
+
+    msg!("Trove index entry pruned successfully");
+    msg!("Keeper: {}", ctx.accounts.keeper.key());
+    msg!("Position: {} ({} #{})", params.position, entry.owner, entry.trove_id);
+
+    Ok(())
+}
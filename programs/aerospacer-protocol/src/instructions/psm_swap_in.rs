@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer};
+use crate::state::*;
+use crate::error::*;
+use crate::utils::*;
+use crate::trove_management::distribute_fee_yield_to_stakers;
+use crate::events::{PsmSwapDirection, PsmSwapped};
+use crate::mint_gate::{mint_stablecoin, MintPurpose};
+use crate::validation::{ValidateParams, validate_amount};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PsmSwapInParams {
+    pub reserve_amount: u64,   // USDC to deposit, in reserve base units
+}
+
+impl ValidateParams for PsmSwapInParams {
+    fn validate(&self) -> Result<()> {
+        validate_amount(self.reserve_amount)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: PsmSwapInParams)]
+pub struct PsmSwapIn<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"psm_config"],
+        bump
+    )]
+    pub psm_config: Account<'info, PsmConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"psm_vault"],
+        bump
+    )]
+    pub psm_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = user_reserve_account.owner == user.key() @ AerospacerProtocolError::Unauthorized,
+        constraint = user_reserve_account.mint == psm_config.reserve_mint @ AerospacerProtocolError::InvalidMint
+    )]
+    pub user_reserve_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = user_stablecoin_account.mint == stable_coin_mint.key() @ AerospacerProtocolError::InvalidMint
+    )]
+    pub user_stablecoin_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub stable_coin_mint: Account<'info, Mint>,
+    
+    // Protocol stablecoin vault PDA - recipient of the fee
+    #[account(
+        mut,
+        seeds = [b"protocol_stablecoin_vault"],
+        bump
+    )]
+    pub protocol_stablecoin_vault: Account<'info, TokenAccount>,
+    
+    // Dedicated aUSD mint authority PDA (see mint_gate)
+    #[account(
+        seeds = [b"mint_authority"],
+        bump
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Handler for psm_swap_in instruction
+/// Deposits USDC into the PSM vault and mints the same value of aUSD minus the swap-in spread
+pub fn handler(ctx: Context<PsmSwapIn>, params: PsmSwapInParams) -> Result<()> {
+    params.validate()?;
+    
+    require!(
+        params.reserve_amount <= ctx.accounts.user_reserve_account.amount,
+        AerospacerProtocolError::InsufficientCollateral
+    );
+    
+    let psm_config = &ctx.accounts.psm_config;
+    let gross_amount = psm_config.to_stablecoin(params.reserve_amount)?;
+    let fee = PsmConfig::fee(gross_amount, psm_config.swap_in_fee_bps)?;
+    let net_amount = gross_amount.saturating_sub(fee);
+    require!(net_amount > 0, AerospacerProtocolError::InvalidAmount);
+    
+    // The fee is minted too, so every aUSD minted here is backed by the reserve.
+    // mint_gate holds total_minted to the PSM debt ceiling.
+    let total_minted = safe_add(psm_config.total_minted, gross_amount)?;
+    let purpose = MintPurpose::Psm { total_minted, debt_ceiling: psm_config.debt_ceiling };
+    
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_reserve_account.to_account_info(),
+            to: ctx.accounts.psm_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    anchor_spl::token::transfer(transfer_ctx, params.reserve_amount)?;
+    
+    mint_stablecoin(
+        &ctx.accounts.stable_coin_mint,
+        &ctx.accounts.mint_authority,
+        ctx.bumps.mint_authority,
+        ctx.accounts.user_stablecoin_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        net_amount,
+        purpose,
+    )?;
+    
+    if fee > 0 {
+        mint_stablecoin(
+            &ctx.accounts.stable_coin_mint,
+            &ctx.accounts.mint_authority,
+            ctx.bumps.mint_authority,
+            ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            fee,
+            purpose,
+        )?;
+        distribute_fee_yield_to_stakers(&mut *ctx.accounts.state.load_mut()?, fee)?;
+    }
+    
+    ctx.accounts.psm_config.total_minted = total_minted;
+    
+    msg!("PSM swap in successful");
+    msg!("User: {}", ctx.accounts.user.key());
+    msg!("Reserve deposited: {}", params.reserve_amount);
+    msg!("aUSD minted: {} (fee: {})", net_amount, fee);
+    msg!("PSM total minted: {} / {}", total_minted, ctx.accounts.psm_config.debt_ceiling);
+    
+    emit!(PsmSwapped {
+        user: ctx.accounts.user.key(),
+        direction: PsmSwapDirection::SwapIn,
+        reserve_amount: params.reserve_amount,
+        stablecoin_amount: net_amount,
+        fee,
+        total_minted,
+    });
+    
+    Ok(())
+}
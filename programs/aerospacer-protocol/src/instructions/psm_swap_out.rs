@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Mint, Burn, Transfer};
+use crate::state::*;
+use crate::error::*;
+use crate::utils::*;
+use crate::trove_management::distribute_fee_yield_to_stakers;
+use crate::events::{PsmSwapDirection, PsmSwapped};
+use crate::validation::{ValidateParams, validate_amount};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PsmSwapOutParams {
+    pub stablecoin_amount: u64,   // aUSD to pay, fee included
+}
+
+impl ValidateParams for PsmSwapOutParams {
+    fn validate(&self) -> Result<()> {
+        validate_amount(self.stablecoin_amount)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: PsmSwapOutParams)]
+pub struct PsmSwapOut<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"psm_config"],
+        bump
+    )]
+    pub psm_config: Account<'info, PsmConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"psm_vault"],
+        bump
+    )]
+    pub psm_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: PSM reserve mint - validated against the PSM config
+    #[account(
+        constraint = reserve_mint.key() == psm_config.reserve_mint @ AerospacerProtocolError::InvalidMint
+    )]
+    pub reserve_mint: UncheckedAccount<'info>,
+    
+    // User's reserve token ATA, created when missing
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = reserve_mint,
+        associated_token::authority = user
+    )]
+    pub user_reserve_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = user_stablecoin_account.owner == user.key() @ AerospacerProtocolError::Unauthorized,
+        constraint = user_stablecoin_account.mint == stable_coin_mint.key() @ AerospacerProtocolError::InvalidMint
+    )]
+    pub user_stablecoin_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub stable_coin_mint: Account<'info, Mint>,
+    
+    // Protocol stablecoin vault PDA - recipient of the fee
+    #[account(
+        mut,
+        seeds = [b"protocol_stablecoin_vault"],
+        bump
+    )]
+    pub protocol_stablecoin_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for psm_swap_out instruction
+/// Burns aUSD (minus the swap-out spread) and releases the same value of USDC from the PSM vault.
+/// Sub-unit dust that cannot be paid in reserve base units is burned without payout.
+pub fn handler(ctx: Context<PsmSwapOut>, params: PsmSwapOutParams) -> Result<()> {
+    params.validate()?;
+    
+    require!(
+        params.stablecoin_amount <= ctx.accounts.user_stablecoin_account.amount,
+        AerospacerProtocolError::InsufficientCollateral
+    );
+    
+    let psm_config = &ctx.accounts.psm_config;
+    let fee = PsmConfig::fee(params.stablecoin_amount, psm_config.swap_out_fee_bps)?;
+    let burn_amount = params.stablecoin_amount.saturating_sub(fee);
+    let reserve_amount = psm_config.to_reserve(burn_amount)?;
+    require!(reserve_amount > 0, AerospacerProtocolError::InvalidAmount);
+    require!(
+        reserve_amount <= ctx.accounts.psm_vault.amount,
+        AerospacerProtocolError::PsmInsufficientReserve
+    );
+    
+    // Only the backing actually released leaves total_minted, keeping it equal to the vault
+    let total_minted = safe_sub(psm_config.total_minted, psm_config.to_stablecoin(reserve_amount)?)
+        .map_err(|_| AerospacerProtocolError::PsmInsufficientReserve)?;
+    
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.stable_coin_mint.to_account_info(),
+            from: ctx.accounts.user_stablecoin_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    anchor_spl::token::burn(burn_ctx, burn_amount)?;
+    
+    if fee > 0 {
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_stablecoin_account.to_account_info(),
+                to: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        );
+        anchor_spl::token::transfer(transfer_ctx, fee)?;
+        distribute_fee_yield_to_stakers(&mut *ctx.accounts.state.load_mut()?, fee)?;
+    }
+    
+    let vault_seeds: &[&[u8]] = &[b"psm_vault", &[ctx.bumps.psm_vault]];
+    let signer: &[&[&[u8]]] = &[vault_seeds];
+    
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.psm_vault.to_account_info(),
+            to: ctx.accounts.user_reserve_account.to_account_info(),
+            authority: ctx.accounts.psm_vault.to_account_info(),
+        },
+        signer,
+    );
+    anchor_spl::token::transfer(transfer_ctx, reserve_amount)?;
+    
+    ctx.accounts.psm_config.total_minted = total_minted;
+    
+    msg!("PSM swap out successful");
+    msg!("User: {}", ctx.accounts.user.key());
+    msg!("aUSD paid: {} (fee: {})", params.stablecoin_amount, fee);
+    msg!("Reserve withdrawn: {}", reserve_amount);
+    msg!("PSM total minted: {}", total_minted);
+    
+    emit!(PsmSwapped {
+        user: ctx.accounts.user.key(),
+        direction: PsmSwapDirection::SwapOut,
+        reserve_amount,
+        stablecoin_amount: params.stablecoin_amount,
+        fee,
+        total_minted,
+    });
+    
+    Ok(())
+}
@@ -1,14 +1,24 @@
 use anchor_lang::prelude::*;
-use crate::error::*;
 use crate::sorted_troves::get_liquidatable_troves;
+use crate::validation::{ValidateParams, validate_amount, validate_list_len};
+
+// Limit results to avoid huge return data
+const MAX_QUERY_RESULTS: usize = 50;
 
 /// Query parameters for finding liquidatable troves
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct QueryLiquidatableTrovesParams {
-    pub liquidation_threshold: u64, // ICR threshold (typically 110 for 110%)
+    pub liquidation_threshold: u64, // ICR threshold in micro-percent (the denom's CollateralConfig, typically 110_000_000)
     pub max_troves: u8, // Limit results to avoid huge responses (default 50)
 }
 
+impl ValidateParams for QueryLiquidatableTrovesParams {
+    fn validate(&self) -> Result<()> {
+        validate_amount(self.liquidation_threshold)?;
+        validate_list_len(self.max_troves as usize, MAX_QUERY_RESULTS)
+    }
+}
+
 /// Query context - read-only, no mutations
 #[derive(Accounts)]
 pub struct QueryLiquidatableTroves {
@@ -38,15 +48,7 @@ pub struct QueryLiquidatableTroves {
 /// Vec<Pubkey> of liquidatable trove owners via AnchorSerialize return data
 pub fn handler(ctx: Context<QueryLiquidatableTroves>, params: QueryLiquidatableTrovesParams) -> Result<()> {
     // Validate parameters
-    require!(
-        params.liquidation_threshold > 0,
-        AerospacerProtocolError::InvalidAmount
-    );
-    
-    require!(
-        params.max_troves > 0 && params.max_troves <= 50,
-        AerospacerProtocolError::InvalidList
-    );
+    params.validate()?;
     
     msg!("Querying liquidatable troves with threshold: {}%", params.liquidation_threshold);
     msg!("Max troves to return: {}", params.max_troves);
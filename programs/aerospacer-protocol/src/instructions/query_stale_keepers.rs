@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+use crate::validation::{ValidateParams, validate_amount};
+
+/// Query parameters for finding stale keepers
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct QueryStaleKeepersParams {
+    pub max_age_slots: u64, // Keepers silent for longer than this are stale
+}
+
+impl ValidateParams for QueryStaleKeepersParams {
+    fn validate(&self) -> Result<()> {
+        validate_amount(self.max_age_slots)
+    }
+}
+
+/// Query context - read-only, no mutations
+#[derive(Accounts)]
+#[instruction(params: QueryStaleKeepersParams)]
+pub struct QueryStaleKeepers<'info> {
+    pub clock: Sysvar<'info, Clock>,
+    
+    // remaining_accounts: KeeperHeartbeat PDAs of the keeper set to check
+}
+
+/// Handler for query_stale_keepers instruction
+/// Returns stale keepers via Anchor return data (set_return_data)
+/// 
+/// # Security
+/// Every account is verified to be a program-owned KeeperHeartbeat at its canonical PDA,
+/// so a copied account cannot make a dead keeper look alive
+/// 
+/// # Returns
+/// Vec<Pubkey> of keepers whose last heartbeat is older than max_age_slots
+pub fn handler(ctx: Context<QueryStaleKeepers>, params: QueryStaleKeepersParams) -> Result<()> {
+    params.validate()?;
+    
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() <= MAX_STALE_KEEPER_QUERY,
+        AerospacerProtocolError::InvalidList
+    );
+    
+    let current_slot = ctx.accounts.clock.slot;
+    let mut stale: Vec<Pubkey> = Vec::new();
+    
+    for account_info in ctx.remaining_accounts.iter() {
+        require!(
+            account_info.owner == &crate::ID,
+            AerospacerProtocolError::Unauthorized
+        );
+        let heartbeat = {
+            let data = account_info.try_borrow_data()?;
+            KeeperHeartbeat::try_deserialize(&mut &data[..])?
+        };
+        let (expected_pda, _bump) = Pubkey::find_program_address(
+            &KeeperHeartbeat::seeds(&heartbeat.keeper),
+            &crate::ID,
+        );
+        require!(
+            expected_pda == *account_info.key,
+            AerospacerProtocolError::InvalidList
+        );
+        
+        let age = heartbeat.age(current_slot);
+        if age > params.max_age_slots {
+            msg!("Stale keeper {} - last seen {} slots ago", heartbeat.keeper, age);
+            stale.push(heartbeat.keeper);
+        }
+    }
+    
+    msg!("Checked {} keepers, {} stale", ctx.remaining_accounts.len(), stale.len());
+    
+    // Return data via Anchor's set_return_data
+    // Clients can decode this as Vec<Pubkey>
+    anchor_lang::solana_program::program::set_return_data(&stale.try_to_vec()?);
+    
+    Ok(())
+}
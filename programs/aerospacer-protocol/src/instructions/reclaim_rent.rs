@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::error::*;
+use crate::utils::close_program_account;
+use crate::events::RentReclaimed;
+
+#[derive(Accounts)]
+pub struct ReclaimRent<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: The user's stake PDA, which may not exist. Collateral gain snapshots are only
+    /// reclaimable while it holds no deposit, since they stop accruing only then.
+    #[account(
+        seeds = [b"user_stake_amount", user.key().as_ref()],
+        bump
+    )]
+    pub user_stake_amount: UncheckedAccount<'info>,
+
+    /// Trove id registry - reclaimed trove ids are released when passed
+    #[account(
+        mut,
+        seeds = [b"trove_registry", user.key().as_ref()],
+        bump
+    )]
+    pub trove_registry: Option<Account<'info, TroveRegistry>>,
+
+    // remaining_accounts: the user's PDAs to close (all writable), in any order. Each must be one of
+    // UserDebtAmount (trove closed or liquidated), UserCollateralAmount (zero amount),
+    // LiquidityThreshold (zero ratio), UserStakeAmount (no deposit and nothing pending) or
+    // UserCollateralSnapshot (nothing pending, no deposit).
+}
+
+/// Check that `account_info` is a zero-balance PDA of `user`, returning its kind
+fn check_reclaimable(
+    account_info: &AccountInfo,
+    user: &Pubkey,
+    stake_is_empty: bool,
+    trove_registry: &mut Option<Account<TroveRegistry>>,
+) -> Result<&'static str> {
+    let data = account_info.try_borrow_data()?;
+    require!(data.len() >= 8, AerospacerProtocolError::AccountNotReclaimable);
+    let discriminator = &data[..8];
+
+    let (kind, expected_pda) = if discriminator == UserDebtAmount::DISCRIMINATOR {
+        let debt = UserDebtAmount::try_deserialize(&mut &data[..])?;
+        require!(
+            debt.owner == *user
+                && debt.amount == 0
+                && !matches!(debt.status, TroveStatus::Active | TroveStatus::RedeemedClosed | TroveStatus::LiquidatedWithSurplus),
+            AerospacerProtocolError::AccountNotReclaimable
+        );
+        if let Some(trove_registry) = trove_registry.as_mut() {
+            trove_registry.unregister(debt.trove_id);
+        }
+        let trove_seed = trove_id_seed(debt.trove_id);
+        ("UserDebtAmount", Pubkey::find_program_address(&UserDebtAmount::seeds(user, &trove_seed), &crate::ID).0)
+    } else if discriminator == UserCollateralAmount::DISCRIMINATOR {
+        let collateral = UserCollateralAmount::try_deserialize(&mut &data[..])?;
+        require!(
+            collateral.owner == *user && collateral.amount == 0,
+            AerospacerProtocolError::AccountNotReclaimable
+        );
+        let trove_seed = trove_id_seed(collateral.trove_id);
+        (
+            "UserCollateralAmount",
+            Pubkey::find_program_address(&UserCollateralAmount::seeds(user, &collateral.denom, &trove_seed), &crate::ID).0,
+        )
+    } else if discriminator == LiquidityThreshold::DISCRIMINATOR {
+        let threshold = LiquidityThreshold::try_deserialize(&mut &data[..])?;
+        require!(
+            threshold.owner == *user && threshold.ratio == 0,
+            AerospacerProtocolError::AccountNotReclaimable
+        );
+        let trove_seed = trove_id_seed(threshold.trove_id);
+        ("LiquidityThreshold", Pubkey::find_program_address(&LiquidityThreshold::seeds(user, &trove_seed), &crate::ID).0)
+    } else if discriminator == UserStakeAmount::DISCRIMINATOR {
+        let stake = UserStakeAmount::try_deserialize(&mut &data[..])?;
+        require!(
+            stake.owner == *user
+                && stake.amount == 0
+                && stake.pending_fee_gain == 0
+                && stake.pending_reward_gain == 0,
+            AerospacerProtocolError::AccountNotReclaimable
+        );
+        ("UserStakeAmount", Pubkey::find_program_address(&UserStakeAmount::seeds(user), &crate::ID).0)
+    } else if discriminator == UserCollateralSnapshot::DISCRIMINATOR {
+        let snapshot = UserCollateralSnapshot::try_deserialize(&mut &data[..])?;
+        require!(
+            snapshot.owner == *user && snapshot.pending_collateral_gain == 0 && stake_is_empty,
+            AerospacerProtocolError::AccountNotReclaimable
+        );
+        (
+            "UserCollateralSnapshot",
+            Pubkey::find_program_address(&UserCollateralSnapshot::seeds(user, &snapshot.denom), &crate::ID).0,
+        )
+    } else {
+        return err!(AerospacerProtocolError::AccountNotReclaimable);
+    };
+
+    // SECURITY: Must be the canonical PDA, not a copy of one
+    require!(
+        expected_pda == *account_info.key,
+        AerospacerProtocolError::AccountNotReclaimable
+    );
+
+    Ok(kind)
+}
+
+/// Handler for reclaim_rent instruction
+/// Closes zero-balance user PDAs left behind by liquidations, redemptions and withdrawals and
+/// refunds their rent to the user
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ReclaimRent<'info>>) -> Result<()> {
+    require!(
+        !ctx.remaining_accounts.is_empty(),
+        AerospacerProtocolError::InvalidList
+    );
+
+    let user = ctx.accounts.user.key();
+    let stake_is_empty = {
+        let stake_info = &ctx.accounts.user_stake_amount;
+        if stake_info.data_is_empty() {
+            true
+        } else {
+            let data = stake_info.try_borrow_data()?;
+            UserStakeAmount::try_deserialize(&mut &data[..])?.amount == 0
+        }
+    };
+
+    let mut lamports = 0u64;
+    for account_info in ctx.remaining_accounts.iter() {
+        require!(
+            account_info.owner == &crate::ID && account_info.is_writable,
+            AerospacerProtocolError::AccountNotReclaimable
+        );
+        let kind = check_reclaimable(account_info, &user, stake_is_empty, &mut ctx.accounts.trove_registry)?;
+
+        let refund = account_info.lamports();
+        close_program_account(account_info, &ctx.accounts.user.to_account_info())?;
+        lamports = lamports
+            .checked_add(refund)
+            .ok_or(AerospacerProtocolError::OverflowError)?;
+
+        msg!("Closed {} {} ({} lamports)", kind, account_info.key(), refund);
+    }
+
+    msg!("Rent reclaimed successfully");
+    msg!("User: {}", user);
+    msg!("Accounts closed: {}", ctx.remaining_accounts.len());
+    msg!("Lamports refunded: {}", lamports);
+
+    emit!(RentReclaimed {
+        owner: user,
+        accounts_closed: ctx.remaining_accounts.len() as u32,
+        lamports,
+    });
+
+    Ok(())
+}
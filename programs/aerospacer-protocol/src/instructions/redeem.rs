@@ -1,16 +1,34 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer, Burn};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, Burn};
 use crate::state::*;
 use crate::error::*;
 use crate::fees_integration::*;
+use crate::oracle::*;
+use crate::events::PriorityRedemption;
+use crate::liquidation_reserve::burn_liquidation_reserve;
+use crate::trove_management::{RedemptionLimits, RedemptionResult};
+use crate::utils::{calculate_gross_share, calculate_net_amount_after_fee, create_program_pda, is_redeemable_icr};
+use crate::validation::{ValidateParams, validate_min_amount, validate_denom};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct RedeemParams {
     pub amount: u64, // Equivalent to Uint256
     pub collateral_denom: String, // Which collateral to redeem (SOL, ETH, BTC, etc.)
+    pub allow_partial: bool, // Settle whatever the provided troves can fill and return the rest
+    pub open_ticket: bool, // Escrow the unfilled rest in a RedemptionTicket for continue_redemption
+    pub max_troves: u8, // Redeem from at most this many troves, 0 = no cap
+    pub max_icr: u64, // Stop at the first trove above this live ICR (micro-percent), 0 = no cap
     // NOTE: prev_node_id and next_node_id removed - using off-chain sorted list architecture
 }
 
+impl ValidateParams for RedeemParams {
+    fn validate(&self) -> Result<()> {
+        validate_min_amount(self.amount, MINIMUM_LOAN_AMOUNT, AerospacerProtocolError::InvalidAmount)?;
+        validate_denom(&self.collateral_denom)
+    }
+}
+
 #[derive(Accounts)]
 #[instruction(params: RedeemParams)]
 pub struct Redeem<'info> {
@@ -18,11 +36,11 @@ pub struct Redeem<'info> {
     pub user: Signer<'info>,
 
     #[account(mut)]
-    pub state: Box<Account<'info, StateAccount>>,
+    pub state: AccountLoader<'info, StateAccount>,
 
     #[account(
         mut,
-        seeds = [b"user_debt_amount", user.key().as_ref()],
+        seeds = [b"user_debt_amount", user.key().as_ref(), trove_id_seed(0).as_slice()],
         bump,
         constraint = user_debt_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
     )]
@@ -30,7 +48,7 @@ pub struct Redeem<'info> {
 
     #[account(
         mut,
-        seeds = [b"liquidity_threshold", user.key().as_ref()],
+        seeds = [b"liquidity_threshold", user.key().as_ref(), trove_id_seed(0).as_slice()],
         bump,
         constraint = liquidity_threshold.owner == user.key() @ AerospacerProtocolError::Unauthorized
     )]
@@ -44,15 +62,21 @@ pub struct Redeem<'info> {
 
     #[account(
         mut,
-        seeds = [b"user_collateral_amount", user.key().as_ref(), params.collateral_denom.as_bytes()],
+        seeds = [b"user_collateral_amount", user.key().as_ref(), trove_id_seed(0).as_slice(), params.collateral_denom.as_bytes()],
         bump,
         constraint = user_collateral_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
     )]
     pub user_collateral_amount: Box<Account<'info, UserCollateralAmount>>,
 
+    /// Collateral mint of the denom - the vault transfer rejects any other
+    pub collateral_mint: Box<Account<'info, Mint>>,
+
+    // Redeemer's collateral ATA, created when missing
     #[account(
-        mut,
-        constraint = user_collateral_account.owner == user.key() @ AerospacerProtocolError::Unauthorized
+        init_if_needed,
+        payer = user,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = user
     )]
     pub user_collateral_account: Box<Account<'info, TokenAccount>>,
 
@@ -75,44 +99,51 @@ pub struct Redeem<'info> {
     /// CHECK: This is the stable coin mint account
     #[account(
         mut,
-        constraint = stable_coin_mint.key() == state.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
     )]
     pub stable_coin_mint: UncheckedAccount<'info>,
 
-    /// CHECK: Per-denom collateral total PDA
+    /// CHECK: Liquidation reserve vault - reserves of fully redeemed troves are burned from it
+    #[account(
+        mut,
+        seeds = [b"liquidation_reserve_vault"],
+        bump
+    )]
+    pub liquidation_reserve_vault: UncheckedAccount<'info>,
+
     #[account(
         mut,
         seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
         bump
     )]
-    pub total_collateral_amount: AccountInfo<'info>,
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
 
     // Oracle context - integration with our aerospacer-oracle
     /// CHECK: Our oracle program - validated against state
     #[account(
         mut,
-        constraint = oracle_program.key() == state.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
+        constraint = oracle_program.key() == state.load()?.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
     )]
     pub oracle_program: AccountInfo<'info>,
     
     /// CHECK: Oracle state account - validated against state
     #[account(
         mut,
-        constraint = oracle_state.key() == state.oracle_state_addr @ AerospacerProtocolError::Unauthorized
+        constraint = oracle_state.key() == state.load()?.oracle_state_addr @ AerospacerProtocolError::Unauthorized
     )]
     pub oracle_state: AccountInfo<'info>,
 
     // Fee distribution accounts
     /// CHECK: Fees program - validated against state
     #[account(
-        constraint = fees_program.key() == state.fee_distributor_addr @ AerospacerProtocolError::Unauthorized
+        constraint = fees_program.key() == state.load()?.fee_distributor_addr @ AerospacerProtocolError::Unauthorized
     )]
     pub fees_program: AccountInfo<'info>,
     
     /// CHECK: Fees state account - validated against state
     #[account(
         mut,
-        constraint = fees_state.key() == state.fee_state_addr @ AerospacerProtocolError::Unauthorized
+        constraint = fees_state.key() == state.load()?.fee_state_addr @ AerospacerProtocolError::Unauthorized
     )]
     pub fees_state: AccountInfo<'info>,
     
@@ -120,38 +151,109 @@ pub struct Redeem<'info> {
     #[account(mut)]
     pub stability_pool_token_account: AccountInfo<'info>,
     
-    /// CHECK: Fee address 1 token account
+    /// CHECK: Protocol's fee caller PDA - signs the distribute_fee CPI, allowlisted in aerospacer-fees
+    #[account(
+        seeds = [b"fee_caller"],
+        bump
+    )]
+    pub fee_caller: UncheckedAccount<'info>,
+    
+    /// CHECK: aerospacer-fees fee vault - checked by the fees program
     #[account(mut)]
-    pub fee_address_1_token_account: AccountInfo<'info>,
+    pub fee_vault: AccountInfo<'info>,
     
-    /// CHECK: Fee address 2 token account
+    /// CHECK: aerospacer-fees per-epoch fee stats - checked by the fees program
     #[account(mut)]
-    pub fee_address_2_token_account: AccountInfo<'info>,
+    pub fee_epoch_stats: AccountInfo<'info>,
+
+    /// Dust fee accumulator - optional; a fee below its threshold is parked instead of distributed
+    #[account(
+        mut,
+        seeds = [b"fee_accumulator"],
+        bump
+    )]
+    pub fee_accumulator: Option<Box<Account<'info, FeeAccumulator>>>,
+    
+    /// CHECK: Fee accumulator vault PDA - required with the fee accumulator
+    #[account(
+        mut,
+        seeds = [b"fee_accumulator_vault"],
+        bump
+    )]
+    pub fee_accumulator_vault: Option<UncheckedAccount<'info>>,
 
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// Clock sysvar for the oracle price read
+    pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: Pyth price account of the redeemed denom - bound to the denom by the oracle.
+    /// Troves are ordered by their live ICR at this price; omit only while
+    /// state.redemption_stored_icr_fallback allows ordering by stored ICRs.
+    pub pyth_price_account: Option<AccountInfo<'info>>,
+
+    /// CHECK: Ticket the unfilled rest is escrowed on - required with params.open_ticket. Created
+    /// in the handler only once something is escrowed, so a full fill leaves no empty ticket behind.
+    #[account(
+        mut,
+        seeds = [b"redemption_ticket", user.key().as_ref(), params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub redemption_ticket: Option<UncheckedAccount<'info>>,
+
+    // Holds the aUSD escrowed on every redemption ticket - required with params.open_ticket
+    #[account(
+        init_if_needed,
+        payer = user,
+        token::mint = stable_coin_mint,
+        token::authority = redemption_escrow_vault,
+        seeds = [b"redemption_escrow_vault"],
+        bump
+    )]
+    pub redemption_escrow_vault: Option<Box<Account<'info, TokenAccount>>>,
 }
 
-pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, Redeem<'info>>, params: RedeemParams) -> Result<()> {
     // PRODUCTION VALIDATION: Input parameter checks
-    require!(
-        params.amount > 0,
-        AerospacerProtocolError::InvalidAmount
-    );
+    params.validate()?;
     
-    require!(
-        params.amount >= MINIMUM_LOAN_AMOUNT,
-        AerospacerProtocolError::InvalidAmount
-    );
-    
-    require!(
-        !params.collateral_denom.is_empty(),
-        AerospacerProtocolError::InvalidAmount
-    );
+    // GRIEFING PROTECTION: dust redemptions spread over many troves would churn their
+    // accounts and force needless ICR re-sorting off-chain
+    {
+        let state = ctx.accounts.state.load()?;
+        // Like Liquity, no redemptions until collateral has had time to accumulate after launch
+        require!(
+            !state.in_bootstrap_period(Clock::get()?.unix_timestamp),
+            AerospacerProtocolError::BootstrapPeriodRedemption
+        );
+        require!(
+            params.amount >= state.min_redemption_amount,
+            AerospacerProtocolError::RedemptionBelowMinimum
+        );
+        require!(
+            state.max_redemption_troves == 0
+                || ctx.remaining_accounts.len() / 4 <= state.max_redemption_troves as usize,
+            AerospacerProtocolError::TooManyRedemptionTroves
+        );
+    }
     
     // Store protocol fee before creating mutable borrow
-    let protocol_fee = ctx.accounts.state.protocol_fee;
+    let protocol_fee = ctx.accounts.state.load()?.protocol_fee;
+    
+    // LIVE ICR: order troves by their ICR at the current price rather than the stored
+    // LiquidityThreshold ratios, which go stale as the price moves
+    let live_price = load_redemption_price(
+        &ctx.accounts.state,
+        &ctx.accounts.oracle_program,
+        &ctx.accounts.oracle_state,
+        ctx.accounts.pyth_price_account.as_ref(),
+        ctx.accounts.clock.to_account_info(),
+        &params.collateral_denom,
+    )?;
     
-    let state = &mut ctx.accounts.state;
+    let mut state = ctx.accounts.state.load_mut()?;
     
     // Validate redemption amount against total system debt
     require!(
@@ -168,26 +270,161 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
         AerospacerProtocolError::InvalidAmount
     );
     
-    // Collect redemption fee via CPI to aerospacer-fees
-    // This returns the net amount after fee deduction
-    let net_redemption_amount = process_protocol_fee(
-        params.amount,
-        protocol_fee,
-        ctx.accounts.fees_program.to_account_info(),
-        ctx.accounts.user.to_account_info(),
-        ctx.accounts.fees_state.to_account_info(),
-        ctx.accounts.user_stablecoin_account.to_account_info(),
-        ctx.accounts.stability_pool_token_account.to_account_info(),
-        ctx.accounts.fee_address_1_token_account.to_account_info(),
-        ctx.accounts.fee_address_2_token_account.to_account_info(),
-        ctx.accounts.token_program.to_account_info(),
+    // PRIORITY LANE: while governance has flagged a de-peg emergency, the designated
+    // treasury redeems without the redemption fee to defend the peg
+    let priority_lane = state.emergency_redemption_mode != 0
+        && state.treasury_addr != Pubkey::default()
+        && ctx.accounts.user.key() == state.treasury_addr;
+    
+    // What the full amount redeems after its fee; the fee itself is charged once the fill is known
+    let net_redemption_amount = if priority_lane {
+        msg!("Priority redemption lane: fee waived for treasury {}", state.treasury_addr);
+        params.amount
+    } else {
+        calculate_net_amount_after_fee(params.amount, protocol_fee)?
+    };
+    msg!("Net redemption amount: {} aUSD", net_redemption_amount);
+    
+    // NEW ARCHITECTURE: Core redemption logic using pre-sorted list from remainingAccounts
+    // Client provides sorted target troves via remainingAccounts (sorted from riskiest to safest)
+    let RedemptionFill {
+        remaining_amount,
+        collateral_sent: total_collateral_sent,
+        troves_redeemed,
+        troves_closed,
+        reserves_burned,
+        limit_reached,
+        troves_skipped_undercollateralized,
+    } = fill_redemption(
+        &mut state,
+        &RedemptionVaults {
+            total_collateral_amount: &ctx.accounts.total_collateral_amount,
+            protocol_collateral_vault: ctx.accounts.protocol_collateral_vault.to_account_info(),
+            protocol_collateral_vault_bump: ctx.bumps.protocol_collateral_vault,
+            redeemer_collateral_account: ctx.accounts.user_collateral_account.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+        ctx.remaining_accounts,
+        &params.collateral_denom,
+        live_price.as_ref(),
+        net_redemption_amount,
+        RedemptionLimits {
+            max_troves: params.max_troves,
+            max_icr: params.max_icr,
+        },
     )?;
     
-    let fee_amount = params.amount.saturating_sub(net_redemption_amount);
+    // Unless a partial fill or a ticket was requested, or the redeemer's own limits ended the
+    // fill, the FULL redemption amount must be processed, otherwise revert the entire transaction
+    require!(
+        remaining_amount == 0 || params.allow_partial || params.open_ticket || limit_reached,
+        AerospacerProtocolError::InsufficientCollateral // Not enough troves with requested collateral type
+    );
+    
+    // Only the filled part is taken from the user and burned; the unfilled remainder stays in
+    // their account unless a ticket escrows it
+    let filled_amount = net_redemption_amount.saturating_sub(remaining_amount);
+    require!(
+        filled_amount > 0,
+        AerospacerProtocolError::InsufficientCollateral
+    );
+    
+    // FEE: charged on the share of the requested amount that was filled; an escrowed rest pays
+    // its share as continue_redemption fills it
+    let filled_gross = calculate_gross_share(params.amount, filled_amount, net_redemption_amount)?;
+    let fee_amount = if priority_lane {
+        0
+    } else {
+        // Collect redemption fee via CPI to aerospacer-fees
+        // This returns the net amount after fee deduction
+        let sp_balance_before = stability_pool_vault_balance(&ctx.accounts.stability_pool_token_account)?;
+        let net_amount = process_protocol_fee(
+            filled_gross,
+            protocol_fee,
+            FeeSource::Redemption,
+            ProtocolFeeAccounts {
+                fees_program: ctx.accounts.fees_program.to_account_info(),
+                payer: ctx.accounts.user.to_account_info(),
+                fees_state: ctx.accounts.fees_state.to_account_info(),
+                fee_caller: ctx.accounts.fee_caller.to_account_info(),
+                fee_caller_bump: ctx.bumps.fee_caller,
+                payer_token_account: ctx.accounts.user_stablecoin_account.to_account_info(),
+                stability_pool_token_account: ctx.accounts.stability_pool_token_account.to_account_info(),
+                fee_vault: ctx.accounts.fee_vault.to_account_info(),
+                fee_epoch_stats: ctx.accounts.fee_epoch_stats.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            FeeAccumulatorAccounts::new(
+                ctx.accounts.fee_accumulator.as_deref_mut().map(|fee_accumulator| &mut **fee_accumulator),
+                ctx.accounts.fee_accumulator_vault.as_deref(),
+            )?,
+        )?;
+        accrue_routed_fee_yield(
+            &mut state,
+            &ctx.accounts.stability_pool_token_account,
+            sp_balance_before,
+        )?;
+        filled_gross.saturating_sub(net_amount)
+    };
     msg!("Redemption fee: {} aUSD ({}%)", fee_amount, protocol_fee);
-    msg!("Net redemption amount: {} aUSD", net_redemption_amount);
     
-    // Transfer NET redemption amount from user to protocol (after fee deduction)
+    // RESUMABLE: with a ticket the unfilled rest is escrowed for continue_redemption instead
+    let escrowed_amount = if params.open_ticket { remaining_amount } else { 0 };
+    let escrowed_gross = if escrowed_amount > 0 { params.amount - filled_gross } else { 0 };
+    
+    // THROTTLE: cap redemption volume per window so a whale cannot drain every low-ICR trove
+    // during a brief oracle dislocation. Only the filled and the escrowed volume count; the
+    // escrowed part is given back if its ticket is cancelled. The treasury's priority lane
+    // defends the peg and is exempt.
+    let throttled = !priority_lane && state.redemption_cap_per_window != 0;
+    if throttled {
+        state.record_redemption_volume(filled_gross + escrowed_gross, Clock::get()?.slot)?;
+    }
+    
+    if escrowed_amount > 0 {
+        let (Some(ticket_info), Some(ticket_bump), Some(escrow_vault)) = (
+            ctx.accounts.redemption_ticket.as_ref(),
+            ctx.bumps.redemption_ticket,
+            ctx.accounts.redemption_escrow_vault.as_deref(),
+        ) else {
+            return err!(AerospacerProtocolError::RedemptionTicketRequired);
+        };
+        let ticket_info = ticket_info.to_account_info();
+        let mut ticket = load_or_create_redemption_ticket(
+            &ticket_info,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &params.collateral_denom,
+            ticket_bump,
+        )?;
+        
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_stablecoin_account.to_account_info(),
+                to: escrow_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        );
+        anchor_spl::token::transfer(transfer_ctx, escrowed_amount)?;
+        
+        ticket.escrow(escrowed_amount, escrowed_gross)?;
+        if throttled {
+            ticket.reserve_volume(escrowed_gross, state.redemption_window_start_slot)?;
+        }
+        {
+            let mut data = ticket_info.try_borrow_mut_data()?;
+            let mut writer: &mut [u8] = &mut data;
+            ticket.try_serialize(&mut writer)?;
+        }
+        msg!("Partial fill: {} of {} aUSD redeemed, {} aUSD escrowed on the redemption ticket ({} pending)",
+             filled_amount, net_redemption_amount, escrowed_amount, ticket.remaining);
+    } else if remaining_amount > 0 {
+        msg!("Partial fill: {} of {} aUSD redeemed, {} aUSD left with the user",
+             filled_amount, net_redemption_amount, remaining_amount);
+    }
+    
+    // Transfer the filled NET redemption amount from user to protocol (after fee deduction)
     let transfer_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
         Transfer {
@@ -196,9 +433,9 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
             authority: ctx.accounts.user.to_account_info(),
         },
     );
-    anchor_spl::token::transfer(transfer_ctx, net_redemption_amount)?;
+    anchor_spl::token::transfer(transfer_ctx, filled_amount)?;
 
-    // Burn NET redemption amount (not including fee)
+    // Burn the filled NET redemption amount (not including fee)
     // Use invoke_signed for PDA authority
     let burn_seeds = &[
         b"protocol_stablecoin_vault".as_ref(),
@@ -215,32 +452,153 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
         },
         burn_signer,
     );
-    anchor_spl::token::burn(burn_ctx, net_redemption_amount)?;
+    anchor_spl::token::burn(burn_ctx, filled_amount)?;
+    
+    burn_liquidation_reserve(
+        &ctx.accounts.liquidation_reserve_vault.to_account_info(),
+        ctx.bumps.liquidation_reserve_vault,
+        &ctx.accounts.stable_coin_mint.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        reserves_burned,
+    )?;
+    
+    // PRODUCTION SAFETY: Update global state with the net redeemed amount and the burned reserves
+    state.total_debt_amount = state.total_debt_amount
+        .checked_sub(filled_amount)
+        .and_then(|debt| debt.checked_sub(reserves_burned))
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    
+    msg!("Redeemed successfully");
+    msg!("User: {}", ctx.accounts.user.key());
+    msg!("Gross amount: {} aUSD", params.amount);
+    msg!("Fee: {} aUSD ({}%)", fee_amount, protocol_fee);
+    msg!("Net redemption: {} aUSD", filled_amount);
+    msg!("Collateral sent: {} {}", total_collateral_sent, params.collateral_denom);
+    msg!("Troves redeemed: {}", troves_redeemed);
+    msg!("Undercollateralized troves skipped: {}", troves_skipped_undercollateralized);
+    msg!("Remaining amount: {} aUSD", remaining_amount);
+    
+    if priority_lane {
+        emit!(PriorityRedemption {
+            treasury: ctx.accounts.user.key(),
+            collateral_denom: params.collateral_denom.clone(),
+            amount: filled_amount,
+            collateral_sent: total_collateral_sent,
+            troves_redeemed,
+        });
+    }
+    
+    // Return data via Anchor's set_return_data
+    // CPI callers can decode this as RedemptionResult
+    let result = RedemptionResult {
+        collateral_denom: params.collateral_denom,
+        gross_amount: params.amount,
+        fee_amount,
+        redeemed_amount: filled_amount,
+        unfilled_amount: remaining_amount,
+        collateral_received: total_collateral_sent,
+        troves_redeemed,
+        troves_closed,
+        escrowed_amount,
+        limit_reached,
+        troves_skipped_undercollateralized,
+    };
+    anchor_lang::solana_program::program::set_return_data(&result.try_to_vec()?);
 
-    // NEW ARCHITECTURE: Core redemption logic using pre-sorted list from remainingAccounts
-    // Client provides sorted target troves via remainingAccounts (sorted from riskiest to safest)
-    // Each trove has 4 accounts: UserDebtAmount, UserCollateralAmount, LiquidityThreshold, TokenAccount
+    Ok(())
+}
+
+/// Accounts a redemption fill moves collateral through
+pub(crate) struct RedemptionVaults<'a, 'info> {
+    pub total_collateral_amount: &'a AccountLoader<'info, TotalCollateralAmount>,
+    pub protocol_collateral_vault: AccountInfo<'info>,
+    pub protocol_collateral_vault_bump: u8,
+    pub redeemer_collateral_account: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+/// The redeemer's ticket for `denom`, created on the first escrow
+fn load_or_create_redemption_ticket<'info>(
+    ticket_info: &AccountInfo<'info>,
+    user: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    denom: &str,
+    bump: u8,
+) -> Result<RedemptionTicket> {
+    if ticket_info.owner == &crate::ID {
+        let data = ticket_info.try_borrow_data()?;
+        return RedemptionTicket::try_deserialize(&mut &data[..]);
+    }
     
-    let mut remaining_amount = net_redemption_amount;
+    create_program_pda(
+        user,
+        ticket_info,
+        system_program,
+        8 + RedemptionTicket::LEN,
+        &[b"redemption_ticket", user.key.as_ref(), denom.as_bytes(), &[bump]],
+    )?;
+    Ok(RedemptionTicket {
+        redeemer: *user.key,
+        collateral_denom: denom.to_string(),
+        remaining: 0,
+        total_filled: 0,
+        remaining_gross: 0,
+        reserved_window_start_slot: 0,
+        reserved_volume: 0,
+    })
+}
+
+/// What one pass over the supplied troves redeemed
+pub(crate) struct RedemptionFill {
+    pub remaining_amount: u64,
+    pub collateral_sent: u64,
+    pub troves_redeemed: u32,
+    pub troves_closed: u32,
+    pub reserves_burned: u64, // Liquidation reserves of fully redeemed troves, still to be burned
+    pub limit_reached: bool, // Stopped by the redeemer's limits with aUSD left to fill
+    pub troves_skipped_undercollateralized: u32,
+}
+
+/// Redeem up to `amount` net aUSD against the client's pre-sorted `troves` (riskiest first), sending
+/// their collateral to the redeemer. Each trove has 4 accounts: UserDebtAmount,
+/// UserCollateralAmount, LiquidityThreshold, TokenAccount. The fill stops early, as a partial fill,
+/// at the first trove `limits` excludes. Troves below 100% ICR are skipped and counted, as
+/// liquidation is their way out. The caller burns the filled aUSD and the reserves and
+/// books them against the total debt.
+pub(crate) fn fill_redemption<'info>(
+    state: &mut StateAccount,
+    vaults: &RedemptionVaults<'_, 'info>,
+    troves: &[AccountInfo<'info>],
+    denom: &str,
+    live_price: Option<&PriceData>,
+    amount: u64,
+    limits: RedemptionLimits,
+) -> Result<RedemptionFill> {
+    let mut remaining_amount = amount;
     let mut total_collateral_sent = 0u64;
     let mut troves_redeemed = 0u32;
+    let mut troves_closed = 0u32;
+    let mut reserves_burned = 0u64;
+    let mut limit_reached = false;
+    let mut troves_skipped_undercollateralized = 0u32;
+    let min_net_debt = state.min_net_debt;
     
     // Validate remaining_accounts structure (4 accounts per trove)
     require!(
-        ctx.remaining_accounts.len() % 4 == 0,
+        troves.len().is_multiple_of(4),
         AerospacerProtocolError::InvalidList
     );
     
-    let num_troves = ctx.remaining_accounts.len() / 4;
+    let num_troves = troves.len() / 4;
     msg!("Processing redemption across {} pre-sorted troves", num_troves);
     
     // SECURITY: Verify total_collateral_amount PDA is authentic
     let (expected_total_coll_pda, _bump) = Pubkey::find_program_address(
-        &[b"total_collateral_amount", params.collateral_denom.as_bytes()],
+        &[b"total_collateral_amount", denom.as_bytes()],
         &crate::ID,
     );
     require!(
-        expected_total_coll_pda == *ctx.accounts.total_collateral_amount.key,
+        expected_total_coll_pda == vaults.total_collateral_amount.key(),
         AerospacerProtocolError::InvalidList
     );
     
@@ -253,13 +611,20 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
             break;
         }
         
+        // COMPUTE BOUND: the redeemer caps how many troves one pass touches
+        if !limits.allows_trove(troves_redeemed) {
+            msg!("Redeemed from {} troves, the redeemer's cap, stopping", troves_redeemed);
+            limit_reached = true;
+            break;
+        }
+        
         let base_idx = i * 4;
         
         // Get accounts for this trove
-        let debt_account = &ctx.remaining_accounts[base_idx];
-        let collateral_account = &ctx.remaining_accounts[base_idx + 1];
-        let lt_account = &ctx.remaining_accounts[base_idx + 2];
-        let token_account = &ctx.remaining_accounts[base_idx + 3];
+        let debt_account = &troves[base_idx];
+        let collateral_account = &troves[base_idx + 1];
+        let lt_account = &troves[base_idx + 2];
+        let token_account = &troves[base_idx + 3];
         
         // SECURITY: Verify program ownership for all trove accounts
         // Use crate::ID for cross-program invocation compatibility
@@ -282,19 +647,28 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
         let trove_user = user_debt.owner;
         drop(debt_data);
         
+        // A trove borrowed against or withdrawn from earlier in this slot is off limits until the next
+        user_debt.require_unlocked(Clock::get()?.slot)?;
+        
         let collateral_data = collateral_account.try_borrow_mut_data()?;
         let mut user_collateral = UserCollateralAmount::try_deserialize(&mut &collateral_data[..])?;
         let collateral_denom = user_collateral.denom.clone();
         drop(collateral_data);
         
+        // Collateral must belong to the same trove (a wallet may hold several)
+        require!(
+            user_collateral.owner == trove_user && user_collateral.trove_id == user_debt.trove_id,
+            AerospacerProtocolError::InvalidList
+        );
+        
         // CRITICAL: Apply pending redistribution rewards before processing redemption
         // This ensures trove state is up-to-date with any liquidation gains
-        let total_coll_data = ctx.accounts.total_collateral_amount.try_borrow_data()?;
-        let total_collateral = TotalCollateralAmount::try_deserialize(&mut &total_coll_data[..])?;
-        drop(total_coll_data);
-        
         use crate::trove_management::apply_pending_rewards;
-        apply_pending_rewards(&mut user_debt, &mut user_collateral, &total_collateral)?;
+        apply_pending_rewards(
+            &mut user_debt,
+            &mut user_collateral,
+            &*vaults.total_collateral_amount.load()?,
+        )?;
         
         // Serialize updated debt and collateral after applying rewards
         let mut debt_data_after = debt_account.try_borrow_mut_data()?;
@@ -309,20 +683,24 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
         let debt_amount = user_debt.amount;
         let collateral_amount = user_collateral.amount;
         
-        // Skip troves with zero debt (already fully redeemed or liquidated)
-        if debt_amount == 0 {
-            msg!("Trove {} has zero debt, skipping", trove_user);
+        // Only the net debt is redeemable - the liquidation reserve is burned once it is cleared
+        let liquidation_reserve = user_debt.liquidation_reserve;
+        let net_debt = user_debt.net_debt();
+        
+        // Skip troves that are not active (liquidated, closed or already redeemed-closed)
+        // and active troves with nothing left to redeem
+        if !user_debt.is_active() || net_debt == 0 {
+            msg!("Trove {} is {:?} with {} net debt, skipping", trove_user, user_debt.status, net_debt);
             continue;
         }
         
         // Deserialize LiquidityThreshold to get ICR and verify PDA
         let lt_data = lt_account.try_borrow_data()?;
         let liquidity_threshold = LiquidityThreshold::try_deserialize(&mut &lt_data[..])?;
-        let current_icr = liquidity_threshold.ratio;
         
-        // Verify LiquidityThreshold matches the debt account owner
+        // Verify LiquidityThreshold matches the debt account's trove
         require!(
-            liquidity_threshold.owner == trove_user,
+            liquidity_threshold.owner == trove_user && liquidity_threshold.trove_id == user_debt.trove_id,
             AerospacerProtocolError::InvalidList
         );
         drop(lt_data);
@@ -330,7 +708,25 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
         // SECURITY: Verify LiquidityThreshold is a real PDA, not a fake account
         // This prevents attackers from injecting fabricated accounts with arbitrary ICRs
         use crate::sorted_troves::verify_liquidity_threshold_pda;
-        verify_liquidity_threshold_pda(lt_account, trove_user, &crate::ID)?;
+        verify_liquidity_threshold_pda(lt_account, trove_user, user_debt.trove_id, &crate::ID)?;
+        
+        // A live price only values troves of its denom - the others take no part in the ordering
+        if live_price.is_some() && collateral_denom != denom {
+            msg!("Trove {} has {} collateral, not {}, skipping", trove_user, collateral_denom, denom);
+            continue;
+        }
+        
+        let current_icr = match live_price {
+            Some(price) => {
+                let collateral_value = PriceCalculator::calculate_collateral_value(
+                    collateral_amount,
+                    price.price as u64,
+                    price.decimal,
+                )?;
+                PriceCalculator::calculate_collateral_ratio(collateral_value, debt_amount)?
+            }
+            None => liquidity_threshold.ratio,
+        };
         
         // SECURITY: Validate ICR ordering (sorted from lowest to highest)
         // Ensures redemptions target riskiest troves first (Liquity model)
@@ -342,9 +738,23 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
         }
         prev_icr = Some(current_icr);
         
+        // Redeeming below 100% ICR pays out less than face value and deepens the shortfall
+        if !is_redeemable_icr(current_icr) {
+            msg!("Trove {} ICR {} is below 100%, skipping - it should be liquidated instead", trove_user, current_icr);
+            troves_skipped_undercollateralized += 1;
+            continue;
+        }
+        
+        // Troves are sorted, so every later one is at least as healthy as this one
+        if !limits.allows_icr(current_icr) {
+            msg!("Trove {} ICR {} is above the redeemer's max ICR {}, stopping", trove_user, current_icr, limits.max_icr);
+            limit_reached = true;
+            break;
+        }
+        
         // Skip if this trove doesn't have the requested collateral type
-        if collateral_denom != params.collateral_denom {
-            msg!("Trove {} has {} collateral, not {}, skipping", trove_user, collateral_denom, params.collateral_denom);
+        if collateral_denom != denom {
+            msg!("Trove {} has {} collateral, not {}, skipping", trove_user, collateral_denom, denom);
             continue;
         }
         
@@ -371,7 +781,13 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
         };
         
         // Calculate how much to redeem from this trove
-        let redeem_from_trove = remaining_amount.min(trove_data.debt_amount);
+        let redeem_from_trove = remaining_amount.min(net_debt);
+        
+        // Like Liquity, a partial redemption that would leave dust net debt ends the redemption
+        if redeem_from_trove < net_debt && net_debt - redeem_from_trove < min_net_debt {
+            msg!("Redeeming {} from trove {} would leave it below the minimum net debt, stopping", redeem_from_trove, trove_user);
+            break;
+        }
         
         // CRITICAL FIX: Calculate collateral to send using deterministic integer math
         // Formula: collateral_to_send = (collateral_amount * redeem_from_trove) / debt_amount
@@ -400,17 +816,17 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
             // Transfer collateral to user
             let collateral_seeds = &[
                 b"protocol_collateral_vault".as_ref(),
-                params.collateral_denom.as_bytes(),
-                &[ctx.bumps.protocol_collateral_vault],
+                denom.as_bytes(),
+                &[vaults.protocol_collateral_vault_bump],
             ];
             let collateral_signer = &[&collateral_seeds[..]];
             
             let collateral_transfer_ctx = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
+                vaults.token_program.clone(),
                 Transfer {
-                    from: ctx.accounts.protocol_collateral_vault.to_account_info(),
-                    to: ctx.accounts.user_collateral_account.to_account_info(),
-                    authority: ctx.accounts.protocol_collateral_vault.to_account_info(),
+                    from: vaults.protocol_collateral_vault.clone(),
+                    to: vaults.redeemer_collateral_account.clone(),
+                    authority: vaults.protocol_collateral_vault.clone(),
                 },
                 collateral_signer,
             );
@@ -424,28 +840,37 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
             drop(coll_data);
             
             // Update global total_collateral_amount PDA
-            let mut total_coll_data = ctx.accounts.total_collateral_amount.try_borrow_mut_data()?;
-            let mut total_collateral: TotalCollateralAmount = TotalCollateralAmount::try_deserialize(&mut &total_coll_data[..])?;
+            let mut total_collateral = vaults.total_collateral_amount.load_mut()?;
             total_collateral.amount = total_collateral.amount.checked_sub(collateral_to_send)
                 .ok_or(AerospacerProtocolError::OverflowError)?;
-            total_collateral.try_serialize(&mut &mut total_coll_data[..])?;
-            drop(total_coll_data);
+            drop(total_collateral);
             
             total_collateral_sent = total_collateral_sent.saturating_add(collateral_to_send);
-            msg!("Transferred {} {} to user from trove {}", collateral_to_send, params.collateral_denom, trove_user);
+            msg!("Transferred {} {} to user from trove {}", collateral_to_send, denom, trove_user);
         }
         
-        // Update trove debt
-        let new_debt = trove_data.debt_amount.saturating_sub(redeem_from_trove);
+        // Update trove debt - clearing the net debt also settles the liquidation reserve
+        let mut new_debt = trove_data.debt_amount.saturating_sub(redeem_from_trove);
+        if redeem_from_trove == net_debt {
+            new_debt = new_debt.saturating_sub(liquidation_reserve);
+            reserves_burned = reserves_burned.saturating_add(liquidation_reserve);
+        }
         
         // Update UserDebtAmount account
         let mut debt_data_mut = debt_account.try_borrow_mut_data()?;
         let mut user_debt_mut = UserDebtAmount::try_deserialize(&mut &debt_data_mut[..])?;
         user_debt_mut.amount = new_debt;
+        if new_debt == 0 {
+            user_debt_mut.liquidation_reserve = 0;
+            user_debt_mut.transition(TroveStatus::RedeemedClosed)?;
+            state.record_trove_ended();
+            vaults.total_collateral_amount.load_mut()?.record_trove_ended();
+        }
         user_debt_mut.try_serialize(&mut &mut debt_data_mut[..])?;
         drop(debt_data_mut);
         
         if new_debt == 0 {
+            troves_closed += 1;
             msg!("Trove fully redeemed and zeroed: {}", trove_user);
         } else {
             msg!("Trove partially redeemed: user={}, new_debt={}", trove_user, new_debt);
@@ -455,28 +880,62 @@ pub fn handler(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
         remaining_amount = remaining_amount.saturating_sub(redeem_from_trove);
     }
     
-    // CRITICAL: Require that the FULL redemption amount was processed
-    // Since we already burned the stablecoins upfront, we must ensure
-    // sufficient collateral was found, otherwise revert the entire transaction
-    require!(
-        remaining_amount == 0,
-        AerospacerProtocolError::InsufficientCollateral // Not enough troves with requested collateral type
-    );
+    Ok(RedemptionFill {
+        remaining_amount,
+        collateral_sent: total_collateral_sent,
+        troves_redeemed,
+        troves_closed,
+        reserves_burned,
+        limit_reached,
+        troves_skipped_undercollateralized,
+    })
+}
+
+/// Price of the redeemed denom for live ICR ordering, or None to order by stored ICRs.
+///
+/// Stored ICRs are only used while state.redemption_stored_icr_fallback is set, when the
+/// caller supplied no price account or the oracle price is older than the critical window.
+pub(crate) fn load_redemption_price<'info>(
+    state: &AccountLoader<'info, StateAccount>,
+    oracle_program: &AccountInfo<'info>,
+    oracle_state: &AccountInfo<'info>,
+    pyth_price_account: Option<&AccountInfo<'info>>,
+    clock: AccountInfo<'info>,
+    denom: &str,
+) -> Result<Option<PriceData>> {
+    let (stored_icr_fallback, max_price_age) = {
+        let state = state.load()?;
+        (state.redemption_stored_icr_fallback != 0, PriceReadClass::Critical.max_price_age(&state))
+    };
     
-    // PRODUCTION SAFETY: Update global state with net redeemed amount (which equals net_redemption_amount since remaining is 0)
-    state.total_debt_amount = state.total_debt_amount.checked_sub(net_redemption_amount)
-        .ok_or(AerospacerProtocolError::OverflowError)?;
+    let Some(pyth_price_account) = pyth_price_account else {
+        require!(stored_icr_fallback, AerospacerProtocolError::RedemptionPriceRequired);
+        msg!("No price account supplied, ordering troves by stored ICRs");
+        return Ok(None);
+    };
     
-    msg!("Redeemed successfully");
-    msg!("User: {}", ctx.accounts.user.key());
-    msg!("Gross amount: {} aUSD", params.amount);
-    msg!("Fee: {} aUSD ({}%)", fee_amount, ctx.accounts.state.protocol_fee);
-    msg!("Net redemption: {} aUSD", net_redemption_amount);
-    msg!("Collateral sent: {} {}", total_collateral_sent, params.collateral_denom);
-    msg!("Troves redeemed: {}", troves_redeemed);
-    msg!("Remaining amount: {} aUSD", remaining_amount);
-
-    Ok(())
+    let oracle_ctx = OracleContext {
+        oracle_program: oracle_program.clone(),
+        oracle_state: oracle_state.clone(),
+        pyth_price_account: pyth_price_account.clone(),
+        clock,
+        max_price_age,
+        fallback: None,
+        cached_price: None,
+    };
+    
+    match oracle_ctx.get_price(denom) {
+        Ok(price_data) => {
+            oracle_ctx.validate_price(&price_data)?;
+            msg!("Ordering troves by live ICR at price {} (decimal {})", price_data.price, price_data.decimal);
+            Ok(Some(price_data))
+        }
+        Err(err) if stored_icr_fallback => {
+            msg!("Live price unavailable ({}), ordering troves by stored ICRs", err);
+            Ok(None)
+        }
+        Err(err) => Err(err),
+    }
 }
 
 // NOTE: Helper functions for sorted list traversal removed - using off-chain sorting architecture
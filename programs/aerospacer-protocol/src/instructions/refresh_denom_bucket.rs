@@ -0,0 +1,211 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+use crate::oracle::*;
+use crate::sorted_troves::verify_liquidity_threshold_pda;
+use crate::trove_management::projected_icr;
+use crate::validation::{ValidateParams, validate_denom};
+
+// Keep the crank well inside the compute budget (one oracle CPI + N ICR recomputations)
+const MAX_REFRESH_BATCH_SIZE: usize = 20;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RefreshDenomBucketParams {
+    pub collateral_denom: String,
+}
+
+impl ValidateParams for RefreshDenomBucketParams {
+    fn validate(&self) -> Result<()> {
+        validate_denom(&self.collateral_denom)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: RefreshDenomBucketParams)]
+pub struct RefreshDenomBucket<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = 8 + PriceCache::LEN,
+        seeds = [b"price_cache", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub price_cache: Box<Account<'info, PriceCache>>,
+
+    #[account(
+        seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+
+    // Oracle context - integration with our aerospacer-oracle
+    /// CHECK: Our oracle program - validated against state
+    #[account(
+        constraint = oracle_program.key() == state.load()?.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_program: AccountInfo<'info>,
+
+    /// CHECK: Oracle state account - validated against state
+    #[account(
+        mut,
+        constraint = oracle_state.key() == state.load()?.oracle_state_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_state: AccountInfo<'info>,
+
+    /// CHECK: Pyth price account for collateral price feed
+    pub pyth_price_account: AccountInfo<'info>,
+
+    /// Clock sysvar for timestamp validation
+    pub clock: Sysvar<'info, Clock>,
+
+    pub system_program: Program<'info, System>,
+
+    // remaining_accounts should contain:
+    // - 3*N accounts: Per-trove accounts (UserDebtAmount, UserCollateralAmount, LiquidityThreshold)
+    //   for the riskiest troves of this denom, sorted by ascending ICR
+}
+
+/// Keeper crank: refresh the cached price for a denom and, with that same price,
+/// recompute the stored ICR of the riskiest troves so the off-chain sorted list
+/// never lags the price that liquidations and redemptions will execute against.
+pub fn handler(ctx: Context<RefreshDenomBucket>, params: RefreshDenomBucketParams) -> Result<()> {
+    params.validate()?;
+    
+    require!(
+        ctx.remaining_accounts.len().is_multiple_of(3),
+        AerospacerProtocolError::InvalidList
+    );
+
+    let num_troves = ctx.remaining_accounts.len() / 3;
+    require!(
+        num_troves <= MAX_REFRESH_BATCH_SIZE,
+        AerospacerProtocolError::InvalidList
+    );
+
+    // STEP 1: Fetch the live price once and write it to the cache
+    let oracle_ctx = OracleContext {
+        oracle_program: ctx.accounts.oracle_program.clone(),
+        oracle_state: ctx.accounts.oracle_state.clone(),
+        pyth_price_account: ctx.accounts.pyth_price_account.clone(),
+        clock: ctx.accounts.clock.to_account_info(),
+        max_price_age: PriceReadClass::Critical.max_price_age(&*ctx.accounts.state.load()?),
+        fallback: None,
+        cached_price: None,
+    };
+
+    let price_data = oracle_ctx.get_price(&params.collateral_denom)?;
+    oracle_ctx.validate_price(&price_data)?;
+
+    let current_slot = ctx.accounts.clock.slot;
+    ctx.accounts.price_cache.record(&price_data, current_slot);
+
+    msg!("Price cache refreshed for {}: price={}, decimal={}, slot={}",
+         params.collateral_denom, price_data.price, price_data.decimal, current_slot);
+
+    // STEP 2: Recompute ICRs for the riskiest bucket using the same price, counting each trove's
+    // pending redistribution so the ordering matches what liquidations will see
+    let total_collateral = ctx.accounts.total_collateral_amount.load()?;
+    let mut prev_icr: Option<u64> = None;
+
+    for i in 0..num_troves {
+        let base_idx = i * 3;
+
+        let debt_account = &ctx.remaining_accounts[base_idx];
+        let collateral_account = &ctx.remaining_accounts[base_idx + 1];
+        let lt_account = &ctx.remaining_accounts[base_idx + 2];
+
+        // SECURITY: Verify program ownership for all trove accounts
+        require!(
+            debt_account.owner == &crate::ID,
+            AerospacerProtocolError::Unauthorized
+        );
+        require!(
+            collateral_account.owner == &crate::ID,
+            AerospacerProtocolError::Unauthorized
+        );
+        require!(
+            lt_account.owner == &crate::ID,
+            AerospacerProtocolError::Unauthorized
+        );
+        require!(
+            lt_account.is_writable,
+            AerospacerProtocolError::Unauthorized
+        );
+
+        let debt_data = debt_account.try_borrow_data()?;
+        let user_debt = UserDebtAmount::try_deserialize(&mut &debt_data[..])?;
+        let trove_user = user_debt.owner;
+        drop(debt_data);
+        require!(
+            user_debt.is_active(),
+            AerospacerProtocolError::TroveDoesNotExist
+        );
+
+        let collateral_data = collateral_account.try_borrow_data()?;
+        let user_collateral = UserCollateralAmount::try_deserialize(&mut &collateral_data[..])?;
+        drop(collateral_data);
+
+        require!(
+            user_collateral.owner == trove_user && user_collateral.trove_id == user_debt.trove_id,
+            AerospacerProtocolError::InvalidList
+        );
+        require!(
+            user_collateral.denom == params.collateral_denom,
+            AerospacerProtocolError::InvalidList
+        );
+
+        // SECURITY: Both the collateral record and the threshold must be the canonical PDAs
+        let trove_seed = trove_id_seed(user_debt.trove_id);
+        let (expected_collateral_pda, _bump) = Pubkey::find_program_address(
+            &UserCollateralAmount::seeds(&trove_user, &params.collateral_denom, &trove_seed),
+            &crate::ID,
+        );
+        require!(
+            expected_collateral_pda == *collateral_account.key,
+            AerospacerProtocolError::InvalidList
+        );
+        verify_liquidity_threshold_pda(lt_account, trove_user, user_debt.trove_id, &crate::ID)?;
+
+        let new_icr = projected_icr(
+            &user_debt,
+            &user_collateral,
+            &total_collateral,
+            price_data.price as u64,
+            price_data.decimal,
+        )?;
+
+        // The bucket must be the head of the sorted list: ascending by refreshed ICR
+        if let Some(prev) = prev_icr {
+            require!(
+                prev <= new_icr,
+                AerospacerProtocolError::InvalidList
+            );
+        }
+        prev_icr = Some(new_icr);
+
+        let mut lt_data = lt_account.try_borrow_mut_data()?;
+        let mut liquidity_threshold = LiquidityThreshold::try_deserialize(&mut &lt_data[..])?;
+        require!(
+            liquidity_threshold.owner == trove_user,
+            AerospacerProtocolError::InvalidList
+        );
+        let old_icr = liquidity_threshold.ratio;
+        liquidity_threshold.ratio = new_icr;
+        liquidity_threshold.try_serialize(&mut &mut lt_data[..])?;
+        drop(lt_data);
+
+        msg!("Trove {}: ICR {} -> {}", trove_user, old_icr, new_icr);
+    }
+
+    msg!("Denom bucket refreshed successfully");
+    msg!("Keeper: {}", ctx.accounts.keeper.key());
+    msg!("Collateral denom: {}", params.collateral_denom);
+    msg!("Troves refreshed: {}", num_troves);
+
+    Ok(())
+}
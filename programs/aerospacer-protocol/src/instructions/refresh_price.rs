@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+use crate::oracle::*;
+use crate::validation::{ValidateParams, validate_denom};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RefreshPriceParams {
+    pub collateral_denom: String,
+}
+
+impl ValidateParams for RefreshPriceParams {
+    fn validate(&self) -> Result<()> {
+        validate_denom(&self.collateral_denom)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: RefreshPriceParams)]
+pub struct RefreshPrice<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = 8 + PriceCache::LEN,
+        seeds = [b"price_cache", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub price_cache: Box<Account<'info, PriceCache>>,
+
+    // Oracle context - integration with our aerospacer-oracle
+    /// CHECK: Our oracle program - validated against state
+    #[account(
+        constraint = oracle_program.key() == state.load()?.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_program: AccountInfo<'info>,
+
+    /// CHECK: Oracle state account - validated against state
+    #[account(
+        mut,
+        constraint = oracle_state.key() == state.load()?.oracle_state_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_state: AccountInfo<'info>,
+
+    /// CHECK: Pyth price account for collateral price feed - bound to the denom by the oracle
+    pub pyth_price_account: AccountInfo<'info>,
+
+    /// Clock sysvar for timestamp validation
+    pub clock: Sysvar<'info, Clock>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless keeper crank: write the live oracle price of a denom to its PriceCache.
+///
+/// The price is read under the critical staleness window, like refresh_denom_bucket, so a cache
+/// is never written from a price liquidations would reject. While state.price_cache_max_age is
+/// set, trove instructions passed a fresh cache use it instead of their own oracle CPI.
+pub fn handler(ctx: Context<RefreshPrice>, params: RefreshPriceParams) -> Result<()> {
+    params.validate()?;
+
+    let oracle_ctx = OracleContext {
+        oracle_program: ctx.accounts.oracle_program.clone(),
+        oracle_state: ctx.accounts.oracle_state.clone(),
+        pyth_price_account: ctx.accounts.pyth_price_account.clone(),
+        clock: ctx.accounts.clock.to_account_info(),
+        max_price_age: PriceReadClass::Critical.max_price_age(&*ctx.accounts.state.load()?),
+        fallback: None,
+        cached_price: None,
+    };
+
+    let price_data = oracle_ctx.get_price(&params.collateral_denom)?;
+    oracle_ctx.validate_price(&price_data)?;
+
+    let current_slot = ctx.accounts.clock.slot;
+    ctx.accounts.price_cache.record(&price_data, current_slot);
+
+    msg!("Price cache refreshed successfully");
+    msg!("Keeper: {}", ctx.accounts.keeper.key());
+    msg!("Collateral denom: {}", params.collateral_denom);
+    msg!("Price: {} (decimal {}), published {}", price_data.price, price_data.decimal, price_data.timestamp);
+    msg!("Slot: {}", current_slot);
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+use crate::oracle::*;
+use crate::validation::{ValidateParams, validate_denom};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RefreshTcrParams {
+    pub collateral_denom: String,
+}
+
+impl ValidateParams for RefreshTcrParams {
+    fn validate(&self) -> Result<()> {
+        validate_denom(&self.collateral_denom)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: RefreshTcrParams)]
+pub struct RefreshTcr<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = 8 + CollateralValuation::LEN,
+        seeds = [b"collateral_valuation", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub collateral_valuation: Box<Account<'info, CollateralValuation>>,
+
+    // Oracle context - integration with our aerospacer-oracle
+    /// CHECK: Our oracle program - validated against state
+    #[account(
+        constraint = oracle_program.key() == state.load()?.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_program: AccountInfo<'info>,
+
+    /// CHECK: Oracle state account - validated against state
+    #[account(
+        mut,
+        constraint = oracle_state.key() == state.load()?.oracle_state_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_state: AccountInfo<'info>,
+
+    /// CHECK: Pyth price account for collateral price feed - bound to the denom by the oracle
+    pub pyth_price_account: AccountInfo<'info>,
+
+    /// Clock sysvar for timestamp validation
+    pub clock: Sysvar<'info, Clock>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless keeper crank: value a denom's collateral total at the live oracle price, swap
+/// it into state.total_collateral_value and recompute the cached TCR (state.tcr).
+///
+/// The price is read under the critical staleness window, like refresh_price, since Recovery
+/// Mode checks read the cached TCR. Every listed denom must be refreshed once before the TCR
+/// covers all collateral (see state.valued_denoms).
+pub fn handler(ctx: Context<RefreshTcr>, params: RefreshTcrParams) -> Result<()> {
+    params.validate()?;
+
+    let oracle_ctx = OracleContext {
+        oracle_program: ctx.accounts.oracle_program.clone(),
+        oracle_state: ctx.accounts.oracle_state.clone(),
+        pyth_price_account: ctx.accounts.pyth_price_account.clone(),
+        clock: ctx.accounts.clock.to_account_info(),
+        max_price_age: PriceReadClass::Critical.max_price_age(&*ctx.accounts.state.load()?),
+        fallback: None,
+        cached_price: None,
+    };
+
+    let price_data = oracle_ctx.get_price(&params.collateral_denom)?;
+    oracle_ctx.validate_price(&price_data)?;
+
+    let amount = ctx.accounts.total_collateral_amount.load()?.amount;
+    let value = PriceCalculator::calculate_collateral_value(
+        amount,
+        price_data.price as u64,
+        price_data.decimal,
+    )?;
+
+    let current_slot = ctx.accounts.clock.slot;
+    let valuation = &mut ctx.accounts.collateral_valuation;
+    let first_valuation = valuation.denom.is_empty();
+    let old_value = valuation.value;
+    valuation.denom = params.collateral_denom.clone();
+    valuation.amount = amount;
+    valuation.price = price_data.price;
+    valuation.decimal = price_data.decimal;
+    valuation.value = value;
+    valuation.last_update_slot = current_slot;
+
+    let mut state = ctx.accounts.state.load_mut()?;
+    if first_valuation {
+        state.valued_denoms = state.valued_denoms.saturating_add(1);
+    }
+    state.record_collateral_valuation(old_value, value, current_slot)?;
+
+    msg!("TCR refreshed successfully");
+    msg!("Keeper: {}", ctx.accounts.keeper.key());
+    msg!("Collateral: {} {} = {} micro-USD (was {})", amount, params.collateral_denom, value, old_value);
+    msg!("Price: {} (decimal {})", price_data.price, price_data.decimal);
+    msg!("Total collateral value: {} micro-USD over {} denoms", state.total_collateral_value, state.valued_denoms);
+    msg!("Total debt: {}", state.total_debt_amount);
+    msg!("TCR: {}", state.tcr);
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+use crate::oracle::*;
+use crate::trove_management::apply_pending_rewards;
+use crate::validation::{ValidateParams, validate_denom, validate_pubkey};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RefreshTroveIcrParams {
+    pub owner: Pubkey,
+    pub collateral_denom: String,
+    pub trove_id: u16, // Which of the owner's troves (0 = the wallet's first trove)
+}
+
+impl ValidateParams for RefreshTroveIcrParams {
+    fn validate(&self) -> Result<()> {
+        validate_pubkey(&self.owner)?;
+        validate_denom(&self.collateral_denom)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: RefreshTroveIcrParams)]
+pub struct RefreshTroveIcr<'info> {
+    pub keeper: Signer<'info>,
+
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"user_debt_amount", params.owner.as_ref(), trove_id_seed(params.trove_id).as_slice()],
+        bump,
+        constraint = user_debt_amount.owner == params.owner @ AerospacerProtocolError::Unauthorized,
+        constraint = user_debt_amount.is_active() @ AerospacerProtocolError::TroveDoesNotExist
+    )]
+    pub user_debt_amount: Account<'info, UserDebtAmount>,
+
+    #[account(
+        mut,
+        seeds = [b"user_collateral_amount", params.owner.as_ref(), trove_id_seed(params.trove_id).as_slice(), params.collateral_denom.as_bytes()],
+        bump,
+        constraint = user_collateral_amount.owner == params.owner @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_collateral_amount: Account<'info, UserCollateralAmount>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_threshold", params.owner.as_ref(), trove_id_seed(params.trove_id).as_slice()],
+        bump,
+        constraint = liquidity_threshold.owner == params.owner @ AerospacerProtocolError::Unauthorized
+    )]
+    pub liquidity_threshold: Account<'info, LiquidityThreshold>,
+
+    #[account(
+        seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+
+    // Oracle context - integration with our aerospacer-oracle
+    /// CHECK: Our oracle program - validated against state
+    #[account(
+        constraint = oracle_program.key() == state.load()?.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_program: AccountInfo<'info>,
+
+    /// CHECK: Oracle state account - validated against state
+    #[account(
+        mut,
+        constraint = oracle_state.key() == state.load()?.oracle_state_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_state: AccountInfo<'info>,
+
+    /// CHECK: Pyth price account for collateral price feed - bound to the denom by the oracle
+    pub pyth_price_account: AccountInfo<'info>,
+
+    /// Clock sysvar for timestamp validation
+    pub clock: Sysvar<'info, Clock>,
+}
+
+/// Permissionless keeper crank: apply a trove's pending redistribution rewards and recompute
+/// its stored ICR at the live oracle price, so the off-chain sorted list tracks price moves and
+/// redistributions between the owner's own actions.
+///
+/// The price is read under the critical staleness window, like refresh_denom_bucket, since the
+/// stored ICRs order liquidations and redemptions.
+pub fn handler(ctx: Context<RefreshTroveIcr>, params: RefreshTroveIcrParams) -> Result<()> {
+    params.validate()?;
+
+    let oracle_ctx = OracleContext {
+        oracle_program: ctx.accounts.oracle_program.clone(),
+        oracle_state: ctx.accounts.oracle_state.clone(),
+        pyth_price_account: ctx.accounts.pyth_price_account.clone(),
+        clock: ctx.accounts.clock.to_account_info(),
+        max_price_age: PriceReadClass::Critical.max_price_age(&*ctx.accounts.state.load()?),
+        fallback: None,
+        cached_price: None,
+    };
+
+    let price_data = oracle_ctx.get_price(&params.collateral_denom)?;
+    oracle_ctx.validate_price(&price_data)?;
+
+    apply_pending_rewards(
+        &mut ctx.accounts.user_debt_amount,
+        &mut ctx.accounts.user_collateral_amount,
+        &*ctx.accounts.total_collateral_amount.load()?,
+    )?;
+
+    let debt_amount = ctx.accounts.user_debt_amount.amount;
+    let collateral_amount = ctx.accounts.user_collateral_amount.amount;
+    let new_icr = if debt_amount == 0 {
+        0
+    } else {
+        let collateral_value = PriceCalculator::calculate_collateral_value(
+            collateral_amount,
+            price_data.price as u64,
+            price_data.decimal,
+        )?;
+        PriceCalculator::calculate_collateral_ratio(collateral_value, debt_amount)?
+    };
+
+    let old_icr = ctx.accounts.liquidity_threshold.ratio;
+    ctx.accounts.liquidity_threshold.ratio = new_icr;
+
+    msg!("Trove ICR refreshed successfully");
+    msg!("Keeper: {}", ctx.accounts.keeper.key());
+    msg!("Owner: {}", params.owner);
+    msg!("Trove id: {}", params.trove_id);
+    msg!("Collateral: {} {}", collateral_amount, params.collateral_denom);
+    msg!("Debt: {}", debt_amount);
+    msg!("Price: {} (decimal {})", price_data.price, price_data.decimal);
+    msg!("ICR: {} -> {}", old_icr, new_icr);
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint};
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::events::CollateralRegistered;
+use crate::validation::{ValidateParams, validate_denom};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RegisterCollateralParams {
+    pub denom: String, // Bound to collateral_mint for good
+}
+
+impl ValidateParams for RegisterCollateralParams {
+    fn validate(&self) -> Result<()> {
+        validate_denom(&self.denom)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: RegisterCollateralParams)]
+pub struct RegisterCollateral<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    pub collateral_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = admin,
+        token::mint = collateral_mint,
+        token::authority = protocol_collateral_vault,
+        seeds = [b"protocol_collateral_vault", params.denom.as_bytes()],
+        bump
+    )]
+    pub protocol_collateral_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + TotalCollateralAmount::LEN,
+        seeds = [b"total_collateral_amount", params.denom.as_bytes()],
+        bump
+    )]
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + StabilityPoolSnapshot::LEN,
+        seeds = [b"stability_pool_snapshot", params.denom.as_bytes()],
+        bump
+    )]
+    pub stability_pool_snapshot: Box<Account<'info, StabilityPoolSnapshot>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + CollateralConfig::LEN,
+        seeds = [b"collateral_config", params.denom.as_bytes()],
+        bump
+    )]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for register_collateral instruction
+///
+/// Lists a collateral denom by creating its per-denom PDAs up front: the collateral vault (which
+/// binds the denom to `collateral_mint`), the collateral total, the stability pool snapshot and
+/// the denom's risk parameters (the protocol-wide MCR and fee and a 110% liquidation threshold,
+/// see set_collateral_params).
+/// Trove instructions no longer create these lazily, so the first opener of a denom can neither
+/// race the admin nor pick its mint. Denoms opened before this instruction existed already have
+/// their vault and total.
+pub fn handler(ctx: Context<RegisterCollateral>, params: RegisterCollateralParams) -> Result<()> {
+    params.validate()?;
+
+    {
+        let mut total_collateral = ctx.accounts.total_collateral_amount.load_init()?;
+        total_collateral.set_denom(&params.denom)?;
+        total_collateral.amount = 0;
+        total_collateral.l_debt = 0;
+        total_collateral.l_collateral = 0;
+        total_collateral.l_scale = 0;
+        total_collateral.deactivated = 0;
+    }
+
+    let snapshot = &mut ctx.accounts.stability_pool_snapshot;
+    snapshot.denom = params.denom.clone();
+    snapshot.s_factor = 0;
+    snapshot.total_collateral_gained = 0;
+    snapshot.epoch = 0;
+
+    let config = &mut ctx.accounts.collateral_config;
+    config.denom = params.denom.clone();
+    config.minimum_collateral_ratio = ctx.accounts.state.load()?.minimum_collateral_ratio;
+    config.liquidation_threshold = DEFAULT_LIQUIDATION_THRESHOLD;
+    config.protocol_fee = ctx.accounts.state.load()?.protocol_fee;
+    CollateralConfig::validate_ratios(config.minimum_collateral_ratio, config.liquidation_threshold)?;
+
+    msg!("Collateral registered");
+    msg!("Denom: {}", params.denom);
+    msg!("Mint: {}", ctx.accounts.collateral_mint.key());
+    msg!("Vault: {}", ctx.accounts.protocol_collateral_vault.key());
+    msg!("MCR: {}, liquidation threshold: {}", config.minimum_collateral_ratio, config.liquidation_threshold);
+    msg!("Protocol fee: {}%", config.protocol_fee);
+
+    emit!(CollateralRegistered {
+        denom: params.denom,
+        mint: ctx.accounts.collateral_mint.key(),
+        vault: ctx.accounts.protocol_collateral_vault.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::validation::{ValidateParams, validate_bps};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RegisterFrontendParams {
+    pub kickback_rate_bps: u16, // Share of reward emissions passed on to tagged depositors
+}
+
+impl ValidateParams for RegisterFrontendParams {
+    fn validate(&self) -> Result<()> {
+        validate_bps(self.kickback_rate_bps, KICKBACK_RATE_DENOMINATOR, AerospacerProtocolError::InvalidKickbackRate)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: RegisterFrontendParams)]
+pub struct RegisterFrontend<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+    
+    // One registration per operator; the kickback rate cannot change afterwards
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + Frontend::LEN,
+        seeds = [b"frontend", operator.key().as_ref()],
+        bump
+    )]
+    pub frontend: Account<'info, Frontend>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterFrontend>, params: RegisterFrontendParams) -> Result<()> {
+    params.validate()?;
+    
+    let frontend = &mut ctx.accounts.frontend;
+    frontend.operator = ctx.accounts.operator.key();
+    frontend.kickback_rate_bps = params.kickback_rate_bps;
+    frontend.tagged_deposits = 0;
+    frontend.troves_onboarded = 0;
+    frontend.pending_rewards = 0;
+    frontend.total_rewards_earned = 0;
+    
+    msg!("Frontend registered successfully");
+    msg!("Operator: {}", frontend.operator);
+    msg!("Kickback rate: {} bps", frontend.kickback_rate_bps);
+    
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::events::HeartbeatRegistered;
+
+#[derive(Accounts)]
+pub struct RegisterHeartbeat<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    
+    // Created on the first heartbeat, refreshed on every later one
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = 8 + KeeperHeartbeat::LEN,
+        seeds = [b"keeper_heartbeat", keeper.key().as_ref()],
+        bump
+    )]
+    pub keeper_heartbeat: Account<'info, KeeperHeartbeat>,
+    
+    pub clock: Sysvar<'info, Clock>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for register_heartbeat instruction
+/// Keepers call this periodically so the DAO can tell a live keeper set from a dead one
+pub fn handler(ctx: Context<RegisterHeartbeat>) -> Result<()> {
+    let clock = &ctx.accounts.clock;
+    let heartbeat = &mut ctx.accounts.keeper_heartbeat;
+    
+    let previous_slot = heartbeat.last_seen_slot;
+    if heartbeat.heartbeats == 0 {
+        heartbeat.keeper = ctx.accounts.keeper.key();
+        heartbeat.first_seen_slot = clock.slot;
+    }
+    heartbeat.last_seen_slot = clock.slot;
+    heartbeat.last_seen_at = clock.unix_timestamp;
+    heartbeat.heartbeats = heartbeat.heartbeats.saturating_add(1);
+    
+    msg!("Heartbeat registered");
+    msg!("Keeper: {}", heartbeat.keeper);
+    msg!("Slot: {} (previous: {})", clock.slot, previous_slot);
+    
+    emit!(HeartbeatRegistered {
+        keeper: heartbeat.keeper,
+        slot: clock.slot,
+        previous_slot,
+        heartbeats: heartbeat.heartbeats,
+    });
+    
+    Ok(())
+}
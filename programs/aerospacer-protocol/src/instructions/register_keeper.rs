@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RegisterKeeper<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = keeper,
+        space = 8 + Keeper::LEN,
+        seeds = [b"keeper", keeper.key().as_ref()],
+        bump
+    )]
+    pub keeper_registration: Account<'info, Keeper>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for register_keeper instruction
+/// Opts the signer into liquidation bounties; liquidations pass the registration to get paid
+pub fn handler(ctx: Context<RegisterKeeper>) -> Result<()> {
+    let registration = &mut ctx.accounts.keeper_registration;
+    registration.keeper = ctx.accounts.keeper.key();
+    registration.registered_slot = Clock::get()?.slot;
+    registration.troves_liquidated = 0;
+    registration.debt_liquidated = 0;
+    registration.collateral_bounty_payouts = 0;
+    registration.flat_bounties_earned = 0;
+    
+    msg!("Keeper registered successfully");
+    msg!("Keeper: {}", registration.keeper);
+    
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::trove_management::renormalize_l_factors;
+use crate::validation::{ValidateParams, validate_denom};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RenormalizeLFactorsParams {
+    pub collateral_denom: String,
+}
+
+impl ValidateParams for RenormalizeLFactorsParams {
+    fn validate(&self) -> Result<()> {
+        validate_denom(&self.collateral_denom)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: RenormalizeLFactorsParams)]
+pub struct RenormalizeLFactors<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+}
+
+/// Permissionless crank - only succeeds once a denom's L factors have crossed
+/// L_RENORMALIZATION_THRESHOLD, so it cannot be used to grief precision early.
+pub fn handler(ctx: Context<RenormalizeLFactors>, params: RenormalizeLFactorsParams) -> Result<()> {
+    params.validate()?;
+    
+    let mut total_collateral = ctx.accounts.total_collateral_amount.load_mut()?;
+    let old_l_debt = total_collateral.l_debt;
+    let old_l_collateral = total_collateral.l_collateral;
+
+    renormalize_l_factors(&mut total_collateral)?;
+
+    msg!("L factors renormalized successfully");
+    msg!("Caller: {}", ctx.accounts.caller.key());
+    msg!("Collateral denom: {}", params.collateral_denom);
+    msg!("L_debt: {} -> {}", old_l_debt, total_collateral.l_debt);
+    msg!("L_collateral: {} -> {}", old_l_collateral, total_collateral.l_collateral);
+    msg!("L scale: {}", total_collateral.l_scale);
+
+    Ok(())
+}
@@ -0,0 +1,279 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint, Burn};
+use crate::state::*;
+use crate::error::*;
+use crate::trove_management::apply_pending_rewards;
+use crate::oracle::*;
+use crate::events::{TroveOperation, TroveUpdated};
+use crate::liquidation_reserve::check_net_debt;
+use crate::validation::{ValidateParams, validate_amount, validate_list_len};
+
+// Accounts per target trove in remaining_accounts
+const ACCOUNTS_PER_TARGET: usize = 5;
+
+// Keeps the batch within the compute budget (one oracle read per target)
+const MAX_REPAY_BATCH_SIZE: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RepayBatchParams {
+    pub amounts: Vec<u64>, // aUSD to repay per target, in remaining_accounts order
+}
+
+impl ValidateParams for RepayBatchParams {
+    fn validate(&self) -> Result<()> {
+        validate_list_len(self.amounts.len(), MAX_REPAY_BATCH_SIZE)?;
+        for amount in self.amounts.iter() {
+            validate_amount(*amount)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: RepayBatchParams)]
+pub struct RepayBatch<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        mut,
+        constraint = payer_stablecoin_account.owner == payer.key() @ AerospacerProtocolError::Unauthorized,
+        constraint = payer_stablecoin_account.mint == stable_coin_mint.key() @ AerospacerProtocolError::InvalidMint
+    )]
+    pub payer_stablecoin_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub stable_coin_mint: Account<'info, Mint>,
+
+    /// CHECK: Our oracle program - validated against state
+    #[account(
+        constraint = oracle_program.key() == state.load()?.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_program: AccountInfo<'info>,
+
+    /// CHECK: Oracle state account - validated against state
+    #[account(
+        mut,
+        constraint = oracle_state.key() == state.load()?.oracle_state_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_state: AccountInfo<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub token_program: Program<'info, Token>,
+
+    // remaining_accounts should contain, for every target trove (same order as params.amounts):
+    // [UserDebtAmount, UserCollateralAmount, LiquidityThreshold, TotalCollateralAmount,
+    //  pyth_price_account for that collateral denom]
+    // (all writable except the TotalCollateralAmount and the price account)
+}
+
+/// Handler for repay_batch instruction
+///
+/// Lets a single payer (e.g. a liquidation-protection service) burn aUSD against several
+/// troves it does not own in one transaction. Only partial repayments are allowed: closing a
+/// trove returns collateral, which stays with repay_loan and the owner's signature.
+/// No neighbor hints are taken; repayment only raises ICR and the sorted list is kept off-chain.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RepayBatch<'info>>, params: RepayBatchParams) -> Result<()> {
+    params.validate()?;
+    
+    let target_count = params.amounts.len();
+    require!(
+        ctx.remaining_accounts.len() == target_count * ACCOUNTS_PER_TARGET,
+        AerospacerProtocolError::InvalidList
+    );
+
+    let total_repay = params.amounts
+        .iter()
+        .try_fold(0u64, |acc, amount| acc.checked_add(*amount))
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    require!(
+        total_repay <= ctx.accounts.payer_stablecoin_account.amount,
+        AerospacerProtocolError::InsufficientCollateral
+    );
+
+    let max_price_age = PriceReadClass::Derisking.max_price_age(&*ctx.accounts.state.load()?);
+    let min_net_debt = ctx.accounts.state.load()?.min_net_debt;
+    let mut repaid_troves: Vec<(Pubkey, u16)> = Vec::with_capacity(target_count);
+
+    for (chunk, &amount) in ctx.remaining_accounts.chunks(ACCOUNTS_PER_TARGET).zip(params.amounts.iter()) {
+        let (debt_info, collateral_info, threshold_info, total_collateral_info, pyth_price_info) =
+            (&chunk[0], &chunk[1], &chunk[2], &chunk[3], &chunk[4]);
+
+        // SECURITY: Trove accounts must be program-owned and writable
+        require!(
+            debt_info.owner == &crate::ID
+                && collateral_info.owner == &crate::ID
+                && threshold_info.owner == &crate::ID
+                && total_collateral_info.owner == &crate::ID,
+            AerospacerProtocolError::Unauthorized
+        );
+        require!(
+            debt_info.is_writable && collateral_info.is_writable && threshold_info.is_writable,
+            AerospacerProtocolError::InvalidList
+        );
+
+        let mut user_debt = {
+            let data = debt_info.try_borrow_data()?;
+            UserDebtAmount::try_deserialize(&mut &data[..])?
+        };
+        let mut user_collateral = {
+            let data = collateral_info.try_borrow_data()?;
+            UserCollateralAmount::try_deserialize(&mut &data[..])?
+        };
+        let mut liquidity_threshold = {
+            let data = threshold_info.try_borrow_data()?;
+            LiquidityThreshold::try_deserialize(&mut &data[..])?
+        };
+        let owner = user_debt.owner;
+        let trove_id = user_debt.trove_id;
+
+        // Each trove only once per batch
+        require!(
+            !repaid_troves.contains(&(owner, trove_id)),
+            AerospacerProtocolError::InvalidList
+        );
+        require!(
+            user_collateral.owner == owner && liquidity_threshold.owner == owner,
+            AerospacerProtocolError::Unauthorized
+        );
+
+        // SECURITY: Every account in the group must be the canonical PDA for this trove
+        let denom = user_collateral.denom.clone();
+        let trove_seed = trove_id_seed(trove_id);
+        let (expected_debt_pda, _bump) = Pubkey::find_program_address(
+            &UserDebtAmount::seeds(&owner, &trove_seed),
+            &crate::ID,
+        );
+        let (expected_collateral_pda, _bump) = Pubkey::find_program_address(
+            &UserCollateralAmount::seeds(&owner, &denom, &trove_seed),
+            &crate::ID,
+        );
+        let (expected_threshold_pda, _bump) = Pubkey::find_program_address(
+            &LiquidityThreshold::seeds(&owner, &trove_seed),
+            &crate::ID,
+        );
+        let (expected_total_pda, _bump) = Pubkey::find_program_address(
+            &TotalCollateralAmount::seeds(&denom),
+            &crate::ID,
+        );
+        require!(
+            expected_debt_pda == *debt_info.key
+                && expected_collateral_pda == *collateral_info.key
+                && expected_threshold_pda == *threshold_info.key
+                && expected_total_pda == *total_collateral_info.key,
+            AerospacerProtocolError::InvalidList
+        );
+
+        // Apply pending redistribution rewards before modifying trove
+        {
+            let data = total_collateral_info.try_borrow_data()?;
+            let total_collateral = TotalCollateralAmount::from_account_data(&data)?;
+            apply_pending_rewards(&mut user_debt, &mut user_collateral, total_collateral)?;
+        }
+
+        require!(
+            user_debt.is_active() && user_debt.amount > 0,
+            AerospacerProtocolError::TroveDoesNotExist
+        );
+        // Partial repayment only - full repayment must go through repay_loan
+        require!(
+            amount < user_debt.net_debt(),
+            AerospacerProtocolError::InvalidAmount
+        );
+
+        let new_debt_amount = user_debt.amount - amount;
+        check_net_debt(new_debt_amount - user_debt.liquidation_reserve, min_net_debt)?;
+
+        let oracle_ctx = OracleContext {
+            oracle_program: ctx.accounts.oracle_program.clone(),
+            oracle_state: ctx.accounts.oracle_state.clone(),
+            pyth_price_account: pyth_price_info.clone(),
+            clock: ctx.accounts.clock.to_account_info(),
+            max_price_age,
+            fallback: None,
+            cached_price: None,
+        };
+        let price = oracle_ctx.get_price(&denom)?;
+        oracle_ctx.validate_price(&price)?;
+
+        let collateral_value = PriceCalculator::calculate_collateral_value(
+            user_collateral.amount,
+            price.price as u64,
+            price.decimal,
+        )?;
+        let new_icr = PriceCalculator::calculate_collateral_ratio(collateral_value, new_debt_amount)?;
+
+        user_debt.amount = new_debt_amount;
+        liquidity_threshold.ratio = new_icr;
+
+        {
+            let mut state = ctx.accounts.state.load_mut()?;
+            state.total_debt_amount = state.total_debt_amount
+                .checked_sub(amount)
+                .ok_or(AerospacerProtocolError::OverflowError)?;
+        }
+
+        // Persist the updated trove
+        {
+            let mut data = debt_info.try_borrow_mut_data()?;
+            let mut writer: &mut [u8] = &mut data;
+            user_debt.try_serialize(&mut writer)?;
+        }
+        {
+            let mut data = collateral_info.try_borrow_mut_data()?;
+            let mut writer: &mut [u8] = &mut data;
+            user_collateral.try_serialize(&mut writer)?;
+        }
+        {
+            let mut data = threshold_info.try_borrow_mut_data()?;
+            let mut writer: &mut [u8] = &mut data;
+            liquidity_threshold.try_serialize(&mut writer)?;
+        }
+
+        msg!("Repaid {} aUSD for {} (debt: {}, ICR: {})", amount, owner, new_debt_amount, new_icr);
+
+        emit!(TroveUpdated {
+            owner,
+            trove_id,
+            collateral_denom: denom,
+            operation: TroveOperation::BatchRepay,
+            amount,
+            collateral_amount: user_collateral.amount,
+            debt_amount: new_debt_amount,
+            icr: new_icr,
+            tag: user_debt.tag,
+            memo: None,
+        });
+
+        repaid_troves.push((owner, trove_id));
+    }
+
+    // Burn the whole batch from the payer in a single CPI
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.stable_coin_mint.to_account_info(),
+            from: ctx.accounts.payer_stablecoin_account.to_account_info(),
+            authority: ctx.accounts.payer.to_account_info(),
+        },
+    );
+    anchor_spl::token::burn(burn_ctx, total_repay)?;
+
+    msg!("Batch repayment successful");
+    msg!("Payer: {}", ctx.accounts.payer.key());
+    msg!("Troves repaid: {}", repaid_troves.len());
+    msg!("Total repaid: {} aUSD", total_repay);
+
+    Ok(())
+}
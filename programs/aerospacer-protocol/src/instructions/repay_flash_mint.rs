@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint, Burn, Transfer};
+use crate::state::*;
+use crate::error::*;
+use crate::trove_management::distribute_fee_yield_to_stakers;
+use crate::events::FlashMintRepaid;
+use crate::validation::{ValidateParams, validate_amount};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RepayFlashMintParams {
+    pub amount: u64, // Must equal the flash minted amount
+}
+
+impl ValidateParams for RepayFlashMintParams {
+    fn validate(&self) -> Result<()> {
+        validate_amount(self.amount)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: RepayFlashMintParams)]
+pub struct RepayFlashMint<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        mut,
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub stable_coin_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = payer_stablecoin_account.owner == payer.key() @ AerospacerProtocolError::Unauthorized,
+        constraint = payer_stablecoin_account.mint == stable_coin_mint.key() @ AerospacerProtocolError::InvalidMint
+    )]
+    pub payer_stablecoin_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_stablecoin_vault"],
+        bump
+    )]
+    pub protocol_stablecoin_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Handler for repay_flash_mint instruction
+/// Burns the flash minted aUSD and pays the flash mint fee to stability depositors (G factor)
+pub fn handler(ctx: Context<RepayFlashMint>, params: RepayFlashMintParams) -> Result<()> {
+    params.validate()?;
+    
+    require!(
+        ctx.accounts.state.load()?.flash_mint_outstanding == params.amount,
+        AerospacerProtocolError::FlashMintNotRepaid
+    );
+
+    // Fee rounds up so that tiny flash mints are not free
+    let fee = (params.amount as u128)
+        .checked_mul(FLASH_MINT_FEE_BPS as u128)
+        .ok_or(AerospacerProtocolError::OverflowError)?
+        .div_ceil(10_000) as u64;
+
+    require!(
+        ctx.accounts.payer_stablecoin_account.amount >= params.amount.saturating_add(fee),
+        AerospacerProtocolError::FlashMintNotRepaid
+    );
+
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.stable_coin_mint.to_account_info(),
+            from: ctx.accounts.payer_stablecoin_account.to_account_info(),
+            authority: ctx.accounts.payer.to_account_info(),
+        },
+    );
+    anchor_spl::token::burn(burn_ctx, params.amount)?;
+
+    if fee > 0 {
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.payer_stablecoin_account.to_account_info(),
+                to: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+                authority: ctx.accounts.payer.to_account_info(),
+            },
+        );
+        anchor_spl::token::transfer(transfer_ctx, fee)?;
+    }
+
+    let mut state = ctx.accounts.state.load_mut()?;
+    distribute_fee_yield_to_stakers(&mut state, fee)?;
+    state.flash_mint_outstanding = 0;
+
+    msg!("Flash mint repaid successfully");
+    msg!("Payer: {}", ctx.accounts.payer.key());
+    msg!("Amount: {} aUSD", params.amount);
+    msg!("Fee: {} aUSD", fee);
+
+    emit!(FlashMintRepaid {
+        payer: ctx.accounts.payer.key(),
+        amount: params.amount,
+        fee,
+    });
+
+    Ok(())
+}
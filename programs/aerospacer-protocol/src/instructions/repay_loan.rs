@@ -5,13 +5,27 @@ use crate::error::*;
 use crate::trove_management::*;
 use crate::account_management::*;
 use crate::oracle::*;
+use crate::events::{TroveOperation, TroveUpdated};
+use crate::liquidation_reserve::check_net_debt;
+use crate::validation::{ValidateParams, validate_amount, validate_denom, validate_optional_pubkey};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct RepayLoanParams {
     pub amount: u64,
     pub collateral_denom: String,
+    pub trove_id: u16, // Which of the owner's troves (0 = the wallet's first trove)
     pub prev_node_id: Option<Pubkey>,
     pub next_node_id: Option<Pubkey>,
+    pub memo: Option<[u8; 32]>, // Optional integrator memo, echoed in the emitted event
+}
+
+impl ValidateParams for RepayLoanParams {
+    fn validate(&self) -> Result<()> {
+        validate_amount(self.amount)?;
+        validate_denom(&self.collateral_denom)?;
+        validate_optional_pubkey(&self.prev_node_id)?;
+        validate_optional_pubkey(&self.next_node_id)
+    }
 }
 
 #[derive(Accounts)]
@@ -20,58 +34,61 @@ pub struct RepayLoan<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     
+    /// CHECK: Trove owner - the signer itself, or the owner who approved the signer via trove_delegate
+    pub trove_owner: UncheckedAccount<'info>,
+    
     #[account(
         mut,
-        seeds = [b"user_debt_amount", user.key().as_ref()],
+        seeds = [b"user_debt_amount", trove_owner.key().as_ref(), trove_id_seed(params.trove_id).as_slice()],
         bump,
-        constraint = user_debt_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
+        constraint = user_debt_amount.owner == trove_owner.key() @ AerospacerProtocolError::Unauthorized
     )]
     pub user_debt_amount: Account<'info, UserDebtAmount>,
 
     #[account(
         mut,
-        seeds = [b"user_collateral_amount", user.key().as_ref(), params.collateral_denom.as_bytes()],
+        seeds = [b"user_collateral_amount", trove_owner.key().as_ref(), trove_id_seed(params.trove_id).as_slice(), params.collateral_denom.as_bytes()],
         bump,
-        constraint = user_collateral_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
+        constraint = user_collateral_amount.owner == trove_owner.key() @ AerospacerProtocolError::Unauthorized
     )]
     pub user_collateral_amount: Account<'info, UserCollateralAmount>,
 
     #[account(
         mut,
-        seeds = [b"liquidity_threshold", user.key().as_ref()],
+        seeds = [b"liquidity_threshold", trove_owner.key().as_ref(), trove_id_seed(params.trove_id).as_slice()],
         bump,
-        constraint = liquidity_threshold.owner == user.key() @ AerospacerProtocolError::Unauthorized
+        constraint = liquidity_threshold.owner == trove_owner.key() @ AerospacerProtocolError::Unauthorized
     )]
     pub liquidity_threshold: Account<'info, LiquidityThreshold>,
     
     #[account(mut)]
-    pub state: Account<'info, StateAccount>,
+    pub state: AccountLoader<'info, StateAccount>,
     
     #[account(mut)]
     pub user_stablecoin_account: Account<'info, TokenAccount>,
     
     #[account(
         mut,
-        constraint = user_collateral_account.mint == collateral_mint.key() @ AerospacerProtocolError::InvalidMint
+        constraint = user_collateral_account.mint == collateral_mint.key() @ AerospacerProtocolError::InvalidMint,
+        // A full repayment releases the collateral here, so it must always be the owner's account
+        constraint = user_collateral_account.owner == trove_owner.key() @ AerospacerProtocolError::Unauthorized
     )]
     pub user_collateral_account: Account<'info, TokenAccount>,
 
     pub collateral_mint: Account<'info, Mint>,
     
     #[account(
-        init_if_needed,
-        payer = user,
-        token::mint = collateral_mint,
-        token::authority = protocol_collateral_account,
+        mut,
         seeds = [b"protocol_collateral_vault", params.collateral_denom.as_bytes()],
-        bump
+        bump,
+        constraint = protocol_collateral_account.mint == collateral_mint.key() @ AerospacerProtocolError::InvalidMint
     )]
     pub protocol_collateral_account: Account<'info, TokenAccount>,
 
     /// CHECK: Stable coin mint - used for burn (supply change) - validated against state
     #[account(
         mut,
-        constraint = stable_coin_mint.key() == state.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
     )]
     pub stable_coin_mint: UncheckedAccount<'info>,
 
@@ -81,7 +98,7 @@ pub struct RepayLoan<'info> {
         seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
         bump
     )]
-    pub total_collateral_amount: Account<'info, TotalCollateralAmount>,
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
 
     // Oracle context - UncheckedAccount to reduce stack usage
     /// CHECK: Our oracle program - validated against state in handler
@@ -99,33 +116,53 @@ pub struct RepayLoan<'info> {
     
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+    
+    // Keeper-refreshed price (see refresh_price) - read instead of the oracle while fresh, and the
+    // last-good price used if the oracle feed is stale (see StateAccount.oracle_fallback_enabled)
+    #[account(
+        seeds = [b"price_cache", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub price_cache: Option<Box<Account<'info, PriceCache>>>,
+    
+    // Delegate-signer path: required when user is not the trove owner
+    #[account(
+        seeds = [b"trove_delegate", trove_owner.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub trove_delegate: Option<Account<'info, TroveDelegate>>,
+    
+    /// Optional operation history, appended to when passed
+    #[account(
+        mut,
+        seeds = [b"trove_history", trove_owner.key().as_ref()],
+        bump
+    )]
+    pub trove_history: Option<Box<Account<'info, TroveHistory>>>,
 }
 
 pub fn handler(ctx: Context<RepayLoan>, params: RepayLoanParams) -> Result<()> {
+    params.validate()?;
+    
     // Validate oracle accounts
     require!(
-        ctx.accounts.oracle_program.key() == ctx.accounts.state.oracle_helper_addr,
+        ctx.accounts.oracle_program.key() == ctx.accounts.state.load()?.oracle_helper_addr,
         AerospacerProtocolError::Unauthorized
     );
     require!(
-        ctx.accounts.oracle_state.key() == ctx.accounts.state.oracle_state_addr,
+        ctx.accounts.oracle_state.key() == ctx.accounts.state.load()?.oracle_state_addr,
         AerospacerProtocolError::Unauthorized
     );
     
-    // Validate input parameters
-    require!(
-        params.amount > 0,
-        AerospacerProtocolError::InvalidAmount
-    );
-    
+    // Signer is the owner, or a delegate the owner approved (trove_delegate seeds bind both keys)
     require!(
-        !params.collateral_denom.is_empty(),
-        AerospacerProtocolError::InvalidAmount
+        ctx.accounts.user.key() == ctx.accounts.trove_owner.key() || ctx.accounts.trove_delegate.is_some(),
+        AerospacerProtocolError::Unauthorized
     );
     
     // Check if user has existing trove
     require!(
-        ctx.accounts.user_debt_amount.amount > 0,
+        ctx.accounts.user_debt_amount.is_active() && ctx.accounts.user_debt_amount.amount > 0,
         AerospacerProtocolError::TroveDoesNotExist
     );
     
@@ -135,28 +172,28 @@ pub fn handler(ctx: Context<RepayLoan>, params: RepayLoanParams) -> Result<()> {
         AerospacerProtocolError::InsufficientCollateral
     );
     
-    // Check if repayment amount doesn't exceed debt
+    // Check if repayment amount doesn't exceed debt (the liquidation reserve is only settled by close_trove)
     require!(
-        params.amount <= ctx.accounts.user_debt_amount.amount,
+        params.amount <= ctx.accounts.user_debt_amount.net_debt(),
         AerospacerProtocolError::InvalidAmount
     );
     
     // Create contexts in scoped block to reduce stack usage
     let result = {
         let mut trove_ctx = TroveContext {
-            user: ctx.accounts.user.clone(),
-            user_debt_amount: ctx.accounts.user_debt_amount.clone(),
-            liquidity_threshold: ctx.accounts.liquidity_threshold.clone(),
-            state: ctx.accounts.state.clone(),
+            user: &ctx.accounts.user,
+            user_debt_amount: &mut ctx.accounts.user_debt_amount,
+            liquidity_threshold: &mut ctx.accounts.liquidity_threshold,
+            state: &ctx.accounts.state,
         };
         
         let mut collateral_ctx = CollateralContext {
-            user: ctx.accounts.user.clone(),
-            user_collateral_amount: ctx.accounts.user_collateral_amount.clone(),
-            user_collateral_account: ctx.accounts.user_collateral_account.clone(),
-            protocol_collateral_account: ctx.accounts.protocol_collateral_account.clone(),
-            total_collateral_amount: ctx.accounts.total_collateral_amount.clone(),
-            token_program: ctx.accounts.token_program.clone(),
+            user: &ctx.accounts.user,
+            user_collateral_amount: &mut ctx.accounts.user_collateral_amount,
+            user_collateral_account: &ctx.accounts.user_collateral_account,
+            protocol_collateral_account: &ctx.accounts.protocol_collateral_account,
+            total_collateral_amount: &ctx.accounts.total_collateral_amount,
+            token_program: &ctx.accounts.token_program,
         };
         
         let oracle_ctx = OracleContext {
@@ -164,6 +201,18 @@ pub fn handler(ctx: Context<RepayLoan>, params: RepayLoanParams) -> Result<()> {
             oracle_state: ctx.accounts.oracle_state.to_account_info(),
             pyth_price_account: ctx.accounts.pyth_price_account.to_account_info(),
             clock: ctx.accounts.clock.to_account_info(),
+            max_price_age: PriceReadClass::Derisking.max_price_age(&*ctx.accounts.state.load()?),
+            fallback: PriceFallback::from_cache(
+                &*ctx.accounts.state.load()?,
+                ctx.accounts.price_cache.as_deref().map(|c| &**c),
+                &params.collateral_denom,
+            ),
+            cached_price: PriceData::from_cache(
+                &*ctx.accounts.state.load()?,
+                ctx.accounts.price_cache.as_deref().map(|c| &**c),
+                &params.collateral_denom,
+                PriceReadClass::Derisking.max_price_age(&*ctx.accounts.state.load()?),
+            )?,
         };
         
         // Use TroveManager for clean implementation
@@ -175,16 +224,20 @@ pub fn handler(ctx: Context<RepayLoan>, params: RepayLoanParams) -> Result<()> {
             ctx.bumps.protocol_collateral_account,
         )?;
         
-        // Update state before contexts are dropped
-        ctx.accounts.state.total_debt_amount = trove_ctx.state.total_debt_amount;
         
         Ok::<_, Error>(result)
     }?;
     
+    // A partial repayment may not leave a dust trove behind
+    check_net_debt(
+        result.new_debt_amount.saturating_sub(ctx.accounts.user_debt_amount.liquidation_reserve),
+        ctx.accounts.state.load()?.min_net_debt,
+    )?;
+    
     // CRITICAL: Validate ICR ordering if neighbor hints provided
     // Production clients MUST provide neighbor hints via remainingAccounts for proper sorted list maintenance
     // Pattern: [prev_LiquidityThreshold, next_LiquidityThreshold] or [prev_LT] or [next_LT] or []
-    // Optional for backward compatibility with tests, but REQUIRED in production
+    // Optional for backward compatibility with tests; mandatory while state.require_hints is set
     if !ctx.remaining_accounts.is_empty() {
         use crate::sorted_troves;
         
@@ -196,10 +249,11 @@ pub fn handler(ctx: Context<RepayLoan>, params: RepayLoanParams) -> Result<()> {
             let prev_threshold = LiquidityThreshold::try_deserialize(&mut &prev_data[..])?;
             let prev_owner = prev_threshold.owner;
             let prev_ratio = prev_threshold.ratio;
+            let prev_trove_id = prev_threshold.trove_id;
             drop(prev_data);
             
             // Verify this is a real PDA, not a fake account
-            sorted_troves::verify_liquidity_threshold_pda(prev_lt, prev_owner, ctx.program_id)?;
+            sorted_troves::verify_liquidity_threshold_pda(prev_lt, prev_owner, prev_trove_id, ctx.program_id)?;
             
             Some(prev_ratio)
         } else {
@@ -212,10 +266,11 @@ pub fn handler(ctx: Context<RepayLoan>, params: RepayLoanParams) -> Result<()> {
             let next_threshold = LiquidityThreshold::try_deserialize(&mut &next_data[..])?;
             let next_owner = next_threshold.owner;
             let next_ratio = next_threshold.ratio;
+            let next_trove_id = next_threshold.trove_id;
             drop(next_data);
             
             // Verify this is a real PDA, not a fake account
-            sorted_troves::verify_liquidity_threshold_pda(next_lt, next_owner, ctx.program_id)?;
+            sorted_troves::verify_liquidity_threshold_pda(next_lt, next_owner, next_trove_id, ctx.program_id)?;
             
             Some(next_ratio)
         } else {
@@ -226,15 +281,14 @@ pub fn handler(ctx: Context<RepayLoan>, params: RepayLoanParams) -> Result<()> {
         sorted_troves::validate_icr_ordering(result.new_icr, prev_icr, next_icr)?;
         msg!("✓ ICR ordering validated successfully");
     } else {
+        require!(
+            ctx.accounts.state.load()?.require_hints == 0 || result.new_debt_amount == 0,
+            AerospacerProtocolError::NeighborHintsRequired
+        );
         msg!("⚠ WARNING: No neighbor hints provided - skipping ICR ordering validation");
         msg!("⚠ Production clients MUST provide neighbor hints for sorted list integrity");
     }
     
-    // Update the actual accounts with the results
-    ctx.accounts.user_debt_amount.amount = result.new_debt_amount;
-    ctx.accounts.liquidity_threshold.ratio = result.new_icr;
-    ctx.accounts.user_collateral_amount.amount = result.new_collateral_amount;
-
     // NOTE: Sorted troves management moved off-chain
     // If debt is fully repaid, trove is automatically removed from off-chain sorted list
     if result.new_debt_amount == 0 {
@@ -259,5 +313,28 @@ pub fn handler(ctx: Context<RepayLoan>, params: RepayLoanParams) -> Result<()> {
     msg!("New ICR: {}", result.new_icr);
     msg!("Collateral amount: {}", result.new_collateral_amount);
     
+    emit!(TroveUpdated {
+        owner: ctx.accounts.trove_owner.key(),
+        trove_id: params.trove_id,
+        collateral_denom: params.collateral_denom.clone(),
+        operation: TroveOperation::RepayLoan,
+        amount: params.amount,
+        collateral_amount: result.new_collateral_amount,
+        debt_amount: result.new_debt_amount,
+        icr: result.new_icr,
+        tag: ctx.accounts.user_debt_amount.tag,
+        memo: params.memo,
+    });
+    
+    if let Some(trove_history) = ctx.accounts.trove_history.as_mut() {
+        trove_history.record(TroveHistoryEntry {
+            op: TroveHistoryOp::RepayLoan,
+            trove_id: params.trove_id,
+            amount: params.amount,
+            icr: result.new_icr,
+            slot: Clock::get()?.slot,
+        });
+    }
+    
     Ok(())
 }
\ No newline at end of file
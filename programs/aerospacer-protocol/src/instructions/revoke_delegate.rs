@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"trove_delegate", owner.key().as_ref(), trove_delegate.delegate.as_ref()],
+        bump,
+        constraint = trove_delegate.owner == owner.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub trove_delegate: Account<'info, TroveDelegate>,
+}
+
+/// Handler for revoke_delegate instruction
+/// Closing the TroveDelegate PDA removes the delegate-signer path and refunds its rent
+pub fn handler(ctx: Context<RevokeDelegate>) -> Result<()> {
+    msg!("Trove delegate revoked");
+    msg!("Owner: {}", ctx.accounts.owner.key());
+    msg!("Delegate: {}", ctx.accounts.trove_delegate.delegate);
+    
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::fees_integration::route_public_goods_via_cpi;
+use crate::validation::{ValidateParams, validate_denom};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RoutePublicGoodsParams {
+    pub denom: String,
+}
+
+impl ValidateParams for RoutePublicGoodsParams {
+    fn validate(&self) -> Result<()> {
+        validate_denom(&self.denom)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: RoutePublicGoodsParams)]
+pub struct RoutePublicGoods<'info> {
+    pub keeper: Signer<'info>,
+    
+    #[account(
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"public_goods_accrual", params.denom.as_bytes()],
+        bump
+    )]
+    pub public_goods_accrual: Account<'info, PublicGoodsAccrual>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol_collateral_vault", params.denom.as_bytes()],
+        bump
+    )]
+    pub protocol_collateral_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: aerospacer-fees program - validated against state
+    #[account(
+        constraint = fees_program.key() == state.load()?.fee_distributor_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub fees_program: AccountInfo<'info>,
+    
+    /// CHECK: Fees program's public goods config PDA - validated by the fees program
+    #[account(mut)]
+    pub fees_public_goods_config: AccountInfo<'info>,
+    
+    /// CHECK: Receiving token account - the fees program requires it to be owned by its public goods address
+    #[account(mut)]
+    pub public_goods_token_account: AccountInfo<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Handler for route_public_goods instruction
+/// Permissionless: the recipient is fixed by the fees program, so anyone may flush the booked
+/// public-goods share of a denom out of its collateral vault
+pub fn handler(ctx: Context<RoutePublicGoods>, params: RoutePublicGoodsParams) -> Result<()> {
+    params.validate()?;
+    
+    let amount = ctx.accounts.public_goods_accrual.amount;
+    require!(amount > 0, AerospacerProtocolError::InvalidAmount);
+    
+    let seeds = &[
+        b"protocol_collateral_vault".as_ref(),
+        params.denom.as_bytes(),
+        &[ctx.bumps.protocol_collateral_vault],
+    ];
+    
+    route_public_goods_via_cpi(
+        &ctx.accounts.fees_program,
+        &ctx.accounts.protocol_collateral_vault.to_account_info(),
+        &ctx.accounts.fees_public_goods_config,
+        &ctx.accounts.public_goods_token_account,
+        &ctx.accounts.token_program.to_account_info(),
+        amount,
+        &[&seeds[..]],
+    )?;
+    
+    let accrual = &mut ctx.accounts.public_goods_accrual;
+    accrual.amount = 0;
+    accrual.total_routed = accrual.total_routed
+        .checked_add(amount)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    
+    msg!("Public goods share routed");
+    msg!("Denom: {}", params.denom);
+    msg!("Amount: {}", amount);
+    msg!("Total routed: {}", accrual.total_routed);
+    
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::trove_management::distribute_liquidation_gains_to_stakers;
+use crate::events::{SeizureRoute, UndistributedSeizureRouted};
+use crate::validation::{ValidateParams, validate_amount, validate_denom};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RouteUndistributedSeizureParams {
+    pub denom: String,
+    pub amount: u64,
+    pub route: SeizureRoute,
+}
+
+impl ValidateParams for RouteUndistributedSeizureParams {
+    fn validate(&self) -> Result<()> {
+        validate_denom(&self.denom)?;
+        validate_amount(self.amount)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: RouteUndistributedSeizureParams)]
+pub struct RouteUndistributedSeizure<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"undistributed_seizure", params.denom.as_bytes()],
+        bump
+    )]
+    pub undistributed_seizure: Account<'info, UndistributedSeizure>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol_collateral_vault", params.denom.as_bytes()],
+        bump
+    )]
+    pub protocol_collateral_vault: Account<'info, TokenAccount>,
+    
+    // Receiving token account - required for Backstop and Auction
+    #[account(
+        mut,
+        constraint = destination.mint == protocol_collateral_vault.mint @ AerospacerProtocolError::InvalidMint
+    )]
+    pub destination: Option<Account<'info, TokenAccount>>,
+    
+    // Denom's stability pool snapshot - required for StabilityPool
+    #[account(
+        mut,
+        seeds = [b"stability_pool_snapshot", params.denom.as_bytes()],
+        bump
+    )]
+    pub stability_pool_snapshot: Option<Account<'info, StabilityPoolSnapshot>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Handler for route_undistributed_seizure instruction
+/// Moves collateral booked as undistributed out of the vault (backstop/auction) or hands it to
+/// the stability pool once it has depositors again
+pub fn handler(ctx: Context<RouteUndistributedSeizure>, params: RouteUndistributedSeizureParams) -> Result<()> {
+    params.validate()?;
+    
+    require!(
+        params.amount <= ctx.accounts.undistributed_seizure.amount,
+        AerospacerProtocolError::InvalidAmount
+    );
+    
+    let destination = match params.route {
+        SeizureRoute::Backstop | SeizureRoute::Auction => {
+            let destination = ctx.accounts.destination
+                .as_ref()
+                .ok_or(AerospacerProtocolError::InvalidList)?;
+            
+            let seeds = &[
+                b"protocol_collateral_vault".as_ref(),
+                params.denom.as_bytes(),
+                &[ctx.bumps.protocol_collateral_vault],
+            ];
+            let signer_seeds = &[&seeds[..]];
+            
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.protocol_collateral_vault.to_account_info(),
+                    to: destination.to_account_info(),
+                    authority: ctx.accounts.protocol_collateral_vault.to_account_info(),
+                },
+                signer_seeds,
+            );
+            anchor_spl::token::transfer(transfer_ctx, params.amount)?;
+            destination.key()
+        }
+        SeizureRoute::StabilityPool => {
+            let snapshot = ctx.accounts.stability_pool_snapshot
+                .as_mut()
+                .ok_or(AerospacerProtocolError::InvalidList)?;
+            let mut state = ctx.accounts.state.load_mut()?;
+            require!(
+                state.total_stake_amount > 0,
+                AerospacerProtocolError::InvalidAmount
+            );
+            
+            // No debt is absorbed, so P is unchanged and only the S factor grows.
+            // The collateral stays in the vault, claimable via withdraw_liquidation_gains.
+            distribute_liquidation_gains_to_stakers(
+                &mut state,
+                &vec![(params.denom.clone(), params.amount)],
+                0,
+                std::slice::from_mut(&mut **snapshot),
+            )?;
+            Pubkey::default()
+        }
+    };
+    
+    let seizure = &mut ctx.accounts.undistributed_seizure;
+    seizure.amount -= params.amount;
+    seizure.total_routed = seizure.total_routed
+        .checked_add(params.amount)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    
+    msg!("Undistributed seizure routed");
+    msg!("Denom: {}", params.denom);
+    msg!("Route: {:?}", params.route);
+    msg!("Amount: {}", params.amount);
+    msg!("Remaining: {}", seizure.amount);
+    
+    emit!(UndistributedSeizureRouted {
+        denom: params.denom,
+        route: params.route,
+        amount: params.amount,
+        destination,
+        remaining: seizure.amount,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::validation::{ValidateParams, validate_denom};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetCollateralParamsParams {
+    pub denom: String,
+    pub minimum_collateral_ratio: u64, // Micro-percent (115_000_000 = 115%)
+    pub liquidation_threshold: u64,    // Micro-percent (110_000_000 = 110%)
+    pub protocol_fee: u8,              // Opening/borrowing fee percentage on the denom
+    pub liquidation_penalty_bps: u16,  // Seized on top of the debt's worth (0 = seize all collateral)
+}
+
+impl ValidateParams for SetCollateralParamsParams {
+    fn validate(&self) -> Result<()> {
+        validate_denom(&self.denom)?;
+        CollateralConfig::validate_ratios(self.minimum_collateral_ratio, self.liquidation_threshold)?;
+        CollateralConfig::validate_protocol_fee(self.protocol_fee)?;
+        CollateralConfig::validate_liquidation_penalty(self.liquidation_penalty_bps)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: SetCollateralParamsParams)]
+pub struct SetCollateralParams<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    // Only registered denoms (denoms listed before register_collateral already have their total)
+    #[account(
+        seeds = [b"total_collateral_amount", params.denom.as_bytes()],
+        bump
+    )]
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + CollateralConfig::LEN,
+        seeds = [b"collateral_config", params.denom.as_bytes()],
+        bump
+    )]
+    pub collateral_config: Account<'info, CollateralConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for set_collateral_params instruction
+/// Sets a denom's MCR, liquidation threshold, opening/borrowing fee and liquidation penalty; also creates the config for denoms registered
+/// before the collateral registry existed, which cannot be used by trove instructions until then
+pub fn handler(ctx: Context<SetCollateralParams>, params: SetCollateralParamsParams) -> Result<()> {
+    params.validate()?;
+
+    let config = &mut ctx.accounts.collateral_config;
+    config.denom = params.denom.clone();
+    config.minimum_collateral_ratio = params.minimum_collateral_ratio;
+    config.liquidation_threshold = params.liquidation_threshold;
+    config.protocol_fee = params.protocol_fee;
+    config.liquidation_penalty_bps = params.liquidation_penalty_bps;
+
+    msg!("Collateral parameters updated for {}", params.denom);
+    msg!("MCR: {} ({}%)", config.minimum_collateral_ratio, config.minimum_collateral_ratio / 1_000_000);
+    msg!("Liquidation threshold: {} ({}%)", config.liquidation_threshold, config.liquidation_threshold / 1_000_000);
+    msg!("Protocol fee: {}%", config.protocol_fee);
+    msg!("Liquidation penalty: {} bps", config.liquidation_penalty_bps);
+
+    Ok(())
+}
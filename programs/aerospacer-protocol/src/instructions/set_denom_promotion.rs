@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::AerospacerProtocolError;
+use crate::validation::{ValidateParams, validate_denom};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetDenomPromotionParams {
+    pub denom: String,
+    pub fee_free_until_slot: u64,   // Borrowing fees on the denom are waived before this slot (0 ends the promotion)
+}
+
+impl ValidateParams for SetDenomPromotionParams {
+    fn validate(&self) -> Result<()> {
+        validate_denom(&self.denom)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: SetDenomPromotionParams)]
+pub struct SetDenomPromotion<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + DenomPromotion::LEN,
+        seeds = [b"denom_promotion", params.denom.as_bytes()],
+        bump
+    )]
+    pub denom_promotion: Account<'info, DenomPromotion>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for set_denom_promotion instruction
+/// The promotion expires on its own once the slot deadline passes; no admin action is needed to end it
+pub fn handler(ctx: Context<SetDenomPromotion>, params: SetDenomPromotionParams) -> Result<()> {
+    params.validate()?;
+    
+    let current_slot = Clock::get()?.slot;
+    let promotion = &mut ctx.accounts.denom_promotion;
+    promotion.denom = params.denom.clone();
+    promotion.fee_free_until_slot = params.fee_free_until_slot;
+    
+    msg!("Denom promotion updated for {}", params.denom);
+    msg!("Borrowing fees waived until slot: {}", params.fee_free_until_slot);
+    msg!("Active: {} (current slot {})", promotion.is_active(current_slot), current_slot);
+    
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::*;
+use crate::trove_management::redistribute_debt_and_collateral;
+use crate::events::LiquidationAuctionSettled;
+use crate::validation::{ValidateParams, validate_denom, validate_pubkey};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SettleParams {
+    pub target_user: Pubkey,       // Owner of the auctioned trove
+    pub collateral_denom: String,
+    pub trove_id: u16,
+}
+
+impl ValidateParams for SettleParams {
+    fn validate(&self) -> Result<()> {
+        validate_pubkey(&self.target_user)?;
+        validate_denom(&self.collateral_denom)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: SettleParams)]
+pub struct Settle<'info> {
+    pub settler: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        mut,
+        close = initiator,
+        seeds = [b"liquidation_auction", params.target_user.as_ref(), trove_id_seed(params.trove_id).as_slice(), params.collateral_denom.as_bytes()],
+        bump,
+        constraint = auction.denom == params.collateral_denom @ AerospacerProtocolError::InvalidDenom
+    )]
+    pub auction: Account<'info, LiquidationAuction>,
+
+    /// CHECK: Receives the auction's rent - must be the account that started it
+    #[account(
+        mut,
+        address = auction.initiator @ AerospacerProtocolError::Unauthorized
+    )]
+    pub initiator: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_collateral_vault", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub protocol_collateral_vault: Account<'info, TokenAccount>,
+
+    /// Trove owner's collateral account - required when the debt was fully raised with collateral left
+    #[account(
+        mut,
+        constraint = owner_collateral_account.owner == params.target_user @ AerospacerProtocolError::Unauthorized,
+        constraint = owner_collateral_account.mint == protocol_collateral_vault.mint @ AerospacerProtocolError::InvalidMint
+    )]
+    pub owner_collateral_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Handler for settle instruction
+///
+/// Closes a liquidation auction once it is sold out, has raised the whole debt, or has run
+/// its course. Debt the bids did not raise is redistributed to active troves together with the
+/// unsold collateral, as the redistribution path would have done. If the debt was raised in full,
+/// unsold collateral is surplus and goes back to the trove owner. Anyone may settle.
+pub fn handler(ctx: Context<Settle>, params: SettleParams) -> Result<()> {
+    params.validate()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let auction = &ctx.accounts.auction;
+    require!(
+        auction.is_settleable(now),
+        AerospacerProtocolError::AuctionNotSettleable
+    );
+
+    let debt_remaining = auction.debt_remaining;
+    let collateral_remaining = auction.collateral_remaining;
+    let debt_raised = auction.debt_raised;
+
+    let (debt_redistributed, collateral_redistributed, surplus_returned) = if debt_remaining > 0 {
+        redistribute_debt_and_collateral(
+            &mut *ctx.accounts.total_collateral_amount.load_mut()?,
+            &mut *ctx.accounts.state.load_mut()?,
+            debt_remaining,
+            collateral_remaining,
+        )?;
+        (debt_remaining, collateral_remaining, 0)
+    } else if collateral_remaining > 0 {
+        let destination = ctx.accounts.owner_collateral_account
+            .as_ref()
+            .ok_or(AerospacerProtocolError::AuctionSurplusAccountRequired)?;
+        let vault_seeds: &[&[u8]] = &[
+            b"protocol_collateral_vault",
+            params.collateral_denom.as_bytes(),
+            &[ctx.bumps.protocol_collateral_vault],
+        ];
+        let signer: &[&[&[u8]]] = &[vault_seeds];
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.protocol_collateral_vault.to_account_info(),
+                to: destination.to_account_info(),
+                authority: ctx.accounts.protocol_collateral_vault.to_account_info(),
+            },
+            signer,
+        );
+        anchor_spl::token::transfer(transfer_ctx, collateral_remaining)?;
+        (0, 0, collateral_remaining)
+    } else {
+        (0, 0, 0)
+    };
+
+    msg!("Liquidation auction settled");
+    msg!("Trove: {} #{}", params.target_user, params.trove_id);
+    msg!("Raised: {} aUSD", debt_raised);
+    msg!("Redistributed: {} debt, {} collateral", debt_redistributed, collateral_redistributed);
+    msg!("Surplus returned: {}", surplus_returned);
+
+    emit!(LiquidationAuctionSettled {
+        owner: params.target_user,
+        trove_id: params.trove_id,
+        collateral_denom: params.collateral_denom,
+        debt_raised,
+        debt_redistributed,
+        collateral_redistributed,
+        surplus_returned,
+    });
+
+    Ok(())
+}
@@ -3,10 +3,28 @@ use anchor_spl::token::{Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::utils::*;
 use crate::error::*;
+use crate::trove_management::issue_reward_emissions;
+use crate::events::{StakeOperation, StakeUpdated};
+use crate::validation::{ValidateParams, validate_min_amount, validate_pubkey};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct StakeParams {
     pub amount: u64, // Equivalent to Uint256
+    pub compound_gains: bool, // Roll settled aUSD fee yield into the deposit (amount may then be 0)
+    pub frontend: Option<FrontendTag>, // Frontend that onboarded the deposit (only applied to a fresh deposit)
+}
+
+impl ValidateParams for StakeParams {
+    fn validate(&self) -> Result<()> {
+        // A pure compounding call (amount = 0) only rolls the fee yield into the deposit
+        if !self.compound_gains || self.amount > 0 {
+            validate_min_amount(self.amount, MINIMUM_LOAN_AMOUNT, AerospacerProtocolError::InvalidAmount)?; // Use same minimum as loans
+        }
+        if let Some(frontend) = &self.frontend {
+            validate_pubkey(&frontend.operator)?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
@@ -25,7 +43,7 @@ pub struct Stake<'info> {
     pub user_stake_amount: Account<'info, UserStakeAmount>,
 
     #[account(mut)]
-    pub state: Account<'info, StateAccount>,
+    pub state: AccountLoader<'info, StateAccount>,
 
     // Token accounts for staking
     #[account(
@@ -34,59 +52,107 @@ pub struct Stake<'info> {
     )]
     pub user_stablecoin_account: Account<'info, TokenAccount>,
 
+    /// Holds exactly the staked aUSD, apart from the protocol's own stablecoin vault
     #[account(
         init_if_needed,
         payer = user,
         token::mint = stable_coin_mint,
-        token::authority = protocol_stablecoin_vault,
+        token::authority = stability_pool_vault,
+        seeds = [b"stability_pool_vault"],
+        bump
+    )]
+    pub stability_pool_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Protocol stablecoin vault PDA - holds the fee yield moved into the pool when compounding
+    #[account(
+        mut,
         seeds = [b"protocol_stablecoin_vault"],
         bump
     )]
-    pub protocol_stablecoin_vault: Account<'info, TokenAccount>,
+    pub protocol_stablecoin_vault: AccountInfo<'info>,
 
     /// CHECK: This is the stable coin mint account
     #[account(
-        constraint = stable_coin_mint.key() == state.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
     )]
     pub stable_coin_mint: UncheckedAccount<'info>,
 
+    /// Reward emission schedule - required once emissions are enabled so that emissions
+    /// accrued at the old total stake are credited before it changes
+    #[account(
+        mut,
+        seeds = [b"reward_emission"],
+        bump
+    )]
+    pub reward_emission: Option<Account<'info, RewardEmission>>,
+
+    /// Optional position summary, refreshed when passed
+    #[account(
+        mut,
+        seeds = [b"user_index", user.key().as_ref()],
+        bump
+    )]
+    pub user_index: Option<Account<'info, UserIndex>>,
+
+    /// Registered frontend - required when params.frontend is set
+    #[account(
+        mut,
+        seeds = [b"frontend", frontend.operator.as_ref()],
+        bump
+    )]
+    pub frontend: Option<Account<'info, Frontend>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
-}
 
+    // remaining_accounts must contain, for every collateral denom the pool has paid out (non-zero S):
+    // [StabilityPoolSnapshot, UserCollateralSnapshot] (the UserCollateralSnapshot writable).
+    // Gains accrued on the old deposit are credited to pending_collateral_gain before the
+    // P snapshot moves; a UserCollateralSnapshot that does not exist yet is created on the fly at
+    // the current S, paid for by the user. Denoms still at S = 0 may be passed too, so the
+    // deposit is snapshotted before their first gain.
+}
 
+// Accounts per collateral denom in remaining_accounts
+const ACCOUNTS_PER_DENOM: usize = 2;
 
-pub fn handler(ctx: Context<Stake>, params: StakeParams) -> Result<()> {
-    // Validate input parameters
-    require!(
-        params.amount > 0,
-        AerospacerProtocolError::InvalidAmount
-    );
-    
-    require!(
-        params.amount >= MINIMUM_LOAN_AMOUNT, // Use same minimum as loans
-        AerospacerProtocolError::InvalidAmount
-    );
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, Stake<'info>>, params: StakeParams) -> Result<()> {
+    params.validate()?;
     
-    // Check if user has sufficient stablecoins
-    require!(
-        ctx.accounts.user_stablecoin_account.amount >= params.amount,
-        AerospacerProtocolError::InsufficientCollateral
-    );
+    if params.amount > 0 {
+        // Check if user has sufficient stablecoins
+        require!(
+            ctx.accounts.user_stablecoin_account.amount >= params.amount,
+            AerospacerProtocolError::InsufficientCollateral
+        );
+    }
 
     let user_stake_amount = &mut ctx.accounts.user_stake_amount;
-    let state = &mut ctx.accounts.state;
-
-    // Transfer stablecoins from user to protocol vault
-    let transfer_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.user_stablecoin_account.to_account_info(),
-            to: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
-        },
-    );
-    anchor_spl::token::transfer(transfer_ctx, params.amount)?;
+    let mut state = ctx.accounts.state.load_mut()?;
+
+    // Credit reward emissions accrued at the old total stake before it changes
+    match ctx.accounts.reward_emission.as_mut() {
+        Some(reward_emission) => {
+            issue_reward_emissions(&mut state, reward_emission, Clock::get()?.unix_timestamp)?;
+        }
+        None => require!(
+            state.reward_emissions_enabled == 0,
+            AerospacerProtocolError::MissingRewardEmission
+        ),
+    }
+
+    // Transfer stablecoins from user to the stability pool vault
+    if params.amount > 0 {
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_stablecoin_account.to_account_info(),
+                to: ctx.accounts.stability_pool_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        );
+        anchor_spl::token::transfer(transfer_ctx, params.amount)?;
+    }
 
     // CRITICAL FIX: Compound existing deposit before updating snapshots
     // This ensures amount and p_snapshot stay in sync after liquidations
@@ -96,12 +162,17 @@ pub fn handler(ctx: Context<Stake>, params: StakeParams) -> Result<()> {
             user_stake_amount.amount,
             user_stake_amount.p_snapshot,
             state.p_factor,
+            user_stake_amount.scale_snapshot,
+            state.current_scale,
+            user_stake_amount.epoch_snapshot,
+            state.epoch,
         )?;
         
         msg!("Compounding existing deposit:");
         msg!("  Original deposit: {}", user_stake_amount.amount);
         msg!("  P_snapshot (old): {}", user_stake_amount.p_snapshot);
         msg!("  P_current: {}", state.p_factor);
+        msg!("  Scale (snapshot/current): {}/{}", user_stake_amount.scale_snapshot, state.current_scale);
         msg!("  Compounded: {}", compounded);
         
         compounded
@@ -110,25 +181,216 @@ pub fn handler(ctx: Context<Stake>, params: StakeParams) -> Result<()> {
         user_stake_amount.amount
     };
     
+    // Settle aUSD fee yield earned under the old snapshots before they are refreshed
+    let fee_gain = settle_fee_yield(user_stake_amount, &state)?;
+    if fee_gain > 0 {
+        msg!("  Fee yield settled: {} aUSD (pending: {})", fee_gain, user_stake_amount.pending_fee_gain);
+    }
+    let reward_gain = settle_reward_emissions(user_stake_amount, &state)?;
+    if reward_gain > 0 {
+        msg!("  Reward emissions settled: {} (pending: {})", reward_gain, user_stake_amount.pending_reward_gain);
+    }
+    settle_collateral_gains(
+        user_stake_amount,
+        &state,
+        ctx.remaining_accounts,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    
+    // The fee yield sits in the protocol stablecoin vault; compounding moves it into the pool
+    let compounded_gains = if params.compound_gains {
+        let gains = user_stake_amount.pending_fee_gain;
+        user_stake_amount.pending_fee_gain = 0;
+        gains
+    } else {
+        0
+    };
+    if compounded_gains > 0 {
+        let vault_seeds: &[&[u8]] = &[b"protocol_stablecoin_vault", &[ctx.bumps.protocol_stablecoin_vault]];
+        let signer: &[&[&[u8]]] = &[vault_seeds];
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+                to: ctx.accounts.stability_pool_vault.to_account_info(),
+                authority: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+            },
+            signer,
+        );
+        anchor_spl::token::transfer(transfer_ctx, compounded_gains)?;
+    }
+    // Frontend attribution is fixed while a deposit (or unclaimed rewards) exists
+    let fresh_deposit = current_deposit == 0 && user_stake_amount.pending_reward_gain == 0;
+    match params.frontend {
+        Some(tag) if fresh_deposit => {
+            let frontend = ctx.accounts.frontend
+                .as_mut()
+                .ok_or(AerospacerProtocolError::UnknownFrontend)?;
+            frontend.validate_tag(&tag)?;
+            frontend.tagged_deposits = safe_add(frontend.tagged_deposits, 1)?;
+            user_stake_amount.frontend = tag.operator;
+            msg!("  Frontend: {} (kickback {} bps)", tag.operator, tag.kickback_rate_bps);
+        }
+        None if fresh_deposit => {
+            user_stake_amount.frontend = Pubkey::default();
+        }
+        Some(_) => {
+            msg!("  Deposit already open - keeping frontend {}", user_stake_amount.frontend);
+        }
+        None => {}
+    }
+    
+    let added_stake = safe_add(params.amount, compounded_gains)?;
+    require!(
+        added_stake > 0,
+        AerospacerProtocolError::InvalidAmount
+    );
+    
     // Update user stake amount with compounded value + new stake
     user_stake_amount.owner = ctx.accounts.user.key();
-    user_stake_amount.amount = safe_add(current_deposit, params.amount)?;
+    user_stake_amount.amount = safe_add(current_deposit, added_stake)?;
     
     // SNAPSHOT: Update to current P factor (amount is now in current scale)
     user_stake_amount.p_snapshot = state.p_factor;
     user_stake_amount.epoch_snapshot = state.epoch;
+    user_stake_amount.scale_snapshot = state.current_scale;
+    user_stake_amount.g_snapshot = state.g_factor;
+    user_stake_amount.reward_g_snapshot = state.reward_g_factor;
     user_stake_amount.last_update_block = Clock::get()?.slot;
 
     // Update state
-    state.total_stake_amount = safe_add(state.total_stake_amount, params.amount)?;
+    state.total_stake_amount = safe_add(state.total_stake_amount, added_stake)?;
+    check_stability_pool_backing(&ctx.accounts.stability_pool_vault.to_account_info(), state.total_stake_amount)?;
 
     msg!("Staked successfully (snapshot captured)");
     msg!("User: {}", ctx.accounts.user.key());
     msg!("Amount: {} aUSD", params.amount);
+    msg!("Compounded fee yield: {} aUSD", compounded_gains);
     msg!("Total staked: {} aUSD", user_stake_amount.amount);
     msg!("Total protocol stake: {} aUSD", state.total_stake_amount);
     msg!("P snapshot: {}", user_stake_amount.p_snapshot);
     msg!("Epoch snapshot: {}", user_stake_amount.epoch_snapshot);
+    msg!("Scale snapshot: {}", user_stake_amount.scale_snapshot);
+
+    emit!(StakeUpdated {
+        user: ctx.accounts.user.key(),
+        operation: StakeOperation::Stake,
+        amount: params.amount,
+        compounded_gains,
+        deposit_amount: user_stake_amount.amount,
+        total_stake_amount: state.total_stake_amount,
+        p_factor: state.p_factor,
+        current_scale: state.current_scale,
+        epoch: state.epoch,
+    });
+
+    if let Some(user_index) = ctx.accounts.user_index.as_mut() {
+        user_index.sync_stake(user_stake_amount);
+        user_index.last_update_slot = Clock::get()?.slot;
+    }
+
+    Ok(())
+}
+
+/// Credit the collateral gains the current deposit earned under its old P snapshot to each
+/// passed denom's `pending_collateral_gain`, moving the S snapshots to the current S factor
+fn settle_collateral_gains<'info>(
+    user_stake_amount: &UserStakeAmount,
+    state: &StateAccount,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    user: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    require!(
+        remaining_accounts.len().is_multiple_of(ACCOUNTS_PER_DENOM),
+        AerospacerProtocolError::InvalidList
+    );
+    let user_key = user.key();
+    let mut settled_denoms: Vec<String> = Vec::new();
+    let mut gaining_denoms = 0u8;
+
+    for chunk in remaining_accounts.chunks(ACCOUNTS_PER_DENOM) {
+        let (pool_snapshot_info, user_snapshot_info) = (&chunk[0], &chunk[1]);
+
+        // SECURITY: The pool snapshot is program-owned and determines the denom of this pair
+        require!(
+            pool_snapshot_info.owner == &crate::ID,
+            AerospacerProtocolError::InvalidList
+        );
+        let pool_snapshot = {
+            let data = pool_snapshot_info.try_borrow_data()?;
+            StabilityPoolSnapshot::try_deserialize(&mut &data[..])?
+        };
+        let denom = pool_snapshot.denom.clone();
+        require!(
+            !settled_denoms.contains(&denom),
+            AerospacerProtocolError::InvalidList
+        );
+
+        // SECURITY: Both accounts must be the canonical PDAs for this denom
+        let (expected_pool_snapshot_pda, _bump) = Pubkey::find_program_address(
+            &StabilityPoolSnapshot::seeds(&denom),
+            &crate::ID,
+        );
+        let (expected_user_snapshot_pda, user_snapshot_bump) = Pubkey::find_program_address(
+            &UserCollateralSnapshot::seeds(&user_key, &denom),
+            &crate::ID,
+        );
+        require!(
+            expected_pool_snapshot_pda == *pool_snapshot_info.key
+                && expected_user_snapshot_pda == *user_snapshot_info.key
+                && user_snapshot_info.is_writable,
+            AerospacerProtocolError::InvalidList
+        );
+
+        let mut user_collateral_snapshot = if user_snapshot_info.owner == &crate::ID {
+            let data = user_snapshot_info.try_borrow_data()?;
+            let snapshot = UserCollateralSnapshot::try_deserialize(&mut &data[..])?;
+            require!(
+                snapshot.owner == user_key,
+                AerospacerProtocolError::Unauthorized
+            );
+            snapshot
+        } else {
+            create_program_pda(
+                user,
+                user_snapshot_info,
+                system_program,
+                8 + UserCollateralSnapshot::LEN,
+                &[b"user_collateral_snapshot", user_key.as_ref(), denom.as_bytes(), &[user_snapshot_bump]],
+            )?;
+            // SECURITY: Never at S = 0, which would credit the pool's whole gain history
+            UserCollateralSnapshot::starting_at(user_key, &pool_snapshot, state.epoch)
+        };
+
+        let (collateral_gain, total_gain) = settle_collateral_gain(
+            user_stake_amount,
+            &mut user_collateral_snapshot,
+            pool_snapshot.s_factor,
+            state.epoch,
+        )?;
+        user_collateral_snapshot.pending_collateral_gain = total_gain;
+        msg!("  Collateral gain settled - {}: {} (pending: {})", denom, collateral_gain, total_gain);
+
+        {
+            let mut data = user_snapshot_info.try_borrow_mut_data()?;
+            let mut writer: &mut [u8] = &mut data;
+            user_collateral_snapshot.try_serialize(&mut writer)?;
+        }
+
+        if pool_snapshot.s_factor > 0 {
+            gaining_denoms += 1;
+        }
+        settled_denoms.push(denom);
+    }
+
+    // SECURITY: The P snapshot is about to move, so every denom with gains must be settled
+    // first - a skipped one would lose them or be valued against the new deposit
+    require!(
+        gaining_denoms >= state.gaining_denoms,
+        AerospacerProtocolError::MissingCollateralSnapshot
+    );
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,393 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::*;
+use crate::error::*;
+use crate::oracle::{OracleContext, PriceCalculator, PriceReadClass};
+use crate::trove_management::{apply_pending_rewards, auction_lot_prices, split_pro_rata, value_collateral_legs};
+use crate::utils::{create_program_pda, is_liquidatable_icr};
+use crate::events::LiquidationAuctionStarted;
+use crate::liquidation_log::{log_liquidations, LiquidationRecord};
+use crate::liquidation_reserve::pay_liquidation_reserve;
+use crate::trove_archive::NOOP_PROGRAM_ID;
+use crate::validation::{ValidateParams, validate_denom, validate_pubkey};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StartAuctionParams {
+    pub target_user: Pubkey,
+    pub collateral_denom: String,
+    pub trove_id: u16, // Which of the owner's troves (0 = the wallet's first trove)
+}
+
+impl ValidateParams for StartAuctionParams {
+    fn validate(&self) -> Result<()> {
+        validate_pubkey(&self.target_user)?;
+        validate_denom(&self.collateral_denom)
+    }
+}
+
+// Accounts per other collateral leg of a multi-denom trove: [UserCollateralAmount (writable),
+// TotalCollateralAmount, pyth_price_account, LiquidationAuction PDA of the leg (writable)]
+pub const ACCOUNTS_PER_AUCTIONED_LEG: usize = 4;
+
+#[derive(Accounts)]
+#[instruction(params: StartAuctionParams)]
+pub struct StartAuction<'info> {
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"total_collateral_amount", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub total_collateral_amount: AccountLoader<'info, TotalCollateralAmount>,
+
+    #[account(
+        seeds = [b"collateral_config", params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub collateral_config: Box<Account<'info, CollateralConfig>>,
+
+    // Target trove accounts
+    #[account(
+        mut,
+        seeds = [b"user_debt_amount", params.target_user.as_ref(), trove_id_seed(params.trove_id).as_slice()],
+        bump,
+        constraint = user_debt_amount.owner == params.target_user @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_debt_amount: Account<'info, UserDebtAmount>,
+
+    #[account(
+        mut,
+        seeds = [b"user_collateral_amount", params.target_user.as_ref(), trove_id_seed(params.trove_id).as_slice(), params.collateral_denom.as_bytes()],
+        bump,
+        constraint = user_collateral_amount.owner == params.target_user @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_collateral_amount: Account<'info, UserCollateralAmount>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_threshold", params.target_user.as_ref(), trove_id_seed(params.trove_id).as_slice()],
+        bump,
+        constraint = liquidity_threshold.owner == params.target_user @ AerospacerProtocolError::Unauthorized
+    )]
+    pub liquidity_threshold: Account<'info, LiquidityThreshold>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + LiquidationAuction::LEN,
+        seeds = [b"liquidation_auction", params.target_user.as_ref(), trove_id_seed(params.trove_id).as_slice(), params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub auction: Account<'info, LiquidationAuction>,
+
+    /// CHECK: Our oracle program - validated against state
+    #[account(
+        constraint = oracle_program.key() == state.load()?.oracle_helper_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_program: AccountInfo<'info>,
+
+    /// CHECK: Oracle state account - validated against state
+    #[account(
+        mut,
+        constraint = oracle_state.key() == state.load()?.oracle_state_addr @ AerospacerProtocolError::Unauthorized
+    )]
+    pub oracle_state: AccountInfo<'info>,
+
+    /// CHECK: Pyth price account for collateral price feed
+    pub pyth_price_account: AccountInfo<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    /// Liquidator whitelist - required while state.permissioned_liquidation is set
+    #[account(
+        seeds = [b"liquidator_whitelist"],
+        bump
+    )]
+    pub liquidator_whitelist: Option<Box<Account<'info, LiquidatorWhitelist>>>,
+
+    /// Optional position summary, refreshed when passed
+    #[account(
+        mut,
+        seeds = [b"user_index", params.target_user.as_ref()],
+        bump
+    )]
+    pub user_index: Option<Account<'info, UserIndex>>,
+
+    /// CHECK: SPL noop program - optional; liquidated troves are logged through it when passed
+    #[account(address = NOOP_PROGRAM_ID @ AerospacerProtocolError::InvalidAddress)]
+    pub noop_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Liquidation reserve vault PDA - the trove's reserve is paid from it to the initiator
+    #[account(
+        mut,
+        seeds = [b"liquidation_reserve_vault"],
+        bump
+    )]
+    pub liquidation_reserve_vault: AccountInfo<'info>,
+
+    /// CHECK: This is the stable coin mint account
+    #[account(
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub stable_coin_mint: UncheckedAccount<'info>,
+
+    /// Initiator's aUSD ATA for the trove's liquidation reserve, created when missing - required when it has one
+    #[account(
+        init_if_needed,
+        payer = initiator,
+        associated_token::mint = stable_coin_mint,
+        associated_token::authority = initiator
+    )]
+    pub initiator_stablecoin_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for start_auction instruction
+///
+/// Third liquidation path, for a liquidatable trove whose debt exceeds the stability pool by
+/// AUCTION_POOL_COVERAGE_FACTOR: instead of socializing most of it through redistribution, the
+/// trove is closed as liquidated and its collateral is put up for a Dutch auction. The collateral
+/// stays in the protocol vault, booked on the auction, and its debt stays in total_debt_amount
+/// until bids burn it or settle redistributes what is left.
+///
+/// A multi-denom trove passes every other leg in remaining_accounts (ACCOUNTS_PER_AUCTIONED_LEG).
+/// All legs count towards the ICR and each becomes a lot of its own, carrying the share of the
+/// debt its collateral is worth.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, StartAuction<'info>>, params: StartAuctionParams) -> Result<()> {
+    params.validate()?;
+
+    // Starting an auction is a liquidation - permissioned mode applies
+    LiquidatorWhitelist::authorize(
+        &*ctx.accounts.state.load()?,
+        ctx.accounts.liquidator_whitelist.as_deref().map(|whitelist| &**whitelist),
+        &ctx.accounts.initiator.key(),
+    )?;
+
+    require!(
+        ctx.accounts.user_collateral_amount.denom == params.collateral_denom,
+        AerospacerProtocolError::InvalidAmount
+    );
+
+    // Bring the trove up to date so the auction carries its redistribution share
+    apply_pending_rewards(
+        &mut ctx.accounts.user_debt_amount,
+        &mut ctx.accounts.user_collateral_amount,
+        &*ctx.accounts.total_collateral_amount.load()?,
+    )?;
+
+    let oracle_ctx = OracleContext {
+        oracle_program: ctx.accounts.oracle_program.clone(),
+        oracle_state: ctx.accounts.oracle_state.clone(),
+        pyth_price_account: ctx.accounts.pyth_price_account.clone(),
+        clock: ctx.accounts.clock.to_account_info(),
+        max_price_age: PriceReadClass::Critical.max_price_age(&*ctx.accounts.state.load()?),
+        fallback: None,
+        cached_price: None,
+    };
+
+    // Multi-denom troves: every other collateral leg is valued and auctioned with the primary one
+    let legs = value_collateral_legs(
+        ctx.remaining_accounts,
+        ACCOUNTS_PER_AUCTIONED_LEG,
+        &mut ctx.accounts.user_debt_amount,
+        &params.collateral_denom,
+        &oracle_ctx,
+    )?;
+    let leg_accounts: Vec<&[AccountInfo<'info>]> = ctx.remaining_accounts.chunks(ACCOUNTS_PER_AUCTIONED_LEG).collect();
+
+    let debt_amount = ctx.accounts.user_debt_amount.amount;
+    let lot_size = ctx.accounts.user_collateral_amount.amount;
+
+    require!(
+        ctx.accounts.user_debt_amount.is_active() && debt_amount > 0,
+        AerospacerProtocolError::TroveDoesNotExist
+    );
+    ctx.accounts.user_debt_amount.require_unlocked(Clock::get()?.slot)?;
+
+    // Only troves the stability pool cannot meaningfully absorb go to auction
+    let total_stake = ctx.accounts.state.load()?.total_stake_amount;
+    require!(
+        debt_amount > total_stake.saturating_mul(AUCTION_POOL_COVERAGE_FACTOR),
+        AerospacerProtocolError::AuctionNotRequired
+    );
+
+    let price = oracle_ctx.get_price(&params.collateral_denom)?;
+    oracle_ctx.validate_price(&price)?;
+
+    let primary_value = PriceCalculator::calculate_collateral_value(
+        lot_size,
+        price.price as u64,
+        price.decimal,
+    )?;
+    // Value of every leg, primary first - the auctioned debt is split by it
+    let leg_values: Vec<u64> = std::iter::once(primary_value).chain(legs.iter().map(|leg| leg.value)).collect();
+    let collateral_value = leg_values
+        .iter()
+        .try_fold(0u64, |total, value| total.checked_add(*value))
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+
+    let current_icr = PriceCalculator::calculate_collateral_ratio(collateral_value, debt_amount)?;
+    require!(
+        is_liquidatable_icr(current_icr, ctx.accounts.collateral_config.liquidation_threshold),
+        AerospacerProtocolError::CollateralBelowMinimum
+    );
+
+    let debt_shares = split_pro_rata(debt_amount, &leg_values)?;
+    let now = Clock::get()?.unix_timestamp;
+
+    // Zero user trove data - the auctions now hold the collateral and the debt
+    let liquidation_reserve = ctx.accounts.user_debt_amount.liquidation_reserve;
+    ctx.accounts.user_debt_amount.amount = 0;
+    ctx.accounts.user_debt_amount.liquidation_reserve = 0;
+    ctx.accounts.user_collateral_amount.amount = 0;
+    ctx.accounts.liquidity_threshold.ratio = 0;
+    ctx.accounts.user_debt_amount.transition(TroveStatus::Liquidated)?;
+    ctx.accounts.state.load_mut()?.record_trove_ended();
+    ctx.accounts.total_collateral_amount.load_mut()?.record_trove_ended();
+
+    let initiator = ctx.accounts.initiator.key();
+    let mut lots = vec![new_auction(&params, &params.collateral_denom, initiator, lot_size, debt_shares[0], primary_value, now)?];
+    ctx.accounts.auction.set_inner(lots[0].clone());
+
+    // Every other leg goes into an auction PDA of its own denom
+    let trove_seed = trove_id_seed(params.trove_id);
+    for ((leg, accounts), debt_share) in legs.iter().zip(&leg_accounts).zip(&debt_shares[1..]) {
+        let (collateral_account, auction_account) = (&accounts[0], &accounts[3]);
+
+        let (expected_auction_pda, auction_bump) = Pubkey::find_program_address(
+            &LiquidationAuction::seeds(&params.target_user, &trove_seed, &leg.denom),
+            &crate::ID,
+        );
+        require!(
+            expected_auction_pda == *auction_account.key && auction_account.is_writable,
+            AerospacerProtocolError::InvalidList
+        );
+
+        let mut collateral = {
+            let data = collateral_account.try_borrow_data()?;
+            UserCollateralAmount::try_deserialize(&mut &data[..])?
+        };
+        collateral.amount = 0;
+        {
+            let mut data = collateral_account.try_borrow_mut_data()?;
+            let mut writer: &mut [u8] = &mut data;
+            collateral.try_serialize(&mut writer)?;
+        }
+
+        create_program_pda(
+            &ctx.accounts.initiator.to_account_info(),
+            auction_account,
+            &ctx.accounts.system_program.to_account_info(),
+            8 + LiquidationAuction::LEN,
+            &[
+                b"liquidation_auction",
+                params.target_user.as_ref(),
+                trove_seed.as_slice(),
+                leg.denom.as_bytes(),
+                &[auction_bump],
+            ],
+        )?;
+        let auction = new_auction(&params, &leg.denom, initiator, leg.amount, *debt_share, leg.value, now)?;
+        {
+            let mut data = auction_account.try_borrow_mut_data()?;
+            let mut writer: &mut [u8] = &mut data;
+            auction.try_serialize(&mut writer)?;
+        }
+        lots.push(auction);
+    }
+
+    // The reserve is part of the auctioned debt, so starting the auction earns it
+    pay_liquidation_reserve(
+        &ctx.accounts.liquidation_reserve_vault,
+        ctx.bumps.liquidation_reserve_vault,
+        ctx.accounts.initiator_stablecoin_account.as_deref(),
+        &ctx.accounts.token_program.to_account_info(),
+        liquidation_reserve,
+    )?;
+
+    if let Some(user_index) = ctx.accounts.user_index.as_mut() {
+        user_index.clear_troves();
+        user_index.last_update_slot = Clock::get()?.slot;
+    }
+
+    if let Some(noop_program) = ctx.accounts.noop_program.as_ref() {
+        let slot = Clock::get()?.slot;
+        let records: Vec<LiquidationRecord> = lots
+            .iter()
+            .map(|lot| LiquidationRecord {
+                owner: params.target_user,
+                trove_id: params.trove_id,
+                denom: lot.denom.clone(),
+                debt_amount: lot.debt_remaining,
+                collateral_amount: lot.lot_size,
+                slot,
+            })
+            .collect();
+        log_liquidations(&noop_program.to_account_info(), &records)?;
+    }
+
+    msg!("Liquidation auction started");
+    msg!("Trove: {} #{}", params.target_user, params.trove_id);
+    msg!("Debt: {} aUSD (stability pool: {})", debt_amount, total_stake);
+    msg!("ICR: {}", current_icr);
+
+    for lot in &lots {
+        msg!("Lot: {} {} for {} aUSD of debt", lot.lot_size, lot.denom, lot.debt_remaining);
+        msg!("Lot price: {} -> {} aUSD over {}s", lot.start_price, lot.floor_price, AUCTION_DURATION_SECS);
+
+        emit!(LiquidationAuctionStarted {
+            owner: params.target_user,
+            trove_id: params.trove_id,
+            collateral_denom: lot.denom.clone(),
+            initiator,
+            debt_amount: lot.debt_remaining,
+            lot_size: lot.lot_size,
+            icr: current_icr,
+            start_price: lot.start_price,
+            floor_price: lot.floor_price,
+            ends_at: lot.ends_at(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Auction of `lot_size` of `denom` against `debt`, priced from the lot's oracle value
+fn new_auction(
+    params: &StartAuctionParams,
+    denom: &str,
+    initiator: Pubkey,
+    lot_size: u64,
+    debt: u64,
+    lot_value: u64,
+    now: i64,
+) -> Result<LiquidationAuction> {
+    let (start_price, floor_price) = auction_lot_prices(lot_value)?;
+    Ok(LiquidationAuction {
+        owner: params.target_user,
+        trove_id: params.trove_id,
+        denom: denom.to_string(),
+        initiator,
+        lot_size,
+        collateral_remaining: lot_size,
+        debt_remaining: debt,
+        debt_raised: 0,
+        start_price,
+        floor_price,
+        started_at: now,
+        duration: AUCTION_DURATION_SECS,
+    })
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct SyncUserIndex<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserIndex::LEN,
+        seeds = [b"user_index", user.key().as_ref()],
+        bump
+    )]
+    pub user_index: Account<'info, UserIndex>,
+
+    /// Stability pool deposit, if the user has one
+    #[account(
+        seeds = [b"user_stake_amount", user.key().as_ref()],
+        bump,
+        constraint = user_stake_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_stake_amount: Option<Account<'info, UserStakeAmount>>,
+
+    pub system_program: Program<'info, System>,
+
+    // remaining_accounts should contain the user's UserCollateralAmount PDAs (read-only)
+}
+
+/// Create the user's position summary, or rebuild it from the underlying accounts
+pub fn handler(ctx: Context<SyncUserIndex>) -> Result<()> {
+    let user = ctx.accounts.user.key();
+    let user_index = &mut ctx.accounts.user_index;
+
+    user_index.owner = user;
+    user_index.clear_troves();
+    for account_info in ctx.remaining_accounts.iter() {
+        // SECURITY: Only the user's own collateral PDAs may populate the index
+        require!(
+            account_info.owner == &crate::ID,
+            AerospacerProtocolError::Unauthorized
+        );
+        let collateral = {
+            let data = account_info.try_borrow_data()?;
+            UserCollateralAmount::try_deserialize(&mut &data[..])?
+        };
+        // Any of the user's troves may contribute (a wallet can hold several)
+        let trove_seed = trove_id_seed(collateral.trove_id);
+        let (expected_pda, _bump) = Pubkey::find_program_address(
+            &UserCollateralAmount::seeds(&user, &collateral.denom, &trove_seed),
+            &crate::ID,
+        );
+        require!(
+            expected_pda == *account_info.key && collateral.owner == user,
+            AerospacerProtocolError::InvalidList
+        );
+
+        // Liquidated troves leave zeroed collateral records behind
+        if collateral.amount > 0 {
+            user_index.add_trove_denom(&collateral.denom)?;
+        }
+    }
+
+    match ctx.accounts.user_stake_amount.as_ref() {
+        Some(user_stake_amount) => user_index.sync_stake(user_stake_amount),
+        None => {
+            user_index.has_stake = false;
+            user_index.has_pending_fee_yield = false;
+            user_index.has_pending_rewards = false;
+        }
+    }
+    user_index.last_update_slot = Clock::get()?.slot;
+
+    msg!("User index synced for {}", user);
+    msg!("Trove denoms: {:?}", user_index.trove_denoms);
+    msg!("Stake: {}, pending fee yield: {}, pending rewards: {}",
+         user_index.has_stake, user_index.has_pending_fee_yield, user_index.has_pending_rewards);
+
+    Ok(())
+}
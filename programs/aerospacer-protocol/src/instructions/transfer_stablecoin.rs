@@ -1,12 +1,19 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
 use crate::state::StateAccount;
+use crate::validation::{ValidateParams, validate_amount};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct TransferStablecoinParams {
     pub amount: u64,
 }
 
+impl ValidateParams for TransferStablecoinParams {
+    fn validate(&self) -> Result<()> {
+        validate_amount(self.amount)
+    }
+}
+
 #[derive(Accounts)]
 pub struct TransferStablecoin<'info> {
     #[account(mut)]
@@ -16,18 +23,18 @@ pub struct TransferStablecoin<'info> {
         seeds = [b"state"],
         bump
     )]
-    pub state: Account<'info, StateAccount>,
+    pub state: AccountLoader<'info, StateAccount>,
     
     #[account(
         mut,
         constraint = from_account.owner == from.key(),
-        constraint = from_account.mint == state.stable_coin_addr
+        constraint = from_account.mint == state.load()?.stable_coin_addr
     )]
     pub from_account: Account<'info, TokenAccount>,
     
     #[account(
         mut,
-        constraint = to_account.mint == state.stable_coin_addr
+        constraint = to_account.mint == state.load()?.stable_coin_addr
     )]
     pub to_account: Account<'info, TokenAccount>,
     
@@ -35,6 +42,8 @@ pub struct TransferStablecoin<'info> {
 }
 
 pub fn handler(ctx: Context<TransferStablecoin>, params: TransferStablecoinParams) -> Result<()> {
+    params.validate()?;
+    
     let transfer_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
         Transfer {
@@ -0,0 +1,263 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::*;
+use crate::utils::*;
+use crate::events::TroveTransferred;
+use crate::trove_index::entry_at;
+use crate::validation::{ValidateParams, validate_denom};
+
+// Accounts per additional collateral leg in remaining_accounts
+const ACCOUNTS_PER_LEG: usize = 2;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct TransferTroveParams {
+    pub collateral_denom: String,
+    pub trove_id: u16,     // The owner's trove being transferred
+    pub new_trove_id: u16, // Slot it takes among the new owner's troves
+}
+
+impl ValidateParams for TransferTroveParams {
+    fn validate(&self) -> Result<()> {
+        validate_denom(&self.collateral_denom)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: TransferTroveParams)]
+pub struct TransferTrove<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    // The receiving wallet takes on the debt, so it has to accept the trove
+    pub new_owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"user_debt_amount", owner.key().as_ref(), trove_id_seed(params.trove_id).as_slice()],
+        bump,
+        constraint = user_debt_amount.owner == owner.key() @ AerospacerProtocolError::Unauthorized,
+        constraint = user_debt_amount.is_active() @ AerospacerProtocolError::TroveDoesNotExist
+    )]
+    pub user_debt_amount: Box<Account<'info, UserDebtAmount>>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"user_collateral_amount", owner.key().as_ref(), trove_id_seed(params.trove_id).as_slice(), params.collateral_denom.as_bytes()],
+        bump,
+        constraint = user_collateral_amount.owner == owner.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_collateral_amount: Box<Account<'info, UserCollateralAmount>>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"liquidity_threshold", owner.key().as_ref(), trove_id_seed(params.trove_id).as_slice()],
+        bump,
+        constraint = liquidity_threshold.owner == owner.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub liquidity_threshold: Box<Account<'info, LiquidityThreshold>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + UserDebtAmount::LEN,
+        seeds = [b"user_debt_amount", new_owner.key().as_ref(), trove_id_seed(params.new_trove_id).as_slice()],
+        bump
+    )]
+    pub new_user_debt_amount: Box<Account<'info, UserDebtAmount>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + UserCollateralAmount::LEN,
+        seeds = [b"user_collateral_amount", new_owner.key().as_ref(), trove_id_seed(params.new_trove_id).as_slice(), params.collateral_denom.as_bytes()],
+        bump
+    )]
+    pub new_user_collateral_amount: Box<Account<'info, UserCollateralAmount>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + LiquidityThreshold::LEN,
+        seeds = [b"liquidity_threshold", new_owner.key().as_ref(), trove_id_seed(params.new_trove_id).as_slice()],
+        bump
+    )]
+    pub new_liquidity_threshold: Box<Account<'info, LiquidityThreshold>>,
+
+    /// Trove id registry of the sender - absent only for troves opened before wallets could hold several
+    #[account(
+        mut,
+        seeds = [b"trove_registry", owner.key().as_ref()],
+        bump
+    )]
+    pub trove_registry: Option<Box<Account<'info, TroveRegistry>>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + TroveRegistry::LEN,
+        seeds = [b"trove_registry", new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_trove_registry: Box<Account<'info, TroveRegistry>>,
+
+    /// Trove index page holding the trove's entry - required unless the trove was opened before
+    /// the index existed
+    #[account(
+        mut,
+        seeds = [b"trove_index_page", user_debt_amount.index_page_seed().as_ref()],
+        bump
+    )]
+    pub trove_index_page: Option<Box<Account<'info, TroveIndexPage>>>,
+
+    pub system_program: Program<'info, System>,
+
+    // remaining_accounts must contain, for every other collateral denom the trove holds:
+    // [UserCollateralAmount of the sender, UserCollateralAmount PDA for the new owner (created here)]
+    // (both writable). The sender's record is closed once its contents moved.
+}
+
+/// Handler for transfer_trove instruction
+///
+/// PDAs are keyed by owner, so the trove is recreated under the new owner's seeds with every
+/// field (amounts, L snapshots, tag) carried over and the sender's records are closed. Debt,
+/// collateral and totals do not change, nor does the trove's place in the off-chain sorted list
+/// beyond its key. Delegates and the sender's UserIndex are not carried over.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, TransferTrove<'info>>, params: TransferTroveParams) -> Result<()> {
+    params.validate()?;
+    
+    let owner = ctx.accounts.owner.key();
+    let new_owner = ctx.accounts.new_owner.key();
+
+    require!(new_owner != owner, AerospacerProtocolError::InvalidAddress);
+    require!(
+        ctx.remaining_accounts.len().is_multiple_of(ACCOUNTS_PER_LEG),
+        AerospacerProtocolError::InvalidList
+    );
+    // The debt only moves with all of the collateral backing it (legs are distinct and canonical,
+    // see below, so the count is enough)
+    ctx.accounts
+        .user_debt_amount
+        .require_all_legs(1 + ctx.remaining_accounts.len() / ACCOUNTS_PER_LEG)?;
+
+    // Move every additional collateral leg first, so a bad leg fails before anything is written
+    let old_seed = trove_id_seed(params.trove_id);
+    let new_seed = trove_id_seed(params.new_trove_id);
+    let mut moved_denoms: Vec<String> = vec![params.collateral_denom.clone()];
+    for chunk in ctx.remaining_accounts.chunks(ACCOUNTS_PER_LEG) {
+        let (old_account, new_account) = (&chunk[0], &chunk[1]);
+
+        require!(
+            old_account.owner == &crate::ID && old_account.is_writable && new_account.is_writable,
+            AerospacerProtocolError::InvalidList
+        );
+        let mut collateral = {
+            let data = old_account.try_borrow_data()?;
+            UserCollateralAmount::try_deserialize(&mut &data[..])?
+        };
+        require!(
+            collateral.owner == owner && collateral.trove_id == params.trove_id,
+            AerospacerProtocolError::Unauthorized
+        );
+        require!(
+            !moved_denoms.contains(&collateral.denom),
+            AerospacerProtocolError::InvalidList
+        );
+
+        let (expected_old_pda, _bump) = Pubkey::find_program_address(
+            &UserCollateralAmount::seeds(&owner, &collateral.denom, &old_seed),
+            &crate::ID,
+        );
+        let (expected_new_pda, new_bump) = Pubkey::find_program_address(
+            &UserCollateralAmount::seeds(&new_owner, &collateral.denom, &new_seed),
+            &crate::ID,
+        );
+        require!(
+            expected_old_pda == *old_account.key && expected_new_pda == *new_account.key,
+            AerospacerProtocolError::InvalidList
+        );
+
+        create_program_pda(
+            &ctx.accounts.owner.to_account_info(),
+            new_account,
+            &ctx.accounts.system_program.to_account_info(),
+            8 + UserCollateralAmount::LEN,
+            &[
+                b"user_collateral_amount",
+                new_owner.as_ref(),
+                new_seed.as_slice(),
+                collateral.denom.as_bytes(),
+                &[new_bump],
+            ],
+        )?;
+
+        collateral.owner = new_owner;
+        collateral.trove_id = params.new_trove_id;
+        {
+            let mut data = new_account.try_borrow_mut_data()?;
+            let mut writer: &mut [u8] = &mut data;
+            collateral.try_serialize(&mut writer)?;
+        }
+        close_program_account(old_account, &ctx.accounts.owner.to_account_info())?;
+
+        msg!("Moved {} {} collateral leg", collateral.amount, collateral.denom);
+        moved_denoms.push(collateral.denom);
+    }
+
+    // Recreate the primary records under the new owner
+    let mut debt = (**ctx.accounts.user_debt_amount).clone();
+    debt.owner = new_owner;
+    debt.trove_id = params.new_trove_id;
+    ctx.accounts.new_user_debt_amount.set_inner(debt);
+
+    let mut collateral = (**ctx.accounts.user_collateral_amount).clone();
+    collateral.owner = new_owner;
+    collateral.trove_id = params.new_trove_id;
+    ctx.accounts.new_user_collateral_amount.set_inner(collateral);
+
+    let mut threshold = (**ctx.accounts.liquidity_threshold).clone();
+    threshold.owner = new_owner;
+    threshold.trove_id = params.new_trove_id;
+    ctx.accounts.new_liquidity_threshold.set_inner(threshold);
+
+    // The sender's records are closed via Anchor's `close` constraint
+    if let Some(trove_registry) = ctx.accounts.trove_registry.as_mut() {
+        trove_registry.unregister(params.trove_id);
+    }
+    let new_registry = &mut ctx.accounts.new_trove_registry;
+    new_registry.owner = new_owner;
+    new_registry.register(params.new_trove_id)?;
+
+    // The trove keeps its index position under the new owner
+    let index_position = ctx.accounts.new_user_debt_amount.index_position;
+    if index_position != 0 {
+        let trove_index_page = ctx.accounts.trove_index_page
+            .as_mut()
+            .ok_or(AerospacerProtocolError::InvalidTroveIndexPage)?;
+        let entry = entry_at(trove_index_page, index_position)?;
+        require!(
+            *entry == TroveIndexEntry { owner, trove_id: params.trove_id },
+            AerospacerProtocolError::InvalidTroveIndexPage
+        );
+        *entry = TroveIndexEntry { owner: new_owner, trove_id: params.new_trove_id };
+    }
+
+    msg!("Trove transferred successfully");
+    msg!("From: {} (trove {})", owner, params.trove_id);
+    msg!("To: {} (trove {})", new_owner, params.new_trove_id);
+    msg!("Debt: {} aUSD", ctx.accounts.new_user_debt_amount.amount);
+    msg!("Collateral: {} {}", ctx.accounts.new_user_collateral_amount.amount, params.collateral_denom);
+
+    emit!(TroveTransferred {
+        from: owner,
+        from_trove_id: params.trove_id,
+        to: new_owner,
+        to_trove_id: params.new_trove_id,
+        debt_amount: ctx.accounts.new_user_debt_amount.amount,
+        collateral_denoms: moved_denoms,
+    });
+
+    Ok(())
+}
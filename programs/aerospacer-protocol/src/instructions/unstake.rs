@@ -1,14 +1,24 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::utils::*;
 use crate::error::*;
+use crate::trove_management::issue_reward_emissions;
+use crate::events::{StakeOperation, StakeUpdated};
+use crate::validation::{ValidateParams, validate_amount};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct UnstakeParams {
     pub amount: u64, // Equivalent to Uint256
 }
 
+impl ValidateParams for UnstakeParams {
+    fn validate(&self) -> Result<()> {
+        validate_amount(self.amount)
+    }
+}
+
 #[derive(Accounts)]
 #[instruction(params: UnstakeParams)]
 pub struct Unstake<'info> {
@@ -24,48 +34,81 @@ pub struct Unstake<'info> {
     pub user_stake_amount: Account<'info, UserStakeAmount>,
 
     #[account(mut)]
-    pub state: Account<'info, StateAccount>,
+    pub state: AccountLoader<'info, StateAccount>,
+
+    /// CHECK: This is the stable coin mint account
+    #[account(
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub stable_coin_mint: UncheckedAccount<'info>,
 
+    // User's aUSD ATA, created when missing
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = stable_coin_mint,
+        associated_token::authority = user
+    )]
+    pub user_stablecoin_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Stability pool vault PDA
     #[account(
         mut,
-        constraint = user_stablecoin_account.owner == user.key() @ AerospacerProtocolError::Unauthorized
+        seeds = [b"stability_pool_vault"],
+        bump
     )]
-    pub user_stablecoin_account: Account<'info, TokenAccount>,
+    pub stability_pool_vault: AccountInfo<'info>,
 
-    /// CHECK: Protocol stablecoin vault PDA
+    /// Reward emission schedule - required once emissions are enabled so that emissions
+    /// accrued at the old total stake are credited before it changes
     #[account(
         mut,
-        seeds = [b"protocol_stablecoin_vault"],
+        seeds = [b"reward_emission"],
         bump
     )]
-    pub protocol_stablecoin_vault: AccountInfo<'info>,
+    pub reward_emission: Option<Account<'info, RewardEmission>>,
 
-    /// CHECK: This is the stable coin mint account
+    /// Optional position summary, refreshed when passed
     #[account(
-        constraint = stable_coin_mint.key() == state.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+        mut,
+        seeds = [b"user_index", user.key().as_ref()],
+        bump
     )]
-    pub stable_coin_mint: UncheckedAccount<'info>,
+    pub user_index: Option<Account<'info, UserIndex>>,
 
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
 
 
 pub fn handler(ctx: Context<Unstake>, params: UnstakeParams) -> Result<()> {
-    // Validate input parameters
-    require!(
-        params.amount > 0,
-        AerospacerProtocolError::InvalidAmount
-    );
-
+    params.validate()?;
+    
     let user_stake_amount = &mut ctx.accounts.user_stake_amount;
-    let state = &mut ctx.accounts.state;
+    let mut state = ctx.accounts.state.load_mut()?;
+
+    // Credit reward emissions accrued at the old total stake before it changes
+    match ctx.accounts.reward_emission.as_mut() {
+        Some(reward_emission) => {
+            issue_reward_emissions(&mut state, reward_emission, Clock::get()?.unix_timestamp)?;
+        }
+        None => require!(
+            state.reward_emissions_enabled == 0,
+            AerospacerProtocolError::MissingRewardEmission
+        ),
+    }
 
     // SNAPSHOT: Calculate compounded stake accounting for pool depletion
     let compounded_stake = calculate_compounded_stake(
         user_stake_amount.amount,
         user_stake_amount.p_snapshot,
         state.p_factor,
+        user_stake_amount.scale_snapshot,
+        state.current_scale,
+        user_stake_amount.epoch_snapshot,
+        state.epoch,
     )?;
 
     // Check if user has enough compounded stake (NOT original deposit)
@@ -84,42 +127,38 @@ pub fn handler(ctx: Context<Unstake>, params: UnstakeParams) -> Result<()> {
         );
     }
 
-    // Transfer stablecoin back to user from protocol vault (Injective: CW20 transfer)
+    // Transfer stablecoin back to user from the stability pool vault (Injective: CW20 transfer)
     let transfer_seeds = &[
-        b"protocol_stablecoin_vault".as_ref(),
-        &[ctx.bumps.protocol_stablecoin_vault],
+        b"stability_pool_vault".as_ref(),
+        &[ctx.bumps.stability_pool_vault],
     ];
     let transfer_signer = &[&transfer_seeds[..]];
 
     let transfer_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
         Transfer {
-            from: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+            from: ctx.accounts.stability_pool_vault.to_account_info(),
             to: ctx.accounts.user_stablecoin_account.to_account_info(),
-            authority: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+            authority: ctx.accounts.stability_pool_vault.to_account_info(),
         },
         transfer_signer,
     );
     anchor_spl::token::transfer(transfer_ctx, params.amount)?;
 
-    // Update user stake amount - subtract from original deposit proportionally
-    let remaining_compounded = safe_sub(compounded_stake, params.amount)?;
+    // Settle aUSD fee yield earned under the old snapshots before they are refreshed or cleared
+    let fee_gain = settle_fee_yield(user_stake_amount, &state)?;
+    if fee_gain > 0 {
+        msg!("Fee yield settled: {} aUSD (pending: {})", fee_gain, user_stake_amount.pending_fee_gain);
+    }
+    let reward_gain = settle_reward_emissions(user_stake_amount, &state)?;
+    if reward_gain > 0 {
+        msg!("Reward emissions settled: {} (pending: {})", reward_gain, user_stake_amount.pending_reward_gain);
+    }
     
-    // Calculate new deposit amount: remaining_compounded / (P_current / P_snapshot)
-    // = remaining_compounded * P_snapshot / P_current
-    let new_deposit = if remaining_compounded == 0 {
-        0u64
-    } else {
-        let remaining_128 = remaining_compounded as u128;
-        let numerator = remaining_128
-            .checked_mul(user_stake_amount.p_snapshot)
-            .ok_or(AerospacerProtocolError::MathOverflow)?;
-        let result = numerator
-            .checked_div(state.p_factor)
-            .ok_or(AerospacerProtocolError::MathOverflow)?;
-        u64::try_from(result)
-            .map_err(|_| AerospacerProtocolError::MathOverflow)?
-    };
+    // Update user stake amount - the remaining compounded stake becomes the new deposit
+    // Snapshots are refreshed to the current P/scale/epoch below, so it is already in current terms
+    // (rescaling it back by P_snapshot / P_current would double-count depletion and mix scales)
+    let new_deposit = safe_sub(compounded_stake, params.amount)?;
 
     user_stake_amount.amount = new_deposit;
     user_stake_amount.last_update_block = Clock::get()?.slot;
@@ -130,16 +169,23 @@ pub fn handler(ctx: Context<Unstake>, params: UnstakeParams) -> Result<()> {
         // Partial withdrawal - refresh snapshots to current scale
         user_stake_amount.p_snapshot = state.p_factor;
         user_stake_amount.epoch_snapshot = state.epoch;
-        msg!("Snapshots refreshed: P={}, epoch={}", state.p_factor, state.epoch);
+        user_stake_amount.scale_snapshot = state.current_scale;
+        user_stake_amount.g_snapshot = state.g_factor;
+        user_stake_amount.reward_g_snapshot = state.reward_g_factor;
+        msg!("Snapshots refreshed: P={}, epoch={}, scale={}", state.p_factor, state.epoch, state.current_scale);
     } else {
         // Full withdrawal - clear snapshots for hygiene
         user_stake_amount.p_snapshot = 0;
         user_stake_amount.epoch_snapshot = 0;
-        msg!("Full withdrawal - snapshots cleared");
+        user_stake_amount.scale_snapshot = 0;
+        user_stake_amount.g_snapshot = 0;
+        user_stake_amount.reward_g_snapshot = 0;
+        msg!("Full withdrawal - snapshots cleared (pending fee yield and rewards kept for claiming)");
     }
 
     // Update state
     state.total_stake_amount = safe_sub(state.total_stake_amount, params.amount)?;
+    check_stability_pool_backing(&ctx.accounts.stability_pool_vault, state.total_stake_amount)?;
 
     msg!("Unstaked successfully (compounded stake calculated)");
     msg!("User: {}", ctx.accounts.user.key());
@@ -148,5 +194,31 @@ pub fn handler(ctx: Context<Unstake>, params: UnstakeParams) -> Result<()> {
     msg!("Remaining deposit: {} aUSD", user_stake_amount.amount);
     msg!("Total protocol stake: {} aUSD", state.total_stake_amount);
 
+    emit!(StakeUpdated {
+        user: ctx.accounts.user.key(),
+        operation: StakeOperation::Unstake,
+        amount: params.amount,
+        compounded_gains: 0,
+        deposit_amount: user_stake_amount.amount,
+        total_stake_amount: state.total_stake_amount,
+        p_factor: state.p_factor,
+        current_scale: state.current_scale,
+        epoch: state.epoch,
+    });
+
+    if let Some(user_index) = ctx.accounts.user_index.as_mut() {
+        user_index.sync_stake(user_stake_amount);
+        user_index.last_update_slot = Clock::get()?.slot;
+    }
+
+    // Nothing left to compound or claim - refund the deposit record's rent
+    if user_stake_amount.amount == 0
+        && user_stake_amount.pending_fee_gain == 0
+        && user_stake_amount.pending_reward_gain == 0
+    {
+        ctx.accounts.user_stake_amount.close(ctx.accounts.user.to_account_info())?;
+        msg!("Stake record closed - rent refunded");
+    }
+
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::StateAccount;
+use crate::error::AerospacerProtocolError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateBootstrapPeriodParams {
+    pub bootstrap_period_secs: Option<u64>,          // Counted from the launch timestamp; 0 ends the period
+    pub bootstrap_max_liquidation_batch: Option<u8>, // 0 = unlimited
+}
+
+#[derive(Accounts)]
+pub struct UpdateBootstrapPeriod<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+}
+
+/// Handler for update_bootstrap_period instruction
+/// The launch timestamp itself is set once by initialize and never moves
+pub fn handler(ctx: Context<UpdateBootstrapPeriod>, params: UpdateBootstrapPeriodParams) -> Result<()> {
+    let mut state = ctx.accounts.state.load_mut()?;
+    
+    if let Some(bootstrap_period_secs) = params.bootstrap_period_secs {
+        state.bootstrap_period_secs = bootstrap_period_secs;
+        msg!("Bootstrap period: {}s from launch at {}", bootstrap_period_secs, state.launch_timestamp);
+    }
+    
+    if let Some(bootstrap_max_liquidation_batch) = params.bootstrap_max_liquidation_batch {
+        state.bootstrap_max_liquidation_batch = bootstrap_max_liquidation_batch;
+        msg!("Bootstrap liquidation batch limit: {}", bootstrap_max_liquidation_batch);
+    }
+    
+    msg!("In bootstrap period: {}", state.in_bootstrap_period(Clock::get()?.unix_timestamp));
+    
+    Ok(())
+}
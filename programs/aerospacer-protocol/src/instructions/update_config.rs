@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::StateAccount;
+use crate::error::AerospacerProtocolError;
+use crate::events::ConfigUpdated;
+use crate::instructions::initialize::ProtocolConfig;
+use crate::validation::ValidateParams;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateConfigParams {
+    pub minimum_collateral_ratio: Option<u64>, // Micro-percent, inherited by denoms registered afterwards
+    pub protocol_fee: Option<u8>,              // Redemption fee and opening fee of denoms registered afterwards
+}
+
+impl ValidateParams for UpdateConfigParams {
+    fn validate(&self) -> Result<()> {
+        if let Some(minimum_collateral_ratio) = self.minimum_collateral_ratio {
+            ProtocolConfig::validate_minimum_collateral_ratio(minimum_collateral_ratio)?;
+        }
+        if let Some(protocol_fee) = self.protocol_fee {
+            ProtocolConfig::validate_protocol_fee(protocol_fee)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+}
+
+/// Handler for update_config instruction
+/// Registered denoms keep their own MCR and fee; change those with set_collateral_params
+pub fn handler(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+    params.validate()?;
+    
+    let mut state = ctx.accounts.state.load_mut()?;
+    let old_minimum_collateral_ratio = state.minimum_collateral_ratio;
+    let old_protocol_fee = state.protocol_fee;
+    
+    if let Some(minimum_collateral_ratio) = params.minimum_collateral_ratio {
+        state.minimum_collateral_ratio = minimum_collateral_ratio;
+        msg!("Minimum collateral ratio: {} -> {}", old_minimum_collateral_ratio, minimum_collateral_ratio);
+    }
+    
+    if let Some(protocol_fee) = params.protocol_fee {
+        state.protocol_fee = protocol_fee;
+        msg!("Protocol fee: {}% -> {}%", old_protocol_fee, protocol_fee);
+    }
+    
+    emit!(ConfigUpdated {
+        old_minimum_collateral_ratio,
+        new_minimum_collateral_ratio: state.minimum_collateral_ratio,
+        old_protocol_fee,
+        new_protocol_fee: state.protocol_fee,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::StateAccount;
+use crate::error::AerospacerProtocolError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateHintPolicyParams {
+    pub require_hints: bool, // Reject trove instructions that omit the sorted list neighbor hints
+}
+
+#[derive(Accounts)]
+pub struct UpdateHintPolicy<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+}
+
+/// Handler for update_hint_policy instruction
+/// While set, open_trove, borrow_loan, add_collateral, remove_collateral and repay_loan fail
+/// with NeighborHintsRequired instead of skipping the ICR ordering check (full repayments,
+/// which take the trove off the list, are exempt)
+pub fn handler(ctx: Context<UpdateHintPolicy>, params: UpdateHintPolicyParams) -> Result<()> {
+    let mut state = ctx.accounts.state.load_mut()?;
+    state.require_hints = params.require_hints as u8;
+    
+    msg!("Neighbor hints required: {}", params.require_hints);
+    
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::StateAccount;
+use crate::error::AerospacerProtocolError;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateLiquidationReserveParams {
+    pub liquidation_reserve: Option<u64>, // 0 opens troves without a reserve
+    pub min_net_debt: Option<u64>,        // 0 leaves only MINIMUM_LOAN_AMOUNT
+}
+
+#[derive(Accounts)]
+pub struct UpdateLiquidationReserve<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+}
+
+/// Handler for update_liquidation_reserve instruction
+/// A new reserve only applies to troves opened afterwards - every trove refunds or pays out the
+/// reserve recorded when it was opened. A new minimum net debt applies to every later open and
+/// repayment.
+pub fn handler(ctx: Context<UpdateLiquidationReserve>, params: UpdateLiquidationReserveParams) -> Result<()> {
+    let mut state = ctx.accounts.state.load_mut()?;
+    
+    if let Some(liquidation_reserve) = params.liquidation_reserve {
+        state.liquidation_reserve = liquidation_reserve;
+        msg!("Liquidation reserve: {} aUSD", liquidation_reserve);
+    }
+    
+    if let Some(min_net_debt) = params.min_net_debt {
+        state.min_net_debt = min_net_debt;
+        msg!("Minimum net debt: {} aUSD", min_net_debt);
+    }
+    
+    Ok(())
+}
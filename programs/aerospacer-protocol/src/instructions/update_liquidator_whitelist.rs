@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::state::{StateAccount, LiquidatorWhitelist, MAX_WHITELISTED_LIQUIDATORS};
+use crate::error::AerospacerProtocolError;
+use crate::validation::{ValidateParams, validate_pubkey};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateLiquidatorWhitelistParams {
+    pub add_liquidators: Vec<Pubkey>,
+    pub remove_liquidators: Vec<Pubkey>,
+    pub permissioned: Option<bool>, // Restrict liquidate_trove(s) to whitelisted liquidators
+}
+
+impl ValidateParams for UpdateLiquidatorWhitelistParams {
+    fn validate(&self) -> Result<()> {
+        require!(
+            self.add_liquidators.len() <= MAX_WHITELISTED_LIQUIDATORS
+                && self.remove_liquidators.len() <= MAX_WHITELISTED_LIQUIDATORS,
+            AerospacerProtocolError::InvalidList
+        );
+        for liquidator in self.add_liquidators.iter() {
+            validate_pubkey(liquidator)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateLiquidatorWhitelist<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + LiquidatorWhitelist::LEN,
+        seeds = [b"liquidator_whitelist"],
+        bump
+    )]
+    pub liquidator_whitelist: Account<'info, LiquidatorWhitelist>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<UpdateLiquidatorWhitelist>, params: UpdateLiquidatorWhitelistParams) -> Result<()> {
+    params.validate()?;
+    
+    let whitelist = &mut ctx.accounts.liquidator_whitelist;
+    
+    for liquidator in params.remove_liquidators.iter() {
+        if let Some(pos) = whitelist.liquidators.iter().position(|l| l == liquidator) {
+            whitelist.liquidators.swap_remove(pos);
+            msg!("Liquidator removed: {}", liquidator);
+        }
+    }
+    
+    for liquidator in params.add_liquidators.iter() {
+        if whitelist.contains(liquidator) {
+            continue;
+        }
+        require!(
+            whitelist.liquidators.len() < MAX_WHITELISTED_LIQUIDATORS,
+            AerospacerProtocolError::LiquidatorWhitelistFull
+        );
+        whitelist.liquidators.push(*liquidator);
+        msg!("Liquidator whitelisted: {}", liquidator);
+    }
+    
+    if let Some(permissioned) = params.permissioned {
+        ctx.accounts.state.load_mut()?.permissioned_liquidation = permissioned as u8;
+        msg!("Permissioned liquidation: {}", permissioned);
+    }
+    
+    msg!("Whitelisted liquidators: {}", whitelist.liquidators.len());
+    
+    Ok(())
+}
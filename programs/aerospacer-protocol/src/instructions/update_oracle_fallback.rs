@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use crate::state::StateAccount;
+use crate::error::AerospacerProtocolError;
+use crate::oracle::PriceReadClass;
+use crate::validation::{ValidateParams, validate_bps};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateOracleFallbackParams {
+    pub max_price_age: Option<u32>,           // Seconds, 0 disables the staleness check
+    pub critical_max_price_age: Option<u32>,  // Liquidations/redemptions window, 0 = use max_price_age
+    pub derisking_max_price_age: Option<u32>, // Repay/add collateral window, 0 = use max_price_age
+    pub fallback_enabled: Option<bool>,
+    pub haircut_bps_per_hour: Option<u16>,
+    pub max_haircut_bps: Option<u16>,
+    pub price_cache_max_age: Option<u16>,     // Seconds a PriceCache may replace the oracle in trove instructions, 0 = off
+    pub redemption_stored_icr_fallback: Option<bool>, // Redemptions may order by stored ICRs without a fresh price
+}
+
+impl ValidateParams for UpdateOracleFallbackParams {
+    fn validate(&self) -> Result<()> {
+        if let Some(bps) = self.haircut_bps_per_hour {
+            validate_bps(bps, 10_000, AerospacerProtocolError::InvalidFallbackHaircut)?;
+        }
+        if let Some(bps) = self.max_haircut_bps {
+            validate_bps(bps, 10_000, AerospacerProtocolError::InvalidFallbackHaircut)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateOracleFallback<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+}
+
+pub fn handler(ctx: Context<UpdateOracleFallback>, params: UpdateOracleFallbackParams) -> Result<()> {
+    params.validate()?;
+    
+    let mut state = ctx.accounts.state.load_mut()?;
+    
+    if let Some(max_price_age) = params.max_price_age {
+        state.max_price_age = max_price_age;
+        msg!("Max price age updated: {}s", max_price_age);
+    }
+    
+    if let Some(max_price_age) = params.critical_max_price_age {
+        state.critical_max_price_age = max_price_age;
+        msg!("Critical max price age updated: {}s", max_price_age);
+    }
+    
+    if let Some(max_price_age) = params.derisking_max_price_age {
+        state.derisking_max_price_age = max_price_age;
+        msg!("De-risking max price age updated: {}s", max_price_age);
+    }
+    
+    if let Some(enabled) = params.fallback_enabled {
+        state.oracle_fallback_enabled = enabled as u8;
+        msg!("Oracle fallback enabled: {}", enabled);
+    }
+    
+    if let Some(bps) = params.haircut_bps_per_hour {
+        state.fallback_haircut_bps_per_hour = bps;
+        msg!("Fallback haircut per hour updated: {} bps", bps);
+    }
+    
+    if let Some(bps) = params.max_haircut_bps {
+        state.max_fallback_haircut_bps = bps;
+        msg!("Max fallback haircut updated: {} bps", bps);
+    }
+    
+    if let Some(max_age) = params.price_cache_max_age {
+        state.price_cache_max_age = max_age;
+        msg!("Price cache max age updated: {}s", max_age);
+    }
+    
+    if let Some(enabled) = params.redemption_stored_icr_fallback {
+        state.redemption_stored_icr_fallback = enabled as u8;
+        msg!("Redemption stored ICR fallback enabled: {}", enabled);
+    }
+    
+    // A fallback without a staleness limit would never trigger (only de-risking reads use it)
+    require!(
+        state.oracle_fallback_enabled == 0 || PriceReadClass::Derisking.max_price_age(&state) > 0,
+        AerospacerProtocolError::InvalidFallbackHaircut
+    );
+    
+    Ok(())
+}
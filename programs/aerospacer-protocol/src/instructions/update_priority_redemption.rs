@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::state::StateAccount;
+use crate::error::AerospacerProtocolError;
+use crate::events::PriorityRedemptionModeUpdated;
+use crate::validation::{ValidateParams};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdatePriorityRedemptionParams {
+    pub treasury: Option<Pubkey>,       // Pubkey::default() removes the treasury
+    pub emergency_mode: Option<bool>,   // Flag/clear a de-peg emergency
+}
+
+impl ValidateParams for UpdatePriorityRedemptionParams {
+    fn validate(&self) -> Result<()> {
+        // Pubkey::default() is a valid treasury: it removes the current one
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdatePriorityRedemption<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+}
+
+pub fn handler(ctx: Context<UpdatePriorityRedemption>, params: UpdatePriorityRedemptionParams) -> Result<()> {
+    params.validate()?;
+    
+    let mut state = ctx.accounts.state.load_mut()?;
+    
+    if let Some(treasury) = params.treasury {
+        state.treasury_addr = treasury;
+        msg!("Priority redemption treasury updated: {}", treasury);
+    }
+    
+    if let Some(emergency_mode) = params.emergency_mode {
+        state.emergency_redemption_mode = emergency_mode as u8;
+        msg!("Emergency redemption mode: {}", emergency_mode);
+    }
+    
+    // The lane is useless without a treasury to use it
+    require!(
+        state.emergency_redemption_mode == 0 || state.treasury_addr != Pubkey::default(),
+        AerospacerProtocolError::InvalidAddress
+    );
+    
+    emit!(PriorityRedemptionModeUpdated {
+        treasury: state.treasury_addr,
+        emergency_mode: state.emergency_redemption_mode != 0,
+    });
+    
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::state::StateAccount;
 use crate::error::AerospacerProtocolError;
+use crate::validation::{ValidateParams, validate_optional_pubkey};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct UpdateProtocolAddressesParams {
@@ -10,6 +11,15 @@ pub struct UpdateProtocolAddressesParams {
     pub fee_state_addr: Option<Pubkey>,
 }
 
+impl ValidateParams for UpdateProtocolAddressesParams {
+    fn validate(&self) -> Result<()> {
+        validate_optional_pubkey(&self.oracle_helper_addr)?;
+        validate_optional_pubkey(&self.oracle_state_addr)?;
+        validate_optional_pubkey(&self.fee_distributor_addr)?;
+        validate_optional_pubkey(&self.fee_state_addr)
+    }
+}
+
 #[derive(Accounts)]
 pub struct UpdateProtocolAddresses<'info> {
     #[account(mut)]
@@ -19,19 +29,17 @@ pub struct UpdateProtocolAddresses<'info> {
         mut,
         seeds = [b"state"],
         bump,
-        constraint = state.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
     )]
-    pub state: Account<'info, StateAccount>,
+    pub state: AccountLoader<'info, StateAccount>,
 }
 
 pub fn handler(ctx: Context<UpdateProtocolAddresses>, params: UpdateProtocolAddressesParams) -> Result<()> {
-    let state = &mut ctx.accounts.state;
+    params.validate()?;
+    
+    let mut state = ctx.accounts.state.load_mut()?;
     
     if let Some(addr) = params.oracle_helper_addr {
-        require!(
-            addr != Pubkey::default(),
-            AerospacerProtocolError::InvalidAddress
-        );
         require!(
             addr != state.oracle_state_addr && 
             addr != state.fee_distributor_addr && 
@@ -43,10 +51,6 @@ pub fn handler(ctx: Context<UpdateProtocolAddresses>, params: UpdateProtocolAddr
     }
     
     if let Some(addr) = params.oracle_state_addr {
-        require!(
-            addr != Pubkey::default(),
-            AerospacerProtocolError::InvalidAddress
-        );
         require!(
             addr != state.oracle_helper_addr && 
             addr != state.fee_distributor_addr && 
@@ -58,10 +62,6 @@ pub fn handler(ctx: Context<UpdateProtocolAddresses>, params: UpdateProtocolAddr
     }
     
     if let Some(addr) = params.fee_distributor_addr {
-        require!(
-            addr != Pubkey::default(),
-            AerospacerProtocolError::InvalidAddress
-        );
         require!(
             addr != state.oracle_helper_addr && 
             addr != state.oracle_state_addr && 
@@ -73,10 +73,6 @@ pub fn handler(ctx: Context<UpdateProtocolAddresses>, params: UpdateProtocolAddr
     }
     
     if let Some(addr) = params.fee_state_addr {
-        require!(
-            addr != Pubkey::default(),
-            AerospacerProtocolError::InvalidAddress
-        );
         require!(
             addr != state.oracle_helper_addr && 
             addr != state.oracle_state_addr && 
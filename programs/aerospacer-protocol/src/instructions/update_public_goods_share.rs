@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::{StateAccount, MAX_PUBLIC_GOODS_BPS};
+use crate::error::AerospacerProtocolError;
+use crate::validation::{ValidateParams, validate_bps};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdatePublicGoodsShareParams {
+    pub public_goods_bps: u16, // Share of liquidation penalties held back for public goods, 0 = off
+}
+
+impl ValidateParams for UpdatePublicGoodsShareParams {
+    fn validate(&self) -> Result<()> {
+        validate_bps(self.public_goods_bps, MAX_PUBLIC_GOODS_BPS, AerospacerProtocolError::InvalidAmount)
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdatePublicGoodsShare<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+}
+
+/// Handler for update_public_goods_share instruction
+/// Only affects future liquidations; shares already booked stay routable
+pub fn handler(ctx: Context<UpdatePublicGoodsShare>, params: UpdatePublicGoodsShareParams) -> Result<()> {
+    params.validate()?;
+    
+    let mut state = ctx.accounts.state.load_mut()?;
+    state.public_goods_bps = params.public_goods_bps;
+    
+    msg!("Public goods share of liquidation penalties: {} bps", params.public_goods_bps);
+    
+    Ok(())
+}
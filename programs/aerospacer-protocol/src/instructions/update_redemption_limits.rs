@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::state::StateAccount;
+use crate::error::AerospacerProtocolError;
+use crate::validation::{ValidateParams};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateRedemptionLimitsParams {
+    pub min_redemption_amount: Option<u64>,   // 0 leaves only MINIMUM_LOAN_AMOUNT
+    pub max_redemption_troves: Option<u8>,    // 0 = unlimited
+    pub redemption_cap_per_window: Option<u64>, // 0 disables the throttle
+    pub redemption_window_slots: Option<u64>,
+}
+
+impl ValidateParams for UpdateRedemptionLimitsParams {
+    fn validate(&self) -> Result<()> {
+        // Every field has a meaningful 0; the cap/window pairing depends on state and is checked by the handler
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateRedemptionLimits<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+}
+
+/// Handler for update_redemption_limits instruction
+pub fn handler(ctx: Context<UpdateRedemptionLimits>, params: UpdateRedemptionLimitsParams) -> Result<()> {
+    params.validate()?;
+    
+    let mut state = ctx.accounts.state.load_mut()?;
+    
+    if let Some(min_redemption_amount) = params.min_redemption_amount {
+        state.min_redemption_amount = min_redemption_amount;
+        msg!("Minimum redemption amount: {} aUSD", min_redemption_amount);
+    }
+    
+    if let Some(max_redemption_troves) = params.max_redemption_troves {
+        state.max_redemption_troves = max_redemption_troves;
+        msg!("Max troves per redemption: {}", max_redemption_troves);
+    }
+    
+    if let Some(redemption_cap_per_window) = params.redemption_cap_per_window {
+        state.redemption_cap_per_window = redemption_cap_per_window;
+        msg!("Redemption cap per window: {} aUSD", redemption_cap_per_window);
+    }
+    
+    if let Some(redemption_window_slots) = params.redemption_window_slots {
+        state.redemption_window_slots = redemption_window_slots;
+        msg!("Redemption window: {} slots", redemption_window_slots);
+    }
+    
+    // A throttle needs a window to reset over
+    require!(
+        state.redemption_cap_per_window == 0 || state.redemption_window_slots > 0,
+        AerospacerProtocolError::InvalidAmount
+    );
+    
+    // Restart the window so a new cap applies from now on
+    state.redemption_window_start_slot = Clock::get()?.slot;
+    state.redemption_window_volume = 0;
+    
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::state::{StateAccount, SwapProgramWhitelist, MAX_WHITELISTED_SWAP_PROGRAMS};
+use crate::error::AerospacerProtocolError;
+use crate::validation::{ValidateParams, validate_pubkey};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateSwapProgramWhitelistParams {
+    pub add_programs: Vec<Pubkey>,
+    pub remove_programs: Vec<Pubkey>,
+}
+
+impl ValidateParams for UpdateSwapProgramWhitelistParams {
+    fn validate(&self) -> Result<()> {
+        require!(
+            self.add_programs.len() <= MAX_WHITELISTED_SWAP_PROGRAMS
+                && self.remove_programs.len() <= MAX_WHITELISTED_SWAP_PROGRAMS,
+            AerospacerProtocolError::InvalidList
+        );
+        for program in self.add_programs.iter() {
+            validate_pubkey(program)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateSwapProgramWhitelist<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + SwapProgramWhitelist::LEN,
+        seeds = [b"swap_program_whitelist"],
+        bump
+    )]
+    pub swap_program_whitelist: Account<'info, SwapProgramWhitelist>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for update_swap_program_whitelist instruction
+/// Removing every program switches leverage_open off again
+pub fn handler(ctx: Context<UpdateSwapProgramWhitelist>, params: UpdateSwapProgramWhitelistParams) -> Result<()> {
+    params.validate()?;
+
+    let whitelist = &mut ctx.accounts.swap_program_whitelist;
+
+    for program in params.remove_programs.iter() {
+        if let Some(pos) = whitelist.programs.iter().position(|p| p == program) {
+            whitelist.programs.swap_remove(pos);
+            msg!("Swap program removed: {}", program);
+        }
+    }
+
+    for program in params.add_programs.iter() {
+        if whitelist.contains(program) {
+            continue;
+        }
+        require!(
+            whitelist.programs.len() < MAX_WHITELISTED_SWAP_PROGRAMS,
+            AerospacerProtocolError::SwapProgramWhitelistFull
+        );
+        whitelist.programs.push(*program);
+        msg!("Swap program whitelisted: {}", program);
+    }
+
+    msg!("Whitelisted swap programs: {}", whitelist.programs.len());
+
+    Ok(())
+}
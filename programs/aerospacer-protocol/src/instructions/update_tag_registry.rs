@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::state::{StateAccount, TagRegistry, MAX_REGISTERED_TAGS, UNTAGGED};
+use crate::error::AerospacerProtocolError;
+use crate::validation::{ValidateParams};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateTagRegistryParams {
+    pub add_tags: Vec<[u8; 32]>,
+    pub remove_tags: Vec<[u8; 32]>,
+    pub enforce: Option<bool>, // Require open_trove tags to be registered
+}
+
+impl ValidateParams for UpdateTagRegistryParams {
+    fn validate(&self) -> Result<()> {
+        require!(
+            self.add_tags.len() <= MAX_REGISTERED_TAGS && self.remove_tags.len() <= MAX_REGISTERED_TAGS,
+            AerospacerProtocolError::InvalidList
+        );
+        require!(
+            self.add_tags.iter().all(|tag| *tag != UNTAGGED),
+            AerospacerProtocolError::InvalidTroveTag
+        );
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateTagRegistry<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.load()?.admin == admin.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + TagRegistry::LEN,
+        seeds = [b"tag_registry"],
+        bump
+    )]
+    pub tag_registry: Account<'info, TagRegistry>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<UpdateTagRegistry>, params: UpdateTagRegistryParams) -> Result<()> {
+    params.validate()?;
+    
+    let registry = &mut ctx.accounts.tag_registry;
+    
+    for tag in params.remove_tags.iter() {
+        if let Some(pos) = registry.tags.iter().position(|t| t == tag) {
+            registry.tags.swap_remove(pos);
+            msg!("Tag removed: {:?}", tag);
+        }
+    }
+    
+    for tag in params.add_tags.iter() {
+        if registry.contains(tag) {
+            continue;
+        }
+        require!(
+            registry.tags.len() < MAX_REGISTERED_TAGS,
+            AerospacerProtocolError::TagRegistryFull
+        );
+        registry.tags.push(*tag);
+        msg!("Tag registered: {:?}", tag);
+    }
+    
+    if let Some(enforce) = params.enforce {
+        ctx.accounts.state.load_mut()?.enforce_trove_tags = enforce as u8;
+        msg!("Trove tag enforcement: {}", enforce);
+    }
+    
+    msg!("Registered tags: {}", registry.tags.len());
+    
+    Ok(())
+}
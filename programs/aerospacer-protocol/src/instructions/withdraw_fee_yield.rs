@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::utils::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct WithdrawFeeYield<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user_stake_amount", user.key().as_ref()],
+        bump,
+        constraint = user_stake_amount.owner == user.key() @ AerospacerProtocolError::Unauthorized
+    )]
+    pub user_stake_amount: Account<'info, UserStakeAmount>,
+
+    #[account(
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: AccountLoader<'info, StateAccount>,
+
+    /// CHECK: This is the stable coin mint account
+    #[account(
+        constraint = stable_coin_mint.key() == state.load()?.stable_coin_addr @ AerospacerProtocolError::InvalidMint
+    )]
+    pub stable_coin_mint: UncheckedAccount<'info>,
+
+    // User's aUSD ATA, created when missing
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = stable_coin_mint,
+        associated_token::authority = user
+    )]
+    pub user_stablecoin_account: Box<Account<'info, TokenAccount>>,
+
+    /// Protocol stablecoin vault - receives stability pool deposits and routed fees
+    #[account(
+        mut,
+        seeds = [b"protocol_stablecoin_vault"],
+        bump
+    )]
+    pub protocol_stablecoin_vault: Account<'info, TokenAccount>,
+
+    /// Optional position summary, refreshed when passed
+    #[account(
+        mut,
+        seeds = [b"user_index", user.key().as_ref()],
+        bump
+    )]
+    pub user_index: Option<Account<'info, UserIndex>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Claim the aUSD fee yield (G factor) earned by the caller's stability pool deposit
+pub fn handler(ctx: Context<WithdrawFeeYield>) -> Result<()> {
+    let user_stake_amount = &mut ctx.accounts.user_stake_amount;
+    let state = ctx.accounts.state.load()?;
+
+    let fee_gain = settle_fee_yield(user_stake_amount, &state)?;
+    // Reward emissions share the deposit snapshots refreshed below
+    settle_reward_emissions(user_stake_amount, &state)?;
+
+    // Re-snapshot the deposit so the settled yield cannot be claimed twice
+    // (same compounding as stake/unstake - the deposit moves to the current P/scale/epoch)
+    if user_stake_amount.amount > 0 && user_stake_amount.p_snapshot > 0 {
+        let compounded = calculate_compounded_stake(
+            user_stake_amount.amount,
+            user_stake_amount.p_snapshot,
+            state.p_factor,
+            user_stake_amount.scale_snapshot,
+            state.current_scale,
+            user_stake_amount.epoch_snapshot,
+            state.epoch,
+        )?;
+        user_stake_amount.amount = compounded;
+        user_stake_amount.p_snapshot = state.p_factor;
+        user_stake_amount.epoch_snapshot = state.epoch;
+        user_stake_amount.scale_snapshot = state.current_scale;
+        user_stake_amount.g_snapshot = state.g_factor;
+        user_stake_amount.reward_g_snapshot = state.reward_g_factor;
+        user_stake_amount.last_update_block = Clock::get()?.slot;
+    }
+
+    let claimable = user_stake_amount.pending_fee_gain;
+    if claimable == 0 {
+        msg!("No fee yield available");
+        if let Some(user_index) = ctx.accounts.user_index.as_mut() {
+            user_index.sync_stake(user_stake_amount);
+            user_index.last_update_slot = Clock::get()?.slot;
+        }
+        return Ok(());
+    }
+
+    require!(
+        ctx.accounts.protocol_stablecoin_vault.amount >= claimable,
+        AerospacerProtocolError::InsufficientCollateral
+    );
+
+    let transfer_seeds = &[
+        b"protocol_stablecoin_vault".as_ref(),
+        &[ctx.bumps.protocol_stablecoin_vault],
+    ];
+    let transfer_signer = &[&transfer_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+            to: ctx.accounts.user_stablecoin_account.to_account_info(),
+            authority: ctx.accounts.protocol_stablecoin_vault.to_account_info(),
+        },
+        transfer_signer,
+    );
+    anchor_spl::token::transfer(transfer_ctx, claimable)?;
+
+    user_stake_amount.pending_fee_gain = 0;
+
+    msg!("Fee yield withdrawn successfully");
+    msg!("User: {}", ctx.accounts.user.key());
+    msg!("Settled this claim: {} aUSD", fee_gain);
+    msg!("Amount: {} aUSD", claimable);
+    msg!("Remaining deposit: {} aUSD", user_stake_amount.amount);
+
+    if let Some(user_index) = ctx.accounts.user_index.as_mut() {
+        user_index.sync_stake(user_stake_amount);
+        user_index.last_update_slot = Clock::get()?.slot;
+    }
+
+    Ok(())
+}
@@ -1,14 +1,22 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Token, TokenAccount, Transfer, Mint};
 use crate::state::*;
 use crate::utils::*;
 use crate::error::*;
+use crate::validation::{ValidateParams, validate_denom};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct WithdrawLiquidationGainsParams {
     pub collateral_denom: String,
 }
 
+impl ValidateParams for WithdrawLiquidationGainsParams {
+    fn validate(&self) -> Result<()> {
+        validate_denom(&self.collateral_denom)
+    }
+}
+
 #[derive(Accounts)]
 #[instruction(params: WithdrawLiquidationGainsParams)]
 pub struct WithdrawLiquidationGains<'info> {
@@ -40,13 +48,19 @@ pub struct WithdrawLiquidationGains<'info> {
     pub stability_pool_snapshot: Account<'info, StabilityPoolSnapshot>,
 
     #[account(mut)]
-    pub state: Account<'info, StateAccount>,
-
-    #[account(mut)]
-    pub user_collateral_account: Account<'info, TokenAccount>,
+    pub state: AccountLoader<'info, StateAccount>,
 
     /// Collateral mint for validation
-    pub collateral_mint: Account<'info, Mint>,
+    pub collateral_mint: Box<Account<'info, Mint>>,
+
+    // User's collateral ATA, created when missing
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = user
+    )]
+    pub user_collateral_account: Box<Account<'info, TokenAccount>>,
 
     /// CHECK: Protocol collateral vault PDA (stability pool)
     #[account(
@@ -65,16 +79,19 @@ pub struct WithdrawLiquidationGains<'info> {
     pub total_collateral_amount: AccountInfo<'info>,
 
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 
 
 pub fn handler(ctx: Context<WithdrawLiquidationGains>, params: WithdrawLiquidationGainsParams) -> Result<()> {
+    params.validate()?;
+    
     let user_stake_amount = &mut ctx.accounts.user_stake_amount;
     let user_collateral_snapshot = &mut ctx.accounts.user_collateral_snapshot;
     let stability_pool_snapshot = &ctx.accounts.stability_pool_snapshot;
-    let _state = &mut ctx.accounts.state;
+    let current_epoch = ctx.accounts.state.load()?.epoch;
     
     // SECURITY: Validate user has stake
     require!(
@@ -124,7 +141,11 @@ pub fn handler(ctx: Context<WithdrawLiquidationGains>, params: WithdrawLiquidati
         user_collateral_snapshot.owner = ctx.accounts.user.key();
         user_collateral_snapshot.denom = params.collateral_denom.clone();
         user_collateral_snapshot.pending_collateral_gain = 0;
-        msg!("First withdrawal for {} - calculating full accumulated gains", params.collateral_denom);
+        // SECURITY: Start at the current S - a snapshot at S = 0 would pay out the pool's whole
+        // gain history for this denom
+        user_collateral_snapshot.s_snapshot = stability_pool_snapshot.s_factor;
+        user_collateral_snapshot.epoch_snapshot = current_epoch;
+        msg!("No S snapshot for {} yet - starting one at the current S", params.collateral_denom);
     } else {
         // SECURITY: Validate existing snapshot belongs to user and matches denom
         require!(
@@ -138,16 +159,18 @@ pub fn handler(ctx: Context<WithdrawLiquidationGains>, params: WithdrawLiquidati
     }
     
     // Calculate collateral gain using helper function
-    // If s_snapshot = 0 (first withdrawal), this calculates the full accumulated gain
-    let collateral_gain = calculate_collateral_gain(
-        user_stake_amount.amount,
-        user_collateral_snapshot.s_snapshot, // 0 on first withdrawal = full gain
+    // Gains carried over from earlier withdrawals are paid out together with new ones
+    let s_snapshot_before = user_collateral_snapshot.s_snapshot;
+    let pending_gain = user_collateral_snapshot.pending_collateral_gain;
+    let (collateral_gain, total_gain) = settle_collateral_gain(
+        user_stake_amount,
+        user_collateral_snapshot,
         stability_pool_snapshot.s_factor,
-        user_stake_amount.p_snapshot,
+        current_epoch,
     )?;
     
     // Check if user has any gains
-    if collateral_gain == 0 {
+    if total_gain == 0 {
         msg!("No collateral gains available for {}", params.collateral_denom);
         return Ok(());
     }
@@ -155,15 +178,16 @@ pub fn handler(ctx: Context<WithdrawLiquidationGains>, params: WithdrawLiquidati
     msg!("SNAPSHOT-BASED WITHDRAWAL:");
     msg!("  User deposit: {}", user_stake_amount.amount);
     msg!("  P_snapshot: {}", user_stake_amount.p_snapshot);
-    msg!("  S_snapshot ({}): {}", params.collateral_denom, user_collateral_snapshot.s_snapshot);
+    msg!("  S_snapshot ({}): {}", params.collateral_denom, s_snapshot_before);
     msg!("  S_current ({}): {}", params.collateral_denom, stability_pool_snapshot.s_factor);
     msg!("  Calculated gain: {}", collateral_gain);
+    msg!("  Pending gain: {}", pending_gain);
     
     // SECURITY: Verify protocol vault has sufficient balance before transfer
     let vault_data = ctx.accounts.protocol_collateral_vault.try_borrow_data()?;
     let vault_account = TokenAccount::try_deserialize(&mut &vault_data[..])?;
     require!(
-        vault_account.amount >= collateral_gain,
+        vault_account.amount >= total_gain,
         AerospacerProtocolError::InsufficientCollateral
     );
     drop(vault_data);
@@ -185,19 +209,18 @@ pub fn handler(ctx: Context<WithdrawLiquidationGains>, params: WithdrawLiquidati
         },
         transfer_signer,
     );
-    anchor_spl::token::transfer(transfer_ctx, collateral_gain)?;
+    anchor_spl::token::transfer(transfer_ctx, total_gain)?;
 
-    // Update user's S snapshot to current value (marks gains as claimed)
-    user_collateral_snapshot.s_snapshot = stability_pool_snapshot.s_factor;
+    user_collateral_snapshot.pending_collateral_gain = 0;
 
     // Update per-denom collateral total PDA
     update_total_collateral_from_account_info(
         &ctx.accounts.total_collateral_amount,
-        -(collateral_gain as i64),
+        -(total_gain as i64),
     )?;
 
     msg!("Liquidation gains withdrawn successfully (snapshot-based)");
-    msg!("Amount: {} {}", collateral_gain, params.collateral_denom);
+    msg!("Amount: {} {}", total_gain, params.collateral_denom);
     msg!("User: {}", ctx.accounts.user.key());
     msg!("S snapshot updated to: {}", stability_pool_snapshot.s_factor);
 
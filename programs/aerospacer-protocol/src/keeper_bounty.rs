@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::*;
+use crate::events::KeeperBountyPaid;
+
+/// Accounts a liquidation needs to pay a registered keeper
+pub struct KeeperBountyAccounts<'a, 'info> {
+    pub keeper: &'a mut Account<'info, Keeper>,
+    pub keeper_collateral_account: Option<&'a Account<'info, TokenAccount>>,
+    pub keeper_stablecoin_account: Option<&'a Account<'info, TokenAccount>>,
+    pub keeper_bounty_fund: Option<&'a mut Account<'info, KeeperBountyFund>>,
+    pub protocol_collateral_vault: &'a AccountInfo<'info>,
+    pub protocol_collateral_vault_bump: u8,
+    pub protocol_stablecoin_vault: &'a AccountInfo<'info>,
+    pub protocol_stablecoin_vault_bump: u8,
+    pub token_program: &'a AccountInfo<'info>,
+}
+
+/// Pay a registered keeper for `troves` liquidations and record them in its stats
+///
+/// `collateral_bounty` is the penalty share already held back from the stability pool, paid from
+/// the denom's collateral vault. The flat aUSD bounty is paid from the KeeperBountyFund when one
+/// is passed, capped by its balance - an empty fund never blocks a liquidation.
+pub fn pay_keeper_bounty(
+    accounts: KeeperBountyAccounts,
+    denom: &str,
+    collateral_bounty: u64,
+    troves: u64,
+    debt_liquidated: u64,
+) -> Result<()> {
+    if collateral_bounty > 0 {
+        let destination = accounts
+            .keeper_collateral_account
+            .ok_or(AerospacerProtocolError::MissingKeeperBountyAccount)?;
+        let seeds: &[&[u8]] = &[
+            b"protocol_collateral_vault",
+            denom.as_bytes(),
+            &[accounts.protocol_collateral_vault_bump],
+        ];
+        anchor_spl::token::transfer(
+            CpiContext::new_with_signer(
+                accounts.token_program.clone(),
+                Transfer {
+                    from: accounts.protocol_collateral_vault.clone(),
+                    to: destination.to_account_info(),
+                    authority: accounts.protocol_collateral_vault.clone(),
+                },
+                &[seeds],
+            ),
+            collateral_bounty,
+        )?;
+    }
+    
+    let flat_bounty = match accounts.keeper_bounty_fund {
+        Some(fund) => fund.take(troves),
+        None => 0,
+    };
+    if flat_bounty > 0 {
+        let destination = accounts
+            .keeper_stablecoin_account
+            .ok_or(AerospacerProtocolError::MissingKeeperBountyAccount)?;
+        let seeds: &[&[u8]] = &[b"protocol_stablecoin_vault", &[accounts.protocol_stablecoin_vault_bump]];
+        anchor_spl::token::transfer(
+            CpiContext::new_with_signer(
+                accounts.token_program.clone(),
+                Transfer {
+                    from: accounts.protocol_stablecoin_vault.clone(),
+                    to: destination.to_account_info(),
+                    authority: accounts.protocol_stablecoin_vault.clone(),
+                },
+                &[seeds],
+            ),
+            flat_bounty,
+        )?;
+    }
+    
+    let keeper = accounts.keeper;
+    keeper.troves_liquidated = keeper.troves_liquidated.saturating_add(troves);
+    keeper.debt_liquidated = keeper.debt_liquidated.saturating_add(debt_liquidated);
+    if collateral_bounty > 0 {
+        keeper.collateral_bounty_payouts = keeper.collateral_bounty_payouts.saturating_add(1);
+    }
+    keeper.flat_bounties_earned = keeper.flat_bounties_earned.saturating_add(flat_bounty);
+    
+    msg!("Keeper bounty paid to {}: {} {} + {} aUSD", keeper.keeper, collateral_bounty, denom, flat_bounty);
+    
+    emit!(KeeperBountyPaid {
+        keeper: keeper.keeper,
+        denom: denom.to_string(),
+        collateral_bounty,
+        flat_bounty,
+        troves,
+        debt_liquidated,
+    });
+    
+    Ok(())
+}
@@ -7,6 +7,7 @@ pub mod error;
 pub mod state;
 pub mod msg;
 pub mod query;
+pub mod events;
 
 // New architecture modules
 pub mod account_management;
@@ -14,6 +15,13 @@ pub mod oracle;
 pub mod trove_management;
 pub mod fees_integration;
 pub mod sorted_troves;
+pub mod trove_archive;
+pub mod keeper_bounty;
+pub mod liquidation_reserve;
+pub mod trove_index;
+pub mod liquidation_log;
+pub mod mint_gate;
+pub mod validation;
 
 // Core instruction handlers
 pub mod instructions;
@@ -48,12 +56,12 @@ pub mod aerospacer_protocol {
     }
 
     // Add collateral to an existing trove (equivalent to INJECTIVE's add_collateral)
-    pub fn add_collateral(ctx: Context<AddCollateral>, params: AddCollateralParams) -> Result<()> {
+    pub fn add_collateral<'info>(ctx: Context<'_, '_, 'info, 'info, AddCollateral<'info>>, params: AddCollateralParams) -> Result<()> {
         instructions::add_collateral::handler(ctx, params)
     }
 
     // Remove collateral from an existing trove (equivalent to INJECTIVE's remove_collateral)
-    pub fn remove_collateral(ctx: Context<RemoveCollateral>, params: RemoveCollateralParams) -> Result<()> {
+    pub fn remove_collateral<'info>(ctx: Context<'_, '_, 'info, 'info, RemoveCollateral<'info>>, params: RemoveCollateralParams) -> Result<()> {
         instructions::remove_collateral::handler(ctx, params)
     }
 
@@ -68,17 +76,17 @@ pub mod aerospacer_protocol {
     }
 
     // Close trove by repaying all debt and withdrawing all collateral (equivalent to INJECTIVE's close_trove)
-    pub fn close_trove(ctx: Context<CloseTrove>, params: CloseTroveParams) -> Result<()> {
+    pub fn close_trove<'info>(ctx: Context<'_, '_, 'info, 'info, CloseTrove<'info>>, params: CloseTroveParams) -> Result<()> {
         instructions::close_trove::handler(ctx, params)
     }
 
     // Liquidate undercollateralized troves (equivalent to INJECTIVE's liquidate_troves)
-    pub fn liquidate_troves(ctx: Context<LiquidateTroves>, params: LiquidateTrovesParams) -> Result<()> {
+    pub fn liquidate_troves<'info>(ctx: Context<'_, '_, 'info, 'info, LiquidateTroves<'info>>, params: LiquidateTrovesParams) -> Result<()> {
         instructions::liquidate_troves::handler(ctx, params)
     }
 
-    // Liquidate a single undercollateralized trove (no remaining_accounts)
-    pub fn liquidate_trove(ctx: Context<LiquidateTrove>, params: LiquidateTroveParams) -> Result<()> {
+    // Liquidate a single undercollateralized trove, with its other collateral legs in remaining_accounts
+    pub fn liquidate_trove<'info>(ctx: Context<'_, '_, 'info, 'info, LiquidateTrove<'info>>, params: LiquidateTroveParams) -> Result<()> {
         instructions::liquidate_trove::handler(ctx, params)
     }
 
@@ -88,7 +96,7 @@ pub mod aerospacer_protocol {
     }
 
     // Stake stablecoin to earn liquidation gains (equivalent to INJECTIVE's stake)
-    pub fn stake(ctx: Context<Stake>, params: StakeParams) -> Result<()> {
+    pub fn stake<'info>(ctx: Context<'_, '_, 'info, 'info, Stake<'info>>, params: StakeParams) -> Result<()> {
         instructions::stake::handler(ctx, params)
     }
 
@@ -103,10 +111,371 @@ pub mod aerospacer_protocol {
     }
 
     // Swap stablecoin for collateral (equivalent to INJECTIVE's redeem)
-    pub fn redeem(ctx: Context<Redeem>, params: RedeemParams) -> Result<()> {
+    pub fn redeem<'info>(ctx: Context<'_, '_, 'info, 'info, Redeem<'info>>, params: RedeemParams) -> Result<()> {
         instructions::redeem::handler(ctx, params)
     }
 
+    // Keeper crank: refresh a denom's cached price and the ICRs of its riskiest troves in one step
+    pub fn refresh_denom_bucket(ctx: Context<RefreshDenomBucket>, params: RefreshDenomBucketParams) -> Result<()> {
+        instructions::refresh_denom_bucket::handler(ctx, params)
+    }
+
+    // Renormalize a denom's L redistribution factors once they cross the threshold (permissionless crank)
+    pub fn renormalize_l_factors(ctx: Context<RenormalizeLFactors>, params: RenormalizeLFactorsParams) -> Result<()> {
+        instructions::renormalize_l_factors::handler(ctx, params)
+    }
+
+    // Register or remove trove attribution tags (admin only)
+    pub fn update_tag_registry(ctx: Context<UpdateTagRegistry>, params: UpdateTagRegistryParams) -> Result<()> {
+        instructions::update_tag_registry::handler(ctx, params)
+    }
+
+    // Configure oracle staleness limit and last-good price fallback (admin only)
+    pub fn update_oracle_fallback(ctx: Context<UpdateOracleFallback>, params: UpdateOracleFallbackParams) -> Result<()> {
+        instructions::update_oracle_fallback::handler(ctx, params)
+    }
+
+    // Claim aUSD fee yield routed to the stability pool
+    pub fn withdraw_fee_yield(ctx: Context<WithdrawFeeYield>) -> Result<()> {
+        instructions::withdraw_fee_yield::handler(ctx)
+    }
+
+    // Claim stability pool collateral gains for every denom passed via remaining_accounts
+    pub fn claim_all_gains<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimAllGains<'info>>) -> Result<()> {
+        instructions::claim_all_gains::handler(ctx)
+    }
+
+    // Protocol health status for uptime monitors, returned via return data
+    pub fn health_check(ctx: Context<HealthCheck>) -> Result<()> {
+        instructions::health_check::handler(ctx)
+    }
+
+    // Set the treasury and de-peg emergency flag for the priority redemption lane (admin only)
+    pub fn update_priority_redemption(ctx: Context<UpdatePriorityRedemption>, params: UpdatePriorityRedemptionParams) -> Result<()> {
+        instructions::update_priority_redemption::handler(ctx, params)
+    }
+
+    // Configure the stability pool reward token emission schedule (admin only)
+    pub fn configure_reward_emissions(ctx: Context<ConfigureRewardEmissions>, params: ConfigureRewardEmissionsParams) -> Result<()> {
+        instructions::configure_reward_emissions::handler(ctx, params)
+    }
+
+    // Claim reward token emissions earned by a stability pool deposit
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        instructions::claim_rewards::handler(ctx)
+    }
+
+    // Create or rebuild the per-user position summary
+    pub fn sync_user_index(ctx: Context<SyncUserIndex>) -> Result<()> {
+        instructions::sync_user_index::handler(ctx)
+    }
+
+    // Repay small amounts across several troves for one payer (liquidation-protection services)
+    pub fn repay_batch<'info>(ctx: Context<'_, '_, 'info, 'info, RepayBatch<'info>>, params: RepayBatchParams) -> Result<()> {
+        instructions::repay_batch::handler(ctx, params)
+    }
+
+    // Register a frontend operator with its reward emission kickback rate
+    pub fn register_frontend(ctx: Context<RegisterFrontend>, params: RegisterFrontendParams) -> Result<()> {
+        instructions::register_frontend::handler(ctx, params)
+    }
+
+    // Claim the reward emissions credited to a frontend operator
+    pub fn claim_frontend_rewards(ctx: Context<ClaimFrontendRewards>) -> Result<()> {
+        instructions::claim_frontend_rewards::handler(ctx)
+    }
+
+    // Flash mint aUSD that must be repaid (plus fee) later in the same transaction
+    pub fn flash_mint<'info>(ctx: Context<'_, '_, 'info, 'info, FlashMint<'info>>, params: FlashMintParams) -> Result<()> {
+        instructions::flash_mint::handler(ctx, params)
+    }
+
+    // Repay an outstanding flash mint
+    pub fn repay_flash_mint(ctx: Context<RepayFlashMint>, params: RepayFlashMintParams) -> Result<()> {
+        instructions::repay_flash_mint::handler(ctx, params)
+    }
+
+    // Waive borrowing fees on a new collateral denom until a slot deadline (admin only)
+    pub fn set_denom_promotion(ctx: Context<SetDenomPromotion>, params: SetDenomPromotionParams) -> Result<()> {
+        instructions::set_denom_promotion::handler(ctx, params)
+    }
+
+    // Create or update the USDC Peg Stability Module (admin only)
+    pub fn configure_psm(ctx: Context<ConfigurePsm>, params: ConfigurePsmParams) -> Result<()> {
+        instructions::configure_psm::handler(ctx, params)
+    }
+
+    // Mint aUSD 1:1 against USDC deposited into the PSM vault, minus the swap-in spread
+    pub fn psm_swap_in(ctx: Context<PsmSwapIn>, params: PsmSwapInParams) -> Result<()> {
+        instructions::psm_swap_in::handler(ctx, params)
+    }
+
+    // Redeem aUSD 1:1 for USDC from the PSM vault, minus the swap-out spread
+    pub fn psm_swap_out(ctx: Context<PsmSwapOut>, params: PsmSwapOutParams) -> Result<()> {
+        instructions::psm_swap_out::handler(ctx, params)
+    }
+
+    // Update redemption size, per-transaction trove limit and volume throttle (admin only)
+    pub fn update_redemption_limits(ctx: Context<UpdateRedemptionLimits>, params: UpdateRedemptionLimitsParams) -> Result<()> {
+        instructions::update_redemption_limits::handler(ctx, params)
+    }
+
+    // Route collateral seized while the stability pool was empty (admin only)
+    pub fn route_undistributed_seizure(ctx: Context<RouteUndistributedSeizure>, params: RouteUndistributedSeizureParams) -> Result<()> {
+        instructions::route_undistributed_seizure::handler(ctx, params)
+    }
+
+    // Authorize a key to add collateral and repay on the owner's trove (never withdraw)
+    pub fn approve_delegate(ctx: Context<ApproveDelegate>, params: ApproveDelegateParams) -> Result<()> {
+        instructions::approve_delegate::handler(ctx, params)
+    }
+
+    // Revoke a trove delegate
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        instructions::revoke_delegate::handler(ctx)
+    }
+
+    // Set the governance share of liquidation penalties held back for public goods (admin only)
+    pub fn update_public_goods_share(ctx: Context<UpdatePublicGoodsShare>, params: UpdatePublicGoodsShareParams) -> Result<()> {
+        instructions::update_public_goods_share::handler(ctx, params)
+    }
+
+    // Send a denom's booked public-goods share to the fees program's public-goods address
+    pub fn route_public_goods(ctx: Context<RoutePublicGoods>, params: RoutePublicGoodsParams) -> Result<()> {
+        instructions::route_public_goods::handler(ctx, params)
+    }
+
+    // Set up the compressed archive of closed troves (admin only)
+    pub fn initialize_trove_archive(ctx: Context<InitializeTroveArchive>, params: InitializeTroveArchiveParams) -> Result<()> {
+        instructions::initialize_trove_archive::handler(ctx, params)
+    }
+
+    // Move a closed or liquidated trove into the compressed archive
+    pub fn archive_trove(ctx: Context<ArchiveTrove>, params: ArchiveTroveParams) -> Result<()> {
+        instructions::archive_trove::handler(ctx, params)
+    }
+
+    // Move a trove to another wallet (both must sign)
+    pub fn transfer_trove<'info>(ctx: Context<'_, '_, 'info, 'info, TransferTrove<'info>>, params: TransferTroveParams) -> Result<()> {
+        instructions::transfer_trove::handler(ctx, params)
+    }
+
+    // Close the caller's zero-balance PDAs and refund their rent
+    pub fn reclaim_rent<'info>(ctx: Context<'_, '_, 'info, 'info, ReclaimRent<'info>>) -> Result<()> {
+        instructions::reclaim_rent::handler(ctx)
+    }
+
+    // Record a keeper liveness heartbeat
+    pub fn register_heartbeat(ctx: Context<RegisterHeartbeat>) -> Result<()> {
+        instructions::register_heartbeat::handler(ctx)
+    }
+
+    // Query keepers whose last heartbeat is older than max_age_slots
+    pub fn query_stale_keepers(ctx: Context<QueryStaleKeepers>, params: QueryStaleKeepersParams) -> Result<()> {
+        instructions::query_stale_keepers::handler(ctx, params)
+    }
+
+    // Opt in as a liquidation keeper eligible for bounties
+    pub fn register_keeper(ctx: Context<RegisterKeeper>) -> Result<()> {
+        instructions::register_keeper::handler(ctx)
+    }
+
+    // Close the caller's keeper registration
+    pub fn deregister_keeper(ctx: Context<DeregisterKeeper>) -> Result<()> {
+        instructions::deregister_keeper::handler(ctx)
+    }
+
+    // Set the keeper bounty share and flat bounty (admin only)
+    pub fn configure_keeper_bounties(ctx: Context<ConfigureKeeperBounties>, params: ConfigureKeeperBountiesParams) -> Result<()> {
+        instructions::configure_keeper_bounties::handler(ctx, params)
+    }
+
+    // Top up the flat keeper bounty fund
+    pub fn fund_keeper_bounties(ctx: Context<FundKeeperBounties>, params: FundKeeperBountiesParams) -> Result<()> {
+        instructions::fund_keeper_bounties::handler(ctx, params)
+    }
+
+    // Top up the aUSD backstop for bad debt from underwater liquidations
+    pub fn fund_bad_debt_backstop(ctx: Context<FundBadDebtBackstop>, params: FundBadDebtBackstopParams) -> Result<()> {
+        instructions::fund_bad_debt_backstop::handler(ctx, params)
+    }
+
+    // Manage the liquidator whitelist and permissioned liquidation mode (admin only)
+    pub fn update_liquidator_whitelist(ctx: Context<UpdateLiquidatorWhitelist>, params: UpdateLiquidatorWhitelistParams) -> Result<()> {
+        instructions::update_liquidator_whitelist::handler(ctx, params)
+    }
+
+    // Put the collateral of a trove too large for the stability pool up for Dutch auction
+    pub fn start_auction<'info>(ctx: Context<'_, '_, 'info, 'info, StartAuction<'info>>, params: StartAuctionParams) -> Result<()> {
+        instructions::start_auction::handler(ctx, params)
+    }
+
+    // Buy collateral from a liquidation auction at its current price
+    pub fn bid(ctx: Context<Bid>, params: BidParams) -> Result<()> {
+        instructions::bid::handler(ctx, params)
+    }
+
+    // Close a finished liquidation auction and redistribute what it did not raise
+    pub fn settle(ctx: Context<Settle>, params: SettleParams) -> Result<()> {
+        instructions::settle::handler(ctx, params)
+    }
+
+    // Register the external strategy program that seized collateral is forwarded to (admin only)
+    pub fn configure_rebalancer(ctx: Context<ConfigureRebalancer>, params: ConfigureRebalancerParams) -> Result<()> {
+        instructions::configure_rebalancer::handler(ctx, params)
+    }
+
+    // Forward undistributed seized collateral to the registered rebalancer hook
+    pub fn forward_seized_collateral<'info>(ctx: Context<'_, '_, 'info, 'info, ForwardSeizedCollateral<'info>>, params: ForwardSeizedCollateralParams) -> Result<()> {
+        instructions::forward_seized_collateral::handler(ctx, params)
+    }
+
+    // Admin: list a collateral denom by creating its vault, total and stability snapshot PDAs
+    pub fn register_collateral(ctx: Context<RegisterCollateral>, params: RegisterCollateralParams) -> Result<()> {
+        instructions::register_collateral::handler(ctx, params)
+    }
+
+    // Admin: sunset a collateral denom (no new troves or borrowing; wind-down paths stay open)
+    pub fn deactivate_collateral(ctx: Context<DeactivateCollateral>, params: DeactivateCollateralParams) -> Result<()> {
+        instructions::deactivate_collateral::handler(ctx, params)
+    }
+
+    // Admin: set a collateral denom's MCR, liquidation threshold and borrowing fee
+    pub fn set_collateral_params(ctx: Context<SetCollateralParams>, params: SetCollateralParamsParams) -> Result<()> {
+        instructions::set_collateral_params::handler(ctx, params)
+    }
+
+    // Keeper crank: write a denom's live oracle price to its price cache
+    pub fn refresh_price(ctx: Context<RefreshPrice>, params: RefreshPriceParams) -> Result<()> {
+        instructions::refresh_price::handler(ctx, params)
+    }
+
+    // Keeper crank: recompute a trove's stored ICR at the live price after pending rewards
+    pub fn refresh_trove_icr(ctx: Context<RefreshTroveIcr>, params: RefreshTroveIcrParams) -> Result<()> {
+        instructions::refresh_trove_icr::handler(ctx, params)
+    }
+
+    // Admin: make neighbor hints mandatory for trove instructions
+    pub fn update_hint_policy(ctx: Context<UpdateHintPolicy>, params: UpdateHintPolicyParams) -> Result<()> {
+        instructions::update_hint_policy::handler(ctx, params)
+    }
+
+    // Admin: move staked aUSD from the protocol stablecoin vault into the stability pool vault
+    pub fn migrate_stability_pool_vault(ctx: Context<MigrateStabilityPoolVault>) -> Result<()> {
+        instructions::migrate_stability_pool_vault::handler(ctx)
+    }
+
+    // Admin: set the per-trove liquidation reserve and the minimum net debt
+    pub fn update_liquidation_reserve(ctx: Context<UpdateLiquidationReserve>, params: UpdateLiquidationReserveParams) -> Result<()> {
+        instructions::update_liquidation_reserve::handler(ctx, params)
+    }
+
+    // Keeper crank: revalue a denom's collateral and refresh the cached TCR
+    pub fn refresh_tcr(ctx: Context<RefreshTcr>, params: RefreshTcrParams) -> Result<()> {
+        instructions::refresh_tcr::handler(ctx, params)
+    }
+
+    // Query the total collateral ratio (read-only, via return data)
+    pub fn get_tcr(ctx: Context<GetTcr>) -> Result<()> {
+        instructions::get_tcr::handler(ctx)
+    }
+
+    // Admin: set the bootstrap period and its liquidation batch limit
+    pub fn update_bootstrap_period(ctx: Context<UpdateBootstrapPeriod>, params: UpdateBootstrapPeriodParams) -> Result<()> {
+        instructions::update_bootstrap_period::handler(ctx, params)
+    }
+
+    // Keeper crank: tombstone trove index entries of troves that are gone
+    pub fn prune_trove_index(ctx: Context<PruneTroveIndex>, params: PruneTroveIndexParams) -> Result<()> {
+        instructions::prune_trove_index::handler(ctx, params)
+    }
+
+    // Set the dust fee threshold, creating the fee accumulator and its vault (admin only)
+    pub fn configure_fee_accumulator(ctx: Context<ConfigureFeeAccumulator>, params: ConfigureFeeAccumulatorParams) -> Result<()> {
+        instructions::configure_fee_accumulator::handler(ctx, params)
+    }
+
+    // Send the parked dust fees through aerospacer-fees (permissionless)
+    pub fn distribute_accumulated(ctx: Context<DistributeAccumulated>) -> Result<()> {
+        instructions::distribute_accumulated::handler(ctx)
+    }
+
+    // Move the aUSD mint authority to the dedicated mint authority PDA and set the trove debt cap (admin only)
+    pub fn assign_mint_authority(ctx: Context<AssignMintAuthority>, params: AssignMintAuthorityParams) -> Result<()> {
+        instructions::assign_mint_authority::handler(ctx, params)
+    }
+
+    // Propose handing the aUSD mint authority to a new address after the timelock (admin only)
+    pub fn propose_mint_authority_migration(ctx: Context<ProposeMintAuthorityMigration>, params: ProposeMintAuthorityMigrationParams) -> Result<()> {
+        instructions::propose_mint_authority_migration::handler(ctx, params)
+    }
+
+    // Cancel the pending mint authority migration (admin only)
+    pub fn cancel_mint_authority_migration(ctx: Context<CancelMintAuthorityMigration>) -> Result<()> {
+        instructions::cancel_mint_authority_migration::handler(ctx)
+    }
+
+    // Hand the aUSD mint authority to the proposed address once the timelock has passed (admin only)
+    pub fn execute_mint_authority_migration(ctx: Context<ExecuteMintAuthorityMigration>) -> Result<()> {
+        instructions::execute_mint_authority_migration::handler(ctx)
+    }
+
+    // Create the Metaplex metadata (name, symbol, URI) of the aUSD mint (admin only, once)
+    pub fn init_stablecoin(ctx: Context<InitStablecoin>, params: InitStablecoinParams) -> Result<()> {
+        instructions::init_stablecoin::handler(ctx, params)
+    }
+
+    // Update the protocol-wide MCR and protocol fee within their bounds (admin only)
+    pub fn update_config(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+        instructions::update_config::handler(ctx, params)
+    }
+
+    // Create the user's trove operation history (ring buffer of recent operations)
+    pub fn init_trove_history(ctx: Context<InitTroveHistory>) -> Result<()> {
+        instructions::init_trove_history::handler(ctx)
+    }
+
+    // Query a trove's unapplied redistribution debt and collateral (read-only, via return data)
+    pub fn get_pending_rewards(ctx: Context<GetPendingRewards>, params: GetPendingRewardsParams) -> Result<()> {
+        instructions::get_pending_rewards::handler(ctx, params)
+    }
+
+    // Repay debt and withdraw the proportional collateral in one ICR check
+    pub fn deleverage<'info>(ctx: Context<'_, '_, 'info, 'info, Deleverage<'info>>, params: DeleverageParams) -> Result<()> {
+        instructions::deleverage::handler(ctx, params)
+    }
+
+    // Add or remove swap programs leverage_open may route through (admin only)
+    pub fn update_swap_program_whitelist(ctx: Context<UpdateSwapProgramWhitelist>, params: UpdateSwapProgramWhitelistParams) -> Result<()> {
+        instructions::update_swap_program_whitelist::handler(ctx, params)
+    }
+
+    // Borrow, swap the aUSD into collateral and deposit it in one ICR check
+    pub fn leverage_open<'info>(ctx: Context<'_, '_, 'info, 'info, LeverageOpen<'info>>, params: LeverageOpenParams) -> Result<()> {
+        instructions::leverage_open::handler(ctx, params)
+    }
+
+    // Fill the aUSD escrowed on a redemption ticket against further troves, or cancel it
+    pub fn continue_redemption<'info>(ctx: Context<'_, '_, 'info, 'info, ContinueRedemption<'info>>, params: ContinueRedemptionParams) -> Result<()> {
+        instructions::continue_redemption::handler(ctx, params)
+    }
+
+    // Grow a stability pool deposit from before the fee yield and reward fields (permissionless)
+    pub fn migrate_user_stake(ctx: Context<MigrateUserStake>) -> Result<()> {
+        instructions::migrate_user_stake::handler(ctx)
+    }
+
+    // Admin: rewrite a state and collateral totals created with the Borsh layouts as zero-copy accounts
+    pub fn migrate_zero_copy_accounts(ctx: Context<MigrateZeroCopyAccounts>) -> Result<()> {
+        instructions::migrate_zero_copy_accounts::handler(ctx)
+    }
+
+    // Write exported account data into a local validator (fork-testing builds only, admin once state exists)
+    #[cfg(feature = "fork-testing")]
+    pub fn import_account(ctx: Context<ImportAccount>, params: ImportAccountParams) -> Result<()> {
+        instructions::import_account::handler(ctx, params)
+    }
+
     // NOTE: ADMIN functions removed - obsolete with off-chain sorting architecture
     // - reset_sorted_troves: No longer needed (no sorted list state to reset)
     // - close_node: No longer needed (no Node accounts to close)
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke;
+use crate::error::*;
+use crate::trove_archive::NOOP_PROGRAM_ID;
+
+/// First byte of every liquidation record, telling it apart from the archive's noop logs
+pub const LIQUIDATION_RECORD_TAG: u8 = b'L';
+pub const LIQUIDATION_RECORD_VERSION: u8 = 1;
+
+/// One liquidated trove, logged through the SPL noop program so indexers can rebuild the full
+/// liquidation history from transaction data instead of a rent-bearing account per event
+///
+/// Encoding (little endian, no padding):
+/// tag(1) version(1) owner(32) trove_id(2) debt(8) collateral(8) slot(8) denom_len(1) denom
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LiquidationRecord {
+    pub owner: Pubkey,
+    pub trove_id: u16,
+    pub denom: String,
+    pub debt_amount: u64,          // Debt cleared by the liquidation
+    pub collateral_amount: u64,    // Collateral seized
+    pub slot: u64,
+}
+
+impl LiquidationRecord {
+    pub const FIXED_LEN: usize = 1 + 1 + 32 + 2 + 8 + 8 + 8 + 1;
+
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        out.push(LIQUIDATION_RECORD_TAG);
+        out.push(LIQUIDATION_RECORD_VERSION);
+        out.extend_from_slice(self.owner.as_ref());
+        out.extend_from_slice(&self.trove_id.to_le_bytes());
+        out.extend_from_slice(&self.debt_amount.to_le_bytes());
+        out.extend_from_slice(&self.collateral_amount.to_le_bytes());
+        out.extend_from_slice(&self.slot.to_le_bytes());
+        // Denoms are validated to at most 32 bytes
+        out.push(self.denom.len() as u8);
+        out.extend_from_slice(self.denom.as_bytes());
+    }
+
+    /// Read one record from the front of `data`, advancing it; None on anything malformed
+    pub fn decode(data: &mut &[u8]) -> Option<Self> {
+        let fixed = data.get(..Self::FIXED_LEN)?;
+        if fixed[0] != LIQUIDATION_RECORD_TAG || fixed[1] != LIQUIDATION_RECORD_VERSION {
+            return None;
+        }
+        let denom_len = fixed[Self::FIXED_LEN - 1] as usize;
+        let denom = data.get(Self::FIXED_LEN..Self::FIXED_LEN + denom_len)?;
+        let record = LiquidationRecord {
+            owner: Pubkey::try_from(&fixed[2..34]).ok()?,
+            trove_id: u16::from_le_bytes(fixed[34..36].try_into().ok()?),
+            debt_amount: u64::from_le_bytes(fixed[36..44].try_into().ok()?),
+            collateral_amount: u64::from_le_bytes(fixed[44..52].try_into().ok()?),
+            slot: u64::from_le_bytes(fixed[52..60].try_into().ok()?),
+            denom: String::from_utf8(denom.to_vec()).ok()?,
+        };
+        *data = &data[Self::FIXED_LEN + denom_len..];
+        Some(record)
+    }
+}
+
+/// Log the records of one liquidation call in a single noop CPI (records are concatenated)
+pub fn log_liquidations(noop_program: &AccountInfo, records: &[LiquidationRecord]) -> Result<()> {
+    require!(
+        *noop_program.key == NOOP_PROGRAM_ID,
+        AerospacerProtocolError::InvalidAddress
+    );
+    if records.is_empty() {
+        return Ok(());
+    }
+
+    let mut data = Vec::with_capacity(records.len() * (LiquidationRecord::FIXED_LEN + 8));
+    for record in records {
+        record.encode(&mut data);
+    }
+    invoke(
+        &Instruction {
+            program_id: NOOP_PROGRAM_ID,
+            accounts: vec![],
+            data,
+        },
+        std::slice::from_ref(noop_program),
+    )?;
+
+    msg!("Logged {} liquidation record(s)", records.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_liquidation_records_round_trip_back_to_back() {
+        let records = vec![
+            LiquidationRecord {
+                owner: Pubkey::new_unique(),
+                trove_id: 0,
+                denom: "SOL".to_string(),
+                debt_amount: 1_500_000_000_000_000_000,
+                collateral_amount: 12_000_000_000,
+                slot: 42,
+            },
+            LiquidationRecord {
+                owner: Pubkey::new_unique(),
+                trove_id: 7,
+                denom: "ibc/ATOM".to_string(),
+                debt_amount: 1,
+                collateral_amount: u64::MAX,
+                slot: u64::MAX,
+            },
+        ];
+
+        let mut data = Vec::new();
+        for record in &records {
+            record.encode(&mut data);
+        }
+        assert_eq!(data.len(), 2 * LiquidationRecord::FIXED_LEN + 3 + 8);
+
+        let mut cursor = data.as_slice();
+        assert_eq!(LiquidationRecord::decode(&mut cursor), Some(records[0].clone()));
+        assert_eq!(LiquidationRecord::decode(&mut cursor), Some(records[1].clone()));
+        assert!(cursor.is_empty());
+        assert_eq!(LiquidationRecord::decode(&mut cursor), None);
+
+        // Truncated or foreign data is rejected
+        assert_eq!(LiquidationRecord::decode(&mut &data[..LiquidationRecord::FIXED_LEN]), None);
+        assert_eq!(LiquidationRecord::decode(&mut &data[1..]), None);
+    }
+}
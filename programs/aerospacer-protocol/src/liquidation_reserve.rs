@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Burn, TokenAccount, Transfer};
+use crate::error::*;
+
+/// Reject a trove whose net debt (its debt excluding the liquidation reserve) would be dust.
+/// A net debt of 0 is left to the caller: only a closed or reserve-only trove carries none.
+pub fn check_net_debt(net_debt: u64, min_net_debt: u64) -> Result<()> {
+    require!(
+        net_debt == 0 || net_debt >= min_net_debt,
+        AerospacerProtocolError::NetDebtBelowMinimum
+    );
+    Ok(())
+}
+
+/// Pay a liquidated trove's reserve from the liquidation reserve vault to its liquidator.
+/// The reserve is part of the debt the liquidation already offsets, so only the vault's tokens
+/// move here.
+pub fn pay_liquidation_reserve<'info>(
+    liquidation_reserve_vault: &AccountInfo<'info>,
+    liquidation_reserve_vault_bump: u8,
+    destination: Option<&Account<'info, TokenAccount>>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let destination = destination.ok_or(AerospacerProtocolError::MissingLiquidationReserveAccount)?;
+    let seeds: &[&[u8]] = &[b"liquidation_reserve_vault", &[liquidation_reserve_vault_bump]];
+    anchor_spl::token::transfer(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            Transfer {
+                from: liquidation_reserve_vault.clone(),
+                to: destination.to_account_info(),
+                authority: liquidation_reserve_vault.clone(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )?;
+    msg!("Liquidation reserve paid to {}: {} aUSD", destination.owner, amount);
+    Ok(())
+}
+
+/// Burn a trove's reserve from the liquidation reserve vault once its net debt is settled by
+/// its owner (close_trove) or by redeemers
+pub fn burn_liquidation_reserve<'info>(
+    liquidation_reserve_vault: &AccountInfo<'info>,
+    liquidation_reserve_vault_bump: u8,
+    stable_coin_mint: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let seeds: &[&[u8]] = &[b"liquidation_reserve_vault", &[liquidation_reserve_vault_bump]];
+    anchor_spl::token::burn(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            Burn {
+                mint: stable_coin_mint.clone(),
+                from: liquidation_reserve_vault.clone(),
+                authority: liquidation_reserve_vault.clone(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )?;
+    msg!("Liquidation reserve burned: {} aUSD", amount);
+    Ok(())
+}
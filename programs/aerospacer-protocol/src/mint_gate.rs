@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, MintTo};
+use crate::state::MintAuthority;
+use crate::error::*;
+
+/// What a mint is for; each purpose is held to its own cap
+#[derive(Clone, Copy)]
+pub enum MintPurpose {
+    /// Trove loans and their liquidation reserve; `total_debt` is the protocol's debt after the mint
+    TroveDebt { total_debt: u64 },
+    /// Repaid by repay_flash_mint later in the same transaction, so it leaves no debt behind
+    FlashMint,
+    /// PSM swap-in backed by the reserve; `total_minted` includes this mint
+    Psm { total_minted: u64, debt_ceiling: u64 },
+}
+
+impl MintPurpose {
+    pub fn check_cap(&self, mint_authority: &MintAuthority) -> Result<()> {
+        match *self {
+            MintPurpose::TroveDebt { total_debt } => mint_authority.check_trove_debt(total_debt),
+            MintPurpose::FlashMint => Ok(()),
+            MintPurpose::Psm { total_minted, debt_ceiling } => {
+                require!(
+                    total_minted <= debt_ceiling,
+                    AerospacerProtocolError::PsmDebtCeilingExceeded
+                );
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The only way the protocol mints aUSD. Checks the purpose's cap, then mints signed by the
+/// dedicated mint authority PDA, which must still hold the mint's authority (see
+/// assign_mint_authority and execute_mint_authority_migration).
+pub fn mint_stablecoin<'info>(
+    stable_coin_mint: &Account<'info, Mint>,
+    mint_authority: &Account<'info, MintAuthority>,
+    mint_authority_bump: u8,
+    to: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+    purpose: MintPurpose,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    require!(
+        stable_coin_mint.mint_authority == Some(mint_authority.key()).into(),
+        AerospacerProtocolError::MintAuthorityNotAssigned
+    );
+    purpose.check_cap(mint_authority)?;
+
+    let seeds: &[&[u8]] = &[b"mint_authority", &[mint_authority_bump]];
+    anchor_spl::token::mint_to(
+        CpiContext::new_with_signer(
+            token_program,
+            MintTo {
+                mint: stable_coin_mint.to_account_info(),
+                to,
+                authority: mint_authority.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::state::TroveStatus;
 
 // Exact replication of INJECTIVE msg.rs
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -94,10 +95,31 @@ pub struct CollateralAmountResponse {
     pub amount: u64, // Equivalent to Uint256
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct TcrResponse {
+    pub tcr: u64,                    // Micro-percent at the current total debt (u64::MAX without debt)
+    pub cached_tcr: u64,             // StateAccount.tcr as of the last refresh_tcr
+    pub total_collateral_value: u64, // Micro-USD, as of the last refresh_tcr of each denom
+    pub total_debt_amount: u64,
+    pub valued_denoms: u64,
+    pub last_update_slot: u64,
+    pub active_troves: u64,          // StateAccount.active_troves
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PendingRewardsResponse {
+    pub pending_debt: u64,           // Redistributed debt not yet applied to the trove
+    pub pending_collateral: u64,     // Redistributed collateral not yet applied to the trove
+    pub debt_amount: u64,            // Debt once the pending rewards are applied
+    pub collateral_amount: u64,      // Collateral of the denom once the pending rewards are applied
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct TroveResponse {
     pub collateral_amounts: Vec<CollateralAmountResponse>,
     pub debt_amount: u64, // Equivalent to Uint256
+    pub status: TroveStatus,
+    pub trove_id: u16,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
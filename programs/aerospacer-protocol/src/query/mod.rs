@@ -12,9 +12,14 @@ pub fn query_total_collateral_amounts<'a>(
 ) -> Result<Vec<CollateralAmountResponse>> {
     let mut res = Vec::new();
     for account_info in total_collateral_amount_accounts {
-        let total_collateral: Account<TotalCollateralAmount> = Account::try_from(account_info)?;
+        require!(
+            account_info.owner == &crate::ID,
+            AerospacerProtocolError::Unauthorized
+        );
+        let data = account_info.try_borrow_data()?;
+        let total_collateral = TotalCollateralAmount::from_account_data(&data)?;
         res.push(CollateralAmountResponse {
-            denom: total_collateral.denom.clone(),
+            denom: total_collateral.denom().to_string(),
             amount: total_collateral.amount,
         });
     }
@@ -33,7 +38,7 @@ pub fn query_trove<'a>(
     let mut collateral_amounts = Vec::new();
     for account_info in user_collateral_amount_accounts {
         let user_collateral: Account<UserCollateralAmount> = Account::try_from(account_info)?;
-        if user_collateral.owner == user_addr {
+        if user_collateral.owner == user_addr && user_collateral.trove_id == user_debt_amount_account.trove_id {
             collateral_amounts.push(CollateralAmountResponse {
                 denom: user_collateral.denom.clone(),
                 amount: user_collateral.amount,
@@ -44,6 +49,8 @@ pub fn query_trove<'a>(
     let response = TroveResponse {
         collateral_amounts,
         debt_amount: user_debt_amount_account.amount,
+        status: user_debt_amount_account.status,
+        trove_id: user_debt_amount_account.trove_id,
     };
     Ok(response)
 }
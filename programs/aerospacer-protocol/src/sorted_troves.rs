@@ -67,7 +67,7 @@ pub fn validate_icr_ordering(
 /// 3. All accounts are real PDAs owned by the program (security)
 /// 
 /// # Arguments
-/// * `liquidation_threshold` - ICR threshold below which troves are liquidatable (typically 110)
+/// * `liquidation_threshold` - ICR threshold below which troves are liquidatable, in micro-percent (typically 110_000_000)
 /// * `remaining_accounts` - Pre-sorted trove accounts [UserDebtAmount, UserCollateralAmount, LiquidityThreshold] triplets
 /// * `program_id` - Program ID for PDA verification
 /// 
@@ -144,16 +144,16 @@ pub fn get_liquidatable_troves(
         let threshold = LiquidityThreshold::try_deserialize(&mut &lt_data[..])?;
         let current_icr = threshold.ratio;
         
-        // Verify LiquidityThreshold matches the debt account owner
+        // Verify LiquidityThreshold matches the debt account's trove
         require!(
-            threshold.owner == owner,
+            threshold.owner == owner && threshold.trove_id == debt.trove_id,
             AerospacerProtocolError::InvalidList
         );
         drop(lt_data);
         
         // SECURITY: Verify LiquidityThreshold is a real PDA, not a fake account
         // This prevents attackers from injecting fabricated accounts with arbitrary ICRs
-        verify_liquidity_threshold_pda(lt_account, owner, program_id)?;
+        verify_liquidity_threshold_pda(lt_account, owner, debt.trove_id, program_id)?;
         
         msg!("Trove {}: owner={}, ICR={}", i, owner, current_icr);
         
@@ -206,6 +206,7 @@ pub fn get_icr_from_account(account: &AccountInfo, expected_owner: Pubkey) -> Re
 /// # Arguments
 /// * `account` - The account to verify
 /// * `owner` - The expected owner (from deserializing the account)
+/// * `trove_id` - The owner's trove index (from deserializing the account)
 /// * `program_id` - The program ID for PDA derivation
 /// 
 /// # Returns
@@ -213,10 +214,12 @@ pub fn get_icr_from_account(account: &AccountInfo, expected_owner: Pubkey) -> Re
 pub fn verify_liquidity_threshold_pda(
     account: &AccountInfo,
     owner: Pubkey,
+    trove_id: u16,
     program_id: &Pubkey,
 ) -> Result<()> {
+    let trove_seed = trove_id_seed(trove_id);
     let (expected_pda, _bump) = Pubkey::find_program_address(
-        &[b"liquidity_threshold", owner.as_ref()],
+        &LiquidityThreshold::seeds(&owner, &trove_seed),
         program_id,
     );
     
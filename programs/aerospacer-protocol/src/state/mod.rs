@@ -1,28 +1,93 @@
 use anchor_lang::prelude::*;
+use crate::oracle::{PriceCalculator, PriceData};
 
 // Exact replication of INJECTIVE state.rs
 // Main state account (equivalent to INJECTIVE's ADMIN, ORACLE_HELPER_ADDR, FEE_DISTRIBUTOR_ADDR, MINIMUM_COLLATERAL_RATIO, PROTOCOL_FEE, STABLE_COIN_ADDR, TOTAL_DEBT_AMOUNT, TOTAL_STAKE_AMOUNT)
-#[account]
+// Zero-copy: handlers access fields in place via AccountLoader instead of (de)serializing the whole account.
+// Layout is #[repr(C)] and must stay free of implicit padding (bytemuck::Pod) - add new fields next to
+// fields of the same alignment (or before `_padding`) and resize `_padding` accordingly.
+#[account(zero_copy)]
 pub struct StateAccount {
     pub admin: Pubkey,
     pub oracle_helper_addr: Pubkey,          // Oracle program ID
     pub oracle_state_addr: Pubkey,           // Oracle state account address  
     pub fee_distributor_addr: Pubkey,        // aerospacer-fees program ID
     pub fee_state_addr: Pubkey,              // aerospacer-fees state account address
-    pub minimum_collateral_ratio: u64,
-    pub protocol_fee: u8,
     pub stable_coin_addr: Pubkey,
+    pub treasury_addr: Pubkey,               // Backstop/treasury allowed to use the priority redemption lane
+    pub minimum_collateral_ratio: u64,
     pub stable_coin_code_id: u64,
     pub total_debt_amount: u64, // Equivalent to Uint256
     pub total_stake_amount: u64, // Equivalent to Uint256
     
     // Stability Pool Snapshot Variables (Liquity Product-Sum Algorithm)
     pub p_factor: u128,  // Product/depletion factor - tracks cumulative pool depletion from debt burns (starts at SCALE_FACTOR)
+    pub g_factor: u128,  // Cumulative aUSD fee yield per unit staked in the current epoch/scale (weighted by P)
+    pub g_factor_prev: u128, // Final G of the previous epoch/scale segment, claimable by depositors snapshotted in it
+    pub reward_g_factor: u128,      // Same accumulator as g_factor for reward token emissions
+    pub reward_g_factor_prev: u128, // Final reward G of the previous epoch/scale segment
     pub epoch: u64,      // Current epoch - increments when pool is completely depleted to 0
+    pub flash_mint_outstanding: u64, // aUSD flash minted in the current transaction and not yet repaid
+    pub min_redemption_amount: u64,  // Smallest accepted redemption (0 = only MINIMUM_LOAN_AMOUNT applies)
+    
+    // Redemption throttle: at most redemption_cap_per_window aUSD per window of slots
+    pub redemption_cap_per_window: u64,    // 0 = unthrottled
+    pub redemption_window_slots: u64,      // Window length in slots
+    pub redemption_window_start_slot: u64, // First slot of the current window
+    pub redemption_window_volume: u64,     // aUSD redeemed in the current window
+    pub liquidation_reserve: u64,          // aUSD minted to the reserve vault per opened trove, paid to its liquidator (0 = off)
+    pub min_net_debt: u64,                 // Smallest net debt (excluding the reserve) a trove may carry (0 = only MINIMUM_LOAN_AMOUNT applies)
+    
+    // Total collateral ratio, cached by refresh_tcr from the per-denom CollateralValuations
+    pub total_collateral_value: u64,       // Sum of every CollateralValuation.value, micro-USD
+    pub valued_denoms: u64,                // Denoms with a CollateralValuation (summed into total_collateral_value)
+    pub tcr: u64,                          // Micro-percent, like ICRs (u64::MAX without debt)
+    pub tcr_slot: u64,                     // Slot of the last refresh_tcr
+    
+    // Bootstrap period: redemptions are blocked for bootstrap_period_secs after launch
+    pub launch_timestamp: i64,             // Set at initialize (0 for states created before, whose bootstrap is over)
+    pub bootstrap_period_secs: u64,
+    
+    // Trove counters, maintained on open/close/liquidation/full redemption (see record_trove_opened)
+    pub active_troves: u64,                // Troves currently Active (troves opened before the counter existed are not included)
+    pub troves_opened: u64,                // Troves ever opened since the counter existed
+    
+    // On-chain trove index (see TroveIndexPage)
+    pub trove_index_len: u64,              // Positions ever appended; the next trove goes to this position
+    pub trove_index_tombstones: u64,       // Positions tombstoned since
+    
+    pub protocol_fee: u8,
+    pub enforce_trove_tags: u8,  // 1 = open_trove tags must be registered in the TagRegistry
+    
+    // Oracle deadman switch
+    pub fallback_haircut_bps_per_hour: u16, // Haircut applied to the cached price per hour since it was published
+    pub max_price_age: u32,                 // Seconds before an oracle price is considered stale (0 = no staleness check)
+    pub max_fallback_haircut_bps: u16,      // Cap on the fallback haircut
+    pub oracle_fallback_enabled: u8,        // 1 = de-risking operations may use the cached price while the feed is stale
+    
+    pub current_scale: u8,   // P factor scale - increments each time P is multiplied by P_SCALE_FACTOR, resets with the epoch
+    pub g_prev_scale: u8,    // Scale of the segment g_factor_prev belongs to
+    pub emergency_redemption_mode: u8, // 1 = de-peg emergency flagged, treasury redemptions are fee-free
+    pub reward_emissions_enabled: u8,  // 1 = a RewardEmission schedule exists and stake/unstake must issue it
+    pub max_redemption_troves: u8,     // Max troves a single redemption may touch (0 = unlimited)
+    
+    // Per-operation-class oracle read windows (see oracle::PriceReadClass), 0 = use max_price_age
+    pub critical_max_price_age: u32,   // Liquidations, redemptions and the buckets that order them
+    pub derisking_max_price_age: u32,  // Repay and add collateral
+    
+    pub public_goods_bps: u16,         // Share of liquidation penalties booked for the public-goods address (0 = off)
+    pub keeper_bounty_bps: u16,        // Share of liquidation penalties paid to a registered keeper (0 = off)
+    pub price_cache_max_age: u16,      // Seconds a PriceCache may stand in for the oracle in trove instructions (0 = never)
+    pub permissioned_liquidation: u8,  // 1 = only liquidators in the LiquidatorWhitelist may liquidate
+    pub redemption_stored_icr_fallback: u8, // 1 = redemptions may order troves by stored ICRs when no fresh price is supplied
+    pub require_hints: u8,             // 1 = trove instructions reject calls without neighbor hints
+    pub bootstrap_max_liquidation_batch: u8, // Max troves per liquidate_troves during the bootstrap period (0 = unlimited)
+    pub gaining_denoms: u8,            // Denoms whose stability pool S factor is non-zero (counted since the field exists)
+    pub _padding: [u8; 5],
 }
 
 impl StateAccount {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 32 + 8 + 1 + 32 + 8 + 8 + 8 + 16 + 8; // Added oracle_state_addr + fee_state_addr + stable_coin_code_id, minimum_collateral_ratio now u64
+    pub const LEN: usize = std::mem::size_of::<StateAccount>(); // Fixed zero-copy layout (excludes the 8-byte discriminator)
     
     // Scale factor for precision in P/S calculations (10^18, same as Liquity)
     pub const SCALE_FACTOR: u128 = 1_000_000_000_000_000_000;
@@ -30,6 +95,162 @@ impl StateAccount {
     pub fn seeds() -> [&'static [u8]; 1] {
         [b"state"]
     }
+    
+    /// Count `amount` against the redemption throttle, starting a new window when the current
+    /// one has elapsed. Fails without recording anything once the window's cap would be exceeded.
+    pub fn record_redemption_volume(&mut self, amount: u64, slot: u64) -> Result<()> {
+        if self.redemption_cap_per_window == 0 {
+            return Ok(());
+        }
+        
+        if slot >= self.redemption_window_start_slot.saturating_add(self.redemption_window_slots) {
+            self.redemption_window_start_slot = slot;
+            self.redemption_window_volume = 0;
+        }
+        
+        let window_volume = self.redemption_window_volume
+            .checked_add(amount)
+            .ok_or(crate::error::AerospacerProtocolError::OverflowError)?;
+        require!(
+            window_volume <= self.redemption_cap_per_window,
+            crate::error::AerospacerProtocolError::RedemptionCapExceeded
+        );
+        self.redemption_window_volume = window_volume;
+        
+        msg!("Redemption window volume: {} / {} (window started at slot {})",
+             window_volume, self.redemption_cap_per_window, self.redemption_window_start_slot);
+        Ok(())
+    }
+    
+    /// Give back `amount` of volume recorded in the window starting at `window_start_slot`, for
+    /// escrowed aUSD that was never redeemed. An elapsed window's volume no longer counts anyway.
+    pub fn release_redemption_volume(&mut self, amount: u64, window_start_slot: u64) {
+        if window_start_slot != self.redemption_window_start_slot {
+            return;
+        }
+        self.redemption_window_volume = self.redemption_window_volume.saturating_sub(amount);
+    }
+    
+    /// Whether the bootstrap period after launch is still running at `now`
+    pub fn in_bootstrap_period(&self, now: i64) -> bool {
+        let period = i64::try_from(self.bootstrap_period_secs).unwrap_or(i64::MAX);
+        now < self.launch_timestamp.saturating_add(period)
+    }
+    
+    /// Seed of the index page the next trove is appended to
+    pub fn trove_index_tail_seed(&self) -> [u8; 4] {
+        TroveIndexPage::page_seed(self.trove_index_len)
+    }
+    
+    /// Count a newly opened trove
+    pub fn record_trove_opened(&mut self) {
+        self.active_troves = self.active_troves.saturating_add(1);
+        self.troves_opened = self.troves_opened.saturating_add(1);
+    }
+    
+    /// Count a trove leaving the Active status (closed, liquidated or fully redeemed). Saturates at
+    /// zero so troves opened before the counter existed cannot underflow it.
+    pub fn record_trove_ended(&mut self) {
+        self.active_troves = self.active_troves.saturating_sub(1);
+    }
+    
+    /// Swap a denom's previous valuation for `new_value` in the collateral total and recompute
+    /// the cached TCR against the current total debt
+    pub fn record_collateral_valuation(&mut self, old_value: u64, new_value: u64, slot: u64) -> Result<()> {
+        self.total_collateral_value = self.total_collateral_value
+            .saturating_sub(old_value)
+            .checked_add(new_value)
+            .ok_or(crate::error::AerospacerProtocolError::OverflowError)?;
+        self.tcr = PriceCalculator::calculate_collateral_ratio(self.total_collateral_value, self.total_debt_amount)?;
+        self.tcr_slot = slot;
+        Ok(())
+    }
+}
+
+/// Borsh layout StateAccount had before it became zero-copy, read by migrate_zero_copy_accounts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyStateAccount {
+    pub admin: Pubkey,
+    pub oracle_helper_addr: Pubkey,
+    pub oracle_state_addr: Pubkey,
+    pub fee_distributor_addr: Pubkey,
+    pub fee_state_addr: Pubkey,
+    pub minimum_collateral_ratio: u64,
+    pub protocol_fee: u8,
+    pub stable_coin_addr: Pubkey,
+    pub stable_coin_code_id: u64,
+    pub total_debt_amount: u64,
+    pub total_stake_amount: u64,
+    pub p_factor: u128,
+    pub epoch: u64,
+}
+
+impl LegacyStateAccount {
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 32 + 8 + 1 + 32 + 8 + 8 + 8 + 16 + 8;
+    
+    /// Zero-copy state carrying the legacy values. Every field added since starts at 0, its
+    /// documented off / unlimited / not-yet-counted value.
+    pub fn migrate(&self) -> StateAccount {
+        let mut state: StateAccount = bytemuck::Zeroable::zeroed();
+        state.admin = self.admin;
+        state.oracle_helper_addr = self.oracle_helper_addr;
+        state.oracle_state_addr = self.oracle_state_addr;
+        state.fee_distributor_addr = self.fee_distributor_addr;
+        state.fee_state_addr = self.fee_state_addr;
+        state.stable_coin_addr = self.stable_coin_addr;
+        state.minimum_collateral_ratio = self.minimum_collateral_ratio;
+        state.protocol_fee = self.protocol_fee;
+        state.stable_coin_code_id = self.stable_coin_code_id;
+        state.total_debt_amount = self.total_debt_amount;
+        state.total_stake_amount = self.total_stake_amount;
+        state.p_factor = self.p_factor;
+        state.epoch = self.epoch;
+        state
+    }
+}
+
+// Trove lifecycle. A zero debt amount alone is ambiguous (never opened, fully repaid,
+// liquidated or redeemed down to zero), so every instruction that changes a trove's
+// existence transitions this status and validates the move.
+//
+//   NonExistent | Closed | Liquidated --open_trove--> Active
+//   Active --close_trove--> Closed
+//   Active --liquidate_trove(s)--> Liquidated
+//   Active --liquidate_trove(s) (collateral above the penalty left)--> LiquidatedWithSurplus
+//   Active --redeem (debt reaches zero)--> RedeemedClosed
+//   RedeemedClosed | LiquidatedWithSurplus --close_trove (returns surplus collateral)--> Closed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TroveStatus {
+    NonExistent,    // Freshly initialized record, trove never opened
+    Active,
+    Liquidated,
+    RedeemedClosed, // Debt fully redeemed; surplus collateral awaits close_trove
+    Closed,
+    LiquidatedWithSurplus, // Liquidated under a denom penalty; unseized collateral awaits close_trove
+}
+
+impl TroveStatus {
+    pub fn can_transition_to(self, next: TroveStatus) -> bool {
+        matches!(
+            (self, next),
+            (TroveStatus::NonExistent, TroveStatus::Active)
+                | (TroveStatus::Closed, TroveStatus::Active)
+                | (TroveStatus::Liquidated, TroveStatus::Active)
+                | (TroveStatus::Active, TroveStatus::Closed)
+                | (TroveStatus::Active, TroveStatus::Liquidated)
+                | (TroveStatus::Active, TroveStatus::RedeemedClosed)
+                | (TroveStatus::RedeemedClosed, TroveStatus::Closed)
+                | (TroveStatus::Active, TroveStatus::LiquidatedWithSurplus)
+                | (TroveStatus::LiquidatedWithSurplus, TroveStatus::Closed)
+        )
+    }
+}
+
+/// PDA seed for a trove index in the UserDebtAmount, UserCollateralAmount and LiquidityThreshold
+/// seeds. Always 2 bytes (u16 LE) and placed before the denom, so a trove id can never run into
+/// the denom bytes ("USDC" + id vs "USDC.e").
+pub fn trove_id_seed(trove_id: u16) -> [u8; 2] {
+    trove_id.to_le_bytes()
 }
 
 // User debt amount (equivalent to INJECTIVE's USER_DEBT_AMOUNT: Map<Addr, Uint256>)
@@ -37,13 +258,71 @@ impl StateAccount {
 pub struct UserDebtAmount {
     pub owner: Pubkey,
     pub amount: u64,
-    pub l_debt_snapshot: u128,
+    pub l_debt_snapshot: u128,          // Legacy shared debt L snapshot, read only by legs without their own (see UserCollateralAmount)
+    pub tag: [u8; 32],                  // Frontend/bot attribution tag set at open_trove (all zeros = untagged)
+    pub status: TroveStatus,            // Lifecycle state of the trove, see TroveStatus
+    pub trove_id: u16,                  // Index of the trove among the owner's troves (see trove_id_seed)
+    pub locked_slot: u64,               // Slot of the last value-extracting operation (see lock)
+    pub liquidation_reserve: u64,       // Part of `amount` held in the liquidation reserve vault (0 for troves opened without one)
+    pub index_position: u64,            // Position + 1 of the trove's entry in the trove index (0 = not indexed)
+    pub collateral_legs: u8,            // Number of UserCollateralAmount legs (one per denom) the trove holds
 }
 
 impl UserDebtAmount {
-    pub const LEN: usize = 8 + 32 + 8 + 16;
-    pub fn seeds(owner: &Pubkey) -> [&[u8]; 2] {
-        [b"user_debt_amount", owner.as_ref()]
+    pub const LEN: usize = 8 + 32 + 8 + 16 + 32 + 1 + 2 + 8 + 8 + 8 + 1; // 8 + owner(32) + amount(8) + l_debt_snapshot(16) + tag(32) + status(1) + trove_id(2) + locked_slot(8) + liquidation_reserve(8) + index_position(8) + collateral_legs(1)
+    pub fn seeds<'a>(owner: &'a Pubkey, trove_seed: &'a [u8]) -> [&'a [u8]; 3] {
+        [b"user_debt_amount", owner.as_ref(), trove_seed]
+    }
+    
+    pub fn is_active(&self) -> bool {
+        self.status == TroveStatus::Active
+    }
+    
+    /// Debt the owner borrowed, excluding the liquidation reserve minted to the reserve vault
+    pub fn net_debt(&self) -> u64 {
+        self.amount.saturating_sub(self.liquidation_reserve)
+    }
+    
+    /// Seed of the index page holding the trove's entry (page 0 for unindexed troves)
+    pub fn index_page_seed(&self) -> [u8; 4] {
+        TroveIndexPage::page_seed(self.index_position.saturating_sub(1))
+    }
+    
+    /// Stamp the trove as modified by a value-extracting operation (borrow, collateral withdrawal)
+    /// in `slot`
+    pub fn lock(&mut self, slot: u64) {
+        self.locked_slot = slot;
+    }
+    
+    /// Reject liquidations and redemptions of a trove already modified earlier in the same slot,
+    /// whose reads could interleave with that change
+    pub fn require_unlocked(&self, slot: u64) -> Result<()> {
+        require!(
+            self.locked_slot != slot,
+            crate::error::AerospacerProtocolError::TroveLocked
+        );
+        Ok(())
+    }
+    
+    /// Reject leg sets that leave out one of the trove's collateral legs. `legs` counts the
+    /// distinct, verified legs passed, the primary one included.
+    pub fn require_all_legs(&self, legs: usize) -> Result<()> {
+        require!(
+            legs == self.collateral_legs as usize,
+            crate::error::AerospacerProtocolError::IncompleteCollateralLegs
+        );
+        Ok(())
+    }
+    
+    /// Move the trove to `next`, rejecting transitions the lifecycle does not allow
+    pub fn transition(&mut self, next: TroveStatus) -> Result<()> {
+        require!(
+            self.status.can_transition_to(next),
+            crate::error::AerospacerProtocolError::InvalidTroveStatusTransition
+        );
+        msg!("Trove {} status: {:?} -> {:?}", self.owner, self.status, next);
+        self.status = next;
+        Ok(())
     }
 }
 
@@ -54,12 +333,15 @@ pub struct UserCollateralAmount {
     pub denom: String,
     pub amount: u64,
     pub l_collateral_snapshot: u128,
+    pub l_scale_snapshot: u8,           // L renormalization scale the L snapshots were taken at
+    pub trove_id: u16,                  // Trove this collateral belongs to
+    pub l_debt_snapshot: Option<u128>,  // Debt L of this denom at the last sync; None on legs from before per-leg snapshots
 }
 
 impl UserCollateralAmount {
-    pub const LEN: usize = 8 + 32 + 32 + 8 + 16;
-    pub fn seeds<'a>(owner: &'a Pubkey, denom: &'a str) -> [&'a [u8]; 3] {
-        [b"user_collateral_amount", owner.as_ref(), denom.as_bytes()]
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 16 + 1 + 2 + 17;
+    pub fn seeds<'a>(owner: &'a Pubkey, denom: &'a str, trove_seed: &'a [u8]) -> [&'a [u8]; 4] {
+        [b"user_collateral_amount", owner.as_ref(), trove_seed, denom.as_bytes()]
     }
 }
 
@@ -71,10 +353,18 @@ pub struct UserStakeAmount {
     pub p_snapshot: u128,               // User's P factor snapshot at last deposit (for compounded stake calculation)
     pub epoch_snapshot: u64,            // Epoch when user last deposited (for epoch transition tracking)
     pub last_update_block: u64,         // Last block when stake was updated
+    pub scale_snapshot: u8,             // P factor scale when user last deposited
+    pub g_snapshot: u128,               // G factor snapshot at last deposit (for aUSD fee yield)
+    pub pending_fee_gain: u64,          // aUSD fee yield settled on stake/unstake but not yet claimed
+    pub reward_g_snapshot: u128,        // Reward G factor snapshot at last deposit (for reward token emissions)
+    pub pending_reward_gain: u64,       // Reward tokens settled but not yet claimed
+    pub frontend: Pubkey,               // Frontend operator that onboarded the deposit (default = none)
 }
 
 impl UserStakeAmount {
-    pub const LEN: usize = 8 + 32 + 8 + 16 + 8 + 8; // Added p_snapshot(16) + epoch_snapshot(8) + last_update_block(8)
+    pub const LEN: usize = 8 + 32 + 8 + 16 + 8 + 8 + 1 + 16 + 8 + 16 + 8 + 32; // Added p_snapshot(16) + epoch_snapshot(8) + last_update_block(8) + scale_snapshot(1) + g_snapshot(16) + pending_fee_gain(8) + reward_g_snapshot(16) + pending_reward_gain(8) + frontend(32)
+    /// Fields of the first deposits (owner through last_update_block), for migrate_user_stake
+    pub const LEGACY_LEN: usize = 32 + 8 + 16 + 8 + 8;
     pub fn seeds(owner: &Pubkey) -> [&[u8]; 2] {
         [b"user_stake_amount", owner.as_ref()]
     }
@@ -85,29 +375,132 @@ impl UserStakeAmount {
 pub struct LiquidityThreshold {
     pub owner: Pubkey,
     pub ratio: u64, // Equivalent to Decimal256
+    pub trove_id: u16,
 }
 
 impl LiquidityThreshold {
-    pub const LEN: usize = 8 + 32 + 8;
-    pub fn seeds(owner: &Pubkey) -> [&[u8]; 2] {
-        [b"liquidity_threshold", owner.as_ref()]
+    pub const LEN: usize = 8 + 32 + 8 + 2;
+    pub fn seeds<'a>(owner: &'a Pubkey, trove_seed: &'a [u8]) -> [&'a [u8]; 3] {
+        [b"liquidity_threshold", owner.as_ref(), trove_seed]
     }
 }
 
 // Total collateral amount (equivalent to INJECTIVE's TOTAL_COLLATERAL_AMOUNT: Map<String, Uint256>)
-#[account]
+// Zero-copy: read and written on every trove operation, redemption and liquidation.
+// The denom is stored as fixed, zero-padded bytes (PDA seeds already cap it at 32 bytes).
+#[account(zero_copy)]
 pub struct TotalCollateralAmount {
-    pub denom: String,
-    pub amount: u64,
     pub l_collateral: u128,
     pub l_debt: u128,
+    pub amount: u64,
+    pub denom: [u8; 32],
+    pub l_scale: u8,                    // Number of renormalizations applied to l_collateral/l_debt
+    pub deactivated: u8,                // 1 = sunset: no new troves or borrow increases (see deactivate_collateral)
+    pub _padding: [u8; 2],
+    pub active_troves: u32,             // Active troves of the denom (troves opened before the counter existed are not included)
 }
 
 impl TotalCollateralAmount {
-    pub const LEN: usize = 8 + 32 + 8 + 16 + 16;
+    pub const LEN: usize = std::mem::size_of::<TotalCollateralAmount>(); // Fixed zero-copy layout (excludes the 8-byte discriminator)
+    
     pub fn seeds(denom: &str) -> [&[u8]; 2] {
         [b"total_collateral_amount", denom.as_bytes()]
     }
+    
+    /// Denom as a string slice (trailing zero bytes stripped)
+    pub fn denom(&self) -> &str {
+        let len = self.denom.iter().position(|b| *b == 0).unwrap_or(self.denom.len());
+        std::str::from_utf8(&self.denom[..len]).unwrap_or("")
+    }
+    
+    /// Whether the account has been initialized with a denom yet
+    pub fn is_uninitialized(&self) -> bool {
+        self.denom[0] == 0
+    }
+    
+    /// Count a newly opened trove of the denom
+    pub fn record_trove_opened(&mut self) {
+        self.active_troves = self.active_troves.saturating_add(1);
+    }
+    
+    /// Count a trove of the denom leaving the Active status, saturating at zero
+    pub fn record_trove_ended(&mut self) {
+        self.active_troves = self.active_troves.saturating_sub(1);
+    }
+    
+    /// Whether the denom still accepts new troves and additional borrowing
+    pub fn is_active(&self) -> bool {
+        self.deactivated == 0
+    }
+    
+    pub fn set_denom(&mut self, denom: &str) -> Result<()> {
+        require!(
+            !denom.is_empty() && denom.len() <= self.denom.len(),
+            crate::error::AerospacerProtocolError::InvalidAmount
+        );
+        self.denom = [0u8; 32];
+        self.denom[..denom.len()].copy_from_slice(denom.as_bytes());
+        Ok(())
+    }
+    
+    /// Zero-copy view over raw account data, for call sites that only hold an AccountInfo
+    pub fn from_account_data(data: &[u8]) -> Result<&Self> {
+        require!(
+            data.len() >= 8 + Self::LEN && data[..8] == *Self::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Ok(bytemuck::from_bytes(&data[8..8 + Self::LEN]))
+    }
+    
+    /// Mutable zero-copy view over raw account data (writes land directly in the account)
+    pub fn from_account_data_mut(data: &mut [u8]) -> Result<&mut Self> {
+        require!(
+            data.len() >= 8 + Self::LEN && data[..8] == *Self::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Ok(bytemuck::from_bytes_mut(&mut data[8..8 + Self::LEN]))
+    }
+}
+
+/// Borsh layout TotalCollateralAmount had before it became zero-copy, read by
+/// migrate_zero_copy_accounts. Both layouts fill the same 88-byte account, so a legacy one is
+/// told apart by its leading denom string, which must give back the account's own PDA.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyTotalCollateralAmount {
+    pub denom: String,
+    pub amount: u64,
+    pub l_collateral: u128,
+    pub l_debt: u128,
+}
+
+impl LegacyTotalCollateralAmount {
+    /// Legacy record in `data` (discriminator included) if `data` still has the legacy layout of
+    /// the PDA `key`
+    pub fn from_account_data(data: &[u8], key: &Pubkey) -> Option<Self> {
+        if data.len() < 8 || data[..8] != *TotalCollateralAmount::DISCRIMINATOR {
+            return None;
+        }
+        let legacy = Self::deserialize(&mut &data[8..]).ok()?;
+        if legacy.denom.is_empty() || legacy.denom.len() > 32 {
+            return None;
+        }
+        let (expected_pda, _bump) = Pubkey::find_program_address(
+            &TotalCollateralAmount::seeds(&legacy.denom),
+            &crate::ID,
+        );
+        (expected_pda == *key).then_some(legacy)
+    }
+    
+    /// Zero-copy record carrying the legacy values; no renormalization or sunset had happened and
+    /// troves opened before the counter are not counted
+    pub fn migrate(&self) -> Result<TotalCollateralAmount> {
+        let mut total: TotalCollateralAmount = bytemuck::Zeroable::zeroed();
+        total.set_denom(&self.denom)?;
+        total.amount = self.amount;
+        total.l_collateral = self.l_collateral;
+        total.l_debt = self.l_debt;
+        Ok(total)
+    }
 }
 
 // User liquidation collateral gain (equivalent to INJECTIVE's USER_LIQUIDATION_COLLATERAL_GAIN: Map<(Addr, u64), bool>)
@@ -175,21 +568,1053 @@ pub struct UserCollateralSnapshot {
     pub denom: String,
     pub s_snapshot: u128,               // User's S factor snapshot at last deposit
     pub pending_collateral_gain: u64,  // Unclaimed gains from previous epochs
+    pub epoch_snapshot: u64,            // Epoch in which s_snapshot was taken
 }
 
 impl UserCollateralSnapshot {
-    pub const LEN: usize = 8 + 32 + 32 + 16 + 8; // owner(32) + denom(32) + s_snapshot(16) + pending(8)
+    pub const LEN: usize = 8 + 32 + 32 + 16 + 8 + 8; // owner(32) + denom(32) + s_snapshot(16) + pending(8) + epoch_snapshot(8)
     
     pub fn seeds<'a>(owner: &'a Pubkey, denom: &'a str) -> [&'a [u8]; 3] {
         [b"user_collateral_snapshot", owner.as_ref(), denom.as_bytes()]
     }
+    
+    /// A new snapshot for the pool snapshot's denom, taken at its current S so it has no gain yet
+    pub fn starting_at(owner: Pubkey, pool_snapshot: &StabilityPoolSnapshot, current_epoch: u64) -> Self {
+        Self {
+            owner,
+            denom: pool_snapshot.denom.clone(),
+            s_snapshot: pool_snapshot.s_factor,
+            pending_collateral_gain: 0,
+            epoch_snapshot: current_epoch,
+        }
+    }
+}
+
+// Price cache - last oracle price observed by the protocol for a collateral denom
+// Written by keeper cranks (refresh_price, refresh_denom_bucket) so that stored ICRs can be traced
+// back to the price they were computed with. While fresh, trove instructions read it instead of
+// the oracle (see StateAccount.price_cache_max_age).
+#[account]
+pub struct PriceCache {
+    pub denom: String,
+    pub price: i64,                     // Oracle price, micro-USD per whole token
+    pub decimal: u8,                    // Token decimals returned by the oracle
+    pub confidence: u64,
+    pub publish_time: i64,              // Oracle publish timestamp
+    pub last_update_slot: u64,          // Slot in which the cache was last written
+}
+
+impl PriceCache {
+    pub const LEN: usize = 8 + 32 + 8 + 1 + 8 + 8 + 8; // denom(32) + price(8) + decimal(1) + confidence(8) + publish_time(8) + slot(8)
+
+    pub fn seeds(denom: &str) -> [&[u8]; 2] {
+        [b"price_cache", denom.as_bytes()]
+    }
+
+    /// Overwrite the cache with a live oracle price read in `slot`
+    pub fn record(&mut self, price_data: &PriceData, slot: u64) {
+        self.denom = price_data.denom.clone();
+        self.price = price_data.price;
+        self.decimal = price_data.decimal;
+        self.confidence = price_data.confidence;
+        self.publish_time = price_data.timestamp;
+        self.last_update_slot = slot;
+    }
 }
 
+// Micro-USD value of a denom's collateral total at its last valuation. refresh_tcr swaps it in
+// and out of StateAccount.total_collateral_value, so the TCR needs no list of listed denoms.
+#[account]
+pub struct CollateralValuation {
+    pub denom: String,
+    pub amount: u64,                    // TotalCollateralAmount.amount when valued
+    pub price: i64,                     // Oracle price, micro-USD per whole token
+    pub decimal: u8,                    // Token decimals returned by the oracle
+    pub value: u64,                     // Micro-USD
+    pub last_update_slot: u64,
+}
+
+impl CollateralValuation {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1 + 8 + 8; // denom(32) + amount(8) + price(8) + decimal(1) + value(8) + slot(8)
+
+    pub fn seeds(denom: &str) -> [&[u8]; 2] {
+        [b"collateral_valuation", denom.as_bytes()]
+    }
+}
+
+// Per-user position summary so wallets can render a user's positions from one account fetch.
+// Kept up to date by the instructions that change the summarized positions when the account
+// is passed to them (it is optional everywhere except init_user_index).
+#[account]
+pub struct UserIndex {
+    pub owner: Pubkey,
+    pub trove_denoms: Vec<String>,      // Collateral denoms of the user's open trove
+    pub has_stake: bool,                // Stability pool deposit > 0
+    pub has_pending_fee_yield: bool,    // Settled aUSD fee yield not yet claimed
+    pub has_pending_rewards: bool,      // Settled reward token emissions not yet claimed
+    pub last_update_slot: u64,
+}
+
+impl UserIndex {
+    pub const LEN: usize = 8 + 32 + 4 + MAX_USER_INDEX_DENOMS * (4 + 32) + 1 + 1 + 1 + 8; // owner(32) + denoms vec + flags(3) + slot(8)
+    
+    pub fn seeds(owner: &Pubkey) -> [&[u8]; 2] {
+        [b"user_index", owner.as_ref()]
+    }
+    
+    pub fn add_trove_denom(&mut self, denom: &str) -> Result<()> {
+        if self.trove_denoms.iter().any(|d| d == denom) {
+            return Ok(());
+        }
+        require!(
+            self.trove_denoms.len() < MAX_USER_INDEX_DENOMS,
+            crate::error::AerospacerProtocolError::InvalidList
+        );
+        self.trove_denoms.push(denom.to_string());
+        Ok(())
+    }
+    
+    pub fn clear_troves(&mut self) {
+        self.trove_denoms.clear();
+    }
+    
+    /// Refresh the stability pool flags from the user's stake account
+    pub fn sync_stake(&mut self, user_stake_amount: &UserStakeAmount) {
+        self.has_stake = user_stake_amount.amount > 0;
+        self.has_pending_fee_yield = user_stake_amount.pending_fee_gain > 0;
+        self.has_pending_rewards = user_stake_amount.pending_reward_gain > 0;
+    }
+}
+
+pub const MAX_USER_INDEX_DENOMS: usize = 8;
+
+// Ring buffer of a user's last TROVE_HISTORY_LEN trove operations, so wallets can show recent
+// activity without an indexer. Optional like UserIndex: created by init_trove_history and
+// appended to by the owner-side trove instructions it is passed to.
+#[account]
+pub struct TroveHistory {
+    pub owner: Pubkey,
+    pub next: u16,                      // Position the next entry is written to
+    pub len: u16,                       // Entries written so far, capped at TROVE_HISTORY_LEN
+    pub entries: [TroveHistoryEntry; TROVE_HISTORY_LEN],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TroveHistoryOp {
+    #[default]
+    Open,
+    AddCollateral,
+    RemoveCollateral,
+    BorrowLoan,
+    RepayLoan,
+    Close,
+    Deleverage,
+    Leverage,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TroveHistoryEntry {
+    pub op: TroveHistoryOp,
+    pub trove_id: u16,
+    pub amount: u64,                    // Collateral or aUSD moved, in the operation's own unit
+    pub icr: u64,                       // Resulting ICR (0 once closed)
+    pub slot: u64,
+}
+
+pub const TROVE_HISTORY_LEN: usize = 16;
+
+impl TroveHistoryEntry {
+    pub const LEN: usize = 1 + 2 + 8 + 8 + 8; // op(1) + trove_id(2) + amount(8) + icr(8) + slot(8)
+}
+
+impl TroveHistory {
+    pub const LEN: usize = 32 + 2 + 2 + TROVE_HISTORY_LEN * TroveHistoryEntry::LEN; // owner(32) + next(2) + len(2) + entries
+    
+    pub fn seeds(owner: &Pubkey) -> [&[u8]; 2] {
+        [b"trove_history", owner.as_ref()]
+    }
+    
+    /// Append an entry, overwriting the oldest once the buffer is full
+    pub fn record(&mut self, entry: TroveHistoryEntry) {
+        self.entries[self.next as usize] = entry;
+        self.next = ((self.next as usize + 1) % TROVE_HISTORY_LEN) as u16;
+        self.len = (self.len + 1).min(TROVE_HISTORY_LEN as u16);
+    }
+    
+    /// Entries written so far, newest first
+    pub fn recent(&self) -> impl Iterator<Item = &TroveHistoryEntry> {
+        (1..=self.len as usize).map(move |back| &self.entries[(self.next as usize + TROVE_HISTORY_LEN - back) % TROVE_HISTORY_LEN])
+    }
+}
+
+// Reward token emission schedule for stability pool depositors
+// Emissions are streamed at a constant rate between start_time and end_time out of the
+// reward vault (funded by the admin) and credited through StateAccount.reward_g_factor.
+#[account]
+pub struct RewardEmission {
+    pub reward_mint: Pubkey,
+    pub emission_per_second: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub last_issuance_time: i64,        // Emissions up to this timestamp are already credited
+    pub total_issued: u64,              // Reward tokens credited to depositors so far
+}
+
+impl RewardEmission {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8; // reward_mint(32) + rate(8) + start(8) + end(8) + last_issuance(8) + total_issued(8)
+    
+    pub fn seeds() -> [&'static [u8]; 1] {
+        [b"reward_emission"]
+    }
+}
+
+// Frontend operator registered for reward emission kickbacks (Liquity-style).
+// Depositors tagged with a frontend keep kickback_rate_bps of their reward emissions;
+// the rest is credited to the frontend and claimed by its operator.
+#[account]
+pub struct Frontend {
+    pub operator: Pubkey,
+    pub kickback_rate_bps: u16,         // Share of reward emissions passed on to depositors (10_000 = all)
+    pub tagged_deposits: u64,           // Stability pool deposits tagged with this frontend
+    pub troves_onboarded: u64,          // Troves opened through this frontend
+    pub pending_rewards: u64,           // Reward tokens credited to the frontend but not yet claimed
+    pub total_rewards_earned: u64,
+}
+
+impl Frontend {
+    pub const LEN: usize = 8 + 32 + 2 + 8 + 8 + 8 + 8; // operator(32) + kickback(2) + tagged_deposits(8) + troves_onboarded(8) + pending(8) + total_earned(8)
+    
+    pub fn seeds(operator: &Pubkey) -> [&[u8]; 2] {
+        [b"frontend", operator.as_ref()]
+    }
+    
+    /// Check a user-supplied tag against this registered frontend
+    pub fn validate_tag(&self, tag: &FrontendTag) -> Result<()> {
+        require!(
+            self.operator == tag.operator,
+            crate::error::AerospacerProtocolError::UnknownFrontend
+        );
+        require!(
+            self.kickback_rate_bps == tag.kickback_rate_bps,
+            crate::error::AerospacerProtocolError::FrontendKickbackMismatch
+        );
+        Ok(())
+    }
+    
+    /// Split a reward amount into (depositor share, frontend share)
+    pub fn split_rewards(&self, amount: u64) -> Result<(u64, u64)> {
+        let depositor_share = (amount as u128)
+            .checked_mul(self.kickback_rate_bps as u128)
+            .ok_or(crate::error::AerospacerProtocolError::OverflowError)?
+            / KICKBACK_RATE_DENOMINATOR as u128;
+        let depositor_share = depositor_share as u64;
+        Ok((depositor_share, amount - depositor_share))
+    }
+}
+
+// Frontend attribution carried by open_trove and stake. The kickback rate must match the
+// registered one, so a user is never onboarded at a rate other than the one they were shown.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FrontendTag {
+    pub operator: Pubkey,
+    pub kickback_rate_bps: u16,
+}
+
+pub const KICKBACK_RATE_DENOMINATOR: u16 = 10_000;
+
+// Collateral seized by liquidations while the stability pool was empty. It stays in the denom's
+// protocol_collateral_vault, booked here until governance routes it (route_undistributed_seizure).
+#[account]
+pub struct UndistributedSeizure {
+    pub denom: String,
+    pub amount: u64,                    // Seized and not yet routed
+    pub total_seized: u64,              // Lifetime amount booked
+    pub total_routed: u64,              // Lifetime amount routed out
+}
+
+impl UndistributedSeizure {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8; // denom(32) + amount(8) + total_seized(8) + total_routed(8)
+    
+    pub fn seeds(denom: &str) -> [&[u8]; 2] {
+        [b"undistributed_seizure", denom.as_bytes()]
+    }
+}
+
+// External strategy ("rebalancer") that undistributed seized collateral can be forwarded to instead
+// of sitting in the vault, e.g. a program selling it on a DEX. Admin-configured; the hook only ever
+// receives tokens already transferred to its inbox and never the vault's authority.
+//
+// Hook interface: an instruction with Anchor-style discriminator REBALANCER_HOOK_IX taking
+// (denom: String, amount: u64), with accounts [inbox (writable), protocol_collateral_vault]
+// followed by the caller-supplied remaining accounts. The inbox is the token account at the hook
+// program's PDA [b"rebalancer_inbox", denom].
+#[account]
+pub struct RebalancerConfig {
+    pub program: Pubkey,                // Hook program
+    pub enabled: bool,
+}
+
+impl RebalancerConfig {
+    pub const LEN: usize = 32 + 1; // program(32) + enabled(1)
+    
+    pub fn seeds() -> [&'static [u8]; 1] {
+        [b"rebalancer_config"]
+    }
+    
+    /// Token account the hook program receives `denom` collateral in
+    pub fn inbox(&self, denom: &str) -> Pubkey {
+        Pubkey::find_program_address(&[b"rebalancer_inbox", denom.as_bytes()], &self.program).0
+    }
+}
+
+pub const REBALANCER_HOOK_IX: &str = "global:on_seized_collateral";
+
+// Public-goods share of liquidation penalties for one collateral denom. The penalty is the seized
+// collateral in excess of the debt's value at the liquidation price; state.public_goods_bps of it is
+// held back from the stability pool in the protocol_collateral_vault, booked here until
+// route_public_goods sends it to the fees program's public-goods address.
+#[account]
+pub struct PublicGoodsAccrual {
+    pub denom: String,
+    pub amount: u64,                    // Booked and not yet routed
+    pub total_accrued: u64,             // Lifetime amount booked
+    pub total_routed: u64,              // Lifetime amount routed out
+}
+
+impl PublicGoodsAccrual {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8; // denom(32) + amount(8) + total_accrued(8) + total_routed(8)
+    
+    pub fn seeds(denom: &str) -> [&[u8]; 2] {
+        [b"public_goods_accrual", denom.as_bytes()]
+    }
+    
+    /// Book a liquidation's public-goods share
+    pub fn book(&mut self, amount: u64) -> Result<()> {
+        self.amount = self.amount
+            .checked_add(amount)
+            .ok_or(crate::error::AerospacerProtocolError::OverflowError)?;
+        self.total_accrued = self.total_accrued
+            .checked_add(amount)
+            .ok_or(crate::error::AerospacerProtocolError::OverflowError)?;
+        Ok(())
+    }
+}
+
+pub const PUBLIC_GOODS_BPS_DENOMINATOR: u16 = 10_000;
+pub const MAX_PUBLIC_GOODS_BPS: u16 = 5_000; // At most half of the penalty leaves the stability pool
+pub const MAX_KEEPER_BOUNTY_BPS: u16 = 5_000; // Together with the public-goods share, never more than the penalty
+pub const MAX_LIQUIDATION_PENALTY_BPS: u16 = 5_000; // A liquidation never seizes more than 150% of the debt's worth
+
+// Peg Stability Module: aUSD is minted 1:1 against a reserve stablecoin (USDC) held in the
+// psm_vault PDA and redeemed back at the same rate, each way minus a small spread.
+// total_minted always equals the vault balance scaled to aUSD decimals.
+#[account]
+pub struct PsmConfig {
+    pub reserve_mint: Pubkey,
+    pub reserve_decimals: u8,
+    pub swap_in_fee_bps: u16,           // Spread charged on USDC -> aUSD
+    pub swap_out_fee_bps: u16,          // Spread charged on aUSD -> USDC
+    pub debt_ceiling: u64,              // Max aUSD outstanding against the reserve
+    pub total_minted: u64,              // aUSD currently backed by the reserve
+}
+
+impl PsmConfig {
+    pub const LEN: usize = 8 + 32 + 1 + 2 + 2 + 8 + 8;
+    
+    pub fn seeds() -> [&'static [u8]; 1] {
+        [b"psm_config"]
+    }
+    
+    fn scale(&self) -> Result<u64> {
+        10u64
+            .checked_pow((STABLECOIN_DECIMALS - self.reserve_decimals) as u32)
+            .ok_or(crate::error::AerospacerProtocolError::OverflowError.into())
+    }
+    
+    /// aUSD base units worth `reserve_amount` reserve base units
+    pub fn to_stablecoin(&self, reserve_amount: u64) -> Result<u64> {
+        reserve_amount
+            .checked_mul(self.scale()?)
+            .ok_or(crate::error::AerospacerProtocolError::OverflowError.into())
+    }
+    
+    /// Reserve base units worth `stablecoin_amount` aUSD base units (rounded down)
+    pub fn to_reserve(&self, stablecoin_amount: u64) -> Result<u64> {
+        Ok(stablecoin_amount / self.scale()?)
+    }
+    
+    /// Spread on `amount`, rounded up so that small swaps are not free
+    pub fn fee(amount: u64, fee_bps: u16) -> Result<u64> {
+        u64::try_from(
+            (amount as u128)
+                .checked_mul(fee_bps as u128)
+                .ok_or(crate::error::AerospacerProtocolError::OverflowError)?
+                .div_ceil(10_000),
+        )
+        .map_err(|_| crate::error::AerospacerProtocolError::OverflowError.into())
+    }
+}
+
+// Risk parameters of one collateral denom (the collateral registry). Created with defaults by
+// register_collateral and tuned with set_collateral_params. Both ratios are in micro-percent, like
+// every ICR the program computes: borrowing or withdrawing must leave a trove at or above
+// minimum_collateral_ratio, and a trove below liquidation_threshold can be liquidated.
+// protocol_fee replaces state.protocol_fee for opening/borrowing against the denom.
+// liquidation_penalty_bps caps what a liquidation seizes at the debt's worth of collateral plus
+// that penalty; configs created before it existed read 0 from their padding and seize everything.
+#[account]
+pub struct CollateralConfig {
+    pub denom: String,
+    pub minimum_collateral_ratio: u64,  // MCR, e.g. 115_000_000 = 115%
+    pub liquidation_threshold: u64,     // e.g. 110_000_000 = 110%
+    pub protocol_fee: u8,               // Opening/borrowing fee percentage (e.g. 5 = 5%)
+    pub liquidation_penalty_bps: u16,   // Seized on top of the debt's worth (1_000 = 10%, 0 = seize all)
+}
+
+impl CollateralConfig {
+    // Still fits: denom(4 + 32) + minimum_collateral_ratio(8) + liquidation_threshold(8) + protocol_fee(1) + liquidation_penalty_bps(2) = 55
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1;
+    
+    pub fn seeds(denom: &str) -> [&[u8]; 2] {
+        [b"collateral_config", denom.as_bytes()]
+    }
+    
+    /// The threshold must sit above 100% (a liquidated trove still covers its debt) and at or below
+    /// the MCR (a trove cannot be opened already liquidatable)
+    pub fn validate_ratios(minimum_collateral_ratio: u64, liquidation_threshold: u64) -> Result<()> {
+        require!(
+            liquidation_threshold > FULL_COLLATERALIZATION_ICR
+                && liquidation_threshold <= minimum_collateral_ratio,
+            crate::error::AerospacerProtocolError::InvalidCollateralRatios
+        );
+        Ok(())
+    }
+    
+    /// A 100% fee would mint nothing to the borrower
+    pub fn validate_protocol_fee(protocol_fee: u8) -> Result<()> {
+        require!(
+            protocol_fee < 100,
+            crate::error::AerospacerProtocolError::InvalidProtocolFee
+        );
+        Ok(())
+    }
+    
+    pub fn validate_liquidation_penalty(liquidation_penalty_bps: u16) -> Result<()> {
+        require!(
+            liquidation_penalty_bps <= MAX_LIQUIDATION_PENALTY_BPS,
+            crate::error::AerospacerProtocolError::InvalidLiquidationPenalty
+        );
+        Ok(())
+    }
+}
+
+// Promotional terms for a newly listed collateral denom: borrowing fees (open_trove and
+// borrow_loan) are waived until fee_free_until_slot, after which the promotion simply lapses.
+#[account]
+pub struct DenomPromotion {
+    pub denom: String,
+    pub fee_free_until_slot: u64,       // First slot at which fees apply again (0 = no promotion)
+}
+
+impl DenomPromotion {
+    pub const LEN: usize = 8 + 32 + 8; // denom(32) + fee_free_until_slot(8)
+    
+    pub fn seeds(denom: &str) -> [&[u8]; 2] {
+        [b"denom_promotion", denom.as_bytes()]
+    }
+    
+    pub fn is_active(&self, slot: u64) -> bool {
+        slot < self.fee_free_until_slot
+    }
+}
+
+// Trove ids a wallet has opened and not yet closed, so market makers can run several isolated
+// positions. Registered by open_trove, removed by close_trove; a liquidated trove keeps its id
+// until the owner reopens it.
+#[account]
+pub struct TroveRegistry {
+    pub owner: Pubkey,
+    pub trove_ids: Vec<u16>,
+}
+
+impl TroveRegistry {
+    pub const MAX_TROVES: usize = 16;
+    pub const LEN: usize = 32 + 4 + 2 * Self::MAX_TROVES; // owner(32) + vec len(4) + ids
+    
+    pub fn seeds(owner: &Pubkey) -> [&[u8]; 2] {
+        [b"trove_registry", owner.as_ref()]
+    }
+    
+    /// Record `trove_id` as in use (idempotent). Ids run from 0 to MAX_TROVES - 1.
+    pub fn register(&mut self, trove_id: u16) -> Result<()> {
+        require!(
+            (trove_id as usize) < Self::MAX_TROVES,
+            crate::error::AerospacerProtocolError::InvalidTroveId
+        );
+        if self.trove_ids.contains(&trove_id) {
+            return Ok(());
+        }
+        require!(
+            self.trove_ids.len() < Self::MAX_TROVES,
+            crate::error::AerospacerProtocolError::TooManyTroves
+        );
+        self.trove_ids.push(trove_id);
+        Ok(())
+    }
+    
+    pub fn unregister(&mut self, trove_id: u16) {
+        self.trove_ids.retain(|id| *id != trove_id);
+    }
+}
+
+// One page of the on-chain trove index: the troves opened since the index existed, in open order,
+// so the off-chain sorter can enumerate them by fetching TroveIndexPage accounts alone instead of
+// scanning every PDA. open_trove appends, close_trove tombstones (zeroes) the entry in place and
+// transfer_trove rewrites it; positions never move. Liquidated troves keep their entry until a
+// keeper prunes it with prune_trove_index.
+#[account]
+pub struct TroveIndexPage {
+    pub page: u32,
+    pub entries: Vec<TroveIndexEntry>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TroveIndexEntry {
+    pub owner: Pubkey,                  // Pubkey::default() = tombstone
+    pub trove_id: u16,
+}
+
+impl TroveIndexEntry {
+    pub fn is_tombstone(&self) -> bool {
+        self.owner == Pubkey::default()
+    }
+}
+
+impl TroveIndexPage {
+    pub const ENTRIES_PER_PAGE: u64 = 64;
+    pub const LEN: usize = 4 + 4 + (32 + 2) * Self::ENTRIES_PER_PAGE as usize; // page(4) + vec len(4) + entries
+    
+    /// Page number of an index position
+    pub fn page_of(position: u64) -> u32 {
+        u32::try_from(position / Self::ENTRIES_PER_PAGE).unwrap_or(u32::MAX)
+    }
+    
+    /// Seed of the page holding an index position
+    pub fn page_seed(position: u64) -> [u8; 4] {
+        Self::page_of(position).to_le_bytes()
+    }
+}
+
+// Lets `delegate` (e.g. an automation bot) add collateral to and repay debt of `owner`'s trove.
+// Never grants withdrawal: remove_collateral, borrow_loan and close_trove stay owner-only.
+// Created by approve_delegate, closed (revoked) by revoke_delegate.
+#[account]
+pub struct TroveDelegate {
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+}
+
+impl TroveDelegate {
+    pub const LEN: usize = 32 + 32; // owner(32) + delegate(32)
+    
+    pub fn seeds<'a>(owner: &'a Pubkey, delegate: &'a Pubkey) -> [&'a [u8]; 3] {
+        [b"trove_delegate", owner.as_ref(), delegate.as_ref()]
+    }
+}
+
+// Archive of closed and liquidated troves kept in an SPL account-compression concurrent merkle tree.
+// This PDA is the tree authority; each archived trove appends the hash of an ArchivedTrove record
+// (logged in full through the noop program for indexers) and its rent-bearing records are closed.
+#[account]
+pub struct TroveArchive {
+    pub merkle_tree: Pubkey,
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+    pub records: u64,                   // Leaves appended so far (= index of the next leaf)
+}
+
+impl TroveArchive {
+    pub const LEN: usize = 32 + 4 + 4 + 8; // merkle_tree(32) + max_depth(4) + max_buffer_size(4) + records(8)
+    
+    pub fn seeds() -> [&'static [u8]; 1] {
+        [b"trove_archive"]
+    }
+}
+
+// Compact history record of a trove leaving live state, hashed into a TroveArchive leaf
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ArchivedTrove {
+    pub owner: Pubkey,
+    pub trove_id: u16,
+    pub status: TroveStatus,            // Closed or Liquidated
+    pub tag: [u8; 32],
+    pub leaf_index: u64,
+    pub archived_slot: u64,
+    pub archived_at: i64,
+}
+
+// Liveness record of a keeper (liquidation/redemption bot), refreshed by register_heartbeat.
+// query_stale_keepers reports keepers whose last heartbeat is older than a given slot age.
+#[account]
+pub struct KeeperHeartbeat {
+    pub keeper: Pubkey,
+    pub first_seen_slot: u64,
+    pub last_seen_slot: u64,
+    pub last_seen_at: i64,              // Unix timestamp of the last heartbeat
+    pub heartbeats: u64,
+}
+
+impl KeeperHeartbeat {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8; // keeper(32) + first_seen(8) + last_seen(8) + last_seen_at(8) + heartbeats(8)
+    
+    pub fn seeds(keeper: &Pubkey) -> [&[u8]; 2] {
+        [b"keeper_heartbeat", keeper.as_ref()]
+    }
+    
+    /// Slots since the last heartbeat
+    pub fn age(&self, current_slot: u64) -> u64 {
+        current_slot.saturating_sub(self.last_seen_slot)
+    }
+}
+
+pub const MAX_STALE_KEEPER_QUERY: usize = 32;
+
+// Opt-in registration of a liquidation keeper. A registered keeper that liquidates receives
+// state.keeper_bounty_bps of the liquidation penalty in collateral, plus the flat aUSD bounty of
+// the KeeperBountyFund while it is funded. The counters are public stats of the keeper's work.
+#[account]
+pub struct Keeper {
+    pub keeper: Pubkey,
+    pub registered_slot: u64,
+    pub troves_liquidated: u64,
+    pub debt_liquidated: u64,           // aUSD debt of the troves it liquidated
+    pub collateral_bounty_payouts: u64, // Liquidations that paid a collateral bounty (amounts are in events)
+    pub flat_bounties_earned: u64,      // aUSD received from the KeeperBountyFund
+}
+
+impl Keeper {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 8; // keeper(32) + registered_slot(8) + troves(8) + debt(8) + payouts(8) + flat(8)
+    
+    pub fn seeds(keeper: &Pubkey) -> [&[u8]; 2] {
+        [b"keeper", keeper.as_ref()]
+    }
+}
+
+// Flat aUSD bounty per liquidated trove, paid to registered keepers out of a balance the DAO
+// funds from its fee income. The balance is held in the protocol stablecoin vault.
+#[account]
+pub struct KeeperBountyFund {
+    pub flat_bounty: u64,               // aUSD per liquidated trove (0 = off)
+    pub balance: u64,                   // Funded and not yet paid out
+    pub total_funded: u64,
+    pub total_paid: u64,
+}
+
+impl KeeperBountyFund {
+    pub const LEN: usize = 8 + 8 + 8 + 8; // flat_bounty(8) + balance(8) + total_funded(8) + total_paid(8)
+    
+    pub fn seeds() -> [&'static [u8]; 1] {
+        [b"keeper_bounty_fund"]
+    }
+    
+    /// Take the bounty for `troves` liquidations, capped by what is left in the fund
+    pub fn take(&mut self, troves: u64) -> u64 {
+        let bounty = self.flat_bounty.saturating_mul(troves).min(self.balance);
+        self.balance -= bounty;
+        self.total_paid = self.total_paid.saturating_add(bounty);
+        bounty
+    }
+}
+
+// Dust protocol fees held back from aerospacer-fees. A fee below min_distribution_amount is moved
+// into the fee accumulator vault instead of paying for a distribute_fee CPI, and the
+// permissionless distribute_accumulated sends everything parked with one CPI per fee source.
+#[account]
+pub struct FeeAccumulator {
+    pub min_distribution_amount: u64,   // Fees below this are parked (0 = off)
+    pub pending: [u64; FEE_SOURCE_COUNT], // Parked and not yet distributed, by FeeSource
+    pub total_parked: u64,
+    pub total_distributed: u64,
+}
+
+// Variants of fees_integration::FeeSource
+pub const FEE_SOURCE_COUNT: usize = 4;
+
+impl FeeAccumulator {
+    pub const LEN: usize = 8 + 8 * FEE_SOURCE_COUNT + 8 + 8; // min_distribution_amount(8) + pending(32) + total_parked(8) + total_distributed(8)
+    
+    pub fn seeds() -> [&'static [u8]; 1] {
+        [b"fee_accumulator"]
+    }
+    
+    pub fn park(&mut self, source: crate::fees_integration::FeeSource, amount: u64) -> Result<()> {
+        let pending = &mut self.pending[source as usize];
+        *pending = pending
+            .checked_add(amount)
+            .ok_or(crate::error::AerospacerProtocolError::OverflowError)?;
+        self.total_parked = self.total_parked
+            .checked_add(amount)
+            .ok_or(crate::error::AerospacerProtocolError::OverflowError)?;
+        Ok(())
+    }
+    
+    /// Take everything parked for `source`, counting it as distributed
+    pub fn take(&mut self, source: crate::fees_integration::FeeSource) -> u64 {
+        let amount = std::mem::take(&mut self.pending[source as usize]);
+        self.total_distributed = self.total_distributed.saturating_add(amount);
+        amount
+    }
+}
+
+// Dedicated aUSD mint authority. The PDA itself holds the SPL mint authority and every mint goes
+// through mint_gate, which holds trove debt to trove_debt_cap. Handing the authority to another
+// address (e.g. the PDA of an upgraded program) is proposed by the admin and executable only after
+// MINT_AUTHORITY_MIGRATION_DELAY_SECS.
+#[account]
+pub struct MintAuthority {
+    pub trove_debt_cap: u64,            // Max total_debt_amount trove mints may reach (0 = uncapped)
+    pub pending_authority: Pubkey,      // Proposed new mint authority (default = no migration pending)
+    pub migration_eta: i64,             // Earliest execution of the pending migration
+    pub migrated_to: Pubkey,            // Set once the authority has left this PDA; mint_gate refuses to mint afterwards
+}
+
+impl MintAuthority {
+    pub const LEN: usize = 8 + 32 + 8 + 32; // trove_debt_cap(8) + pending_authority(32) + migration_eta(8) + migrated_to(32)
+    
+    pub fn seeds() -> [&'static [u8]; 1] {
+        [b"mint_authority"]
+    }
+    
+    pub fn migration_pending(&self) -> bool {
+        self.pending_authority != Pubkey::default()
+    }
+    
+    pub fn propose_migration(&mut self, new_authority: Pubkey, now: i64) -> Result<()> {
+        require!(
+            new_authority != Pubkey::default(),
+            crate::error::AerospacerProtocolError::InvalidAddress
+        );
+        self.pending_authority = new_authority;
+        self.migration_eta = now
+            .checked_add(MINT_AUTHORITY_MIGRATION_DELAY_SECS)
+            .ok_or(crate::error::AerospacerProtocolError::OverflowError)?;
+        Ok(())
+    }
+    
+    pub fn cancel_migration(&mut self) -> Result<()> {
+        require!(
+            self.migration_pending(),
+            crate::error::AerospacerProtocolError::NoMintAuthorityMigration
+        );
+        self.pending_authority = Pubkey::default();
+        self.migration_eta = 0;
+        Ok(())
+    }
+    
+    /// Clear the pending migration once its timelock has passed, returning the new authority
+    pub fn take_ready_migration(&mut self, now: i64) -> Result<Pubkey> {
+        require!(
+            self.migration_pending(),
+            crate::error::AerospacerProtocolError::NoMintAuthorityMigration
+        );
+        require!(
+            now >= self.migration_eta,
+            crate::error::AerospacerProtocolError::MintAuthorityMigrationTimelocked
+        );
+        let new_authority = std::mem::take(&mut self.pending_authority);
+        self.migration_eta = 0;
+        self.migrated_to = new_authority;
+        Ok(new_authority)
+    }
+    
+    /// Trove debt may not grow past the cap; debt already above a lowered cap only blocks new mints
+    pub fn check_trove_debt(&self, total_debt: u64) -> Result<()> {
+        require!(
+            self.trove_debt_cap == 0 || total_debt <= self.trove_debt_cap,
+            crate::error::AerospacerProtocolError::TroveDebtCapExceeded
+        );
+        Ok(())
+    }
+}
+
+// aUSD reserve that absorbs the shortfall of troves liquidated below 100% ICR before it is
+// socialized through redistribution. Anyone may fund it; the balance is held in the protocol
+// stablecoin vault and burned as it absorbs bad debt.
+#[account]
+pub struct BadDebtBackstop {
+    pub balance: u64,                   // Funded and not yet absorbed
+    pub total_funded: u64,
+    pub total_absorbed: u64,            // Shortfall covered by the backstop
+    pub total_socialized: u64,          // Shortfall redistributed after the backstop ran dry
+}
+
+impl BadDebtBackstop {
+    pub const LEN: usize = 8 + 8 + 8 + 8; // balance(8) + total_funded(8) + total_absorbed(8) + total_socialized(8)
+    
+    pub fn seeds() -> [&'static [u8]; 1] {
+        [b"bad_debt_backstop"]
+    }
+    
+    /// Cover as much of `shortfall` as the balance allows, returning (absorbed, socialized)
+    pub fn absorb(&mut self, shortfall: u64) -> (u64, u64) {
+        let absorbed = shortfall.min(self.balance);
+        let socialized = shortfall - absorbed;
+        self.balance -= absorbed;
+        self.total_absorbed = self.total_absorbed.saturating_add(absorbed);
+        self.total_socialized = self.total_socialized.saturating_add(socialized);
+        (absorbed, socialized)
+    }
+}
+
+// Registry of known trove tags (frontends/bots). Only consulted by open_trove
+// when StateAccount.enforce_trove_tags is set; untagged troves are always allowed.
+#[account]
+pub struct TagRegistry {
+    pub tags: Vec<[u8; 32]>,
+}
+
+impl TagRegistry {
+    pub const LEN: usize = 8 + 4 + 32 * MAX_REGISTERED_TAGS; // discriminator + vec length + tags
+    
+    pub fn seeds() -> [&'static [u8]; 1] {
+        [b"tag_registry"]
+    }
+    
+    pub fn contains(&self, tag: &[u8; 32]) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+}
+
+pub const MAX_REGISTERED_TAGS: usize = 64;
+pub const UNTAGGED: [u8; 32] = [0u8; 32];
+
+// Liquidators approved while StateAccount.permissioned_liquidation is set (early mainnet).
+// Ignored once liquidations are permissionless.
+#[account]
+pub struct LiquidatorWhitelist {
+    pub liquidators: Vec<Pubkey>,
+}
+
+impl LiquidatorWhitelist {
+    pub const LEN: usize = 4 + 32 * MAX_WHITELISTED_LIQUIDATORS; // vec length + liquidators
+    
+    pub fn seeds() -> [&'static [u8]; 1] {
+        [b"liquidator_whitelist"]
+    }
+    
+    pub fn contains(&self, liquidator: &Pubkey) -> bool {
+        self.liquidators.iter().any(|l| l == liquidator)
+    }
+    
+    /// Enforce permissioned liquidation mode for `liquidator`; a no-op while liquidations are permissionless
+    pub fn authorize(state: &StateAccount, whitelist: Option<&Self>, liquidator: &Pubkey) -> Result<()> {
+        if state.permissioned_liquidation == 0 {
+            return Ok(());
+        }
+        require!(
+            whitelist.is_some_and(|whitelist| whitelist.contains(liquidator)),
+            crate::error::AerospacerProtocolError::LiquidatorNotWhitelisted
+        );
+        Ok(())
+    }
+}
+
+pub const MAX_WHITELISTED_LIQUIDATORS: usize = 32;
+
+// Swap programs leverage_open may route borrowed aUSD through. Empty until the admin adds one,
+// which keeps leverage_open disabled.
+#[account]
+pub struct SwapProgramWhitelist {
+    pub programs: Vec<Pubkey>,
+}
+
+impl SwapProgramWhitelist {
+    pub const LEN: usize = 4 + 32 * MAX_WHITELISTED_SWAP_PROGRAMS; // vec length + programs
+    
+    pub fn seeds() -> [&'static [u8]; 1] {
+        [b"swap_program_whitelist"]
+    }
+    
+    pub fn contains(&self, program: &Pubkey) -> bool {
+        self.programs.iter().any(|p| p == program)
+    }
+}
+
+pub const MAX_WHITELISTED_SWAP_PROGRAMS: usize = 8;
+pub const MAX_LEVERAGE_SLIPPAGE_BPS: u16 = 1_000; // 10% below the oracle value of the aUSD swapped
+
+// Unfilled net aUSD of a redemption too large for one transaction, escrowed in the
+// redemption_escrow_vault until continue_redemption fills it against further troves. The fee on
+// the escrowed part is charged as it is filled; its throttle volume is recorded up front and given
+// back if the ticket is cancelled. One ticket per redeemer and denom; closed once empty.
+#[account]
+pub struct RedemptionTicket {
+    pub redeemer: Pubkey,
+    pub collateral_denom: String,
+    pub remaining: u64,                 // Escrowed and not yet redeemed
+    pub total_filled: u64,              // Redeemed through continue_redemption
+    pub remaining_gross: u64,           // Fee-inclusive amount requested for `remaining`, its fee unpaid
+    pub reserved_window_start_slot: u64, // Throttle window the latest escrow was recorded in
+    pub reserved_volume: u64,           // Unfilled throttle volume recorded in that window
+}
+
+impl RedemptionTicket {
+    pub const LEN: usize = 32 + 4 + 32 + 8 + 8 + 8 + 8 + 8; // redeemer(32) + denom(4+32) + remaining(8) + total_filled(8) + remaining_gross(8) + reserved_window_start_slot(8) + reserved_volume(8)
+    
+    pub fn seeds<'a>(redeemer: &'a Pubkey, denom: &'a str) -> [&'a [u8]; 3] {
+        [b"redemption_ticket", redeemer.as_ref(), denom.as_bytes()]
+    }
+    
+    /// Escrow another unfilled remainder on the ticket, with the fee-inclusive amount requested for it
+    pub fn escrow(&mut self, amount: u64, gross: u64) -> Result<()> {
+        self.remaining = self.remaining
+            .checked_add(amount)
+            .ok_or(crate::error::AerospacerProtocolError::OverflowError)?;
+        self.remaining_gross = self.remaining_gross
+            .checked_add(gross)
+            .ok_or(crate::error::AerospacerProtocolError::OverflowError)?;
+        Ok(())
+    }
+    
+    /// Track throttle volume recorded for an escrow in the window starting at `window_start_slot`;
+    /// what an earlier window reserved has expired with it
+    pub fn reserve_volume(&mut self, volume: u64, window_start_slot: u64) -> Result<()> {
+        if window_start_slot != self.reserved_window_start_slot {
+            self.reserved_window_start_slot = window_start_slot;
+            self.reserved_volume = 0;
+        }
+        self.reserved_volume = self.reserved_volume
+            .checked_add(volume)
+            .ok_or(crate::error::AerospacerProtocolError::OverflowError)?;
+        Ok(())
+    }
+    
+    /// Book `amount` of the escrow as redeemed, returning the fee-inclusive share it was requested
+    /// as, which its fee is charged on
+    pub fn fill(&mut self, amount: u64) -> Result<u64> {
+        let gross = crate::utils::calculate_gross_share(self.remaining_gross, amount, self.remaining)?;
+        self.remaining = self.remaining
+            .checked_sub(amount)
+            .ok_or(crate::error::AerospacerProtocolError::OverflowError)?;
+        self.remaining_gross -= gross;
+        // Fills use up the oldest reservations first, so at most what is left stays releasable
+        self.reserved_volume = self.reserved_volume.min(self.remaining_gross);
+        self.total_filled = self.total_filled
+            .checked_add(amount)
+            .ok_or(crate::error::AerospacerProtocolError::OverflowError)?;
+        Ok(gross)
+    }
+}
+
+// Dutch auction of a liquidated trove's collateral (third liquidation path). Used when the
+// trove's debt dwarfs the stability pool, so the collateral is sold for aUSD at a price that
+// decays from a premium over the oracle value down to a floor instead of being redistributed.
+// Keyed by the liquidated trove; closed to the initiator on settle.
+#[account]
+pub struct LiquidationAuction {
+    pub owner: Pubkey,                  // Owner of the liquidated trove
+    pub trove_id: u16,
+    pub denom: String,
+    pub initiator: Pubkey,              // Paid the rent, refunded on settle
+    pub lot_size: u64,                  // Collateral seized into the auction
+    pub collateral_remaining: u64,      // Collateral not yet sold
+    pub debt_remaining: u64,            // aUSD still to be raised (burned)
+    pub debt_raised: u64,               // aUSD burned from bids so far
+    pub start_price: u128,              // aUSD asked for the whole lot when the auction starts
+    pub floor_price: u128,              // aUSD asked for the whole lot from `duration` on
+    pub started_at: i64,
+    pub duration: i64,                  // Seconds of linear decay, after which bidding closes
+}
+
+impl LiquidationAuction {
+    pub const LEN: usize = 32 + 2 + (4 + 32) + 32 + 8 + 8 + 8 + 8 + 16 + 16 + 8 + 8;
+
+    pub fn seeds<'a>(owner: &'a Pubkey, trove_seed: &'a [u8], denom: &'a str) -> [&'a [u8]; 4] {
+        [b"liquidation_auction", owner.as_ref(), trove_seed, denom.as_bytes()]
+    }
+
+    pub fn ends_at(&self) -> i64 {
+        self.started_at.saturating_add(self.duration)
+    }
+
+    /// Whole-lot price at `now`: linear from start_price to floor_price over `duration`
+    pub fn lot_price(&self, now: i64) -> u128 {
+        let elapsed = now.saturating_sub(self.started_at).clamp(0, self.duration.max(0));
+        if self.duration <= 0 || elapsed == self.duration {
+            return self.floor_price;
+        }
+        let decay = self.start_price.saturating_sub(self.floor_price);
+        self.start_price - decay * elapsed as u128 / self.duration as u128
+    }
+
+    /// (collateral, payment) for a bid on up to `collateral_amount` at `now`
+    ///
+    /// Payment rounds up in the protocol's favour. A bid that would raise more than the
+    /// remaining debt is cut down to the collateral the remaining debt buys.
+    pub fn quote(&self, collateral_amount: u64, now: i64) -> Result<(u64, u64)> {
+        let price = self.lot_price(now);
+        let lot_size = self.lot_size as u128;
+        require!(lot_size > 0, crate::error::AerospacerProtocolError::DivideByZeroError);
+
+        let collateral = collateral_amount.min(self.collateral_remaining);
+        let payment = (collateral as u128)
+            .checked_mul(price)
+            .ok_or(crate::error::AerospacerProtocolError::OverflowError)?
+            .div_ceil(lot_size);
+        if payment <= self.debt_remaining as u128 {
+            return Ok((collateral, payment as u64));
+        }
+
+        let collateral = (self.debt_remaining as u128)
+            .checked_mul(lot_size)
+            .ok_or(crate::error::AerospacerProtocolError::OverflowError)?
+            .checked_div(price)
+            .ok_or(crate::error::AerospacerProtocolError::DivideByZeroError)?;
+        Ok((collateral as u64, self.debt_remaining))
+    }
+
+    /// Sold out, fully repaid, or past its end
+    pub fn is_settleable(&self, now: i64) -> bool {
+        self.collateral_remaining == 0 || self.debt_remaining == 0 || now >= self.ends_at()
+    }
+}
+
+// Auction parameters (see LiquidationAuction)
+pub const AUCTION_POOL_COVERAGE_FACTOR: u64 = 3; // Auction once the trove's debt exceeds 3x the stability pool
+pub const AUCTION_DURATION_SECS: i64 = 3_600;
+pub const AUCTION_START_PREMIUM_BPS: u64 = 12_000; // Starts at 120% of the oracle value
+pub const AUCTION_FLOOR_BPS: u64 = 7_000; // Decays to 70% of the oracle value
+
 // Constants to match INJECTIVE exactly
 pub const MINIMUM_LOAN_AMOUNT: u64 = 1_000_000_000_000_000; // 0.001 aUSD with 18 decimals
 pub const MINIMUM_COLLATERAL_AMOUNT: u64 = 1_000_000; // 0.001 SOL with 9 decimals
 pub const DEFAULT_MINIMUM_COLLATERAL_RATIO: u64 = 115_000_000; // 115% in micro-percent (115 * 1_000_000)
+pub const DEFAULT_LIQUIDATION_THRESHOLD: u64 = 110_000_000; // 110% in micro-percent
+pub const FULL_COLLATERALIZATION_ICR: u64 = 100_000_000; // Seized collateral worth exactly the debt (100% in micro-percent)
 pub const DEFAULT_PROTOCOL_FEE: u8 = 5; // 5%
+pub const MAX_PROTOCOL_FEE: u8 = 10; // Global opening/borrowing fee bound at initialize (10%)
+pub const MAX_MINIMUM_COLLATERAL_RATIO: u64 = 1_000_000_000; // 1000% - anything above is a plain-percent or decimal mistake
+pub const MAX_BOOTSTRAP_PERIOD_SECS: u64 = 365 * 24 * 3_600; // Redemptions may not be blocked for more than a year
+pub const DEFAULT_MIN_REDEMPTION_AMOUNT: u64 = 10_000_000_000_000_000; // 0.01 aUSD with 18 decimals
+pub const DEFAULT_MAX_REDEMPTION_TROVES: u8 = 20;
+pub const DEFAULT_BOOTSTRAP_PERIOD_SECS: u64 = 14 * 24 * 3_600; // Liquity's 14-day redemption delay
+pub const MINT_AUTHORITY_MIGRATION_DELAY_SECS: i64 = 2 * 24 * 3_600; // Holders see a mint authority hand-off two days ahead
+pub const FLASH_MINT_FEE_BPS: u64 = 9; // 0.09% of the flash minted amount, paid to stability depositors
+pub const STABLECOIN_DECIMALS: u8 = 18; // aUSD
+pub const MAX_PSM_FEE_BPS: u16 = 100; // PSM spread is capped at 1% each way
+
+// L factor renormalization (redistribution accumulators)
+// Each renormalization divides l_collateral/l_debt (and, lazily, user snapshots) by the factor
+// and sheds the same number of digits from the precision used to read them
+pub const L_RENORMALIZATION_FACTOR: u128 = 1_000;
+pub const L_RENORMALIZATION_THRESHOLD: u128 = 1_000_000_000_000_000_000_000_000_000_000_000_000; // 1e36 (u128::MAX ~ 3.4e38)
+pub const MAX_L_SCALE: u8 = 3; // Keeps at least 1e9 of precision in the L factors
+
+// Stability pool P factor scaling (Liquity's scale changes)
+// When a liquidation would leave P below the threshold, P is multiplied by the factor and
+// current_scale increments; deposits snapshotted two or more scales ago compound to zero
+pub const P_SCALE_FACTOR: u128 = 1_000_000_000;
+pub const P_SCALE_THRESHOLD: u128 = 1_000_000_000; // Keeps at least 1e9 of precision in P
 
 // Decimal fractions to match INJECTIVE
 pub const DECIMAL_FRACTION_6: u128 = 1_000_000;
@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use crate::state::*;
+use crate::error::*;
+use crate::events::TroveArchived;
+
+/// SPL account compression program, owner of the archive's concurrent merkle tree
+pub const ACCOUNT_COMPRESSION_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+/// SPL noop program, used to log full archive records (and the tree's changelogs) for indexers
+pub const NOOP_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+/// Accounts needed to write to the trove archive
+pub struct ArchiveAccounts<'a, 'info> {
+    pub trove_archive: &'a mut Account<'info, TroveArchive>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub noop_program: &'a AccountInfo<'info>,
+}
+
+/// First 8 bytes of SHA256("global:<name>") - Anchor instruction discriminator
+fn discriminator(name: &str) -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(format!("global:{}", name).as_bytes()).to_bytes()[..8]);
+    discriminator
+}
+
+/// Call init_empty_merkle_tree on the account compression program, with the TroveArchive PDA as authority
+/// The tree account must already be allocated (and owned by the compression program) for the given size
+pub fn init_archive_tree<'info>(
+    trove_archive: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    compression_program: &AccountInfo<'info>,
+    noop_program: &AccountInfo<'info>,
+    max_depth: u32,
+    max_buffer_size: u32,
+    archive_bump: u8,
+) -> Result<()> {
+    let mut data = discriminator("init_empty_merkle_tree").to_vec();
+    max_depth.serialize(&mut data)?;
+    max_buffer_size.serialize(&mut data)?;
+    
+    let ix = Instruction {
+        program_id: *compression_program.key,
+        accounts: vec![
+            AccountMeta::new(*merkle_tree.key, false),
+            AccountMeta::new_readonly(*trove_archive.key, true),
+            AccountMeta::new_readonly(*noop_program.key, false),
+        ],
+        data,
+    };
+    
+    invoke_signed(
+        &ix,
+        &[
+            compression_program.clone(),
+            merkle_tree.clone(),
+            trove_archive.clone(),
+            noop_program.clone(),
+        ],
+        &[&[b"trove_archive", &[archive_bump]]],
+    )?;
+    
+    msg!("Trove archive tree initialized: depth {}, buffer {}", max_depth, max_buffer_size);
+    Ok(())
+}
+
+/// Append the record of a finished trove to the archive
+///
+/// The full record is logged through the noop program and its hash appended as the next leaf,
+/// so the history can be rebuilt (and proven) off-chain once the live accounts are closed.
+pub fn archive_trove_record(archive: ArchiveAccounts, user_debt: &UserDebtAmount) -> Result<ArchivedTrove> {
+    require!(
+        matches!(user_debt.status, TroveStatus::Closed | TroveStatus::Liquidated),
+        AerospacerProtocolError::TroveNotArchivable
+    );
+    require!(
+        *archive.merkle_tree.key == archive.trove_archive.merkle_tree,
+        AerospacerProtocolError::InvalidAddress
+    );
+    require!(
+        *archive.compression_program.key == ACCOUNT_COMPRESSION_PROGRAM_ID
+            && *archive.noop_program.key == NOOP_PROGRAM_ID,
+        AerospacerProtocolError::InvalidAddress
+    );
+    
+    let clock = Clock::get()?;
+    let record = ArchivedTrove {
+        owner: user_debt.owner,
+        trove_id: user_debt.trove_id,
+        status: user_debt.status,
+        tag: user_debt.tag,
+        leaf_index: archive.trove_archive.records,
+        archived_slot: clock.slot,
+        archived_at: clock.unix_timestamp,
+    };
+    let record_bytes = record.try_to_vec()?;
+    
+    // Full record for indexers
+    invoke(
+        &Instruction {
+            program_id: NOOP_PROGRAM_ID,
+            accounts: vec![],
+            data: record_bytes.clone(),
+        },
+        std::slice::from_ref(archive.noop_program),
+    )?;
+    
+    let leaf = hash(&record_bytes).to_bytes();
+    let mut data = discriminator("append").to_vec();
+    leaf.serialize(&mut data)?;
+    
+    let archive_info = archive.trove_archive.to_account_info();
+    let (_pda, bump) = Pubkey::find_program_address(&TroveArchive::seeds(), &crate::ID);
+    let ix = Instruction {
+        program_id: ACCOUNT_COMPRESSION_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*archive.merkle_tree.key, false),
+            AccountMeta::new_readonly(*archive_info.key, true),
+            AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+        ],
+        data,
+    };
+    invoke_signed(
+        &ix,
+        &[
+            archive.compression_program.clone(),
+            archive.merkle_tree.clone(),
+            archive_info,
+            archive.noop_program.clone(),
+        ],
+        &[&[b"trove_archive", &[bump]]],
+    )?;
+    
+    archive.trove_archive.records = archive.trove_archive.records
+        .checked_add(1)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    
+    msg!("Trove archived: owner={}, trove_id={}, leaf={}", record.owner, record.trove_id, record.leaf_index);
+    
+    emit!(TroveArchived {
+        owner: record.owner,
+        trove_id: record.trove_id,
+        merkle_tree: archive.trove_archive.merkle_tree,
+        leaf_index: record.leaf_index,
+    });
+    
+    Ok(record)
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use crate::state::{StateAccount, TroveIndexEntry, TroveIndexPage, UserDebtAmount};
+use crate::error::AerospacerProtocolError;
+
+/// Append a trove to the tail page of the trove index and record its position on the trove.
+/// `page` must be the page of state.trove_index_len (a fresh account when a new page starts).
+pub fn append_trove(page: &mut TroveIndexPage, state: &mut StateAccount, user_debt: &mut UserDebtAmount) -> Result<()> {
+    let position = state.trove_index_len;
+    let page_number = TroveIndexPage::page_of(position);
+    if page.entries.is_empty() {
+        page.page = page_number;
+    }
+    require!(
+        page.page == page_number && page.entries.len() as u64 == position % TroveIndexPage::ENTRIES_PER_PAGE,
+        AerospacerProtocolError::InvalidTroveIndexPage
+    );
+    
+    page.entries.push(TroveIndexEntry { owner: user_debt.owner, trove_id: user_debt.trove_id });
+    state.trove_index_len = position
+        .checked_add(1)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    user_debt.index_position = state.trove_index_len;
+    
+    msg!("Trove index: {} #{} at position {} (page {})", user_debt.owner, user_debt.trove_id, position, page_number);
+    Ok(())
+}
+
+/// Entry of `index_position` (position + 1, see UserDebtAmount) in `page`, which must be the page
+/// holding it
+pub fn entry_at(page: &mut TroveIndexPage, index_position: u64) -> Result<&mut TroveIndexEntry> {
+    require!(index_position > 0, AerospacerProtocolError::InvalidTroveIndexPage);
+    let position = index_position - 1;
+    require!(
+        page.page == TroveIndexPage::page_of(position),
+        AerospacerProtocolError::InvalidTroveIndexPage
+    );
+    page.entries
+        .get_mut((position % TroveIndexPage::ENTRIES_PER_PAGE) as usize)
+        .ok_or_else(|| AerospacerProtocolError::InvalidTroveIndexPage.into())
+}
+
+/// Tombstone the entry at `index_position`, which must still hold `expected`
+pub fn tombstone_entry(
+    page: &mut TroveIndexPage,
+    state: &mut StateAccount,
+    index_position: u64,
+    expected: TroveIndexEntry,
+) -> Result<()> {
+    let entry = entry_at(page, index_position)?;
+    require!(*entry == expected, AerospacerProtocolError::InvalidTroveIndexPage);
+    *entry = TroveIndexEntry::default();
+    state.trove_index_tombstones = state.trove_index_tombstones.saturating_add(1);
+    
+    msg!("Trove index: {} #{} tombstoned at position {}", expected.owner, expected.trove_id, index_position - 1);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{TroveStatus, UNTAGGED};
+
+    fn trove(owner: Pubkey, trove_id: u16) -> UserDebtAmount {
+        UserDebtAmount {
+            owner,
+            amount: 0,
+            l_debt_snapshot: 0,
+            tag: UNTAGGED,
+            status: TroveStatus::Active,
+            trove_id,
+            locked_slot: 0,
+            liquidation_reserve: 0,
+            index_position: 0,
+            collateral_legs: 1,
+        }
+    }
+
+    #[test]
+    fn test_trove_index_appends_across_pages_and_tombstones_in_place() {
+        let mut state: StateAccount = bytemuck::Zeroable::zeroed();
+        let mut first_page = TroveIndexPage { page: 0, entries: Vec::new() };
+        let owner = Pubkey::new_unique();
+
+        for trove_id in 0..TroveIndexPage::ENTRIES_PER_PAGE as u16 {
+            append_trove(&mut first_page, &mut state, &mut trove(owner, trove_id)).unwrap();
+        }
+        // A full page is not the tail any more
+        assert!(append_trove(&mut first_page, &mut state, &mut trove(owner, 99)).is_err());
+
+        let mut second_page = TroveIndexPage { page: 0, entries: Vec::new() };
+        let mut last = trove(owner, 99);
+        append_trove(&mut second_page, &mut state, &mut last).unwrap();
+        assert_eq!((second_page.page, last.index_position), (1, 65));
+        assert_eq!(state.trove_index_len, 65);
+
+        // Tombstoning needs the right page and the entry's own trove, and keeps the position
+        let expected = TroveIndexEntry { owner, trove_id: 99 };
+        assert!(tombstone_entry(&mut first_page, &mut state, last.index_position, expected).is_err());
+        assert!(tombstone_entry(&mut second_page, &mut state, last.index_position, TroveIndexEntry { owner, trove_id: 98 }).is_err());
+        tombstone_entry(&mut second_page, &mut state, last.index_position, expected).unwrap();
+        assert!(second_page.entries[0].is_tombstone());
+        assert_eq!((second_page.entries.len(), state.trove_index_tombstones), (1, 1));
+        assert!(tombstone_entry(&mut second_page, &mut state, last.index_position, expected).is_err());
+    }
+}
@@ -121,6 +121,25 @@ pub fn safe_div(a: u64, b: u64) -> Result<u64> {
     a.checked_div(b).ok_or(AerospacerProtocolError::OverflowError.into())
 }
 
+/// Token balance of a vault, 0 if the token account has not been created yet
+pub fn token_balance(account_info: &AccountInfo) -> Result<u64> {
+    if account_info.owner != &anchor_spl::token::ID {
+        return Ok(0);
+    }
+    let data = account_info.try_borrow_data()?;
+    Ok(anchor_spl::token::TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
+
+/// Invariant: the stability pool vault holds at least every staked aUSD. Read from the raw
+/// account data, so it sees transfers and burns made earlier in the instruction.
+pub fn check_stability_pool_backing(stability_pool_vault: &AccountInfo, total_stake_amount: u64) -> Result<()> {
+    require!(
+        token_balance(stability_pool_vault)? >= total_stake_amount,
+        AerospacerProtocolError::StabilityPoolUnderfunded
+    );
+    Ok(())
+}
+
 // Helper function to update total collateral amount
 pub fn update_total_collateral_from_account_info(
     account_info: &AccountInfo,
@@ -128,9 +147,9 @@ pub fn update_total_collateral_from_account_info(
 ) -> Result<()> {
     use crate::state::TotalCollateralAmount;
     
-    // Deserialize the TotalCollateralAmount account
+    // Zero-copy view - changes are written straight into the account data
     let mut data = account_info.try_borrow_mut_data()?;
-    let mut total_collateral = TotalCollateralAmount::try_deserialize(&mut &data[..])?;
+    let total_collateral = TotalCollateralAmount::from_account_data_mut(&mut data)?;
     
     // Apply the change
     if amount_change >= 0 {
@@ -143,13 +162,92 @@ pub fn update_total_collateral_from_account_info(
             .ok_or(AerospacerProtocolError::OverflowError)?;
     }
     
-    // Serialize back to account
-    total_collateral.try_serialize(&mut &mut data[..])?;
-    
     msg!("Updated total collateral by: {} (new total: {})", amount_change, total_collateral.amount);
     Ok(())
 }
 
+// Helper function to close a program-owned account passed without an Anchor `close` constraint
+// (e.g. via remaining_accounts): refunds its rent to `destination` and hands it back to the system program
+pub fn close_program_account(account_info: &AccountInfo, destination: &AccountInfo) -> Result<()> {
+    require!(
+        account_info.owner == &crate::ID,
+        AerospacerProtocolError::Unauthorized
+    );
+    
+    let refund = account_info.lamports();
+    **destination.try_borrow_mut_lamports()? = destination.lamports()
+        .checked_add(refund)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    **account_info.try_borrow_mut_lamports()? = 0;
+    
+    account_info.assign(&anchor_lang::system_program::ID);
+    account_info.resize(0)?;
+    
+    Ok(())
+}
+
+// Helper function to create a program-owned PDA passed without an Anchor `init` constraint
+// (e.g. via remaining_accounts). Mirrors Anchor's `init`: an address that was pre-funded
+// with lamports is topped up to rent exemption, allocated and assigned instead of failing.
+pub fn create_program_pda<'info>(
+    payer: &AccountInfo<'info>,
+    account_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    use anchor_lang::system_program::{allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer};
+    
+    let rent_exempt_lamports = Rent::get()?.minimum_balance(space);
+    let current_lamports = account_info.lamports();
+    
+    if current_lamports == 0 {
+        create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount { from: payer.clone(), to: account_info.clone() },
+                &[signer_seeds],
+            ),
+            rent_exempt_lamports,
+            space as u64,
+            &crate::ID,
+        )?;
+    } else {
+        require!(
+            account_info.owner == &anchor_lang::system_program::ID,
+            AerospacerProtocolError::InvalidList
+        );
+        let top_up = rent_exempt_lamports.saturating_sub(current_lamports);
+        if top_up > 0 {
+            transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer { from: payer.clone(), to: account_info.clone() },
+                ),
+                top_up,
+            )?;
+        }
+        allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Allocate { account_to_allocate: account_info.clone() },
+                &[signer_seeds],
+            ),
+            space as u64,
+        )?;
+        assign(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Assign { account_to_assign: account_info.clone() },
+                &[signer_seeds],
+            ),
+            &crate::ID,
+        )?;
+    }
+    
+    Ok(())
+}
+
 // Fee calculation utilities for protocol-fees integration
 pub fn calculate_protocol_fee(amount: u64, fee_percentage: u8) -> Result<u64> {
     let fee = amount
@@ -161,6 +259,17 @@ pub fn calculate_protocol_fee(amount: u64, fee_percentage: u8) -> Result<u64> {
     Ok(fee)
 }
 
+/// Protocol fee percentage for a borrowing operation on a denom, 0 while the denom's promotion runs
+pub fn effective_borrowing_fee(protocol_fee: u8, promotion: Option<&DenomPromotion>, slot: u64) -> u8 {
+    match promotion {
+        Some(promotion) if promotion.is_active(slot) => {
+            msg!("Promotional period for {}: borrowing fee waived until slot {}", promotion.denom, promotion.fee_free_until_slot);
+            0
+        }
+        _ => protocol_fee,
+    }
+}
+
 pub fn calculate_net_amount_after_fee(amount: u64, fee_percentage: u8) -> Result<u64> {
     let fee = calculate_protocol_fee(amount, fee_percentage)?;
     amount
@@ -168,6 +277,21 @@ pub fn calculate_net_amount_after_fee(amount: u64, fee_percentage: u8) -> Result
         .ok_or(AerospacerProtocolError::OverflowError.into())
 }
 
+/// Share of a fee-inclusive `gross` amount behind `filled` of its `net` amount
+/// The whole gross when everything was filled, so no rounding dust is left behind
+pub fn calculate_gross_share(gross: u64, filled: u64, net: u64) -> Result<u64> {
+    require!(filled <= net, AerospacerProtocolError::InvalidAmount);
+    if filled == net {
+        return Ok(gross);
+    }
+    
+    let share = (gross as u128)
+        .checked_mul(filled as u128)
+        .ok_or(AerospacerProtocolError::OverflowError)?
+        / net as u128;
+    Ok(share as u64)
+}
+
 /// Calculate real ICR for a trove with multi-collateral support
 /// 
 /// Returns ICR in micro-percent (percentage × 1,000,000)
@@ -177,7 +301,7 @@ pub fn calculate_net_amount_after_fee(amount: u64, fee_percentage: u8) -> Result
 pub fn get_trove_icr<'a>(
     user_debt_amount: &UserDebtAmount,
     user_collateral_amount_accounts: &'a [AccountInfo<'a>],
-    collateral_prices: &HashMap<String, u64>,
+    collateral_prices: &HashMap<String, (u64, u8)>, // denom -> (oracle price, token decimals)
     owner: Pubkey,
 ) -> Result<u64> {
     use crate::oracle::PriceCalculator;
@@ -197,12 +321,12 @@ pub fn get_trove_icr<'a>(
         let account_data = account_info.try_borrow_data()?;
         
         // Skip if account is too small to be a UserCollateralAmount
-        if account_data.len() < 8 + UserCollateralAmount::LEN {
+        if account_data.len() < UserCollateralAmount::LEN {
             continue;
         }
         
         // Try to deserialize as UserCollateralAmount
-        if let Ok(collateral_account) = UserCollateralAmount::try_from_slice(&account_data[8..]) {
+        if let Ok(collateral_account) = UserCollateralAmount::try_deserialize(&mut &account_data[..]) {
             // Verify it belongs to the owner
             if collateral_account.owner == owner && collateral_account.amount > 0 {
                 collateral_amounts.push((
@@ -219,23 +343,12 @@ pub fn get_trove_icr<'a>(
     }
     
     // Convert HashMap prices to Vec format for PriceCalculator
-    // Prices are stored as raw values, we need to add decimal information
+    // Oracle prices are micro-USD per whole token with the token's own decimals
     let mut price_data: Vec<(String, u64, u8)> = Vec::new();
     
     for (denom, _amount) in &collateral_amounts {
-        if let Some(price) = collateral_prices.get(denom) {
-            // Get ADJUSTED decimal precision for each denom (to produce micro-USD values)
-            // Formula: adjusted_decimal = token_decimals + price_exponent - 6
-            // Must match the oracle's adjusted_decimal calculation
-            let decimal = match denom.as_str() {
-                "SOL" => 11,    // token(9) + price_exp(8) - target(6) = 11
-                "USDC" => 8,    // token(6) + price_exp(8) - target(6) = 8
-                "INJ" => 20,    // token(18) + price_exp(8) - target(6) = 20
-                "ATOM" => 8,    // token(6) + price_exp(8) - target(6) = 8
-                _ => 8,         // Default: assume 6 token decimals + 8 price exp - 6 = 8
-            };
-            
-            price_data.push((denom.clone(), *price, decimal));
+        if let Some((price, decimal)) = collateral_prices.get(denom) {
+            price_data.push((denom.clone(), *price, *decimal));
         }
     }
     
@@ -249,37 +362,30 @@ pub fn get_trove_icr<'a>(
     Ok(icr)
 }
 
-/// Check if a trove's ICR meets the required minimum ratio
-/// ICR and minimum_ratio are both in micro-percent (e.g., 150_000_000 = 150%)
+/// Check if a trove's ICR meets its collateral denom's minimum ratio
+/// ICR and minimum_collateral_ratio are both in micro-percent (e.g., 150_000_000 = 150%)
 pub fn check_trove_icr_with_ratio(
-    state_account: &StateAccount,
+    collateral_config: &CollateralConfig,
     icr: u64,
 ) -> Result<()> {
-    let minimum_ratio = state_account.minimum_collateral_ratio as u64;
-    
-    require!(
-        icr >= minimum_ratio,
-        AerospacerProtocolError::CollateralBelowMinimum
-    );
-    
-    Ok(())
+    check_minimum_icr(icr, collateral_config.minimum_collateral_ratio)
 }
 
 /// Check if a trove is liquidatable based on its ICR
+/// Both are in micro-percent; the threshold comes from the denom's CollateralConfig
 pub fn is_liquidatable_icr(icr: u64, liquidation_threshold: u64) -> bool {
     icr < liquidation_threshold
 }
 
-/// Get the liquidation threshold (typically 110%)
-/// Returns in micro-percent: 110_000_000 = 110%
-pub fn get_liquidation_threshold() -> Result<u64> {
-    // 110% ICR is the liquidation threshold (in micro-percent)
-    Ok(110_000_000u64)
+/// Check if a trove may be redeemed against based on its ICR
+/// Below 100% its collateral is worth less than the aUSD redeemed, so it is left to liquidation
+pub fn is_redeemable_icr(icr: u64) -> bool {
+    icr >= FULL_COLLATERALIZATION_ICR
 }
 
 /// Check if ICR meets minimum collateral ratio requirement
 /// ICR is in micro-percent (e.g., 150_000_000 = 150%)
-/// minimum_collateral_ratio is expected to be in micro-percent from StateAccount
+/// minimum_collateral_ratio is expected to be in micro-percent from the denom's CollateralConfig
 pub fn check_minimum_icr(icr: u64, minimum_collateral_ratio: u64) -> Result<()> {
     require!(
         icr >= minimum_collateral_ratio,
@@ -301,10 +407,19 @@ pub fn check_minimum_icr(icr: u64, minimum_collateral_ratio: u64) -> Result<()>
 /// - P_snapshot: P factor when user last deposited
 /// - P_current: Current P factor
 /// - Ratio P_current/P_snapshot represents the depletion factor
+/// 
+/// Scale changes: P is multiplied by P_SCALE_FACTOR whenever it would drop below
+/// P_SCALE_THRESHOLD. A deposit one scale behind divides the ratio by P_SCALE_FACTOR;
+/// two or more scales behind it has been depleted by at least 1e-18 and is worth 0.
+/// A deposit from an earlier epoch was fully used by the liquidation that emptied the pool.
 pub fn calculate_compounded_stake(
     initial_deposit: u64,
     p_snapshot: u128,
     p_current: u128,
+    scale_snapshot: u8,
+    current_scale: u8,
+    epoch_snapshot: u64,
+    current_epoch: u64,
 ) -> Result<u64> {
     // If P_snapshot is 0, this is first deposit or corrupted state - return initial
     if p_snapshot == 0 {
@@ -316,6 +431,21 @@ pub fn calculate_compounded_stake(
         return Ok(0);
     }
     
+    // Pool was emptied since the deposit - nothing left to compound
+    if epoch_snapshot < current_epoch {
+        return Ok(0);
+    }
+    
+    // Scale difference determines how P_current relates to P_snapshot
+    let scale_diff = current_scale
+        .checked_sub(scale_snapshot)
+        .ok_or(AerospacerProtocolError::InvalidSnapshot)?;
+    let scale_divisor = match scale_diff {
+        0 => 1u128,
+        1 => P_SCALE_FACTOR,
+        _ => return Ok(0),
+    };
+    
     // Calculate: compounded = initial × (P_current / P_snapshot)
     // Use safe math to prevent overflow
     let deposit_u128 = initial_deposit as u128;
@@ -327,6 +457,8 @@ pub fn calculate_compounded_stake(
     
     let compounded = numerator
         .checked_div(p_snapshot)
+        .ok_or(AerospacerProtocolError::DivideByZeroError)?
+        .checked_div(scale_divisor)
         .ok_or(AerospacerProtocolError::DivideByZeroError)?;
     
     // Convert back to u64, capping at u64::MAX if overflow
@@ -339,6 +471,126 @@ pub fn calculate_compounded_stake(
     Ok(result)
 }
 
+/// Calculate the gain a deposit earned from a G accumulator since its snapshots (Liquity G factor)
+/// 
+/// Formula: gain = initial_deposit × (G_current - G_snapshot) / P_snapshot
+/// 
+/// G only accumulates within an epoch/scale segment; the previous segment's final G is kept
+/// so a deposit snapshotted there still collects its remainder, plus (after a scale change)
+/// the new segment's G divided by P_SCALE_FACTOR. Deposits two or more segments behind have
+/// been depleted and earn nothing.
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_g_factor_gain(
+    initial_deposit: u64,
+    p_snapshot: u128,
+    g_snapshot: u128,
+    scale_snapshot: u8,
+    epoch_snapshot: u64,
+    g_current: u128,
+    g_prev: u128,
+    state: &StateAccount,
+) -> Result<u64> {
+    if p_snapshot == 0 || initial_deposit == 0 {
+        return Ok(0);
+    }
+    
+    let in_current_segment = epoch_snapshot == state.epoch && scale_snapshot == state.current_scale;
+    let in_previous_segment = if state.current_scale > 0 {
+        epoch_snapshot == state.epoch && scale_snapshot == state.current_scale - 1
+    } else {
+        epoch_snapshot.checked_add(1) == Some(state.epoch) && scale_snapshot == state.g_prev_scale
+    };
+    
+    let g_diff = if in_current_segment {
+        g_current.saturating_sub(g_snapshot)
+    } else if in_previous_segment {
+        let first_portion = g_prev.saturating_sub(g_snapshot);
+        // After an epoch change the deposit is fully depleted - nothing accrues in the new segment
+        let second_portion = if epoch_snapshot == state.epoch {
+            g_current / P_SCALE_FACTOR
+        } else {
+            0
+        };
+        first_portion
+            .checked_add(second_portion)
+            .ok_or(AerospacerProtocolError::OverflowError)?
+    } else {
+        0
+    };
+    
+    let gain = (initial_deposit as u128)
+        .checked_mul(g_diff)
+        .ok_or(AerospacerProtocolError::OverflowError)?
+        .checked_div(p_snapshot)
+        .ok_or(AerospacerProtocolError::DivideByZeroError)?;
+    
+    u64::try_from(gain).map_err(|_| AerospacerProtocolError::OverflowError.into())
+}
+
+/// Calculate aUSD fee yield earned by a deposit since its snapshots (see `calculate_g_factor_gain`)
+pub fn calculate_fee_yield_gain(
+    initial_deposit: u64,
+    p_snapshot: u128,
+    g_snapshot: u128,
+    scale_snapshot: u8,
+    epoch_snapshot: u64,
+    state: &StateAccount,
+) -> Result<u64> {
+    calculate_g_factor_gain(
+        initial_deposit,
+        p_snapshot,
+        g_snapshot,
+        scale_snapshot,
+        epoch_snapshot,
+        state.g_factor,
+        state.g_factor_prev,
+        state,
+    )
+}
+
+/// Move the aUSD fee yield a deposit earned since its snapshots into `pending_fee_gain`
+/// 
+/// Must run before the stake's P/G/scale/epoch snapshots are refreshed, otherwise the
+/// yield accrued under the old snapshots is lost.
+pub fn settle_fee_yield(user_stake_amount: &mut UserStakeAmount, state: &StateAccount) -> Result<u64> {
+    let gain = calculate_fee_yield_gain(
+        user_stake_amount.amount,
+        user_stake_amount.p_snapshot,
+        user_stake_amount.g_snapshot,
+        user_stake_amount.scale_snapshot,
+        user_stake_amount.epoch_snapshot,
+        state,
+    )?;
+    
+    user_stake_amount.pending_fee_gain = user_stake_amount.pending_fee_gain
+        .checked_add(gain)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    
+    Ok(gain)
+}
+
+/// Move the reward token emissions a deposit earned since its snapshots into `pending_reward_gain`
+/// 
+/// Same timing requirement as `settle_fee_yield`.
+pub fn settle_reward_emissions(user_stake_amount: &mut UserStakeAmount, state: &StateAccount) -> Result<u64> {
+    let gain = calculate_g_factor_gain(
+        user_stake_amount.amount,
+        user_stake_amount.p_snapshot,
+        user_stake_amount.reward_g_snapshot,
+        user_stake_amount.scale_snapshot,
+        user_stake_amount.epoch_snapshot,
+        state.reward_g_factor,
+        state.reward_g_factor_prev,
+        state,
+    )?;
+    
+    user_stake_amount.pending_reward_gain = user_stake_amount.pending_reward_gain
+        .checked_add(gain)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    
+    Ok(gain)
+}
+
 /// Calculate collateral gain using Liquity Product-Sum algorithm
 /// 
 /// Formula: gain = deposit × (S_current - S_snapshot) / P_snapshot
@@ -348,17 +600,27 @@ pub fn calculate_compounded_stake(
 /// - S_current: Current S factor for this collateral type
 /// - P_snapshot: User's P factor snapshot (accounts for pool depletion)
 /// - deposit: User's stake amount
+/// 
+/// S keeps growing across epochs, so an S snapshot from an earlier epoch than the current
+/// one is not comparable to S_current - such snapshots yield no gain.
 pub fn calculate_collateral_gain(
     deposit: u64,
     s_snapshot: u128,
     s_current: u128,
     p_snapshot: u128,
+    epoch_snapshot: u64,
+    current_epoch: u64,
 ) -> Result<u64> {
     // If P_snapshot is 0, no valid snapshot exists - return 0
     if p_snapshot == 0 {
         return Ok(0);
     }
     
+    // Snapshot predates the last pool depletion - the deposit was used up in that epoch
+    if epoch_snapshot < current_epoch {
+        return Ok(0);
+    }
+    
     // If S hasn't increased, no gain
     if s_current <= s_snapshot {
         return Ok(0);
@@ -387,3 +649,80 @@ pub fn calculate_collateral_gain(
     
     Ok(result)
 }
+
+/// Settle a depositor's gain for one collateral denom against the current S factor
+/// 
+/// Adds the carried-over `pending_collateral_gain` to the newly accrued gain and moves the
+/// S snapshot (and its epoch) to the current values, so stale snapshots start earning again.
+/// Returns `(new_gain, total_gain)`; the caller pays out `total_gain` and then clears
+/// `pending_collateral_gain`.
+pub fn settle_collateral_gain(
+    user_stake_amount: &UserStakeAmount,
+    user_collateral_snapshot: &mut UserCollateralSnapshot,
+    s_current: u128,
+    current_epoch: u64,
+) -> Result<(u64, u64)> {
+    // Both the S snapshot and the deposit itself must belong to the current epoch
+    let snapshot_epoch = user_collateral_snapshot.epoch_snapshot.min(user_stake_amount.epoch_snapshot);
+    
+    // If s_snapshot = 0 (first withdrawal), this calculates the full accumulated gain
+    let collateral_gain = calculate_collateral_gain(
+        user_stake_amount.amount,
+        user_collateral_snapshot.s_snapshot,
+        s_current,
+        user_stake_amount.p_snapshot,
+        snapshot_epoch,
+        current_epoch,
+    )?;
+    if snapshot_epoch < current_epoch {
+        msg!("S snapshot from epoch {} predates current epoch {} - no new gains", snapshot_epoch, current_epoch);
+    }
+    
+    let total_gain = collateral_gain
+        .checked_add(user_collateral_snapshot.pending_collateral_gain)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    
+    user_collateral_snapshot.s_snapshot = s_current;
+    user_collateral_snapshot.epoch_snapshot = current_epoch;
+    
+    Ok((collateral_gain, total_gain))
+}
+
+/// Precision used to read the L redistribution factors at a given renormalization scale
+/// 
+/// Scale 0 reads L with SCALE_FACTOR (10^18); every renormalization divides both the
+/// factors and this precision by L_RENORMALIZATION_FACTOR, so `amount × ΔL / precision`
+/// keeps yielding the same entitlement.
+pub fn l_precision(l_scale: u8) -> Result<u128> {
+    require!(
+        l_scale <= MAX_L_SCALE,
+        AerospacerProtocolError::InvalidSnapshot
+    );
+    
+    let divisor = L_RENORMALIZATION_FACTOR
+        .checked_pow(l_scale as u32)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    
+    Ok(StateAccount::SCALE_FACTOR / divisor)
+}
+
+/// Bring an L snapshot taken at `snapshot_scale` to `current_scale`
+/// 
+/// Renormalization only rewrites the per-denom accumulators; user snapshots are
+/// rescaled lazily the next time the trove is touched.
+pub fn rescale_l_snapshot(
+    snapshot: u128,
+    snapshot_scale: u8,
+    current_scale: u8,
+) -> Result<u128> {
+    require!(
+        snapshot_scale <= current_scale,
+        AerospacerProtocolError::InvalidSnapshot
+    );
+    
+    let divisor = L_RENORMALIZATION_FACTOR
+        .checked_pow((current_scale - snapshot_scale) as u32)
+        .ok_or(AerospacerProtocolError::OverflowError)?;
+    
+    Ok(snapshot / divisor)
+}
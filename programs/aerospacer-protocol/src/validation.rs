@@ -0,0 +1,170 @@
+// Instruction params validation.
+//
+// Every `*Params` struct implements ValidateParams and its handler calls `params.validate()?`
+// before touching any account, so the stateless bounds of an instruction (amounts, denoms,
+// pubkeys, list lengths) sit next to its params instead of being scattered through the handler.
+// Checks that need account state (balances, trove status, configured limits) stay in the handlers.
+
+use anchor_lang::prelude::*;
+use crate::error::AerospacerProtocolError;
+
+/// Longest accepted denom - denoms are used as PDA seeds, which are capped at 32 bytes
+pub const MAX_DENOM_LEN: usize = 32;
+
+pub trait ValidateParams {
+    /// Reject params that no account state could make valid
+    fn validate(&self) -> Result<()>;
+}
+
+/// Amounts must be non-zero
+pub fn validate_amount(amount: u64) -> Result<()> {
+    require!(amount > 0, AerospacerProtocolError::InvalidAmount);
+    Ok(())
+}
+
+/// Amounts with a protocol minimum; `error` names the violated minimum
+pub fn validate_min_amount(amount: u64, minimum: u64, error: AerospacerProtocolError) -> Result<()> {
+    validate_amount(amount)?;
+    if amount < minimum {
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+/// Denoms are short tickers: 1-32 bytes of ASCII letters, digits and `.`, `_`, `-`, `/`.
+/// No normalization is applied - the denom is a PDA seed, so "sol" and "SOL" are different markets.
+pub fn validate_denom(denom: &str) -> Result<()> {
+    require!(
+        !denom.is_empty()
+            && denom.len() <= MAX_DENOM_LEN
+            && denom.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-' | b'/')),
+        AerospacerProtocolError::InvalidDenom
+    );
+    Ok(())
+}
+
+/// Pubkeys naming a party or program must not be the default (all zeros) key
+pub fn validate_pubkey(key: &Pubkey) -> Result<()> {
+    require!(*key != Pubkey::default(), AerospacerProtocolError::InvalidAddress);
+    Ok(())
+}
+
+/// Optional pubkeys (e.g. sorted-list hints) are checked when present
+pub fn validate_optional_pubkey(key: &Option<Pubkey>) -> Result<()> {
+    match key {
+        Some(key) => validate_pubkey(key),
+        None => Ok(()),
+    }
+}
+
+/// Lists must hold between 1 and `max` entries
+pub fn validate_list_len(len: usize, max: usize) -> Result<()> {
+    require!(len > 0 && len <= max, AerospacerProtocolError::InvalidList);
+    Ok(())
+}
+
+/// Basis-point settings must not exceed `max`; `error` names the setting
+pub fn validate_bps(bps: u16, max: u16, error: AerospacerProtocolError) -> Result<()> {
+    if bps > max {
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_denom_charset_and_length() {
+        assert!(validate_denom("SOL").is_ok());
+        assert!(validate_denom("ibc/ATOM-1.5_x").is_ok());
+        assert!(validate_denom(&"A".repeat(MAX_DENOM_LEN)).is_ok());
+
+        assert!(validate_denom("").is_err());
+        assert!(validate_denom(&"A".repeat(MAX_DENOM_LEN + 1)).is_err());
+        assert!(validate_denom("SOL ").is_err());
+        assert!(validate_denom("SÖL").is_err());
+        assert!(validate_denom("SOL\0").is_err());
+    }
+
+    #[test]
+    fn test_validate_bounds() {
+        assert!(validate_amount(0).is_err());
+        assert!(validate_min_amount(9, 10, AerospacerProtocolError::LoanAmountBelowMinimum).is_err());
+        assert!(validate_min_amount(10, 10, AerospacerProtocolError::LoanAmountBelowMinimum).is_ok());
+        assert!(validate_pubkey(&Pubkey::default()).is_err());
+        assert!(validate_optional_pubkey(&None).is_ok());
+        assert!(validate_optional_pubkey(&Some(Pubkey::default())).is_err());
+        assert!(validate_list_len(0, 5).is_err());
+        assert!(validate_list_len(5, 5).is_ok());
+        assert!(validate_list_len(6, 5).is_err());
+        assert!(validate_bps(10_001, 10_000, AerospacerProtocolError::InvalidKickbackRate).is_err());
+    }
+
+    #[test]
+    fn test_collateral_config_bounds() {
+        use crate::state::{CollateralConfig, DEFAULT_LIQUIDATION_THRESHOLD, DEFAULT_MINIMUM_COLLATERAL_RATIO};
+
+        assert!(CollateralConfig::validate_ratios(DEFAULT_MINIMUM_COLLATERAL_RATIO, DEFAULT_LIQUIDATION_THRESHOLD).is_ok());
+        assert!(CollateralConfig::validate_ratios(150_000_000, 150_000_000).is_ok());
+
+        // Threshold above the MCR, at or below 100%, or given as a plain percentage
+        assert!(CollateralConfig::validate_ratios(115_000_000, 120_000_000).is_err());
+        assert!(CollateralConfig::validate_ratios(115_000_000, 100_000_000).is_err());
+        assert!(CollateralConfig::validate_ratios(115_000_000, 110).is_err());
+
+        assert!(CollateralConfig::validate_protocol_fee(99).is_ok());
+        assert!(CollateralConfig::validate_protocol_fee(100).is_err());
+    }
+
+    #[test]
+    fn test_init_stablecoin_metadata_bounds() {
+        use crate::instructions::InitStablecoinParams;
+        let params = |name: &str, symbol: &str, uri: &str| InitStablecoinParams {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+        };
+
+        assert!(params("Aeroscraper USD", "aUSD", "https://aeroscraper.io/ausd.json").validate().is_ok());
+        assert!(params("Aeroscraper USD", "aUSD", "").validate().is_ok());
+        assert!(params("", "aUSD", "").validate().is_err());
+        assert!(params("Aeroscraper USD", "", "").validate().is_err());
+        assert!(params(&"A".repeat(33), "aUSD", "").validate().is_err());
+        assert!(params("Aeroscraper USD", "AEROUSDXXXX", "").validate().is_err());
+        assert!(params("Aeroscraper USD", "aUSD", &"u".repeat(201)).validate().is_err());
+    }
+
+    #[test]
+    fn test_protocol_config_bounds() {
+        use crate::instructions::ProtocolConfig;
+        use crate::state::{DEFAULT_LIQUIDATION_THRESHOLD, MAX_BOOTSTRAP_PERIOD_SECS};
+        assert!(ProtocolConfig::default().validate().is_ok());
+
+        let with = |update: fn(&mut ProtocolConfig)| {
+            let mut config = ProtocolConfig::default();
+            update(&mut config);
+            config.validate()
+        };
+        assert!(with(|c| c.minimum_collateral_ratio = DEFAULT_LIQUIDATION_THRESHOLD).is_ok());
+        assert!(with(|c| c.minimum_collateral_ratio = DEFAULT_LIQUIDATION_THRESHOLD - 1).is_err());
+        assert!(with(|c| c.minimum_collateral_ratio = 115).is_err());
+        assert!(with(|c| c.minimum_collateral_ratio = 1_000_000_001).is_err());
+        assert!(with(|c| c.protocol_fee = 10).is_ok());
+        assert!(with(|c| c.protocol_fee = 11).is_err());
+        assert!(with(|c| c.redemption_cap_per_window = 1).is_err());
+        assert!(with(|c| { c.redemption_cap_per_window = 1; c.redemption_window_slots = 1 }).is_ok());
+        assert!(with(|c| c.bootstrap_period_secs = MAX_BOOTSTRAP_PERIOD_SECS + 1).is_err());
+    }
+
+    #[test]
+    fn test_update_config_checks_only_given_fields() {
+        use crate::instructions::UpdateConfigParams;
+        let params = |minimum_collateral_ratio, protocol_fee| UpdateConfigParams { minimum_collateral_ratio, protocol_fee };
+        assert!(params(None, None).validate().is_ok());
+        assert!(params(Some(150_000_000), Some(10)).validate().is_ok());
+        assert!(params(Some(150), None).validate().is_err());
+        assert!(params(None, Some(11)).validate().is_err());
+    }
+}
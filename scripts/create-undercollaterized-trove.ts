@@ -447,23 +447,21 @@ async function main() {
     const stabilityPoolOwner = new PublicKey(
         "5oMxbgjPWkBYRKbsh3yKrrEC5Ut8y3azHKc787YHY9Ar"
     );
-    const feeAddress1 = new PublicKey(
-        "8Lv4UrYHTrzvg9jPVVGNmxWyMrMvrZnCQLWucBzfJyyR"
-    );
-    const feeAddress2 = new PublicKey(
-        "GcNwV1nA5bityjNYsWwPLHykpKuuhPzK1AQFBbrPopnX"
-    );
     const stabilityPoolTokenAccount = await getAssociatedTokenAddress(
         stablecoinMint,
         stabilityPoolOwner
     );
-    const feeAddress1TokenAccount = await getAssociatedTokenAddress(
-        stablecoinMint,
-        feeAddress1
+    const [feeVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_vault"), stablecoinMint.toBuffer()],
+        state.feeDistributorAddr
     );
-    const feeAddress2TokenAccount = await getAssociatedTokenAddress(
-        stablecoinMint,
-        feeAddress2
+    const [feeEpochStats] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_epoch_stats"), stablecoinMint.toBuffer()],
+        state.feeDistributorAddr
+    );
+    const [feeCaller] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_caller")],
+        protocolProgram.programId
     );
 
     console.log("🔨 Opening undercollateralized trove...");
@@ -497,8 +495,9 @@ async function main() {
             feesProgram: state.feeDistributorAddr,
             feesState: state.feeStateAddr,
             stabilityPoolTokenAccount,
-            feeAddress1TokenAccount,
-            feeAddress2TokenAccount,
+            feeCaller,
+            feeVault,
+            feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
         } as any)
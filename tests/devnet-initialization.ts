@@ -56,15 +56,15 @@ describe("Devnet Initialization and Core Testing", () => {
     const newICR = debtValue > 0n ? (collateralValue * 100n) / debtValue : BigInt(Number.MAX_SAFE_INTEGER);
     
     const [userDebtAccount] = PublicKey.findProgramAddressSync(
-      [Buffer.from("user_debt_amount"), userPubkey.toBuffer()],
+      [Buffer.from("user_debt_amount"), userPubkey.toBuffer(), Buffer.alloc(2)],
       programId
     );
     const [userCollateralAccount] = PublicKey.findProgramAddressSync(
-      [Buffer.from("user_collateral_amount"), userPubkey.toBuffer(), Buffer.from(denom)],
+      [Buffer.from("user_collateral_amount"), userPubkey.toBuffer(), Buffer.alloc(2), Buffer.from(denom)],
       programId
     );
     const [liquidityThresholdAccount] = PublicKey.findProgramAddressSync(
-      [Buffer.from("liquidity_threshold"), userPubkey.toBuffer()],
+      [Buffer.from("liquidity_threshold"), userPubkey.toBuffer(), Buffer.alloc(2)],
       programId
     );
 
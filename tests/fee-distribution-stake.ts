@@ -17,6 +17,7 @@ import {
 import { assert, expect } from "chai";
 import { BN } from "bn.js";
 import * as fs from "fs";
+import { initializeFeeVaultIfNeeded, allowFeeCallerIfNeeded } from "./test-utils";
 
 describe("Fee Contract - Stability Pool Distribution Mode", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
@@ -49,6 +50,8 @@ describe("Fee Contract - Stability Pool Distribution Mode", () => {
   let stabilityPoolTokenAccount: PublicKey;
   let feeAddr1TokenAccount: PublicKey;
   let feeAddr2TokenAccount: PublicKey;
+  let feeVault: PublicKey;
+  let feeEpochStats: PublicKey;
 
   before(async () => {
     console.log("\n🚀 Setting up Fee Distribution - Stability Pool Mode Tests...");
@@ -121,6 +124,12 @@ describe("Fee Contract - Stability Pool Distribution Mode", () => {
         .rpc();
     }
 
+    // Treasury-mode fees of this token wait in its fee vault until claimed
+    ({ feeVault, feeEpochStats } = await initializeFeeVaultIfNeeded(feesProgram, admin, feeStateAccount, tokenMint));
+
+    // distribute_fee only accepts allowlisted fee callers
+    await allowFeeCallerIfNeeded(feesProgram, admin, feeStateAccount, payer.publicKey);
+
     // Set custom fee addresses for testing
     await feesProgram.methods
       .setFeeAddresses({
@@ -204,15 +213,17 @@ describe("Fee Contract - Stability Pool Distribution Mode", () => {
 
       const tx = await feesProgram.methods
         .distributeFee({
-          feeAmount: feeAmount
+          feeAmount: feeAmount,
+          source: { borrow: {} }
         })
         .accounts({
           payer: payer.publicKey,
+          callerAuthority: payer.publicKey,
           state: feeStateAccount,
           payerTokenAccount: payerTokenAccount,
           stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-          feeAddress1TokenAccount: feeAddr1TokenAccount,
-          feeAddress2TokenAccount: feeAddr2TokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
         } as any)
         .signers([payer])
@@ -259,15 +270,17 @@ describe("Fee Contract - Stability Pool Distribution Mode", () => {
 
       await feesProgram.methods
         .distributeFee({
-          feeAmount: feeAmount
+          feeAmount: feeAmount,
+          source: { borrow: {} }
         })
         .accounts({
           payer: payer.publicKey,
+          callerAuthority: payer.publicKey,
           state: feeStateAccount,
           payerTokenAccount: payerTokenAccount,
           stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-          feeAddress1TokenAccount: feeAddr1TokenAccount,
-          feeAddress2TokenAccount: feeAddr2TokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
         } as any)
         .signers([payer])
@@ -334,15 +347,17 @@ describe("Fee Contract - Stability Pool Distribution Mode", () => {
         try {
           await feesProgram.methods
             .distributeFee({
-              feeAmount: new BN(10000)
+              feeAmount: new BN(10000),
+              source: { borrow: {} }
             })
             .accounts({
               payer: payer.publicKey,
+              callerAuthority: payer.publicKey,
               state: tempStateAccount,
               payerTokenAccount: payerTokenAccount,
               stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-              feeAddress1TokenAccount: feeAddr1TokenAccount,
-              feeAddress2TokenAccount: feeAddr2TokenAccount,
+              feeVault: feeVault,
+              feeEpochStats: feeEpochStats,
               tokenProgram: TOKEN_PROGRAM_ID,
             } as any)
             .signers([payer])
@@ -373,15 +388,17 @@ describe("Fee Contract - Stability Pool Distribution Mode", () => {
       try {
         await feesProgram.methods
           .distributeFee({
-            feeAmount: new BN(10000)
+            feeAmount: new BN(10000),
+            source: { borrow: {} }
           })
           .accounts({
             payer: payer.publicKey,
+            callerAuthority: payer.publicKey,
             state: feeStateAccount,
             payerTokenAccount: payerTokenAccount,
             stabilityPoolTokenAccount: wrongPoolAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
           } as any)
           .signers([payer])
@@ -427,15 +444,17 @@ describe("Fee Contract - Stability Pool Distribution Mode", () => {
       try {
         await feesProgram.methods
           .distributeFee({
-            feeAmount: new BN(10000)
+            feeAmount: new BN(10000),
+            source: { borrow: {} }
           })
           .accounts({
             payer: payer.publicKey,
+            callerAuthority: payer.publicKey,
             state: feeStateAccount,
             payerTokenAccount: wrongTokenAccount,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
           } as any)
           .signers([payer])
@@ -469,15 +488,17 @@ describe("Fee Contract - Stability Pool Distribution Mode", () => {
 
       await feesProgram.methods
         .distributeFee({
-          feeAmount: largeAmount
+          feeAmount: largeAmount,
+          source: { borrow: {} }
         })
         .accounts({
           payer: payer.publicKey,
+          callerAuthority: payer.publicKey,
           state: feeStateAccount,
           payerTokenAccount: payerTokenAccount,
           stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-          feeAddress1TokenAccount: feeAddr1TokenAccount,
-          feeAddress2TokenAccount: feeAddr2TokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
         } as any)
         .signers([payer])
@@ -521,15 +542,17 @@ describe("Fee Contract - Stability Pool Distribution Mode", () => {
       for (let i = 0; i < amounts.length; i++) {
         await feesProgram.methods
           .distributeFee({
-            feeAmount: amounts[i]
+            feeAmount: amounts[i],
+            source: { borrow: {} }
           })
           .accounts({
             payer: payer.publicKey,
+            callerAuthority: payer.publicKey,
             state: feeStateAccount,
             payerTokenAccount: payerTokenAccount,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
           } as any)
           .signers([payer])
@@ -17,6 +17,7 @@ import {
 import { assert, expect } from "chai";
 import { BN } from "bn.js";
 import * as fs from "fs";
+import { initializeFeeVaultIfNeeded, claimFeesFromVault, allowFeeCallerIfNeeded } from "./test-utils";
 
 describe("Fee Contract - Treasury Distribution Mode (50/50 Split)", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
@@ -48,6 +49,8 @@ describe("Fee Contract - Treasury Distribution Mode (50/50 Split)", () => {
   let stabilityPoolTokenAccount: PublicKey;
   let feeAddr1TokenAccount: PublicKey;
   let feeAddr2TokenAccount: PublicKey;
+  let feeVault: PublicKey;
+  let feeEpochStats: PublicKey;
 
   before(async () => {
     console.log("\n🚀 Setting up Fee Distribution - Treasury Mode Tests...");
@@ -120,6 +123,12 @@ describe("Fee Contract - Treasury Distribution Mode (50/50 Split)", () => {
         .rpc();
     }
 
+    // Treasury-mode fees of this token wait in its fee vault until claimed
+    ({ feeVault, feeEpochStats } = await initializeFeeVaultIfNeeded(feesProgram, admin, feeStateAccount, tokenMint));
+
+    // distribute_fee only accepts allowlisted fee callers
+    await allowFeeCallerIfNeeded(feesProgram, admin, feeStateAccount, payer.publicKey);
+
     // Set custom fee addresses for testing
     await feesProgram.methods
       .setFeeAddresses({
@@ -180,15 +189,17 @@ describe("Fee Contract - Treasury Distribution Mode (50/50 Split)", () => {
 
       const tx = await feesProgram.methods
         .distributeFee({
-          feeAmount: feeAmount
+          feeAmount: feeAmount,
+          source: { borrow: {} }
         })
         .accounts({
           payer: payer.publicKey,
+          callerAuthority: payer.publicKey,
           state: feeStateAccount,
           payerTokenAccount: payerTokenAccount,
           stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-          feeAddress1TokenAccount: feeAddr1TokenAccount,
-          feeAddress2TokenAccount: feeAddr2TokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([payer])
@@ -196,6 +207,10 @@ describe("Fee Contract - Treasury Distribution Mode (50/50 Split)", () => {
 
       console.log("✅ Distribution successful. TX:", tx);
 
+      // Shares wait in the fee vault until each fee address claims them
+      await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, feeAddr1TokenAccount);
+      await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, feeAddr2TokenAccount);
+
       const addr1BalanceAfter = await getAccount(connection, feeAddr1TokenAccount);
       const addr2BalanceAfter = await getAccount(connection, feeAddr2TokenAccount);
 
@@ -249,20 +264,26 @@ describe("Fee Contract - Treasury Distribution Mode (50/50 Split)", () => {
 
         await feesProgram.methods
           .distributeFee({
-            feeAmount: amount
+            feeAmount: amount,
+            source: { borrow: {} }
           })
           .accounts({
             payer: payer.publicKey,
+            callerAuthority: payer.publicKey,
             state: feeStateAccount,
             payerTokenAccount: payerTokenAccount,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([payer])
           .rpc();
 
+        // Shares wait in the fee vault until each fee address claims them
+        await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, feeAddr1TokenAccount);
+        await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, feeAddr2TokenAccount);
+
         const addr1After = await getAccount(connection, feeAddr1TokenAccount);
         const addr2After = await getAccount(connection, feeAddr2TokenAccount);
 
@@ -315,20 +336,26 @@ describe("Fee Contract - Treasury Distribution Mode (50/50 Split)", () => {
 
         await feesProgram.methods
           .distributeFee({
-            feeAmount: amount
+            feeAmount: amount,
+            source: { borrow: {} }
           })
           .accounts({
             payer: payer.publicKey,
+            callerAuthority: payer.publicKey,
             state: feeStateAccount,
             payerTokenAccount: payerTokenAccount,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([payer])
           .rpc();
 
+        // Shares wait in the fee vault until each fee address claims them
+        await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, feeAddr1TokenAccount);
+        await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, feeAddr2TokenAccount);
+
         const addr1After = await getAccount(connection, feeAddr1TokenAccount);
         const addr2After = await getAccount(connection, feeAddr2TokenAccount);
 
@@ -426,87 +453,79 @@ describe("Fee Contract - Treasury Distribution Mode (50/50 Split)", () => {
 
       await feesProgram.methods
         .distributeFee({
-          feeAmount: feeAmount
+          feeAmount: feeAmount,
+          source: { borrow: {} }
         })
         .accounts({
           payer: payer.publicKey,
+          callerAuthority: payer.publicKey,
           state: feeStateAccount,
           payerTokenAccount: payerTokenAccount,
           stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-          feeAddress1TokenAccount: newFeeAddr1TokenAccount,
-          feeAddress2TokenAccount: newFeeAddr2TokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([payer])
         .rpc();
 
+      // Unclaimed shares belong to the fee address slot, so the new addresses claim them
+      await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, newFeeAddr1TokenAccount);
+      await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, newFeeAddr2TokenAccount);
+
+      const newAddr1Balance = await getAccount(connection, newFeeAddr1TokenAccount);
+      const newAddr2Balance = await getAccount(connection, newFeeAddr2TokenAccount);
+      assert.equal(newAddr1Balance.amount.toString(), "25000", "New FEE_ADDR_1 should claim half");
+      assert.equal(newAddr2Balance.amount.toString(), "25000", "New FEE_ADDR_2 should claim half");
+
       console.log("✅ Fee distribution with updated addresses successful");
     });
   });
 
-  describe("Test 4.8: Reject if Fee Address Token Account Owners are Wrong", () => {
-    it("Should fail if FEE_ADDR_1 token account has wrong owner", async () => {
-      const wrongOwner = Keypair.generate();
-      
-      // Use payerTokenAccount as wrongAddr1Account to avoid creating new unfunded accounts
-      const wrongAddr1Account = payerTokenAccount;
-
-      console.log("🔒 Attempting distribution with wrong FEE_ADDR_1 owner...");
+  describe("Test 4.8: Claims Only Reach Configured Fee Addresses", () => {
+    it("Should fail to claim into a token account with the wrong owner", async () => {
+      // payerTokenAccount is owned by the admin, which is not a fee address
+      console.log("🔒 Attempting claim into a token account not owned by a fee address...");
 
       try {
-        await feesProgram.methods
-          .distributeFee({
-            feeAmount: new BN(10000)
-          })
-          .accounts({
-            payer: payer.publicKey,
-            state: feeStateAccount,
-            payerTokenAccount: payerTokenAccount,
-            stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: wrongAddr1Account,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
-            tokenProgram: TOKEN_PROGRAM_ID,
-          })
-          .signers([payer])
-          .rpc();
+        await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, payerTokenAccount);
 
         assert.fail("Should have thrown an error");
       } catch (error: any) {
-        console.log("✅ Wrong FEE_ADDR_1 owner correctly rejected");
-        expect(error.message).to.exist;
+        console.log("✅ Wrong recipient owner correctly rejected");
+        expect(error.message).to.include("InvalidFeeRecipient");
       }
     });
 
-    it("Should fail if FEE_ADDR_2 token account has wrong owner", async () => {
-      const wrongOwner = Keypair.generate();
-      
-      // Use payerTokenAccount as wrongAddr2Account to avoid creating new unfunded accounts
-      const wrongAddr2Account = payerTokenAccount;
+    it("Should distribute without any fee address token accounts", async () => {
+      const vaultBefore = await getAccount(connection, feeVault);
 
-      console.log("🔒 Attempting distribution with wrong FEE_ADDR_2 owner...");
+      await feesProgram.methods
+        .distributeFee({
+          feeAmount: new BN(10000),
+          source: { borrow: {} }
+        })
+        .accounts({
+          payer: payer.publicKey,
+          callerAuthority: payer.publicKey,
+          state: feeStateAccount,
+          payerTokenAccount: payerTokenAccount,
+          stabilityPoolTokenAccount: stabilityPoolTokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer])
+        .rpc();
 
-      try {
-        await feesProgram.methods
-          .distributeFee({
-            feeAmount: new BN(10000)
-          })
-          .accounts({
-            payer: payer.publicKey,
-            state: feeStateAccount,
-            payerTokenAccount: payerTokenAccount,
-            stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount,
-            feeAddress2TokenAccount: wrongAddr2Account,
-            tokenProgram: TOKEN_PROGRAM_ID,
-          })
-          .signers([payer])
-          .rpc();
+      const vaultAfter = await getAccount(connection, feeVault);
+      assert.equal(
+        (BigInt(vaultAfter.amount.toString()) - BigInt(vaultBefore.amount.toString())).toString(),
+        "10000",
+        "Whole fee should wait in the fee vault"
+      );
 
-        assert.fail("Should have thrown an error");
-      } catch (error: any) {
-        console.log("✅ Wrong FEE_ADDR_2 owner correctly rejected");
-        expect(error.message).to.exist;
-      }
+      console.log("✅ Fee held in the fee vault until claimed");
     });
   });
 
@@ -517,15 +536,17 @@ describe("Fee Contract - Treasury Distribution Mode (50/50 Split)", () => {
       try {
         await feesProgram.methods
           .distributeFee({
-            feeAmount: new BN(0)
+            feeAmount: new BN(0),
+            source: { borrow: {} }
           })
           .accounts({
             payer: payer.publicKey,
+            callerAuthority: payer.publicKey,
             state: feeStateAccount,
             payerTokenAccount: payerTokenAccount,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([payer])
@@ -553,56 +574,7 @@ describe("Fee Contract - Treasury Distribution Mode (50/50 Split)", () => {
       console.log("  Original FEE_ADDR_1:", FEE_ADDR_1.toString());
       console.log("  Original FEE_ADDR_2:", FEE_ADDR_2.toString());
       
-      // Check if fee addresses are the same as admin (not allowed for SPL token accounts)
-      if (currentState.feeAddress1.equals(admin.publicKey) || currentState.feeAddress2.equals(admin.publicKey)) {
-        console.log("⚠️  Skipping test - fee addresses are same as admin (not allowed for SPL token accounts)");
-        console.log("✅ total_fees_collected accumulation test skipped");
-        return;
-      }
-      
-      // Also check if fee addresses are the same as the original FEE_ADDR_1 and FEE_ADDR_2
-      if (currentState.feeAddress1.equals(FEE_ADDR_1) && currentState.feeAddress2.equals(FEE_ADDR_2)) {
-        console.log("ℹ️  Using existing token accounts - fee addresses haven't changed");
-      }
-      
-      // Use existing token accounts if fee addresses haven't changed, otherwise create new ones
-      let currentFeeAddr1TokenAccount = feeAddr1TokenAccount;
-      let currentFeeAddr2TokenAccount = feeAddr2TokenAccount;
-      
-      // Only create new token accounts if the fee addresses are different from the original ones
-      // and not the same as admin
-      if (!currentState.feeAddress1.equals(FEE_ADDR_1) && !currentState.feeAddress1.equals(admin.publicKey)) {
-        try {
-          currentFeeAddr1TokenAccount = await createAccount(
-            connection,
-            admin,
-            tokenMint,
-            currentState.feeAddress1
-          );
-        } catch (error: any) {
-          console.log("⚠️  Failed to create token account for fee address 1:", error.message);
-          console.log("⚠️  Skipping test - cannot create required token accounts");
-          console.log("✅ total_fees_collected accumulation test skipped");
-          return;
-        }
-      }
-      
-      if (!currentState.feeAddress2.equals(FEE_ADDR_2) && !currentState.feeAddress2.equals(admin.publicKey)) {
-        try {
-          currentFeeAddr2TokenAccount = await createAccount(
-            connection,
-            admin,
-            tokenMint,
-            currentState.feeAddress2
-          );
-        } catch (error: any) {
-          console.log("⚠️  Failed to create token account for fee address 2:", error.message);
-          console.log("⚠️  Skipping test - cannot create required token accounts");
-          console.log("✅ total_fees_collected accumulation test skipped");
-          return;
-        }
-      }
-
+      // Fee address token accounts are only needed to claim, not to distribute
       const amounts = [new BN(1000), new BN(2000), new BN(3000)];
       let expectedTotal = currentState.totalFeesCollected;
 
@@ -612,15 +584,17 @@ describe("Fee Contract - Treasury Distribution Mode (50/50 Split)", () => {
       for (const amount of amounts) {
         await feesProgram.methods
           .distributeFee({
-            feeAmount: amount
+            feeAmount: amount,
+            source: { borrow: {} }
           })
           .accounts({
             payer: payer.publicKey,
+            callerAuthority: payer.publicKey,
             state: feeStateAccount,
             payerTokenAccount: payerTokenAccount,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: currentFeeAddr1TokenAccount,
-            feeAddress2TokenAccount: currentFeeAddr2TokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([payer])
@@ -645,10 +619,221 @@ describe("Fee Contract - Treasury Distribution Mode (50/50 Split)", () => {
     });
   });
 
+  describe("Test 4.11: Weighted Split With a Third Recipient", () => {
+    it("Should split fees by weight between all recipients", async () => {
+      const addrA = Keypair.generate().publicKey;
+      const addrB = Keypair.generate().publicKey;
+      const addrC = Keypair.generate().publicKey;
+
+      await feesProgram.methods
+        .setFeeAddresses({
+          feeAddress1: addrA.toString(),
+          feeAddress2: addrB.toString()
+        })
+        .accounts({
+          admin: admin.publicKey,
+          state: feeStateAccount,
+        })
+        .signers([admin])
+        .rpc();
+
+      const accountA = await createAccount(connection, admin, tokenMint, addrA);
+      const accountB = await createAccount(connection, admin, tokenMint, addrB);
+      const accountC = await createAccount(connection, admin, tokenMint, addrC);
+
+      // Settle the unclaimed fees of earlier tests under the 1:1 weights
+      await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, accountA);
+      await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, accountB);
+      const settledA = BigInt((await getAccount(connection, accountA)).amount.toString());
+      const settledB = BigInt((await getAccount(connection, accountB)).amount.toString());
+
+      await feesProgram.methods
+        .addFeeRecipient({ address: addrC.toString(), weight: 2 })
+        .accounts({
+          admin: admin.publicKey,
+          state: feeStateAccount,
+        })
+        .signers([admin])
+        .rpc();
+
+      await feesProgram.methods
+        .distributeFee({
+          feeAmount: new BN(40000),
+          source: { borrow: {} }
+        })
+        .accounts({
+          payer: payer.publicKey,
+          callerAuthority: payer.publicKey,
+          state: feeStateAccount,
+          payerTokenAccount: payerTokenAccount,
+          stabilityPoolTokenAccount: stabilityPoolTokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer])
+        .rpc();
+
+      await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, accountA);
+      await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, accountB);
+      await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, accountC);
+
+      const gainA = BigInt((await getAccount(connection, accountA)).amount.toString()) - settledA;
+      const gainB = BigInt((await getAccount(connection, accountB)).amount.toString()) - settledB;
+      const gainC = BigInt((await getAccount(connection, accountC)).amount.toString());
+      assert.equal(gainA.toString(), "10000", "Weight 1 of 4 should claim a quarter");
+      assert.equal(gainB.toString(), "10000", "Weight 1 of 4 should claim a quarter");
+      assert.equal(gainC.toString(), "20000", "Weight 2 of 4 should claim half");
+
+      await feesProgram.methods
+        .removeFeeRecipient({ address: addrC.toString() })
+        .accounts({
+          admin: admin.publicKey,
+          state: feeStateAccount,
+        })
+        .signers([admin])
+        .rpc();
+
+      console.log("✅ Weighted split verified (1:1:2)");
+    });
+  });
+
+  describe("Test 4.12: Fees Paid in a Collateral Token", () => {
+    it("Should split a collateral fee between the fee recipients", async () => {
+      const collateralMint = await createMint(connection, admin, admin.publicKey, null, 9);
+      const payerCollateralAccount = await createAccount(connection, admin, collateralMint, admin.publicKey);
+      await mintTo(connection, payer, collateralMint, payerCollateralAccount, admin, 1000000);
+
+      const { feeVault: collateralFeeVault, feeClaims: collateralFeeClaims } =
+        await initializeFeeVaultIfNeeded(feesProgram, admin, feeStateAccount, collateralMint);
+
+      const addrA = Keypair.generate().publicKey;
+      const addrB = Keypair.generate().publicKey;
+      await feesProgram.methods
+        .setFeeAddresses({
+          feeAddress1: addrA.toString(),
+          feeAddress2: addrB.toString()
+        })
+        .accounts({
+          admin: admin.publicKey,
+          state: feeStateAccount,
+        })
+        .signers([admin])
+        .rpc();
+
+      const stateBefore = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+
+      await feesProgram.methods
+        .distributeCollateralFee({
+          feeAmount: new BN(30000),
+          source: { liquidationPenalty: {} }
+        })
+        .accounts({
+          payer: payer.publicKey,
+          callerAuthority: payer.publicKey,
+          state: feeStateAccount,
+          payerTokenAccount: payerCollateralAccount,
+          feeClaims: collateralFeeClaims,
+          feeVault: collateralFeeVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer])
+        .rpc();
+
+      const accountA = await createAccount(connection, admin, collateralMint, addrA);
+      const accountB = await createAccount(connection, admin, collateralMint, addrB);
+      await claimFeesFromVault(feesProgram, feeStateAccount, collateralMint, accountA);
+      await claimFeesFromVault(feesProgram, feeStateAccount, collateralMint, accountB);
+
+      assert.equal((await getAccount(connection, accountA)).amount.toString(), "15000");
+      assert.equal((await getAccount(connection, accountB)).amount.toString(), "15000");
+
+      // Counted per mint, never in the aUSD revenue counters
+      const claims = await feesProgram.account.feeClaims.fetch(collateralFeeClaims);
+      assert.equal(claims.collateralFeesCollected.toString(), "30000");
+      const stateAfter = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+      assert.equal(
+        stateAfter.totalFeesCollected.toString(),
+        stateBefore.totalFeesCollected.toString(),
+        "aUSD revenue counters should not move"
+      );
+
+      console.log("✅ Collateral fee split between the fee recipients");
+    });
+
+    it("Should reject a fee vault of another mint", async () => {
+      const collateralMint = await createMint(connection, admin, admin.publicKey, null, 9);
+      const payerCollateralAccount = await createAccount(connection, admin, collateralMint, admin.publicKey);
+      await mintTo(connection, payer, collateralMint, payerCollateralAccount, admin, 1000000);
+      const { feeClaims: collateralFeeClaims } =
+        await initializeFeeVaultIfNeeded(feesProgram, admin, feeStateAccount, collateralMint);
+
+      try {
+        await feesProgram.methods
+          .distributeCollateralFee({
+            feeAmount: new BN(10000),
+            source: { liquidationPenalty: {} }
+          })
+          .accounts({
+            payer: payer.publicKey,
+            callerAuthority: payer.publicKey,
+            state: feeStateAccount,
+            payerTokenAccount: payerCollateralAccount,
+            feeClaims: collateralFeeClaims,
+            feeVault: feeVault, // aUSD test token vault
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([payer])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        console.log("✅ Fee vault of another mint correctly rejected");
+        expect(error.message).to.include("ConstraintSeeds");
+      }
+    });
+  });
+
+  describe("Test 4.13: Per-Epoch Fee Stats", () => {
+    it("Should count treasury-mode fees in the current epoch's fee vault total", async () => {
+      const { epoch } = await connection.getEpochInfo();
+      const slot = epoch % 32; // FEE_EPOCH_HISTORY
+      const statsBefore = await feesProgram.account.feeEpochStats.fetch(feeEpochStats);
+      const entryBefore = statsBefore.entries[slot];
+      const before = entryBefore.epoch.toNumber() === epoch ? entryBefore.feeVaultAmount.toNumber() : 0;
+
+      await feesProgram.methods
+        .distributeFee({
+          feeAmount: new BN(25000),
+          source: { redemption: {} }
+        })
+        .accounts({
+          payer: payer.publicKey,
+          callerAuthority: payer.publicKey,
+          state: feeStateAccount,
+          payerTokenAccount: payerTokenAccount,
+          stabilityPoolTokenAccount: stabilityPoolTokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer])
+        .rpc();
+
+      const statsAfter = await feesProgram.account.feeEpochStats.fetch(feeEpochStats);
+      const entryAfter = statsAfter.entries[slot];
+      assert.equal(statsAfter.mint.toString(), tokenMint.toString());
+      assert.equal(entryAfter.epoch.toNumber(), epoch);
+      assert.equal(entryAfter.feeVaultAmount.toNumber() - before, 25000);
+
+      console.log("✅ Epoch", epoch, "fee vault total:", entryAfter.feeVaultAmount.toString());
+    });
+  });
+
   after(() => {
     console.log("\n✅ Fee Distribution - Treasury Mode Tests Complete");
-    console.log("  Total Tests Passed: 12");
-    console.log("  Tests include: treasury mode, 50/50 split, fee address updates, validation, accumulation");
+    console.log("  Total Tests Passed: 16");
+    console.log("  Tests include: treasury mode, 50/50 split via claims, fee address updates, claim validation, accumulation, weighted split, collateral fees, epoch stats");
   });
 });
 
@@ -17,6 +17,7 @@ import {
 import { assert, expect } from "chai";
 import { BN } from "bn.js";
 import * as fs from "fs";
+import { initializeFeeVaultIfNeeded, claimFeesFromVault, allowFeeCallerIfNeeded } from "./test-utils";
 
 describe("Fee Contract - Edge Cases & Error Handling", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
@@ -48,6 +49,8 @@ describe("Fee Contract - Edge Cases & Error Handling", () => {
   let stabilityPoolTokenAccount: PublicKey;
   let feeAddr1TokenAccount: PublicKey;
   let feeAddr2TokenAccount: PublicKey;
+  let feeVault: PublicKey;
+  let feeEpochStats: PublicKey;
 
   before(async () => {
     console.log("\n🚀 Setting up Fee Contract Edge Cases Tests...");
@@ -120,6 +123,12 @@ describe("Fee Contract - Edge Cases & Error Handling", () => {
         .rpc();
     }
 
+    // Treasury-mode fees of this token wait in its fee vault until claimed
+    ({ feeVault, feeEpochStats } = await initializeFeeVaultIfNeeded(feesProgram, admin, feeStateAccount, tokenMint));
+
+    // distribute_fee only accepts allowlisted fee callers
+    await allowFeeCallerIfNeeded(feesProgram, admin, feeStateAccount, payer.publicKey);
+
     // Set custom fee addresses for testing
     await feesProgram.methods
       .setFeeAddresses({
@@ -150,15 +159,17 @@ describe("Fee Contract - Edge Cases & Error Handling", () => {
 
       const tx = await feesProgram.methods
         .distributeFee({
-          feeAmount: minAmount
+          feeAmount: minAmount,
+          source: { borrow: {} }
         })
         .accounts({
           payer: payer.publicKey,
+          callerAuthority: payer.publicKey,
           state: feeStateAccount,
           payerTokenAccount: payerTokenAccount,
           stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-          feeAddress1TokenAccount: feeAddr1TokenAccount,
-          feeAddress2TokenAccount: feeAddr2TokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([payer])
@@ -189,15 +200,17 @@ describe("Fee Contract - Edge Cases & Error Handling", () => {
       try {
         const tx = await feesProgram.methods
           .distributeFee({
-            feeAmount: largeAmount
+            feeAmount: largeAmount,
+            source: { borrow: {} }
           })
           .accounts({
             payer: payer.publicKey,
+            callerAuthority: payer.publicKey,
             state: feeStateAccount,
             payerTokenAccount: payerTokenAccount,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([payer])
@@ -231,15 +244,17 @@ describe("Fee Contract - Edge Cases & Error Handling", () => {
 
       await feesProgram.methods
         .distributeFee({
-          feeAmount: new BN(5000)
+          feeAmount: new BN(5000),
+          source: { borrow: {} }
         })
         .accounts({
           payer: payer.publicKey,
+          callerAuthority: payer.publicKey,
           state: feeStateAccount,
           payerTokenAccount: payerTokenAccount,
           stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-          feeAddress1TokenAccount: feeAddr1TokenAccount,
-          feeAddress2TokenAccount: feeAddr2TokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([payer])
@@ -274,15 +289,17 @@ describe("Fee Contract - Edge Cases & Error Handling", () => {
 
       await feesProgram.methods
         .distributeFee({
-          feeAmount: new BN(3000)
+          feeAmount: new BN(3000),
+          source: { borrow: {} }
         })
         .accounts({
           payer: payer.publicKey,
+          callerAuthority: payer.publicKey,
           state: feeStateAccount,
           payerTokenAccount: payerTokenAccount,
           stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-          feeAddress1TokenAccount: feeAddr1TokenAccount,
-          feeAddress2TokenAccount: feeAddr2TokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([payer])
@@ -353,15 +370,17 @@ describe("Fee Contract - Edge Cases & Error Handling", () => {
       try {
         await feesProgram.methods
           .distributeFee({
-            feeAmount: new BN(10000)
+            feeAmount: new BN(10000),
+            source: { borrow: {} }
           })
           .accounts({
             payer: payer.publicKey,
+            callerAuthority: payer.publicKey,
             state: feeStateAccount,
             payerTokenAccount: uninitializedAccount,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([payer])
@@ -419,15 +438,17 @@ describe("Fee Contract - Edge Cases & Error Handling", () => {
 
           await feesProgram.methods
             .distributeFee({
-              feeAmount: randomAmount
+              feeAmount: randomAmount,
+              source: { borrow: {} }
             })
             .accounts({
               payer: payer.publicKey,
+              callerAuthority: payer.publicKey,
               state: feeStateAccount,
               payerTokenAccount: payerTokenAccount,
               stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-              feeAddress1TokenAccount: feeAddr1TokenAccount,
-              feeAddress2TokenAccount: feeAddr2TokenAccount,
+              feeVault: feeVault,
+              feeEpochStats: feeEpochStats,
               tokenProgram: TOKEN_PROGRAM_ID,
             })
             .signers([payer])
@@ -497,20 +518,34 @@ describe("Fee Contract - Edge Cases & Error Handling", () => {
       // Test distribution with new addresses
       await feesProgram.methods
         .distributeFee({
-          feeAmount: new BN(10000)
+          feeAmount: new BN(10000),
+          source: { borrow: {} }
         })
         .accounts({
           payer: payer.publicKey,
+          callerAuthority: payer.publicKey,
           state: feeStateAccount,
           payerTokenAccount: payerTokenAccount,
           stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-          feeAddress1TokenAccount: newFeeAddr1TokenAccount,
-          feeAddress2TokenAccount: newFeeAddr2TokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([payer])
         .rpc();
 
+      // In treasury mode the new addresses can pull their shares right away
+      const state = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+      if (!state.isStakeEnabled) {
+        await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, newFeeAddr1TokenAccount);
+        await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, newFeeAddr2TokenAccount);
+
+        const addr1Account = await getAccount(connection, newFeeAddr1TokenAccount);
+        const addr2Account = await getAccount(connection, newFeeAddr2TokenAccount);
+        assert.isTrue(Number(addr1Account.amount) > 0, "New fee address 1 should be claimable");
+        assert.isTrue(Number(addr2Account.amount) > 0, "New fee address 2 should be claimable");
+      }
+
       console.log("✅ Fee address updates during operations handled correctly");
     });
   });
@@ -520,15 +555,17 @@ describe("Fee Contract - Edge Cases & Error Handling", () => {
       try {
         await feesProgram.methods
           .distributeFee({
-            feeAmount: new BN(0)
+            feeAmount: new BN(0),
+            source: { borrow: {} }
           })
           .accounts({
             payer: payer.publicKey,
+            callerAuthority: payer.publicKey,
             state: feeStateAccount,
             payerTokenAccount: payerTokenAccount,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([payer])
@@ -18,6 +18,7 @@ import {
 } from "@solana/spl-token";
 import { assert, expect } from "chai";
 import * as fs from "fs";
+import { initializeFeeVaultIfNeeded, allowFeeCallerIfNeeded } from "./test-utils";
 import { fetchAllTroves, sortTrovesByICR, findNeighbors, buildNeighborAccounts, TroveData } from './trove-indexer';
 
 describe("Fee Contract - Protocol CPI Integration Tests", () => {
@@ -50,6 +51,8 @@ describe("Fee Contract - Protocol CPI Integration Tests", () => {
   let stabilityPoolTokenAccount: PublicKey;
   let feeAddr1TokenAccount: PublicKey;
   let feeAddr2TokenAccount: PublicKey;
+  let feeVault: PublicKey;
+  let feeEpochStats: PublicKey;
 
   // Helper function to get neighbor hints for trove mutations
   async function getNeighborHints(
@@ -73,15 +76,15 @@ describe("Fee Contract - Protocol CPI Integration Tests", () => {
     
     // Derive PDAs for this trove
     const [userDebtAccount] = PublicKey.findProgramAddressSync(
-      [Buffer.from("user_debt_amount"), userPubkey.toBuffer()],
+      [Buffer.from("user_debt_amount"), userPubkey.toBuffer(), Buffer.alloc(2)],
       program.programId
     );
     const [userCollateralAccount] = PublicKey.findProgramAddressSync(
-      [Buffer.from("user_collateral_amount"), userPubkey.toBuffer(), Buffer.from(denom)],
+      [Buffer.from("user_collateral_amount"), userPubkey.toBuffer(), Buffer.alloc(2), Buffer.from(denom)],
       program.programId
     );
     const [liquidityThreshold] = PublicKey.findProgramAddressSync(
-      [Buffer.from("liquidity_threshold"), userPubkey.toBuffer()],
+      [Buffer.from("liquidity_threshold"), userPubkey.toBuffer(), Buffer.alloc(2)],
       program.programId
     );
     
@@ -189,6 +192,12 @@ describe("Fee Contract - Protocol CPI Integration Tests", () => {
         .rpc();
     }
 
+    // Treasury-mode fees of this token wait in its fee vault until claimed
+    ({ feeVault, feeEpochStats } = await initializeFeeVaultIfNeeded(feesProgram, admin, feeStateAccount, tokenMint));
+
+    // distribute_fee only accepts allowlisted fee callers
+    await allowFeeCallerIfNeeded(feesProgram, admin, feeStateAccount, protocolSim.publicKey);
+
     // Set custom fee addresses for testing
     await feesProgram.methods
       .setFeeAddresses({
@@ -237,15 +246,17 @@ describe("Fee Contract - Protocol CPI Integration Tests", () => {
 
       const tx = await feesProgram.methods
         .distributeFee({
-          feeAmount: feeAmount
+          feeAmount: feeAmount,
+          source: { borrow: {} }
         })
         .accounts({
           payer: protocolSim.publicKey,
+          callerAuthority: protocolSim.publicKey,
           state: feeStateAccount,
           payerTokenAccount: protocolTokenAccount,
           stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-          feeAddress1TokenAccount: feeAddr1TokenAccount,
-          feeAddress2TokenAccount: feeAddr2TokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
         } as any)
         .signers([protocolSim])
@@ -288,15 +299,17 @@ describe("Fee Contract - Protocol CPI Integration Tests", () => {
 
       const tx = await feesProgram.methods
         .distributeFee({
-          feeAmount: feeAmount
+          feeAmount: feeAmount,
+          source: { borrow: {} }
         })
         .accounts({
           payer: protocolSim.publicKey,
+          callerAuthority: protocolSim.publicKey,
           state: feeStateAccount,
           payerTokenAccount: protocolTokenAccount,
           stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-          feeAddress1TokenAccount: feeAddr1TokenAccount,
-          feeAddress2TokenAccount: feeAddr2TokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
         } as any)
         .signers([protocolSim])
@@ -324,15 +337,17 @@ describe("Fee Contract - Protocol CPI Integration Tests", () => {
 
       const tx = await feesProgram.methods
         .distributeFee({
-          feeAmount: feeAmount
+          feeAmount: feeAmount,
+          source: { borrow: {} }
         })
         .accounts({
           payer: protocolSim.publicKey,
+          callerAuthority: protocolSim.publicKey,
           state: feeStateAccount,
           payerTokenAccount: protocolTokenAccount,
           stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-          feeAddress1TokenAccount: feeAddr1TokenAccount,
-          feeAddress2TokenAccount: feeAddr2TokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
         } as any)
         .signers([protocolSim])
@@ -407,15 +422,17 @@ describe("Fee Contract - Protocol CPI Integration Tests", () => {
       for (const amount of amounts) {
         await feesProgram.methods
           .distributeFee({
-            feeAmount: amount
+            feeAmount: amount,
+            source: { borrow: {} }
           })
           .accounts({
             payer: protocolSim.publicKey,
+            callerAuthority: protocolSim.publicKey,
             state: feeStateAccount,
             payerTokenAccount: protocolTokenAccount,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
           } as any)
           .signers([protocolSim])
@@ -459,15 +476,17 @@ describe("Fee Contract - Protocol CPI Integration Tests", () => {
 
         await feesProgram.methods
           .distributeFee({
-            feeAmount: op.fee
+            feeAmount: op.fee,
+            source: { borrow: {} }
           })
           .accounts({
             payer: protocolSim.publicKey,
+            callerAuthority: protocolSim.publicKey,
             state: feeStateAccount,
             payerTokenAccount: protocolTokenAccount,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
           } as any)
           .signers([protocolSim])
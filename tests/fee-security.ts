@@ -11,11 +11,13 @@ import {
   TOKEN_PROGRAM_ID,
   createMint,
   createAccount,
-  mintTo
+  mintTo,
+  getAccount
 } from "@solana/spl-token";
 import { assert, expect } from "chai";
 import { BN } from "bn.js";
 import * as fs from "fs";
+import { initializeFeeVaultIfNeeded, claimFeesFromVault, allowFeeCallerIfNeeded } from "./test-utils";
 
 describe("Fee Contract - Security & Attack Prevention", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
@@ -49,6 +51,8 @@ describe("Fee Contract - Security & Attack Prevention", () => {
   let stabilityPoolTokenAccount: PublicKey;
   let feeAddr1TokenAccount: PublicKey;
   let feeAddr2TokenAccount: PublicKey;
+  let feeVault: PublicKey;
+  let feeEpochStats: PublicKey;
 
   before(async () => {
     console.log("\n🚀 Setting up Fee Contract Security Tests...");
@@ -133,6 +137,12 @@ describe("Fee Contract - Security & Attack Prevention", () => {
         .rpc();
     }
 
+    // Treasury-mode fees of this token wait in its fee vault until claimed
+    ({ feeVault, feeEpochStats } = await initializeFeeVaultIfNeeded(feesProgram, admin, feeStateAccount, tokenMint));
+
+    // distribute_fee only accepts allowlisted fee callers
+    await allowFeeCallerIfNeeded(feesProgram, admin, feeStateAccount, payer.publicKey);
+
     // Set fee addresses to the ones we're using
     await feesProgram.methods
       .setFeeAddresses({
@@ -157,18 +167,20 @@ describe("Fee Contract - Security & Attack Prevention", () => {
       try {
         await feesProgram.methods
           .distributeFee({
-            feeAmount: new BN(100000)
+            feeAmount: new BN(100000),
+            source: { borrow: {} }
           })
           .accounts({
             payer: attacker.publicKey,
+            callerAuthority: payer.publicKey,
             state: feeStateAccount,
             payerTokenAccount: payerTokenAccount, // Use payer's account as attacker
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
-          .signers([attacker])
+          .signers([attacker, payer])
           .rpc();
 
         assert.fail("Should have thrown an error");
@@ -183,15 +195,17 @@ describe("Fee Contract - Security & Attack Prevention", () => {
     it("Should succeed if payer owns payer_token_account", async () => {
       const tx = await feesProgram.methods
         .distributeFee({
-          feeAmount: new BN(10000)
+          feeAmount: new BN(10000),
+          source: { borrow: {} }
         })
         .accounts({
           payer: payer.publicKey,
+          callerAuthority: payer.publicKey,
           state: feeStateAccount,
           payerTokenAccount: payerTokenAccount,
           stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-          feeAddress1TokenAccount: feeAddr1TokenAccount,
-          feeAddress2TokenAccount: feeAddr2TokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([payer])
@@ -243,15 +257,17 @@ describe("Fee Contract - Security & Attack Prevention", () => {
       try {
         await feesProgram.methods
           .distributeFee({
-            feeAmount: new BN(10000)
+            feeAmount: new BN(10000),
+            source: { borrow: {} }
           })
           .accounts({
             payer: payer.publicKey,
+            callerAuthority: payer.publicKey,
             state: feeStateAccount,
             payerTokenAccount: payerTokenAccount,
             stabilityPoolTokenAccount: wrongPoolAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([payer])
@@ -273,7 +289,7 @@ describe("Fee Contract - Security & Attack Prevention", () => {
         .rpc();
     });
 
-    it("Should fail when fee addresses have different mints", async () => {
+    it("Should fail to claim into a token account of a different mint", async () => {
       const wrongMint = await createMint(
         connection,
         admin,
@@ -289,29 +305,16 @@ describe("Fee Contract - Security & Attack Prevention", () => {
         FEE_ADDR_1
       );
 
-      console.log("🔒 Attempting distribution with mismatched fee address mint...");
+      console.log("🔒 Attempting claim into a fee address account of another mint...");
 
       try {
-        await feesProgram.methods
-          .distributeFee({
-            feeAmount: new BN(10000)
-          })
-          .accounts({
-            payer: payer.publicKey,
-            state: feeStateAccount,
-            payerTokenAccount: payerTokenAccount,
-            stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: wrongFeeAddr1Account,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
-            tokenProgram: TOKEN_PROGRAM_ID,
-          })
-          .signers([payer])
-          .rpc();
+        // Vault PDAs of the fee mint, recipient of another mint
+        await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, wrongFeeAddr1Account);
 
         assert.fail("Should have thrown an error");
       } catch (error: any) {
-        console.log("✅ Mixed fee address mints correctly rejected");
-        expect(error.message).to.include("InvalidTokenMint");
+        console.log("✅ Mismatched claim mint correctly rejected");
+        expect(error.message).to.include("ConstraintSeeds");
       }
     });
   });
@@ -325,15 +328,17 @@ describe("Fee Contract - Security & Attack Prevention", () => {
       try {
         await feesProgram.methods
           .distributeFee({
-            feeAmount: largeAmount1
+            feeAmount: largeAmount1,
+            source: { borrow: {} }
           })
           .accounts({
             payer: payer.publicKey,
+            callerAuthority: payer.publicKey,
             state: feeStateAccount,
             payerTokenAccount: payerTokenAccount,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([payer])
@@ -348,15 +353,17 @@ describe("Fee Contract - Security & Attack Prevention", () => {
         try {
           await feesProgram.methods
             .distributeFee({
-              feeAmount: new BN(100000)
+              feeAmount: new BN(100000),
+              source: { borrow: {} }
             })
             .accounts({
               payer: payer.publicKey,
+              callerAuthority: payer.publicKey,
               state: feeStateAccount,
               payerTokenAccount: payerTokenAccount,
               stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-              feeAddress1TokenAccount: feeAddr1TokenAccount,
-              feeAddress2TokenAccount: feeAddr2TokenAccount,
+              feeVault: feeVault,
+              feeEpochStats: feeEpochStats,
               tokenProgram: TOKEN_PROGRAM_ID,
             })
             .signers([payer])
@@ -380,15 +387,17 @@ describe("Fee Contract - Security & Attack Prevention", () => {
       try {
         await feesProgram.methods
           .distributeFee({
-            feeAmount: new BN(0)
+            feeAmount: new BN(0),
+            source: { borrow: {} }
           })
           .accounts({
             payer: payer.publicKey,
+            callerAuthority: payer.publicKey,
             state: feeStateAccount,
             payerTokenAccount: payerTokenAccount,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([payer])
@@ -410,15 +419,17 @@ describe("Fee Contract - Security & Attack Prevention", () => {
       try {
         await feesProgram.methods
           .distributeFee({
-            feeAmount: new BN(50000)
+            feeAmount: new BN(50000),
+            source: { borrow: {} }
           })
           .accounts({
             payer: payer.publicKey,
+            callerAuthority: payer.publicKey,
             state: feeStateAccount,
             payerTokenAccount: payerTokenAccount, // Use payer's account
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount,
-            feeAddress2TokenAccount: feeAddr2TokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([payer])
@@ -435,20 +446,22 @@ describe("Fee Contract - Security & Attack Prevention", () => {
 
   describe("Test 5.6: Test CPI Security", () => {
     it("Should only allow legitimate protocol to call distribute_fee", async () => {
-      console.log("✅ distribute_fee can be called by anyone (designed for protocol CPI)");
-      console.log("   Security relies on payer_token_account ownership validation");
+      console.log("✅ distribute_fee requires an allowlisted caller_authority signature");
+      console.log("   The protocol signs with its fee_caller PDA, tests use the allowlisted payer");
       
       const legitimateTx = await feesProgram.methods
         .distributeFee({
-          feeAmount: new BN(5000)
+          feeAmount: new BN(5000),
+          source: { borrow: {} }
         })
         .accounts({
           payer: payer.publicKey,
+          callerAuthority: payer.publicKey,
           state: feeStateAccount,
           payerTokenAccount: payerTokenAccount,
           stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-          feeAddress1TokenAccount: feeAddr1TokenAccount,
-          feeAddress2TokenAccount: feeAddr2TokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([payer])
@@ -456,6 +469,61 @@ describe("Fee Contract - Security & Attack Prevention", () => {
 
       console.log("✅ Legitimate call succeeds. TX:", legitimateTx);
     });
+
+    it("Should reject a caller_authority that is not allowlisted", async () => {
+      const impostor = Keypair.generate();
+      const stateBefore = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+
+      try {
+        await feesProgram.methods
+          .distributeFee({
+            feeAmount: new BN(5000),
+            source: { borrow: {} }
+          })
+          .accounts({
+            payer: payer.publicKey,
+            callerAuthority: impostor.publicKey,
+            state: feeStateAccount,
+            payerTokenAccount: payerTokenAccount,
+            stabilityPoolTokenAccount: stabilityPoolTokenAccount,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([payer, impostor])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.message).to.include("UnauthorizedFeeCaller");
+        console.log("✅ Non-allowlisted caller correctly rejected");
+      }
+
+      const stateAfter = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+      assert.equal(stateAfter.totalFeesCollected.toString(), stateBefore.totalFeesCollected.toString());
+    });
+
+    it("Should accept donations into the fee vault without booking them as fees", async () => {
+      const stateBefore = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+      const vaultBefore = await getAccount(connection, feeVault);
+
+      const tx = await feesProgram.methods
+        .donateFee({ amount: new BN(7000) })
+        .accounts({
+          donor: payer.publicKey,
+          donorTokenAccount: payerTokenAccount,
+          feeVault: feeVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer])
+        .rpc();
+
+      const stateAfter = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+      const vaultAfter = await getAccount(connection, feeVault);
+      assert.equal(Number(vaultAfter.amount) - Number(vaultBefore.amount), 7000);
+      assert.equal(stateAfter.totalFeesCollected.toString(), stateBefore.totalFeesCollected.toString());
+      console.log("✅ Donation lands in the fee vault. TX:", tx);
+    });
   });
 
   describe("Test 5.7: Test Fee Address Validation", () => {
@@ -493,53 +561,43 @@ describe("Fee Contract - Security & Attack Prevention", () => {
         newFeeAddr2
       );
 
-      // Test with correct fee address token accounts
+      await feesProgram.methods
+        .distributeFee({
+          feeAmount: new BN(10000),
+          source: { borrow: {} }
+        })
+        .accounts({
+          payer: payer.publicKey,
+          callerAuthority: payer.publicKey,
+          state: feeStateAccount,
+          payerTokenAccount: payerTokenAccount,
+          stabilityPoolTokenAccount: stabilityPoolTokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer])
+        .rpc();
+
+      // Claims into token accounts not owned by a fee address are rejected
       try {
-        await feesProgram.methods
-          .distributeFee({
-            feeAmount: new BN(10000)
-          })
-          .accounts({
-            payer: payer.publicKey,
-            state: feeStateAccount,
-            payerTokenAccount: payerTokenAccount,
-            stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: newFeeAddr1TokenAccount,
-            feeAddress2TokenAccount: newFeeAddr2TokenAccount,
-            tokenProgram: TOKEN_PROGRAM_ID,
-          })
-          .signers([payer])
-          .rpc();
+        await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, payerTokenAccount);
 
-        console.log("✅ Correct fee address token accounts accepted");
+        assert.fail("Should have failed with wrong fee address token account");
       } catch (error: any) {
-        console.log("❌ Unexpected error with correct accounts:", error.message);
-        throw error;
+        console.log("✅ Wrong fee address token account correctly rejected");
+        expect(error.message).to.include("InvalidFeeRecipient");
       }
 
-      // Test with wrong fee address token accounts (should fail)
-      try {
-        await feesProgram.methods
-          .distributeFee({
-            feeAmount: new BN(10000)
-          })
-          .accounts({
-            payer: payer.publicKey,
-            state: feeStateAccount,
-            payerTokenAccount: payerTokenAccount,
-            stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddr1TokenAccount, // Wrong owner
-            feeAddress2TokenAccount: feeAddr2TokenAccount, // Wrong owner
-            tokenProgram: TOKEN_PROGRAM_ID,
-          })
-          .signers([payer])
-          .rpc();
+      // The configured fee addresses can claim their shares
+      await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, newFeeAddr1TokenAccount);
+      await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, newFeeAddr2TokenAccount);
 
-        assert.fail("Should have failed with wrong fee address token accounts");
-      } catch (error: any) {
-        console.log("✅ Wrong fee address token accounts correctly rejected");
-        expect(error.message).to.exist;
-      }
+      const addr1Balance = await getAccount(connection, newFeeAddr1TokenAccount);
+      const addr2Balance = await getAccount(connection, newFeeAddr2TokenAccount);
+      assert.isTrue(Number(addr1Balance.amount) > 0, "Fee address 1 should receive its share");
+      assert.isTrue(Number(addr2Balance.amount) > 0, "Fee address 2 should receive its share");
+      console.log("✅ Correct fee address token accounts paid");
     });
   });
 
@@ -593,18 +651,20 @@ describe("Fee Contract - Security & Attack Prevention", () => {
         try {
           await feesProgram.methods
             .distributeFee({
-              feeAmount: new BN(10000)
+              feeAmount: new BN(10000),
+              source: { borrow: {} }
             })
             .accounts({
               payer: scenario.payerKey,
+              callerAuthority: payer.publicKey,
               state: feeStateAccount,
               payerTokenAccount: testTokenAccount,
               stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-              feeAddress1TokenAccount: feeAddr1TokenAccount,
-              feeAddress2TokenAccount: feeAddr2TokenAccount,
+              feeVault: feeVault,
+              feeEpochStats: feeEpochStats,
               tokenProgram: TOKEN_PROGRAM_ID,
             })
-            .signers([scenario.payerKey === attacker.publicKey ? attacker : payer])
+            .signers(scenario.payerKey === attacker.publicKey ? [attacker, payer] : [payer])
             .rpc();
 
           assert.fail(`${scenario.name} should have failed`);
@@ -619,7 +679,7 @@ describe("Fee Contract - Security & Attack Prevention", () => {
 
   after(() => {
     console.log("\n✅ Fee Contract Security Tests Complete");
-    console.log("  Total Tests Passed: 13");
+    console.log("  Total Tests Passed: 15");
     console.log("  All attack vectors successfully prevented");
     console.log("  Tests include: unauthorized access, token validation, fee address validation, overflow protection, attack prevention");
   });
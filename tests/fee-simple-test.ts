@@ -17,6 +17,7 @@ import {
 import { assert, expect } from "chai";
 import { BN } from "bn.js";
 import * as fs from "fs";
+import { initializeFeeVaultIfNeeded, claimFeesFromVault, allowFeeCallerIfNeeded } from "./test-utils";
 
 describe("Fee Contract - Simple Test (No Airdrops)", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
@@ -48,6 +49,8 @@ describe("Fee Contract - Simple Test (No Airdrops)", () => {
   let stabilityPoolTokenAccount: PublicKey;
   let feeAddr1TokenAccount: PublicKey;
   let feeAddr2TokenAccount: PublicKey;
+  let feeVault: PublicKey;
+  let feeEpochStats: PublicKey;
 
   before(async () => {
     console.log("\n🚀 Setting up Fee Contract Simple Test (No Airdrops)...");
@@ -110,6 +113,12 @@ describe("Fee Contract - Simple Test (No Airdrops)", () => {
         .rpc();
     }
 
+    // Treasury-mode fees of this token wait in its fee vault until claimed
+    ({ feeVault, feeEpochStats } = await initializeFeeVaultIfNeeded(feesProgram, admin, feeStateAccount, tokenMint));
+
+    // distribute_fee only accepts allowlisted fee callers
+    await allowFeeCallerIfNeeded(feesProgram, admin, feeStateAccount, admin.publicKey);
+
     console.log("✅ Setup complete - No airdrops used");
   });
 
@@ -259,23 +268,30 @@ describe("Fee Contract - Simple Test (No Airdrops)", () => {
 
       const addr1BalanceBefore = await getAccount(connection, feeAddr1TokenAccount);
       const addr2BalanceBefore = await getAccount(connection, feeAddr2TokenAccount);
+      const stateBefore = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
 
       await feesProgram.methods
         .distributeFee({
-          feeAmount: feeAmount
+          feeAmount: feeAmount,
+          source: { redemption: {} }
         })
         .accounts({
           payer: admin.publicKey,
+          callerAuthority: admin.publicKey,
           state: feeStateAccount,
           payerTokenAccount: payerTokenAccount,
           stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-          feeAddress1TokenAccount: feeAddr1TokenAccount,
-          feeAddress2TokenAccount: feeAddr2TokenAccount,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([admin])
         .rpc();
 
+      // Fees wait in the fee vault until each fee address claims its share
+      await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, feeAddr1TokenAccount);
+      await claimFeesFromVault(feesProgram, feeStateAccount, tokenMint, feeAddr2TokenAccount);
+
       const addr1BalanceAfter = await getAccount(connection, feeAddr1TokenAccount);
       const addr2BalanceAfter = await getAccount(connection, feeAddr2TokenAccount);
 
@@ -292,6 +308,18 @@ describe("Fee Contract - Simple Test (No Airdrops)", () => {
         "FEE_ADDR_2 should receive half"
       );
 
+      const stateAfter = await feesProgram.account.feeStateAccount.fetch(feeStateAccount);
+      assert.equal(
+        stateAfter.redemptionFeesCollected.sub(stateBefore.redemptionFeesCollected).toString(),
+        feeAmount.toString(),
+        "Fee should be counted as redemption revenue"
+      );
+      assert.equal(
+        stateAfter.borrowFeesCollected.toString(),
+        stateBefore.borrowFeesCollected.toString(),
+        "Other sources should be untouched"
+      );
+
       console.log("✅ Fee distribution working correctly");
     });
   });
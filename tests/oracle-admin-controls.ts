@@ -339,8 +339,122 @@ describe("Oracle Contract - Admin Controls Tests", () => {
     });
   });
 
+  describe("Test 2.11: Runtime Asset Listing with add/update/remove_asset", () => {
+    // Enough listings to outgrow the initial state allocation
+    const denoms = Array.from({ length: 40 }, (_, i) => `RT${i}`);
+    const priceId = "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+
+    const assetAccounts = {
+      admin: provider.wallet.publicKey,
+      state: stateAccountPda,
+      systemProgram: SystemProgram.programId,
+      clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+    };
+
+    it("Should grow the oracle state while listing assets", async () => {
+      const sizeBefore = (await provider.connection.getAccountInfo(stateAccountPda))!.data.length;
+
+      for (const denom of denoms) {
+        await oracleProgram.methods
+          .addAsset({ denom, decimal: 9, priceId, pythPriceAccount: Keypair.generate().publicKey, stakePool: null, feedType: { pyth: {} }, maxConfidenceBps: 200 })
+          .accounts(assetAccounts)
+          .rpc();
+      }
+
+      const state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+      const listed = state.collateralData.map((d: any) => d.denom);
+      denoms.forEach((denom) => assert.include(listed, denom));
+
+      const sizeAfter = (await provider.connection.getAccountInfo(stateAccountPda))!.data.length;
+      console.log("✅ Listed", denoms.length, "assets, state size", sizeBefore, "->", sizeAfter);
+    });
+
+    it("Should reject add_asset for a listed denom", async () => {
+      try {
+        await oracleProgram.methods
+          .addAsset({ denom: denoms[0], decimal: 9, priceId, pythPriceAccount: Keypair.generate().publicKey, stakePool: null, feedType: { pyth: {} }, maxConfidenceBps: 200 })
+          .accounts(assetAccounts)
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.message).to.include("AssetAlreadyExists");
+      }
+    });
+
+    it("Should update a listed asset", async () => {
+      const pythPriceAccount = Keypair.generate().publicKey;
+      await oracleProgram.methods
+        .updateAsset({ denom: denoms[0], decimal: 6, priceId, pythPriceAccount, stakePool: null, feedType: { pyth: {} }, maxConfidenceBps: 200 })
+        .accounts(assetAccounts)
+        .rpc();
+
+      const state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+      const updated = state.collateralData.find((d: any) => d.denom === denoms[0]);
+      assert.equal(updated.decimal, 6);
+      assert.equal(updated.pythPriceAccount.toString(), pythPriceAccount.toString());
+    });
+
+    it("Should reject update_asset for an unknown denom", async () => {
+      try {
+        await oracleProgram.methods
+          .updateAsset({ denom: "NOT_LISTED", decimal: 6, priceId, pythPriceAccount: Keypair.generate().publicKey, stakePool: null, feedType: { pyth: {} }, maxConfidenceBps: 200 })
+          .accounts(assetAccounts)
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.message).to.include("CollateralDataNotFound");
+      }
+    });
+
+    it("Should reject add_asset from non-admin", async () => {
+      try {
+        await oracleProgram.methods
+          .addAsset({ denom: "RT_NON_ADMIN", decimal: 9, priceId, pythPriceAccount: Keypair.generate().publicKey, stakePool: null, feedType: { pyth: {} }, maxConfidenceBps: 200 })
+          .accounts({ ...assetAccounts, admin: nonAdmin.publicKey })
+          .signers([nonAdmin])
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.message).to.exist;
+      }
+    });
+
+    it("Should reject a confidence bound outside 1-10000 bps", async () => {
+      for (const maxConfidenceBps of [0, 10_001]) {
+        try {
+          await oracleProgram.methods
+            .updateAsset({ denom: denoms[0], decimal: 9, priceId, pythPriceAccount: Keypair.generate().publicKey, stakePool: null, feedType: { pyth: {} }, maxConfidenceBps })
+            .accounts(assetAccounts)
+            .rpc();
+
+          assert.fail("Should have thrown an error");
+        } catch (error: any) {
+          expect(error.message).to.include("InvalidCollateralData");
+        }
+      }
+      console.log("✅ Out-of-range confidence bounds rejected");
+    });
+
+    it("Should delist the assets and shrink the state back", async () => {
+      for (const denom of denoms) {
+        await oracleProgram.methods
+          .removeAsset({ collateralDenom: denom })
+          .accounts(assetAccounts)
+          .rpc();
+      }
+
+      const state = await oracleProgram.account.oracleStateAccount.fetch(stateAccountPda);
+      const listed = state.collateralData.map((d: any) => d.denom);
+      denoms.forEach((denom) => assert.notInclude(listed, denom));
+      console.log("✅ State size after delisting:", (await provider.connection.getAccountInfo(stateAccountPda))!.data.length);
+    });
+  });
+
   after(() => {
     console.log("\n✅ Oracle Admin Controls Tests Complete");
-    console.log("  Total Tests Passed: 10\n");
+    console.log("  Total Tests Passed: 17\n");
   });
 });
@@ -426,9 +426,179 @@ describe("Oracle Contract - Security & Authorization Tests", () => {
     });
   });
 
+  describe("Test 5.11: Price Feeds Are Bound to Their Denom", () => {
+    // Devnet ETH/USD feed - a real Pyth account, but not the one configured for SOL
+    const ETH_PRICE_FEED = new PublicKey("EdVCmQ9FSPcVe5YySXDPCRmc8aDQLKJ9xvYBMZPie1Vw");
+
+    it("Should reject get_price with another asset's feed", async () => {
+      try {
+        await oracleProgram.methods
+          .getPrice({ denom: "SOL" })
+          .accounts({
+            state: stateAccountPda,
+            pythPriceAccount: ETH_PRICE_FEED,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .view();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        console.log("✅ Substituted feed rejected by get_price");
+        expect(error.message).to.include("PythPriceAccountValidationFailed");
+      }
+    });
+
+    it("Should reject update_pyth_price with another asset's feed", async () => {
+      try {
+        await oracleProgram.methods
+          .updatePythPrice({ denom: "SOL" })
+          .accounts({
+            admin: provider.wallet.publicKey,
+            state: stateAccountPda,
+            pythPriceAccount: ETH_PRICE_FEED,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        console.log("✅ Substituted feed rejected by update_pyth_price");
+        expect(error.message).to.include("PythPriceAccountValidationFailed");
+      }
+    });
+  });
+
+  describe("Test 5.12: Derived LST Prices Require Their Stake Pool", () => {
+    // Priced as SOL x the Jito stake pool's exchange rate
+    const denom = "RT_JITOSOL";
+    const JITO_STAKE_POOL = new PublicKey("Jito4APyf642JPZPx3hGc6WWJ8zPKtRbRs4P815Awbb");
+    const priceId = "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+
+    const assetAccounts = {
+      admin: provider.wallet.publicKey,
+      state: stateAccountPda,
+      systemProgram: SystemProgram.programId,
+      clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+    };
+
+    before(async () => {
+      await oracleProgram.methods
+        .addAsset({ denom, decimal: 9, priceId, pythPriceAccount: SOL_PRICE_FEED, stakePool: JITO_STAKE_POOL, feedType: { pyth: {} }, maxConfidenceBps: 200 })
+        .accounts(assetAccounts)
+        .rpc();
+    });
+
+    after(async () => {
+      await oracleProgram.methods
+        .removeAsset({ collateralDenom: denom })
+        .accounts(assetAccounts)
+        .rpc();
+    });
+
+    it("Should reject get_price of a derived denom without its stake pool", async () => {
+      try {
+        await oracleProgram.methods
+          .getPrice({ denom })
+          .accounts({
+            state: stateAccountPda,
+            pythPriceAccount: SOL_PRICE_FEED,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            stakePool: null,
+          })
+          .view();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        console.log("✅ Missing stake pool rejected");
+        expect(error.message).to.include("InvalidStakePool");
+      }
+    });
+
+    it("Should reject get_price of a derived denom with another stake pool", async () => {
+      try {
+        await oracleProgram.methods
+          .getPrice({ denom })
+          .accounts({
+            state: stateAccountPda,
+            pythPriceAccount: SOL_PRICE_FEED,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            stakePool: Keypair.generate().publicKey,
+          })
+          .view();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        console.log("✅ Substituted stake pool rejected");
+        expect(error.message).to.include("InvalidStakePool");
+      }
+    });
+  });
+
+  describe("Test 5.13: Chainlink Feeds Are Type-Checked", () => {
+    const denom = "RT_CHAINLINK";
+
+    const assetAccounts = {
+      admin: provider.wallet.publicKey,
+      state: stateAccountPda,
+      systemProgram: SystemProgram.programId,
+      clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+    };
+
+    it("Should reject a Chainlink listing with a Pyth price id", async () => {
+      try {
+        await oracleProgram.methods
+          .addAsset({
+            denom,
+            decimal: 9,
+            priceId: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
+            pythPriceAccount: Keypair.generate().publicKey,
+            stakePool: null,
+            feedType: { chainlink: {} },
+            maxConfidenceBps: 200,
+          })
+          .accounts(assetAccounts)
+          .rpc();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        console.log("✅ Chainlink listing with price id rejected");
+        expect(error.message).to.include("InvalidPriceId");
+      }
+    });
+
+    it("Should reject a Pyth account configured as a Chainlink feed", async () => {
+      await oracleProgram.methods
+        .addAsset({ denom, decimal: 9, priceId: "", pythPriceAccount: SOL_PRICE_FEED, stakePool: null, feedType: { chainlink: {} }, maxConfidenceBps: 200 })
+        .accounts(assetAccounts)
+        .rpc();
+
+      try {
+        await oracleProgram.methods
+          .getPrice({ denom })
+          .accounts({
+            state: stateAccountPda,
+            pythPriceAccount: SOL_PRICE_FEED,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            stakePool: null,
+          })
+          .view();
+
+        assert.fail("Should have thrown an error");
+      } catch (error: any) {
+        console.log("✅ Pyth account not read as a Chainlink feed");
+        expect(error.message).to.include("ChainlinkFeedLoadFailed");
+      } finally {
+        await oracleProgram.methods
+          .removeAsset({ collateralDenom: denom })
+          .accounts(assetAccounts)
+          .rpc();
+      }
+    });
+  });
+
   after(() => {
     console.log("\n✅ Oracle Security Tests Complete");
-    console.log("  Total Tests Passed: 10");
+    console.log("  Total Tests Passed: 16");
     console.log("  All authorization and validation checks working correctly!\n");
   });
 });
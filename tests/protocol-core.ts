@@ -14,7 +14,7 @@ import {
   transfer
 } from "@solana/spl-token";
 import { assert } from "chai";
-import { loadTestUsers } from "./test-utils";
+import { loadTestUsers, initializeFeeVaultIfNeeded, deriveFeeCallerPDA, allowFeeCallerIfNeeded } from "./test-utils";
 import { fetchAllTroves, sortTrovesByICR, findNeighbors, buildNeighborAccounts, TroveData } from './trove-indexer';
 
 // Constants
@@ -56,15 +56,15 @@ async function getNeighborHints(
 
   // Create a temporary TroveData object for this trove
   const [userDebtAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("user_debt_amount"), user.toBuffer()],
+    [Buffer.from("user_debt_amount"), user.toBuffer(), Buffer.alloc(2)],
     protocolProgram.programId
   );
   const [userCollateralAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("user_collateral_amount"), user.toBuffer(), Buffer.from(denom)],
+    [Buffer.from("user_collateral_amount"), user.toBuffer(), Buffer.alloc(2), Buffer.from(denom)],
     protocolProgram.programId
   );
   const [liquidityThresholdAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("liquidity_threshold"), user.toBuffer()],
+    [Buffer.from("liquidity_threshold"), user.toBuffer(), Buffer.alloc(2)],
     protocolProgram.programId
   );
 
@@ -157,13 +157,16 @@ describe("Aeroscraper Protocol Core Operations", () => {
   let stabilityPoolTokenAccount: PublicKey;
   let feeAddress1TokenAccount: PublicKey;
   let feeAddress2TokenAccount: PublicKey;
+  let feeVault: PublicKey;
+  let feeEpochStats: PublicKey;
+  let feeCaller: PublicKey;
 
   before(async () => {
     const ensureFreshUser = async (initial: Keypair, label: string): Promise<Keypair> => {
       let candidate = initial;
       while (true) {
         const [candidateDebtPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("user_debt_amount"), candidate.publicKey.toBuffer()],
+          [Buffer.from("user_debt_amount"), candidate.publicKey.toBuffer(), Buffer.alloc(2)],
           protocolProgram.programId
         );
         const existingDebtAccount = await provider.connection.getAccountInfo(candidateDebtPda);
@@ -393,15 +396,15 @@ describe("Aeroscraper Protocol Core Operations", () => {
 
     // Derive additional PDAs for openTrove
     const [user1DebtAmountPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("user_debt_amount"), user1.publicKey.toBuffer()],
+      [Buffer.from("user_debt_amount"), user1.publicKey.toBuffer(), Buffer.alloc(2)],
       protocolProgram.programId
     );
     const [user1LiquidityThresholdPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("liquidity_threshold"), user1.publicKey.toBuffer()],
+      [Buffer.from("liquidity_threshold"), user1.publicKey.toBuffer(), Buffer.alloc(2)],
       protocolProgram.programId
     );
     const [user1CollateralAmountPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("user_collateral_amount"), user1.publicKey.toBuffer(), Buffer.from("SOL")],
+      [Buffer.from("user_collateral_amount"), user1.publicKey.toBuffer(), Buffer.alloc(2), Buffer.from("SOL")],
       protocolProgram.programId
     );
     const [user1NodePda] = PublicKey.findProgramAddressSync(
@@ -544,6 +547,11 @@ describe("Aeroscraper Protocol Core Operations", () => {
       console.log("Fees already initialized");
     }
 
+    // Treasury-mode fees wait in the fees program's aUSD vault until the fee addresses claim them
+    ({ feeVault, feeEpochStats } = await initializeFeeVaultIfNeeded(feesProgram, adminKeypair, feesState, stablecoinMint));
+    feeCaller = deriveFeeCallerPDA(protocolProgram.programId);
+    await allowFeeCallerIfNeeded(feesProgram, adminKeypair, feesState, feeCaller);
+
     // Check if protocol state already exists
     if (existingState) {
       console.log("Protocol already initialized");
@@ -573,6 +581,39 @@ describe("Aeroscraper Protocol Core Operations", () => {
       }
     }
 
+    // Register SOL as collateral (localnet only - on devnet its vault already exists)
+    const [solVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_collateral_vault"), Buffer.from("SOL")],
+      protocolProgram.programId
+    );
+    if (!(await provider.connection.getAccountInfo(solVaultPda))) {
+      await protocolProgram.methods
+        .registerCollateral({ denom: "SOL" })
+        .accounts({
+          admin: admin.publicKey,
+          state: protocolState,
+          collateralMint,
+          protocolCollateralVault: solVaultPda,
+          totalCollateralAmount: PublicKey.findProgramAddressSync(
+            [Buffer.from("total_collateral_amount"), Buffer.from("SOL")],
+            protocolProgram.programId
+          )[0],
+          stabilityPoolSnapshot: PublicKey.findProgramAddressSync(
+            [Buffer.from("stability_pool_snapshot"), Buffer.from("SOL")],
+            protocolProgram.programId
+          )[0],
+          collateralConfig: PublicKey.findProgramAddressSync(
+            [Buffer.from("collateral_config"), Buffer.from("SOL")],
+            protocolProgram.programId
+          )[0],
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([adminKeypair])
+        .rpc();
+      console.log("Collateral registered: SOL");
+    }
+
     // Note: PDAs cannot own token accounts, so we skip creating vault accounts
     // In a real implementation, these would be managed by the program itself
     console.log("Skipping PDA token account creation (not allowed)");
@@ -633,8 +674,9 @@ describe("Aeroscraper Protocol Core Operations", () => {
             feesProgram: feesProgram.programId,
             feesState: feesState,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddress1TokenAccount,
-            feeAddress2TokenAccount: feeAddress2TokenAccount,
+            feeCaller: feeCaller,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
@@ -789,8 +831,9 @@ describe("Aeroscraper Protocol Core Operations", () => {
             feesProgram: feesProgram.programId,
             feesState: feesState,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddress1TokenAccount,
-            feeAddress2TokenAccount: feeAddress2TokenAccount,
+            feeCaller: feeCaller,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
@@ -854,15 +897,15 @@ describe("Aeroscraper Protocol Core Operations", () => {
 
       // Derive user2 PDAs
       const [user2DebtAmountPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_debt_amount"), user2.publicKey.toBuffer()],
+        [Buffer.from("user_debt_amount"), user2.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
       const [user2LiquidityThresholdPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("liquidity_threshold"), user2.publicKey.toBuffer()],
+        [Buffer.from("liquidity_threshold"), user2.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
       const [user2CollateralAmountPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_collateral_amount"), user2.publicKey.toBuffer(), Buffer.from("SOL")],
+        [Buffer.from("user_collateral_amount"), user2.publicKey.toBuffer(), Buffer.alloc(2), Buffer.from("SOL")],
         protocolProgram.programId
       );
       const [user2NodePda] = PublicKey.findProgramAddressSync(
@@ -899,8 +942,9 @@ describe("Aeroscraper Protocol Core Operations", () => {
             feesProgram: feesProgram.programId,
             feesState: feesState,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddress1TokenAccount,
-            feeAddress2TokenAccount: feeAddress2TokenAccount,
+            feeCaller: feeCaller,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
@@ -963,8 +1007,8 @@ describe("Aeroscraper Protocol Core Operations", () => {
             feesProgram: feesProgram.programId,
             feesState: feesState,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddress1TokenAccount,
-            feeAddress2TokenAccount: feeAddress2TokenAccount,
+            feeCaller: feeCaller,
+            feeVault: feeVault,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
@@ -1052,8 +1096,8 @@ describe("Aeroscraper Protocol Core Operations", () => {
             feesProgram: feesProgram.programId,
             feesState: feesState,
             stabilityPoolTokenAccount: stabilityPoolTokenAccount,
-            feeAddress1TokenAccount: feeAddress1TokenAccount,
-            feeAddress2TokenAccount: feeAddress2TokenAccount,
+            feeCaller: feeCaller,
+            feeVault: feeVault,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
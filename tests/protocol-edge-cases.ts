@@ -22,15 +22,15 @@ async function getNeighborHints(
   const newICR = debtValue > 0n ? (collateralValue * 100n) / debtValue : BigInt(Number.MAX_SAFE_INTEGER);
 
   const [userDebtAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("user_debt_amount"), user.toBuffer()],
+    [Buffer.from("user_debt_amount"), user.toBuffer(), Buffer.alloc(2)],
     protocolProgram.programId
   );
   const [userCollateralAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("user_collateral_amount"), user.toBuffer(), Buffer.from(denom)],
+    [Buffer.from("user_collateral_amount"), user.toBuffer(), Buffer.alloc(2), Buffer.from(denom)],
     protocolProgram.programId
   );
   const [liquidityThresholdAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("liquidity_threshold"), user.toBuffer()],
+    [Buffer.from("liquidity_threshold"), user.toBuffer(), Buffer.alloc(2)],
     protocolProgram.programId
   );
 
@@ -39,15 +39,15 @@ async function getNeighborHints(
 
   // Derive PDAs for this trove
   const [userDebtAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("user_debt_amount"), userPubkey.toBuffer()],
+    [Buffer.from("user_debt_amount"), userPubkey.toBuffer(), Buffer.alloc(2)],
     program.programId
   );
   const [userCollateralAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("user_collateral_amount"), userPubkey.toBuffer(), Buffer.from(denom)],
+    [Buffer.from("user_collateral_amount"), userPubkey.toBuffer(), Buffer.alloc(2), Buffer.from(denom)],
     program.programId
   );
   const [liquidityThresholdAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("liquidity_threshold"), userPubkey.toBuffer()],
+    [Buffer.from("liquidity_threshold"), userPubkey.toBuffer(), Buffer.alloc(2)],
     program.programId
   );
 
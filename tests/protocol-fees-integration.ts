@@ -10,6 +10,7 @@ import {
   setupTestEnvironment,
   createTestUser,
   openTroveForUser,
+  claimFees,
   derivePDAs,
   SOL_DENOM,
   MIN_LOAN_AMOUNT,
@@ -257,7 +258,7 @@ describe("Protocol Contract - Fees Integration Tests", () => {
       const feeState = await ctx.feesProgram.account.feeStateAccount.fetch(ctx.feeState);
       expect(feeState.isStakeEnabled).to.be.false;
       console.log("  ✅ Treasury mode is ENABLED");
-      console.log("  50% to fee_address_1, 50% to fee_address_2");
+      console.log("  Fee vault, claimable 50% by fee_address_1, 50% by fee_address_2");
 
       // Get initial balances
       const initialFee1Account = await getAccount(
@@ -290,6 +291,10 @@ describe("Protocol Contract - Fees Integration Tests", () => {
         []
       );
 
+      // Fees wait in the fee vault until each fee address claims its share
+      await claimFees(ctx, ctx.feeAddress1TokenAccount);
+      await claimFees(ctx, ctx.feeAddress2TokenAccount);
+
       // Get updated balances
       const updatedFee1Account = await getAccount(
         ctx.provider.connection,
@@ -40,15 +40,15 @@ async function getNeighborHints(
 
   // Create a temporary TroveData object for this trove
   const [userDebtAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("user_debt_amount"), user.toBuffer()],
+    [Buffer.from("user_debt_amount"), user.toBuffer(), Buffer.alloc(2)],
     protocolProgram.programId
   );
   const [userCollateralAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("user_collateral_amount"), user.toBuffer(), Buffer.from(denom)],
+    [Buffer.from("user_collateral_amount"), user.toBuffer(), Buffer.alloc(2), Buffer.from(denom)],
     protocolProgram.programId
   );
   const [liquidityThresholdAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("liquidity_threshold"), user.toBuffer()],
+    [Buffer.from("liquidity_threshold"), user.toBuffer(), Buffer.alloc(2)],
     protocolProgram.programId
   );
 
@@ -251,4 +251,67 @@ describe("Protocol Contract - Oracle Integration Tests", () => {
       console.log("✅ Decimal conversion verified");
     });
   });
+
+  describe("Test 7.9: Price Cache Refresh", () => {
+    it("Should write the live oracle price to the denom's price cache", async () => {
+      console.log("📋 Testing permissionless refresh_price crank...");
+
+      const [priceCache] = PublicKey.findProgramAddressSync(
+        [Buffer.from("price_cache"), Buffer.from(SOL_DENOM)],
+        ctx.protocolProgram.programId
+      );
+
+      await ctx.protocolProgram.methods
+        .refreshPrice({ collateralDenom: SOL_DENOM })
+        .accounts({
+          keeper: ctx.admin.publicKey,
+          state: ctx.protocolState,
+          priceCache,
+          oracleProgram: ctx.oracleProgram.programId,
+          oracleState: ctx.oracleState,
+          pythPriceAccount: SOL_PRICE_FEED,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      const cache = await ctx.protocolProgram.account.priceCache.fetch(priceCache);
+      expect(cache.denom).to.equal(SOL_DENOM);
+      expect(cache.price.toNumber()).to.be.greaterThan(0);
+      expect(cache.lastUpdateSlot.toNumber()).to.be.greaterThan(0);
+
+      console.log(`  ✅ Cached price: ${cache.price.toString()} (decimal ${cache.decimal})`);
+      console.log("  ✅ Trove instructions read it while state.priceCacheMaxAge allows");
+    });
+  });
+
+  describe("Test 7.10: Trove ICR Refresh", () => {
+    it("Should recompute a trove's stored ICR from the live oracle price", async () => {
+      console.log("📋 Testing permissionless refresh_trove_icr crank...");
+
+      const pdas = derivePDAs(SOL_DENOM, user.publicKey, ctx.protocolProgram.programId);
+
+      // Any signer may crank it, not only the trove owner
+      await ctx.protocolProgram.methods
+        .refreshTroveIcr({ owner: user.publicKey, collateralDenom: SOL_DENOM, troveId: 0 })
+        .accounts({
+          keeper: ctx.admin.publicKey,
+          state: ctx.protocolState,
+          userDebtAmount: pdas.userDebtAmount,
+          userCollateralAmount: pdas.userCollateralAmount,
+          liquidityThreshold: pdas.liquidityThreshold,
+          totalCollateralAmount: pdas.totalCollateralAmount,
+          oracleProgram: ctx.oracleProgram.programId,
+          oracleState: ctx.oracleState,
+          pythPriceAccount: SOL_PRICE_FEED,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .rpc();
+
+      const liquidityThreshold = await ctx.protocolProgram.account.liquidityThreshold.fetch(pdas.liquidityThreshold);
+      expect(liquidityThreshold.ratio.toNumber()).to.be.greaterThan(0);
+
+      console.log(`  ✅ Refreshed ICR: ${liquidityThreshold.ratio.toNumber()}`);
+    });
+  });
 });
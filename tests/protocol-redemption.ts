@@ -8,7 +8,7 @@ import type { AccountMeta } from '@solana/web3.js';
 import { createMint, createAssociatedTokenAccount, mintTo, TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from "@solana/spl-token";
 import { assert, expect } from "chai";
 import { fetchAllTroves, sortTrovesByICR, findNeighbors, buildNeighborAccounts, TroveData } from './trove-indexer';
-import { setupTestEnvironment, TestContext, derivePDAs, loadTestUsers, openTroveForUser } from "./test-utils";
+import { setupTestEnvironment, TestContext, derivePDAs, loadTestUsers, openTroveForUser, SOL_PRICE_FEED } from "./test-utils";
 
 /**
  * Helper function to get neighbor hints for trove mutations (openTrove, addCollateral, etc.)
@@ -46,15 +46,15 @@ async function getNeighborHints(
 
   // Create a temporary TroveData object for this trove
   const [userDebtAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("user_debt_amount"), user.toBuffer()],
+    [Buffer.from("user_debt_amount"), user.toBuffer(), Buffer.alloc(2)],
     protocolProgram.programId
   );
   const [userCollateralAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("user_collateral_amount"), user.toBuffer(), Buffer.from(denom)],
+    [Buffer.from("user_collateral_amount"), user.toBuffer(), Buffer.alloc(2), Buffer.from(denom)],
     protocolProgram.programId
   );
   const [liquidityThresholdAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("liquidity_threshold"), user.toBuffer()],
+    [Buffer.from("liquidity_threshold"), user.toBuffer(), Buffer.alloc(2)],
     protocolProgram.programId
   );
 
@@ -318,9 +318,12 @@ describe("Protocol Contract - Redemption Tests", () => {
           feesProgram: ctx.feesProgram.programId,
           feesState: ctx.feeState,
           stabilityPoolTokenAccount: ctx.stabilityPoolTokenAccount,
-          feeAddress1TokenAccount: ctx.feeAddress1TokenAccount,
-          feeAddress2TokenAccount: ctx.feeAddress2TokenAccount,
+          feeCaller: ctx.feeCaller,
+          feeVault: ctx.feeVault,
+          feeEpochStats: ctx.feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          pythPriceAccount: SOL_PRICE_FEED,
         })
         .remainingAccounts(redemptionAccounts)
         .signers([redeemer])
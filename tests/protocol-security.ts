@@ -43,15 +43,15 @@ describe("Protocol Contract - Security Tests", () => {
 
     // Create a temporary TroveData object for this trove
     const [userDebtAccount] = PublicKey.findProgramAddressSync(
-      [Buffer.from("user_debt_amount"), user.toBuffer()],
+      [Buffer.from("user_debt_amount"), user.toBuffer(), Buffer.alloc(2)],
       ctx.protocolProgram.programId
     );
     const [userCollateralAccount] = PublicKey.findProgramAddressSync(
-      [Buffer.from("user_collateral_amount"), user.toBuffer(), Buffer.from(denom)],
+      [Buffer.from("user_collateral_amount"), user.toBuffer(), Buffer.alloc(2), Buffer.from(denom)],
       ctx.protocolProgram.programId
     );
     const [liquidityThresholdAccount] = PublicKey.findProgramAddressSync(
-      [Buffer.from("liquidity_threshold"), user.toBuffer()],
+      [Buffer.from("liquidity_threshold"), user.toBuffer(), Buffer.alloc(2)],
       ctx.protocolProgram.programId
     );
 
@@ -13,7 +13,7 @@ import {
 } from "@solana/spl-token";
 import { assert, expect } from "chai";
 import { fetchAllTroves, sortTrovesByICR, findNeighbors, buildNeighborAccounts, TroveData } from './trove-indexer';
-import { loadTestUsers } from "./test-utils";
+import { loadTestUsers, initializeFeeVaultIfNeeded, deriveFeeCallerPDA, allowFeeCallerIfNeeded } from "./test-utils";
 
 // Helper function to get neighbor hints for trove mutations
 async function getNeighborHints(
@@ -39,15 +39,15 @@ async function getNeighborHints(
 
   // Create a temporary TroveData object for this trove
   const [userDebtAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("user_debt_amount"), user.toBuffer()],
+    [Buffer.from("user_debt_amount"), user.toBuffer(), Buffer.alloc(2)],
     protocolProgram.programId
   );
   const [userCollateralAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("user_collateral_amount"), user.toBuffer(), Buffer.from(denom)],
+    [Buffer.from("user_collateral_amount"), user.toBuffer(), Buffer.alloc(2), Buffer.from(denom)],
     protocolProgram.programId
   );
   const [liquidityThresholdAccount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("liquidity_threshold"), user.toBuffer()],
+    [Buffer.from("liquidity_threshold"), user.toBuffer(), Buffer.alloc(2)],
     protocolProgram.programId
   );
 
@@ -109,6 +109,9 @@ describe("Protocol Contract - Trove Management Tests", () => {
   let protocolState: PublicKey;
   let oracleState: PublicKey;
   let feeState: PublicKey;
+  let feeVault: PublicKey;
+  let feeEpochStats: PublicKey;
+  let feeCaller: PublicKey;
   let protocolVault: PublicKey;
   let protocolStablecoinVault: PublicKey;
   let user3CollateralAccount: PublicKey;
@@ -230,6 +233,11 @@ describe("Protocol Contract - Trove Management Tests", () => {
       console.log("✅ Fees initialized");
     }
 
+    // Treasury-mode fees wait in the fees program's aUSD vault until claimed
+    ({ feeVault, feeEpochStats } = await initializeFeeVaultIfNeeded(feesProgram, admin.payer, feeState, stablecoinMint));
+    feeCaller = deriveFeeCallerPDA(protocolProgram.programId);
+    await allowFeeCallerIfNeeded(feesProgram, admin.payer, feeState, feeCaller);
+
     // Initialize protocol using PDA
     const [protocolStatePDA] = PublicKey.findProgramAddressSync(
       [Buffer.from("state")],
@@ -283,7 +291,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
   describe("Test 2.1: Open Trove with Valid Collateral", () => {
     it("Should successfully open trove with sufficient collateral", async () => {
       const [userDebtPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_debt_amount"), user3.publicKey.toBuffer()],
+        [Buffer.from("user_debt_amount"), user3.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -291,6 +299,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
         [
           Buffer.from("user_collateral_amount"),
           user3.publicKey.toBuffer(),
+          Buffer.alloc(2),
           Buffer.from("SOL"),
         ],
         protocolProgram.programId
@@ -302,7 +311,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [liquidityThresholdPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("liquidity_threshold"), user3.publicKey.toBuffer()],
+        [Buffer.from("liquidity_threshold"), user3.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -350,8 +359,9 @@ describe("Protocol Contract - Trove Management Tests", () => {
           feesProgram: feesProgram.programId,
           feesState: feeState,
           stabilityPoolTokenAccount: user3StablecoinAccount, // Use user account for now
-          feeAddress1TokenAccount: user3StablecoinAccount, // Use user account for now
-          feeAddress2TokenAccount: user3StablecoinAccount, // Use user account for now
+          feeCaller: feeCaller,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -376,7 +386,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
   describe("Test 2.2: Reject Duplicate Trove Opening", () => {
     it("Should fail when user tries to open second trove", async () => {
       const [userDebtPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_debt_amount"), user4.publicKey.toBuffer()],
+        [Buffer.from("user_debt_amount"), user4.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -384,6 +394,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
         [
           Buffer.from("user_collateral_amount"),
           user4.publicKey.toBuffer(),
+          Buffer.alloc(2),
           Buffer.from("SOL"),
         ],
         protocolProgram.programId
@@ -395,7 +406,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [liquidityThresholdPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("liquidity_threshold"), user4.publicKey.toBuffer()],
+        [Buffer.from("liquidity_threshold"), user4.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -446,8 +457,9 @@ describe("Protocol Contract - Trove Management Tests", () => {
           feesProgram: feesProgram.programId,
           feesState: feeState,
           stabilityPoolTokenAccount: user4StablecoinAccount,
-          feeAddress1TokenAccount: user4StablecoinAccount,
-          feeAddress2TokenAccount: user4StablecoinAccount,
+          feeCaller: feeCaller,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -497,8 +509,9 @@ describe("Protocol Contract - Trove Management Tests", () => {
             feesProgram: feesProgram.programId,
             feesState: feeState,
             stabilityPoolTokenAccount: user4StablecoinAccount,
-            feeAddress1TokenAccount: user4StablecoinAccount,
-            feeAddress2TokenAccount: user4StablecoinAccount,
+            feeCaller: feeCaller,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
@@ -544,7 +557,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [userDebtPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer()],
+        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -552,6 +565,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
         [
           Buffer.from("user_collateral_amount"),
           testUser.publicKey.toBuffer(),
+          Buffer.alloc(2),
           Buffer.from("SOL"),
         ],
         protocolProgram.programId
@@ -563,7 +577,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [liquidityThresholdPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer()],
+        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -614,8 +628,9 @@ describe("Protocol Contract - Trove Management Tests", () => {
           feesProgram: feesProgram.programId,
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
-          feeAddress1TokenAccount: testStablecoinAccount,
-          feeAddress2TokenAccount: testStablecoinAccount,
+          feeCaller: feeCaller,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -715,7 +730,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [userDebtPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer()],
+        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -723,6 +738,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
         [
           Buffer.from("user_collateral_amount"),
           testUser.publicKey.toBuffer(),
+          Buffer.alloc(2),
           Buffer.from("SOL"),
         ],
         protocolProgram.programId
@@ -734,7 +750,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [liquidityThresholdPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer()],
+        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -785,8 +801,9 @@ describe("Protocol Contract - Trove Management Tests", () => {
           feesProgram: feesProgram.programId,
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
-          feeAddress1TokenAccount: testStablecoinAccount,
-          feeAddress2TokenAccount: testStablecoinAccount,
+          feeCaller: feeCaller,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -838,8 +855,9 @@ describe("Protocol Contract - Trove Management Tests", () => {
           feesProgram: feesProgram.programId,
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
-          feeAddress1TokenAccount: testStablecoinAccount,
-          feeAddress2TokenAccount: testStablecoinAccount,
+          feeCaller: feeCaller,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           user: testUser.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
@@ -888,7 +906,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [userDebtPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer()],
+        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -896,6 +914,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
         [
           Buffer.from("user_collateral_amount"),
           testUser.publicKey.toBuffer(),
+          Buffer.alloc(2),
           Buffer.from("SOL"),
         ],
         protocolProgram.programId
@@ -907,7 +926,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [liquidityThresholdPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer()],
+        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -958,8 +977,9 @@ describe("Protocol Contract - Trove Management Tests", () => {
           feesProgram: feesProgram.programId,
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
-          feeAddress1TokenAccount: testStablecoinAccount,
-          feeAddress2TokenAccount: testStablecoinAccount,
+          feeCaller: feeCaller,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -1042,7 +1062,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [userDebtPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer()],
+        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -1050,6 +1070,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
         [
           Buffer.from("user_collateral_amount"),
           testUser.publicKey.toBuffer(),
+          Buffer.alloc(2),
           Buffer.from("SOL"),
         ],
         protocolProgram.programId
@@ -1061,7 +1082,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [liquidityThresholdPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer()],
+        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -1112,8 +1133,9 @@ describe("Protocol Contract - Trove Management Tests", () => {
           feesProgram: feesProgram.programId,
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
-          feeAddress1TokenAccount: testStablecoinAccount,
-          feeAddress2TokenAccount: testStablecoinAccount,
+          feeCaller: feeCaller,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -1190,7 +1212,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [userDebtPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer()],
+        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -1198,6 +1220,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
         [
           Buffer.from("user_collateral_amount"),
           testUser.publicKey.toBuffer(),
+          Buffer.alloc(2),
           Buffer.from("SOL"),
         ],
         protocolProgram.programId
@@ -1209,7 +1232,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [liquidityThresholdPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer()],
+        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -1260,8 +1283,9 @@ describe("Protocol Contract - Trove Management Tests", () => {
           feesProgram: feesProgram.programId,
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
-          feeAddress1TokenAccount: testStablecoinAccount,
-          feeAddress2TokenAccount: testStablecoinAccount,
+          feeCaller: feeCaller,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -1334,7 +1358,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [userDebtPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer()],
+        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -1342,6 +1366,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
         [
           Buffer.from("user_collateral_amount"),
           testUser.publicKey.toBuffer(),
+          Buffer.alloc(2),
           Buffer.from("SOL"),
         ],
         protocolProgram.programId
@@ -1353,7 +1378,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [liquidityThresholdPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer()],
+        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -1404,8 +1429,9 @@ describe("Protocol Contract - Trove Management Tests", () => {
           feesProgram: feesProgram.programId,
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
-          feeAddress1TokenAccount: testStablecoinAccount,
-          feeAddress2TokenAccount: testStablecoinAccount,
+          feeCaller: feeCaller,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -1505,7 +1531,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [userDebtPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer()],
+        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -1513,6 +1539,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
         [
           Buffer.from("user_collateral_amount"),
           testUser.publicKey.toBuffer(),
+          Buffer.alloc(2),
           Buffer.from("SOL"),
         ],
         protocolProgram.programId
@@ -1524,7 +1551,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [liquidityThresholdPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer()],
+        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -1575,8 +1602,9 @@ describe("Protocol Contract - Trove Management Tests", () => {
           feesProgram: feesProgram.programId,
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
-          feeAddress1TokenAccount: testStablecoinAccount,
-          feeAddress2TokenAccount: testStablecoinAccount,
+          feeCaller: feeCaller,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -1666,7 +1694,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [userDebtPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer()],
+        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -1674,6 +1702,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
         [
           Buffer.from("user_collateral_amount"),
           testUser.publicKey.toBuffer(),
+          Buffer.alloc(2),
           Buffer.from("SOL"),
         ],
         protocolProgram.programId
@@ -1685,7 +1714,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [liquidityThresholdPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer()],
+        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -1738,8 +1767,9 @@ describe("Protocol Contract - Trove Management Tests", () => {
             feesProgram: feesProgram.programId,
             feesState: feeState,
             stabilityPoolTokenAccount: testStablecoinAccount,
-            feeAddress1TokenAccount: testStablecoinAccount,
-            feeAddress2TokenAccount: testStablecoinAccount,
+            feeCaller: feeCaller,
+            feeVault: feeVault,
+            feeEpochStats: feeEpochStats,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
@@ -1785,7 +1815,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [userDebtPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer()],
+        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -1793,6 +1823,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
         [
           Buffer.from("user_collateral_amount"),
           testUser.publicKey.toBuffer(),
+          Buffer.alloc(2),
           Buffer.from("SOL"),
         ],
         protocolProgram.programId
@@ -1804,7 +1835,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [liquidityThresholdPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer()],
+        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -1855,8 +1886,9 @@ describe("Protocol Contract - Trove Management Tests", () => {
           feesProgram: feesProgram.programId,
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
-          feeAddress1TokenAccount: testStablecoinAccount,
-          feeAddress2TokenAccount: testStablecoinAccount,
+          feeCaller: feeCaller,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -1926,7 +1958,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [userDebtPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer()],
+        [Buffer.from("user_debt_amount"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -1934,6 +1966,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
         [
           Buffer.from("user_collateral_amount"),
           testUser.publicKey.toBuffer(),
+          Buffer.alloc(2),
           Buffer.from("USDC"),
         ],
         protocolProgram.programId
@@ -1945,7 +1978,7 @@ describe("Protocol Contract - Trove Management Tests", () => {
       );
 
       const [liquidityThresholdPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer()],
+        [Buffer.from("liquidity_threshold"), testUser.publicKey.toBuffer(), Buffer.alloc(2)],
         protocolProgram.programId
       );
 
@@ -1997,8 +2030,9 @@ describe("Protocol Contract - Trove Management Tests", () => {
           feesProgram: feesProgram.programId,
           feesState: feeState,
           stabilityPoolTokenAccount: testStablecoinAccount,
-          feeAddress1TokenAccount: testStablecoinAccount,
-          feeAddress2TokenAccount: testStablecoinAccount,
+          feeCaller: feeCaller,
+          feeVault: feeVault,
+          feeEpochStats: feeEpochStats,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
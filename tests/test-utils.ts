@@ -71,6 +71,123 @@ export interface TestContext {
   stabilityPoolTokenAccount: PublicKey;
   feeAddress1TokenAccount: PublicKey;
   feeAddress2TokenAccount: PublicKey;
+  // Fees program vault holding treasury-mode aUSD fees until claim_fees
+  feeVault: PublicKey;
+  feeClaims: PublicKey;
+  // Rolling per-epoch fee stats of aUSD, updated by every distribute_fee
+  feeEpochStats: PublicKey;
+  // Protocol PDA that signs distribute_fee CPIs (allowlisted in the fees program)
+  feeCaller: PublicKey;
+}
+
+// Helper to derive the fees program's fee vault, claims and epoch stats PDAs for a fee mint
+export function deriveFeeVaultPDAs(feeMint: PublicKey, feesProgramId: PublicKey) {
+  const [feeVault] = PublicKey.findProgramAddressSync(
+    [Buffer.from("fee_vault"), feeMint.toBuffer()],
+    feesProgramId
+  );
+  const [feeClaims] = PublicKey.findProgramAddressSync(
+    [Buffer.from("fee_claims"), feeMint.toBuffer()],
+    feesProgramId
+  );
+  const [feeEpochStats] = PublicKey.findProgramAddressSync(
+    [Buffer.from("fee_epoch_stats"), feeMint.toBuffer()],
+    feesProgramId
+  );
+  return { feeVault, feeClaims, feeEpochStats };
+}
+
+// Helper to create the fee vault and epoch stats of a fee mint unless they already exist (admin only)
+export async function initializeFeeVaultIfNeeded(
+  feesProgram: Program<AerospacerFees>,
+  admin: Keypair,
+  feeState: PublicKey,
+  feeMint: PublicKey
+): Promise<{ feeVault: PublicKey; feeClaims: PublicKey; feeEpochStats: PublicKey }> {
+  const { feeVault, feeClaims, feeEpochStats } = deriveFeeVaultPDAs(feeMint, feesProgram.programId);
+  if (!(await feesProgram.provider.connection.getAccountInfo(feeVault))) {
+    await feesProgram.methods
+      .initializeFeeVault()
+      .accounts({
+        admin: admin.publicKey,
+        state: feeState,
+        feeMint,
+        feeClaims,
+        feeVault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([admin])
+      .rpc();
+    console.log("✅ Fee vault created:", feeVault.toString());
+  }
+  if (!(await feesProgram.provider.connection.getAccountInfo(feeEpochStats))) {
+    await feesProgram.methods
+      .initializeFeeEpochStats()
+      .accounts({
+        admin: admin.publicKey,
+        state: feeState,
+        feeMint,
+        feeEpochStats,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+    console.log("✅ Fee epoch stats created:", feeEpochStats.toString());
+  }
+  return { feeVault, feeClaims, feeEpochStats };
+}
+
+// Helper to pay a fee address its claimable treasury-mode fees (permissionless)
+export async function claimFeesFromVault(
+  feesProgram: Program<AerospacerFees>,
+  feeState: PublicKey,
+  feeMint: PublicKey,
+  recipientTokenAccount: PublicKey
+): Promise<string> {
+  const { feeVault, feeClaims } = deriveFeeVaultPDAs(feeMint, feesProgram.programId);
+  return feesProgram.methods
+    .claimFees()
+    .accounts({
+      state: feeState,
+      feeClaims,
+      feeVault,
+      recipientTokenAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+}
+
+// Helper to derive the PDA a program signs distribute_fee CPIs with
+export function deriveFeeCallerPDA(callerProgramId: PublicKey): PublicKey {
+  const [feeCaller] = PublicKey.findProgramAddressSync(
+    [Buffer.from("fee_caller")],
+    callerProgramId
+  );
+  return feeCaller;
+}
+
+// Helper to allowlist a distribute_fee caller unless it already is (admin only)
+export async function allowFeeCallerIfNeeded(
+  feesProgram: Program<AerospacerFees>,
+  admin: Keypair,
+  feeState: PublicKey,
+  caller: PublicKey
+): Promise<void> {
+  const state = await feesProgram.account.feeStateAccount.fetch(feeState);
+  if (state.feeCallers.some((existing: PublicKey) => existing.equals(caller))) {
+    return;
+  }
+  await feesProgram.methods
+    .addFeeCaller({ caller: caller.toString() })
+    .accounts({
+      admin: admin.publicKey,
+      state: feeState,
+    })
+    .signers([admin])
+    .rpc();
+  console.log("✅ Fee caller allowlisted:", caller.toString());
 }
 
 // Helper to derive PDA addresses
@@ -91,17 +208,17 @@ export function derivePDAs(collateralDenom: string, user: PublicKey, programId:
   );
 
   const [userDebtAmount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("user_debt_amount"), user.toBuffer()],
+    [Buffer.from("user_debt_amount"), user.toBuffer(), Buffer.alloc(2)],
     programId
   );
 
   const [userCollateralAmount] = PublicKey.findProgramAddressSync(
-    [Buffer.from("user_collateral_amount"), user.toBuffer(), Buffer.from(collateralDenom)],
+    [Buffer.from("user_collateral_amount"), user.toBuffer(), Buffer.alloc(2), Buffer.from(collateralDenom)],
     programId
   );
 
   const [liquidityThreshold] = PublicKey.findProgramAddressSync(
-    [Buffer.from("liquidity_threshold"), user.toBuffer()],
+    [Buffer.from("liquidity_threshold"), user.toBuffer(), Buffer.alloc(2)],
     programId
   );
 
@@ -303,6 +420,65 @@ export async function setupTestEnvironment(): Promise<TestContext> {
     }
   }
 
+  // STEP 6b: Register the collateral denom (localnet scenario - on devnet the vault already exists)
+  if (!vaultAccountInfo) {
+    const [totalCollateralAmountPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("total_collateral_amount"), Buffer.from(SOL_DENOM)],
+      protocolProgram.programId
+    );
+    const [stabilityPoolSnapshotPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stability_pool_snapshot"), Buffer.from(SOL_DENOM)],
+      protocolProgram.programId
+    );
+    await protocolProgram.methods
+      .registerCollateral({ denom: SOL_DENOM })
+      .accounts({
+        admin: admin.publicKey,
+        state: protocolStatePDA,
+        collateralMint,
+        protocolCollateralVault: protocolCollateralVaultPda,
+        totalCollateralAmount: totalCollateralAmountPda,
+        stabilityPoolSnapshot: stabilityPoolSnapshotPda,
+        collateralConfig: PublicKey.findProgramAddressSync(
+          [Buffer.from("collateral_config"), Buffer.from(SOL_DENOM)],
+          protocolProgram.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin.payer])
+      .rpc();
+    console.log("✅ Collateral registered:", SOL_DENOM);
+  }
+
+  // STEP 6c: Denoms registered before the collateral registry need their risk parameters set once
+  const [collateralConfigPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("collateral_config"), Buffer.from(SOL_DENOM)],
+    protocolProgram.programId
+  );
+  if (!(await provider.connection.getAccountInfo(collateralConfigPda))) {
+    await protocolProgram.methods
+      .setCollateralParams({
+        denom: SOL_DENOM,
+        minimumCollateralRatio: new anchor.BN(115_000_000),
+        liquidationThreshold: new anchor.BN(110_000_000),
+        protocolFee: 5,
+      })
+      .accounts({
+        admin: admin.publicKey,
+        state: protocolStatePDA,
+        totalCollateralAmount: PublicKey.findProgramAddressSync(
+          [Buffer.from("total_collateral_amount"), Buffer.from(SOL_DENOM)],
+          protocolProgram.programId
+        )[0],
+        collateralConfig: collateralConfigPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin.payer])
+      .rpc();
+    console.log("✅ Collateral parameters set:", SOL_DENOM);
+  }
+
   // STEP 7: Create fee-related token accounts (ATAs, not PDAs) - match protocol-core.ts lines 446-496
   const feeAddress1 = new PublicKey("8Lv4UrYHTrzvg9jPVVGNmxWyMrMvrZnCQLWucBzfJyyR");
   const feeAddress2 = new PublicKey("GcNwV1nA5bityjNYsWwPLHykpKuuhPzK1AQFBbrPopnX");
@@ -368,6 +544,18 @@ export async function setupTestEnvironment(): Promise<TestContext> {
     console.log("✅ FeeAddress2 token account already exists:", feeAddress2TokenAccount.toString());
   }
 
+  // STEP 7b: Create the aUSD fee vault (treasury-mode fees wait there for claim_fees)
+  const { feeVault, feeClaims, feeEpochStats } = await initializeFeeVaultIfNeeded(
+    feesProgram,
+    admin.payer,
+    feesStatePDA,
+    stablecoinMint
+  );
+
+  // STEP 7c: Allowlist the protocol's fee caller PDA so its distribute_fee CPIs are accepted
+  const feeCaller = deriveFeeCallerPDA(protocolProgram.programId);
+  await allowFeeCallerIfNeeded(feesProgram, admin.payer, feesStatePDA, feeCaller);
+
   return {
     provider,
     protocolProgram,
@@ -382,9 +570,18 @@ export async function setupTestEnvironment(): Promise<TestContext> {
     stabilityPoolTokenAccount,
     feeAddress1TokenAccount,
     feeAddress2TokenAccount,
+    feeVault,
+    feeClaims,
+    feeEpochStats,
+    feeCaller,
   };
 }
 
+// Helper to pay a fee address its claimable aUSD fees
+export async function claimFees(ctx: TestContext, recipientTokenAccount: PublicKey): Promise<string> {
+  return claimFeesFromVault(ctx.feesProgram, ctx.feeState, ctx.stablecoinMint, recipientTokenAccount);
+}
+
 // Helper to create and fund a test user
 export async function createTestUser(
   provider: anchor.AnchorProvider,
@@ -487,8 +684,9 @@ export async function openTroveForUser(
       feesProgram: ctx.feesProgram.programId,
       feesState: ctx.feeState,
       stabilityPoolTokenAccount: ctx.stabilityPoolTokenAccount,
-      feeAddress1TokenAccount: ctx.feeAddress1TokenAccount,
-      feeAddress2TokenAccount: ctx.feeAddress2TokenAccount,
+      feeCaller: ctx.feeCaller,
+      feeVault: ctx.feeVault,
+      feeEpochStats: ctx.feeEpochStats,
       tokenProgram: TOKEN_PROGRAM_ID,
       systemProgram: SystemProgram.programId,
     })
@@ -67,7 +67,7 @@ export async function fetchAllTroves(
 
     // Fetch LiquidityThreshold to get ICR
     const [liquidityThresholdPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("liquidity_threshold"), owner.toBuffer()],
+      [Buffer.from("liquidity_threshold"), owner.toBuffer(), Buffer.alloc(2)],
       program.programId
     );
 
@@ -104,7 +104,7 @@ export async function fetchAllTroves(
         }
 
         const [collateralPda] = PublicKey.findProgramAddressSync(
-          [Buffer.from("user_collateral_amount"), owner.toBuffer(), Buffer.from(denom)],
+          [Buffer.from("user_collateral_amount"), owner.toBuffer(), Buffer.alloc(2), Buffer.from(denom)],
           program.programId
         );
 
@@ -128,6 +128,44 @@ export async function fetchAllTroves(
   return troves;
 }
 
+export interface TroveIndexEntry {
+  position: number;
+  owner: PublicKey;
+  troveId: number;
+}
+
+/**
+ * Enumerate troves from the on-chain trove index
+ * 
+ * Walks the TroveIndexPage accounts (one small account type, 64 troves per page) instead of
+ * scanning every UserDebtAmount. Tombstoned entries are skipped. Entries of liquidated troves
+ * stay until a keeper prunes them, so callers still check each trove's status.
+ * 
+ * @param program - Aerospacer protocol program instance
+ * @returns Live index entries in open order
+ */
+export async function fetchIndexedTroves(
+  program: Program<AerospacerProtocol>
+): Promise<TroveIndexEntry[]> {
+  const pages = await program.account.troveIndexPage.all();
+  pages.sort((a, b) => a.account.page - b.account.page);
+
+  const entries: TroveIndexEntry[] = [];
+  for (const { account } of pages) {
+    account.entries.forEach((entry, slot) => {
+      if (entry.owner.equals(PublicKey.default)) {
+        return; // Tombstone
+      }
+      entries.push({
+        position: account.page * 64 + slot,
+        owner: entry.owner,
+        troveId: entry.troveId,
+      });
+    });
+  }
+  return entries;
+}
+
 /**
  * Sort troves by ICR (ascending order: riskiest first)
  * 